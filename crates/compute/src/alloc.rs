@@ -22,8 +22,27 @@ pub trait ComputeAllocator<F, Mem: ComputeMemory<F>> {
 	/// Returns the remaining number of elements that can be allocated.
 	fn capacity(&self) -> usize;
 
+	/// The allocator type of a scoped region, see [`Self::subscope_allocator`].
+	type Subscope<'s>: ComputeAllocator<F, Mem>
+	where
+		Self: 's;
+
 	/// Returns the remaining unallocated capacity as a new allocator with a limited scope.
-	fn subscope_allocator(&mut self) -> impl ComputeAllocator<F, Mem>;
+	///
+	/// The parent allocator is mutably borrowed for as long as the sub-allocator lives. When the
+	/// sub-allocator is dropped, all of its allocations are released and the memory is available
+	/// to the parent again.
+	fn subscope_allocator(&mut self) -> Self::Subscope<'_>;
+
+	/// Runs a closure with a scoped region of the remaining unallocated capacity.
+	///
+	/// All allocations made from the scope are released when the closure returns, so
+	/// phase-local buffers don't hold on to memory for the rest of the parent allocator's
+	/// lifetime. The borrow checker ensures that no scoped allocation outlives the closure.
+	fn alloc_scope<'s, R>(&'s mut self, f: impl FnOnce(&mut Self::Subscope<'s>) -> R) -> R {
+		let mut scope = self.subscope_allocator();
+		f(&mut scope)
+	}
 }
 
 /// Basic bump allocator that allocates slices from an underlying memory buffer provided at
@@ -99,7 +118,12 @@ where
 			.len()
 	}
 
-	fn subscope_allocator(&mut self) -> impl ComputeAllocator<F, Mem> {
+	type Subscope<'s>
+		= BumpAllocator<'s, F, Mem>
+	where
+		Self: 's;
+
+	fn subscope_allocator(&mut self) -> Self::Subscope<'_> {
 		BumpAllocator::<F, Mem>::new(self.remaining())
 	}
 }
@@ -107,6 +131,269 @@ where
 /// Alias for a bump allocator over CPU host memory.
 pub type HostBumpAllocator<'a, F> = BumpAllocator<'a, F, CpuMemory>;
 
+/// Allocator that recycles freed buffers from an underlying memory buffer provided at
+/// construction.
+///
+/// Unlike [`BumpAllocator`], buffers obtained with [`PoolAllocator::alloc_owned`] may be returned
+/// to the pool with [`PoolAllocator::free`] and are reused by later allocations. This lets
+/// buffers from one proving phase be recycled in the next instead of remaining reserved until
+/// the whole allocator is dropped.
+///
+/// Allocation uses a best-fit strategy over the freed blocks before drawing from the untouched
+/// remainder of the buffer. Since memory handles are opaque, adjacent freed blocks are not
+/// coalesced; [`PoolStats::fragmentation`] reports how scattered the free memory has become.
+///
+/// The pool can optionally be capped to a byte budget that is smaller than the underlying
+/// buffer, which is useful to simulate or share a device with less available memory.
+pub struct PoolAllocator<'a, F, Mem: ComputeMemory<F>> {
+	state: Mutex<PoolState<'a, F, Mem>>,
+	budget: usize,
+}
+
+struct PoolState<'a, F, Mem: ComputeMemory<F>> {
+	/// The part of the buffer that has never been allocated.
+	unallocated: Option<Mem::FSliceMut<'a>>,
+	/// Blocks that were allocated and then freed.
+	free_blocks: Vec<Mem::FSliceMut<'a>>,
+	capacity: usize,
+	in_use: usize,
+	high_water_mark: usize,
+	n_allocations: usize,
+	n_reused: usize,
+}
+
+/// Usage statistics of a [`PoolAllocator`].
+///
+/// All sizes are counted in field elements.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+	/// Total number of elements managed by the pool.
+	pub capacity: usize,
+	/// Maximum number of elements the pool may hand out at once, as set by the byte budget.
+	pub budget: usize,
+	/// Number of elements currently allocated.
+	pub in_use: usize,
+	/// Maximum number of elements that were allocated at the same time.
+	pub high_water_mark: usize,
+	/// Number of elements in freed blocks that are available for reuse.
+	pub free_listed: usize,
+	/// Length of the largest contiguous block that is available for allocation.
+	pub largest_free_block: usize,
+	/// Total number of successful allocations.
+	pub n_allocations: usize,
+	/// Number of allocations that were served from freed blocks.
+	pub n_reused: usize,
+}
+
+impl PoolStats {
+	/// Returns the external fragmentation of the available memory.
+	///
+	/// This is `1 - largest_free_block / available`, where `available` is the number of elements
+	/// not in use. The value is 0 when all available memory is contiguous and approaches 1 as it
+	/// becomes scattered across many small blocks.
+	pub fn fragmentation(&self) -> f64 {
+		let available = self.capacity - self.in_use;
+		if available == 0 {
+			0.0
+		} else {
+			1.0 - self.largest_free_block as f64 / available as f64
+		}
+	}
+}
+
+impl<'a, F, Mem> PoolAllocator<'a, F, Mem>
+where
+	F: 'static,
+	Mem: ComputeMemory<F>,
+{
+	pub fn new(buffer: Mem::FSliceMut<'a>) -> Self {
+		let capacity = buffer.len();
+		Self {
+			state: Mutex::new(PoolState {
+				unallocated: Some(buffer),
+				free_blocks: Vec::new(),
+				capacity,
+				in_use: 0,
+				high_water_mark: 0,
+				n_allocations: 0,
+				n_reused: 0,
+			}),
+			budget: capacity,
+		}
+	}
+
+	/// Creates a pool that hands out at most `budget_bytes` bytes of the buffer at a time.
+	///
+	/// The byte size of an allocation is its length times the size of `F`.
+	pub fn with_byte_budget(buffer: Mem::FSliceMut<'a>, budget_bytes: usize) -> Self {
+		let mut pool = Self::new(buffer);
+		pool.budget = pool.budget.min(budget_bytes / size_of::<F>().max(1));
+		pool
+	}
+
+	/// Allocates a buffer that can later be returned to the pool with [`Self::free`].
+	///
+	/// ## Pre-conditions
+	///
+	/// - `n` must be a multiple of `Mem::ALIGNMENT`, or a power of two smaller than
+	///   `Mem::ALIGNMENT`
+	pub fn alloc_owned(&self, n: usize) -> Result<Mem::FSliceMut<'a>, Error> {
+		let mut state = self.state.lock().expect("mutex is always available");
+		if state.in_use + n > self.budget {
+			return Err(Error::OutOfMemory);
+		}
+
+		let block_len = n.max(Mem::ALIGNMENT);
+		let best_fit = state
+			.free_blocks
+			.iter()
+			.enumerate()
+			.filter(|(_, block)| block.len() >= n && Self::can_split(block.len(), n))
+			.min_by_key(|(_, block)| block.len())
+			.map(|(index, _)| index);
+
+		let block = match best_fit {
+			Some(index) => {
+				state.n_reused += 1;
+				state.free_blocks.swap_remove(index)
+			}
+			None => {
+				let unallocated = state
+					.unallocated
+					.take()
+					.expect("unallocated is always Some by invariant");
+				if unallocated.len() < block_len {
+					state.unallocated = Some(unallocated);
+					return Err(Error::OutOfMemory);
+				}
+				let (block, rest) = Mem::split_at_mut(unallocated, block_len);
+				state.unallocated = Some(rest);
+				block
+			}
+		};
+
+		let (block, remainders) = Self::split_block(block, n);
+		state
+			.free_blocks
+			.extend(remainders.into_iter().filter(|block| !block.is_empty()));
+
+		state.in_use += n;
+		state.high_water_mark = state.high_water_mark.max(state.in_use);
+		state.n_allocations += 1;
+		Ok(block)
+	}
+
+	/// Returns a buffer to the pool so that it can be reused by subsequent allocations.
+	///
+	/// ## Pre-conditions
+	///
+	/// - `block` must have been returned by [`Self::alloc_owned`] on this pool
+	pub fn free(&self, block: Mem::FSliceMut<'a>) {
+		let mut state = self.state.lock().expect("mutex is always available");
+		state.in_use -= block.len();
+		if !block.is_empty() {
+			state.free_blocks.push(block);
+		}
+	}
+
+	/// Returns a snapshot of the pool usage statistics.
+	pub fn stats(&self) -> PoolStats {
+		let state = self.state.lock().expect("mutex is always available");
+		let unallocated_len = state
+			.unallocated
+			.as_ref()
+			.expect("unallocated is always Some by invariant")
+			.len();
+		let free_listed = state.free_blocks.iter().map(|block| block.len()).sum();
+		let largest_free_block = state
+			.free_blocks
+			.iter()
+			.map(|block| block.len())
+			.chain([unallocated_len])
+			.max()
+			.unwrap_or_default();
+		PoolStats {
+			capacity: state.capacity,
+			budget: self.budget,
+			in_use: state.in_use,
+			high_water_mark: state.high_water_mark,
+			free_listed,
+			largest_free_block,
+			n_allocations: state.n_allocations,
+			n_reused: state.n_reused,
+		}
+	}
+
+	/// Whether a free block of length `block_len` can be split into a block of length `n`.
+	fn can_split(block_len: usize, n: usize) -> bool {
+		if n < Mem::ALIGNMENT {
+			block_len % Mem::ALIGNMENT == 0 || block_len.is_power_of_two()
+		} else {
+			block_len % Mem::ALIGNMENT == 0
+		}
+	}
+
+	/// Splits a block of at least `n` elements into a block of exactly `n` elements and the
+	/// remainders.
+	fn split_block(
+		block: Mem::FSliceMut<'a>,
+		n: usize,
+	) -> (Mem::FSliceMut<'a>, Vec<Mem::FSliceMut<'a>>) {
+		let mut remainders = Vec::new();
+		let mut block = if block.len() > n.max(Mem::ALIGNMENT) {
+			let (lhs, rhs) = Mem::split_at_mut(block, n.max(Mem::ALIGNMENT));
+			remainders.push(rhs);
+			lhs
+		} else {
+			block
+		};
+		if n < Mem::ALIGNMENT {
+			assert!(n.is_power_of_two(), "n must be a power of two");
+			while block.len() > n {
+				let (lhs, rhs) = Mem::split_half_mut(block);
+				remainders.push(rhs);
+				block = lhs;
+			}
+		}
+		(block, remainders)
+	}
+}
+
+impl<'a, F, Mem: ComputeMemory<F>> ComputeAllocator<F, Mem> for PoolAllocator<'a, F, Mem>
+where
+	F: 'static,
+{
+	fn alloc(&self, n: usize) -> Result<Mem::FSliceMut<'_>, Error> {
+		self.alloc_owned(n).map(Mem::narrow_mut)
+	}
+
+	fn capacity(&self) -> usize {
+		let stats = self.stats();
+		stats
+			.largest_free_block
+			.min(stats.budget.saturating_sub(stats.in_use))
+	}
+
+	type Subscope<'s>
+		= PoolAllocator<'s, F, Mem>
+	where
+		Self: 's;
+
+	fn subscope_allocator(&mut self) -> Self::Subscope<'_> {
+		let budget = self.budget.saturating_sub(self.stats().in_use);
+		let state = self.state.get_mut().expect("mutex is always available");
+		let remaining = Mem::to_owned_mut(
+			state
+				.unallocated
+				.as_mut()
+				.expect("unallocated is always Some by invariant"),
+		);
+		let mut pool = PoolAllocator::<F, Mem>::new(remaining);
+		pool.budget = pool.budget.min(budget);
+		pool
+	}
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
 	#[error("allocator is out of memory")]
@@ -155,4 +442,94 @@ mod tests {
 
 		let _ = bump.alloc(100).unwrap();
 	}
+
+	#[test]
+	fn test_pool_reuses_freed_blocks() {
+		let mut data = (0..256u128).collect::<Vec<_>>();
+		let pool = PoolAllocator::<u128, CpuMemory>::new(&mut data);
+
+		let a = pool.alloc_owned(128).unwrap();
+		let b = pool.alloc_owned(64).unwrap();
+		assert_matches!(pool.alloc_owned(128), Err(Error::OutOfMemory));
+
+		pool.free(a);
+		let c = pool.alloc_owned(32).unwrap();
+		let d = pool.alloc_owned(96).unwrap();
+		assert_eq!(c.len(), 32);
+		assert_eq!(d.len(), 96);
+
+		let stats = pool.stats();
+		assert_eq!(stats.in_use, 192);
+		assert_eq!(stats.high_water_mark, 192);
+		assert_eq!(stats.n_allocations, 4);
+		assert_eq!(stats.n_reused, 2);
+
+		pool.free(b);
+		pool.free(c);
+		pool.free(d);
+		let stats = pool.stats();
+		assert_eq!(stats.in_use, 0);
+		assert_eq!(stats.free_listed, 192);
+		assert_eq!(stats.largest_free_block, 96);
+		assert!(stats.fragmentation() > 0.5);
+	}
+
+	#[test]
+	fn test_pool_byte_budget() {
+		let mut data = vec![0u128; 256];
+		let pool = PoolAllocator::<u128, CpuMemory>::with_byte_budget(&mut data, 100 * 16);
+		assert_eq!(pool.capacity(), 100);
+
+		let a = pool.alloc_owned(64).unwrap();
+		assert_matches!(pool.alloc_owned(64), Err(Error::OutOfMemory));
+		pool.free(a);
+		assert_eq!(pool.alloc(64).unwrap().len(), 64);
+	}
+
+	#[test]
+	fn test_pool_subscope() {
+		let mut data = vec![0u128; 256];
+		let mut pool = PoolAllocator::<u128, CpuMemory>::new(&mut data);
+		let _ = pool.alloc(100).unwrap();
+		{
+			let subscope = pool.subscope_allocator();
+			assert_eq!(subscope.capacity(), 156);
+			let _ = subscope.alloc(156).unwrap();
+		}
+		assert_eq!(pool.alloc(156).unwrap().len(), 156);
+	}
+
+	#[test]
+	fn test_alloc_scope_releases_allocations() {
+		let mut data = vec![0u128; 256];
+		let mut bump = BumpAllocator::<u128, CpuMemory>::new(&mut data);
+		let _ = bump.alloc(100).unwrap();
+
+		for _ in 0..3 {
+			let len = bump.alloc_scope(|scope| {
+				let nested_len = scope.alloc_scope(|nested| {
+					assert_eq!(nested.capacity(), 156);
+					nested.alloc(128).unwrap().len()
+				});
+				assert_eq!(nested_len, 128);
+				let buffer = scope.alloc(128).unwrap();
+				assert_matches!(scope.alloc(29), Err(Error::OutOfMemory));
+				buffer.len()
+			});
+			assert_eq!(len, 128);
+			assert_eq!(bump.capacity(), 156);
+		}
+	}
+
+	#[test]
+	fn test_pool_alloc_scope_keeps_budget() {
+		let mut data = vec![0u128; 256];
+		let mut pool = PoolAllocator::<u128, CpuMemory>::with_byte_budget(&mut data, 128 * 16);
+		let _ = pool.alloc(64).unwrap();
+		pool.alloc_scope(|scope| {
+			assert_eq!(scope.capacity(), 64);
+			assert_matches!(scope.alloc(65), Err(Error::OutOfMemory));
+		});
+		assert_eq!(pool.alloc(64).unwrap().len(), 64);
+	}
 }
@@ -0,0 +1,70 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Static limits of a compute device.
+//!
+//! High-level operations query [`ComputeLayer::capabilities`](crate::ComputeLayer::capabilities)
+//! to split work that would exceed the device limits into several smaller operations.
+
+/// The limits and preferences of a compute device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceCapabilities {
+	/// The total device memory size in bytes, or `usize::MAX` if it is unbounded or unknown.
+	pub memory_size: usize,
+	/// The maximum number of field elements that a single operation may process, or `usize::MAX`
+	/// if there is no limit.
+	pub max_kernel_len: usize,
+	/// The preferred alignment, in field elements, of device slices and work partitions.
+	pub preferred_alignment: usize,
+}
+
+impl DeviceCapabilities {
+	/// Returns the capabilities of a device without memory or kernel size limits.
+	pub const fn unbounded(preferred_alignment: usize) -> Self {
+		Self {
+			memory_size: usize::MAX,
+			max_kernel_len: usize::MAX,
+			preferred_alignment,
+		}
+	}
+
+	/// Returns the base-2 logarithm of the largest power-of-two chunk length that a single
+	/// operation may process, given slices of length `2^log_len`.
+	///
+	/// The chunk length is never smaller than the preferred alignment, so that the chunks can be
+	/// sliced from device memory.
+	pub fn max_log_chunk_len(&self, log_len: usize) -> usize {
+		let log_max_kernel_len = self.max_kernel_len.max(1).ilog2() as usize;
+		let log_alignment = self.preferred_alignment.max(1).next_power_of_two().ilog2() as usize;
+		log_max_kernel_len.max(log_alignment).min(log_len)
+	}
+}
+
+impl Default for DeviceCapabilities {
+	fn default() -> Self {
+		Self::unbounded(1)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_max_log_chunk_len() {
+		let caps = DeviceCapabilities::default();
+		assert_eq!(caps.max_log_chunk_len(20), 20);
+
+		let caps = DeviceCapabilities {
+			max_kernel_len: 1000,
+			..DeviceCapabilities::unbounded(4)
+		};
+		assert_eq!(caps.max_log_chunk_len(20), 9);
+		assert_eq!(caps.max_log_chunk_len(5), 5);
+
+		let caps = DeviceCapabilities {
+			max_kernel_len: 2,
+			..DeviceCapabilities::unbounded(16)
+		};
+		assert_eq!(caps.max_log_chunk_len(20), 4);
+	}
+}
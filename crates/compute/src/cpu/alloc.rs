@@ -1,13 +1,35 @@
 // Copyright 2025 Irreducible Inc.
 
+//! Host memory for the CPU compute layer.
+//!
+//! A [`CpuComputeAllocator`] owns a zero-initialized buffer, which is either allocated on the
+//! heap or, with [`CpuComputeAllocator::new_mmap`], memory-mapped. A memory-mapped buffer may be
+//! backed by a file, so that a witness larger than the physical memory is paged out to disk
+//! instead of exhausting the memory of the process, and accepts `madvise` hints for transparent
+//! huge pages and sequential access.
+
 use std::fmt::Debug;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{fs::File, io, path::PathBuf};
 
 use bytemuck::zeroed_vec;
+#[cfg(not(target_arch = "wasm32"))]
+use memmap2::MmapMut;
 
 use crate::alloc::HostBumpAllocator;
 
 pub struct CpuComputeAllocator<F> {
-	data: Vec<F>,
+	storage: Storage<F>,
+}
+
+enum Storage<F> {
+	Heap(Vec<F>),
+	#[cfg(not(target_arch = "wasm32"))]
+	Mmap {
+		mmap: MmapMut,
+		len: usize,
+		_file: Option<File>,
+	},
 }
 
 impl<F> CpuComputeAllocator<F>
@@ -15,7 +37,26 @@ where
 	F: Sync + Debug + Send + 'static,
 {
 	pub fn into_bump_allocator(&mut self) -> HostBumpAllocator<'_, F> {
-		HostBumpAllocator::new(self.data.as_mut_slice())
+		HostBumpAllocator::new(self.as_mut_slice())
+	}
+
+	/// Returns whether the buffer is memory-mapped.
+	pub fn is_mmap(&self) -> bool {
+		!matches!(self.storage, Storage::Heap(_))
+	}
+
+	fn as_mut_slice(&mut self) -> &mut [F] {
+		match &mut self.storage {
+			Storage::Heap(data) => data.as_mut_slice(),
+			#[cfg(not(target_arch = "wasm32"))]
+			Storage::Mmap { mmap, len, .. } => {
+				// SAFETY: The mapping holds `len` elements, is page-aligned, which satisfies the
+				// alignment of `F` as checked on construction, and is zero-initialized, which is a
+				// valid bit pattern of `F` because it is `Zeroable`. The mapping is exclusively
+				// borrowed for the lifetime of the slice.
+				unsafe { std::slice::from_raw_parts_mut(mmap.as_mut_ptr().cast::<F>(), *len) }
+			}
+		}
 	}
 }
 
@@ -25,7 +66,148 @@ where
 {
 	pub fn new(capacity: usize) -> Self {
 		Self {
-			data: zeroed_vec(capacity),
+			storage: Storage::Heap(zeroed_vec(capacity)),
+		}
+	}
+
+	/// Creates an allocator with a memory-mapped buffer of `capacity` elements.
+	///
+	/// The pages of the mapping are only committed when they are first written. Advice that the
+	/// platform does not support is ignored.
+	///
+	/// ## Throws
+	///
+	/// * If the size of the mapping in bytes overflows `usize`.
+	/// * If the backing file cannot be created or the memory cannot be mapped.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn new_mmap(capacity: usize, options: &MmapOptions) -> io::Result<Self> {
+		assert!(
+			align_of::<F>() <= MIN_PAGE_SIZE,
+			"memory mappings are page-aligned, which must satisfy the alignment of the elements"
+		);
+		let n_bytes = capacity.checked_mul(size_of::<F>()).ok_or_else(|| {
+			io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("a mapping of {capacity} elements overflows the address space"),
+			)
+		})?;
+		if n_bytes == 0 {
+			return Ok(Self::new(capacity));
 		}
+
+		let (mmap, file) = match &options.backing_dir {
+			Some(dir) => {
+				let file = tempfile::tempfile_in(dir)?;
+				file.set_len(n_bytes as u64)?;
+				// SAFETY: The file is anonymous, so no other process or mapping can modify it.
+				let mmap = unsafe { MmapMut::map_mut(&file)? };
+				(mmap, Some(file))
+			}
+			None => (MmapMut::map_anon(n_bytes)?, None),
+		};
+		advise(&mmap, options);
+
+		Ok(Self {
+			storage: Storage::Mmap {
+				mmap,
+				len: capacity,
+				_file: file,
+			},
+		})
+	}
+}
+
+/// Options of a memory-mapped [`CpuComputeAllocator`].
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MmapOptions {
+	/// Directory of a temporary file that backs the mapping. The mapping is anonymous if not set.
+	///
+	/// The file is removed from the file system when it is created, so the storage is released
+	/// when the allocator is dropped.
+	pub backing_dir: Option<PathBuf>,
+	/// Whether to advise the kernel to back the mapping with transparent huge pages.
+	///
+	/// Huge pages are only supported on Linux.
+	pub huge_pages: bool,
+	/// Whether to advise the kernel that the mapping is accessed sequentially, so that pages are
+	/// read ahead aggressively and freed soon after they are accessed.
+	pub sequential: bool,
+}
+
+#[cfg(unix)]
+fn advise(mmap: &MmapMut, options: &MmapOptions) {
+	use memmap2::Advice;
+
+	// The advice only affects performance, so a failure is not an error.
+	#[cfg(target_os = "linux")]
+	if options.huge_pages {
+		let _ = mmap.advise(Advice::HugePage);
+	}
+	if options.sequential {
+		let _ = mmap.advise(Advice::Sequential);
+	}
+}
+
+#[cfg(not(any(unix, target_arch = "wasm32")))]
+fn advise(_mmap: &MmapMut, _options: &MmapOptions) {}
+
+/// The smallest page size of the supported platforms, which is the minimum alignment of a mapping.
+#[cfg(not(target_arch = "wasm32"))]
+const MIN_PAGE_SIZE: usize = 4096;
+
+#[cfg(test)]
+mod tests {
+	use binius_field::{BinaryField128b, Field};
+
+	use super::*;
+	use crate::alloc::ComputeAllocator;
+
+	fn check_allocator(mut allocator: CpuComputeAllocator<BinaryField128b>) {
+		let bump = allocator.into_bump_allocator();
+		assert_eq!(bump.capacity(), 1 << 10);
+
+		let data = bump.alloc(1 << 9).unwrap();
+		assert!(data.iter().all(|&x| x == BinaryField128b::ZERO));
+		data.fill(BinaryField128b::ONE);
+		let rest = bump.alloc(1 << 9).unwrap();
+		assert_eq!(rest.len(), 1 << 9);
+		assert!(rest.iter().all(|&x| x == BinaryField128b::ZERO));
+	}
+
+	#[test]
+	fn test_anonymous_mmap() {
+		let options = MmapOptions {
+			huge_pages: true,
+			sequential: true,
+			..Default::default()
+		};
+		let allocator = CpuComputeAllocator::new_mmap(1 << 10, &options).unwrap();
+		assert!(allocator.is_mmap());
+		check_allocator(allocator);
+	}
+
+	#[test]
+	fn test_file_backed_mmap() {
+		let options = MmapOptions {
+			backing_dir: Some(std::env::temp_dir()),
+			..Default::default()
+		};
+		let allocator = CpuComputeAllocator::new_mmap(1 << 10, &options).unwrap();
+		assert!(allocator.is_mmap());
+		check_allocator(allocator);
+	}
+
+	#[test]
+	fn test_empty_mmap() {
+		let allocator =
+			CpuComputeAllocator::<BinaryField128b>::new_mmap(0, &MmapOptions::default()).unwrap();
+		assert!(!allocator.is_mmap());
+	}
+	#[test]
+	fn test_overflowing_mmap() {
+		let result =
+			CpuComputeAllocator::<BinaryField128b>::new_mmap(usize::MAX, &MmapOptions::default());
+		assert_eq!(result.err().map(|err| err.kind()), Some(io::ErrorKind::InvalidInput));
 	}
 }
@@ -2,23 +2,60 @@
 
 use std::{iter, marker::PhantomData};
 
-use binius_field::{BinaryField, ExtensionField, Field, TowerField, util::inner_product_unchecked};
+use binius_field::{
+	BinaryField, ExtensionField, Field, PackedExtension, PackedField, TowerField,
+	packed::{get_packed_slice, set_packed_slice},
+	unpack_if_possible_mut,
+	util::inner_product_unchecked,
+};
+use binius_hash::multi_digest::ParallelDigest;
 use binius_math::{ArithCircuit, TowerTop, extrapolate_line_scalar};
-use binius_ntt::AdditiveNTT;
-use binius_utils::checked_arithmetics::{checked_log_2, strict_log_2};
+use binius_maybe_rayon::prelude::*;
+use binius_ntt::{AdditiveNTT, NTTShape};
+use binius_utils::{
+	checked_arithmetics::{checked_log_2, strict_log_2},
+	mem::slice_uninit_mut,
+};
 use bytemuck::zeroed_vec;
+use digest::{FixedOutputReset, Output, core_api::BlockSizeUser};
 use itertools::izip;
 
 use super::{memory::CpuMemory, tower_macro::each_tower_subfield};
 use crate::{
 	ComputeData, ComputeHolder, ComputeLayerExecutor, KernelExecutor,
 	alloc::{BumpAllocator, ComputeAllocator, HostBumpAllocator},
+	capabilities::DeviceCapabilities,
 	layer::{ComputeLayer, Error, FSlice, FSliceMut, KernelBuffer, KernelMemMap},
 	memory::{ComputeMemory, SizedSlice, SlicesBatch, SubfieldSlice},
+	profile::ProfilerHook,
 };
 
 #[derive(Debug, Default)]
-pub struct CpuLayer<F>(PhantomData<F>);
+pub struct CpuLayer<F> {
+	profiler: ProfilerHook,
+	capabilities: DeviceCapabilities,
+	_marker: PhantomData<F>,
+}
+
+impl<F> CpuLayer<F> {
+	/// Creates a layer that reports every operation to the given profiling hook.
+	pub fn with_profiler(profiler: ProfilerHook) -> Self {
+		Self {
+			profiler,
+			capabilities: DeviceCapabilities::default(),
+			_marker: PhantomData,
+		}
+	}
+
+	/// Sets the device limits that the layer reports.
+	///
+	/// The CPU layer itself has no limits; this is useful to exercise the chunking of high-level
+	/// operations as it would happen on a constrained device.
+	pub fn with_capabilities(mut self, capabilities: DeviceCapabilities) -> Self {
+		self.capabilities = capabilities;
+		self
+	}
+}
 
 impl<F: TowerTop> ComputeLayer<F> for CpuLayer<F> {
 	type Exec<'a> = CpuLayerExecutor<F>;
@@ -30,6 +67,7 @@ impl<F: TowerTop> ComputeLayer<F> for CpuLayer<F> {
 			dst.len(),
 			"precondition: src and dst buffers must have the same length"
 		);
+		let _op = self.profiler.field_op::<F>("copy_h2d", 2 * src.len());
 		dst.copy_from_slice(src);
 		Ok(())
 	}
@@ -40,6 +78,7 @@ impl<F: TowerTop> ComputeLayer<F> for CpuLayer<F> {
 			dst.len(),
 			"precondition: src and dst buffers must have the same length"
 		);
+		let _op = self.profiler.field_op::<F>("copy_d2h", 2 * src.len());
 		dst.copy_from_slice(src);
 		Ok(())
 	}
@@ -54,6 +93,7 @@ impl<F: TowerTop> ComputeLayer<F> for CpuLayer<F> {
 			dst.len(),
 			"precondition: src and dst buffers must have the same length"
 		);
+		let _op = self.profiler.field_op::<F>("copy_d2d", 2 * src.len());
 		dst.copy_from_slice(src);
 		Ok(())
 	}
@@ -65,7 +105,10 @@ impl<F: TowerTop> ComputeLayer<F> for CpuLayer<F> {
 	where
 		'b: 'a,
 	{
-		f(&mut CpuLayerExecutor::<F>::default())
+		f(&mut CpuLayerExecutor {
+			profiler: self.profiler.clone(),
+			_marker: PhantomData,
+		})
 	}
 
 	fn compile_expr(
@@ -75,18 +118,40 @@ impl<F: TowerTop> ComputeLayer<F> for CpuLayer<F> {
 		Ok(expr.clone())
 	}
 
+	fn capabilities(&self) -> DeviceCapabilities {
+		self.capabilities
+	}
+
 	fn fill(
 		&self,
 		slice: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
 		value: F,
 	) -> Result<(), Error> {
+		let _op = self.profiler.field_op::<F>("fill", slice.len());
 		slice.fill(value);
 		Ok(())
 	}
+
+	fn fill_masked(
+		&self,
+		slice: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
+		value: F,
+		len: usize,
+	) -> Result<(), Error> {
+		let _op = self.profiler.field_op::<F>("fill_masked", slice.len());
+		validate_mask_len(len, slice.len())?;
+		let (prefix, padding) = slice.split_at_mut(len);
+		prefix.fill(value);
+		padding.fill(F::ZERO);
+		Ok(())
+	}
 }
 
 #[derive(Debug)]
-pub struct CpuLayerExecutor<F>(PhantomData<F>);
+pub struct CpuLayerExecutor<F> {
+	profiler: ProfilerHook,
+	_marker: PhantomData<F>,
+}
 
 impl<F: TowerTop> CpuLayerExecutor<F> {
 	fn map_kernel_mem<'a>(
@@ -155,7 +220,10 @@ impl<F: TowerTop> CpuLayerExecutor<F> {
 
 impl<F> Default for CpuLayerExecutor<F> {
 	fn default() -> Self {
-		Self(PhantomData)
+		Self {
+			profiler: ProfilerHook::default(),
+			_marker: PhantomData,
+		}
 	}
 }
 
@@ -175,6 +243,10 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 		) -> Result<Vec<F>, Error>,
 		inputs: Vec<KernelMemMap<'_, F, Self::DevMem>>,
 	) -> Result<Vec<Self::OpValue>, Error> {
+		let n_elements = inputs.iter().map(mem_map_len).sum();
+		let _op = self
+			.profiler
+			.field_op::<F>("accumulate_kernels", n_elements);
 		self.process_kernels_chunks(map, inputs)?
 			.reduce(|out1, out2| {
 				let mut out1 = out1?;
@@ -198,6 +270,8 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 		) -> Result<(), Error>,
 		mem_maps: Vec<KernelMemMap<'_, F, Self::DevMem>>,
 	) -> Result<(), Error> {
+		let n_elements = mem_maps.iter().map(mem_map_len).sum();
+		let _op = self.profiler.field_op::<F>("map_kernels", n_elements);
 		self.process_kernels_chunks(map, mem_maps)?.for_each(drop);
 		Ok(())
 	}
@@ -207,6 +281,9 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 		a_in: SubfieldSlice<'_, F, Self::DevMem>,
 		b_in: &'a [F],
 	) -> Result<F, Error> {
+		let _op = self
+			.profiler
+			.field_op::<F>("inner_product", a_in.slice.len() + b_in.len());
 		if a_in.tower_level > F::TOWER_LEVEL
 			|| a_in.slice.len() << (F::TOWER_LEVEL - a_in.tower_level) != b_in.len()
 		{
@@ -235,12 +312,56 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 		Ok(result)
 	}
 
+	fn inner_product_masked<'a>(
+		&'a mut self,
+		a_in: SubfieldSlice<'_, F, Self::DevMem>,
+		b_in: &'a [F],
+		len: usize,
+	) -> Result<F, Error> {
+		let _op = self
+			.profiler
+			.field_op::<F>("inner_product_masked", a_in.slice.len() + b_in.len());
+		if a_in.tower_level > F::TOWER_LEVEL
+			|| a_in.slice.len() << (F::TOWER_LEVEL - a_in.tower_level) != b_in.len()
+		{
+			return Err(Error::InputValidation(format!(
+				"invalid input: a_edeg={} |a|={} |b|={}",
+				a_in.tower_level,
+				a_in.slice.len(),
+				b_in.len()
+			)));
+		}
+		validate_mask_len(len, b_in.len())?;
+
+		fn inner_product_masked<F, FExt>(a_in: &[FExt], b_in: &[FExt], len: usize) -> FExt
+		where
+			F: Field,
+			FExt: ExtensionField<F>,
+		{
+			inner_product_unchecked(
+				b_in[..len].iter().copied(),
+				a_in.iter()
+					.flat_map(<FExt as ExtensionField<F>>::iter_bases)
+					.take(len),
+			)
+		}
+
+		let result = each_tower_subfield!(
+			a_in.tower_level,
+			inner_product_masked::<_, F>(a_in.slice, b_in, len)
+		);
+		Ok(result)
+	}
+
 	fn fold_left(
 		&mut self,
 		mat: SubfieldSlice<'_, F, Self::DevMem>,
 		vec: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
 		out: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
 	) -> Result<(), Error> {
+		let _op = self
+			.profiler
+			.field_op::<F>("fold_left", mat.slice.len() + vec.len() + out.len());
 		if mat.tower_level > F::TOWER_LEVEL {
 			return Err(Error::InputValidation(format!(
 				"invalid evals: tower_level={} > {}",
@@ -263,6 +384,9 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 		vec: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
 		out: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
 	) -> Result<(), Error> {
+		let _op = self
+			.profiler
+			.field_op::<F>("fold_right", mat.slice.len() + vec.len() + out.len());
 		if mat.tower_level > F::TOWER_LEVEL {
 			return Err(Error::InputValidation(format!(
 				"invalid evals: tower_level={} > {}",
@@ -285,6 +409,7 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 		coordinates: &[F],
 		data: &mut &mut [F],
 	) -> Result<(), Error> {
+		let _op = self.profiler.field_op::<F>("tensor_expand", 2 * data.len());
 		if data.len() != 1 << (log_n + coordinates.len()) {
 			return Err(Error::InputValidation(format!("invalid data length: {}", data.len())));
 		}
@@ -314,80 +439,143 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 		FSub: BinaryField,
 		F: ExtensionField<FSub>,
 	{
-		if data_in.len() != 1 << (log_len + log_batch_size) {
-			return Err(Error::InputValidation(format!(
-				"invalid data_in length: {}",
-				data_in.len()
-			)));
-		}
-
-		if challenges.len() < log_batch_size {
-			return Err(Error::InputValidation(format!(
-				"invalid challenges length: {}",
-				challenges.len()
-			)));
-		}
-
-		if challenges.len() > log_batch_size + log_len {
-			return Err(Error::InputValidation(format!(
-				"challenges length too big: {}",
-				challenges.len()
-			)));
-		}
-
-		if data_out.len() != 1 << (log_len - (challenges.len() - log_batch_size)) {
+		let _op = self
+			.profiler
+			.field_op::<F>("fri_fold", data_in.len() + data_out.len());
+		let out_len =
+			validate_fri_fold_lengths(log_len, log_batch_size, challenges.len(), data_in.len())?;
+		if data_out.len() != out_len {
 			return Err(Error::InputValidation(format!(
 				"invalid data_out length: {}",
 				data_out.len()
 			)));
 		}
 
-		let (interleave_challenges, fold_challenges) = challenges.split_at(log_batch_size);
-		let log_size = fold_challenges.len();
-
 		let mut values = vec![F::ZERO; 1 << challenges.len()];
 		for (chunk_index, (chunk, out)) in data_in
 			.chunks_exact(1 << challenges.len())
 			.zip(data_out.iter_mut())
 			.enumerate()
 		{
-			// Apply folding with interleaved challenges.
-			values[..(1 << challenges.len())].copy_from_slice(chunk);
-			let mut current_values = &mut values[0..1 << challenges.len()];
-			for challenge in interleave_challenges {
-				let new_num_elements = current_values.len() / 2;
-				for out_idx in 0..new_num_elements {
-					current_values[out_idx] = extrapolate_line_scalar(
-						current_values[out_idx * 2],
-						current_values[out_idx * 2 + 1],
-						*challenge,
-					);
-				}
-				current_values = &mut current_values[0..new_num_elements];
-			}
-
-			// Apply the inverse NTT to the folded values.
-			let mut log_len = log_len;
-			let mut log_size = log_size;
-			for &challenge in fold_challenges {
-				for index_offset in 0..1 << (log_size - 1) {
-					let t = ntt
-						.get_subspace_eval(log_len, (chunk_index << (log_size - 1)) | index_offset);
-					let (mut u, mut v) =
-						(values[index_offset << 1], values[(index_offset << 1) | 1]);
-					v += u;
-					u += v * t;
-					values[index_offset] = extrapolate_line_scalar(u, v, challenge);
-				}
+			*out = fri_fold_chunk(
+				ntt,
+				log_len,
+				log_batch_size,
+				challenges,
+				chunk_index,
+				chunk,
+				&mut values,
+			);
+		}
 
-				log_len -= 1;
-				log_size -= 1;
-			}
+		Ok(())
+	}
 
-			*out = values[0];
+	fn fri_fold_inplace<'a, FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		log_len: usize,
+		log_batch_size: usize,
+		challenges: &[F],
+		data: <Self::DevMem as ComputeMemory<F>>::FSliceMut<'a>,
+	) -> Result<<Self::DevMem as ComputeMemory<F>>::FSliceMut<'a>, Error>
+	where
+		FSub: BinaryField,
+		F: ExtensionField<FSub>,
+	{
+		let _op = self.profiler.field_op::<F>("fri_fold_inplace", data.len());
+		let out_len =
+			validate_fri_fold_lengths(log_len, log_batch_size, challenges.len(), data.len())?;
+
+		let log_chunk_len = challenges.len();
+		let mut values = vec![F::ZERO; 1 << log_chunk_len];
+		// The chunk at index `i` starts at `i << log_chunk_len`, which is not before `i`, so the
+		// folded values never overwrite a chunk that is yet to be read.
+		for chunk_index in 0..out_len {
+			let chunk = &data[chunk_index << log_chunk_len..(chunk_index + 1) << log_chunk_len];
+			data[chunk_index] = fri_fold_chunk(
+				ntt,
+				log_len,
+				log_batch_size,
+				challenges,
+				chunk_index,
+				chunk,
+				&mut values,
+			);
 		}
 
-		Ok(())
+		Ok(&mut data[..out_len])
+	}
+
+	fn forward_ntt<FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+		data: &mut &mut [F],
+	) -> Result<(), Error>
+	where
+		FSub: BinaryField,
+		F: ExtensionField<FSub> + PackedExtension<FSub>,
+	{
+		let _op = self.profiler.field_op::<F>("forward_ntt", 2 * data.len());
+		validate_ntt_shape(data.len(), shape)?;
+		ntt_transform_ext(ntt, data, shape, coset, coset_bits, skip_rounds, false)
+	}
+
+	fn inverse_ntt<FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+		data: &mut &mut [F],
+	) -> Result<(), Error>
+	where
+		FSub: BinaryField,
+		F: ExtensionField<FSub> + PackedExtension<FSub>,
+	{
+		let _op = self.profiler.field_op::<F>("inverse_ntt", 2 * data.len());
+		validate_ntt_shape(data.len(), shape)?;
+		ntt_transform_ext(ntt, data, shape, coset, coset_bits, skip_rounds, true)
+	}
+
+	fn rs_encode<FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		log_dim: usize,
+		log_inv_rate: usize,
+		log_batch_size: usize,
+		message: &[F],
+		codeword: &mut &mut [F],
+	) -> Result<(), Error>
+	where
+		FSub: BinaryField,
+		F: ExtensionField<FSub> + PackedExtension<FSub>,
+	{
+		let _op = self
+			.profiler
+			.field_op::<F>("rs_encode", message.len() + codeword.len());
+		validate_rs_encode_lengths(
+			log_dim,
+			log_inv_rate,
+			log_batch_size,
+			message.len(),
+			codeword.len(),
+		)?;
+
+		for coset_codeword in codeword.chunks_exact_mut(message.len()) {
+			coset_codeword.copy_from_slice(message);
+		}
+		let shape = NTTShape {
+			log_x: log_batch_size,
+			log_y: log_dim + log_inv_rate,
+			..Default::default()
+		};
+		ntt_transform_ext(ntt, codeword, shape, 0, 0, log_inv_rate, false)
 	}
 
 	fn extrapolate_line(
@@ -396,6 +584,9 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 		evals_1: &[F],
 		z: F,
 	) -> Result<(), Error> {
+		let _op = self
+			.profiler
+			.field_op::<F>("extrapolate_line", 2 * evals_0.len() + evals_1.len());
 		if evals_0.len() != evals_1.len() {
 			return Err(Error::InputValidation(
 				"evals_0 and evals_1 must be the same length".into(),
@@ -407,12 +598,77 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 		Ok(())
 	}
 
+	fn fold_low(&mut self, evals: &[F], z: F, out: &mut &mut [F]) -> Result<(), Error> {
+		let _op = self
+			.profiler
+			.field_op::<F>("fold_low", evals.len() + out.len());
+		validate_fold_lengths(evals.len(), out.len())?;
+		for (out_i, evals_i) in iter::zip(&mut **out, evals.chunks_exact(2)) {
+			*out_i = extrapolate_line_scalar(evals_i[0], evals_i[1], z);
+		}
+		Ok(())
+	}
+
+	fn fold_high(&mut self, evals: &[F], z: F, out: &mut &mut [F]) -> Result<(), Error> {
+		let _op = self
+			.profiler
+			.field_op::<F>("fold_high", evals.len() + out.len());
+		validate_fold_lengths(evals.len(), out.len())?;
+		let (evals_0, evals_1) = evals.split_at(out.len());
+		for (out_i, &x0, &x1) in izip!(&mut **out, evals_0, evals_1) {
+			*out_i = extrapolate_line_scalar(x0, x1, z);
+		}
+		Ok(())
+	}
+
+	fn fold_low_masked(
+		&mut self,
+		evals: &[F],
+		len: usize,
+		z: F,
+		out: &mut &mut [F],
+	) -> Result<(), Error> {
+		let _op = self
+			.profiler
+			.field_op::<F>("fold_low_masked", evals.len() + out.len());
+		validate_fold_lengths(evals.len(), out.len())?;
+		validate_mask_len(len, evals.len())?;
+		let eval = |i: usize| if i < len { evals[i] } else { F::ZERO };
+		for (i, out_i) in out.iter_mut().enumerate() {
+			*out_i = extrapolate_line_scalar(eval(2 * i), eval(2 * i + 1), z);
+		}
+		Ok(())
+	}
+
+	fn fold_high_masked(
+		&mut self,
+		evals: &[F],
+		len: usize,
+		z: F,
+		out: &mut &mut [F],
+	) -> Result<(), Error> {
+		let _op = self
+			.profiler
+			.field_op::<F>("fold_high_masked", evals.len() + out.len());
+		validate_fold_lengths(evals.len(), out.len())?;
+		validate_mask_len(len, evals.len())?;
+		let eval = |i: usize| if i < len { evals[i] } else { F::ZERO };
+		let half = out.len();
+		for (i, out_i) in out.iter_mut().enumerate() {
+			*out_i = extrapolate_line_scalar(eval(i), eval(i + half), z);
+		}
+		Ok(())
+	}
+
 	fn compute_composite(
 		&mut self,
 		inputs: &SlicesBatch<<Self::DevMem as ComputeMemory<F>>::FSlice<'_>>,
 		output: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
 		composition: &Self::ExprEval,
 	) -> Result<(), Error> {
+		let _op = self
+			.profiler
+			.field_op::<F>("compute_composite", (inputs.n_rows() + 1) * inputs.row_len());
 		if inputs.row_len() != output.len() {
 			return Err(Error::InputValidation("inputs and output must be the same length".into()));
 		}
@@ -439,6 +695,9 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 		input: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
 		round_outputs: &mut [<Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>],
 	) -> Result<(), Error> {
+		let _op = self
+			.profiler
+			.field_op::<F>("pairwise_product_reduce", 2 * input.len());
 		let log_num_inputs = match strict_log_2(input.len()) {
 			None => {
 				return Err(Error::InputValidation(format!(
@@ -482,6 +741,32 @@ impl<F: TowerTop> ComputeLayerExecutor<F> for CpuLayerExecutor<F> {
 
 		Ok(())
 	}
+
+	fn hash_leaves<H>(
+		&mut self,
+		data: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
+		digests: &mut [Output<H::Digest>],
+	) -> Result<(), Error>
+	where
+		H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	{
+		let _op = self.profiler.field_op::<F>("hash_leaves", data.len());
+		if digests.is_empty() || data.is_empty() || data.len() % digests.len() != 0 {
+			return Err(Error::InputValidation(format!(
+				"data length {} must be a non-zero multiple of the number of digests {}",
+				data.len(),
+				digests.len()
+			)));
+		}
+
+		let chunk_len = data.len() / digests.len();
+		H::new().digest(
+			data.par_chunks(chunk_len)
+				.map(|chunk| chunk.iter().copied()),
+			slice_uninit_mut(digests),
+		);
+		Ok(())
+	}
 }
 
 #[derive(Debug)]
@@ -566,6 +851,269 @@ pub fn count_total_local_buffer_sizes<F, Mem: ComputeMemory<F>>(
 		.sum()
 }
 
+/// Returns the total number of elements covered by a kernel memory mapping.
+pub fn mem_map_len<F, Mem: ComputeMemory<F>>(mem_map: &KernelMemMap<'_, F, Mem>) -> usize {
+	match mem_map {
+		KernelMemMap::Chunked { data, .. } => data.len(),
+		KernelMemMap::ChunkedMut { data, .. } => 2 * data.len(),
+		KernelMemMap::Local { log_size } => 1 << log_size,
+	}
+}
+
+/// Checks that a buffer of `len` elements matches the dimensions of an NTT shape.
+pub fn validate_ntt_shape(len: usize, shape: NTTShape) -> Result<(), Error> {
+	let log_len = shape.log_x + shape.log_y + shape.log_z;
+	if len != 1 << log_len {
+		return Err(Error::InputValidation(format!(
+			"data length {len} does not match the NTT shape, expected 2^{log_len}"
+		)));
+	}
+	Ok(())
+}
+
+/// Checks the message and codeword buffer lengths for a batched Reed–Solomon encoding.
+pub fn validate_rs_encode_lengths(
+	log_dim: usize,
+	log_inv_rate: usize,
+	log_batch_size: usize,
+	message_len: usize,
+	codeword_len: usize,
+) -> Result<(), Error> {
+	if message_len != 1 << (log_dim + log_batch_size) {
+		return Err(Error::InputValidation(format!(
+			"message length {message_len} must be 2^{}",
+			log_dim + log_batch_size
+		)));
+	}
+	if codeword_len != 1 << (log_dim + log_inv_rate + log_batch_size) {
+		return Err(Error::InputValidation(format!(
+			"codeword length {codeword_len} must be 2^{}",
+			log_dim + log_inv_rate + log_batch_size
+		)));
+	}
+	Ok(())
+}
+
+/// Checks the input length and the number of challenges of a FRI fold, and returns the output
+/// length.
+pub fn validate_fri_fold_lengths(
+	log_len: usize,
+	log_batch_size: usize,
+	n_challenges: usize,
+	data_in_len: usize,
+) -> Result<usize, Error> {
+	if data_in_len != 1 << (log_len + log_batch_size) {
+		return Err(Error::InputValidation(format!("invalid data_in length: {data_in_len}")));
+	}
+	if n_challenges < log_batch_size {
+		return Err(Error::InputValidation(format!("invalid challenges length: {n_challenges}")));
+	}
+	if n_challenges > log_batch_size + log_len {
+		return Err(Error::InputValidation(format!("challenges length too big: {n_challenges}")));
+	}
+	Ok(1 << (log_len - (n_challenges - log_batch_size)))
+}
+
+/// Checks the input and output buffer lengths of a single-variable multilinear fold.
+pub fn validate_fold_lengths(evals_len: usize, out_len: usize) -> Result<(), Error> {
+	if !matches!(strict_log_2(evals_len), Some(log_len) if log_len > 0) {
+		return Err(Error::InputValidation(format!(
+			"evals length {evals_len} must be a power of two greater than one"
+		)));
+	}
+	if 2 * out_len != evals_len {
+		return Err(Error::InputValidation(format!(
+			"output length {out_len} must be half the evals length {evals_len}"
+		)));
+	}
+	Ok(())
+}
+
+/// Checks that the logical length of a masked operation fits in its buffer.
+pub fn validate_mask_len(len: usize, buffer_len: usize) -> Result<(), Error> {
+	if len > buffer_len {
+		return Err(Error::InputValidation(format!(
+			"masked length {len} exceeds the buffer length {buffer_len}"
+		)));
+	}
+	Ok(())
+}
+
+/// Applies an additive NTT over a subfield to a buffer of packed extension field elements.
+///
+/// The extension field elements are decomposed into their `FSub` coordinates, which are
+/// interleaved along the X axis of the transform shape, as in
+/// [`AdditiveNTT::forward_transform_ext`]. The buffer must contain at least the number of
+/// scalars described by `shape`.
+///
+/// The buffer is transformed in place when its scalars can be viewed as a slice. Otherwise, the
+/// `FSub` coordinates are copied out and back.
+pub fn ntt_transform_packed_ext<F, FSub, P>(
+	ntt: &impl AdditiveNTT<FSub>,
+	data: &mut [P],
+	shape: NTTShape,
+	coset: usize,
+	coset_bits: usize,
+	skip_rounds: usize,
+	inverse: bool,
+) -> Result<(), Error>
+where
+	F: ExtensionField<FSub> + PackedExtension<FSub>,
+	FSub: BinaryField,
+	P: PackedField<Scalar = F>,
+{
+	let n_scalars = 1 << (shape.log_x + shape.log_y + shape.log_z);
+	unpack_if_possible_mut(
+		data,
+		|scalars| ntt_transform_ext(ntt, scalars, shape, coset, coset_bits, skip_rounds, inverse),
+		|packed| {
+			let mut bases = PackedField::iter_slice(packed)
+				.take(n_scalars)
+				.flat_map(ExtensionField::<FSub>::into_iter_bases)
+				.collect::<Vec<_>>();
+
+			let shape_sub = NTTShape {
+				log_x: shape.log_x + <F as ExtensionField<FSub>>::LOG_DEGREE,
+				..shape
+			};
+			let result = if inverse {
+				ntt.inverse_transform(&mut bases, shape_sub, coset, coset_bits, skip_rounds)
+			} else {
+				ntt.forward_transform(&mut bases, shape_sub, coset, coset_bits, skip_rounds)
+			};
+			result.map_err(|err| Error::CoreLibError(Box::new(err)))?;
+
+			for (i, chunk) in bases
+				.chunks_exact(<F as ExtensionField<FSub>>::DEGREE)
+				.enumerate()
+			{
+				let value = F::from_bases(chunk.iter().copied())
+					.expect("chunk has exactly F::DEGREE elements");
+				set_packed_slice(packed, i, value);
+			}
+			Ok(())
+		},
+	)
+}
+
+/// Applies an additive NTT over a subfield in place to a buffer of extension field elements.
+///
+/// See [`ntt_transform_packed_ext`] for the data layout.
+fn ntt_transform_ext<F, FSub>(
+	ntt: &impl AdditiveNTT<FSub>,
+	data: &mut [F],
+	shape: NTTShape,
+	coset: usize,
+	coset_bits: usize,
+	skip_rounds: usize,
+	inverse: bool,
+) -> Result<(), Error>
+where
+	F: PackedExtension<FSub>,
+	FSub: BinaryField,
+{
+	let data = &mut data[..1 << (shape.log_x + shape.log_y + shape.log_z)];
+	let result = if inverse {
+		ntt.inverse_transform_ext(data, shape, coset, coset_bits, skip_rounds)
+	} else {
+		ntt.forward_transform_ext(data, shape, coset, coset_bits, skip_rounds)
+	};
+	result.map_err(|err| Error::CoreLibError(Box::new(err)))
+}
+
+/// Reed–Solomon encodes a batch of interleaved messages stored in packed buffers.
+///
+/// The message is repeated to fill the codeword buffer, and then the forward NTT is applied,
+/// skipping the rounds that would operate on the repeated cosets. See
+/// [`ComputeLayerExecutor::rs_encode`] for the parameter definitions. The buffer lengths must be
+/// validated by the caller.
+pub fn rs_encode_packed<F, FSub, P>(
+	ntt: &impl AdditiveNTT<FSub>,
+	log_dim: usize,
+	log_inv_rate: usize,
+	log_batch_size: usize,
+	message: &[P],
+	codeword: &mut [P],
+) -> Result<(), Error>
+where
+	F: ExtensionField<FSub> + PackedExtension<FSub>,
+	FSub: BinaryField,
+	P: PackedField<Scalar = F>,
+{
+	let message_len = 1 << (log_dim + log_batch_size);
+	if message_len >= P::WIDTH {
+		let message = &message[..message_len >> P::LOG_WIDTH];
+		for coset_codeword in codeword
+			.chunks_exact_mut(message.len())
+			.take(1 << log_inv_rate)
+		{
+			coset_codeword.copy_from_slice(message);
+		}
+	} else {
+		for i in 0..message_len << log_inv_rate {
+			set_packed_slice(codeword, i, get_packed_slice(message, i % message_len));
+		}
+	}
+
+	let shape = NTTShape {
+		log_x: log_batch_size,
+		log_y: log_dim + log_inv_rate,
+		..Default::default()
+	};
+	ntt_transform_packed_ext(ntt, codeword, shape, 0, 0, log_inv_rate, false)
+}
+
+/// FRI-folds the chunk of the interleaved codeword at `chunk_index` into a single value.
+///
+/// `values` is a scratch buffer with the length of the chunk.
+fn fri_fold_chunk<F, FSub>(
+	ntt: &impl AdditiveNTT<FSub>,
+	mut log_len: usize,
+	log_batch_size: usize,
+	challenges: &[F],
+	chunk_index: usize,
+	chunk: &[F],
+	values: &mut [F],
+) -> F
+where
+	F: Field + ExtensionField<FSub>,
+	FSub: BinaryField,
+{
+	let (interleave_challenges, fold_challenges) = challenges.split_at(log_batch_size);
+	let mut log_size = fold_challenges.len();
+
+	// Apply folding with interleaved challenges.
+	values.copy_from_slice(chunk);
+	let mut current_values = &mut values[..];
+	for challenge in interleave_challenges {
+		let new_num_elements = current_values.len() / 2;
+		for out_idx in 0..new_num_elements {
+			current_values[out_idx] = extrapolate_line_scalar::<F, F>(
+				current_values[out_idx * 2],
+				current_values[out_idx * 2 + 1],
+				*challenge,
+			);
+		}
+		current_values = &mut current_values[0..new_num_elements];
+	}
+
+	// Apply the inverse NTT to the folded values.
+	for &challenge in fold_challenges {
+		for index_offset in 0..1 << (log_size - 1) {
+			let t = ntt.get_subspace_eval(log_len, (chunk_index << (log_size - 1)) | index_offset);
+			let (mut u, mut v) = (values[index_offset << 1], values[(index_offset << 1) | 1]);
+			v += u;
+			u += v * t;
+			values[index_offset] = extrapolate_line_scalar::<F, F>(u, v, challenge);
+		}
+
+		log_len -= 1;
+		log_size -= 1;
+	}
+
+	values[0]
+}
+
 /// Compute the left fold operation.
 ///
 /// evals is treated as a matrix with `1 << log_query_size` columns and each row is dot-produced
@@ -2,10 +2,16 @@
 
 use std::{marker::PhantomData, ops::Range};
 
-use binius_field::{BinaryField, ExtensionField, Field};
+use binius_field::{BinaryField, ExtensionField, Field, PackedExtension, TowerField};
+use binius_hash::{PseudoCompressionFunction, multi_digest::ParallelDigest};
 use binius_math::ArithCircuit;
-use binius_ntt::AdditiveNTT;
-use binius_utils::checked_arithmetics::{checked_int_div, checked_log_2};
+use binius_maybe_rayon::prelude::*;
+use binius_ntt::{AdditiveNTT, NTTShape};
+use binius_utils::{
+	checked_arithmetics::{checked_int_div, checked_log_2},
+	rayon::ThreadPoolHandle,
+};
+use digest::{FixedOutputReset, Output, core_api::BlockSizeUser};
 use itertools::Either;
 
 use super::{
@@ -14,8 +20,11 @@ use super::{
 };
 use crate::{
 	alloc::ComputeAllocator,
-	cpu::CpuMemory,
+	capabilities::DeviceCapabilities,
+	cpu::{CpuMemory, layer::validate_mask_len},
 	memory::{SizedSlice, SlicesBatch},
+	pinned::PinnedBuffer,
+	stream::{Event, StreamId, Transfer, TransferScope, check_stream},
 };
 
 /// A hardware abstraction layer (HAL) for compute operations.
@@ -71,6 +80,114 @@ pub trait ComputeLayer<F: Field> {
 	where
 		'b: 'a;
 
+	/// Returns the memory and kernel size limits of the device.
+	///
+	/// The default implementation reports a device without limits, whose preferred alignment is
+	/// the device memory alignment.
+	fn capabilities(&self) -> DeviceCapabilities {
+		DeviceCapabilities::unbounded(Self::DevMem::ALIGNMENT)
+	}
+
+	/// Returns the number of execution streams that the device supports.
+	///
+	/// Operations submitted to distinct streams may execute concurrently with each other and with
+	/// host-side work. Every compute layer supports at least [`StreamId::DEFAULT`].
+	fn n_streams(&self) -> usize {
+		1
+	}
+
+	/// Submits an operation to an execution stream and returns an event for its result.
+	///
+	/// This is the asynchronous counterpart of [`Self::execute`]. Operations submitted to the same
+	/// stream appear to execute in submission order. The caller must wait on the returned event
+	/// before reading any device memory written by the operation.
+	///
+	/// The default implementation executes the operation synchronously and returns an event that
+	/// has already completed.
+	///
+	/// ## Throws
+	///
+	/// * if `stream` is not less than [`Self::n_streams`]
+	fn execute_on_stream<'a, 'b>(
+		&'b self,
+		stream: StreamId,
+		f: impl FnOnce(
+			&mut Self::Exec<'a>,
+		) -> Result<Vec<<Self::Exec<'a> as ComputeLayerExecutor<F>>::OpValue>, Error>,
+	) -> Result<Event<Vec<F>>, Error>
+	where
+		'b: 'a,
+	{
+		check_stream(stream, self.n_streams())?;
+		Ok(Event::ready(self.execute(f)))
+	}
+
+	/// Allocates a host buffer for staging transfers to and from the device.
+	///
+	/// Devices attached over a bus like PCIe require page-locked host memory to reach full
+	/// transfer bandwidth, and implementations for such devices should return a pinned buffer.
+	/// The buffer is zero-initialized.
+	///
+	/// The default implementation allocates ordinary, pageable host memory, which is appropriate
+	/// for compute layers that share memory with the host.
+	fn alloc_pinned_host(&self, n: usize) -> Result<PinnedBuffer<F>, Error> {
+		Ok(PinnedBuffer::unpinned(n))
+	}
+
+	/// Submits an asynchronous copy from the host to the device on an execution stream.
+	///
+	/// The copy is ordered with respect to other operations on the same stream. The source and
+	/// destination buffers stay borrowed until the end of the
+	/// [`transfer_scope`](crate::stream::transfer_scope), which waits for the copy. For best
+	/// throughput, `src` should reside in a buffer from [`Self::alloc_pinned_host`].
+	///
+	/// The default implementation performs a synchronous [`Self::copy_h2d`].
+	///
+	/// ## Preconditions
+	///
+	/// * `src` and `dst` must have the same length.
+	///
+	/// ## Throws
+	///
+	/// * if `stream` is not less than [`Self::n_streams`]
+	fn copy_h2d_async<'scope>(
+		&self,
+		scope: &'scope TransferScope<'scope, '_>,
+		stream: StreamId,
+		src: &'scope [F],
+		dst: &'scope mut FSliceMut<'_, F, Self>,
+	) -> Result<Transfer<'scope>, Error> {
+		check_stream(stream, self.n_streams())?;
+		Ok(scope.ready(self.copy_h2d(src, dst)))
+	}
+
+	/// Submits an asynchronous copy from the device to the host on an execution stream.
+	///
+	/// The copy is ordered with respect to other operations on the same stream. The source and
+	/// destination buffers stay borrowed until the end of the
+	/// [`transfer_scope`](crate::stream::transfer_scope), which waits for the copy. For best
+	/// throughput, `dst` should reside in a buffer from [`Self::alloc_pinned_host`].
+	///
+	/// The default implementation performs a synchronous [`Self::copy_d2h`].
+	///
+	/// ## Preconditions
+	///
+	/// * `src` and `dst` must have the same length.
+	///
+	/// ## Throws
+	///
+	/// * if `stream` is not less than [`Self::n_streams`]
+	fn copy_d2h_async<'scope>(
+		&self,
+		scope: &'scope TransferScope<'scope, '_>,
+		stream: StreamId,
+		src: FSlice<'scope, F, Self>,
+		dst: &'scope mut [F],
+	) -> Result<Transfer<'scope>, Error> {
+		check_stream(stream, self.n_streams())?;
+		Ok(scope.ready(self.copy_d2h(src, dst)))
+	}
+
 	/// Fills a mutable slice of field elements with a given value.
 	///
 	/// This operation takes a mutable slice (`FSliceMut<F>`) and a field element `value`,
@@ -85,6 +202,36 @@ pub trait ComputeLayer<F: Field> {
 		slice: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
 		value: F,
 	) -> Result<(), Error>;
+
+	/// Fills the first `len` elements of a mutable slice with a given value and the remaining
+	/// elements with zero.
+	///
+	/// This initializes a buffer that holds a vector of logical length `len`, padded with zeros
+	/// to the buffer length, which is usually a power of two.
+	///
+	/// The default implementation stages the values in host memory and copies them to the
+	/// device. Backends should override it with an on-device fill.
+	///
+	/// ### Arguments
+	///
+	/// * `slice` - A mutable slice of field elements to be filled.
+	/// * `value` - The field element used to fill each position in the logical prefix.
+	/// * `len` - The logical length of the vector.
+	///
+	/// ## Throws
+	///
+	/// * if `len` exceeds the length of `slice`.
+	fn fill_masked(
+		&self,
+		slice: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
+		value: F,
+		len: usize,
+	) -> Result<(), Error> {
+		validate_mask_len(len, slice.len())?;
+		let mut host = vec![F::ZERO; slice.len()];
+		host[..len].fill(value);
+		self.copy_h2d(&host, slice)
+	}
 }
 
 /// An interface for executing a sequence of operations on an accelerated compute device
@@ -266,6 +413,29 @@ pub trait ComputeLayerExecutor<F: Field> {
 		b_in: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
 	) -> Result<Self::OpValue, Error>;
 
+	/// Returns the inner product of the first `len` elements of two vectors.
+	///
+	/// This is [`Self::inner_product`] where the elements of both inputs at indices `len` and
+	/// above are treated as zero, so that vectors with a logical length that is not a power of two
+	/// can be stored in power-of-two buffers without zeroing the padding.
+	///
+	/// ## Arguments
+	///
+	/// * `a_in` - the first input slice of subfield elements.
+	/// * `b_in` - the second input slice of `F` elements.
+	/// * `len` - the number of leading elements that are included in the inner product.
+	///
+	/// ## Throws
+	///
+	/// * under the same conditions as [`Self::inner_product`]
+	/// * if `len` exceeds the length of `b_in`
+	fn inner_product_masked(
+		&mut self,
+		a_in: SubfieldSlice<'_, F, Self::DevMem>,
+		b_in: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
+		len: usize,
+	) -> Result<Self::OpValue, Error>;
+
 	/// Computes the iterative tensor product of the input with the given coordinates.
 	///
 	/// This operation modifies the data buffer in place.
@@ -399,6 +569,118 @@ pub trait ComputeLayerExecutor<F: Field> {
 		FSub: BinaryField,
 		F: ExtensionField<FSub>;
 
+	/// FRI-fold the interleaved codeword in place, using the given challenges.
+	///
+	/// This is [`Self::fri_fold`] with the output written over the front of the input buffer
+	/// instead of into a separate buffer. The input is consumed and the folded prefix, of length
+	/// $2^n$, is returned; the rest of the buffer is left with unspecified contents. When the
+	/// codeword is not needed after the fold, as in the commit phase of FRI, this avoids holding
+	/// the output buffer alongside the input, which for the largest oracles is half of the peak
+	/// codeword memory.
+	///
+	/// ## Arguments
+	///
+	/// * `ntt` - the NTT instance, used to look up the twiddle values.
+	/// * `log_len` - $n + \eta$, the binary logarithm of the code length.
+	/// * `log_batch_size` - $b$, the binary logarithm of the interleaved code batch size.
+	/// * `challenges` - the folding challenges, with length $b + \eta$.
+	/// * `data` - the input vector, with length $2^{n + b + \eta}$.
+	///
+	/// ## Throws
+	///
+	/// * under the same conditions as [`Self::fri_fold`]
+	/// * if the output length is not a multiple of the memory alignment
+	fn fri_fold_inplace<'a, FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		log_len: usize,
+		log_batch_size: usize,
+		challenges: &[F],
+		data: <Self::DevMem as ComputeMemory<F>>::FSliceMut<'a>,
+	) -> Result<<Self::DevMem as ComputeMemory<F>>::FSliceMut<'a>, Error>
+	where
+		FSub: BinaryField,
+		F: ExtensionField<FSub>;
+
+	/// Applies the forward additive NTT in place to a buffer of extension field elements.
+	///
+	/// The transform is defined over the subfield `FSub`, and the buffer elements are treated as
+	/// vectors over `FSub`, in the same way as [`AdditiveNTT::forward_transform_ext`]. The `shape`
+	/// describes the layout of the `F` elements in the buffer; see [`NTTShape`] for details. The
+	/// remaining parameters have the same meaning as in [`AdditiveNTT::forward_transform`].
+	///
+	/// ## Throws
+	///
+	/// * unless `data.len()` equals `2^(shape.log_x + shape.log_y + shape.log_z)`
+	/// * if the NTT does not support the requested transform dimensions
+	fn forward_ntt<FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+		data: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
+	) -> Result<(), Error>
+	where
+		FSub: BinaryField,
+		F: ExtensionField<FSub> + PackedExtension<FSub>;
+
+	/// Applies the inverse additive NTT in place to a buffer of extension field elements.
+	///
+	/// This is the inverse of [`Self::forward_ntt`] for the same parameters.
+	///
+	/// ## Throws
+	///
+	/// * unless `data.len()` equals `2^(shape.log_x + shape.log_y + shape.log_z)`
+	/// * if the NTT does not support the requested transform dimensions
+	fn inverse_ntt<FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+		data: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
+	) -> Result<(), Error>
+	where
+		FSub: BinaryField,
+		F: ExtensionField<FSub> + PackedExtension<FSub>;
+
+	/// Reed–Solomon encodes a batch of interleaved messages.
+	///
+	/// The message buffer holds $2^b$ interleaved messages of length $2^k$, where $b$ is
+	/// `log_batch_size` and $k$ is `log_dim`. The codeword buffer receives the $2^b$ interleaved
+	/// codewords of length $2^{k + r}$, where $r$ is `log_inv_rate`. The code is defined over the
+	/// NTT subfield `FSub` and extended to `F` element-wise, matching the encoding of
+	/// `ReedSolomonCode::encode_ext_batch_inplace` in `binius_core`.
+	///
+	/// ## Arguments
+	///
+	/// * `ntt` - the NTT instance, whose domain determines the code's evaluation domain.
+	/// * `log_dim` - $k$, the binary logarithm of the message length.
+	/// * `log_inv_rate` - $r$, the binary logarithm of the inverse code rate.
+	/// * `log_batch_size` - $b$, the binary logarithm of the number of interleaved messages.
+	/// * `message` - an input buffer, with length $2^{k + b}$.
+	/// * `codeword` - an output buffer, with length $2^{k + r + b}$.
+	///
+	/// ## Throws
+	///
+	/// * if the `message` or `codeword` lengths do not match the code parameters
+	/// * if the NTT domain is too small for the code length
+	fn rs_encode<FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		log_dim: usize,
+		log_inv_rate: usize,
+		log_batch_size: usize,
+		message: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
+		codeword: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
+	) -> Result<(), Error>
+	where
+		FSub: BinaryField,
+		F: ExtensionField<FSub> + PackedExtension<FSub>;
+
 	/// Extrapolates a line between a vector of evaluations at 0 and evaluations at 1.
 	///
 	/// Given two values $y_0, y_1$, this operation computes the value $y_z = y_0 + (y_1 - y_0) z$,
@@ -425,6 +707,106 @@ pub trait ComputeLayerExecutor<F: Field> {
 		z: F,
 	) -> Result<(), Error>;
 
+	/// Folds the lowest variable of a multilinear polynomial at a challenge point.
+	///
+	/// ## Mathematical Definition
+	///
+	/// Given the evaluations of an $n$-variate multilinear $P$ over the hypercube, with the lowest
+	/// variable in the least significant bit of the index, and a challenge $z$, this computes the
+	/// evaluations of the $(n-1)$-variate multilinear $P(z, X_1, \ldots, X_{n-1})$:
+	///
+	/// $$
+	/// \mathrm{out}\[i\] = \mathrm{evals}\[2i\] + (\mathrm{evals}\[2i+1\] - \mathrm{evals}\[2i\])
+	/// z. $$
+	///
+	/// This is the fold of interleaved codewords in FRI and of sumchecks that bind variables from
+	/// low to high.
+	///
+	/// ## Args
+	///
+	/// * `evals` - the $2^n$ evaluations of the multilinear.
+	/// * `z` - the challenge at which the lowest variable is evaluated.
+	/// * `out` - an output buffer of length $2^{n-1}$.
+	///
+	/// ## Throws
+	///
+	/// * if the length of `evals` is not a power of two greater than one.
+	/// * if the length of `out` is not half the length of `evals`.
+	fn fold_low(
+		&mut self,
+		evals: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
+		z: F,
+		out: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
+	) -> Result<(), Error>;
+
+	/// Folds the highest variable of a multilinear polynomial at a challenge point.
+	///
+	/// ## Mathematical Definition
+	///
+	/// Given the evaluations of an $n$-variate multilinear $P$ over the hypercube, with the highest
+	/// variable in the most significant bit of the index, and a challenge $z$, this computes the
+	/// evaluations of the $(n-1)$-variate multilinear $P(X_0, \ldots, X_{n-2}, z)$:
+	///
+	/// $$
+	/// \mathrm{out}\[i\] = \mathrm{evals}\[i\] + (\mathrm{evals}\[i + 2^{n-1}\] -
+	/// \mathrm{evals}\[i\]) z. $$
+	///
+	/// This is the out-of-place counterpart of [`Self::extrapolate_line`] applied to the two
+	/// halves of `evals`, and is the fold of sumchecks that bind variables from high to low.
+	///
+	/// ## Args
+	///
+	/// * `evals` - the $2^n$ evaluations of the multilinear.
+	/// * `z` - the challenge at which the highest variable is evaluated.
+	/// * `out` - an output buffer of length $2^{n-1}$.
+	///
+	/// ## Throws
+	///
+	/// * if the length of `evals` is not a power of two greater than one.
+	/// * if the length of `out` is not half the length of `evals`.
+	fn fold_high(
+		&mut self,
+		evals: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
+		z: F,
+		out: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
+	) -> Result<(), Error>;
+
+	/// Folds the lowest variable of a multilinear whose evaluations past `len` are zero.
+	///
+	/// This is [`Self::fold_low`] where the elements of `evals` at indices `len` and above are
+	/// treated as zero, whatever the buffer holds. The output is written in full, including the
+	/// entries that fold only padding, which are zero.
+	///
+	/// ## Throws
+	///
+	/// * under the same conditions as [`Self::fold_low`]
+	/// * if `len` exceeds the length of `evals`
+	fn fold_low_masked(
+		&mut self,
+		evals: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
+		len: usize,
+		z: F,
+		out: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
+	) -> Result<(), Error>;
+
+	/// Folds the highest variable of a multilinear whose evaluations past `len` are zero.
+	///
+	/// This is [`Self::fold_high`] where the elements of `evals` at indices `len` and above are
+	/// treated as zero, whatever the buffer holds. The output is written in full, including the
+	/// entries that fold only padding, which are zero.
+	///
+	/// ## Throws
+	///
+	/// * under the same conditions as [`Self::fold_high`]
+	/// * if `len` exceeds the length of `evals`
+	fn fold_high_masked(
+		&mut self,
+		evals: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
+		len: usize,
+		z: F,
+		out: &mut <Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>,
+	) -> Result<(), Error>;
+
 	/// Computes the elementwise application of a compiled arithmetic expression to multiple input
 	/// slices.
 	///
@@ -507,6 +889,71 @@ pub trait ComputeLayerExecutor<F: Field> {
 		input: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
 		round_outputs: &mut [<Self::DevMem as ComputeMemory<F>>::FSliceMut<'_>],
 	) -> Result<(), Error>;
+
+	/// Hashes equal-sized chunks of a device buffer into Merkle tree leaf digests.
+	///
+	/// The input buffer is split into `digests.len()` contiguous chunks of equal length, and each
+	/// chunk is hashed with `H` into the corresponding output digest. The field elements are
+	/// serialized in canonical tower encoding, so the digests match those computed by the
+	/// host-side binary Merkle tree construction.
+	///
+	/// This lets the Merkle commitment run on the device where the codeword resides, so that only
+	/// the digests, rather than the whole codeword, need to be copied back to the host.
+	///
+	/// ## Arguments
+	///
+	/// * `data` - the buffer of leaf values, e.g. an interleaved codeword.
+	/// * `digests` - the output buffer for the leaf digests.
+	///
+	/// ## Throws
+	///
+	/// * unless `data.len()` is a non-zero multiple of `digests.len()`
+	fn hash_leaves<H>(
+		&mut self,
+		data: <Self::DevMem as ComputeMemory<F>>::FSlice<'_>,
+		digests: &mut [Output<H::Digest>],
+	) -> Result<(), Error>
+	where
+		F: TowerField,
+		H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>;
+
+	/// Compresses pairs of adjacent digests in a Merkle tree layer into the next layer up.
+	///
+	/// The digest at index `i` of `next_layer` is the compression of the digests at indices
+	/// `2i` and `2i + 1` of `prev_layer`.
+	///
+	/// The default implementation compresses the layer on the host in parallel. Backends that
+	/// keep digests in device memory may override it.
+	///
+	/// ## Throws
+	///
+	/// * unless `prev_layer.len()` equals `2 * next_layer.len()`
+	fn build_merkle_layer<D, C>(
+		&mut self,
+		compression: &C,
+		prev_layer: &[D],
+		next_layer: &mut [D],
+	) -> Result<(), Error>
+	where
+		D: Clone + Send + Sync,
+		C: PseudoCompressionFunction<D, 2> + Sync,
+	{
+		if prev_layer.len() != 2 * next_layer.len() {
+			return Err(Error::InputValidation(format!(
+				"prev_layer length {} must be twice the next_layer length {}",
+				prev_layer.len(),
+				next_layer.len()
+			)));
+		}
+
+		prev_layer
+			.par_chunks_exact(2)
+			.zip(next_layer.par_iter_mut())
+			.for_each(|(prev_pair, next_digest)| {
+				*next_digest = compression.compress([prev_pair[0].clone(), prev_pair[1].clone()]);
+			});
+		Ok(())
+	}
 }
 
 /// An interface for defining execution kernels.
@@ -752,6 +1199,15 @@ where
 	pub hal: &'a HAL,
 	pub host_alloc: HostAllocatorType,
 	pub dev_alloc: DeviceAllocatorType,
+	/// The thread pool that the prover runs its host-side parallel operations on.
+	pub thread_pool: ThreadPoolHandle,
+	/// Whether the prover keeps the committed codewords resident in device memory.
+	///
+	/// In the device-resident mode, the codewords are encoded and hashed into their Merkle trees
+	/// on the device, and stay there for the FRI fold rounds. They are copied to the host only
+	/// for the query openings, instead of being encoded on the host and uploaded for the first
+	/// fold.
+	pub device_resident: bool,
 	_phantom_data: PhantomData<F>,
 }
 
@@ -770,9 +1226,27 @@ where
 			hal,
 			host_alloc,
 			dev_alloc,
+			thread_pool: ThreadPoolHandle::GLOBAL,
+			device_resident: false,
 			_phantom_data: PhantomData::<F>,
 		}
 	}
+
+	/// Runs the host-side parallel operations on the given thread pool instead of the global one.
+	pub fn with_thread_pool(mut self, thread_pool: ThreadPoolHandle) -> Self {
+		self.thread_pool = thread_pool;
+		self
+	}
+
+	/// Keeps the committed codewords resident in device memory across the proving phases.
+	///
+	/// This trades device memory, which must hold every committed codeword until the end of the
+	/// proof, for the host-to-device transfers of the codewords between the commit and the FRI
+	/// fold phases.
+	pub fn with_device_resident(mut self, device_resident: bool) -> Self {
+		self.device_resident = device_resident;
+		self
+	}
 }
 
 #[cfg(test)]
@@ -796,7 +1270,7 @@ mod tests {
 			hal,
 			host_alloc,
 			dev_alloc,
-			_phantom_data,
+			..
 		} = compute_holder.to_data();
 
 		let mut rng = StdRng::seed_from_u64(0);
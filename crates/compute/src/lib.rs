@@ -7,10 +7,19 @@
 //! operations from complex cryptographic and control flow logic required in the prover.
 
 pub mod alloc;
+pub mod capabilities;
 pub mod cpu;
 pub mod layer;
 pub mod memory;
+pub mod multi_device;
+pub mod naive;
 pub mod ops;
+pub mod pinned;
+pub mod profile;
+pub mod stream;
 
+pub use capabilities::DeviceCapabilities;
 pub use layer::*;
 pub use memory::*;
+pub use pinned::PinnedBuffer;
+pub use stream::{Event, StreamId, Transfer, TransferScope, transfer_scope};
@@ -0,0 +1,224 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Execution of compute layer operations across multiple devices.
+//!
+//! A [`MultiDevice`] groups several [`ComputeLayer`] instances, each of which drives a distinct
+//! device. Large operations that decompose into independent pieces, such as per-table commitments
+//! or independent sumcheck instances, are partitioned over the devices with [`partition_work`] and
+//! the per-device results are aggregated on the host.
+
+use std::ops::Range;
+
+use binius_field::Field;
+use binius_maybe_rayon::prelude::*;
+
+use crate::layer::{ComputeLayer, Error};
+
+/// Splits `n_items` units of work into `n_parts` contiguous, balanced ranges.
+///
+/// All range boundaries are multiples of `alignment`, except for the end of the last non-empty
+/// range, which is always `n_items`. The number of `alignment`-sized blocks assigned to any two
+/// parts differs by at most one. Trailing ranges are empty when there are fewer blocks than parts.
+///
+/// ## Preconditions
+///
+/// * `n_parts` must be non-zero
+/// * `alignment` must be non-zero
+pub fn partition_work(n_items: usize, n_parts: usize, alignment: usize) -> Vec<Range<usize>> {
+	assert!(n_parts > 0, "precondition: n_parts must be non-zero");
+	assert!(alignment > 0, "precondition: alignment must be non-zero");
+
+	let n_blocks = n_items.div_ceil(alignment);
+	let blocks_per_part = n_blocks / n_parts;
+	let n_larger_parts = n_blocks % n_parts;
+
+	let mut start = 0;
+	(0..n_parts)
+		.map(|i| {
+			let n_part_blocks = blocks_per_part + usize::from(i < n_larger_parts);
+			let end = (start + n_part_blocks * alignment).min(n_items);
+			let range = start..end;
+			start = end;
+			range
+		})
+		.collect()
+}
+
+/// A group of compute devices that jointly execute partitioned operations.
+///
+/// Each device is driven by its own [`ComputeLayer`] instance. Device memory is not shared
+/// between devices, so an operation running on one device may only access buffers that were
+/// allocated on that same device.
+#[derive(Debug)]
+pub struct MultiDevice<HAL> {
+	devices: Vec<HAL>,
+}
+
+impl<HAL> MultiDevice<HAL> {
+	/// Creates a new device group.
+	///
+	/// ## Throws
+	///
+	/// * if `devices` is empty
+	pub fn new(devices: Vec<HAL>) -> Result<Self, Error> {
+		if devices.is_empty() {
+			return Err(Error::InputValidation(
+				"a device group must contain at least one device".to_string(),
+			));
+		}
+		Ok(Self { devices })
+	}
+
+	/// Returns the number of devices in the group.
+	pub fn n_devices(&self) -> usize {
+		self.devices.len()
+	}
+
+	/// Returns the devices in the group, ordered by device index.
+	pub fn devices(&self) -> &[HAL] {
+		&self.devices
+	}
+
+	/// Returns the device with the given index.
+	pub fn device(&self, index: usize) -> Option<&HAL> {
+		self.devices.get(index)
+	}
+
+	/// Consumes the group, returning the devices.
+	pub fn into_devices(self) -> Vec<HAL> {
+		self.devices
+	}
+
+	/// Returns the work partition that [`Self::map_partitioned`] assigns to the devices.
+	///
+	/// The range at index `i` is assigned to device `i`.
+	pub fn partition(&self, n_items: usize, alignment: usize) -> Vec<Range<usize>> {
+		partition_work(n_items, self.n_devices(), alignment)
+	}
+
+	/// Partitions `n_items` units of work across the devices and runs `op` concurrently on every
+	/// device that receives a non-empty share.
+	///
+	/// The closure receives the device index, the device, and the range of work items assigned to
+	/// the device. The results are returned in order of device index. Devices that are assigned an
+	/// empty range are skipped and do not produce a result.
+	///
+	/// ## Arguments
+	///
+	/// * `n_items` - the total number of work items, e.g. the number of tables to commit.
+	/// * `alignment` - the granularity of the partition, see [`partition_work`].
+	/// * `op` - the operation to run on each device.
+	pub fn map_partitioned<Out: Send>(
+		&self,
+		n_items: usize,
+		alignment: usize,
+		op: impl Sync + Fn(usize, &HAL, Range<usize>) -> Result<Out, Error>,
+	) -> Result<Vec<Out>, Error>
+	where
+		HAL: Sync,
+	{
+		let partition = self.partition(n_items, alignment);
+		self.devices
+			.par_iter()
+			.zip(partition)
+			.enumerate()
+			.filter(|(_, (_, range))| !range.is_empty())
+			.map(|(index, (device, range))| op(index, device, range))
+			.collect::<Result<Vec<_>, _>>()
+	}
+
+	/// Runs a partitioned operation and accumulates the per-device results with field addition.
+	///
+	/// This follows the accumulation semantics of
+	/// [`ComputeLayerExecutor::accumulate_kernels`](crate::ComputeLayerExecutor::accumulate_kernels):
+	/// each device returns a short vector of field elements, and the vectors are summed
+	/// element-wise. If the vectors have different lengths, the missing trailing entries are
+	/// treated as zero.
+	///
+	/// See [`Self::map_partitioned`] for the description of the arguments.
+	pub fn accumulate_partitioned<F: Field>(
+		&self,
+		n_items: usize,
+		alignment: usize,
+		op: impl Sync + Fn(usize, &HAL, Range<usize>) -> Result<Vec<F>, Error>,
+	) -> Result<Vec<F>, Error>
+	where
+		HAL: ComputeLayer<F> + Sync,
+	{
+		let results = self.map_partitioned(n_items, alignment, op)?;
+		let accumulated = results
+			.into_iter()
+			.reduce(|mut acc, result| {
+				let mut result_iter = result.into_iter();
+				for (acc_i, result_i) in std::iter::zip(&mut acc, &mut result_iter) {
+					*acc_i += result_i;
+				}
+				acc.extend(result_iter);
+				acc
+			})
+			.unwrap_or_default();
+		Ok(accumulated)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::iter::repeat_with;
+
+	use assert_matches::assert_matches;
+	use binius_field::{Field, TowerField, util::inner_product_unchecked};
+	use binius_math::B128;
+	use rand::{SeedableRng, prelude::StdRng};
+
+	use super::*;
+	use crate::{ComputeLayerExecutor, cpu::CpuLayer, memory::SubfieldSlice};
+
+	#[test]
+	fn test_partition_work_balanced() {
+		assert_eq!(partition_work(10, 3, 1), vec![0..4, 4..7, 7..10]);
+		assert_eq!(partition_work(64, 4, 16), vec![0..16, 16..32, 32..48, 48..64]);
+		assert_eq!(partition_work(70, 4, 16), vec![0..32, 32..48, 48..64, 64..70]);
+	}
+
+	#[test]
+	fn test_partition_work_fewer_blocks_than_parts() {
+		assert_eq!(partition_work(3, 5, 1), vec![0..1, 1..2, 2..3, 3..3, 3..3]);
+		assert_eq!(partition_work(0, 2, 4), vec![0..0, 0..0]);
+	}
+
+	#[test]
+	fn test_empty_device_group() {
+		assert_matches!(MultiDevice::<CpuLayer<B128>>::new(vec![]), Err(Error::InputValidation(_)));
+	}
+
+	#[test]
+	fn test_accumulate_partitioned_inner_product() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let n = 1 << 8;
+		let a = repeat_with(|| B128::random(&mut rng))
+			.take(n)
+			.collect::<Vec<_>>();
+		let b = repeat_with(|| B128::random(&mut rng))
+			.take(n)
+			.collect::<Vec<_>>();
+
+		let devices = MultiDevice::new(vec![
+			CpuLayer::<B128>::default(),
+			CpuLayer::default(),
+			CpuLayer::default(),
+		])
+		.unwrap();
+
+		let actual = devices
+			.accumulate_partitioned(n, 16, |_, hal, range| {
+				hal.execute(|exec| {
+					let a_in = SubfieldSlice::new(&a[range.clone()], B128::TOWER_LEVEL);
+					Ok(vec![exec.inner_product(a_in, &b[range])?])
+				})
+			})
+			.unwrap();
+
+		let expected = inner_product_unchecked::<B128, B128>(a.iter().copied(), b.iter().copied());
+		assert_eq!(actual, vec![expected]);
+	}
+}
@@ -0,0 +1,816 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{iter, marker::PhantomData};
+
+use binius_field::{BinaryField, ExtensionField, Field, PackedExtension};
+use binius_hash::{HashBuffer, PseudoCompressionFunction, multi_digest::ParallelDigest};
+use binius_math::{ArithCircuit, TowerTop, extrapolate_line_scalar};
+use binius_ntt::{AdditiveNTT, NTTShape};
+use binius_utils::{SerializationMode, SerializeBytes, checked_arithmetics::strict_log_2};
+use bytemuck::zeroed_vec;
+use digest::{Digest, FixedOutputReset, Output, core_api::BlockSizeUser};
+
+use crate::{
+	ComputeData, ComputeHolder, ComputeLayerExecutor,
+	alloc::{BumpAllocator, ComputeAllocator, HostBumpAllocator},
+	cpu::{
+		CpuMemory,
+		layer::{
+			CpuKernelBuilder, count_total_local_buffer_sizes, validate_fold_lengths,
+			validate_fri_fold_lengths, validate_mask_len, validate_ntt_shape,
+			validate_rs_encode_lengths,
+		},
+	},
+	each_tower_subfield,
+	layer::{ComputeLayer, Error, FSlice, FSliceMut, KernelBuffer, KernelMemMap},
+	memory::{ComputeMemory, SizedSlice, SlicesBatch, SubfieldSlice},
+};
+
+/// A compute layer that evaluates every operation from its definition with scalar arithmetic.
+///
+/// See the [module documentation](super) for the intended use.
+#[derive(Debug, Default)]
+pub struct NaiveLayer<F> {
+	_marker: PhantomData<F>,
+}
+
+impl<F: TowerTop> ComputeLayer<F> for NaiveLayer<F> {
+	type Exec<'a> = NaiveLayerExecutor<F>;
+	type DevMem = CpuMemory;
+
+	fn copy_h2d(&self, src: &[F], dst: &mut FSliceMut<'_, F, Self>) -> Result<(), Error> {
+		assert_eq!(
+			src.len(),
+			dst.len(),
+			"precondition: src and dst buffers must have the same length"
+		);
+		for (dst_i, &src_i) in iter::zip(dst.iter_mut(), src) {
+			*dst_i = src_i;
+		}
+		Ok(())
+	}
+
+	fn copy_d2h(&self, src: FSlice<'_, F, Self>, dst: &mut [F]) -> Result<(), Error> {
+		assert_eq!(
+			src.len(),
+			dst.len(),
+			"precondition: src and dst buffers must have the same length"
+		);
+		for (dst_i, &src_i) in iter::zip(dst, src) {
+			*dst_i = src_i;
+		}
+		Ok(())
+	}
+
+	fn copy_d2d(
+		&self,
+		src: FSlice<'_, F, Self>,
+		dst: &mut FSliceMut<'_, F, Self>,
+	) -> Result<(), Error> {
+		assert_eq!(
+			src.len(),
+			dst.len(),
+			"precondition: src and dst buffers must have the same length"
+		);
+		for (dst_i, &src_i) in iter::zip(dst.iter_mut(), src) {
+			*dst_i = src_i;
+		}
+		Ok(())
+	}
+
+	fn execute<'a, 'b>(
+		&'b self,
+		f: impl FnOnce(&mut Self::Exec<'a>) -> Result<Vec<F>, Error>,
+	) -> Result<Vec<F>, Error>
+	where
+		'b: 'a,
+	{
+		f(&mut NaiveLayerExecutor::default())
+	}
+
+	fn compile_expr(
+		&self,
+		expr: &ArithCircuit<F>,
+	) -> Result<<Self::Exec<'_> as ComputeLayerExecutor<F>>::ExprEval, Error> {
+		Ok(expr.clone())
+	}
+
+	fn fill(&self, slice: &mut FSliceMut<'_, F, Self>, value: F) -> Result<(), Error> {
+		for x in slice.iter_mut() {
+			*x = value;
+		}
+		Ok(())
+	}
+
+	fn fill_masked(
+		&self,
+		slice: &mut FSliceMut<'_, F, Self>,
+		value: F,
+		len: usize,
+	) -> Result<(), Error> {
+		validate_mask_len(len, slice.len())?;
+		for (i, x) in slice.iter_mut().enumerate() {
+			*x = if i < len { value } else { F::ZERO };
+		}
+		Ok(())
+	}
+}
+
+#[derive(Debug)]
+pub struct NaiveLayerExecutor<F> {
+	_marker: PhantomData<F>,
+}
+
+impl<F> Default for NaiveLayerExecutor<F> {
+	fn default() -> Self {
+		Self {
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<F: TowerTop> NaiveLayerExecutor<F> {
+	/// Runs a kernel once over the whole extent of its memory mappings.
+	fn run_single_kernel<R>(
+		map: impl for<'a> Fn(
+			&'a mut CpuKernelBuilder,
+			usize,
+			Vec<KernelBuffer<'a, F, CpuMemory>>,
+		) -> Result<R, Error>,
+		mut mem_maps: Vec<KernelMemMap<'_, F, CpuMemory>>,
+	) -> Result<R, Error> {
+		let log_chunks_range = KernelMemMap::log_chunks_range(&mem_maps)
+			.expect("Many variant must have at least one entry");
+		assert_eq!(log_chunks_range.start, 0, "a single chunk must always be a valid split");
+
+		let mut local_buffer = zeroed_vec(count_total_local_buffer_sizes(&mem_maps, 0));
+		let local_buffer_alloc = BumpAllocator::<F, CpuMemory>::new(local_buffer.as_mut_slice());
+		let kernel_data = mem_maps
+			.iter_mut()
+			.map(|mapping| match mapping {
+				KernelMemMap::Chunked { data, .. } => KernelBuffer::Ref(*data),
+				KernelMemMap::ChunkedMut { data, .. } => KernelBuffer::Mut(&mut **data),
+				KernelMemMap::Local { log_size } => KernelBuffer::Mut(
+					local_buffer_alloc
+						.alloc(1 << *log_size)
+						.expect("local buffer is sized to fit all mappings"),
+				),
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(local_buffer_alloc.capacity(), 0, "all local buffer space must be mapped");
+
+		map(&mut CpuKernelBuilder, 0, kernel_data)
+	}
+}
+
+impl<F: TowerTop> ComputeLayerExecutor<F> for NaiveLayerExecutor<F> {
+	type OpValue = F;
+	type ExprEval = ArithCircuit<F>;
+	type KernelExec = CpuKernelBuilder;
+	type DevMem = CpuMemory;
+
+	fn accumulate_kernels(
+		&mut self,
+		map: impl Sync
+		+ for<'a> Fn(
+			&'a mut Self::KernelExec,
+			usize,
+			Vec<KernelBuffer<'a, F, Self::DevMem>>,
+		) -> Result<Vec<F>, Error>,
+		inputs: Vec<KernelMemMap<'_, F, Self::DevMem>>,
+	) -> Result<Vec<Self::OpValue>, Error> {
+		Self::run_single_kernel(map, inputs)
+	}
+
+	fn map_kernels(
+		&mut self,
+		map: impl Sync
+		+ for<'a> Fn(
+			&'a mut Self::KernelExec,
+			usize,
+			Vec<KernelBuffer<'a, F, Self::DevMem>>,
+		) -> Result<(), Error>,
+		mem_maps: Vec<KernelMemMap<'_, F, Self::DevMem>>,
+	) -> Result<(), Error> {
+		Self::run_single_kernel(map, mem_maps)
+	}
+
+	fn inner_product<'a>(
+		&'a mut self,
+		a_in: SubfieldSlice<'_, F, Self::DevMem>,
+		b_in: &'a [F],
+	) -> Result<F, Error> {
+		let a_in = unpack_subfield_slice(a_in)?;
+		if a_in.len() != b_in.len() {
+			return Err(Error::InputValidation(format!(
+				"invalid input: |a|={} |b|={}",
+				a_in.len(),
+				b_in.len()
+			)));
+		}
+
+		let mut acc = F::ZERO;
+		for i in 0..a_in.len() {
+			acc += a_in[i] * b_in[i];
+		}
+		Ok(acc)
+	}
+
+	fn inner_product_masked<'a>(
+		&'a mut self,
+		a_in: SubfieldSlice<'_, F, Self::DevMem>,
+		b_in: &'a [F],
+		len: usize,
+	) -> Result<F, Error> {
+		let a_in = unpack_subfield_slice(a_in)?;
+		if a_in.len() != b_in.len() {
+			return Err(Error::InputValidation(format!(
+				"invalid input: |a|={} |b|={}",
+				a_in.len(),
+				b_in.len()
+			)));
+		}
+		validate_mask_len(len, b_in.len())?;
+
+		let mut acc = F::ZERO;
+		for i in 0..len {
+			acc += a_in[i] * b_in[i];
+		}
+		Ok(acc)
+	}
+
+	fn tensor_expand(
+		&mut self,
+		log_n: usize,
+		coordinates: &[F],
+		data: &mut &mut [F],
+	) -> Result<(), Error> {
+		if data.len() != 1 << (log_n + coordinates.len()) {
+			return Err(Error::InputValidation(format!("invalid data length: {}", data.len())));
+		}
+
+		// The block at index b is the prefix scaled by the equality indicator eq(b, r).
+		let prefix = data[..1 << log_n].to_vec();
+		for b in 0..1 << coordinates.len() {
+			let mut eq_ind = F::ONE;
+			for (k, &r_k) in coordinates.iter().enumerate() {
+				eq_ind *= if (b >> k) & 1 == 1 { r_k } else { F::ONE - r_k };
+			}
+			for (i, &x_i) in prefix.iter().enumerate() {
+				data[(b << log_n) | i] = x_i * eq_ind;
+			}
+		}
+
+		// The equality indicator sums to one over the hypercube, so every prefix value must be
+		// recovered by summing its expansion.
+		for (i, &x_i) in prefix.iter().enumerate() {
+			let sum = (0..1 << coordinates.len())
+				.map(|b| data[(b << log_n) | i])
+				.sum::<F>();
+			assert_eq!(sum, x_i, "tensor expansion of index {i} must sum to the original value");
+		}
+		Ok(())
+	}
+
+	fn fold_left(
+		&mut self,
+		mat: SubfieldSlice<'_, F, Self::DevMem>,
+		vec: &[F],
+		out: &mut &mut [F],
+	) -> Result<(), Error> {
+		let mat = unpack_subfield_slice(mat)?;
+		let num_rows = validate_fold_matrix(mat.len(), vec.len(), out.len())?;
+
+		// The matrix is stored column-major, with one column per element of `vec`.
+		for (i, out_i) in out.iter_mut().enumerate() {
+			let mut acc = F::ZERO;
+			for (j, &vec_j) in vec.iter().enumerate() {
+				acc += vec_j * mat[j * num_rows + i];
+			}
+			*out_i = acc;
+		}
+		Ok(())
+	}
+
+	fn fold_right(
+		&mut self,
+		mat: SubfieldSlice<'_, F, Self::DevMem>,
+		vec: &[F],
+		out: &mut &mut [F],
+	) -> Result<(), Error> {
+		let mat = unpack_subfield_slice(mat)?;
+		validate_fold_matrix(mat.len(), vec.len(), out.len())?;
+
+		// The matrix is stored row-major, with one row per element of `out`.
+		for (i, out_i) in out.iter_mut().enumerate() {
+			let mut acc = F::ZERO;
+			for (j, &vec_j) in vec.iter().enumerate() {
+				acc += vec_j * mat[i * vec.len() + j];
+			}
+			*out_i = acc;
+		}
+		Ok(())
+	}
+
+	fn fri_fold<FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		log_len: usize,
+		log_batch_size: usize,
+		challenges: &[F],
+		data_in: &[F],
+		data_out: &mut &mut [F],
+	) -> Result<(), Error>
+	where
+		FSub: BinaryField,
+		F: ExtensionField<FSub>,
+	{
+		let values = fri_fold_values(ntt, log_len, log_batch_size, challenges, data_in)?;
+		if data_out.len() != values.len() {
+			return Err(Error::InputValidation(format!(
+				"invalid data_out length: {}",
+				data_out.len()
+			)));
+		}
+		for (out_i, value) in iter::zip(data_out.iter_mut(), values) {
+			*out_i = value;
+		}
+		Ok(())
+	}
+
+	fn fri_fold_inplace<'a, FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		log_len: usize,
+		log_batch_size: usize,
+		challenges: &[F],
+		data: <Self::DevMem as ComputeMemory<F>>::FSliceMut<'a>,
+	) -> Result<<Self::DevMem as ComputeMemory<F>>::FSliceMut<'a>, Error>
+	where
+		FSub: BinaryField,
+		F: ExtensionField<FSub>,
+	{
+		let values = fri_fold_values(ntt, log_len, log_batch_size, challenges, data)?;
+		let out = &mut data[..values.len()];
+		out.copy_from_slice(&values);
+		Ok(out)
+	}
+
+	fn forward_ntt<FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+		data: &mut &mut [F],
+	) -> Result<(), Error>
+	where
+		FSub: BinaryField,
+		F: ExtensionField<FSub> + PackedExtension<FSub>,
+	{
+		ntt_butterflies(ntt, data, shape, coset, coset_bits, skip_rounds, false)
+	}
+
+	fn inverse_ntt<FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+		data: &mut &mut [F],
+	) -> Result<(), Error>
+	where
+		FSub: BinaryField,
+		F: ExtensionField<FSub> + PackedExtension<FSub>,
+	{
+		ntt_butterflies(ntt, data, shape, coset, coset_bits, skip_rounds, true)
+	}
+
+	fn rs_encode<FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		log_dim: usize,
+		log_inv_rate: usize,
+		log_batch_size: usize,
+		message: &[F],
+		codeword: &mut &mut [F],
+	) -> Result<(), Error>
+	where
+		FSub: BinaryField,
+		F: ExtensionField<FSub> + PackedExtension<FSub>,
+	{
+		validate_rs_encode_lengths(
+			log_dim,
+			log_inv_rate,
+			log_batch_size,
+			message.len(),
+			codeword.len(),
+		)?;
+
+		// The codeword is the concatenation of the message evaluations on each coset of the
+		// message domain.
+		let shape = NTTShape {
+			log_x: log_batch_size,
+			log_y: log_dim,
+			..Default::default()
+		};
+		for (coset, coset_codeword) in codeword.chunks_exact_mut(message.len()).enumerate() {
+			coset_codeword.copy_from_slice(message);
+			ntt_butterflies(ntt, coset_codeword, shape, coset, log_inv_rate, 0, false)?;
+		}
+		Ok(())
+	}
+
+	fn extrapolate_line(
+		&mut self,
+		evals_0: &mut &mut [F],
+		evals_1: &[F],
+		z: F,
+	) -> Result<(), Error> {
+		if evals_0.len() != evals_1.len() {
+			return Err(Error::InputValidation(
+				"evals_0 and evals_1 must be the same length".into(),
+			));
+		}
+		for i in 0..evals_0.len() {
+			evals_0[i] = evals_0[i] + (evals_1[i] - evals_0[i]) * z;
+		}
+		Ok(())
+	}
+
+	fn fold_low(&mut self, evals: &[F], z: F, out: &mut &mut [F]) -> Result<(), Error> {
+		self.fold_low_masked(evals, evals.len(), z, out)
+	}
+
+	fn fold_high(&mut self, evals: &[F], z: F, out: &mut &mut [F]) -> Result<(), Error> {
+		self.fold_high_masked(evals, evals.len(), z, out)
+	}
+
+	fn fold_low_masked(
+		&mut self,
+		evals: &[F],
+		len: usize,
+		z: F,
+		out: &mut &mut [F],
+	) -> Result<(), Error> {
+		validate_fold_lengths(evals.len(), out.len())?;
+		validate_mask_len(len, evals.len())?;
+		let eval = |i: usize| if i < len { evals[i] } else { F::ZERO };
+		for (i, out_i) in out.iter_mut().enumerate() {
+			let (x0, x1) = (eval(2 * i), eval(2 * i + 1));
+			*out_i = x0 + (x1 - x0) * z;
+		}
+		Ok(())
+	}
+
+	fn fold_high_masked(
+		&mut self,
+		evals: &[F],
+		len: usize,
+		z: F,
+		out: &mut &mut [F],
+	) -> Result<(), Error> {
+		validate_fold_lengths(evals.len(), out.len())?;
+		validate_mask_len(len, evals.len())?;
+		let eval = |i: usize| if i < len { evals[i] } else { F::ZERO };
+		let half = out.len();
+		for (i, out_i) in out.iter_mut().enumerate() {
+			let (x0, x1) = (eval(i), eval(half + i));
+			*out_i = x0 + (x1 - x0) * z;
+		}
+		Ok(())
+	}
+
+	fn compute_composite(
+		&mut self,
+		inputs: &SlicesBatch<&[F]>,
+		output: &mut &mut [F],
+		composition: &Self::ExprEval,
+	) -> Result<(), Error> {
+		if inputs.row_len() != output.len() {
+			return Err(Error::InputValidation("inputs and output must be the same length".into()));
+		}
+		if composition.n_vars() != inputs.n_rows() {
+			return Err(Error::InputValidation("composition not match with input".into()));
+		}
+
+		for (i, out_i) in output.iter_mut().enumerate() {
+			let query = (0..inputs.n_rows())
+				.map(|j| inputs.row(j)[i])
+				.collect::<Vec<_>>();
+			*out_i = composition
+				.evaluate(&query)
+				.expect("query has one value per variable");
+		}
+		Ok(())
+	}
+
+	fn pairwise_product_reduce(
+		&mut self,
+		input: &[F],
+		round_outputs: &mut [&mut [F]],
+	) -> Result<(), Error> {
+		let log_num_inputs = match strict_log_2(input.len()) {
+			Some(log_num_inputs) if log_num_inputs > 0 => log_num_inputs,
+			_ => {
+				return Err(Error::InputValidation(format!(
+					"input length must be a power of 2 greater than 1: {}",
+					input.len()
+				)));
+			}
+		};
+		if round_outputs.len() != log_num_inputs {
+			return Err(Error::InputValidation(format!(
+				"round_outputs.len() does not match the expected length: {} != {log_num_inputs}",
+				round_outputs.len()
+			)));
+		}
+		for (round_idx, round_output) in round_outputs.iter().enumerate() {
+			let expected_len = input.len() >> (round_idx + 1);
+			if round_output.len() != expected_len {
+				return Err(Error::InputValidation(format!(
+					"round_outputs[{round_idx}].len() = {}, expected {expected_len}",
+					round_output.len()
+				)));
+			}
+		}
+
+		let mut prev_round = input.to_vec();
+		for round_output in round_outputs.iter_mut() {
+			for (i, out_i) in round_output.iter_mut().enumerate() {
+				*out_i = prev_round[2 * i] * prev_round[2 * i + 1];
+			}
+			prev_round = round_output.to_vec();
+		}
+
+		assert_eq!(
+			prev_round,
+			[input.iter().product::<F>()],
+			"the final round must hold the product of all inputs"
+		);
+		Ok(())
+	}
+
+	fn hash_leaves<H>(&mut self, data: &[F], digests: &mut [Output<H::Digest>]) -> Result<(), Error>
+	where
+		H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	{
+		if digests.is_empty() || data.is_empty() || data.len() % digests.len() != 0 {
+			return Err(Error::InputValidation(format!(
+				"data length {} must be a non-zero multiple of the number of digests {}",
+				data.len(),
+				digests.len()
+			)));
+		}
+
+		let chunk_len = data.len() / digests.len();
+		for (chunk, digest) in iter::zip(data.chunks_exact(chunk_len), digests) {
+			let mut hasher = H::Digest::new();
+			{
+				let mut buffer = HashBuffer::new(&mut hasher);
+				for elem in chunk {
+					SerializeBytes::serialize(elem, &mut buffer, SerializationMode::CanonicalTower)
+						.expect("hash buffer has unbounded capacity");
+				}
+			}
+			*digest = hasher.finalize();
+		}
+		Ok(())
+	}
+
+	fn build_merkle_layer<D, C>(
+		&mut self,
+		compression: &C,
+		prev_layer: &[D],
+		next_layer: &mut [D],
+	) -> Result<(), Error>
+	where
+		D: Clone + Send + Sync,
+		C: PseudoCompressionFunction<D, 2> + Sync,
+	{
+		if prev_layer.len() != 2 * next_layer.len() {
+			return Err(Error::InputValidation(format!(
+				"prev_layer length {} must be twice the next_layer length {}",
+				prev_layer.len(),
+				next_layer.len()
+			)));
+		}
+
+		for (i, next_digest) in next_layer.iter_mut().enumerate() {
+			*next_digest =
+				compression.compress([prev_layer[2 * i].clone(), prev_layer[2 * i + 1].clone()]);
+		}
+		Ok(())
+	}
+}
+
+/// Expands a slice of packed subfield elements into one extension field element per subfield
+/// scalar.
+fn unpack_subfield_slice<F: TowerTop>(
+	slice: SubfieldSlice<'_, F, CpuMemory>,
+) -> Result<Vec<F>, Error> {
+	fn unpack<FSub: Field, F: ExtensionField<FSub>>(data: &[F]) -> Vec<F> {
+		data.iter()
+			.flat_map(ExtensionField::<FSub>::iter_bases)
+			.map(F::from)
+			.collect()
+	}
+
+	if slice.tower_level > F::TOWER_LEVEL {
+		return Err(Error::InputValidation(format!(
+			"invalid subfield slice: tower_level={} > {}",
+			slice.tower_level,
+			F::TOWER_LEVEL
+		)));
+	}
+	let scalars = each_tower_subfield!(slice.tower_level, unpack::<_, F>(slice.slice));
+	assert_eq!(scalars.len(), slice.slice.len() << (F::TOWER_LEVEL - slice.tower_level));
+	Ok(scalars)
+}
+
+/// Computes the FRI fold of an interleaved codeword one round at a time, as in [DP24], Def. 3.6.
+///
+/// [DP24]: <https://eprint.iacr.org/2024/504>
+fn fri_fold_values<F, FSub>(
+	ntt: &impl AdditiveNTT<FSub>,
+	log_len: usize,
+	log_batch_size: usize,
+	challenges: &[F],
+	data_in: &[F],
+) -> Result<Vec<F>, Error>
+where
+	F: TowerTop + ExtensionField<FSub>,
+	FSub: BinaryField,
+{
+	validate_fri_fold_lengths(log_len, log_batch_size, challenges.len(), data_in.len())?;
+	if log_len > ntt.log_domain_size() {
+		return Err(Error::InputValidation(format!(
+			"log_len {log_len} exceeds the NTT domain size 2^{}",
+			ntt.log_domain_size()
+		)));
+	}
+
+	let (interleave_challenges, fold_challenges) = challenges.split_at(log_batch_size);
+
+	// Fold away the interleaved batch, one variable at a time.
+	let mut values = data_in.to_vec();
+	for &challenge in interleave_challenges {
+		values = (0..values.len() / 2)
+			.map(|i| extrapolate_line_scalar(values[2 * i], values[2 * i + 1], challenge))
+			.collect();
+	}
+
+	// Each FRI fold round applies one layer of the inverse NTT to the whole codeword and then
+	// folds the resulting pair of polynomial halves.
+	let mut log_len = log_len;
+	for &challenge in fold_challenges {
+		assert_eq!(values.len(), 1 << log_len);
+		values = (0..values.len() / 2)
+			.map(|i| {
+				let t = ntt.get_subspace_eval(log_len, i);
+				let (mut u, mut v) = (values[2 * i], values[2 * i + 1]);
+				v += u;
+				u += v * t;
+				extrapolate_line_scalar(u, v, challenge)
+			})
+			.collect();
+		log_len -= 1;
+	}
+
+	Ok(values)
+}
+
+/// Checks the dimensions of a matrix-vector fold and returns the number of output elements.
+fn validate_fold_matrix(mat_len: usize, vec_len: usize, out_len: usize) -> Result<usize, Error> {
+	if !vec_len.is_power_of_two() || !mat_len.is_power_of_two() || vec_len > mat_len {
+		return Err(Error::InputValidation(format!(
+			"matrix length {mat_len} and vector length {vec_len} must be powers of two, with the \
+			 vector no longer than the matrix"
+		)));
+	}
+	if out_len * vec_len != mat_len {
+		return Err(Error::InputValidation(format!(
+			"output has {out_len} elements, expected {}",
+			mat_len / vec_len
+		)));
+	}
+	Ok(out_len)
+}
+
+/// Applies the butterfly network of an additive NTT over a subfield directly to extension field
+/// elements.
+///
+/// The transform is linear over the subfield, so transforming the extension field elements is
+/// equivalent to transforming each of their subfield coordinates. Layer `i` of the network
+/// combines elements at distance `2^(log_x + i)` with the twiddle factors of the `i`-th subspace
+/// polynomial, evaluated on the cosets of the requested coset.
+fn ntt_butterflies<F, FSub>(
+	ntt: &impl AdditiveNTT<FSub>,
+	data: &mut [F],
+	shape: NTTShape,
+	coset: usize,
+	coset_bits: usize,
+	skip_rounds: usize,
+	inverse: bool,
+) -> Result<(), Error>
+where
+	F: ExtensionField<FSub>,
+	FSub: BinaryField,
+{
+	validate_ntt_shape(data.len(), shape)?;
+	let NTTShape {
+		log_x,
+		log_y,
+		log_z,
+	} = shape;
+	if skip_rounds > log_y {
+		return Err(Error::InputValidation(format!(
+			"skip_rounds {skip_rounds} exceeds log_y {log_y}"
+		)));
+	}
+	if coset >> coset_bits != 0 {
+		return Err(Error::InputValidation(format!(
+			"coset {coset} does not fit in {coset_bits} bits"
+		)));
+	}
+	if log_y + coset_bits > ntt.log_domain_size() {
+		return Err(Error::InputValidation(format!(
+			"transform over 2^{} cosets of size 2^{log_y} exceeds the NTT domain size 2^{}",
+			coset_bits,
+			ntt.log_domain_size()
+		)));
+	}
+
+	let butterfly_layer = |data: &mut [F], i: usize| {
+		for j in 0..1 << log_z {
+			for k in 0..1 << (log_y - 1 - i) {
+				let twiddle =
+					ntt.get_subspace_eval(log_y + coset_bits - i, (coset << (log_y - 1 - i)) | k);
+				for l in 0..1 << (log_x + i) {
+					let idx0 = (j << (log_x + log_y)) | (k << (log_x + i + 1)) | l;
+					let idx1 = idx0 | (1 << (log_x + i));
+					assert!(idx1 < data.len());
+					if inverse {
+						data[idx1] += data[idx0];
+						data[idx0] += data[idx1] * twiddle;
+					} else {
+						data[idx0] += data[idx1] * twiddle;
+						data[idx1] += data[idx0];
+					}
+				}
+			}
+		}
+	};
+
+	let layers = 0..log_y - skip_rounds;
+	if inverse {
+		layers.for_each(|i| butterfly_layer(data, i));
+	} else {
+		layers.rev().for_each(|i| butterfly_layer(data, i));
+	}
+	Ok(())
+}
+
+#[derive(Default)]
+pub struct NaiveLayerHolder<F> {
+	layer: NaiveLayer<F>,
+	host_mem: Vec<F>,
+	dev_mem: Vec<F>,
+}
+
+impl<F: TowerTop> NaiveLayerHolder<F> {
+	pub fn new(host_mem_size: usize, dev_mem_size: usize) -> Self {
+		Self {
+			layer: NaiveLayer::default(),
+			host_mem: zeroed_vec(host_mem_size),
+			dev_mem: zeroed_vec(dev_mem_size),
+		}
+	}
+}
+
+impl<F: TowerTop> ComputeHolder<F, NaiveLayer<F>> for NaiveLayerHolder<F> {
+	type HostComputeAllocator<'a> = HostBumpAllocator<'a, F>;
+	type DeviceComputeAllocator<'a> =
+		BumpAllocator<'a, F, <NaiveLayer<F> as ComputeLayer<F>>::DevMem>;
+
+	fn to_data<'a, 'b>(
+		&'a mut self,
+	) -> ComputeData<
+		'a,
+		F,
+		NaiveLayer<F>,
+		Self::HostComputeAllocator<'b>,
+		Self::DeviceComputeAllocator<'b>,
+	>
+	where
+		'a: 'b,
+	{
+		ComputeData::new(
+			&self.layer,
+			BumpAllocator::new(self.host_mem.as_mut_slice()),
+			BumpAllocator::new(self.dev_mem.as_mut_slice()),
+		)
+	}
+}
@@ -0,0 +1,17 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Naïve scalar implementation of a compute layer, for differential testing.
+//!
+//! Every operation is computed directly from its mathematical definition, one scalar at a time,
+//! on a single thread, and with assertions on all intermediate invariants. The implementation
+//! deliberately shares no arithmetic with the optimized routines used by the other backends:
+//! NTTs are evaluated butterfly by butterfly from the subspace twiddles, FRI folds proceed one
+//! round at a time over the whole buffer, and kernels run as a single chunk. This makes it an
+//! independent oracle to compare the [reference](crate::cpu) and optimized layers against, and a
+//! tool to narrow down which operation diverges when a prover built on another backend fails.
+//!
+//! The layer is far too slow for anything but small test instances.
+
+pub mod layer;
+
+pub use layer::{NaiveLayer, NaiveLayerHolder};
@@ -1,11 +1,17 @@
 // Copyright 2025 Irreducible Inc.
 
-use binius_field::TowerField;
+use binius_field::{Field, TowerField};
+use binius_hash::{PseudoCompressionFunction, multi_digest::ParallelDigest};
+use binius_math::ArithCircuit;
+use binius_utils::checked_arithmetics::strict_log_2;
+use digest::{FixedOutputReset, Output, core_api::BlockSizeUser};
+use itertools::izip;
 
 use super::{
 	ComputeLayerExecutor, ComputeMemory,
 	alloc::ComputeAllocator,
-	layer::{ComputeLayer, Error, FSliceMut},
+	layer::{ComputeLayer, Error, FSlice, FSliceMut},
+	memory::{SizedSlice, SlicesBatch, SubfieldSlice},
 };
 
 /// Computes the partial evaluation of the equality indicator polynomial.
@@ -22,6 +28,10 @@ use super::{
 ///
 /// See [DP23], Section 2.1 for more information about the equality indicator polynomial.
 ///
+/// If the output exceeds the maximum kernel length in [`ComputeLayer::capabilities`], it is
+/// computed in several chunks, each of which is a separate tensor expansion on the device. Use
+/// [`eq_ind_partial_eval_batch`] to expand several points at once.
+///
 /// [DP23]: <https://eprint.iacr.org/2023/1784>
 pub fn eq_ind_partial_eval<'a, F, Hal, DeviceAllocatorType>(
 	hal: &Hal,
@@ -33,18 +43,436 @@ where
 	Hal: ComputeLayer<F>,
 	DeviceAllocatorType: ComputeAllocator<F, Hal::DevMem>,
 {
-	let n_vars = point.len();
-	let mut out = dev_alloc.alloc(1 << n_vars)?;
+	let mut outs = eq_ind_partial_eval_batch(hal, dev_alloc, &[point])?;
+	Ok(outs.pop().expect("one output is returned per point"))
+}
+
+/// Computes the partial evaluations of the equality indicator polynomial at several points.
+///
+/// Returns one slice per point, in order, each holding the same values as
+/// [`eq_ind_partial_eval`] would for that point. The points may have different numbers of
+/// coordinates.
+///
+/// All tensor expansions, including the chunks of expansions that exceed the maximum kernel
+/// length, are scheduled in a single [`ComputeLayer::execute`] call, so the device does not
+/// synchronize after every point or chunk.
+pub fn eq_ind_partial_eval_batch<'a, F, Hal, DeviceAllocatorType>(
+	hal: &Hal,
+	dev_alloc: &'a DeviceAllocatorType,
+	points: &[&[F]],
+) -> Result<Vec<FSliceMut<'a, F, Hal>>, Error>
+where
+	F: TowerField,
+	Hal: ComputeLayer<F>,
+	DeviceAllocatorType: ComputeAllocator<F, Hal::DevMem>,
+{
+	scaled_eq_ind_partial_eval_batch(hal, dev_alloc, points, &vec![F::ONE; points.len()])
+}
+
+/// Computes the partial evaluations of the equality indicator polynomial at several points, each
+/// multiplied by a scalar.
+///
+/// This is [`eq_ind_partial_eval_batch`], with the values for the $i$-th point multiplied by
+/// `scales[i]`.
+///
+/// ## Throws
+///
+/// * if the number of scales does not equal the number of points
+pub fn scaled_eq_ind_partial_eval_batch<'a, F, Hal, DeviceAllocatorType>(
+	hal: &Hal,
+	dev_alloc: &'a DeviceAllocatorType,
+	points: &[&[F]],
+	scales: &[F],
+) -> Result<Vec<FSliceMut<'a, F, Hal>>, Error>
+where
+	F: TowerField,
+	Hal: ComputeLayer<F>,
+	DeviceAllocatorType: ComputeAllocator<F, Hal::DevMem>,
+{
+	if scales.len() != points.len() {
+		return Err(Error::InputValidation(format!(
+			"{} scales were given for {} points",
+			scales.len(),
+			points.len()
+		)));
+	}
+
+	let mut outs = points
+		.iter()
+		.map(|point| dev_alloc.alloc(1 << point.len()))
+		.collect::<Result<Vec<_>, _>>()?;
 
-	{
-		let mut dev_val = Hal::DevMem::slice_power_of_two_mut(&mut out, 1);
-		hal.fill(&mut dev_val, F::ONE)?;
+	let mut chunks = Vec::new();
+	for (out, point, &scale) in izip!(&mut outs, points, scales) {
+		// Every chunk is the expansion of the low coordinates, scaled by the equality indicator
+		// of the high coordinates evaluated at the chunk index.
+		let log_chunk_len = hal.capabilities().max_log_chunk_len(point.len());
+		let (low_coords, high_coords) = point.split_at(log_chunk_len);
+		let chunk_scales = binius_math::eq_ind_partial_eval::<F>(high_coords);
+		let out_chunks =
+			Hal::DevMem::slice_chunks_mut(Hal::DevMem::to_owned_mut(out), 1 << log_chunk_len);
+		for (mut chunk, chunk_scale) in out_chunks.zip(chunk_scales) {
+			{
+				let mut dev_val = Hal::DevMem::slice_power_of_two_mut(&mut chunk, 1);
+				hal.fill(&mut dev_val, scale * chunk_scale)?;
+			}
+			chunks.push((chunk, low_coords));
+		}
 	}
 
 	hal.execute(|exec| {
-		exec.tensor_expand(0, point, &mut out)?;
+		exec.map(chunks.into_iter(), |exec, (mut chunk, coordinates)| {
+			exec.tensor_expand(0, coordinates, &mut chunk)
+		})?;
 		Ok(vec![])
 	})?;
 
+	Ok(outs)
+}
+
+/// Builds a binary Merkle tree over the chunks of a device buffer.
+///
+/// The buffer is split into `2^log_len` equal-sized chunks, each of which is hashed into a leaf
+/// digest on the device with [`ComputeLayerExecutor::hash_leaves`]. The upper layers are then
+/// computed with [`ComputeLayerExecutor::build_merkle_layer`].
+///
+/// Returns the tree nodes as a flattened array of layers, starting with the leaf digests and
+/// ending with the root. This is the same layout that the host-side binary Merkle tree uses for
+/// its inner nodes.
+pub fn build_merkle_tree<F, Hal, H, C>(
+	hal: &Hal,
+	data: FSlice<'_, F, Hal>,
+	log_len: usize,
+	compression: &C,
+) -> Result<Vec<Output<H::Digest>>, Error>
+where
+	F: TowerField,
+	Hal: ComputeLayer<F>,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
+{
+	let mut nodes = vec![Output::<H::Digest>::default(); (1 << (log_len + 1)) - 1];
+
+	hal.execute(|exec| {
+		let (mut prev_layer, mut remaining) = nodes.split_at_mut(1 << log_len);
+		exec.hash_leaves::<H>(data, prev_layer)?;
+		for i in 1..=log_len {
+			let (next_layer, next_remaining) = remaining.split_at_mut(1 << (log_len - i));
+			exec.build_merkle_layer(compression, prev_layer, next_layer)?;
+			prev_layer = next_layer;
+			remaining = next_remaining;
+		}
+		Ok(vec![])
+	})?;
+
+	Ok(nodes)
+}
+
+/// Applies a composition expression elementwise to a batch of device slices.
+///
+/// Given input slices $P_0, \ldots, P_{m-1}$ and an $m$-variate composition $C$, this writes
+/// $C(P_0\[i\], \ldots, P_{m-1}\[i\])$ into `output[i]` for every index $i$. The expression is
+/// compiled for the device with [`ComputeLayer::compile_expr`] on every call; callers that apply
+/// the same expression many times should compile it once and use
+/// [`ComputeLayerExecutor::compute_composite`] directly. Slices that exceed the maximum kernel
+/// length in [`ComputeLayer::capabilities`] are processed in several chunks.
+///
+/// ## Throws
+///
+/// * if the number of inputs does not equal the number of variables in `composition`
+/// * if the inputs and the output do not all have the same power-of-two length
+pub fn map_elementwise<F, Hal>(
+	hal: &Hal,
+	inputs: &[FSlice<'_, F, Hal>],
+	output: &mut FSliceMut<'_, F, Hal>,
+	composition: &ArithCircuit<F>,
+) -> Result<(), Error>
+where
+	F: TowerField,
+	Hal: ComputeLayer<F>,
+{
+	if composition.n_vars() != inputs.len() {
+		return Err(Error::InputValidation(format!(
+			"composition has {} variables but {} inputs were given",
+			composition.n_vars(),
+			inputs.len()
+		)));
+	}
+	if inputs.iter().any(|input| input.len() != output.len()) {
+		return Err(Error::InputValidation(
+			"inputs and output must have the same length".to_string(),
+		));
+	}
+	if !output.len().is_power_of_two() {
+		return Err(Error::InputValidation(format!(
+			"slice length {} must be a power of two",
+			output.len()
+		)));
+	}
+
+	let composition = hal.compile_expr(composition)?;
+	let log_len = output.len().ilog2() as usize;
+	let chunk_len = 1 << hal.capabilities().max_log_chunk_len(log_len);
+	if chunk_len == output.len() {
+		let inputs = SlicesBatch::new(inputs.to_vec(), output.len());
+		hal.execute(|exec| {
+			exec.compute_composite(&inputs, output, &composition)?;
+			Ok(vec![])
+		})?;
+		return Ok(());
+	}
+
+	let mut input_chunks = inputs
+		.iter()
+		.map(|&input| Hal::DevMem::slice_chunks(input, chunk_len))
+		.collect::<Vec<_>>();
+	let output_chunks = Hal::DevMem::slice_chunks_mut(Hal::DevMem::to_owned_mut(output), chunk_len);
+	for mut output_chunk in output_chunks {
+		let inputs = input_chunks
+			.iter_mut()
+			.map(|chunks| {
+				chunks
+					.next()
+					.expect("inputs and output have the same length")
+			})
+			.collect();
+		let inputs = SlicesBatch::new(inputs, chunk_len);
+		hal.execute(|exec| {
+			exec.compute_composite(&inputs, &mut output_chunk, &composition)?;
+			Ok(vec![])
+		})?;
+	}
+	Ok(())
+}
+
+/// Folds the two halves of a device slice together with a bivariate composition expression.
+///
+/// For an input of length $2^n$ and a composition $C(X_0, X_1)$, this returns a newly allocated
+/// slice of length $2^{n-1}$ with entries
+///
+/// $$
+/// C(\mathrm{input}\[i\], \mathrm{input}\[i + 2^{n-1}\]).
+/// $$
+///
+/// Applying the fold $n$ times reduces the input to a single element. When $C$ is an associative
+/// and commutative operation, such as field addition or multiplication, the result is the
+/// reduction of the whole input under that operation.
+///
+/// ## Throws
+///
+/// * if `composition` does not have exactly two variables
+/// * if the input length is not a power of two greater than one
+pub fn pairwise_fold<'a, F, Hal, DeviceAllocatorType>(
+	hal: &Hal,
+	dev_alloc: &'a DeviceAllocatorType,
+	input: FSlice<'_, F, Hal>,
+	composition: &ArithCircuit<F>,
+) -> Result<FSliceMut<'a, F, Hal>, Error>
+where
+	F: TowerField,
+	Hal: ComputeLayer<F>,
+	DeviceAllocatorType: ComputeAllocator<F, Hal::DevMem>,
+{
+	if composition.n_vars() != 2 {
+		return Err(Error::InputValidation(format!(
+			"pairwise fold requires a bivariate composition, got {} variables",
+			composition.n_vars()
+		)));
+	}
+	if !matches!(strict_log_2(input.len()), Some(log_len) if log_len > 0) {
+		return Err(Error::InputValidation(format!(
+			"input length {} must be a power of two greater than one",
+			input.len()
+		)));
+	}
+
+	let halves = Hal::DevMem::split_half(input);
+	let mut out = dev_alloc.alloc(halves.0.len())?;
+	map_elementwise(hal, &<[_; 2]>::from(halves), &mut out, composition)?;
+	Ok(out)
+}
+
+/// Sums each contiguous segment of a device slice.
+///
+/// The input is split into segments of length $2^k$, where $k$ is `log_segment_len`, and the
+/// returned slice holds the sum of the elements of each segment, in order. The reduction runs on
+/// the device as a right fold of the input against a vector of ones.
+///
+/// ## Throws
+///
+/// * if the input length is not a power of two
+/// * if the segment length exceeds the input length
+pub fn segmented_sum<'a, F, Hal, DeviceAllocatorType>(
+	hal: &Hal,
+	dev_alloc: &'a DeviceAllocatorType,
+	input: FSlice<'_, F, Hal>,
+	log_segment_len: usize,
+) -> Result<FSliceMut<'a, F, Hal>, Error>
+where
+	F: TowerField,
+	Hal: ComputeLayer<F>,
+	DeviceAllocatorType: ComputeAllocator<F, Hal::DevMem>,
+{
+	let Some(log_len) = strict_log_2(input.len()) else {
+		return Err(Error::InputValidation(format!(
+			"input length {} must be a power of two",
+			input.len()
+		)));
+	};
+	if log_segment_len > log_len {
+		return Err(Error::InputValidation(format!(
+			"segment length 2^{log_segment_len} exceeds the input length 2^{log_len}"
+		)));
+	}
+
+	let mut ones = dev_alloc.alloc(1 << log_segment_len)?;
+	hal.fill(&mut ones, F::ONE)?;
+	let mut out = dev_alloc.alloc(1 << (log_len - log_segment_len))?;
+	hal.execute(|exec| {
+		exec.fold_right(
+			SubfieldSlice::new(input, F::TOWER_LEVEL),
+			Hal::DevMem::as_const(&ones),
+			&mut out,
+		)?;
+		Ok(vec![])
+	})?;
 	Ok(out)
 }
+
+/// Computes the inclusive scan of a device slice in place with a bivariate composition.
+///
+/// After the call, `data[0]` is unchanged and `data[i] = C(data[i - 1], x_i)` for $i > 0$, where
+/// $x_i$ is the original value and $C(X_0, X_1)$ is `composition`. For $C = X_0 + X_1$ this is the
+/// prefix sum, and for $C = X_0 X_1$ the prefix product.
+///
+/// The scan is inherently sequential and the [`ComputeLayerExecutor`] interface has no scan
+/// primitive, so the data is staged through host memory. Backends that add a native scan should
+/// be preferred for large inputs.
+///
+/// ## Throws
+///
+/// * if `composition` does not have exactly two variables
+pub fn scan<F, Hal>(
+	hal: &Hal,
+	data: &mut FSliceMut<'_, F, Hal>,
+	composition: &ArithCircuit<F>,
+) -> Result<(), Error>
+where
+	F: Field,
+	Hal: ComputeLayer<F>,
+{
+	if composition.n_vars() != 2 {
+		return Err(Error::InputValidation(format!(
+			"scan requires a bivariate composition, got {} variables",
+			composition.n_vars()
+		)));
+	}
+
+	let mut host = vec![F::ZERO; data.len()];
+	hal.copy_d2h(Hal::DevMem::as_const(data), &mut host)?;
+	for i in 1..host.len() {
+		host[i] = composition
+			.evaluate(&[host[i - 1], host[i]])
+			.map_err(|err| Error::CoreLibError(Box::new(err)))?;
+	}
+	hal.copy_h2d(&host, data)?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::iter::{self, repeat_with};
+
+	use binius_math::B128;
+	use rand::{Rng, SeedableRng, prelude::StdRng};
+
+	use super::*;
+	use crate::{DeviceCapabilities, alloc::BumpAllocator, cpu::CpuLayer};
+
+	fn constrained_cpu_layer(max_kernel_len: usize) -> CpuLayer<B128> {
+		CpuLayer::default().with_capabilities(DeviceCapabilities {
+			max_kernel_len,
+			..DeviceCapabilities::default()
+		})
+	}
+
+	#[test]
+	fn test_eq_ind_partial_eval_chunked() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let point = repeat_with(|| B128::random(&mut rng))
+			.take(7)
+			.collect::<Vec<_>>();
+		let expected = binius_math::eq_ind_partial_eval::<B128>(&point);
+
+		for max_kernel_len in [1, 10, 16, 1 << 7, usize::MAX] {
+			let hal = constrained_cpu_layer(max_kernel_len);
+			let mut dev_mem = vec![B128::ZERO; 1 << point.len()];
+			let dev_alloc = BumpAllocator::<B128, _>::new(dev_mem.as_mut_slice());
+			let out = eq_ind_partial_eval(&hal, &dev_alloc, &point).unwrap();
+			assert_eq!(out, expected.as_slice());
+		}
+	}
+
+	#[test]
+	fn test_eq_ind_partial_eval_batch() {
+		let mut rng = StdRng::seed_from_u64(0);
+		for _ in 0..8 {
+			let n_points = rng.random_range(1..6);
+			let points = repeat_with(|| {
+				let n_vars = rng.random_range(0..8);
+				repeat_with(|| B128::random(&mut rng))
+					.take(n_vars)
+					.collect::<Vec<_>>()
+			})
+			.take(n_points)
+			.collect::<Vec<_>>();
+			let points = points.iter().map(Vec::as_slice).collect::<Vec<_>>();
+			let scales = repeat_with(|| B128::random(&mut rng))
+				.take(n_points)
+				.collect::<Vec<_>>();
+
+			for max_kernel_len in [1, 10, 16, 1 << 7, usize::MAX] {
+				let hal = constrained_cpu_layer(max_kernel_len);
+				let mut dev_mem = vec![B128::ZERO; points.len() << 8];
+				let dev_alloc = BumpAllocator::<B128, _>::new(dev_mem.as_mut_slice());
+				let outs = eq_ind_partial_eval_batch(&hal, &dev_alloc, &points).unwrap();
+				assert_eq!(outs.len(), points.len());
+				for (out, point) in iter::zip(outs, &points) {
+					assert_eq!(out, binius_math::eq_ind_partial_eval::<B128>(point).as_slice());
+				}
+
+				let mut dev_mem = vec![B128::ZERO; points.len() << 8];
+				let dev_alloc = BumpAllocator::<B128, _>::new(dev_mem.as_mut_slice());
+				let outs =
+					scaled_eq_ind_partial_eval_batch(&hal, &dev_alloc, &points, &scales).unwrap();
+				for (out, point, &scale) in izip!(outs, &points, &scales) {
+					let expected = binius_math::eq_ind_partial_eval::<B128>(point)
+						.into_iter()
+						.map(|val| scale * val)
+						.collect::<Vec<_>>();
+					assert_eq!(out, expected.as_slice());
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_map_elementwise_chunked() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let a = repeat_with(|| B128::random(&mut rng))
+			.take(1 << 6)
+			.collect::<Vec<_>>();
+		let b = repeat_with(|| B128::random(&mut rng))
+			.take(1 << 6)
+			.collect::<Vec<_>>();
+		let composition = ArithCircuit::var(0) * ArithCircuit::var(1) + ArithCircuit::var(1);
+
+		let hal = constrained_cpu_layer(8);
+		let mut out = vec![B128::ZERO; a.len()];
+		map_elementwise(&hal, &[a.as_slice(), b.as_slice()], &mut out.as_mut_slice(), &composition)
+			.unwrap();
+		for ((&out_i, &a_i), &b_i) in out.iter().zip(&a).zip(&b) {
+			assert_eq!(out_i, a_i * b_i + b_i);
+		}
+	}
+}
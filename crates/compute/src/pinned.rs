@@ -0,0 +1,117 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Page-locked host memory for staging transfers between the host and a compute device.
+//!
+//! Devices attached over PCIe can only reach full transfer bandwidth when copying from and to
+//! page-locked (pinned) host memory, since pageable memory must first be copied into a pinned
+//! bounce buffer by the driver. [`PinnedBuffer`] is the host buffer type returned by
+//! [`ComputeLayer::alloc_pinned_host`](crate::ComputeLayer::alloc_pinned_host). Compute layers
+//! without such a constraint, like the CPU layer, return ordinary heap memory.
+
+use std::{
+	fmt::{self, Debug},
+	ops::{Deref, DerefMut},
+};
+
+use binius_field::Field;
+
+/// A callback that releases the page lock on a buffer before it is freed.
+pub type UnpinFn<F> = Box<dyn FnOnce(&mut [F]) + Send + Sync>;
+
+/// A host memory buffer that may be page-locked for fast device transfers.
+///
+/// The buffer dereferences to a slice of field elements, so it can be passed anywhere a host
+/// slice is expected, e.g. to [`ComputeLayer::copy_h2d`](crate::ComputeLayer::copy_h2d) or to a
+/// host allocator.
+pub struct PinnedBuffer<F> {
+	data: Box<[F]>,
+	unpin: Option<UnpinFn<F>>,
+}
+
+impl<F: Field> PinnedBuffer<F> {
+	/// Allocates a zero-initialized buffer in ordinary, pageable memory.
+	pub fn unpinned(len: usize) -> Self {
+		Self {
+			data: vec![F::ZERO; len].into_boxed_slice(),
+			unpin: None,
+		}
+	}
+}
+
+impl<F> PinnedBuffer<F> {
+	/// Wraps a buffer that a compute layer has page-locked.
+	///
+	/// The `unpin` callback is invoked with the buffer contents right before the buffer is freed,
+	/// and must release the page lock.
+	pub fn pinned(data: Box<[F]>, unpin: UnpinFn<F>) -> Self {
+		Self {
+			data,
+			unpin: Some(unpin),
+		}
+	}
+
+	/// Returns whether the buffer is page-locked.
+	pub fn is_pinned(&self) -> bool {
+		self.unpin.is_some()
+	}
+}
+
+impl<F> Deref for PinnedBuffer<F> {
+	type Target = [F];
+
+	fn deref(&self) -> &[F] {
+		&self.data
+	}
+}
+
+impl<F> DerefMut for PinnedBuffer<F> {
+	fn deref_mut(&mut self) -> &mut [F] {
+		&mut self.data
+	}
+}
+
+impl<F> Drop for PinnedBuffer<F> {
+	fn drop(&mut self) {
+		if let Some(unpin) = self.unpin.take() {
+			unpin(&mut self.data);
+		}
+	}
+}
+
+impl<F> Debug for PinnedBuffer<F> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PinnedBuffer")
+			.field("len", &self.data.len())
+			.field("is_pinned", &self.is_pinned())
+			.finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+	};
+
+	use binius_math::B128;
+
+	use super::*;
+
+	#[test]
+	fn test_unpin_on_drop() {
+		let unpinned = Arc::new(AtomicBool::new(false));
+		let buffer = PinnedBuffer::<B128>::pinned(vec![B128::ZERO; 4].into_boxed_slice(), {
+			let unpinned = unpinned.clone();
+			Box::new(move |data| {
+				assert_eq!(data.len(), 4);
+				unpinned.store(true, Ordering::SeqCst);
+			})
+		});
+		assert!(buffer.is_pinned());
+		assert!(!unpinned.load(Ordering::SeqCst));
+
+		drop(buffer);
+		assert!(unpinned.load(Ordering::SeqCst));
+	}
+}
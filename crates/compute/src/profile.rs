@@ -0,0 +1,198 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Per-operation profiling hooks for compute layer implementations.
+//!
+//! Compute layers report every operation they execute to an optional, user-supplied
+//! [`OpProfiler`]. Each report carries the operation name, the number of field elements it
+//! processed, an estimate of the bytes it moved through device memory, and the elapsed wall-clock
+//! time. Independently of whether a profiler is installed, every operation runs inside a
+//! `compute_op` tracing span, so operations also show up in the existing tracing output.
+//!
+//! [`OpStatsProfiler`] is a ready-made profiler that aggregates the reports by operation name.
+//!
+//! On targets without a clock, such as `wasm32-unknown-unknown`, operations are reported with a
+//! zero duration.
+
+use std::{
+	collections::BTreeMap,
+	fmt::{self, Debug},
+	sync::{Arc, Mutex},
+	time::{Duration, Instant},
+};
+
+/// Description of a single compute layer operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpInfo {
+	/// The operation name, e.g. `"inner_product"`.
+	pub name: &'static str,
+	/// The number of field elements the operation read and wrote.
+	pub n_elements: usize,
+	/// The estimated number of bytes read from and written to device memory.
+	pub bytes_moved: usize,
+}
+
+/// A user-supplied sink for per-operation profiling reports.
+///
+/// Implementations may be called concurrently from multiple threads.
+pub trait OpProfiler: Send + Sync {
+	/// Called before an operation starts.
+	fn op_started(&self, _info: &OpInfo) {}
+
+	/// Called after an operation finishes, with the elapsed wall-clock time.
+	fn op_finished(&self, info: &OpInfo, duration: Duration);
+}
+
+/// The profiling hook held by a compute layer.
+///
+/// The hook is cheap to clone and is empty by default, in which case operations only open their
+/// tracing spans and no timing is performed.
+#[derive(Clone, Default)]
+pub struct ProfilerHook(Option<Arc<dyn OpProfiler>>);
+
+impl ProfilerHook {
+	/// Creates a hook that reports operations to the given profiler.
+	pub fn new(profiler: Arc<dyn OpProfiler>) -> Self {
+		Self(Some(profiler))
+	}
+
+	/// Returns whether a profiler is installed.
+	pub fn is_enabled(&self) -> bool {
+		self.0.is_some()
+	}
+
+	/// Starts profiling an operation that reads and writes `n_elements` values of type `T`.
+	///
+	/// The number of bytes moved is estimated from the size of `T`.
+	pub fn field_op<T>(&self, name: &'static str, n_elements: usize) -> OpScope {
+		self.op(name, n_elements, n_elements * size_of::<T>())
+	}
+
+	/// Starts profiling an operation.
+	///
+	/// The operation is considered finished when the returned scope is dropped.
+	pub fn op(&self, name: &'static str, n_elements: usize, bytes_moved: usize) -> OpScope {
+		let info = OpInfo {
+			name,
+			n_elements,
+			bytes_moved,
+		};
+		let span = tracing::debug_span!("compute_op", op = name, n_elements, bytes_moved).entered();
+		let profiling = self.0.clone().map(|profiler| {
+			profiler.op_started(&info);
+			(profiler, binius_utils::time::now())
+		});
+		OpScope {
+			info,
+			profiling,
+			_span: span,
+		}
+	}
+}
+
+impl Debug for ProfilerHook {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("ProfilerHook")
+			.field(&self.is_enabled())
+			.finish()
+	}
+}
+
+/// A running operation, which is reported to the profiler when dropped.
+#[must_use = "the operation is reported as finished when the scope is dropped"]
+pub struct OpScope {
+	info: OpInfo,
+	profiling: Option<(Arc<dyn OpProfiler>, Option<Instant>)>,
+	_span: tracing::span::EnteredSpan,
+}
+
+impl Drop for OpScope {
+	fn drop(&mut self) {
+		if let Some((profiler, start)) = self.profiling.take() {
+			let duration = start.map_or(Duration::ZERO, |start| start.elapsed());
+			profiler.op_finished(&self.info, duration);
+		}
+	}
+}
+
+/// Aggregated statistics for all executions of one operation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OpStats {
+	/// The number of times the operation executed.
+	pub count: usize,
+	/// The total number of field elements read and written.
+	pub n_elements: usize,
+	/// The total estimated number of bytes moved.
+	pub bytes_moved: usize,
+	/// The total elapsed wall-clock time.
+	pub duration: Duration,
+}
+
+/// A profiler that aggregates operation reports by operation name.
+#[derive(Debug, Default)]
+pub struct OpStatsProfiler {
+	stats: Mutex<BTreeMap<&'static str, OpStats>>,
+}
+
+impl OpStatsProfiler {
+	/// Returns the aggregated statistics, keyed and ordered by operation name.
+	pub fn stats(&self) -> BTreeMap<&'static str, OpStats> {
+		self.stats.lock().expect("mutex is never poisoned").clone()
+	}
+
+	/// Clears all collected statistics.
+	pub fn reset(&self) {
+		self.stats.lock().expect("mutex is never poisoned").clear();
+	}
+}
+
+impl OpProfiler for OpStatsProfiler {
+	fn op_finished(&self, info: &OpInfo, duration: Duration) {
+		let mut stats = self.stats.lock().expect("mutex is never poisoned");
+		let entry = stats.entry(info.name).or_default();
+		entry.count += 1;
+		entry.n_elements += info.n_elements;
+		entry.bytes_moved += info.bytes_moved;
+		entry.duration += duration;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_field::{Field, TowerField};
+	use binius_math::B128;
+
+	use super::*;
+	use crate::{ComputeLayer, ComputeLayerExecutor, cpu::CpuLayer, memory::SubfieldSlice};
+
+	#[test]
+	fn test_cpu_layer_reports_ops() {
+		let profiler = Arc::new(OpStatsProfiler::default());
+		let hal = CpuLayer::<B128>::with_profiler(ProfilerHook::new(profiler.clone()));
+
+		let a = vec![B128::ONE; 16];
+		let mut b = vec![B128::ZERO; 16];
+		hal.copy_h2d(&a, &mut b.as_mut_slice()).unwrap();
+		hal.execute(|exec| {
+			let a_in = SubfieldSlice::new(a.as_slice(), B128::TOWER_LEVEL);
+			Ok(vec![exec.inner_product(a_in, b.as_slice())?])
+		})
+		.unwrap();
+
+		let stats = profiler.stats();
+		assert_eq!(stats["copy_h2d"].count, 1);
+		assert_eq!(stats["copy_h2d"].n_elements, 32);
+		assert_eq!(stats["copy_h2d"].bytes_moved, 32 * size_of::<B128>());
+		assert_eq!(stats["inner_product"].count, 1);
+		assert_eq!(stats["inner_product"].n_elements, 32);
+
+		profiler.reset();
+		assert!(profiler.stats().is_empty());
+	}
+
+	#[test]
+	fn test_disabled_hook() {
+		let hook = ProfilerHook::default();
+		assert!(!hook.is_enabled());
+		drop(hook.op("noop", 0, 0));
+	}
+}
@@ -0,0 +1,486 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Execution streams and completion events for asynchronous compute operations.
+//!
+//! A compute device may expose several _streams_, which are independent queues of operations.
+//! Operations submitted to the same stream execute in submission order, while operations on
+//! different streams may overlap with each other and with host-side work. Submitting an operation
+//! returns an [`Event`], which is a handle to the operation's eventual result.
+//!
+//! [`Event`] can be waited on synchronously with [`Event::wait`] or awaited as a [`Future`] by
+//! callers that drive an async executor.
+//!
+//! Asynchronous copies between host and device memory borrow their buffers while they are in
+//! flight. They are submitted within a [`transfer_scope`], which waits for all of them before it
+//! returns, in the way that [`std::thread::scope`] joins its threads.
+
+use std::{
+	fmt::{self, Debug},
+	future::Future,
+	marker::PhantomData,
+	mem,
+	pin::Pin,
+	sync::{Arc, Condvar, Mutex},
+	task::{Context, Poll, Waker},
+};
+
+use crate::layer::Error;
+
+/// Identifier of an execution stream on a compute device.
+///
+/// Valid stream identifiers range from 0 up to, but not including,
+/// [`ComputeLayer::n_streams`](crate::ComputeLayer::n_streams).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StreamId(pub usize);
+
+impl StreamId {
+	/// The default stream, which every compute layer supports.
+	pub const DEFAULT: Self = Self(0);
+}
+
+/// A handle to the result of an operation submitted to an execution stream.
+///
+/// The result is produced exactly once, either when the event is created in a completed state
+/// with [`Event::ready`] or when the paired [`EventSender`] calls [`EventSender::complete`].
+pub struct Event<T> {
+	state: Arc<EventState<T>>,
+}
+
+/// The producer side of an [`Event`], used by compute layer implementations to signal
+/// completion.
+///
+/// Dropping the sender without calling [`EventSender::complete`] completes the event with an
+/// error.
+pub struct EventSender<T> {
+	state: Arc<EventState<T>>,
+}
+
+struct EventState<T> {
+	slot: Mutex<EventSlot<T>>,
+	cond: Condvar,
+}
+
+struct EventSlot<T> {
+	result: Option<Result<T, Error>>,
+	/// Whether the result was produced, which stays set after the result is taken.
+	completed: bool,
+	waker: Option<Waker>,
+}
+
+impl<T> EventSlot<T> {
+	/// Takes the result of a completed operation, which can be retrieved only once.
+	fn take_result(&mut self) -> Result<T, Error> {
+		debug_assert!(self.completed);
+		self.result.take().unwrap_or_else(|| {
+			Err(Error::InputValidation("the event result was already retrieved".into()))
+		})
+	}
+}
+
+impl<T> Event<T> {
+	/// Creates a pending event along with the sender that completes it.
+	pub fn pending() -> (Self, EventSender<T>) {
+		let state = Arc::new(EventState {
+			slot: Mutex::new(EventSlot {
+				result: None,
+				completed: false,
+				waker: None,
+			}),
+			cond: Condvar::new(),
+		});
+		let event = Self {
+			state: state.clone(),
+		};
+		(event, EventSender { state })
+	}
+
+	/// Creates an event that has already completed with the given result.
+	pub fn ready(result: Result<T, Error>) -> Self {
+		let (event, sender) = Self::pending();
+		sender.complete(result);
+		event
+	}
+
+	/// Returns whether the operation has completed.
+	pub fn is_complete(&self) -> bool {
+		self.state
+			.slot
+			.lock()
+			.expect("mutex is never poisoned")
+			.completed
+	}
+
+	/// Blocks the current thread until the operation completes and returns its result.
+	///
+	/// Returns immediately if the operation has already completed. If the result was already
+	/// retrieved by polling the event as a [`Future`], this returns an error instead of blocking.
+	pub fn wait(self) -> Result<T, Error> {
+		let mut slot = self.state.slot.lock().expect("mutex is never poisoned");
+		while !slot.completed {
+			slot = self.state.cond.wait(slot).expect("mutex is never poisoned");
+		}
+		slot.take_result()
+	}
+
+	/// Waits for all events in order and collects their results.
+	///
+	/// Returns the first error encountered. All events are waited on even if an earlier event
+	/// resolves to an error, so that no operation is still running when this returns.
+	pub fn wait_all(events: impl IntoIterator<Item = Self>) -> Result<Vec<T>, Error> {
+		let results = events.into_iter().map(Self::wait).collect::<Vec<_>>();
+		results.into_iter().collect()
+	}
+}
+
+impl<T> EventSender<T> {
+	/// Completes the paired event with the given result, waking up any waiters.
+	pub fn complete(self, result: Result<T, Error>) {
+		self.set(result);
+	}
+
+	fn set(&self, result: Result<T, Error>) {
+		let mut slot = self.state.slot.lock().expect("mutex is never poisoned");
+		if slot.completed {
+			return;
+		}
+		slot.result = Some(result);
+		slot.completed = true;
+		if let Some(waker) = slot.waker.take() {
+			waker.wake();
+		}
+		self.state.cond.notify_all();
+	}
+}
+
+impl<T> Drop for EventSender<T> {
+	/// Completes the paired event with an error if the sender is dropped without completing it,
+	/// so that waiters are not blocked forever.
+	fn drop(&mut self) {
+		self.set(Err(Error::DeviceError("the operation was abandoned without completing".into())));
+	}
+}
+
+impl<T> Future for Event<T> {
+	type Output = Result<T, Error>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let mut slot = self.state.slot.lock().expect("mutex is never poisoned");
+		if !slot.completed {
+			slot.waker = Some(cx.waker().clone());
+			return Poll::Pending;
+		}
+		Poll::Ready(slot.take_result())
+	}
+}
+
+impl<T> Debug for Event<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Event")
+			.field("is_complete", &self.is_complete())
+			.finish()
+	}
+}
+
+/// A pending asynchronous copy between host and device memory, submitted within a
+/// [`TransferScope`].
+///
+/// The transfer borrows the source and destination buffers for the lifetime of its scope, and the
+/// scope waits for the copy before it ends, so neither buffer can be accessed or freed while the
+/// copy may still be in flight. Dropping or leaking the transfer does not end the borrows. Call
+/// [`Transfer::wait`] to wait for the copy within the scope and observe its result.
+pub struct Transfer<'scope> {
+	event: Event<()>,
+	_buffers: PhantomData<&'scope mut ()>,
+}
+
+impl Transfer<'_> {
+	/// Returns whether the copy has completed.
+	pub fn is_complete(&self) -> bool {
+		self.event.is_complete()
+	}
+
+	/// Blocks until the copy completes and returns its result.
+	pub fn wait(self) -> Result<(), Error> {
+		self.event.wait()
+	}
+}
+
+impl Debug for Transfer<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Transfer")
+			.field("is_complete", &self.is_complete())
+			.finish()
+	}
+}
+
+/// A scope for asynchronous copies between host and device memory, created by
+/// [`transfer_scope`].
+///
+/// Compute layers register the copies they submit with [`TransferScope::submit`]. The
+/// `'scope` lifetime is that of the scope and the `'env` lifetime is that of the borrows from
+/// outside of it, which the copies may use.
+pub struct TransferScope<'scope, 'env: 'scope> {
+	pending: Mutex<Vec<Arc<EventState<()>>>>,
+	scope: PhantomData<&'scope mut &'scope ()>,
+	env: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'scope> TransferScope<'scope, '_> {
+	/// Registers a submitted copy, which completes when `event` does, with the scope.
+	pub fn submit(&'scope self, event: Event<()>) -> Transfer<'scope> {
+		self.pending
+			.lock()
+			.expect("mutex is never poisoned")
+			.push(event.state.clone());
+		Transfer {
+			event,
+			_buffers: PhantomData,
+		}
+	}
+
+	/// Registers a copy that has already completed with the given result with the scope.
+	pub fn ready(&'scope self, result: Result<(), Error>) -> Transfer<'scope> {
+		self.submit(Event::ready(result))
+	}
+
+	/// Blocks until all registered copies complete.
+	///
+	/// Returns the first error of a copy whose transfer was not waited on.
+	fn wait_all(&self) -> Result<(), Error> {
+		let pending = mem::take(&mut *self.pending.lock().expect("mutex is never poisoned"));
+		let mut result = Ok(());
+		for state in pending {
+			let mut slot = state.slot.lock().expect("mutex is never poisoned");
+			while !slot.completed {
+				slot = state.cond.wait(slot).expect("mutex is never poisoned");
+			}
+			if let (Some(Err(err)), Ok(())) = (slot.result.take(), &result) {
+				result = Err(err);
+			}
+		}
+		result
+	}
+}
+
+impl Debug for TransferScope<'_, '_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let n_pending = self.pending.lock().expect("mutex is never poisoned").len();
+		f.debug_struct("TransferScope")
+			.field("n_pending", &n_pending)
+			.finish()
+	}
+}
+
+/// Creates a scope for asynchronous copies between host and device memory.
+///
+/// The copies that `f` submits with [`ComputeLayer::copy_h2d_async`] and
+/// [`ComputeLayer::copy_d2h_async`] may borrow buffers from outside of the scope. The scope waits
+/// for all of them before it returns, even if `f` panics or leaks a [`Transfer`], so the buffers
+/// are not used by any copy once the borrows end.
+///
+/// ## Throws
+///
+/// * the first error of a copy whose transfer was not waited on within the scope
+///
+/// [`ComputeLayer::copy_h2d_async`]: crate::ComputeLayer::copy_h2d_async
+/// [`ComputeLayer::copy_d2h_async`]: crate::ComputeLayer::copy_d2h_async
+pub fn transfer_scope<'env, R>(
+	f: impl for<'scope> FnOnce(&'scope TransferScope<'scope, 'env>) -> R,
+) -> Result<R, Error> {
+	/// Waits for the copies of the scope if `f` unwinds.
+	struct WaitOnDrop<'a, 'scope, 'env>(&'a TransferScope<'scope, 'env>);
+
+	impl Drop for WaitOnDrop<'_, '_, '_> {
+		fn drop(&mut self) {
+			let _ = self.0.wait_all();
+		}
+	}
+
+	let scope = TransferScope {
+		pending: Mutex::new(Vec::new()),
+		scope: PhantomData,
+		env: PhantomData,
+	};
+	let guard = WaitOnDrop(&scope);
+	let result = f(&scope);
+	scope.wait_all()?;
+	drop(guard);
+	Ok(result)
+}
+
+/// Checks that a stream identifier is valid for a device with `n_streams` streams.
+pub(crate) fn check_stream(stream: StreamId, n_streams: usize) -> Result<(), Error> {
+	if stream.0 >= n_streams {
+		return Err(Error::InputValidation(format!(
+			"stream {} is out of range, the device supports {n_streams} streams",
+			stream.0
+		)));
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		task::{Wake, Waker},
+		thread,
+	};
+
+	use assert_matches::assert_matches;
+	use binius_field::{Field, TowerField};
+	use binius_math::B128;
+
+	use super::*;
+	use crate::{ComputeLayer, cpu::CpuLayer, layer::ComputeLayerExecutor, memory::SubfieldSlice};
+
+	struct NoopWaker;
+
+	impl Wake for NoopWaker {
+		fn wake(self: Arc<Self>) {}
+	}
+
+	#[test]
+	fn test_event_completed_from_other_thread() {
+		let (event, sender) = Event::<u32>::pending();
+		assert!(!event.is_complete());
+
+		let handle = thread::spawn(move || sender.complete(Ok(42)));
+		assert_eq!(event.wait().unwrap(), 42);
+		handle.join().unwrap();
+	}
+
+	#[test]
+	fn test_event_poll() {
+		let waker = Waker::from(Arc::new(NoopWaker));
+		let mut cx = Context::from_waker(&waker);
+
+		let (mut event, sender) = Event::<u32>::pending();
+		assert!(Pin::new(&mut event).poll(&mut cx).is_pending());
+		sender.complete(Err(Error::InputValidation("failed".into())));
+		assert_matches!(
+			Pin::new(&mut event).poll(&mut cx),
+			Poll::Ready(Err(Error::InputValidation(_)))
+		);
+	}
+
+	#[test]
+	fn test_event_wait_after_poll() {
+		let waker = Waker::from(Arc::new(NoopWaker));
+		let mut cx = Context::from_waker(&waker);
+
+		let (mut event, sender) = Event::<u32>::pending();
+		assert!(Pin::new(&mut event).poll(&mut cx).is_pending());
+		sender.complete(Ok(42));
+		assert_matches!(Pin::new(&mut event).poll(&mut cx), Poll::Ready(Ok(42)));
+
+		// The result was retrieved by polling, so waiting returns immediately.
+		assert_matches!(event.wait(), Err(Error::InputValidation(_)));
+
+		let mut event = Event::ready(Ok(7u32));
+		assert_matches!(Pin::new(&mut event).poll(&mut cx), Poll::Ready(Ok(7)));
+		assert_matches!(Event::wait_all([event]), Err(Error::InputValidation(_)));
+	}
+
+	#[test]
+	fn test_wait_all_reports_error() {
+		let events = vec![
+			Event::ready(Ok(1)),
+			Event::ready(Err(Error::InputValidation("failed".into()))),
+			Event::ready(Ok(3)),
+		];
+		assert_matches!(Event::wait_all(events), Err(Error::InputValidation(_)));
+	}
+
+	#[test]
+	fn test_cpu_execute_on_streams() {
+		let hal = CpuLayer::<B128>::default();
+		let a = vec![B128::ONE; 4];
+		let b = vec![B128::new(3); 4];
+
+		let events = (0..hal.n_streams())
+			.map(|i| {
+				hal.execute_on_stream(StreamId(i), |exec| {
+					let a_in = SubfieldSlice::new(a.as_slice(), B128::TOWER_LEVEL);
+					Ok(vec![exec.inner_product(a_in, b.as_slice())?])
+				})
+			})
+			.collect::<Result<Vec<_>, _>>()
+			.unwrap();
+		for result in Event::wait_all(events).unwrap() {
+			assert_eq!(result, vec![B128::ZERO]);
+		}
+
+		assert_matches!(
+			hal.execute_on_stream(StreamId(hal.n_streams()), |_| Ok(vec![])),
+			Err(Error::InputValidation(_))
+		);
+	}
+
+	#[test]
+	fn test_cpu_async_transfers() {
+		let hal = CpuLayer::<B128>::default();
+		let mut host = hal.alloc_pinned_host(8).unwrap();
+		assert!(!host.is_pinned());
+		for (i, x) in host.iter_mut().enumerate() {
+			*x = B128::new(i as u128);
+		}
+
+		let mut dev = vec![B128::ZERO; 8];
+		let mut dev_slice = dev.as_mut_slice();
+		transfer_scope(|scope| {
+			hal.copy_h2d_async(scope, StreamId::DEFAULT, &host, &mut dev_slice)
+				.unwrap()
+				.wait()
+		})
+		.unwrap()
+		.unwrap();
+
+		let mut out = vec![B128::ZERO; 8];
+		transfer_scope(|scope| {
+			let transfer = hal
+				.copy_d2h_async(scope, StreamId::DEFAULT, dev_slice, &mut out)
+				.unwrap();
+			assert!(transfer.is_complete());
+		})
+		.unwrap();
+		assert_eq!(out, *host);
+
+		assert_matches!(
+			transfer_scope(|scope| {
+				hal.copy_d2h_async(scope, StreamId(hal.n_streams()), &dev, &mut out)
+					.map(|_| ())
+			})
+			.unwrap(),
+			Err(Error::InputValidation(_))
+		);
+	}
+
+	#[test]
+	fn test_dropped_sender_completes_event_with_error() {
+		let (event, sender) = Event::<u32>::pending();
+		let handle = thread::spawn(move || drop(sender));
+		assert_matches!(event.wait(), Err(Error::DeviceError(_)));
+		handle.join().unwrap();
+
+		let (event, sender) = Event::<()>::pending();
+		let result = transfer_scope(|scope| {
+			let _transfer = scope.submit(event);
+			drop(sender);
+		});
+		assert_matches!(result, Err(Error::DeviceError(_)));
+	}
+
+	#[test]
+	fn test_transfer_scope_waits_for_leaked_transfers() {
+		let (event, sender) = Event::pending();
+		let handle = thread::spawn(move || {
+			thread::sleep(std::time::Duration::from_millis(10));
+			sender.complete(Err(Error::InputValidation("failed".into())));
+		});
+
+		let result = transfer_scope(|scope| mem::forget(scope.submit(event)));
+		// The scope waited for the copy, and reports its error as it was never waited on.
+		assert_matches!(result, Err(Error::InputValidation(_)));
+		handle.join().unwrap();
+	}
+}
@@ -1,11 +1,17 @@
 // Copyright 2025 Irreducible Inc.
 
-use binius_compute::cpu::layer::CpuLayerHolder;
-use binius_compute_test_utils::layer::{
-	test_generic_fri_fold, test_generic_kernel_add, test_generic_map_with_multilinear_evaluations,
-	test_generic_multiple_multilinear_evaluations, test_generic_single_inner_product,
-	test_generic_single_inner_product_using_kernel_accumulator, test_generic_single_left_fold,
-	test_generic_single_right_fold, test_generic_single_tensor_expand,
+use binius_compute::{cpu::layer::CpuLayerHolder, naive::NaiveLayerHolder};
+use binius_compute_test_utils::{
+	conformance::{ConformanceConfig, run_conformance_suite},
+	differential::run_differential_suite,
+	layer::{
+		test_generic_fri_fold, test_generic_fri_fold_inplace, test_generic_kernel_add,
+		test_generic_map_and_fold_ops, test_generic_map_with_multilinear_evaluations,
+		test_generic_multiple_multilinear_evaluations, test_generic_ntt, test_generic_rs_encode,
+		test_generic_segmented_sum_and_scan, test_generic_single_inner_product,
+		test_generic_single_inner_product_using_kernel_accumulator, test_generic_single_left_fold,
+		test_generic_single_right_fold, test_generic_single_tensor_expand,
+	},
 };
 use binius_math::{B16, B32, B128};
 
@@ -119,6 +125,44 @@ fn test_exec_fri_fold_zero_log_batch() {
 	);
 }
 
+#[test]
+fn test_exec_fri_fold_inplace_non_zero_log_batch() {
+	type F = B128;
+	type FSub = B16;
+
+	let log_len = 10;
+	let log_batch_size = 4;
+	let log_fold_challenges = 2;
+	test_generic_fri_fold_inplace::<F, FSub, _, _>(
+		CpuLayerHolder::<B128>::new(
+			1 << (log_len + log_batch_size + 2),
+			1 << (log_len + log_batch_size + 1),
+		),
+		log_len,
+		log_batch_size,
+		log_fold_challenges,
+	);
+}
+
+#[test]
+fn test_exec_fri_fold_inplace_zero_log_batch() {
+	type F = B128;
+	type FSub = B16;
+
+	let log_len = 10;
+	let log_batch_size = 0;
+	let log_fold_challenges = 2;
+	test_generic_fri_fold_inplace::<F, FSub, _, _>(
+		CpuLayerHolder::<B128>::new(
+			1 << (log_len + log_batch_size + 2),
+			1 << (log_len + log_batch_size + 1),
+		),
+		log_len,
+		log_batch_size,
+		log_fold_challenges,
+	);
+}
+
 #[test]
 fn test_exec_kernel_add() {
 	let log_len = 10;
@@ -137,6 +181,28 @@ fn test_extrapolate_line() {
 	);
 }
 
+#[test]
+fn test_fold_low_and_high() {
+	for log_len in [1, 10] {
+		binius_compute_test_utils::layer::test_fold_low_and_high(
+			CpuLayerHolder::<B128>::new(1 << (log_len + 3), 1 << (log_len + 3)),
+			log_len,
+		);
+	}
+}
+
+#[test]
+fn test_masked_ops() {
+	let log_len = 10;
+	for len in [0, 1, 7, 600, 1 << log_len] {
+		binius_compute_test_utils::layer::test_generic_masked_ops::<B16, _, _, _>(
+			CpuLayerHolder::<B128>::new(1 << (log_len + 3), 1 << (log_len + 3)),
+			log_len,
+			len,
+		);
+	}
+}
+
 #[test]
 fn test_map_kernels() {
 	let log_len = 10;
@@ -163,3 +229,92 @@ fn test_pairwise_product_reduce() {
 		log_len,
 	);
 }
+
+#[test]
+fn test_build_merkle_tree() {
+	let log_len = 6;
+	binius_compute_test_utils::layer::test_generic_build_merkle_tree(
+		CpuLayerHolder::<B128>::new(1 << (log_len + 4), 1 << (log_len + 3)),
+		log_len,
+		2,
+	);
+}
+
+#[test]
+fn test_exec_ntt() {
+	type F = B128;
+	type FSub = B16;
+	let log_len = 8;
+	let log_batch_size = 2;
+	test_generic_ntt::<F, FSub, _, _>(
+		CpuLayerHolder::<B128>::new(
+			1 << (log_len + log_batch_size + 1),
+			1 << (log_len + log_batch_size + 1),
+		),
+		log_len,
+		log_batch_size,
+	);
+}
+
+#[test]
+fn test_exec_rs_encode() {
+	type F = B128;
+	type FSub = B16;
+	for (log_dim, log_inv_rate, log_batch_size) in [(6, 1, 0), (6, 2, 3), (0, 1, 0)] {
+		test_generic_rs_encode::<F, FSub, _, _>(
+			CpuLayerHolder::<B128>::new(
+				1 << (log_dim + log_inv_rate + log_batch_size + 2),
+				1 << (log_dim + log_inv_rate + log_batch_size + 2),
+			),
+			log_dim,
+			log_inv_rate,
+			log_batch_size,
+		);
+	}
+}
+
+#[test]
+fn test_map_and_fold_ops() {
+	type F = B128;
+	let log_len = 8;
+	test_generic_map_and_fold_ops::<F, _, _>(
+		CpuLayerHolder::<B128>::new(1 << (log_len + 3), 1 << (log_len + 3)),
+		log_len,
+	);
+}
+
+#[test]
+fn test_segmented_sum_and_scan() {
+	type F = B128;
+	let log_len = 8;
+	for log_segment_len in [0, 3, log_len] {
+		test_generic_segmented_sum_and_scan::<F, _, _>(
+			CpuLayerHolder::<B128>::new(1 << (log_len + 3), 1 << (log_len + 3)),
+			log_len,
+			log_segment_len,
+		);
+	}
+}
+
+#[test]
+fn test_conformance() {
+	run_conformance_suite(&ConformanceConfig::default(), |host_len, dev_len| {
+		CpuLayerHolder::<B128>::new(host_len, dev_len)
+	});
+}
+
+#[test]
+fn test_naive_conformance() {
+	run_conformance_suite(&ConformanceConfig::default(), |host_len, dev_len| {
+		NaiveLayerHolder::<B128>::new(host_len, dev_len)
+	});
+}
+
+#[test]
+fn test_differential_against_naive() {
+	run_differential_suite(
+		&ConformanceConfig::default(),
+		NaiveLayerHolder::<B128>::new,
+		CpuLayerHolder::<B128>::new,
+	);
+}
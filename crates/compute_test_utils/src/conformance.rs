@@ -0,0 +1,261 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Backend-agnostic conformance suite for [`ComputeLayer`] implementations.
+//!
+//! The suite runs every operation of the compute layer interface against host-side reference
+//! results, with randomized problem sizes and buffer alignments. New backends should pass the
+//! suite before they are used in the prover:
+//!
+//! ```ignore
+//! #[test]
+//! fn test_conformance() {
+//!     run_conformance_suite(&ConformanceConfig::default(), |host_len, dev_len| {
+//!         MyLayerHolder::new(host_len, dev_len)
+//!     });
+//! }
+//! ```
+
+use std::panic::{self, AssertUnwindSafe};
+
+use binius_compute::{ComputeHolder, ComputeLayer};
+use binius_math::{B16, B32, B128};
+use rand::{Rng, SeedableRng, prelude::StdRng};
+
+use crate::layer::{
+	test_extrapolate_line, test_fold_low_and_high, test_generic_aligned_copies,
+	test_generic_build_merkle_tree, test_generic_compute_composite, test_generic_fri_fold,
+	test_generic_fri_fold_inplace, test_generic_kernel_add, test_generic_map_and_fold_ops,
+	test_generic_map_with_multilinear_evaluations, test_generic_masked_ops,
+	test_generic_multiple_multilinear_evaluations, test_generic_ntt,
+	test_generic_pairwise_product_reduce, test_generic_rs_encode,
+	test_generic_segmented_sum_and_scan, test_generic_single_inner_product,
+	test_generic_single_inner_product_using_kernel_accumulator, test_generic_single_left_fold,
+	test_generic_single_right_fold, test_generic_single_tensor_expand, test_map_kernels,
+};
+
+/// The names of all operations checked by the conformance suite, in the order they run.
+pub const CONFORMANCE_OPS: &[&str] = &[
+	"aligned_copies",
+	"tensor_expand",
+	"inner_product",
+	"multilinear_evaluations",
+	"map_with_multilinear_evaluations",
+	"accumulate_kernels",
+	"kernel_add",
+	"fold_left",
+	"fold_right",
+	"fri_fold",
+	"fri_fold_inplace",
+	"ntt",
+	"rs_encode",
+	"extrapolate_line",
+	"fold_low_and_high",
+	"masked_ops",
+	"compute_composite",
+	"map_kernels",
+	"pairwise_product_reduce",
+	"build_merkle_tree",
+	"map_and_fold_ops",
+	"segmented_sum_and_scan",
+];
+
+/// Parameters of a conformance suite run.
+#[derive(Debug, Clone)]
+pub struct ConformanceConfig {
+	/// Seed for the randomized problem sizes.
+	pub seed: u64,
+	/// Number of times every operation is checked, each time with a fresh random size.
+	pub n_rounds: usize,
+	/// Smallest base-2 logarithm of the problem size.
+	pub min_log_len: usize,
+	/// Largest base-2 logarithm of the problem size.
+	pub max_log_len: usize,
+	/// Operations from [`CONFORMANCE_OPS`] that the backend does not support yet.
+	pub skip: Vec<&'static str>,
+}
+
+impl Default for ConformanceConfig {
+	fn default() -> Self {
+		Self {
+			seed: 0,
+			n_rounds: 2,
+			min_log_len: 5,
+			max_log_len: 9,
+			skip: Vec::new(),
+		}
+	}
+}
+
+/// Runs the conformance suite against a compute layer over the canonical 128-bit tower field.
+///
+/// `make_holder` is called with the number of host and device field elements needed for a case
+/// and must return a fresh compute holder with at least that much memory.
+///
+/// All cases run even if some of them fail, and the function then panics with a report that
+/// lists every failed case together with its problem size.
+pub fn run_conformance_suite<Hal, Holder>(
+	config: &ConformanceConfig,
+	make_holder: impl Fn(usize, usize) -> Holder,
+) where
+	Hal: ComputeLayer<B128>,
+	Holder: ComputeHolder<B128, Hal>,
+{
+	assert!(config.min_log_len >= 5, "precondition: problem sizes must be at least 2^5");
+	assert!(
+		config.min_log_len <= config.max_log_len,
+		"precondition: min_log_len must not exceed max_log_len"
+	);
+
+	let mut rng = StdRng::seed_from_u64(config.seed);
+	let mut failures = Vec::new();
+	for round in 0..config.n_rounds {
+		for &op in CONFORMANCE_OPS {
+			if config.skip.contains(&op) {
+				continue;
+			}
+
+			let log_len = rng.random_range(config.min_log_len..=config.max_log_len);
+			let case_seed = rng.random();
+			let result = panic::catch_unwind(AssertUnwindSafe(|| {
+				run_case(op, log_len, case_seed, &make_holder)
+			}));
+			if let Err(payload) = result {
+				let message = payload
+					.downcast_ref::<&str>()
+					.copied()
+					.or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+					.unwrap_or("<non-string panic payload>");
+				failures.push(format!("{op} (round {round}, log_len {log_len}): {message}"));
+			}
+		}
+	}
+
+	assert!(
+		failures.is_empty(),
+		"{} conformance case(s) failed:\n{}",
+		failures.len(),
+		failures.join("\n")
+	);
+}
+
+fn run_case<Hal, Holder>(
+	op: &str,
+	log_len: usize,
+	seed: u64,
+	make_holder: &impl Fn(usize, usize) -> Holder,
+) where
+	Hal: ComputeLayer<B128>,
+	Holder: ComputeHolder<B128, Hal>,
+{
+	let mut rng = StdRng::seed_from_u64(seed);
+	let holder =
+		|log_host_len: usize, log_dev_len: usize| make_holder(1 << log_host_len, 1 << log_dev_len);
+
+	match op {
+		"aligned_copies" => {
+			test_generic_aligned_copies(holder(log_len + 2, log_len + 2), log_len, seed)
+		}
+		"tensor_expand" => test_generic_single_tensor_expand(holder(log_len + 1, log_len), log_len),
+		"inner_product" => test_generic_single_inner_product::<B16, _, _, _>(
+			holder(log_len + 2, log_len + 1),
+			log_len,
+		),
+		"multilinear_evaluations" => {
+			test_generic_multiple_multilinear_evaluations::<B16, B32, _, _, _>(
+				holder(log_len + 2, log_len + 1),
+				log_len,
+			)
+		}
+		"map_with_multilinear_evaluations" => {
+			test_generic_map_with_multilinear_evaluations(holder(log_len + 2, log_len + 3), log_len)
+		}
+		"accumulate_kernels" => test_generic_single_inner_product_using_kernel_accumulator::<
+			B128,
+			_,
+			_,
+		>(holder(log_len + 2, log_len + 1), log_len),
+		"kernel_add" => {
+			test_generic_kernel_add::<B128, _, _>(holder(log_len + 4, log_len + 3), log_len)
+		}
+		"fold_left" => test_generic_single_left_fold::<B16, B128, _, _>(
+			holder(log_len + 2, log_len + 2),
+			log_len,
+			rng.random_range(0..=log_len - 4),
+		),
+		"fold_right" => test_generic_single_right_fold::<B16, B128, _, _>(
+			holder(log_len + 2, log_len + 2),
+			log_len,
+			rng.random_range(0..=log_len - 4),
+		),
+		"fri_fold" => {
+			let log_batch_size = rng.random_range(0..=2);
+			let log_fold_challenges = rng.random_range(1..=3);
+			test_generic_fri_fold::<B128, B16, _, _>(
+				holder(log_len + log_batch_size + 2, log_len + log_batch_size + 1),
+				log_len,
+				log_batch_size,
+				log_fold_challenges,
+			)
+		}
+		"fri_fold_inplace" => {
+			let log_batch_size = rng.random_range(0..=2);
+			let log_fold_challenges = rng.random_range(1..=3);
+			test_generic_fri_fold_inplace::<B128, B16, _, _>(
+				holder(log_len + log_batch_size + 2, log_len + log_batch_size + 1),
+				log_len,
+				log_batch_size,
+				log_fold_challenges,
+			)
+		}
+		"ntt" => {
+			let log_batch_size = rng.random_range(0..=2);
+			test_generic_ntt::<B128, B16, _, _>(
+				holder(log_len + log_batch_size + 1, log_len + log_batch_size + 1),
+				log_len,
+				log_batch_size,
+			)
+		}
+		"rs_encode" => {
+			let log_inv_rate = rng.random_range(1..=2);
+			let log_batch_size = rng.random_range(0..=2);
+			let log_dim = log_len - log_inv_rate;
+			test_generic_rs_encode::<B128, B16, _, _>(
+				holder(log_len + log_batch_size + 2, log_len + log_batch_size + 2),
+				log_dim,
+				log_inv_rate,
+				log_batch_size,
+			)
+		}
+		"extrapolate_line" => test_extrapolate_line(holder(log_len + 4, log_len + 3), log_len),
+		"fold_low_and_high" => test_fold_low_and_high(holder(log_len + 3, log_len + 3), log_len),
+		"masked_ops" => test_generic_masked_ops::<B16, _, _, _>(
+			holder(log_len + 3, log_len + 3),
+			log_len,
+			rng.random_range(0..=1 << log_len),
+		),
+		"compute_composite" => {
+			test_generic_compute_composite(holder(log_len + 4, log_len + 3), log_len)
+		}
+		"map_kernels" => test_map_kernels(holder(log_len + 4, log_len + 3), log_len),
+		"pairwise_product_reduce" => {
+			test_generic_pairwise_product_reduce(holder(log_len + 4, log_len + 3), log_len)
+		}
+		"build_merkle_tree" => {
+			let log_batch_size = rng.random_range(0..=2);
+			test_generic_build_merkle_tree(
+				holder(log_len + log_batch_size + 4, log_len + log_batch_size + 3),
+				log_len,
+				log_batch_size,
+			)
+		}
+		"map_and_fold_ops" => {
+			test_generic_map_and_fold_ops::<B128, _, _>(holder(log_len + 3, log_len + 3), log_len)
+		}
+		"segmented_sum_and_scan" => test_generic_segmented_sum_and_scan::<B128, _, _>(
+			holder(log_len + 3, log_len + 3),
+			log_len,
+			rng.random_range(0..=log_len),
+		),
+		_ => panic!("unknown conformance operation {op}"),
+	}
+}
@@ -0,0 +1,319 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Differential testing of two [`ComputeLayer`] implementations against each other.
+//!
+//! Where the [conformance suite](crate::conformance) checks every operation against fixed
+//! host-side expectations, the differential suite feeds identical random inputs, with random
+//! operation parameters, to two backends and requires bit-identical outputs. The intended
+//! reference backend is the naïve layer in `binius_compute::naive`:
+//!
+//! ```ignore
+//! #[test]
+//! fn test_differential() {
+//!     run_differential_suite(
+//!         &ConformanceConfig::default(),
+//!         |host_len, dev_len| NaiveLayerHolder::<B128>::new(host_len, dev_len),
+//!         |host_len, dev_len| MyLayerHolder::new(host_len, dev_len),
+//!     );
+//! }
+//! ```
+
+use std::{
+	iter::repeat_with,
+	panic::{self, AssertUnwindSafe},
+};
+
+use binius_compute::{
+	ComputeData, ComputeHolder, ComputeLayer, ComputeLayerExecutor, FSliceMut,
+	alloc::ComputeAllocator,
+	memory::{ComputeMemory, SizedSlice, SlicesBatch, SubfieldSlice},
+};
+use binius_field::{ExtensionField, Field, TowerField};
+use binius_math::{ArithExpr, B16, B128};
+use binius_ntt::{NTTShape, SingleThreadedNTT};
+use rand::{Rng, SeedableRng, prelude::StdRng};
+
+use crate::conformance::ConformanceConfig;
+
+/// The names of all operations compared by the differential suite, in the order they run.
+pub const DIFFERENTIAL_OPS: &[&str] = &[
+	"tensor_expand",
+	"inner_product",
+	"fold_left",
+	"fold_right",
+	"fri_fold",
+	"fri_fold_inplace",
+	"ntt",
+	"rs_encode",
+	"fold_low_and_high",
+	"compute_composite",
+];
+
+/// Runs every operation of [`DIFFERENTIAL_OPS`] on two compute layers with identical random
+/// inputs and checks that the outputs agree.
+///
+/// The `skip` list of the config names operations from [`DIFFERENTIAL_OPS`]. The holder
+/// constructors have the same contract as in [`run_conformance_suite`]. All cases run even if
+/// some of them fail, and the function then panics with a report that lists every mismatch
+/// together with its problem size.
+///
+/// [`run_conformance_suite`]: crate::conformance::run_conformance_suite
+pub fn run_differential_suite<HalA, HolderA, HalB, HolderB>(
+	config: &ConformanceConfig,
+	make_holder_a: impl Fn(usize, usize) -> HolderA,
+	make_holder_b: impl Fn(usize, usize) -> HolderB,
+) where
+	HalA: ComputeLayer<B128>,
+	HolderA: ComputeHolder<B128, HalA>,
+	HalB: ComputeLayer<B128>,
+	HolderB: ComputeHolder<B128, HalB>,
+{
+	assert!(config.min_log_len >= 5, "precondition: problem sizes must be at least 2^5");
+	assert!(
+		config.min_log_len <= config.max_log_len,
+		"precondition: min_log_len must not exceed max_log_len"
+	);
+
+	let mut rng = StdRng::seed_from_u64(config.seed);
+	let mut failures = Vec::new();
+	for round in 0..config.n_rounds {
+		for &op in DIFFERENTIAL_OPS {
+			if config.skip.contains(&op) {
+				continue;
+			}
+
+			let log_len = rng.random_range(config.min_log_len..=config.max_log_len);
+			let case_seed = rng.random();
+			let result = panic::catch_unwind(AssertUnwindSafe(|| {
+				let output_a = run_case(op, log_len, case_seed, &make_holder_a);
+				let output_b = run_case(op, log_len, case_seed, &make_holder_b);
+				if let Some(index) = output_a.iter().zip(&output_b).position(|(a, b)| a != b) {
+					panic!(
+						"outputs differ at index {index}: {:?} != {:?}",
+						output_a[index], output_b[index]
+					);
+				}
+				assert_eq!(output_a.len(), output_b.len(), "output lengths differ");
+			}));
+			if let Err(payload) = result {
+				let message = payload
+					.downcast_ref::<&str>()
+					.copied()
+					.or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+					.unwrap_or("<non-string panic payload>");
+				failures.push(format!("{op} (round {round}, log_len {log_len}): {message}"));
+			}
+		}
+	}
+
+	assert!(
+		failures.is_empty(),
+		"{} differential case(s) failed:\n{}",
+		failures.len(),
+		failures.join("\n")
+	);
+}
+
+/// Runs one operation on freshly seeded random inputs and returns its outputs, copied back to
+/// the host.
+fn run_case<Hal, Holder>(
+	op: &str,
+	log_len: usize,
+	seed: u64,
+	make_holder: &impl Fn(usize, usize) -> Holder,
+) -> Vec<B128>
+where
+	Hal: ComputeLayer<B128>,
+	Holder: ComputeHolder<B128, Hal>,
+{
+	type F = B128;
+
+	let mut rng = StdRng::seed_from_u64(seed);
+	let mut holder = make_holder(1 << log_len, 1 << (log_len + 4));
+	let ComputeData { hal, dev_alloc, .. } = holder.to_data();
+	let to_device = |data: &Vec<F>| {
+		let mut slice = dev_alloc.alloc(data.len()).unwrap();
+		hal.copy_h2d(data, &mut slice).unwrap();
+		slice
+	};
+	let to_host = |slice: &FSliceMut<'_, F, Hal>| {
+		let mut data = vec![F::ZERO; slice.len()];
+		hal.copy_d2h(Hal::DevMem::as_const(slice), &mut data)
+			.unwrap();
+		data
+	};
+
+	match op {
+		"tensor_expand" => {
+			let log_n = rng.random_range(0..=2);
+			let coordinates: Vec<_> = random_vec(&mut rng, log_len - log_n);
+			let mut data = vec![F::ZERO; 1 << log_len];
+			data[..1 << log_n].copy_from_slice(&random_vec(&mut rng, 1 << log_n));
+			let mut data = to_device(&data);
+			hal.execute(|exec| {
+				exec.tensor_expand(log_n, &coordinates, &mut data)?;
+				Ok(vec![])
+			})
+			.unwrap();
+			to_host(&data)
+		}
+		"inner_product" => {
+			let a = to_device(&random_vec(&mut rng, 1 << log_len));
+			let b = random_vec(&mut rng, 1 << (log_len + <F as ExtensionField<B16>>::LOG_DEGREE));
+			let b = to_device(&b);
+			hal.execute(|exec| {
+				let a = SubfieldSlice::new(Hal::DevMem::as_const(&a), B16::TOWER_LEVEL);
+				let result = exec.inner_product(a, Hal::DevMem::as_const(&b))?;
+				Ok(vec![result])
+			})
+			.unwrap()
+		}
+		"fold_left" | "fold_right" => {
+			let log_scalars = log_len + <F as ExtensionField<B16>>::LOG_DEGREE;
+			let log_vec_len = rng.random_range(0..=4);
+			let mat = to_device(&random_vec(&mut rng, 1 << log_len));
+			let vec = to_device(&random_vec(&mut rng, 1 << log_vec_len));
+			let mut out = to_device(&vec![F::ZERO; 1 << (log_scalars - log_vec_len)]);
+			hal.execute(|exec| {
+				let mat = SubfieldSlice::new(Hal::DevMem::as_const(&mat), B16::TOWER_LEVEL);
+				let vec = Hal::DevMem::as_const(&vec);
+				if op == "fold_left" {
+					exec.fold_left(mat, vec, &mut out)?;
+				} else {
+					exec.fold_right(mat, vec, &mut out)?;
+				}
+				Ok(vec![])
+			})
+			.unwrap();
+			to_host(&out)
+		}
+		"fri_fold" => {
+			let log_batch_size = rng.random_range(0..=2);
+			let log_fold_challenges = rng.random_range(1..=3);
+			let ntt = SingleThreadedNTT::<B16>::new(log_len).unwrap();
+			let data_in = to_device(&random_vec(&mut rng, 1 << (log_len + log_batch_size)));
+			let challenges: Vec<_> = random_vec(&mut rng, log_batch_size + log_fold_challenges);
+			let mut data_out = to_device(&vec![F::ZERO; 1 << (log_len - log_fold_challenges)]);
+			hal.execute(|exec| {
+				exec.fri_fold(
+					&ntt,
+					log_len,
+					log_batch_size,
+					&challenges,
+					Hal::DevMem::as_const(&data_in),
+					&mut data_out,
+				)?;
+				Ok(vec![])
+			})
+			.unwrap();
+			to_host(&data_out)
+		}
+		"fri_fold_inplace" => {
+			let log_batch_size = rng.random_range(0..=2);
+			let log_fold_challenges = rng.random_range(1..=3);
+			let ntt = SingleThreadedNTT::<B16>::new(log_len).unwrap();
+			let mut data = to_device(&random_vec(&mut rng, 1 << (log_len + log_batch_size)));
+			let challenges: Vec<_> = random_vec(&mut rng, log_batch_size + log_fold_challenges);
+			hal.execute(|exec| {
+				exec.fri_fold_inplace(
+					&ntt,
+					log_len,
+					log_batch_size,
+					&challenges,
+					Hal::DevMem::to_owned_mut(&mut data),
+				)?;
+				Ok(vec![])
+			})
+			.unwrap();
+			// Only the folded prefix of the buffer is specified.
+			let mut folded = to_host(&data);
+			folded.truncate(1 << (log_len - log_fold_challenges));
+			folded
+		}
+		"ntt" => {
+			let log_x = rng.random_range(0..=2);
+			let log_z = rng.random_range(0..=1);
+			let shape = NTTShape {
+				log_x,
+				log_y: log_len - log_x - log_z,
+				log_z,
+			};
+			let coset_bits = rng.random_range(0..=2);
+			let coset = rng.random_range(0..1 << coset_bits);
+			let skip_rounds = rng.random_range(0..=1);
+			let ntt = SingleThreadedNTT::<B16>::new(log_len + 2).unwrap();
+			let mut forward = to_device(&random_vec(&mut rng, 1 << log_len));
+			let mut inverse = to_device(&random_vec(&mut rng, 1 << log_len));
+			hal.execute(|exec| {
+				exec.forward_ntt(&ntt, shape, coset, coset_bits, skip_rounds, &mut forward)?;
+				exec.inverse_ntt(&ntt, shape, coset, coset_bits, skip_rounds, &mut inverse)?;
+				Ok(vec![])
+			})
+			.unwrap();
+			[to_host(&forward), to_host(&inverse)].concat()
+		}
+		"rs_encode" => {
+			let log_inv_rate = rng.random_range(1..=2);
+			let log_batch_size = rng.random_range(0..=2);
+			let log_dim = log_len - log_batch_size;
+			let ntt = SingleThreadedNTT::<B16>::new(log_dim + log_inv_rate).unwrap();
+			let message = to_device(&random_vec(&mut rng, 1 << log_len));
+			let mut codeword = to_device(&vec![F::ZERO; 1 << (log_len + log_inv_rate)]);
+			hal.execute(|exec| {
+				exec.rs_encode(
+					&ntt,
+					log_dim,
+					log_inv_rate,
+					log_batch_size,
+					Hal::DevMem::as_const(&message),
+					&mut codeword,
+				)?;
+				Ok(vec![])
+			})
+			.unwrap();
+			to_host(&codeword)
+		}
+		"fold_low_and_high" => {
+			let len = rng.random_range(0..=1 << log_len);
+			let z = F::random(&mut rng);
+			let evals = to_device(&random_vec(&mut rng, 1 << log_len));
+			let mut outs: [_; 4] =
+				std::array::from_fn(|_| to_device(&vec![F::ZERO; 1 << (log_len - 1)]));
+			hal.execute(|exec| {
+				let evals = Hal::DevMem::as_const(&evals);
+				let [low, high, low_masked, high_masked] = &mut outs;
+				exec.fold_low(evals, z, low)?;
+				exec.fold_high(evals, z, high)?;
+				exec.fold_low_masked(evals, len, z, low_masked)?;
+				exec.fold_high_masked(evals, len, z, high_masked)?;
+				Ok(vec![])
+			})
+			.unwrap();
+			outs.iter().flat_map(to_host).collect()
+		}
+		"compute_composite" => {
+			let expr = ArithExpr::Var(0) * ArithExpr::Var(1)
+				+ ArithExpr::Var(2).pow(3)
+				+ ArithExpr::Const(F::random(&mut rng));
+			let composition = hal.compile_expr(&expr.into()).unwrap();
+			let inputs: [_; 3] =
+				std::array::from_fn(|_| to_device(&random_vec(&mut rng, 1 << log_len)));
+			let mut output = to_device(&vec![F::ZERO; 1 << log_len]);
+			hal.execute(|exec| {
+				let inputs = SlicesBatch::new(
+					inputs.iter().map(Hal::DevMem::as_const).collect(),
+					1 << log_len,
+				);
+				exec.compute_composite(&inputs, &mut output, &composition)?;
+				Ok(vec![])
+			})
+			.unwrap();
+			to_host(&output)
+		}
+		_ => panic!("unknown differential operation {op}"),
+	}
+}
+
+fn random_vec(rng: &mut StdRng, len: usize) -> Vec<B128> {
+	repeat_with(|| B128::random(&mut *rng)).take(len).collect()
+}
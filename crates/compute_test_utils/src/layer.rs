@@ -7,14 +7,16 @@ use binius_compute::{
 	alloc::ComputeAllocator,
 	layer::{ComputeLayer, KernelBuffer, KernelMemMap},
 	memory::{ComputeMemory, SizedSlice, SlicesBatch, SubfieldSlice},
+	ops::{build_merkle_tree, map_elementwise, pairwise_fold, scan, segmented_sum},
 };
-use binius_core::composition::BivariateProduct;
+use binius_core::{composition::BivariateProduct, merkle_tree, reed_solomon::ReedSolomonCode};
 use binius_field::{BinaryField, ExtensionField, Field, PackedExtension, PackedField, TowerField};
+use binius_hash::groestl::{Groestl256, Groestl256ByteCompression};
 use binius_math::{
 	ArithCircuit, CompositionPoly, MultilinearExtension, MultilinearQuery, extrapolate_line_scalar,
 	tensor_prod_eq_ind,
 };
-use binius_ntt::fri::fold_interleaved;
+use binius_ntt::{AdditiveNTT, NTTShape, fri::fold_interleaved};
 use binius_utils::checked_arithmetics::checked_log_2;
 use itertools::Itertools;
 use rand::{Rng, SeedableRng, prelude::StdRng};
@@ -71,6 +73,66 @@ pub fn test_generic_single_tensor_expand<
 	assert_eq!(buffer, buffer_clone);
 }
 
+pub fn test_generic_aligned_copies<F, C, ComputeHolderType>(
+	mut compute_holder: ComputeHolderType,
+	log_len: usize,
+	seed: u64,
+) where
+	F: Field,
+	C: ComputeLayer<F>,
+	ComputeHolderType: ComputeHolder<F, C>,
+{
+	let mut rng = StdRng::seed_from_u64(seed);
+
+	let ComputeData {
+		hal: compute,
+		host_alloc,
+		dev_alloc,
+		..
+	} = compute_holder.to_data();
+
+	// Pick a random subrange of the device buffers whose bounds are multiples of the alignment.
+	let alignment = C::DevMem::ALIGNMENT;
+	let n_blocks = (1usize << log_len).div_ceil(alignment);
+	let len = n_blocks * alignment;
+	let start_block = rng.random_range(0..n_blocks);
+	let end_block = rng.random_range(start_block + 1..=n_blocks);
+	let range = start_block * alignment..end_block * alignment;
+
+	let src = host_alloc.alloc(range.len()).unwrap();
+	src.fill_with(|| <F as Field>::random(&mut rng));
+
+	let mut dev = dev_alloc.alloc(2 * len).unwrap();
+	let (mut lhs, mut rhs) = C::DevMem::split_at_mut_borrowed(&mut dev, len);
+	compute.fill(&mut rhs, F::ZERO).unwrap();
+
+	let mut lhs_range = C::DevMem::slice_mut(&mut lhs, range.clone());
+	compute.copy_h2d(src, &mut lhs_range).unwrap();
+	let value = <F as Field>::random(&mut rng);
+	{
+		let mut rhs_range = C::DevMem::slice_mut(&mut rhs, range.clone());
+		compute
+			.copy_d2d(C::DevMem::as_const(&lhs_range), &mut rhs_range)
+			.unwrap();
+
+		let dst = host_alloc.alloc(range.len()).unwrap();
+		compute
+			.copy_d2h(C::DevMem::as_const(&rhs_range), dst)
+			.unwrap();
+		assert_eq!(dst, src);
+
+		compute.fill(&mut rhs_range, value).unwrap();
+	}
+
+	// The fill must only touch the selected range.
+	let out = host_alloc.alloc(len).unwrap();
+	compute.copy_d2h(C::DevMem::as_const(&rhs), out).unwrap();
+	for (i, &out_i) in out.iter().enumerate() {
+		let expected = if range.contains(&i) { value } else { F::ZERO };
+		assert_eq!(out_i, expected, "mismatch at index {i}");
+	}
+}
+
 pub fn test_generic_single_inner_product<
 	F2: TowerField,
 	F: Field + PackedExtension<F2> + ExtensionField<F2>,
@@ -191,7 +253,7 @@ pub fn test_generic_multiple_multilinear_evaluations<
 			{
 				// Swap first element on the device buffer
 				let mut first_elt =
-					<C::DevMem as ComputeMemory<F>>::slice_mut(&mut eq_ind_slice, ..1);
+					<C::DevMem as ComputeMemory<F>>::slice_power_of_two_mut(&mut eq_ind_slice, 1);
 				compute.copy_h2d(&[F::ONE], &mut first_elt)?;
 			}
 
@@ -288,7 +350,7 @@ pub fn test_generic_map_with_multilinear_evaluations<
 			{
 				// Swap first element on the device buffer
 				let mut first_elt =
-					<C::DevMem as ComputeMemory<F>>::slice_mut(&mut eq_ind_slice, ..1);
+					<C::DevMem as ComputeMemory<F>>::slice_power_of_two_mut(&mut eq_ind_slice, 1);
 				compute.copy_h2d(&[F::ONE], &mut first_elt)?;
 			}
 
@@ -569,6 +631,344 @@ pub fn test_generic_fri_fold<F, FSub, C, ComputeHolderType: ComputeHolder<F, C>>
 	assert_eq!(data_out, &expected_result);
 }
 
+pub fn test_generic_fri_fold_inplace<F, FSub, C, ComputeHolderType: ComputeHolder<F, C>>(
+	mut compute_data: ComputeHolderType,
+	log_len: usize,
+	log_batch_size: usize,
+	log_fold_challenges: usize,
+) where
+	F: TowerField + ExtensionField<FSub>,
+	FSub: BinaryField,
+	C: ComputeLayer<F>,
+{
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let ComputeData {
+		hal: compute,
+		host_alloc,
+		dev_alloc,
+		..
+	} = compute_data.to_data();
+
+	let ntt = binius_ntt::SingleThreadedNTT::<FSub>::new(log_len).unwrap();
+
+	let data_in = host_alloc.alloc(1 << (log_len + log_batch_size)).unwrap();
+	for x_i in data_in.iter_mut() {
+		*x_i = <F as Field>::random(&mut rng);
+	}
+
+	let mut data_slice = dev_alloc.alloc(data_in.len()).unwrap();
+	compute.copy_h2d(data_in, &mut data_slice).unwrap();
+
+	let challenges = repeat_with(|| <F as Field>::random(&mut rng))
+		.take(log_batch_size + log_fold_challenges)
+		.collect::<Vec<_>>();
+
+	// Run the HAL operation, which leaves the folded values at the front of the buffer.
+	let out_len = 1 << (log_len - log_fold_challenges);
+	compute
+		.execute(|exec| {
+			let folded = exec.fri_fold_inplace(
+				&ntt,
+				log_len,
+				log_batch_size,
+				&challenges,
+				C::DevMem::to_owned_mut(&mut data_slice),
+			)?;
+			assert_eq!(folded.len(), out_len);
+			Ok(vec![])
+		})
+		.unwrap();
+
+	let data_out = host_alloc.alloc(out_len).unwrap();
+	let folded_slice = C::DevMem::slice(C::DevMem::as_const(&data_slice), ..out_len);
+	compute.copy_d2h(folded_slice, data_out).unwrap();
+
+	// Compute the expected result and compare
+	let expected_result = fold_interleaved(&ntt, data_in, &challenges, log_len, log_batch_size);
+	assert_eq!(data_out, &expected_result);
+}
+
+pub fn test_generic_ntt<F, FSub, C, ComputeHolderType>(
+	mut compute_holder: ComputeHolderType,
+	log_len: usize,
+	log_batch_size: usize,
+) where
+	F: TowerField + ExtensionField<FSub> + PackedExtension<FSub>,
+	FSub: BinaryField,
+	C: ComputeLayer<F>,
+	ComputeHolderType: ComputeHolder<F, C>,
+{
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let ComputeData {
+		hal: compute,
+		host_alloc,
+		dev_alloc,
+		..
+	} = compute_holder.to_data();
+
+	let ntt = binius_ntt::SingleThreadedNTT::<FSub>::new(log_len + 1).unwrap();
+	let shape = NTTShape {
+		log_x: log_batch_size,
+		log_y: log_len,
+		..Default::default()
+	};
+
+	let data = host_alloc.alloc(1 << (log_len + log_batch_size)).unwrap();
+	for x_i in data.iter_mut() {
+		*x_i = <F as Field>::random(&mut rng);
+	}
+	let original = data.to_vec();
+
+	let mut data_slice = dev_alloc.alloc(data.len()).unwrap();
+	compute.copy_h2d(data, &mut data_slice).unwrap();
+
+	compute
+		.execute(|exec| {
+			exec.forward_ntt(&ntt, shape, 1, 1, 0, &mut data_slice)?;
+			Ok(vec![])
+		})
+		.unwrap();
+	compute
+		.copy_d2h(C::DevMem::as_const(&data_slice), data)
+		.unwrap();
+
+	let mut expected = original.clone();
+	ntt.forward_transform_ext(&mut expected, shape, 1, 1, 0)
+		.unwrap();
+	assert_eq!(data, &expected);
+
+	compute
+		.execute(|exec| {
+			exec.inverse_ntt(&ntt, shape, 1, 1, 0, &mut data_slice)?;
+			Ok(vec![])
+		})
+		.unwrap();
+	compute
+		.copy_d2h(C::DevMem::as_const(&data_slice), data)
+		.unwrap();
+	assert_eq!(data, &original);
+}
+
+pub fn test_generic_rs_encode<F, FSub, C, ComputeHolderType>(
+	mut compute_holder: ComputeHolderType,
+	log_dim: usize,
+	log_inv_rate: usize,
+	log_batch_size: usize,
+) where
+	F: TowerField + ExtensionField<FSub> + PackedExtension<FSub>,
+	FSub: BinaryField,
+	C: ComputeLayer<F>,
+	ComputeHolderType: ComputeHolder<F, C>,
+{
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let ComputeData {
+		hal: compute,
+		host_alloc,
+		dev_alloc,
+		..
+	} = compute_holder.to_data();
+
+	let ntt = binius_ntt::SingleThreadedNTT::<FSub>::new(log_dim + log_inv_rate).unwrap();
+
+	let message = host_alloc.alloc(1 << (log_dim + log_batch_size)).unwrap();
+	for x_i in message.iter_mut() {
+		*x_i = <F as Field>::random(&mut rng);
+	}
+	let mut message_slice = dev_alloc.alloc(message.len()).unwrap();
+	compute.copy_h2d(message, &mut message_slice).unwrap();
+	let message_slice = C::DevMem::as_const(&message_slice);
+
+	let codeword = host_alloc
+		.alloc(1 << (log_dim + log_inv_rate + log_batch_size))
+		.unwrap();
+	let mut codeword_slice = dev_alloc.alloc(codeword.len()).unwrap();
+
+	compute
+		.execute(|exec| {
+			exec.rs_encode(
+				&ntt,
+				log_dim,
+				log_inv_rate,
+				log_batch_size,
+				message_slice,
+				&mut codeword_slice,
+			)?;
+			Ok(vec![])
+		})
+		.unwrap();
+	compute
+		.copy_d2h(C::DevMem::as_const(&codeword_slice), codeword)
+		.unwrap();
+
+	let rs_code = ReedSolomonCode::<FSub>::new(log_dim, log_inv_rate).unwrap();
+	let mut expected = vec![F::ZERO; codeword.len()];
+	expected[..message.len()].copy_from_slice(message);
+	rs_code
+		.encode_ext_batch_inplace(&ntt, &mut expected, log_batch_size)
+		.unwrap();
+	assert_eq!(codeword, &expected);
+}
+
+pub fn test_generic_build_merkle_tree<F, C, ComputeHolderType>(
+	mut compute_holder: ComputeHolderType,
+	log_len: usize,
+	log_batch_size: usize,
+) where
+	F: TowerField,
+	C: ComputeLayer<F>,
+	ComputeHolderType: ComputeHolder<F, C>,
+{
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let ComputeData {
+		hal: compute,
+		host_alloc,
+		dev_alloc,
+		..
+	} = compute_holder.to_data();
+
+	let data = host_alloc.alloc(1 << (log_len + log_batch_size)).unwrap();
+	for x_i in data.iter_mut() {
+		*x_i = F::random(&mut rng);
+	}
+
+	let mut data_slice = dev_alloc.alloc(data.len()).unwrap();
+	compute.copy_h2d(data, &mut data_slice).unwrap();
+	let data_slice = C::DevMem::as_const(&data_slice);
+
+	let nodes = build_merkle_tree::<_, _, Groestl256, _>(
+		compute,
+		data_slice,
+		log_len,
+		&Groestl256ByteCompression,
+	)
+	.unwrap();
+
+	let expected = merkle_tree::build::<_, Groestl256, _>(
+		&Groestl256ByteCompression,
+		data,
+		1 << log_batch_size,
+	)
+	.unwrap();
+	assert_eq!(nodes, expected.inner_nodes);
+}
+
+pub fn test_generic_map_and_fold_ops<F, C, ComputeHolderType>(
+	mut compute_holder: ComputeHolderType,
+	log_len: usize,
+) where
+	F: TowerField,
+	C: ComputeLayer<F>,
+	ComputeHolderType: ComputeHolder<F, C>,
+{
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let ComputeData {
+		hal: compute,
+		host_alloc,
+		dev_alloc,
+		..
+	} = compute_holder.to_data();
+
+	let a = host_alloc.alloc(1 << log_len).unwrap();
+	a.fill_with(|| <F as Field>::random(&mut rng));
+	let b = host_alloc.alloc(1 << log_len).unwrap();
+	b.fill_with(|| <F as Field>::random(&mut rng));
+
+	let mut a_dev = dev_alloc.alloc(a.len()).unwrap();
+	compute.copy_h2d(a, &mut a_dev).unwrap();
+	let mut b_dev = dev_alloc.alloc(b.len()).unwrap();
+	compute.copy_h2d(b, &mut b_dev).unwrap();
+
+	// out = a * b + a
+	let composition = ArithCircuit::var(0) * ArithCircuit::var(1) + ArithCircuit::var(0);
+	let mut out_dev = dev_alloc.alloc(a.len()).unwrap();
+	map_elementwise(
+		compute,
+		&[C::DevMem::as_const(&a_dev), C::DevMem::as_const(&b_dev)],
+		&mut out_dev,
+		&composition,
+	)
+	.unwrap();
+
+	let out = host_alloc.alloc(a.len()).unwrap();
+	compute
+		.copy_d2h(C::DevMem::as_const(&out_dev), out)
+		.unwrap();
+	for ((&out_i, &a_i), &b_i) in out.iter().zip(a.iter()).zip(b.iter()) {
+		assert_eq!(out_i, a_i * b_i + a_i);
+	}
+
+	// Repeatedly fold the halves of `a` with multiplication to get the product of all entries.
+	let product = ArithCircuit::var(0) * ArithCircuit::var(1);
+	let mut folded =
+		pairwise_fold(compute, &dev_alloc, C::DevMem::as_const(&a_dev), &product).unwrap();
+	for _ in 1..log_len {
+		folded =
+			pairwise_fold(compute, &dev_alloc, C::DevMem::as_const(&folded), &product).unwrap();
+	}
+	let mut result = [F::ZERO];
+	compute
+		.copy_d2h(C::DevMem::as_const(&folded), &mut result)
+		.unwrap();
+	assert_eq!(result[0], a.iter().product::<F>());
+}
+
+pub fn test_generic_segmented_sum_and_scan<F, C, ComputeHolderType>(
+	mut compute_holder: ComputeHolderType,
+	log_len: usize,
+	log_segment_len: usize,
+) where
+	F: TowerField,
+	C: ComputeLayer<F>,
+	ComputeHolderType: ComputeHolder<F, C>,
+{
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let ComputeData {
+		hal: compute,
+		host_alloc,
+		dev_alloc,
+		..
+	} = compute_holder.to_data();
+
+	let data = host_alloc.alloc(1 << log_len).unwrap();
+	data.fill_with(|| <F as Field>::random(&mut rng));
+	let mut data_dev = dev_alloc.alloc(data.len()).unwrap();
+	compute.copy_h2d(data, &mut data_dev).unwrap();
+
+	let sums_dev =
+		segmented_sum(compute, &dev_alloc, C::DevMem::as_const(&data_dev), log_segment_len)
+			.unwrap();
+	let sums = host_alloc.alloc(sums_dev.len()).unwrap();
+	compute
+		.copy_d2h(C::DevMem::as_const(&sums_dev), sums)
+		.unwrap();
+	let expected_sums = data
+		.chunks(1 << log_segment_len)
+		.map(|segment| segment.iter().copied().sum::<F>())
+		.collect::<Vec<_>>();
+	assert_eq!(sums, expected_sums.as_slice());
+
+	let prefix_sum = ArithCircuit::var(0) + ArithCircuit::var(1);
+	scan(compute, &mut data_dev, &prefix_sum).unwrap();
+	let scanned = host_alloc.alloc(data.len()).unwrap();
+	compute
+		.copy_d2h(C::DevMem::as_const(&data_dev), scanned)
+		.unwrap();
+	let expected_scan = data
+		.iter()
+		.scan(F::ZERO, |acc, &x| {
+			*acc += x;
+			Some(*acc)
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(scanned, expected_scan.as_slice());
+}
+
 pub fn test_generic_single_left_fold<
 	F: Field + TowerField,
 	F2: ExtensionField<F> + TowerField,
@@ -770,6 +1170,147 @@ pub fn test_extrapolate_line<
 	assert_eq!(result_host, &expected_result);
 }
 
+pub fn test_fold_low_and_high<
+	F: Field,
+	Hal: ComputeLayer<F>,
+	ComputeHolderType: ComputeHolder<F, Hal>,
+>(
+	mut compute_holder: ComputeHolderType,
+	log_len: usize,
+) {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let ComputeData {
+		hal,
+		host_alloc,
+		dev_alloc,
+		..
+	} = compute_holder.to_data();
+
+	let evals_host = host_alloc.alloc(1 << log_len).unwrap();
+	let result_host = host_alloc.alloc(1 << (log_len - 1)).unwrap();
+	evals_host.fill_with(|| F::random(&mut rng));
+
+	let mut evals_dev = dev_alloc.alloc(1 << log_len).unwrap();
+	hal.copy_h2d(evals_host, &mut evals_dev).unwrap();
+	let evals_dev = Hal::DevMem::to_const(evals_dev);
+
+	let z = F::random(&mut rng);
+
+	let mut folded_low_dev = dev_alloc.alloc(1 << (log_len - 1)).unwrap();
+	let mut folded_high_dev = dev_alloc.alloc(1 << (log_len - 1)).unwrap();
+	let _ = hal
+		.execute(|exec| {
+			exec.fold_low(evals_dev, z, &mut folded_low_dev)?;
+			exec.fold_high(evals_dev, z, &mut folded_high_dev)?;
+			Ok(Vec::new())
+		})
+		.unwrap();
+
+	hal.copy_d2h(Hal::DevMem::as_const(&folded_low_dev), result_host)
+		.unwrap();
+	let expected_low = evals_host
+		.chunks_exact(2)
+		.map(|evals| extrapolate_line_scalar(evals[0], evals[1], z))
+		.collect::<Vec<_>>();
+	assert_eq!(result_host, &expected_low);
+
+	hal.copy_d2h(Hal::DevMem::as_const(&folded_high_dev), result_host)
+		.unwrap();
+	let (evals_0, evals_1) = evals_host.split_at(1 << (log_len - 1));
+	let expected_high = iter::zip(evals_0, evals_1)
+		.map(|(x0, x1)| extrapolate_line_scalar(*x0, *x1, z))
+		.collect::<Vec<_>>();
+	assert_eq!(result_host, &expected_high);
+}
+
+/// Checks the length-masked operations against their definitions over the masked prefix.
+///
+/// The buffers hold random values past `len`, which the operations must ignore.
+pub fn test_generic_masked_ops<
+	F2: TowerField,
+	F: Field + PackedExtension<F2> + ExtensionField<F2>,
+	Hal: ComputeLayer<F>,
+	ComputeHolderType: ComputeHolder<F, Hal>,
+>(
+	mut compute_holder: ComputeHolderType,
+	log_len: usize,
+	len: usize,
+) {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let ComputeData {
+		hal,
+		host_alloc,
+		dev_alloc,
+		..
+	} = compute_holder.to_data();
+
+	let evals_host = host_alloc.alloc(1 << log_len).unwrap();
+	let result_host = host_alloc.alloc(1 << log_len).unwrap();
+	evals_host.fill_with(|| <F as Field>::random(&mut rng));
+	let masked_eval = |i: usize| if i < len { evals_host[i] } else { F::ZERO };
+
+	// Fill
+	let value = <F as Field>::random(&mut rng);
+	let mut filled_dev = dev_alloc.alloc(1 << log_len).unwrap();
+	hal.copy_h2d(evals_host, &mut filled_dev).unwrap();
+	hal.fill_masked(&mut filled_dev, value, len).unwrap();
+	hal.copy_d2h(Hal::DevMem::as_const(&filled_dev), result_host)
+		.unwrap();
+	let expected_filled = (0..1 << log_len)
+		.map(|i| if i < len { value } else { F::ZERO })
+		.collect::<Vec<_>>();
+	assert_eq!(result_host, &expected_filled);
+
+	// Folds
+	let mut evals_dev = dev_alloc.alloc(1 << log_len).unwrap();
+	hal.copy_h2d(evals_host, &mut evals_dev).unwrap();
+	let evals_dev = Hal::DevMem::to_const(evals_dev);
+
+	let z = <F as Field>::random(&mut rng);
+	let half_len = 1 << (log_len - 1);
+	let mut folded_low_dev = dev_alloc.alloc(half_len).unwrap();
+	let mut folded_high_dev = dev_alloc.alloc(half_len).unwrap();
+	let _ = hal
+		.execute(|exec| {
+			exec.fold_low_masked(evals_dev, len, z, &mut folded_low_dev)?;
+			exec.fold_high_masked(evals_dev, len, z, &mut folded_high_dev)?;
+			Ok(Vec::new())
+		})
+		.unwrap();
+
+	hal.copy_d2h(Hal::DevMem::as_const(&folded_low_dev), &mut result_host[..half_len])
+		.unwrap();
+	let expected_low = (0..half_len)
+		.map(|i| extrapolate_line_scalar::<F, F>(masked_eval(2 * i), masked_eval(2 * i + 1), z))
+		.collect::<Vec<_>>();
+	assert_eq!(&result_host[..half_len], &expected_low);
+
+	hal.copy_d2h(Hal::DevMem::as_const(&folded_high_dev), &mut result_host[..half_len])
+		.unwrap();
+	let expected_high = (0..half_len)
+		.map(|i| extrapolate_line_scalar::<F, F>(masked_eval(i), masked_eval(i + half_len), z))
+		.collect::<Vec<_>>();
+	assert_eq!(&result_host[..half_len], &expected_high);
+
+	// Inner product
+	let a_host = host_alloc.alloc(1 << (log_len - F::LOG_DEGREE)).unwrap();
+	a_host.fill_with(|| <F as Field>::random(&mut rng));
+	let mut a_dev = dev_alloc.alloc(a_host.len()).unwrap();
+	hal.copy_h2d(a_host, &mut a_dev).unwrap();
+	let a_dev = SubfieldSlice::new(Hal::DevMem::as_const(&a_dev), F2::TOWER_LEVEL);
+
+	let actual = hal
+		.execute(|exec| Ok(vec![exec.inner_product_masked(a_dev, evals_dev, len)?]))
+		.unwrap()
+		.remove(0);
+	let expected = iter::zip(PackedField::iter_slice(F::cast_bases(a_host)), &evals_host[..len])
+		.map(|(a_i, &b_i)| b_i * a_i)
+		.sum::<F>();
+	assert_eq!(actual, expected);
+}
+
 pub fn test_generic_compute_composite<
 	F: Field,
 	Hal: ComputeLayer<F>,
@@ -1,6 +1,8 @@
 // Copyright 2025 Irreducible Inc.
 
 pub mod bivariate_sumcheck;
+pub mod conformance;
+pub mod differential;
 pub mod layer;
 pub mod piop;
 pub mod ring_switch;
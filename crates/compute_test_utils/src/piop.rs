@@ -13,7 +13,7 @@ use binius_core::{
 		CommitMeta, PIOPSumcheckClaim, commit, make_commit_params_with_optimal_arity, prove, verify,
 	},
 	polynomial::MultivariatePoly,
-	protocols::fri::CommitOutput,
+	protocols::fri::{CommitOutput, CommittedCodeword},
 	transcript::ProverTranscript,
 	transparent,
 };
@@ -187,7 +187,7 @@ pub fn commit_prove_verify<FDomain, FEncode, F, P, MTScheme, HAL, ComputeHolderT
 		merkle_prover,
 		commit_meta,
 		committed,
-		&codeword,
+		&CommittedCodeword::Host(codeword),
 		&committed_multilins,
 		transparent_multilins,
 		&sumcheck_claims,
@@ -10,7 +10,7 @@ use binius_core::{
 	piop,
 	protocols::{
 		evalcheck::{EvalcheckMultilinearClaim, subclaims::MemoizedData},
-		fri::CommitOutput,
+		fri::{CommitOutput, CommittedCodeword},
 	},
 	ring_switch::{EvalClaimSystem, ReducedClaim, ReducedWitness, prove, verify},
 	transcript::ProverTranscript,
@@ -215,7 +215,6 @@ pub fn commit_prove_verify_piop<U, F, MTScheme, MTProver, Hal, HalHolder>(
 	let hal = compute_data_ref.hal;
 
 	let dev_alloc = &compute_data_ref.dev_alloc;
-	let host_alloc = &compute_data_ref.host_alloc;
 
 	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(oracles).unwrap();
 
@@ -256,16 +255,8 @@ pub fn commit_prove_verify_piop<U, F, MTScheme, MTProver, Hal, HalHolder>(
 	let ReducedWitness {
 		transparents: transparent_multilins,
 		sumcheck_claims,
-	} = prove(
-		&system,
-		&committed_multilins,
-		&mut proof,
-		MemoizedData::new(),
-		hal,
-		dev_alloc,
-		host_alloc,
-	)
-	.unwrap();
+	} = prove(&system, &committed_multilins, &mut proof, &mut MemoizedData::new(), hal, dev_alloc)
+		.unwrap();
 
 	piop::prove(
 		compute_data_ref,
@@ -274,7 +265,7 @@ pub fn commit_prove_verify_piop<U, F, MTScheme, MTProver, Hal, HalHolder>(
 		merkle_prover,
 		&commit_meta,
 		committed,
-		&codeword,
+		&CommittedCodeword::Host(codeword),
 		&committed_multilins,
 		transparent_multilins,
 		&sumcheck_claims,
@@ -0,0 +1,833 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Estimates of the work done by the verifier of a constraint system.
+//!
+//! [`estimate_verifier_cost`] walks through the phases of [`verify`](super::verify) for a given
+//! assignment of table sizes and counts the proof bytes, the invocations of the hash function,
+//! and the field multiplications, without producing a proof. The FRI and Merkle tree costs follow
+//! the commit parameters that the verifier chooses, the costs of the interactive reductions are
+//! derived from the shapes of the claims and are approximate. The estimate is meant for comparing
+//! circuit and parameter choices, not for predicting the cost of a particular proof exactly.
+//!
+//! [`plan_log_inv_rate`] uses the same FRI cost model to choose the code rate of the commit
+//! batches at [`CommitRate::Planned`].
+
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	mem,
+};
+
+use binius_field::{BinaryField, ExtensionField, TowerField, tower::TowerFamily};
+use binius_hash::PseudoCompressionFunction;
+use binius_math::CompositionPoly;
+use binius_utils::{
+	SerializationMode, SerializeBytes, checked_arithmetics::log2_ceil_usize,
+	sparse_index::SparseIndex,
+};
+use digest::{Digest, Output, OutputSizeUser, core_api::BlockSizeUser};
+use itertools::{chain, izip};
+
+use super::{
+	CommitRate, ConstraintSystem, ProofHeader, TableSizeSpec,
+	channel::{Flush, OracleOrConst},
+	common::{FDomain, FEncode, FExt},
+	error::Error,
+};
+use crate::{
+	merkle_tree::{BinaryMerkleTreeScheme, MerkleTreeScheme},
+	oracle::{CompositeMLE, ConstraintSet, MultilinearOracleSet, MultilinearPolyVariant, OracleId},
+	piop::{self, CommitMeta},
+	protocols::fri::{FRIParams, vcs_optimal_layers_depths_iter},
+};
+
+/// An estimate of the work done by the verifier of a constraint system.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerifierCost {
+	/// Number of bytes in the proof transcript.
+	pub proof_bytes: usize,
+	/// Number of invocations of the hash function, including the Merkle tree compressions and the
+	/// Fiat-Shamir challenger.
+	pub hash_invocations: usize,
+	/// Total number of bytes passed to the hash function.
+	pub hashed_bytes: usize,
+	/// Number of multiplications in the extension field.
+	pub field_mults: usize,
+}
+
+impl VerifierCost {
+	/// Returns the approximate gas cost of verifying a proof in an EVM contract.
+	///
+	/// This is meaningful for proofs that use Keccak-256 as the hash function, which the EVM
+	/// provides as an opcode. The estimate covers the transaction, the calldata carrying the
+	/// proof, the hashing, and the field arithmetic; memory expansion and the control flow of the
+	/// verifier contract are not accounted for.
+	pub fn evm_gas(&self, schedule: &EvmGasSchedule) -> u64 {
+		let hashed_words = self.hashed_bytes.div_ceil(32) as u64;
+		schedule.transaction
+			+ schedule.calldata_byte * self.proof_bytes as u64
+			+ schedule.keccak * self.hash_invocations as u64
+			+ schedule.keccak_word * hashed_words
+			+ schedule.field_mult * self.field_mults as u64
+	}
+}
+
+/// The gas prices used by [`VerifierCost::evm_gas`].
+///
+/// The default is the Ethereum mainnet schedule. The EVM has no binary field arithmetic, so the
+/// price of a multiplication in the 128-bit binary field is a rough assumption, which should be
+/// calibrated against the verifier contract in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvmGasSchedule {
+	/// Base cost of a transaction.
+	pub transaction: u64,
+	/// Cost of a non-zero byte of calldata.
+	pub calldata_byte: u64,
+	/// Static cost of the `KECCAK256` opcode.
+	pub keccak: u64,
+	/// Cost per 32-byte word hashed by the `KECCAK256` opcode.
+	pub keccak_word: u64,
+	/// Cost of a multiplication in the 128-bit binary field.
+	pub field_mult: u64,
+}
+
+impl Default for EvmGasSchedule {
+	fn default() -> Self {
+		Self {
+			transaction: 21_000,
+			calldata_byte: 16,
+			keccak: 30,
+			keccak_word: 6,
+			field_mult: 500,
+		}
+	}
+}
+
+/// Estimates the work done by [`verify`](super::verify) on a proof of a constraint system.
+///
+/// The hash function and the compression function are the ones the proof would be produced with,
+/// they determine the digest size and so the sizes of the commitments and Merkle paths.
+///
+/// ## Arguments
+///
+/// * `constraint_system` - the constraint system.
+/// * `table_sizes` - the number of rows of every table, as the prover would assign them.
+/// * `log_inv_rate` - the binary logarithm of the inverse Reed–Solomon code rate.
+/// * `security_bits` - the target security level in bits.
+///
+/// ## Throws
+///
+/// * [`Error::TableSizesLenMismatch`] and the other errors of
+///   [`ConstraintSystem::check_table_sizes`] if the table sizes do not match the specification
+/// * the errors of choosing the FRI parameters if the security level is unattainable
+pub fn estimate_verifier_cost<Tower, Hash, Compress>(
+	constraint_system: &ConstraintSystem<FExt<Tower>>,
+	table_sizes: &[usize],
+	log_inv_rate: usize,
+	security_bits: usize,
+) -> Result<VerifierCost, Error>
+where
+	Tower: TowerFamily,
+	Hash: Digest + BlockSizeUser + OutputSizeUser,
+	Compress: PseudoCompressionFunction<Output<Hash>, 2> + Default + Sync,
+{
+	constraint_system.check_table_sizes(table_sizes)?;
+	let oracles = constraint_system.oracles.instantiate(table_sizes)?;
+
+	let mut counter = CostCounter {
+		cost: VerifierCost::default(),
+		elem_size: FExt::<Tower>::N_BITS / 8,
+		digest_size: <Hash as Digest>::output_size(),
+	};
+
+	// The header, which precedes the transcript
+	let mut header = Vec::new();
+	ProofHeader::new(log_inv_rate, security_bits)
+		.serialize(&mut header, SerializationMode::CanonicalTower)
+		.expect("serializing into a Vec does not fail");
+	counter.advice_bytes(header.len());
+
+	// Constraint system digest and table sizes
+	counter.observe(counter.digest_size);
+	counter.message_bytes(table_sizes.len() * size_of::<u32>());
+
+	let merkle_scheme = BinaryMerkleTreeScheme::<FExt<Tower>, Hash, _>::new(Compress::default());
+	let commit_metas = piop::make_oracle_commit_metas(&oracles, &constraint_system.commit_batches)?;
+	let fri_params = make_commit_params::<_, FEncode<Tower>, _>(
+		constraint_system,
+		&commit_metas,
+		&merkle_scheme,
+		security_bits,
+		log_inv_rate,
+	)?;
+	counter.message_bytes(commit_metas.len() * counter.digest_size);
+
+	// Exponentiation, one GKR layer per exponent bit
+	let exponents = constraint_system
+		.exponents
+		.iter()
+		.filter(|exp| !oracles.is_zero_sized(exp.exp_result_id))
+		.collect::<Vec<_>>();
+	if let Some(max_n_vars) = exponents.iter().map(|exp| exp.n_vars(&oracles)).max() {
+		counter.sample(max_n_vars);
+		counter.message_elems(exponents.len());
+		let max_bits = exponents
+			.iter()
+			.map(|exp| exp.bits_ids.len())
+			.max()
+			.unwrap_or(0);
+		for layer in 0..max_bits {
+			let active = exponents.iter().filter(|exp| exp.bits_ids.len() > layer);
+			let n_rounds = active
+				.clone()
+				.map(|exp| exp.n_vars(&oracles))
+				.max()
+				.unwrap_or(0);
+			counter.sumcheck(n_rounds, 3);
+			for exp in active {
+				counter.message_elems(2);
+				counter.eq_ind(exp.n_vars(&oracles));
+			}
+		}
+	}
+	let mut claims = ClaimTracker::new(&oracles);
+	let exp_stage = claims.next_stage();
+	for exp in &exponents {
+		let point = EvalPointKey::sumcheck(exp_stage, exp.n_vars(&oracles));
+		let base_id = match exp.base {
+			OracleOrConst::Oracle(id) => Some(id),
+			OracleOrConst::Const { .. } => None,
+		};
+		for id in chain!(exp.bits_ids.iter().copied(), base_id) {
+			claims.claim(&mut counter, id, point.clone());
+		}
+	}
+
+	// Grand products of the non-zero oracles and the flushes, one batched GKR layer per variable
+	let flushes = constraint_system
+		.flushes
+		.iter()
+		.filter(|flush| table_sizes[flush.table_id] > 0)
+		.collect::<Vec<_>>();
+	let flush_n_vars =
+		|flush: &Flush<_>| flush.log_values_per_row + log2_ceil_usize(table_sizes[flush.table_id]);
+	let non_zero_ids = constraint_system
+		.non_zero_oracle_ids
+		.iter()
+		.copied()
+		.filter(|&id| !oracles.is_zero_sized(id))
+		.collect::<Vec<_>>();
+	let prodcheck_n_vars = chain!(
+		flushes.iter().map(|flush| flush_n_vars(flush)),
+		non_zero_ids.iter().map(|&id| oracles.n_vars(id))
+	)
+	.collect::<Vec<_>>();
+	counter.message_elems(non_zero_ids.len());
+	counter.sample(1 + constraint_system.channel_count);
+	counter.message_elems(flushes.len());
+	let max_prodcheck_n_vars = prodcheck_n_vars.iter().copied().max().unwrap_or(0);
+	for layer in 0..max_prodcheck_n_vars {
+		let n_active = prodcheck_n_vars
+			.iter()
+			.filter(|&&n_vars| n_vars > layer)
+			.count();
+		counter.sample(1);
+		counter.batch_sumcheck([(layer, 3, 2 * n_active + 1)]);
+		counter.sample(1);
+		for _ in 0..n_active {
+			counter.eq_ind(layer);
+		}
+	}
+	let prodcheck_stage = claims.next_stage();
+	for &id in &non_zero_ids {
+		let point = EvalPointKey::sumcheck(prodcheck_stage, oracles.n_vars(id));
+		claims.claim(&mut counter, id, point);
+	}
+
+	// MLE-checks reducing the evaluations of the flushes with selectors to the selectors and the
+	// flushed oracles, one for every point of the grand products. The flushes without selectors
+	// are linear combinations of the flushed oracles, which the evalcheck reduces.
+	let mut flush_mlechecks = BTreeMap::<usize, (usize, BTreeSet<OracleId>)>::new();
+	for flush in &flushes {
+		let n_vars = flush_n_vars(flush);
+		let oracle_ids = flush.oracles.iter().filter_map(|oracle| match oracle {
+			OracleOrConst::Oracle(id) => Some(*id),
+			OracleOrConst::Const { .. } => None,
+		});
+		// Tables of arbitrary size get a step-down selector for their padding rows
+		let step_down =
+			matches!(constraint_system.table_size_specs[flush.table_id], TableSizeSpec::Arbitrary);
+		let n_selectors = flush.selectors.len() + usize::from(step_down);
+		if n_selectors == 0 {
+			let point = EvalPointKey::sumcheck(prodcheck_stage, n_vars);
+			counter.message_bytes(size_of::<u32>());
+			claims.linear_combination(&mut counter, oracle_ids, &point);
+		} else {
+			let (degree, inner_ids) = flush_mlechecks.entry(n_vars).or_default();
+			*degree = (*degree).max(n_selectors + 1);
+			inner_ids.extend(chain!(flush.selectors.iter().copied(), oracle_ids));
+		}
+	}
+	counter.sample(flush_mlechecks.len());
+	counter.batch_sumcheck(
+		flush_mlechecks
+			.iter()
+			.map(|(&n_vars, (degree, inner_ids))| (n_vars, degree + 1, inner_ids.len() + 1)),
+	);
+	let flush_stage = claims.next_stage();
+	for (&n_vars, (_, inner_ids)) in &flush_mlechecks {
+		counter.eq_ind(n_vars);
+		for &id in inner_ids {
+			claims.claim(&mut counter, id, EvalPointKey::sumcheck(flush_stage, n_vars));
+		}
+	}
+
+	// Zerocheck, with a univariate round over the first variables of all tables, followed by a
+	// batched eq-ind sumcheck over the remaining variables and the univariatizing reduction
+	let constraint_sets = constraint_system
+		.table_constraints
+		.iter()
+		.filter(|set| table_sizes[set.table_id] > 0)
+		.collect::<Vec<_>>();
+	let set_n_vars = |set: &ConstraintSet<_>| {
+		set.log_values_per_row + log2_ceil_usize(table_sizes[set.table_id])
+	};
+	let set_degree = |set: &ConstraintSet<_>| {
+		set.constraints
+			.iter()
+			.map(|constraint| constraint.composition.degree())
+			.max()
+			.unwrap_or(0)
+	};
+	let max_zerocheck_n_vars = constraint_sets
+		.iter()
+		.map(|set| set_n_vars(set))
+		.max()
+		.unwrap_or(0);
+	let skip_rounds = constraint_sets
+		.iter()
+		.map(|set| FDomain::<Tower>::N_BITS.saturating_sub(log2_ceil_usize(set_degree(set))))
+		.min()
+		.unwrap_or(0)
+		.min(max_zerocheck_n_vars);
+	let domain_size = constraint_sets
+		.iter()
+		.map(|set| set_degree(set) << skip_rounds)
+		.max()
+		.unwrap_or(0);
+	counter.sample(max_zerocheck_n_vars - skip_rounds + constraint_sets.len());
+	counter.message_elems(domain_size - domain_size.min(1 << skip_rounds));
+	counter.sample(1);
+	counter.cost.field_mults += domain_size;
+	counter.batch_sumcheck(constraint_sets.iter().map(|set| {
+		(
+			set_n_vars(set).saturating_sub(skip_rounds),
+			set_degree(set) + 1,
+			set.oracle_ids.len() + 1,
+		)
+	}));
+	let n_zerocheck_oracles = constraint_sets
+		.iter()
+		.map(|set| set.oracle_ids.len())
+		.sum::<usize>();
+	counter.sample(1);
+	counter.batch_sumcheck([(skip_rounds, 2, n_zerocheck_oracles + 1)]);
+	for set in &constraint_sets {
+		counter.eq_ind(set_n_vars(set));
+		for constraint in &set.constraints {
+			counter.cost.field_mults += constraint.composition.eval_cost().mult_cost_approx() + 1;
+		}
+		for &id in &set.oracle_ids {
+			claims.claim(&mut counter, id, EvalPointKey::zerocheck());
+		}
+	}
+
+	// Evalcheck, down to the claims on the committed oracles
+	claims.reduce(&mut counter);
+
+	// Ring switch and the PIOP compiler for every commit batch
+	for ((commit_meta, oracle_to_commit_index), fri_params) in commit_metas.iter().zip(&fri_params)
+	{
+		let batch_claims = claims
+			.committed
+			.iter()
+			.filter(|(id, _)| oracle_to_commit_index.get(id.index()).is_some())
+			.map(|(id, point)| (FExt::<Tower>::TOWER_LEVEL - oracles[*id].tower_level, point))
+			.collect::<Vec<_>>();
+
+		// A tensor algebra element, packed into the extension field, for every distinct prefix of
+		// the evaluation points and a row-batched evaluation for every claim
+		let prefixes = batch_claims
+			.iter()
+			.map(|&(kappa, point)| (kappa, point.origin))
+			.collect::<BTreeSet<_>>();
+		counter.sample(log2_ceil_usize(batch_claims.len()));
+		for &(kappa, _) in &prefixes {
+			counter.message_elems(1 << kappa);
+			counter.cost.field_mults += (kappa + 1) << kappa;
+		}
+		counter.sample(prefixes.iter().map(|&(kappa, _)| kappa).max().unwrap_or(0));
+		counter.message_elems(batch_claims.len());
+
+		// The sumcheck over the committed oracles and the equality indicators of the distinct
+		// suffixes of the evaluation points, interleaved with the FRI folding
+		let n_suffixes = batch_claims.iter().collect::<BTreeSet<_>>().len();
+		counter.sumcheck(commit_meta.total_vars(), 2);
+		counter.message_elems(commit_meta.total_multilins() + n_suffixes);
+		counter.fri(fri_params, &merkle_scheme);
+	}
+
+	Ok(counter.cost)
+}
+
+/// Chooses the code rate of a commit batch that minimizes the estimated cost of committing and
+/// opening it, and returns the binary logarithm of its inverse.
+///
+/// The cost of a rate is the size of the codeword that the prover encodes and hashes plus the
+/// size of the FRI opening proof, where a byte of proof costs as much as `proof_weight` bytes of
+/// codeword. A lower rate shortens the proof, as fewer queries reach the security level, but
+/// lengthens the codeword, which dominates the cost of large batches. The inverse rates from 2 up
+/// to `2^max_log_inv_rate` are considered.
+///
+/// ## Throws
+///
+/// * the errors of choosing the FRI parameters if the security level is unattainable at every
+///   considered rate
+pub fn plan_log_inv_rate<F, FEncode, MTScheme>(
+	commit_meta: &CommitMeta,
+	merkle_scheme: &MTScheme,
+	security_bits: usize,
+	max_log_inv_rate: usize,
+	proof_weight: usize,
+) -> Result<usize, Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	MTScheme: MerkleTreeScheme<F>,
+{
+	let mut best = None;
+	let mut last_err = None;
+	for log_inv_rate in 1..=max_log_inv_rate.max(1) {
+		let fri_params = match piop::make_commit_params_with_optimal_arity::<F, FEncode, _>(
+			commit_meta,
+			merkle_scheme,
+			security_bits,
+			log_inv_rate,
+		) {
+			Ok(fri_params) => fri_params,
+			Err(err) => {
+				last_err = Some(err);
+				continue;
+			}
+		};
+
+		let mut counter = CostCounter {
+			cost: VerifierCost::default(),
+			elem_size: F::N_BITS / 8,
+			digest_size: size_of::<MTScheme::Digest>(),
+		};
+		counter.fri(&fri_params, merkle_scheme);
+		let cost =
+			(counter.elem_size << fri_params.log_len()) + proof_weight * counter.cost.proof_bytes;
+		if best.is_none_or(|(best_cost, _)| cost < best_cost) {
+			best = Some((cost, log_inv_rate));
+		}
+	}
+
+	match (best, last_err) {
+		(Some((_, log_inv_rate)), _) => Ok(log_inv_rate),
+		(None, Some(err)) => Err(err.into()),
+		(None, None) => unreachable!("at least one rate is considered"),
+	}
+}
+
+/// Chooses the FRI parameters of every commit batch of [`piop::make_oracle_commit_metas`], at the
+/// code rate that the constraint system assigns to the batch.
+///
+/// `log_inv_rate` is the rate of the batches at [`CommitRate::Default`].
+pub(super) fn make_commit_params<F, FEncode, MTScheme>(
+	constraint_system: &ConstraintSystem<F>,
+	commit_metas: &[(CommitMeta, SparseIndex<usize>)],
+	merkle_scheme: &MTScheme,
+	security_bits: usize,
+	log_inv_rate: usize,
+) -> Result<Vec<FRIParams<F, FEncode>>, Error>
+where
+	F: TowerField + ExtensionField<FEncode>,
+	FEncode: BinaryField,
+	MTScheme: MerkleTreeScheme<F>,
+{
+	commit_metas
+		.iter()
+		.enumerate()
+		.map(|(batch, (commit_meta, _))| {
+			let log_inv_rate = match constraint_system.commit_rate(batch) {
+				CommitRate::Default => log_inv_rate,
+				CommitRate::Fixed { log_inv_rate } => log_inv_rate,
+				CommitRate::Planned {
+					max_log_inv_rate,
+					proof_weight,
+				} => plan_log_inv_rate::<F, FEncode, _>(
+					commit_meta,
+					merkle_scheme,
+					security_bits,
+					max_log_inv_rate,
+					proof_weight,
+				)?,
+			};
+			let fri_params = piop::make_commit_params_with_optimal_arity(
+				commit_meta,
+				merkle_scheme,
+				security_bits,
+				log_inv_rate,
+			)?;
+			Ok(match constraint_system.commit_log_leaf_size(batch) {
+				Some(log_leaf_size) => fri_params
+					.with_log_leaf_size(log_leaf_size)
+					.map_err(piop::Error::from)?,
+				None => fri_params,
+			})
+		})
+		.collect()
+}
+
+/// Accumulates the verifier cost of the transcript operations and the primitive checks.
+struct CostCounter {
+	cost: VerifierCost,
+	elem_size: usize,
+	digest_size: usize,
+}
+
+impl CostCounter {
+	/// Bytes absorbed by the Fiat-Shamir challenger.
+	fn observe(&mut self, n_bytes: usize) {
+		self.cost.hashed_bytes += n_bytes;
+	}
+
+	/// Bytes of a prover message, which are part of the proof and observed by the challenger.
+	fn message_bytes(&mut self, n_bytes: usize) {
+		self.cost.proof_bytes += n_bytes;
+		self.observe(n_bytes);
+	}
+
+	fn message_elems(&mut self, n_elems: usize) {
+		self.message_bytes(n_elems * self.elem_size);
+	}
+
+	/// Bytes of decommitment advice, which are part of the proof but not observed.
+	fn advice_bytes(&mut self, n_bytes: usize) {
+		self.cost.proof_bytes += n_bytes;
+	}
+
+	/// Samples challenges, where every digest of output costs a hash invocation that is fed
+	/// forward into the challenger.
+	fn sample(&mut self, n_elems: usize) {
+		let n_invocations = (n_elems * self.elem_size).div_ceil(self.digest_size);
+		self.cost.hash_invocations += n_invocations;
+		self.cost.hashed_bytes += n_invocations * self.digest_size;
+	}
+
+	/// A sumcheck with a round polynomial of the given degree in every round.
+	fn sumcheck(&mut self, n_rounds: usize, degree: usize) {
+		for _ in 0..n_rounds {
+			self.message_elems(degree);
+			self.sample(1);
+			self.cost.field_mults += degree;
+		}
+	}
+
+	/// A front-loaded batched sumcheck over claims with the given numbers of variables, degrees
+	/// and numbers of multilinears, in which the claims with fewer variables finish early.
+	fn batch_sumcheck(&mut self, claims: impl IntoIterator<Item = (usize, usize, usize)>) {
+		let claims = claims.into_iter().collect::<Vec<_>>();
+		let n_rounds = claims.iter().map(|&(n_vars, ..)| n_vars).max().unwrap_or(0);
+		for round in 0..n_rounds {
+			let degree = claims
+				.iter()
+				.filter(|&&(n_vars, ..)| n_vars > round)
+				.map(|&(_, degree, _)| degree)
+				.max()
+				.unwrap_or(0);
+			self.message_elems(degree);
+			self.sample(1);
+			self.cost.field_mults += degree;
+		}
+		self.message_elems(
+			claims
+				.iter()
+				.map(|&(.., n_multilinears)| n_multilinears)
+				.sum(),
+		);
+	}
+
+	/// The evaluation of an equality indicator at the sampled point.
+	fn eq_ind(&mut self, n_vars: usize) {
+		self.cost.field_mults += 2 * n_vars;
+	}
+
+	fn hash(&mut self, n_bytes: usize) {
+		self.cost.hash_invocations += 1;
+		self.cost.hashed_bytes += n_bytes;
+	}
+
+	fn compress(&mut self, n_compressions: usize) {
+		self.cost.hash_invocations += n_compressions;
+		self.cost.hashed_bytes += n_compressions * 2 * self.digest_size;
+	}
+
+	/// The FRI proximity test, with the Merkle layers, the terminal codeword and the queries.
+	fn fri<F, FA, MTScheme>(&mut self, fri_params: &FRIParams<F, FA>, merkle_scheme: &MTScheme)
+	where
+		F: TowerField + ExtensionField<FA>,
+		FA: BinaryField,
+		MTScheme: MerkleTreeScheme<F>,
+	{
+		// Commitments of the folded oracles and the folding challenges
+		self.message_bytes(fri_params.n_oracles() * self.digest_size);
+		self.sample(fri_params.n_fold_rounds());
+
+		// The terminal codeword is re-encoded by the verifier
+		let log_terminate_len =
+			fri_params.n_final_challenges() + fri_params.rs_code().log_inv_rate();
+		self.advice_bytes(self.elem_size << log_terminate_len);
+		self.cost.field_mults += (log_terminate_len << log_terminate_len) / 2;
+
+		let layer_depths =
+			vcs_optimal_layers_depths_iter(fri_params, merkle_scheme).collect::<Vec<_>>();
+		for &layer_depth in &layer_depths {
+			self.advice_bytes(self.digest_size << layer_depth);
+			self.compress((1 << layer_depth) - 1);
+		}
+
+		let tree_depths = fri_params.oracle_tree_depths().collect::<Vec<_>>();
+		let index_bytes = fri_params.index_bits().div_ceil(8);
+		for _ in 0..fri_params.n_test_queries() {
+			let n_invocations = index_bytes.div_ceil(self.digest_size);
+			self.cost.hash_invocations += n_invocations;
+			self.cost.hashed_bytes += n_invocations * self.digest_size;
+			for (&arity, log_leaf_size, &tree_depth, &layer_depth) in izip!(
+				fri_params.fold_arities(),
+				fri_params.oracle_log_leaf_sizes(),
+				&tree_depths,
+				&layer_depths
+			) {
+				let leaf_bytes = self.elem_size << log_leaf_size;
+				self.advice_bytes(leaf_bytes);
+				self.hash(leaf_bytes);
+
+				let path_len = tree_depth - layer_depth;
+				self.advice_bytes(path_len * self.digest_size);
+				self.compress(path_len);
+
+				self.cost.field_mults += arity << arity;
+			}
+		}
+	}
+}
+
+/// The sumcheck that produced an evaluation point.
+///
+/// A batched sumcheck evaluates its claims with the same number of variables at the same point.
+/// The zerocheck evaluates the claims of all tables at points that begin with the challenges of
+/// its univariate round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum PointOrigin {
+	Zerocheck,
+	Sumcheck { stage: usize, n_vars: usize },
+}
+
+/// An evaluation point, up to the values of the challenges.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct EvalPointKey {
+	origin: PointOrigin,
+	/// The virtual oracles that derived the point from the point of its origin, which leave the
+	/// first variables of the point in place.
+	derivations: Vec<OracleId>,
+}
+
+impl EvalPointKey {
+	const fn zerocheck() -> Self {
+		Self {
+			origin: PointOrigin::Zerocheck,
+			derivations: Vec::new(),
+		}
+	}
+
+	const fn sumcheck(stage: usize, n_vars: usize) -> Self {
+		Self {
+			origin: PointOrigin::Sumcheck { stage, n_vars },
+			derivations: Vec::new(),
+		}
+	}
+
+	fn derive(&self, id: OracleId) -> Self {
+		let mut derived = self.clone();
+		derived.derivations.push(id);
+		derived
+	}
+}
+
+/// Follows the evaluation claims through the evalcheck to the claims on the committed oracles,
+/// and accounts for the evalcheck messages and sumchecks along the way.
+struct ClaimTracker<'a, F: TowerField> {
+	oracles: &'a MultilinearOracleSet<F>,
+	n_stages: usize,
+	visited: BTreeSet<(OracleId, EvalPointKey)>,
+	committed: BTreeSet<(OracleId, EvalPointKey)>,
+	/// The inner oracles of the shifted and packed oracles and their numbers of variables, which
+	/// are reduced by bivariate sumchecks.
+	bivariate: Vec<(OracleId, usize)>,
+	/// The composite oracles by evaluation point, which are reduced by MLE-checks.
+	mlechecks: BTreeMap<EvalPointKey, Vec<&'a CompositeMLE<F>>>,
+}
+
+impl<'a, F: TowerField> ClaimTracker<'a, F> {
+	const fn new(oracles: &'a MultilinearOracleSet<F>) -> Self {
+		Self {
+			oracles,
+			n_stages: 0,
+			visited: BTreeSet::new(),
+			committed: BTreeSet::new(),
+			bivariate: Vec::new(),
+			mlechecks: BTreeMap::new(),
+		}
+	}
+
+	/// Returns a new stage for the points of a sumcheck.
+	const fn next_stage(&mut self) -> usize {
+		self.n_stages += 1;
+		self.n_stages - 1
+	}
+
+	/// A claim that the evalcheck receives, which is either new or refers to an earlier claim.
+	fn claim(&mut self, counter: &mut CostCounter, id: OracleId, point: EvalPointKey) {
+		counter.message_bytes(size_of::<u32>());
+		if self.visited.contains(&(id, point.clone())) {
+			counter.message_bytes(size_of::<u32>());
+		} else {
+			self.visit(counter, id, point);
+		}
+	}
+
+	/// A claim on a linear combination, which sends the evaluations of the inner oracles that are
+	/// not claimed yet.
+	fn linear_combination(
+		&mut self,
+		counter: &mut CostCounter,
+		inner_ids: impl IntoIterator<Item = OracleId>,
+		point: &EvalPointKey,
+	) {
+		for id in inner_ids {
+			counter.message_bytes(size_of::<u32>());
+			if self.visited.contains(&(id, point.clone())) {
+				counter.message_bytes(size_of::<u32>());
+			} else {
+				counter.message_elems(1);
+				self.visit(counter, id, point.clone());
+			}
+		}
+	}
+
+	fn visit(&mut self, counter: &mut CostCounter, id: OracleId, point: EvalPointKey) {
+		if !self.visited.insert((id, point.clone())) {
+			return;
+		}
+		let oracles = self.oracles;
+		let oracle = &oracles[id];
+		match &oracle.variant {
+			MultilinearPolyVariant::Committed => {
+				self.committed.insert((id, point));
+			}
+			MultilinearPolyVariant::Transparent(_) | MultilinearPolyVariant::Structured(_) => {
+				counter.cost.field_mults += oracle.n_vars;
+			}
+			MultilinearPolyVariant::Repeating { id: inner_id, .. } => {
+				self.claim(counter, *inner_id, point.derive(id));
+			}
+			MultilinearPolyVariant::Projected(projected) => {
+				self.claim(counter, projected.id(), point.derive(id));
+			}
+			MultilinearPolyVariant::ZeroPadded(padded) => {
+				self.claim(counter, padded.id(), point.derive(id));
+			}
+			MultilinearPolyVariant::Shifted(shifted) => {
+				self.bivariate
+					.push((shifted.id(), oracles.n_vars(shifted.id())));
+			}
+			MultilinearPolyVariant::Packed(packed) => {
+				self.bivariate
+					.push((packed.id(), oracles.n_vars(packed.id())));
+			}
+			MultilinearPolyVariant::Composite(composite) => {
+				counter.message_bytes(size_of::<u32>());
+				self.mlechecks.entry(point).or_default().push(composite);
+			}
+			MultilinearPolyVariant::LinearCombination(linear_combination) => {
+				counter.cost.field_mults += linear_combination.n_polys();
+				self.linear_combination(counter, linear_combination.polys(), &point);
+			}
+		}
+	}
+
+	/// Runs the bivariate sumchecks and the MLE-checks of the evalcheck and follows the claims
+	/// that they produce, until only the claims on committed oracles remain.
+	fn reduce(&mut self, counter: &mut CostCounter) {
+		loop {
+			let bivariate = mem::take(&mut self.bivariate);
+			let mlechecks = mem::take(&mut self.mlechecks);
+			if bivariate.is_empty() && mlechecks.is_empty() {
+				break;
+			}
+			let mut new_claims = Vec::new();
+
+			if !bivariate.is_empty() {
+				// One claim for every number of variables, over the inner oracles and the
+				// transparent shift or packing indicators
+				let mut n_multilinears = BTreeMap::<usize, usize>::new();
+				for &(_, n_vars) in &bivariate {
+					*n_multilinears.entry(n_vars).or_default() += 2;
+				}
+				counter.sample(n_multilinears.len());
+				counter.batch_sumcheck(
+					n_multilinears
+						.into_iter()
+						.map(|(n_vars, n_multilinears)| (n_vars, 2, n_multilinears)),
+				);
+				let stage = self.next_stage();
+				new_claims.extend(
+					bivariate
+						.into_iter()
+						.map(|(id, n_vars)| (id, EvalPointKey::sumcheck(stage, n_vars))),
+				);
+			}
+
+			if !mlechecks.is_empty() {
+				let composites = mlechecks.into_values().collect::<Vec<_>>();
+				counter.sample(composites.len());
+				counter.batch_sumcheck(composites.iter().map(|composites| {
+					let n_vars = composites[0].n_vars();
+					let degree = composites
+						.iter()
+						.map(|composite| CompositionPoly::<F>::degree(composite.c()))
+						.max()
+						.unwrap_or(0);
+					let n_inner = composites
+						.iter()
+						.flat_map(|composite| composite.polys())
+						.collect::<BTreeSet<_>>()
+						.len();
+					(n_vars, degree + 1, n_inner + 1)
+				}));
+				let stage = self.next_stage();
+				for composite in composites.into_iter().flatten() {
+					counter.eq_ind(composite.n_vars());
+					let point = EvalPointKey::sumcheck(stage, composite.n_vars());
+					new_claims.extend(composite.polys().map(|id| (id, point.clone())));
+				}
+			}
+
+			for (id, point) in new_claims {
+				self.claim(counter, id, point);
+			}
+		}
+	}
+}
@@ -1,5 +1,9 @@
 // Copyright 2024-2025 Irreducible Inc.
 
+use std::time::Duration;
+
+use binius_utils::error_code::{ErrorCode, ErrorDomain, HasErrorCode};
+
 use super::channel::ChannelId;
 use crate::{
 	constraint_system::TableId,
@@ -72,6 +76,51 @@ pub enum Error {
 	#[error("Tower level cannot be more than 7")]
 	IncorrectTowerLevel,
 
+	#[error("hash scheme {name} is not registered")]
+	UnknownHashScheme { name: String },
+
+	#[error("hash scheme {name} is registered more than once")]
+	HashSchemeNameConflict { name: String },
+
+	#[error("profile {name} is not known")]
+	UnknownProfile { name: String },
+
+	#[error(
+		"constraint {name} of table {table_id} has degree {degree}, which exceeds the maximum degree {max}"
+	)]
+	ConstraintDegreeTooLarge {
+		table_id: TableId,
+		name: String,
+		degree: usize,
+		max: usize,
+	},
+
+	#[error("index {index} is out of range for oracle {oracle} with n_vars={n_vars}")]
+	OpeningIndexOutOfRange {
+		oracle: OracleId,
+		index: usize,
+		n_vars: usize,
+	},
+
+	#[error("the prover was cancelled")]
+	ProverCancelled,
+
+	#[error("the prover exceeded its time limit of {limit:?} after {elapsed:?}")]
+	ProverTimeLimitExceeded { limit: Duration, elapsed: Duration },
+
+	#[error("the prover exceeded its memory limit of {limit} bytes with {used} bytes resident")]
+	ProverMemoryLimitExceeded { limit: usize, used: usize },
+
+	#[error("constraint set {index} is out of range, the system has {count} constraint sets")]
+	ConstraintSetOutOfRange { index: usize, count: usize },
+
+	#[error("constraint set {index} has {expected} oracles, instead got {got} oracle evaluations")]
+	ConstraintSetEvalsLenMismatch {
+		index: usize,
+		expected: usize,
+		got: usize,
+	},
+
 	#[error("{oracle} underlier witness data does not match")]
 	PackedUnderlierMismatch { oracle: String },
 
@@ -131,4 +180,105 @@ pub enum VerificationError {
 		"Channel with id={id} is not balanced. Pushes and pulls do not contain the same elements"
 	)]
 	ChannelUnbalanced { id: ChannelId },
+	#[error("the constraint system digest does not match the constraint system")]
+	ConstraintSystemDigestMismatch,
+	#[error("the proof was produced by protocol version {got}, the verifier runs {expected}")]
+	ProtocolVersionMismatch { expected: String, got: String },
+	#[error(
+		"the proof was produced with log_inv_rate={got_log_inv_rate} and \
+		 security_bits={got_security_bits}, the verifier expects \
+		 log_inv_rate={expected_log_inv_rate} and security_bits={expected_security_bits}"
+	)]
+	ProofParametersMismatch {
+		expected_log_inv_rate: usize,
+		expected_security_bits: usize,
+		got_log_inv_rate: usize,
+		got_security_bits: usize,
+	},
+	#[error("the proof was produced with profile {got}, the verifier expects {expected}")]
+	ProfileMismatch { expected: String, got: String },
+}
+
+impl HasErrorCode for Error {
+	fn error_code(&self) -> ErrorCode {
+		let index = match self {
+			Self::FlushSelectorTowerLevel { .. } => 1,
+			Self::EmptyFlushOracles => 2,
+			Self::ChannelFlushWidthMismatch { .. } => 3,
+			Self::ChannelFlushNvarsMismatch { .. } => 4,
+			Self::ChannelIdOutOfRange { .. } => 5,
+			Self::VirtualOracleEvalMismatch { .. } => 6,
+			Self::VirtualOracleNvarsMismatch { .. } => 7,
+			Self::TableSizePowerOfTwoRequired { .. } => 8,
+			Self::TableSizeFixedRequired { .. } => 9,
+			Self::TableSizesLenMismatch { .. } => 10,
+			Self::IncompatibleFlushSelector { .. } => 11,
+			Self::Zeros => 12,
+			Self::FalseEqEvaluationClaim => 13,
+			Self::CannotCommitTowerLevel { .. } => 14,
+			Self::IncorrectTowerLevel => 15,
+			Self::UnknownHashScheme { .. } => 16,
+			Self::HashSchemeNameConflict { .. } => 17,
+			Self::PackedUnderlierMismatch { .. } => 18,
+			Self::Witness(_) => 19,
+			Self::Constraint(_) => 20,
+			Self::Polynomial(_) => 21,
+			Self::Evalcheck(_) => 22,
+			Self::Prodcheck(_) => 23,
+			Self::Oracle(_) => 24,
+			Self::HalError(_) => 25,
+			Self::MathError(_) => 26,
+			Self::NTTError(_) => 27,
+			Self::PolyCommitError(_) => 28,
+			Self::PIOPCompilerError(err) => return err.error_code(),
+			Self::RingSwitch(err) => return err.error_code(),
+			Self::Verification(err) => return err.error_code(),
+			Self::TranscriptError(_) => 29,
+			Self::GkrExp(_) => 30,
+			Self::UnknownProfile { .. } => 31,
+			Self::OpeningIndexOutOfRange { .. } => 32,
+			Self::ConstraintDegreeTooLarge { .. } => 33,
+			Self::ProverCancelled => 34,
+			Self::ProverTimeLimitExceeded { .. } => 35,
+			Self::ProverMemoryLimitExceeded { .. } => 36,
+			Self::ConstraintSetOutOfRange { .. } => 37,
+			Self::ConstraintSetEvalsLenMismatch { .. } => 38,
+		};
+		ErrorCode::new(ErrorDomain::ConstraintSystem, index)
+	}
+}
+
+impl HasErrorCode for VerificationError {
+	fn error_code(&self) -> ErrorCode {
+		let index = match self {
+			Self::IncorrectNumberOfCommitments => 101,
+			Self::IncorrectNumberOfFlushProducts => 102,
+			Self::ChannelUnbalanced { .. } => 103,
+			Self::ConstraintSystemDigestMismatch => 104,
+			Self::ProtocolVersionMismatch { .. } => 105,
+			Self::ProofParametersMismatch { .. } => 106,
+			Self::ProfileMismatch { .. } => 107,
+		};
+		ErrorCode::new(ErrorDomain::ConstraintSystem, index)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_utils::error_code::{ErrorCode, ErrorDomain, HasErrorCode};
+
+	use super::*;
+	use crate::protocols::fri;
+
+	#[test]
+	fn test_error_code_of_wrapped_error() {
+		let err = Error::PIOPCompilerError(piop::Error::FRI(fri::Error::EarlyProverFinish));
+		assert_eq!(err.error_code(), ErrorCode::new(ErrorDomain::Fri, 12));
+
+		let err = Error::TableSizesLenMismatch {
+			expected: 2,
+			got: 1,
+		};
+		assert_eq!(err.error_code().to_string(), "E01010");
+	}
 }
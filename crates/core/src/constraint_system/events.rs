@@ -0,0 +1,313 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Structured events emitted by [`prove`](super::prove) and [`verify`](super::verify).
+//!
+//! The spans of the prover are meant for profiling and change with the implementation. The events
+//! in this module instead follow a stable schema, so that dashboards can consume the timings and
+//! sizes of proofs with any [`tracing`] subscriber, without parsing the profiles.
+//!
+//! All events are emitted at the `INFO` level with the target [`TARGET`] and carry the fields
+//! `schema_version` (currently [`SCHEMA_VERSION`]) and `role`, which is either `"prover"` or
+//! `"verifier"`. The events are, by name:
+//!
+//! * `table` — the size of a table, with the fields `table_id` and `table_size` (the number of
+//!   rows). Emitted once per table, after the table sizes are known.
+//! * `phase` — a completed protocol phase, with the fields `phase` (see [`Phase::as_str`]),
+//!   `duration_us` (the wall-clock time of the phase in microseconds), and `proof_bytes` (the
+//!   number of proof bytes written by the prover or read by the verifier during the phase).
+//! * `proof` — a completed proof or verification, with the fields `duration_us` and `proof_bytes`,
+//!   which cover the whole proof.
+//!
+//! The events are compiled out unless the `structured_events` feature is enabled. On targets
+//! without a clock, such as `wasm32-unknown-unknown`, only the `table` events are emitted.
+//!
+//! The phases are recorded regardless of the feature, and the verifier returns them in the
+//! [`VerificationReport`](super::VerificationReport) of
+//! [`verify_with_report`](super::verify_with_report). The clock is only read if the feature is
+//! enabled or a report is requested.
+
+use std::time::{Duration, Instant};
+
+/// The target of the events.
+pub const TARGET: &str = "binius::events";
+
+/// The version of the event schema, which is incremented on incompatible changes.
+pub const SCHEMA_VERSION: u64 = 1;
+
+/// The party that emits an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+	Prover,
+	Verifier,
+}
+
+impl Role {
+	/// The value of the `role` field.
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Prover => "prover",
+			Self::Verifier => "verifier",
+		}
+	}
+}
+
+/// A phase of the protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+	/// Finalization of the witness, which is only performed by the prover.
+	Witness,
+	/// Commitment of the committed multilinears.
+	Commit,
+	/// The GKR exponentiation argument.
+	Exp,
+	/// The grand product arguments for the non-zero checks and the channel flushes.
+	Prodcheck,
+	/// The zerocheck of the table constraints.
+	Zerocheck,
+	/// Reduction of the evaluation claims to the committed multilinears.
+	Evalcheck,
+	/// The ring-switching reduction.
+	RingSwitch,
+	/// The compiled PIOP, which includes the FRI proximity test.
+	PiopCompiler,
+}
+
+impl Phase {
+	/// The value of the `phase` field.
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Witness => "witness",
+			Self::Commit => "commit",
+			Self::Exp => "exp",
+			Self::Prodcheck => "prodcheck",
+			Self::Zerocheck => "zerocheck",
+			Self::Evalcheck => "evalcheck",
+			Self::RingSwitch => "ring_switch",
+			Self::PiopCompiler => "piop_compiler",
+		}
+	}
+}
+
+/// A completed protocol phase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseReport {
+	pub phase: Phase,
+	/// The wall-clock time of the phase, or `None` on targets without a clock.
+	pub duration: Option<Duration>,
+	/// The number of proof bytes written by the prover or read by the verifier during the phase.
+	pub proof_bytes: usize,
+}
+
+/// Emits the `table` events for the table sizes.
+pub(super) fn emit_table_sizes(role: Role, table_sizes: &[usize]) {
+	if !ENABLED {
+		return;
+	}
+	for (table_id, &table_size) in table_sizes.iter().enumerate() {
+		tracing::event!(
+			name: "table",
+			target: TARGET,
+			tracing::Level::INFO,
+			schema_version = SCHEMA_VERSION,
+			role = role.as_str(),
+			table_id,
+			table_size,
+		);
+	}
+}
+
+/// Records consecutive phases, and emits their `phase` events and the final `proof` event.
+///
+/// The transcript positions passed to the methods are the number of proof bytes written or read so
+/// far, and the bytes between two positions are attributed to the phase entered before.
+#[derive(Debug)]
+pub(super) struct PhaseEvents {
+	role: Role,
+	/// Whether the phases are timed even if the events are disabled.
+	timed: bool,
+	start: Option<Instant>,
+	current: Option<(Phase, Option<Instant>, usize)>,
+	phases: Vec<PhaseReport>,
+}
+
+impl PhaseEvents {
+	/// Creates the recorder, which times the phases only if the events are enabled or `timed` is
+	/// set.
+	pub(super) fn new(role: Role, timed: bool) -> Self {
+		Self {
+			role,
+			timed,
+			start: now(timed),
+			current: None,
+			phases: Vec::new(),
+		}
+	}
+
+	/// Completes the current phase, if any, and enters `phase`.
+	pub(super) fn enter(&mut self, phase: Phase, transcript_position: usize) {
+		self.complete_phase(transcript_position);
+		self.current = Some((phase, now(self.timed), transcript_position));
+	}
+
+	/// Completes the current phase, if any, and the proof.
+	///
+	/// Returns the completed phases in order and the wall-clock time of the whole proof.
+	pub(super) fn finish(
+		mut self,
+		transcript_position: usize,
+	) -> (Vec<PhaseReport>, Option<Duration>) {
+		self.complete_phase(transcript_position);
+		let duration = self.start.map(|start| start.elapsed());
+		if let (true, Some(duration)) = (ENABLED, duration) {
+			tracing::event!(
+				name: "proof",
+				target: TARGET,
+				tracing::Level::INFO,
+				schema_version = SCHEMA_VERSION,
+				role = self.role.as_str(),
+				duration_us = duration.as_micros() as u64,
+				proof_bytes = transcript_position,
+			);
+		}
+		(self.phases, duration)
+	}
+
+	fn complete_phase(&mut self, transcript_position: usize) {
+		let Some((phase, start, start_position)) = self.current.take() else {
+			return;
+		};
+		let report = PhaseReport {
+			phase,
+			duration: start.map(|start| start.elapsed()),
+			proof_bytes: transcript_position - start_position,
+		};
+		if let (true, Some(duration)) = (ENABLED, report.duration) {
+			tracing::event!(
+				name: "phase",
+				target: TARGET,
+				tracing::Level::INFO,
+				schema_version = SCHEMA_VERSION,
+				role = self.role.as_str(),
+				phase = phase.as_str(),
+				duration_us = duration.as_micros() as u64,
+				proof_bytes = report.proof_bytes,
+			);
+		}
+		self.phases.push(report);
+	}
+}
+
+const ENABLED: bool = cfg!(feature = "structured_events");
+
+/// Returns the current time if the events are enabled or `timed` is set, and the target has a
+/// clock.
+///
+/// The events are emitted only if a start time was recorded, so disabling the feature removes
+/// them as dead code, and the clock is not read unless a report is requested.
+fn now(timed: bool) -> Option<Instant> {
+	if ENABLED || timed {
+		binius_utils::time::now()
+	} else {
+		None
+	}
+}
+
+#[cfg(all(test, feature = "structured_events"))]
+mod tests {
+	use std::{
+		fmt::Debug,
+		sync::{Arc, Mutex},
+	};
+
+	use tracing::{
+		Event, Metadata, Subscriber,
+		field::{Field, Visit},
+		span::{Attributes, Id, Record},
+		subscriber::Interest,
+	};
+
+	use super::*;
+
+	type Fields = Vec<(String, String)>;
+
+	/// Records the name and fields of every event with the target of the schema.
+	#[derive(Default, Clone)]
+	struct Recorder(Arc<Mutex<Vec<(&'static str, Fields)>>>);
+
+	struct FieldVisitor(Fields);
+
+	impl Visit for FieldVisitor {
+		fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+			self.0
+				.push((field.name().to_string(), format!("{value:?}")));
+		}
+	}
+
+	impl Subscriber for Recorder {
+		fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+			Interest::sometimes()
+		}
+
+		fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+			metadata.target() == TARGET
+		}
+
+		fn new_span(&self, _span: &Attributes<'_>) -> Id {
+			Id::from_u64(1)
+		}
+
+		fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+		fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+		fn event(&self, event: &Event<'_>) {
+			let mut visitor = FieldVisitor(Vec::new());
+			event.record(&mut visitor);
+			self.0
+				.lock()
+				.unwrap()
+				.push((event.metadata().name(), visitor.0));
+		}
+
+		fn enter(&self, _span: &Id) {}
+
+		fn exit(&self, _span: &Id) {}
+	}
+
+	fn field<'a>(fields: &'a [(String, String)], name: &str) -> &'a str {
+		&fields
+			.iter()
+			.find(|(field_name, _)| field_name == name)
+			.unwrap_or_else(|| panic!("missing field {name}"))
+			.1
+	}
+
+	#[test]
+	fn test_events_follow_schema() {
+		let recorder = Recorder::default();
+		tracing::subscriber::with_default(recorder.clone(), || {
+			emit_table_sizes(Role::Prover, &[4, 0]);
+			let mut phase_events = PhaseEvents::new(Role::Prover, false);
+			phase_events.enter(Phase::Commit, 8);
+			phase_events.enter(Phase::Exp, 40);
+			phase_events.finish(100);
+		});
+
+		let events = recorder.0.lock().unwrap();
+		let names = events.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+		assert_eq!(names, ["table", "table", "phase", "phase", "proof"]);
+
+		for (_, fields) in events.iter() {
+			assert_eq!(field(fields, "schema_version"), "1");
+			assert_eq!(field(fields, "role"), "\"prover\"");
+		}
+		assert_eq!(field(&events[0].1, "table_id"), "0");
+		assert_eq!(field(&events[0].1, "table_size"), "4");
+		assert_eq!(field(&events[2].1, "phase"), "\"commit\"");
+		assert_eq!(field(&events[2].1, "proof_bytes"), "32");
+		assert_eq!(field(&events[3].1, "phase"), "\"exp\"");
+		assert_eq!(field(&events[3].1, "proof_bytes"), "60");
+		assert_eq!(field(&events[4].1, "proof_bytes"), "100");
+		field(&events[4].1, "duration_us");
+	}
+}
@@ -0,0 +1,386 @@
+// Copyright 2025 Irreducible Inc.
+
+//! A framed encoding of proofs that can be checked for integrity while it is read.
+//!
+//! A [`Proof`] is an opaque transcript, and a verifier only finds out that a corrupted or
+//! truncated proof is malformed once it has received the whole transcript and replayed the
+//! protocol up to the damaged part. A verification service that accepts proofs of several
+//! megabytes from untrusted clients wants to reject those earlier, and without allocating the
+//! size that a malformed proof claims to have.
+//!
+//! [`write_framed_proof`] splits the transcript into sections of a fixed power-of-two length and
+//! writes every section with its length and a digest. [`read_framed_proof`] checks the header
+//! against a maximum proof length before allocating anything, grows the transcript only as the
+//! sections arrive, and checks the section digests as it goes, for a batch of sections at a time
+//! in parallel. The digests detect accidental corruption; they are not authenticated, so the
+//! proof must still be verified.
+//!
+//! The encoding, with all integers in little-endian order, is
+//!
+//! * the magic bytes [`FRAMED_PROOF_MAGIC`],
+//! * the transcript length as a `u64`,
+//! * the binary logarithm of the section length as a `u8`,
+//! * the digest length as a `u8`,
+//! * for every section, its length as a `u32`, its bytes, and the digest of its index as a `u64`
+//!   followed by its bytes.
+//!
+//! Every section has the section length, except for the last one, which holds the rest of the
+//! transcript. The empty transcript has no sections.
+
+use std::{
+	io::{self, Read, Write},
+	ops::Range,
+};
+
+use binius_maybe_rayon::prelude::*;
+use binius_utils::error_code::{ErrorCode, ErrorDomain, HasErrorCode};
+use digest::{Digest, Output};
+
+use super::Proof;
+
+/// The bytes that a framed proof starts with.
+pub const FRAMED_PROOF_MAGIC: [u8; 4] = *b"BNFP";
+
+/// The smallest supported binary logarithm of the section length.
+pub const MIN_LOG_SECTION_LEN: usize = 10;
+
+/// The largest supported binary logarithm of the section length.
+///
+/// The reader holds a batch of sections before their digests are checked, so the section length
+/// bounds the memory that a proof with a bad digest can make it allocate.
+pub const MAX_LOG_SECTION_LEN: usize = 24;
+
+/// The binary logarithm of the section length used by [`write_framed_proof`] callers that have no
+/// reason to pick another one.
+pub const DEFAULT_LOG_SECTION_LEN: usize = 16;
+
+/// An error in a framed proof.
+#[derive(Debug, thiserror::Error)]
+pub enum FramingError {
+	#[error("failed to read the framed proof: {0}")]
+	Io(#[from] io::Error),
+	#[error("the data does not start with the framed proof magic bytes")]
+	BadMagic,
+	#[error("the framed proof has {got}-byte digests, expected {expected}")]
+	DigestLengthMismatch { expected: usize, got: usize },
+	#[error(
+		"the framed proof has sections of 2^{log_section_len} bytes, supported are 2^{} to 2^{}",
+		MIN_LOG_SECTION_LEN,
+		MAX_LOG_SECTION_LEN
+	)]
+	SectionLengthOutOfRange { log_section_len: usize },
+	#[error("the proof has {len} bytes, more than the maximum of {max}")]
+	ProofTooLarge { len: u64, max: usize },
+	#[error("the framed proof ends within section {section}")]
+	Truncated { section: usize },
+	#[error("section {section} has {got} bytes, expected {expected}")]
+	SectionLengthMismatch {
+		section: usize,
+		expected: usize,
+		got: usize,
+	},
+	#[error("the digest of section {section} does not match its contents")]
+	SectionDigestMismatch { section: usize },
+	#[error("the framed proof ends within its header")]
+	HeaderTruncated,
+}
+
+impl HasErrorCode for FramingError {
+	fn error_code(&self) -> ErrorCode {
+		let index = match self {
+			Self::Io(_) => 201,
+			Self::BadMagic => 202,
+			Self::DigestLengthMismatch { .. } => 203,
+			Self::SectionLengthOutOfRange { .. } => 204,
+			Self::ProofTooLarge { .. } => 205,
+			Self::Truncated { .. } => 206,
+			Self::SectionLengthMismatch { .. } => 207,
+			Self::SectionDigestMismatch { .. } => 208,
+			Self::HeaderTruncated => 209,
+		};
+		ErrorCode::new(ErrorDomain::ConstraintSystem, index)
+	}
+}
+
+/// Writes the framed encoding of a proof with sections of `2^log_section_len` bytes.
+///
+/// The section digests are computed in parallel before anything is written.
+///
+/// ## Panics
+///
+/// * if `log_section_len` is not between [`MIN_LOG_SECTION_LEN`] and [`MAX_LOG_SECTION_LEN`]
+pub fn write_framed_proof<D: Digest>(
+	proof: &Proof,
+	log_section_len: usize,
+	mut writer: impl Write,
+) -> io::Result<()> {
+	assert!(
+		(MIN_LOG_SECTION_LEN..=MAX_LOG_SECTION_LEN).contains(&log_section_len),
+		"log_section_len {log_section_len} is out of range"
+	);
+	let transcript = &proof.transcript;
+	let digests = transcript
+		.par_chunks(1 << log_section_len)
+		.enumerate()
+		.map(|(section, bytes)| section_digest::<D>(section, bytes))
+		.collect::<Vec<_>>();
+
+	writer.write_all(&FRAMED_PROOF_MAGIC)?;
+	writer.write_all(&(transcript.len() as u64).to_le_bytes())?;
+	writer.write_all(&[log_section_len as u8, <D as Digest>::output_size() as u8])?;
+	for (bytes, digest) in transcript.chunks(1 << log_section_len).zip(digests) {
+		writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+		writer.write_all(bytes)?;
+		writer.write_all(&digest)?;
+	}
+	Ok(())
+}
+
+/// Reads a framed proof of at most `max_len` transcript bytes and checks its section digests.
+///
+/// The reader stops after the last section, so that further data in the stream is left unread.
+/// On error, it has read an unspecified part of the stream.
+pub fn read_framed_proof<D: Digest>(
+	mut reader: impl Read,
+	max_len: usize,
+) -> Result<Proof, FramingError> {
+	let mut magic = [0u8; 4];
+	let mut len = [0u8; 8];
+	let mut params = [0u8; 2];
+	read_header(&mut reader, &mut magic)?;
+	if magic != FRAMED_PROOF_MAGIC {
+		return Err(FramingError::BadMagic);
+	}
+	read_header(&mut reader, &mut len)?;
+	read_header(&mut reader, &mut params)?;
+
+	let len = u64::from_le_bytes(len);
+	let [log_section_len, digest_len] = params.map(usize::from);
+	if digest_len != <D as Digest>::output_size() {
+		return Err(FramingError::DigestLengthMismatch {
+			expected: <D as Digest>::output_size(),
+			got: digest_len,
+		});
+	}
+	if !(MIN_LOG_SECTION_LEN..=MAX_LOG_SECTION_LEN).contains(&log_section_len) {
+		return Err(FramingError::SectionLengthOutOfRange { log_section_len });
+	}
+	let len = match usize::try_from(len) {
+		Ok(len) if len <= max_len => len,
+		_ => return Err(FramingError::ProofTooLarge { len, max: max_len }),
+	};
+
+	let section_len = 1 << log_section_len;
+	let n_sections = len.div_ceil(section_len);
+	let batch_len = binius_maybe_rayon::current_num_threads().max(1);
+
+	let mut transcript = Vec::new();
+	let mut digests = Vec::with_capacity(batch_len);
+	for batch_start in (0..n_sections).step_by(batch_len) {
+		let batch = batch_start..n_sections.min(batch_start + batch_len);
+		digests.clear();
+		for section in batch.clone() {
+			let expected_len = section_len.min(len - section * section_len);
+			let mut section_header = [0u8; 4];
+			read_section(&mut reader, &mut section_header, section)?;
+			let got_len = u32::from_le_bytes(section_header) as usize;
+			if got_len != expected_len {
+				return Err(FramingError::SectionLengthMismatch {
+					section,
+					expected: expected_len,
+					got: got_len,
+				});
+			}
+
+			let start = transcript.len();
+			transcript.resize(start + expected_len, 0);
+			read_section(&mut reader, &mut transcript[start..], section)?;
+			let mut digest = Output::<D>::default();
+			read_section(&mut reader, &mut digest, section)?;
+			digests.push(digest);
+		}
+		check_section_digests::<D>(&transcript, section_len, batch, &digests)?;
+	}
+
+	Ok(Proof { transcript })
+}
+
+fn read_header(reader: &mut impl Read, buf: &mut [u8]) -> Result<(), FramingError> {
+	reader.read_exact(buf).map_err(|err| match err.kind() {
+		io::ErrorKind::UnexpectedEof => FramingError::HeaderTruncated,
+		_ => err.into(),
+	})
+}
+
+fn read_section(
+	reader: &mut impl Read,
+	buf: &mut [u8],
+	section: usize,
+) -> Result<(), FramingError> {
+	reader.read_exact(buf).map_err(|err| match err.kind() {
+		io::ErrorKind::UnexpectedEof => FramingError::Truncated { section },
+		_ => err.into(),
+	})
+}
+
+/// Checks the digests of a batch of sections in parallel and reports the first mismatch.
+fn check_section_digests<D: Digest>(
+	transcript: &[u8],
+	section_len: usize,
+	batch: Range<usize>,
+	digests: &[Output<D>],
+) -> Result<(), FramingError> {
+	let mismatch = batch
+		.into_par_iter()
+		.zip(digests)
+		.filter(|&(section, digest)| {
+			let start = section * section_len;
+			let end = transcript.len().min(start + section_len);
+			section_digest::<D>(section, &transcript[start..end]) != *digest
+		})
+		.map(|(section, _)| section)
+		.min();
+	match mismatch {
+		Some(section) => Err(FramingError::SectionDigestMismatch { section }),
+		None => Ok(()),
+	}
+}
+
+fn section_digest<D: Digest>(section: usize, bytes: &[u8]) -> Output<D> {
+	let mut hasher = D::new();
+	hasher.update((section as u64).to_le_bytes());
+	hasher.update(bytes);
+	hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+	use assert_matches::assert_matches;
+	use binius_hash::groestl::Groestl256;
+	use rand::{RngCore, SeedableRng, rngs::StdRng};
+
+	use super::*;
+
+	const LOG_SECTION_LEN: usize = MIN_LOG_SECTION_LEN;
+	const DIGEST_LEN: usize = 32;
+
+	fn random_proof(len: usize) -> Proof {
+		let mut transcript = vec![0u8; len];
+		StdRng::seed_from_u64(0).fill_bytes(&mut transcript);
+		Proof { transcript }
+	}
+
+	fn framed(proof: &Proof) -> Vec<u8> {
+		let mut buf = Vec::new();
+		write_framed_proof::<Groestl256>(proof, LOG_SECTION_LEN, &mut buf).unwrap();
+		buf
+	}
+
+	/// The offset of the first byte of a section in the framed encoding.
+	fn section_offset(section: usize) -> usize {
+		14 + section * (4 + (1 << LOG_SECTION_LEN) + DIGEST_LEN)
+	}
+
+	#[test]
+	fn test_roundtrip() {
+		for len in [
+			0,
+			1,
+			1 << LOG_SECTION_LEN,
+			20 << LOG_SECTION_LEN,
+			(20 << LOG_SECTION_LEN) + 3,
+		] {
+			let proof = random_proof(len);
+			let mut buf = framed(&proof);
+			// Data after the proof is left unread.
+			buf.extend_from_slice(b"next");
+			let mut reader = buf.as_slice();
+			let read = read_framed_proof::<Groestl256>(&mut reader, len).unwrap();
+			assert_eq!(read.transcript, proof.transcript);
+			assert_eq!(reader, b"next");
+		}
+	}
+
+	#[test]
+	fn test_rejects_large_proof_before_reading_sections() {
+		let buf = framed(&random_proof(4 << LOG_SECTION_LEN));
+		// Only the header is given, so the check must not need the sections.
+		let header = &buf[..section_offset(0)];
+		assert_matches!(
+			read_framed_proof::<Groestl256>(header, (4 << LOG_SECTION_LEN) - 1),
+			Err(FramingError::ProofTooLarge { len, .. }) if len == 4 << LOG_SECTION_LEN
+		);
+		assert_matches!(
+			read_framed_proof::<Groestl256>(&header[..10], 4 << LOG_SECTION_LEN),
+			Err(FramingError::HeaderTruncated)
+		);
+	}
+
+	#[test]
+	fn test_rejects_malformed_header() {
+		let buf = framed(&random_proof(100));
+
+		let mut bad_magic = buf.clone();
+		bad_magic[0] ^= 1;
+		assert_matches!(
+			read_framed_proof::<Groestl256>(bad_magic.as_slice(), 100),
+			Err(FramingError::BadMagic)
+		);
+
+		let mut bad_section_len = buf.clone();
+		bad_section_len[12] = 40;
+		assert_matches!(
+			read_framed_proof::<Groestl256>(bad_section_len.as_slice(), 100),
+			Err(FramingError::SectionLengthOutOfRange {
+				log_section_len: 40
+			})
+		);
+
+		let mut bad_digest_len = buf;
+		bad_digest_len[13] = 20;
+		assert_matches!(
+			read_framed_proof::<Groestl256>(bad_digest_len.as_slice(), 100),
+			Err(FramingError::DigestLengthMismatch { got: 20, .. })
+		);
+	}
+
+	#[test]
+	fn test_rejects_corrupted_sections() {
+		let len = 20 << LOG_SECTION_LEN;
+		let buf = framed(&random_proof(len));
+
+		let mut corrupted = buf.clone();
+		corrupted[section_offset(13) + 4 + 7] ^= 1;
+		assert_matches!(
+			read_framed_proof::<Groestl256>(corrupted.as_slice(), len),
+			Err(FramingError::SectionDigestMismatch { section: 13 })
+		);
+
+		let mut bad_len = buf.clone();
+		bad_len[section_offset(5)] ^= 1;
+		assert_matches!(
+			read_framed_proof::<Groestl256>(bad_len.as_slice(), len),
+			Err(FramingError::SectionLengthMismatch { section: 5, .. })
+		);
+
+		let truncated = &buf[..section_offset(17) + 10];
+		assert_matches!(
+			read_framed_proof::<Groestl256>(truncated, len),
+			Err(FramingError::Truncated { section: 17 })
+		);
+	}
+
+	#[test]
+	fn test_rejects_swapped_sections() {
+		let len = 4 << LOG_SECTION_LEN;
+		let mut buf = framed(&random_proof(len));
+		let (start, mid, end) = (section_offset(1), section_offset(2), section_offset(3));
+		let section_1 = buf[start..mid].to_vec();
+		buf.copy_within(mid..end, start);
+		buf[start + (end - mid)..end].copy_from_slice(&section_1);
+		assert_matches!(
+			read_framed_proof::<Groestl256>(buf.as_slice(), len),
+			Err(FramingError::SectionDigestMismatch { section: 1 })
+		);
+	}
+}
@@ -0,0 +1,231 @@
+// Copyright 2025 Irreducible Inc.
+
+//! A registry of hash schemes keyed by stable identifiers.
+//!
+//! A hash scheme is the combination of the hash function, the Merkle tree compression function,
+//! and the Fiat-Shamir challenger that a proof was produced with. [`verify`](super::verify) takes
+//! these as generic parameters, so every call site must name them. The registry instead maps an
+//! identifier that can be stored next to a serialized proof, see [`TaggedProof`], to a
+//! monomorphized verifier over the canonical tower.
+//!
+//! Like the deserializers of transparent polynomials, entries are collected with the inventory
+//! crate, so third party code can register its own schemes with [`inventory::submit!`]. An
+//! identifier that is registered more than once can not be used.
+
+use std::{collections::HashMap, sync::LazyLock};
+
+use binius_field::{BinaryField128b, arch::OptimalUnderlier, tower::CanonicalTowerFamily};
+use binius_hash::{
+	PseudoCompressionFunction, Vision32Compression, VisionHasherDigest,
+	blake3::{Blake3, Blake3Compression},
+	groestl::{Groestl256, Groestl256ByteCompression},
+	keccak::{Keccak256, Keccak256ByteCompression},
+	sha2::{Sha256, Sha256Compression},
+};
+use binius_macros::{DeserializeBytes, SerializeBytes};
+use digest::{Digest, Output, OutputSizeUser, core_api::BlockSizeUser};
+
+use super::{ConstraintSystem, Proof, channel::Boundary, error::Error};
+use crate::fiat_shamir::{Challenger, HasherChallenger};
+
+/// A proof together with the identifier of the hash scheme that it was produced with.
+#[derive(Debug, Clone, SerializeBytes, DeserializeBytes)]
+pub struct TaggedProof {
+	pub hash_scheme: String,
+	pub proof: Proof,
+}
+
+/// Verifies a tagged proof against a constraint system with the registered hash scheme.
+///
+/// The digest of the constraint system is computed with the hash function of the scheme.
+///
+/// ## Throws
+///
+/// * [`Error::UnknownHashScheme`] if the identifier is not registered
+/// * [`Error::HashSchemeNameConflict`] if the identifier is registered more than once
+pub fn verify_tagged(
+	constraint_system: &ConstraintSystem<BinaryField128b>,
+	log_inv_rate: usize,
+	security_bits: usize,
+	boundaries: &[Boundary<BinaryField128b>],
+	proof: TaggedProof,
+) -> Result<(), Error> {
+	let TaggedProof { hash_scheme, proof } = proof;
+	let verify = lookup(&hash_scheme)?;
+	verify(constraint_system, log_inv_rate, security_bits, boundaries, proof)
+}
+
+/// Returns whether a hash scheme is registered under the identifier `name`.
+pub fn is_registered(name: &str) -> bool {
+	matches!(REGISTRY.get(name), Some(Some(_)))
+}
+
+fn lookup(name: &str) -> Result<ErasedVerify, Error> {
+	match REGISTRY.get(name) {
+		Some(Some(verify)) => Ok(*verify),
+		Some(None) => Err(Error::HashSchemeNameConflict {
+			name: name.to_string(),
+		}),
+		None => Err(Error::UnknownHashScheme {
+			name: name.to_string(),
+		}),
+	}
+}
+
+/// A hash scheme submitted to the registry.
+pub struct HashSchemeEntry {
+	name: &'static str,
+	verify: ErasedVerify,
+}
+
+impl HashSchemeEntry {
+	/// Creates an entry for the scheme with the hash function `Hash`, the compression function
+	/// `Compress`, and the challenger `Challenger_`.
+	pub const fn new<Hash, Compress, Challenger_>(name: &'static str) -> Self
+	where
+		Hash: Digest + BlockSizeUser + OutputSizeUser,
+		Compress: PseudoCompressionFunction<Output<Hash>, 2> + Default + Sync,
+		Challenger_: Challenger + Default,
+	{
+		Self {
+			name,
+			verify: verify_erased::<Hash, Compress, Challenger_>,
+		}
+	}
+
+	/// The identifier of the scheme.
+	pub const fn name(&self) -> &'static str {
+		self.name
+	}
+}
+
+type ErasedVerify = fn(
+	&ConstraintSystem<BinaryField128b>,
+	usize,
+	usize,
+	&[Boundary<BinaryField128b>],
+	Proof,
+) -> Result<(), Error>;
+
+fn verify_erased<Hash, Compress, Challenger_>(
+	constraint_system: &ConstraintSystem<BinaryField128b>,
+	log_inv_rate: usize,
+	security_bits: usize,
+	boundaries: &[Boundary<BinaryField128b>],
+	proof: Proof,
+) -> Result<(), Error>
+where
+	Hash: Digest + BlockSizeUser + OutputSizeUser,
+	Compress: PseudoCompressionFunction<Output<Hash>, 2> + Default + Sync,
+	Challenger_: Challenger + Default,
+{
+	let constraint_system_digest = constraint_system.digest::<Hash>();
+	super::verify::<OptimalUnderlier, CanonicalTowerFamily, Hash, Compress, Challenger_>(
+		constraint_system,
+		log_inv_rate,
+		security_bits,
+		&constraint_system_digest,
+		boundaries,
+		proof,
+	)
+}
+
+inventory::collect!(HashSchemeEntry);
+
+inventory::submit! {
+	HashSchemeEntry::new::<Groestl256, Groestl256ByteCompression, HasherChallenger<Groestl256>>(
+		"groestl256",
+	)
+}
+
+inventory::submit! {
+	HashSchemeEntry::new::<Sha256, Sha256Compression, HasherChallenger<Sha256>>("sha256")
+}
+
+inventory::submit! {
+	HashSchemeEntry::new::<Keccak256, Keccak256ByteCompression, HasherChallenger<Keccak256>>(
+		"keccak256",
+	)
+}
+
+inventory::submit! {
+	HashSchemeEntry::new::<Blake3, Blake3Compression, HasherChallenger<Blake3>>("blake3")
+}
+
+inventory::submit! {
+	HashSchemeEntry::new::<
+		VisionHasherDigest,
+		Vision32Compression,
+		HasherChallenger<VisionHasherDigest>,
+	>("vision32")
+}
+
+static REGISTRY: LazyLock<HashMap<&'static str, Option<ErasedVerify>>> = LazyLock::new(|| {
+	let mut registry = HashMap::new();
+	inventory::iter::<HashSchemeEntry>
+		.into_iter()
+		.for_each(|&HashSchemeEntry { name, verify }| match registry.entry(name) {
+			std::collections::hash_map::Entry::Vacant(entry) => {
+				entry.insert(Some(verify));
+			}
+			std::collections::hash_map::Entry::Occupied(mut entry) => {
+				entry.insert(None);
+			}
+		});
+	registry
+});
+
+#[cfg(test)]
+mod tests {
+	use binius_utils::{DeserializeBytes, SerializationMode, SerializeBytes};
+
+	use super::*;
+
+	inventory::submit! {
+		HashSchemeEntry::new::<Sha256, Sha256Compression, HasherChallenger<Sha256>>(
+			"test-conflict",
+		)
+	}
+
+	inventory::submit! {
+		HashSchemeEntry::new::<Blake3, Blake3Compression, HasherChallenger<Blake3>>(
+			"test-conflict",
+		)
+	}
+
+	#[test]
+	fn test_builtin_schemes_are_registered() {
+		for name in ["groestl256", "sha256", "keccak256", "blake3", "vision32"] {
+			assert!(is_registered(name), "{name} is not registered");
+		}
+		assert!(!is_registered("md5"));
+	}
+
+	#[test]
+	fn test_lookup_errors() {
+		assert!(matches!(lookup("md5"), Err(Error::UnknownHashScheme { name }) if name == "md5"));
+		assert!(matches!(
+			lookup("test-conflict"),
+			Err(Error::HashSchemeNameConflict { name }) if name == "test-conflict"
+		));
+	}
+
+	#[test]
+	fn test_tagged_proof_serialization() {
+		let tagged = TaggedProof {
+			hash_scheme: "groestl256".to_string(),
+			proof: Proof {
+				transcript: vec![1, 2, 3],
+			},
+		};
+
+		let mut buf = Vec::new();
+		tagged
+			.serialize(&mut buf, SerializationMode::CanonicalTower)
+			.unwrap();
+		let deserialized =
+			TaggedProof::deserialize(buf.as_slice(), SerializationMode::CanonicalTower).unwrap();
+		assert_eq!(deserialized.hash_scheme, tagged.hash_scheme);
+		assert_eq!(deserialized.proof.transcript, tagged.proof.transcript);
+	}
+}
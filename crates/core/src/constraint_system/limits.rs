@@ -0,0 +1,210 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Resource limits and cooperative cancellation of the prover.
+//!
+//! A service that embeds the prover runs jobs of sizes that it does not control, and needs to
+//! abort a job that runs too long or grows too large without killing the process. The
+//! [`ProverLimits`] passed to [`prove_with_limits`](super::prove_with_limits) hold a
+//! [`CancellationToken`] and optional limits on the wall-clock time and the resident memory of
+//! the process. The prover checks them at the boundaries of the protocol phases and between the
+//! steps of its longer loops, and returns an error as soon as a check fails, dropping all the
+//! memory that it allocated. The checks are cooperative, so a single step, such as the sumcheck
+//! of one constraint set, runs to completion before the prover notices that it should stop.
+
+use std::{
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+	},
+	time::{Duration, Instant},
+};
+
+use binius_utils::time::now;
+
+use super::error::Error;
+
+/// A flag that requests a running prover to stop.
+///
+/// Clones of a token share the flag, so a token can be cancelled from another thread while the
+/// prover holds a clone of it.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+	/// Creates a token that is not cancelled.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Requests the provers holding a clone of this token to stop.
+	pub fn cancel(&self) {
+		self.0.store(true, Ordering::Relaxed);
+	}
+
+	/// Returns whether the token was cancelled.
+	pub fn is_cancelled(&self) -> bool {
+		self.0.load(Ordering::Relaxed)
+	}
+}
+
+/// The resource limits of a prover run, see the [module documentation](self).
+///
+/// The default has no cancellation token and no limits.
+#[derive(Debug, Clone, Default)]
+pub struct ProverLimits {
+	cancellation: Option<CancellationToken>,
+	time_limit: Option<Duration>,
+	memory_limit: Option<usize>,
+}
+
+impl ProverLimits {
+	/// Creates limits that never stop the prover.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Stops the prover once `token` is cancelled.
+	pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+		self.cancellation = Some(token);
+		self
+	}
+
+	/// Stops the prover once it has run for longer than `time_limit`.
+	///
+	/// The limit is not enforced on targets without a clock.
+	pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+		self.time_limit = Some(time_limit);
+		self
+	}
+
+	/// Stops the prover once the resident set size of the process exceeds `memory_limit` bytes.
+	///
+	/// The resident set size covers the whole process, including other jobs running in it. The
+	/// limit is only enforced on Linux, where the size can be read from `/proc`.
+	pub fn with_memory_limit(mut self, memory_limit: usize) -> Self {
+		self.memory_limit = Some(memory_limit);
+		self
+	}
+
+	/// Starts measuring the time limit from now.
+	pub(super) fn start(&self) -> ActiveLimits<'_> {
+		ActiveLimits {
+			limits: self,
+			start: now(),
+		}
+	}
+}
+
+/// The limits of a prover run that has started.
+#[derive(Debug)]
+pub(super) struct ActiveLimits<'a> {
+	limits: &'a ProverLimits,
+	start: Option<Instant>,
+}
+
+impl ActiveLimits<'_> {
+	/// Checks that the prover may continue.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::ProverCancelled`] if the cancellation token was cancelled
+	/// * [`Error::ProverTimeLimitExceeded`] if the time limit has passed
+	/// * [`Error::ProverMemoryLimitExceeded`] if the resident set size exceeds the memory limit
+	pub(super) fn check(&self) -> Result<(), Error> {
+		let ProverLimits {
+			cancellation,
+			time_limit,
+			memory_limit,
+		} = self.limits;
+
+		if cancellation
+			.as_ref()
+			.is_some_and(CancellationToken::is_cancelled)
+		{
+			return Err(Error::ProverCancelled);
+		}
+		if let (Some(limit), Some(start)) = (*time_limit, self.start) {
+			let elapsed = start.elapsed();
+			if elapsed > limit {
+				return Err(Error::ProverTimeLimitExceeded { limit, elapsed });
+			}
+		}
+		if let Some(limit) = *memory_limit {
+			if let Some(used) = resident_set_size().filter(|&used| used > limit) {
+				return Err(Error::ProverMemoryLimitExceeded { limit, used });
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Returns the resident set size of the process in bytes, if it can be read.
+#[cfg(target_os = "linux")]
+fn resident_set_size() -> Option<usize> {
+	let status = std::fs::read_to_string("/proc/self/status").ok()?;
+	let kib = status
+		.lines()
+		.find_map(|line| line.strip_prefix("VmRSS:"))?
+		.trim()
+		.strip_suffix("kB")?
+		.trim()
+		.parse::<usize>()
+		.ok()?;
+	Some(kib * 1024)
+}
+
+/// Returns the resident set size of the process in bytes, if it can be read.
+#[cfg(not(target_os = "linux"))]
+fn resident_set_size() -> Option<usize> {
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_default_limits_pass() {
+		let limits = ProverLimits::new();
+		assert!(limits.start().check().is_ok());
+	}
+
+	#[test]
+	fn test_cancellation() {
+		let token = CancellationToken::new();
+		let limits = ProverLimits::new().with_cancellation(token.clone());
+		let active = limits.start();
+		assert!(active.check().is_ok());
+
+		token.cancel();
+		assert!(token.is_cancelled());
+		assert!(matches!(active.check(), Err(Error::ProverCancelled)));
+	}
+
+	#[test]
+	fn test_time_limit() {
+		let limits = ProverLimits::new().with_time_limit(Duration::ZERO);
+		let active = limits.start();
+		std::thread::sleep(Duration::from_millis(1));
+		assert!(matches!(active.check(), Err(Error::ProverTimeLimitExceeded { .. })));
+
+		let limits = ProverLimits::new().with_time_limit(Duration::from_secs(3600));
+		assert!(limits.start().check().is_ok());
+	}
+
+	#[cfg(target_os = "linux")]
+	#[test]
+	fn test_memory_limit() {
+		let used = resident_set_size().unwrap();
+		assert!(used > 0);
+
+		let limits = ProverLimits::new().with_memory_limit(1);
+		assert!(matches!(
+			limits.start().check(),
+			Err(Error::ProverMemoryLimitExceeded { limit: 1, .. })
+		));
+
+		let limits = ProverLimits::new().with_memory_limit(usize::MAX);
+		assert!(limits.start().check().is_ok());
+	}
+}
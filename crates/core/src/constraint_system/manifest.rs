@@ -0,0 +1,227 @@
+// Copyright 2025 Irreducible Inc.
+
+//! A canonical, human-readable description of a constraint system.
+//!
+//! The manifest lists the tables with their oracles, constraints and flushes, the channels, the
+//! exponentiations, and the commit batches of a [`ConstraintSystem`], with one item per line in
+//! a fixed order. Its hash is part of [`ConstraintSystem::digest`], so two parties that agree on
+//! the manifest text agree on the digest inputs that it describes, and parties that disagree on
+//! the digest can find the difference by comparing their manifests.
+
+use std::{
+	collections::BTreeSet,
+	fmt::{self, Write},
+};
+
+use binius_field::TowerField;
+
+use super::{
+	ConstraintSystem, TableSizeSpec,
+	channel::{FlushDirection, OracleOrConst},
+};
+use crate::oracle::{
+	ConstraintPredicate, OracleId, SymbolicMultilinearOracle, SymbolicMultilinearPolyVariant,
+};
+
+impl<F: TowerField> ConstraintSystem<F> {
+	/// Returns the manifest of the constraint system.
+	///
+	/// Oracles are referred to as `#<index>`. Tables are listed in order of their IDs, each with
+	/// its size specification, and then the oracles, the constraint sets and the flushes of the
+	/// table in the order they are defined, indented by a tab. Constraints are given by their
+	/// names and degrees rather than their expressions, and transparent and structured oracles by
+	/// their kind, so the manifest stays readable; those details are only covered by the digest.
+	pub fn manifest(&self) -> String {
+		let mut manifest = String::new();
+		self.write_manifest(&mut manifest)
+			.expect("writing to a string does not fail");
+		manifest
+	}
+
+	fn write_manifest(&self, f: &mut impl Write) -> fmt::Result {
+		let table_ids = (0..self.table_size_specs.len())
+			.chain(self.oracles.polys().map(|oracle| oracle.table_id))
+			.chain(self.table_constraints.iter().map(|set| set.table_id))
+			.chain(self.flushes.iter().map(|flush| flush.table_id))
+			.collect::<BTreeSet<_>>();
+
+		for table_id in table_ids {
+			write!(f, "table {table_id}")?;
+			match self.table_size_specs.get(table_id) {
+				Some(TableSizeSpec::Arbitrary) => writeln!(f, " arbitrary")?,
+				Some(TableSizeSpec::PowerOfTwo) => writeln!(f, " power_of_two")?,
+				Some(TableSizeSpec::Fixed { log_size }) => {
+					writeln!(f, " fixed log_size={log_size}")?
+				}
+				None => writeln!(f, " unspecified")?,
+			}
+
+			for (id, oracle) in self.oracles.iter() {
+				if oracle.table_id == table_id {
+					write_oracle(f, id, oracle)?;
+				}
+			}
+
+			for set in &self.table_constraints {
+				if set.table_id != table_id {
+					continue;
+				}
+				writeln!(
+					f,
+					"\tconstraint_set log_values_per_row={} oracles={}",
+					set.log_values_per_row,
+					OracleList(&set.oracle_ids)
+				)?;
+				for constraint in &set.constraints {
+					let predicate = match constraint.predicate {
+						ConstraintPredicate::Zero => "zero",
+						ConstraintPredicate::Sum(_) => "sum",
+					};
+					writeln!(
+						f,
+						"\t\t{predicate} {:?} degree={}",
+						constraint.name,
+						constraint.composition.degree()
+					)?;
+				}
+			}
+
+			for flush in &self.flushes {
+				if flush.table_id != table_id {
+					continue;
+				}
+				let direction = match flush.direction {
+					FlushDirection::Push => "push",
+					FlushDirection::Pull => "pull",
+				};
+				write!(
+					f,
+					"\tflush {direction} channel={} log_values_per_row={} values=(",
+					flush.channel_id, flush.log_values_per_row
+				)?;
+				for (i, value) in flush.oracles.iter().enumerate() {
+					if i != 0 {
+						write!(f, ", ")?;
+					}
+					write_oracle_or_const(f, value)?;
+				}
+				writeln!(
+					f,
+					") selectors={} multiplicity={}",
+					OracleList(&flush.selectors),
+					flush.multiplicity
+				)?;
+			}
+		}
+
+		writeln!(f, "channels {}", self.channel_count)?;
+		for exp in &self.exponents {
+			write!(f, "exp base=")?;
+			write_oracle_or_const(f, &exp.base)?;
+			writeln!(
+				f,
+				" bits={} result=#{}",
+				OracleList(&exp.bits_ids),
+				exp.exp_result_id.index()
+			)?;
+		}
+		writeln!(f, "non_zero {}", OracleList(&self.non_zero_oracle_ids))?;
+		for (i, batch) in self.commit_batches.iter().enumerate() {
+			writeln!(f, "commit_batch {i} {}", OracleList(batch))?;
+		}
+		for (i, rate) in self.commit_rates.iter().enumerate() {
+			writeln!(f, "commit_rate {i} {rate:?}")?;
+		}
+		for (i, log_leaf_size) in self.commit_log_leaf_sizes.iter().enumerate() {
+			if let Some(log_leaf_size) = log_leaf_size {
+				writeln!(f, "commit_log_leaf_size {i} {log_leaf_size}")?;
+			}
+		}
+		Ok(())
+	}
+}
+
+fn write_oracle<F: TowerField>(
+	f: &mut impl Write,
+	id: OracleId,
+	oracle: &SymbolicMultilinearOracle<F>,
+) -> fmt::Result {
+	write!(f, "\toracle #{} {:?} ", id.index(), oracle.name.as_deref().unwrap_or(""))?;
+	match &oracle.variant {
+		SymbolicMultilinearPolyVariant::Committed => write!(f, "committed")?,
+		SymbolicMultilinearPolyVariant::Transparent(_) => write!(f, "transparent")?,
+		SymbolicMultilinearPolyVariant::Structured(circuit) => {
+			write!(f, "structured degree={}", circuit.degree())?
+		}
+		SymbolicMultilinearPolyVariant::Repeating { id } => write!(f, "repeating #{}", id.index())?,
+		SymbolicMultilinearPolyVariant::Projected {
+			id,
+			values,
+			variant,
+		} => write!(f, "projected #{} {variant:?} n_values={}", id.index(), values.len())?,
+		SymbolicMultilinearPolyVariant::Shifted {
+			id,
+			shift_offset,
+			block_size,
+			shift_variant,
+		} => write!(
+			f,
+			"shifted #{} {shift_variant:?} offset={shift_offset} block_size={block_size}",
+			id.index()
+		)?,
+		SymbolicMultilinearPolyVariant::Packed { id, log_degree } => {
+			write!(f, "packed #{} log_degree={log_degree}", id.index())?
+		}
+		SymbolicMultilinearPolyVariant::LinearCombination { inner, .. } => {
+			let ids = inner.iter().map(|&(id, _)| id).collect::<Vec<_>>();
+			write!(f, "linear_combination {}", OracleList(&ids))?
+		}
+		SymbolicMultilinearPolyVariant::ZeroPadded {
+			id,
+			n_pad_vars,
+			nonzero_index,
+			start_index,
+		} => write!(
+			f,
+			"zero_padded #{} n_pad_vars={n_pad_vars} nonzero_index={nonzero_index} \
+			 start_index={start_index}",
+			id.index()
+		)?,
+		SymbolicMultilinearPolyVariant::Composite { inner, circuit } => {
+			write!(f, "composite {} degree={}", OracleList(inner), circuit.degree())?
+		}
+	}
+	writeln!(
+		f,
+		" tower_level={} log_values_per_row={}",
+		oracle.tower_level, oracle.log_values_per_row
+	)
+}
+
+fn write_oracle_or_const<F: TowerField>(
+	f: &mut impl Write,
+	value: &OracleOrConst<F>,
+) -> fmt::Result {
+	match value {
+		OracleOrConst::Oracle(id) => write!(f, "#{}", id.index()),
+		OracleOrConst::Const { base, tower_level } => {
+			write!(f, "const({base}, tower_level={tower_level})")
+		}
+	}
+}
+
+/// Formats a list of oracle IDs as `(#a, #b, ...)`.
+struct OracleList<'a>(&'a [OracleId]);
+
+impl fmt::Display for OracleList<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "(")?;
+		for (i, id) in self.0.iter().enumerate() {
+			if i != 0 {
+				write!(f, ", ")?;
+			}
+			write!(f, "#{}", id.index())?;
+		}
+		write!(f, ")")
+	}
+}
@@ -2,8 +2,16 @@
 
 pub mod channel;
 mod common;
+pub mod cost;
 pub mod error;
+pub mod events;
 pub mod exp;
+pub mod framing;
+pub mod hash_scheme;
+pub mod limits;
+mod manifest;
+pub mod opening;
+pub mod profile;
 mod prove;
 pub mod validate;
 mod verify;
@@ -13,18 +21,29 @@ mod tests;
 
 use binius_field::{BinaryField128b, TowerField};
 use binius_macros::{DeserializeBytes, SerializeBytes};
-use binius_utils::{SerializationMode, SerializeBytes};
+use binius_utils::{DeserializeBytes, SerializationMode, SerializeBytes};
 use channel::Flush;
 use digest::{Digest, Output};
 use exp::Exp;
-pub use prove::prove;
-pub use verify::verify;
+pub use prove::{prove, prove_with_limits};
+pub use verify::{
+	ProofCommitments, VerificationReport, verify, verify_commitments, verify_with_report,
+};
 
 use crate::{
-	constraint_system::error::Error,
+	constraint_system::error::{Error, VerificationError},
 	oracle::{ConstraintSet, OracleId, SymbolicMultilinearOracleSet},
+	protocols::sumcheck::batch_weighted_value,
 };
 
+/// The highest degree of a constraint composition that the sumcheck protocols support.
+///
+/// Sumcheck round polynomials are evaluated on a domain in the 8-bit subfield of the tower, and a
+/// round polynomial of degree $d$ needs $d + 1$ evaluation points. Within this bound the provers
+/// size the evaluation domains and the number of univariate skip rounds from the degrees of the
+/// constraints.
+pub const MAX_CONSTRAINT_DEGREE: usize = (1 << 8) - 1;
+
 /// Contains the 3 things that place constraints on witness data in Binius
 /// - virtual oracles
 /// - polynomial constraints
@@ -42,17 +61,122 @@ pub struct ConstraintSystem<F: TowerField> {
 	pub exponents: Vec<Exp<F>>,
 	pub channel_count: usize,
 	pub table_size_specs: Vec<TableSizeSpec>,
+	/// Batches of committed oracles that are committed separately from the other committed
+	/// oracles, each with its own polynomial commitment.
+	///
+	/// The committed oracles that are not in any of the batches are committed together in a
+	/// default batch, which is the only batch if this is empty.
+	pub commit_batches: Vec<Vec<OracleId>>,
+	/// The Reed–Solomon code rates of the commit batches, the default batch first, followed by
+	/// the batches of `commit_batches` in order.
+	///
+	/// Batches without an entry are committed at [`CommitRate::Default`].
+	pub commit_rates: Vec<CommitRate>,
+	/// The binary logarithms of the number of codeword values in each Merkle leaf of the FRI
+	/// oracles of the commit batches, in the same order as `commit_rates`.
+	///
+	/// Batches without an entry, or with `None`, hash one folding coset per leaf. See
+	/// [`FRIParams::with_log_leaf_size`](crate::protocols::fri::FRIParams::with_log_leaf_size).
+	pub commit_log_leaf_sizes: Vec<Option<usize>>,
 }
 
 impl<F: TowerField> ConstraintSystem<F> {
 	/// Returns the hash digest of this constraint system.
 	///
+	/// The digest covers the serialized constraint system and the hash of its
+	/// [manifest](Self::manifest).
+	///
 	/// This assumes that the constraint system should be serializable.
 	pub fn digest<Hash: Digest>(&self) -> Output<Hash> {
 		let mut buf = Vec::new();
 		self.serialize(&mut buf, SerializationMode::CanonicalTower)
 			.expect("the constraint system should be serializable");
-		Hash::digest(&buf)
+		let mut hasher = Hash::new();
+		hasher.update(&buf);
+		hasher.update(Hash::digest(self.manifest()));
+		hasher.finalize()
+	}
+
+	/// Returns the highest degree of the constraint compositions of all tables.
+	pub fn max_degree(&self) -> usize {
+		self.table_constraints
+			.iter()
+			.flat_map(|constraint_set| &constraint_set.constraints)
+			.map(|constraint| constraint.composition.degree())
+			.max()
+			.unwrap_or(0)
+	}
+
+	/// Checks that no constraint composition has a degree greater than `max_degree`.
+	///
+	/// The prover and verifier check this against [`MAX_CONSTRAINT_DEGREE`] before running any
+	/// sumcheck, so that an unsupported constraint is reported by name.
+	pub fn check_degree(&self, max_degree: usize) -> Result<(), Error> {
+		for constraint_set in &self.table_constraints {
+			for constraint in &constraint_set.constraints {
+				let degree = constraint.composition.degree();
+				if degree > max_degree {
+					return Err(Error::ConstraintDegreeTooLarge {
+						table_id: constraint_set.table_id,
+						name: constraint.name.clone(),
+						degree,
+						max: max_degree,
+					});
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Evaluates the batched composition of the constraint set at `index` in
+	/// [`table_constraints`](Self::table_constraints).
+	///
+	/// `oracle_evals` holds the evaluations of the oracles of the constraint set, in the order of
+	/// its `oracle_ids`. The constraints are batched as the sumcheck and zerocheck verifiers do at
+	/// the end of the reduction, by returning $\sum_{i} \beta^{i+1} C_i(evals)$ for the
+	/// compositions $C_i$ and the batching coefficient $\beta$. External recursion frameworks can
+	/// use this to reproduce the final check of the verifier, and tests to compare it with the
+	/// evaluations of the prover.
+	pub fn evaluate_composition(
+		&self,
+		index: usize,
+		batch_coeff: F,
+		oracle_evals: &[F],
+	) -> Result<F, Error> {
+		let constraint_set =
+			self.table_constraints
+				.get(index)
+				.ok_or(Error::ConstraintSetOutOfRange {
+					index,
+					count: self.table_constraints.len(),
+				})?;
+		if oracle_evals.len() != constraint_set.oracle_ids.len() {
+			return Err(Error::ConstraintSetEvalsLenMismatch {
+				index,
+				expected: constraint_set.oracle_ids.len(),
+				got: oracle_evals.len(),
+			});
+		}
+		let composite_evals = constraint_set
+			.constraints
+			.iter()
+			.map(|constraint| constraint.composition.evaluate(oracle_evals))
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(batch_weighted_value(batch_coeff, composite_evals.into_iter()))
+	}
+
+	/// Returns the code rate of the commit batch at `batch`, where batch 0 is the default batch.
+	pub fn commit_rate(&self, batch: usize) -> CommitRate {
+		self.commit_rates
+			.get(batch)
+			.copied()
+			.unwrap_or(CommitRate::Default)
+	}
+
+	/// Returns the binary logarithm of the FRI leaf size of the commit batch at `batch`, if it is
+	/// set, where batch 0 is the default batch.
+	pub fn commit_log_leaf_size(&self, batch: usize) -> Option<usize> {
+		self.commit_log_leaf_sizes.get(batch).copied().flatten()
 	}
 
 	/// Checks whether the table sizes assigned by prover matches the specification of this
@@ -94,7 +218,7 @@ impl<F: TowerField> ConstraintSystem<F> {
 }
 
 /// Constraint system proof that has been serialized into bytes
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, SerializeBytes, DeserializeBytes)]
 pub struct Proof {
 	pub transcript: Vec<u8>,
 }
@@ -105,6 +229,69 @@ impl Proof {
 	}
 }
 
+/// The version tag of the proof protocol.
+///
+/// The transcript layout and the protocols may change between any two versions of this crate, so
+/// the tag is bound to the crate version.
+pub const PROTOCOL_VERSION: &str = concat!("binius_core/", env!("CARGO_PKG_VERSION"));
+
+/// The header at the start of a proof transcript.
+///
+/// The header is the first prover message, so the Fiat-Shamir challenger observes the protocol
+/// version and the parameters before any challenge is sampled. The verifier checks the header
+/// before anything else, so that a proof produced by an incompatible crate version, or with
+/// other parameters, is rejected with a clear error instead of failing deep inside the protocol.
+#[derive(Debug, Clone, PartialEq, Eq, SerializeBytes, DeserializeBytes)]
+pub struct ProofHeader {
+	/// The [`PROTOCOL_VERSION`] of the prover.
+	pub version: String,
+	/// The binary logarithm of the inverse Reed–Solomon code rate.
+	pub log_inv_rate: usize,
+	/// The target security level in bits.
+	pub security_bits: usize,
+}
+
+impl ProofHeader {
+	/// Returns the header of a proof produced by this crate version with the given parameters.
+	pub fn new(log_inv_rate: usize, security_bits: usize) -> Self {
+		Self {
+			version: PROTOCOL_VERSION.to_string(),
+			log_inv_rate,
+			security_bits,
+		}
+	}
+
+	/// Reads the header of a proof, without verifying the proof.
+	pub fn read(proof: &Proof) -> Result<Self, Error> {
+		Self::deserialize(proof.transcript.as_slice(), SerializationMode::CanonicalTower)
+			.map_err(|err| Error::TranscriptError(err.into()))
+	}
+
+	/// Checks that the header `got` of a proof matches this header.
+	///
+	/// ## Throws
+	///
+	/// * [`VerificationError::ProtocolVersionMismatch`] if the protocol versions differ
+	/// * [`VerificationError::ProofParametersMismatch`] if the parameters differ
+	pub fn check(&self, got: &Self) -> Result<(), VerificationError> {
+		if got.version != self.version {
+			return Err(VerificationError::ProtocolVersionMismatch {
+				expected: self.version.clone(),
+				got: got.version.clone(),
+			});
+		}
+		if (got.log_inv_rate, got.security_bits) != (self.log_inv_rate, self.security_bits) {
+			return Err(VerificationError::ProofParametersMismatch {
+				expected_log_inv_rate: self.log_inv_rate,
+				expected_security_bits: self.security_bits,
+				got_log_inv_rate: got.log_inv_rate,
+				got_security_bits: got.security_bits,
+			});
+		}
+		Ok(())
+	}
+}
+
 pub type TableId = usize;
 
 /// A category of the size specification of a table.
@@ -120,3 +307,25 @@ pub enum TableSizeSpec {
 	/// The table size must be a fixed power of two.
 	Fixed { log_size: usize },
 }
+
+/// The Reed–Solomon code rate at which a commit batch is committed.
+///
+/// Tables of very different sizes are best committed at different rates: a lower rate shortens
+/// the FRI opening proof, as fewer queries reach the security level, at the cost of a longer
+/// codeword to encode and hash. The rate is part of the constraint system, so that the prover and
+/// the verifier agree on it.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, SerializeBytes, DeserializeBytes)]
+pub enum CommitRate {
+	/// The rate that the proof is produced and verified with.
+	#[default]
+	Default,
+	/// A fixed rate, given as the binary logarithm of the inverse rate.
+	Fixed { log_inv_rate: usize },
+	/// The rate chosen by [`cost::plan_log_inv_rate`] from the size of the batch, with the
+	/// binary logarithm of the inverse rate at most `max_log_inv_rate`, and a byte of opening
+	/// proof weighing as much as `proof_weight` bytes of codeword.
+	Planned {
+		max_log_inv_rate: usize,
+		proof_weight: usize,
+	},
+}
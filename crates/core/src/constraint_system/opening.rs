@@ -0,0 +1,490 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Openings of committed oracles at individual hypercube indices, proven after the fact.
+//!
+//! A proof produced by [`prove`](super::prove) commits to the witness, but reveals nothing about
+//! it beyond the boundaries. An application may later want to reveal selected witness cells, for
+//! example a single row of a table, without proving the constraint system again. The committed
+//! codewords are not systematic, so a cell can not be read off a Merkle opening directly. Instead,
+//! the value of a committed multilinear at an index is its evaluation at the corresponding point of
+//! the boolean hypercube, and [`prove_openings`] proves these evaluation claims against the
+//! commitments of the original proof with the ring-switching reduction and the PIOP compiler.
+//!
+//! The prover recommits the witness of the commit batches that contain opened oracles, which
+//! reproduces the original commitments because the commitment scheme is deterministic. The
+//! verifier takes the commitments from [`verify_commitments`](super::verify_commitments) on the
+//! original proof, so an opening proof is only accepted for the exact witness that was committed
+//! there.
+
+use binius_compute::{ComputeData, ComputeLayer, alloc::ComputeAllocator, cpu::CpuMemory};
+use binius_field::{
+	PackedFieldIndexable, RepackedExtension, TowerField,
+	as_packed_field::PackedType,
+	linear_transformation::PackedTransformationFactory,
+	tower::{PackedTop, ProverTowerFamily, ProverTowerUnderlier, TowerFamily, TowerUnderlier},
+};
+use binius_hash::{PseudoCompressionFunction, multi_digest::ParallelDigest};
+use binius_math::MultilinearPoly;
+use binius_ntt::SingleThreadedNTT;
+use binius_utils::{SerializeBytes, bail};
+use bytes::BufMut;
+use digest::{Digest, FixedOutputReset, Output, OutputSizeUser, core_api::BlockSizeUser};
+use itertools::izip;
+use tracing::instrument;
+
+use super::{
+	ConstraintSystem, Proof, ProofCommitments, ProofHeader,
+	cost::make_commit_params,
+	error::{Error, VerificationError},
+	exp,
+	verify::{read_proof_header, split_eval_claims_by_batch},
+};
+use crate::{
+	constraint_system::common::{FEncode, FExt, FFastExt},
+	fiat_shamir::Challenger,
+	merkle_tree::{BinaryMerkleTreeProver, BinaryMerkleTreeScheme},
+	oracle::{MultilinearOracleSet, OracleId},
+	piop,
+	protocols::{
+		evalcheck::{EvalPoint, EvalcheckMultilinearClaim, subclaims::MemoizedData},
+		fri::{self, CommitOutput},
+	},
+	ring_switch,
+	transcript::{ProverTranscript, TranscriptWriter, VerifierTranscript},
+	witness::MultilinearExtensionIndex,
+};
+
+/// The value of a committed oracle at an index of the boolean hypercube.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Opening<F> {
+	pub oracle: OracleId,
+	pub index: usize,
+	pub value: F,
+}
+
+/// Openings together with a proof of their values.
+#[derive(Debug, Clone)]
+pub struct ProvenOpenings<F> {
+	pub openings: Vec<Opening<F>>,
+	pub proof: Proof,
+}
+
+/// Proves the values of committed oracles at the given hypercube indices.
+///
+/// The constraint system, parameters, digest, table sizes and witness must be the ones the original
+/// proof was produced with. The returned proof is checked with [`verify_openings`].
+///
+/// ## Throws
+///
+/// * [`Error::OpeningIndexOutOfRange`] if an index is not on the hypercube of its oracle
+/// * [`Error::RingSwitch`] if an opened oracle is not committed
+#[allow(clippy::too_many_arguments)]
+pub fn prove_openings<
+	Hal,
+	U,
+	Tower,
+	Hash,
+	Compress,
+	Challenger_,
+	HostAllocatorType,
+	DeviceAllocatorType,
+>(
+	compute_data: &mut ComputeData<Tower::B128, Hal, HostAllocatorType, DeviceAllocatorType>,
+	constraint_system: &ConstraintSystem<FExt<Tower>>,
+	log_inv_rate: usize,
+	security_bits: usize,
+	constraint_system_digest: &Output<Hash::Digest>,
+	table_sizes: &[usize],
+	witness: MultilinearExtensionIndex<PackedType<U, FExt<Tower>>>,
+	indices: &[(OracleId, usize)],
+) -> Result<ProvenOpenings<FExt<Tower>>, Error>
+where
+	Hal: ComputeLayer<Tower::B128> + Default + Sync,
+	U: ProverTowerUnderlier<Tower>,
+	Tower: ProverTowerFamily,
+	Tower::B128:
+		binius_math::TowerTop + binius_math::PackedTop + PackedTop<Tower> + From<FFastExt<Tower>>,
+	Hash: ParallelDigest,
+	Hash::Digest: BlockSizeUser + FixedOutputReset + Send + Sync + Clone,
+	Compress: PseudoCompressionFunction<Output<Hash::Digest>, 2> + Default + Sync,
+	Challenger_: Challenger + Default,
+	PackedType<U, Tower::B128>: PackedTop<Tower>
+		+ PackedFieldIndexable
+		+ RepackedExtension<PackedType<U, Tower::B1>>
+		+ RepackedExtension<PackedType<U, Tower::B8>>
+		+ RepackedExtension<PackedType<U, Tower::B16>>
+		+ RepackedExtension<PackedType<U, Tower::B32>>
+		+ RepackedExtension<PackedType<U, Tower::B64>>
+		+ RepackedExtension<PackedType<U, Tower::B128>>
+		+ PackedTransformationFactory<PackedType<U, Tower::FastB128>>
+		+ binius_math::PackedTop,
+	PackedType<U, Tower::FastB128>: PackedTransformationFactory<PackedType<U, Tower::B128>>,
+	HostAllocatorType: ComputeAllocator<Tower::B128, CpuMemory> + Send,
+	DeviceAllocatorType: ComputeAllocator<Tower::B128, Hal::DevMem> + Send,
+{
+	let thread_pool = compute_data.thread_pool.clone();
+	thread_pool.install(move || {
+		prove_openings_on_current_thread_pool::<_, U, Tower, Hash, Compress, Challenger_, _, _>(
+			compute_data,
+			constraint_system,
+			log_inv_rate,
+			security_bits,
+			constraint_system_digest,
+			table_sizes,
+			witness,
+			indices,
+		)
+	})
+}
+
+#[allow(clippy::too_many_arguments)]
+#[instrument("constraint_system::prove_openings", skip_all, level = "debug")]
+fn prove_openings_on_current_thread_pool<
+	Hal,
+	U,
+	Tower,
+	Hash,
+	Compress,
+	Challenger_,
+	HostAllocatorType,
+	DeviceAllocatorType,
+>(
+	compute_data: &ComputeData<Tower::B128, Hal, HostAllocatorType, DeviceAllocatorType>,
+	constraint_system: &ConstraintSystem<FExt<Tower>>,
+	log_inv_rate: usize,
+	security_bits: usize,
+	constraint_system_digest: &Output<Hash::Digest>,
+	table_sizes: &[usize],
+	mut witness: MultilinearExtensionIndex<PackedType<U, FExt<Tower>>>,
+	indices: &[(OracleId, usize)],
+) -> Result<ProvenOpenings<FExt<Tower>>, Error>
+where
+	Hal: ComputeLayer<Tower::B128> + Default,
+	U: ProverTowerUnderlier<Tower>,
+	Tower: ProverTowerFamily,
+	Tower::B128:
+		binius_math::TowerTop + binius_math::PackedTop + PackedTop<Tower> + From<FFastExt<Tower>>,
+	Hash: ParallelDigest,
+	Hash::Digest: BlockSizeUser + FixedOutputReset + Send + Sync + Clone,
+	Compress: PseudoCompressionFunction<Output<Hash::Digest>, 2> + Default + Sync,
+	Challenger_: Challenger + Default,
+	PackedType<U, Tower::B128>: PackedTop<Tower>
+		+ PackedFieldIndexable
+		+ RepackedExtension<PackedType<U, Tower::B1>>
+		+ RepackedExtension<PackedType<U, Tower::B8>>
+		+ RepackedExtension<PackedType<U, Tower::B16>>
+		+ RepackedExtension<PackedType<U, Tower::B32>>
+		+ RepackedExtension<PackedType<U, Tower::B64>>
+		+ RepackedExtension<PackedType<U, Tower::B128>>
+		+ PackedTransformationFactory<PackedType<U, Tower::FastB128>>
+		+ binius_math::PackedTop,
+	PackedType<U, Tower::FastB128>: PackedTransformationFactory<PackedType<U, Tower::B128>>,
+	HostAllocatorType: ComputeAllocator<Tower::B128, CpuMemory>,
+	DeviceAllocatorType: ComputeAllocator<Tower::B128, Hal::DevMem>,
+{
+	constraint_system.check_table_sizes(table_sizes)?;
+	let oracles = constraint_system.oracles.instantiate(table_sizes)?;
+
+	// The results of exponentiations are committed, but only added to the witness by the prover.
+	let mut exponents = constraint_system.exponents.clone();
+	exponents.retain(|exp| !oracles.is_zero_sized(exp.exp_result_id));
+	let _exp_witnesses = exp::make_exp_witnesses::<U, Tower>(&mut witness, &oracles, &exponents)?;
+
+	let openings = indices
+		.iter()
+		.map(|&(oracle, index)| {
+			check_opening_index(&oracles, oracle, index)?;
+			let value = witness
+				.get_multilin_poly(oracle)?
+				.evaluate_on_hypercube(index)?;
+			Ok(Opening {
+				oracle,
+				index,
+				value,
+			})
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Hash, _>::new(Compress::default());
+	let commit_metas = piop::make_oracle_commit_metas(&oracles, &constraint_system.commit_batches)?;
+	let batch_eval_claims = split_eval_claims_by_batch(
+		make_eval_claims(&oracles, &openings),
+		commit_metas
+			.iter()
+			.map(|(_, oracle_to_commit_index)| oracle_to_commit_index),
+	);
+
+	let fri_params = make_commit_params::<_, FEncode<Tower>, _>(
+		constraint_system,
+		&commit_metas,
+		merkle_prover.scheme(),
+		security_bits,
+		log_inv_rate,
+	)?;
+
+	// Recommit the batches that contain opened oracles.
+	let batches = izip!(commit_metas, fri_params, batch_eval_claims)
+		.filter(|(.., eval_claims)| !eval_claims.is_empty())
+		.map(|((commit_meta, oracle_to_commit_index), fri_params, eval_claims)| {
+			let committed_multilins = piop::collect_committed_witnesses::<U, _>(
+				&commit_meta,
+				&oracle_to_commit_index,
+				&oracles,
+				&witness,
+			)?;
+			let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace())?
+				.precompute_twiddles()
+				.multithreaded();
+			let (commitment, committed, codeword) = if compute_data.device_resident {
+				let fri::DeviceCommitOutput {
+					commitment,
+					committed,
+					device_codeword,
+				} = piop::commit_on_device(
+					compute_data.hal,
+					&compute_data.dev_alloc,
+					&fri_params,
+					&ntt,
+					&merkle_prover,
+					&committed_multilins,
+				)?;
+				(commitment, committed, fri::CommittedCodeword::Device(device_codeword))
+			} else {
+				let CommitOutput {
+					commitment,
+					committed,
+					codeword,
+				} = piop::commit(&fri_params, &ntt, &merkle_prover, &committed_multilins)?;
+				(commitment, committed, fri::CommittedCodeword::Host(codeword))
+			};
+			Ok::<_, Error>((
+				commit_meta,
+				oracle_to_commit_index,
+				eval_claims,
+				committed_multilins,
+				fri_params,
+				ntt,
+				commitment,
+				committed,
+				codeword,
+			))
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut transcript = ProverTranscript::<Challenger_>::new();
+	transcript
+		.message()
+		.write(&ProofHeader::new(log_inv_rate, security_bits));
+	observe_statement(
+		&mut transcript.observe(),
+		constraint_system_digest,
+		table_sizes,
+		batches.iter().map(|(.., commitment, _, _)| commitment),
+		&openings,
+	);
+
+	for (
+		commit_meta,
+		oracle_to_commit_index,
+		eval_claims,
+		committed_multilins,
+		fri_params,
+		ntt,
+		_,
+		committed,
+		codeword,
+	) in batches
+	{
+		let system = ring_switch::EvalClaimSystem::new(
+			&oracles,
+			&commit_meta,
+			&oracle_to_commit_index,
+			&eval_claims,
+		)?;
+		let ring_switch::ReducedWitness {
+			transparents: transparent_multilins,
+			sumcheck_claims: piop_sumcheck_claims,
+		} = ring_switch::prove(
+			&system,
+			&committed_multilins,
+			&mut transcript,
+			&mut MemoizedData::new(),
+			compute_data.hal,
+			&compute_data.dev_alloc,
+		)?;
+		piop::prove(
+			compute_data,
+			&fri_params,
+			&ntt,
+			&merkle_prover,
+			&commit_meta,
+			committed,
+			&codeword,
+			&committed_multilins,
+			transparent_multilins,
+			&piop_sumcheck_claims,
+			&mut transcript,
+		)?;
+	}
+
+	let proof = Proof {
+		transcript: transcript.finalize(),
+	};
+	Ok(ProvenOpenings { openings, proof })
+}
+
+/// Verifies a proof of openings against the commitments of an original proof.
+///
+/// `commitments` must be obtained with [`verify_commitments`](super::verify_commitments) from the
+/// original proof, with the same constraint system, parameters and digest.
+#[instrument("constraint_system::verify_openings", skip_all, level = "debug")]
+#[allow(clippy::too_many_arguments)]
+pub fn verify_openings<U, Tower, Hash, Compress, Challenger_>(
+	constraint_system: &ConstraintSystem<FExt<Tower>>,
+	log_inv_rate: usize,
+	security_bits: usize,
+	constraint_system_digest: &Output<Hash>,
+	commitments: &ProofCommitments<Output<Hash>>,
+	openings: &[Opening<FExt<Tower>>],
+	proof: Proof,
+) -> Result<(), Error>
+where
+	U: TowerUnderlier<Tower>,
+	Tower: TowerFamily,
+	Tower::B128: binius_math::TowerTop + binius_math::PackedTop + PackedTop<Tower>,
+	Hash: Digest + BlockSizeUser + OutputSizeUser,
+	Compress: PseudoCompressionFunction<Output<Hash>, 2> + Default + Sync,
+	Challenger_: Challenger + Default,
+{
+	let ProofCommitments {
+		table_sizes,
+		commitments,
+	} = commitments;
+	constraint_system.check_table_sizes(table_sizes)?;
+	let oracles = constraint_system.oracles.instantiate(table_sizes)?;
+	for opening in openings {
+		check_opening_index(&oracles, opening.oracle, opening.index)?;
+	}
+
+	let merkle_scheme = BinaryMerkleTreeScheme::<FExt<Tower>, Hash, _>::new(Compress::default());
+	let commit_metas = piop::make_oracle_commit_metas(&oracles, &constraint_system.commit_batches)?;
+	if commitments.len() != commit_metas.len() {
+		bail!(VerificationError::IncorrectNumberOfCommitments);
+	}
+	let batch_eval_claims = split_eval_claims_by_batch(
+		make_eval_claims(&oracles, openings),
+		commit_metas
+			.iter()
+			.map(|(_, oracle_to_commit_index)| oracle_to_commit_index),
+	);
+	let fri_params = make_commit_params::<_, FEncode<Tower>, _>(
+		constraint_system,
+		&commit_metas,
+		&merkle_scheme,
+		security_bits,
+		log_inv_rate,
+	)?;
+	let batches = izip!(&commit_metas, fri_params, commitments, batch_eval_claims)
+		.filter(|(.., eval_claims)| !eval_claims.is_empty())
+		.collect::<Vec<_>>();
+
+	let mut transcript = VerifierTranscript::<Challenger_>::new(proof.transcript);
+	read_proof_header(&mut transcript, log_inv_rate, security_bits)?;
+	observe_statement(
+		&mut transcript.observe(),
+		constraint_system_digest,
+		table_sizes,
+		batches.iter().map(|(_, _, commitment, _)| *commitment),
+		openings,
+	);
+
+	for ((commit_meta, oracle_to_commit_index), fri_params, commitment, eval_claims) in batches {
+		let system = ring_switch::EvalClaimSystem::new(
+			&oracles,
+			commit_meta,
+			oracle_to_commit_index,
+			&eval_claims,
+		)?;
+		let ring_switch::ReducedClaim {
+			transparents,
+			sumcheck_claims: piop_sumcheck_claims,
+		} = ring_switch::verify(&system, &mut transcript)?;
+		piop::verify(
+			commit_meta,
+			&merkle_scheme,
+			&fri_params,
+			commitment,
+			&transparents,
+			&piop_sumcheck_claims,
+			&mut transcript,
+		)?;
+	}
+
+	transcript.finalize()?;
+	Ok(())
+}
+
+fn check_opening_index<F: TowerField>(
+	oracles: &MultilinearOracleSet<F>,
+	oracle: OracleId,
+	index: usize,
+) -> Result<(), Error> {
+	let n_vars = oracles.n_vars(oracle);
+	if index >> n_vars != 0 {
+		bail!(Error::OpeningIndexOutOfRange {
+			oracle,
+			index,
+			n_vars,
+		});
+	}
+	Ok(())
+}
+
+/// The evaluation of a multilinear at a hypercube index is its evaluation at the point whose
+/// coordinates are the bits of the index.
+fn make_eval_claims<F: TowerField>(
+	oracles: &MultilinearOracleSet<F>,
+	openings: &[Opening<F>],
+) -> Vec<EvalcheckMultilinearClaim<F>> {
+	openings
+		.iter()
+		.map(|opening| {
+			let eval_point = (0..oracles.n_vars(opening.oracle))
+				.map(|i| {
+					if (opening.index >> i) & 1 == 1 {
+						F::ONE
+					} else {
+						F::ZERO
+					}
+				})
+				.collect::<Vec<_>>();
+			EvalcheckMultilinearClaim {
+				id: opening.oracle,
+				eval_point: EvalPoint::from(eval_point),
+				eval: opening.value,
+			}
+		})
+		.collect()
+}
+
+fn observe_statement<'a, F, Digest>(
+	writer: &mut TranscriptWriter<impl BufMut>,
+	constraint_system_digest: &Digest,
+	table_sizes: &[usize],
+	commitments: impl IntoIterator<Item = &'a Digest>,
+	openings: &[Opening<F>],
+) where
+	F: TowerField,
+	Digest: SerializeBytes + AsRef<[u8]> + 'a,
+{
+	writer.write_slice(constraint_system_digest.as_ref());
+	writer.write_slice(table_sizes);
+	for commitment in commitments {
+		writer.write(commitment);
+	}
+	for opening in openings {
+		writer.write(&opening.oracle.index());
+		writer.write(&opening.index);
+		writer.write_scalar(opening.value);
+	}
+}
@@ -0,0 +1,216 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Named parameter presets for proving and verifying constraint systems.
+//!
+//! [`prove`](super::prove) and [`verify`](super::verify) take the Reed–Solomon rate, the security
+//! level and the hash scheme as separate parameters, and the prover and the verifier must agree on
+//! all of them. A [`Profile`] fixes a vetted combination under a stable identifier, which is stored
+//! in the header of a [`ProfiledProof`]. The verifier is configured with the profile it accepts and
+//! rejects a proof that names any other, since letting the proof choose its own parameters would
+//! let a prover downgrade the security level. The FRI fold arity is not part of a profile, because
+//! it is derived deterministically from the other parameters and the size of the committed data.
+
+use std::{fmt, str::FromStr};
+
+use binius_field::BinaryField128b;
+use binius_macros::{DeserializeBytes, SerializeBytes};
+
+use super::{
+	ConstraintSystem, Proof,
+	channel::Boundary,
+	error::{Error, VerificationError},
+	hash_scheme::{self, TaggedProof},
+};
+
+/// A named set of proving parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Profile {
+	/// 96 bits of security with the lowest prover cost, at the expense of proof size.
+	Bits96Fast,
+	/// 100 bits of security with the lowest prover cost, at the expense of proof size.
+	Bits100Fast,
+	/// 100 bits of security with a lower code rate, which trades prover time for smaller proofs.
+	///
+	/// The soundness error of FRI over the 128-bit extension field grows with the length of the
+	/// committed codewords, so security levels close to 128 bits are not attainable.
+	Bits100Small,
+}
+
+impl Profile {
+	/// All profiles, in the order of their security level.
+	pub const ALL: [Self; 3] = [Self::Bits96Fast, Self::Bits100Fast, Self::Bits100Small];
+
+	/// The stable identifier of the profile.
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Bits96Fast => "bits96-fast",
+			Self::Bits100Fast => "bits100-fast",
+			Self::Bits100Small => "bits100-small",
+		}
+	}
+
+	/// The target security level in bits.
+	pub const fn security_bits(self) -> usize {
+		match self {
+			Self::Bits96Fast => 96,
+			Self::Bits100Fast | Self::Bits100Small => 100,
+		}
+	}
+
+	/// The negative binary logarithm of the Reed–Solomon code rate.
+	pub const fn log_inv_rate(self) -> usize {
+		match self {
+			Self::Bits96Fast | Self::Bits100Fast => 1,
+			Self::Bits100Small => 3,
+		}
+	}
+
+	/// The identifier of the hash scheme in the [`hash_scheme`] registry.
+	///
+	/// Proofs for every profile use Grøstl-256 for the Merkle trees and the Fiat-Shamir
+	/// challenger, which the prover may compute with any implementation of that hash function.
+	pub const fn hash_scheme(self) -> &'static str {
+		match self {
+			Self::Bits96Fast | Self::Bits100Fast | Self::Bits100Small => "groestl256",
+		}
+	}
+}
+
+impl fmt::Display for Profile {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+impl FromStr for Profile {
+	type Err = Error;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		Self::ALL
+			.into_iter()
+			.find(|profile| profile.name() == name)
+			.ok_or_else(|| Error::UnknownProfile {
+				name: name.to_string(),
+			})
+	}
+}
+
+/// A proof together with the identifier of the profile that it was produced with.
+#[derive(Debug, Clone, SerializeBytes, DeserializeBytes)]
+pub struct ProfiledProof {
+	pub profile: String,
+	pub proof: Proof,
+}
+
+impl ProfiledProof {
+	pub fn new(profile: Profile, proof: Proof) -> Self {
+		Self {
+			profile: profile.name().to_string(),
+			proof,
+		}
+	}
+}
+
+/// Verifies a profiled proof against a constraint system with the parameters of `profile`.
+///
+/// The profile is chosen by the verifier, and the proof must name the same profile.
+///
+/// ## Throws
+///
+/// * [`VerificationError::ProfileMismatch`] if the proof was produced with another profile
+pub fn verify_profiled(
+	constraint_system: &ConstraintSystem<BinaryField128b>,
+	profile: Profile,
+	boundaries: &[Boundary<BinaryField128b>],
+	proof: ProfiledProof,
+) -> Result<(), Error> {
+	let ProfiledProof {
+		profile: proof_profile,
+		proof,
+	} = proof;
+	if proof_profile != profile.name() {
+		return Err(VerificationError::ProfileMismatch {
+			expected: profile.name().to_string(),
+			got: proof_profile,
+		}
+		.into());
+	}
+	hash_scheme::verify_tagged(
+		constraint_system,
+		profile.log_inv_rate(),
+		profile.security_bits(),
+		boundaries,
+		TaggedProof {
+			hash_scheme: profile.hash_scheme().to_string(),
+			proof,
+		},
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_utils::{DeserializeBytes, SerializationMode, SerializeBytes};
+
+	use super::*;
+
+	#[test]
+	fn test_profile_names_round_trip() {
+		for profile in Profile::ALL {
+			assert_eq!(profile.to_string().parse::<Profile>().unwrap(), profile);
+			// The identifier names the security level that the profile actually provides.
+			assert!(
+				profile
+					.name()
+					.starts_with(&format!("bits{}-", profile.security_bits()))
+			);
+			assert!(hash_scheme::is_registered(profile.hash_scheme()));
+		}
+		assert!(matches!(
+			"bits64-insecure".parse::<Profile>(),
+			Err(Error::UnknownProfile { name }) if name == "bits64-insecure"
+		));
+	}
+
+	#[test]
+	fn test_profiled_proof_serialization() {
+		let profiled = ProfiledProof::new(
+			Profile::Bits100Small,
+			Proof {
+				transcript: vec![1, 2, 3],
+			},
+		);
+
+		let mut buf = Vec::new();
+		profiled
+			.serialize(&mut buf, SerializationMode::CanonicalTower)
+			.unwrap();
+		let deserialized =
+			ProfiledProof::deserialize(buf.as_slice(), SerializationMode::CanonicalTower).unwrap();
+		assert_eq!(deserialized.profile, "bits100-small");
+		assert_eq!(deserialized.proof.transcript, profiled.proof.transcript);
+	}
+
+	#[test]
+	fn test_verify_profiled_rejects_other_profile() {
+		// A proof that names a weaker profile than the verifier expects is rejected before it is
+		// verified, so the constraint system does not matter.
+		let constraint_system = ConstraintSystem {
+			oracles: Default::default(),
+			table_constraints: vec![],
+			non_zero_oracle_ids: vec![],
+			flushes: vec![],
+			exponents: vec![],
+			channel_count: 0,
+			table_size_specs: vec![],
+			commit_batches: vec![],
+			commit_rates: vec![],
+			commit_log_leaf_sizes: vec![],
+		};
+		let proof = ProfiledProof::new(Profile::Bits96Fast, Proof { transcript: vec![] });
+		assert!(matches!(
+			verify_profiled(&constraint_system, Profile::Bits100Small, &[], proof),
+			Err(Error::Verification(VerificationError::ProfileMismatch { expected, got }))
+				if expected == "bits100-small" && got == "bits96-fast"
+		));
+	}
+}
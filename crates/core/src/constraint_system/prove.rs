@@ -20,29 +20,33 @@ use binius_math::{
 };
 use binius_maybe_rayon::prelude::*;
 use binius_ntt::SingleThreadedNTT;
-use binius_utils::{bail, checked_arithmetics::log2_ceil_usize};
+use binius_utils::{bail, checked_arithmetics::log2_ceil_usize, sparse_index::SparseIndex};
 use bytemuck::zeroed_vec;
 use digest::{FixedOutputReset, Output, core_api::BlockSizeUser};
 use itertools::chain;
 use tracing::instrument;
+#[cfg(not(target_arch = "wasm32"))]
 use tracing_profile::utils::emit_max_rss;
 
 use super::{
-	ConstraintSystem, Proof,
+	ConstraintSystem, MAX_CONSTRAINT_DEGREE, Proof, ProofHeader,
 	channel::Boundary,
+	cost::make_commit_params,
 	error::Error,
-	verify::{make_flush_oracles, max_n_vars_and_skip_rounds},
+	limits::ProverLimits,
+	verify::{make_flush_oracles, max_n_vars_and_skip_rounds, split_eval_claims_by_batch},
 };
 use crate::{
 	constraint_system::{
 		Flush,
 		channel::OracleOrConst,
 		common::{FDomain, FEncode, FExt, FFastExt},
+		events::{self, Phase, PhaseEvents, Role},
 		exp::{self, reorder_exponents},
 		verify::augment_flush_po2_step_down,
 	},
 	fiat_shamir::{CanSample, Challenger},
-	merkle_tree::BinaryMerkleTreeProver,
+	merkle_tree::{BinaryMerkleTreeProver, MerkleTreeProver},
 	oracle::{
 		Constraint, ConstraintSetBuilder, MultilinearOracleSet, MultilinearPolyVariant, OracleId,
 		SizedConstraintSet,
@@ -51,9 +55,9 @@ use crate::{
 	protocols::{
 		evalcheck::{
 			ConstraintSetEqIndPoint, EvalPoint, EvalcheckMultilinearClaim,
-			subclaims::{MemoizedData, prove_mlecheck_with_switchover},
+			subclaims::{MemoizedData, prove_mlechecks_with_switchover},
 		},
-		fri::CommitOutput,
+		fri::{self, CommitOutput, FRIParams},
 		gkr_exp,
 		gkr_gpa::{self, GrandProductBatchProveOutput, GrandProductWitness},
 		greedy_evalcheck::{self, GreedyEvalcheckProveOutput},
@@ -69,8 +73,9 @@ use crate::{
 };
 
 /// Generates a proof that a witness satisfies a constraint system with the standard FRI PCS.
+///
+/// The host-side parallel operations run on the thread pool of the compute data.
 #[allow(clippy::too_many_arguments)]
-#[instrument("constraint_system::prove", skip_all, level = "debug")]
 pub fn prove<
 	Hal,
 	U,
@@ -89,8 +94,148 @@ pub fn prove<
 	constraint_system_digest: &Output<Hash::Digest>,
 	boundaries: &[Boundary<FExt<Tower>>],
 	table_sizes: &[usize],
+	witness: MultilinearExtensionIndex<PackedType<U, FExt<Tower>>>,
+	backend: &Backend,
+) -> Result<Proof, Error>
+where
+	Hal: ComputeLayer<Tower::B128> + Default + Sync,
+	U: ProverTowerUnderlier<Tower>,
+	Tower: ProverTowerFamily,
+	Tower::B128:
+		binius_math::TowerTop + binius_math::PackedTop + PackedTop<Tower> + From<FFastExt<Tower>>,
+	Hash: ParallelDigest,
+	Hash::Digest: BlockSizeUser + FixedOutputReset + Send + Sync + Clone,
+	Compress: PseudoCompressionFunction<Output<Hash::Digest>, 2> + Default + Sync,
+	Challenger_: Challenger + Default,
+	Backend: ComputationBackend,
+	// REVIEW: Consider changing TowerFamily and associated traits to shorten/remove these bounds
+	PackedType<U, Tower::B128>: PackedTop<Tower>
+		+ PackedFieldIndexable
+		// REVIEW: remove this bound after piop::commit is adjusted
+		+ RepackedExtension<PackedType<U, Tower::B1>>
+		+ RepackedExtension<PackedType<U, Tower::B8>>
+		+ RepackedExtension<PackedType<U, Tower::B16>>
+		+ RepackedExtension<PackedType<U, Tower::B32>>
+		+ RepackedExtension<PackedType<U, Tower::B64>>
+		+ RepackedExtension<PackedType<U, Tower::B128>>
+		+ PackedTransformationFactory<PackedType<U, Tower::FastB128>>
+		+ binius_math::PackedTop,
+	PackedType<U, Tower::FastB128>: PackedTransformationFactory<PackedType<U, Tower::B128>>,
+	HostAllocatorType: ComputeAllocator<Tower::B128, CpuMemory> + Send,
+	DeviceAllocatorType: ComputeAllocator<Tower::B128, Hal::DevMem> + Send,
+{
+	prove_with_limits::<_, U, Tower, Hash, Compress, Challenger_, _, _, _>(
+		compute_data,
+		constraint_system,
+		log_inv_rate,
+		security_bits,
+		constraint_system_digest,
+		boundaries,
+		table_sizes,
+		witness,
+		backend,
+		&ProverLimits::default(),
+	)
+}
+
+/// Generates a proof like [`prove`], stopping early with an error when one of the `limits` is hit.
+///
+/// See the [`limits`](super::limits) module for when the limits are checked.
+///
+/// ## Throws
+///
+/// * [`Error::ProverCancelled`], [`Error::ProverTimeLimitExceeded`] or
+///   [`Error::ProverMemoryLimitExceeded`] if the prover stopped because of the limits
+#[allow(clippy::too_many_arguments)]
+pub fn prove_with_limits<
+	Hal,
+	U,
+	Tower,
+	Hash,
+	Compress,
+	Challenger_,
+	Backend,
+	HostAllocatorType,
+	DeviceAllocatorType,
+>(
+	compute_data: &mut ComputeData<Tower::B128, Hal, HostAllocatorType, DeviceAllocatorType>,
+	constraint_system: &ConstraintSystem<FExt<Tower>>,
+	log_inv_rate: usize,
+	security_bits: usize,
+	constraint_system_digest: &Output<Hash::Digest>,
+	boundaries: &[Boundary<FExt<Tower>>],
+	table_sizes: &[usize],
+	witness: MultilinearExtensionIndex<PackedType<U, FExt<Tower>>>,
+	backend: &Backend,
+	limits: &ProverLimits,
+) -> Result<Proof, Error>
+where
+	Hal: ComputeLayer<Tower::B128> + Default + Sync,
+	U: ProverTowerUnderlier<Tower>,
+	Tower: ProverTowerFamily,
+	Tower::B128:
+		binius_math::TowerTop + binius_math::PackedTop + PackedTop<Tower> + From<FFastExt<Tower>>,
+	Hash: ParallelDigest,
+	Hash::Digest: BlockSizeUser + FixedOutputReset + Send + Sync + Clone,
+	Compress: PseudoCompressionFunction<Output<Hash::Digest>, 2> + Default + Sync,
+	Challenger_: Challenger + Default,
+	Backend: ComputationBackend,
+	// REVIEW: Consider changing TowerFamily and associated traits to shorten/remove these bounds
+	PackedType<U, Tower::B128>: PackedTop<Tower>
+		+ PackedFieldIndexable
+		// REVIEW: remove this bound after piop::commit is adjusted
+		+ RepackedExtension<PackedType<U, Tower::B1>>
+		+ RepackedExtension<PackedType<U, Tower::B8>>
+		+ RepackedExtension<PackedType<U, Tower::B16>>
+		+ RepackedExtension<PackedType<U, Tower::B32>>
+		+ RepackedExtension<PackedType<U, Tower::B64>>
+		+ RepackedExtension<PackedType<U, Tower::B128>>
+		+ PackedTransformationFactory<PackedType<U, Tower::FastB128>>
+		+ binius_math::PackedTop,
+	PackedType<U, Tower::FastB128>: PackedTransformationFactory<PackedType<U, Tower::B128>>,
+	HostAllocatorType: ComputeAllocator<Tower::B128, CpuMemory> + Send,
+	DeviceAllocatorType: ComputeAllocator<Tower::B128, Hal::DevMem> + Send,
+{
+	let thread_pool = compute_data.thread_pool.clone();
+	thread_pool.install(move || {
+		prove_on_current_thread_pool::<_, U, Tower, Hash, Compress, Challenger_, _, _, _>(
+			compute_data,
+			constraint_system,
+			log_inv_rate,
+			security_bits,
+			constraint_system_digest,
+			boundaries,
+			table_sizes,
+			witness,
+			backend,
+			limits,
+		)
+	})
+}
+
+#[allow(clippy::too_many_arguments)]
+#[instrument("constraint_system::prove", skip_all, level = "debug")]
+fn prove_on_current_thread_pool<
+	Hal,
+	U,
+	Tower,
+	Hash,
+	Compress,
+	Challenger_,
+	Backend,
+	HostAllocatorType,
+	DeviceAllocatorType,
+>(
+	compute_data: &ComputeData<Tower::B128, Hal, HostAllocatorType, DeviceAllocatorType>,
+	constraint_system: &ConstraintSystem<FExt<Tower>>,
+	log_inv_rate: usize,
+	security_bits: usize,
+	constraint_system_digest: &Output<Hash::Digest>,
+	boundaries: &[Boundary<FExt<Tower>>],
+	table_sizes: &[usize],
 	mut witness: MultilinearExtensionIndex<PackedType<U, FExt<Tower>>>,
 	backend: &Backend,
+	limits: &ProverLimits,
 ) -> Result<Proof, Error>
 where
 	Hal: ComputeLayer<Tower::B128> + Default,
@@ -124,6 +269,8 @@ where
 		rayon_threads = binius_maybe_rayon::current_num_threads(),
 		"using computation backend: {backend:?}"
 	);
+	let limits = limits.start();
+	let mut phase_events = PhaseEvents::new(Role::Prover, false);
 
 	let domain_factory = DefaultEvaluationDomainFactory::<FDomain<Tower>>::default();
 	let fast_domain_factory = IsomorphicEvaluationDomainFactory::<FFastExt<Tower>>::default();
@@ -136,9 +283,13 @@ where
 		mut non_zero_oracle_ids,
 		channel_count,
 		table_size_specs,
+		commit_batches,
+		commit_rates: _,
+		commit_log_leaf_sizes: _,
 	} = constraint_system.clone();
 
 	constraint_system.check_table_sizes(table_sizes)?;
+	constraint_system.check_degree(MAX_CONSTRAINT_DEGREE)?;
 	let mut oracles = oracles.instantiate(table_sizes)?;
 
 	// Prepare the constraint system for proving:
@@ -169,13 +320,19 @@ where
 	reorder_exponents(&mut exponents, &oracles);
 
 	let mut transcript = ProverTranscript::<Challenger_>::new();
+	transcript
+		.message()
+		.write(&ProofHeader::new(log_inv_rate, security_bits));
 	transcript
 		.observe()
 		.write_slice(constraint_system_digest.as_ref());
 	transcript.observe().write_slice(boundaries);
 	let mut writer = transcript.message();
 	writer.write_slice(table_sizes);
+	events::emit_table_sizes(Role::Prover, table_sizes);
 
+	limits.check()?;
+	phase_events.enter(Phase::Witness, transcript.bytes_written());
 	let witness_span = tracing::info_span!(
 		"[phase] Witness Finalization",
 		phase = "witness",
@@ -200,39 +357,134 @@ where
 	let merkle_prover = BinaryMerkleTreeProver::<_, Hash, _>::new(Compress::default());
 	let merkle_scheme = merkle_prover.scheme();
 
-	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(&oracles)?;
-	let committed_multilins = piop::collect_committed_witnesses::<U, _>(
-		&commit_meta,
-		&oracle_to_commit_index,
-		&oracles,
-		&witness,
-	)?;
-
-	let fri_params = piop::make_commit_params_with_optimal_arity::<_, FEncode<Tower>, _>(
-		&commit_meta,
+	let commit_metas = piop::make_oracle_commit_metas(&oracles, &commit_batches)?;
+	let fri_params = make_commit_params::<_, FEncode<Tower>, _>(
+		constraint_system,
+		&commit_metas,
 		merkle_scheme,
 		security_bits,
 		log_inv_rate,
 	)?;
-	let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace())?
-		.precompute_twiddles()
-		.multithreaded();
 
+	limits.check()?;
+	phase_events.enter(Phase::Commit, transcript.bytes_written());
 	let commit_span =
 		tracing::info_span!("[phase] Commit", phase = "commit", perfetto_category = "phase.main")
 			.entered();
-	let CommitOutput {
-		commitment,
-		committed,
-		codeword,
-	} = piop::commit(&fri_params, &ntt, &merkle_prover, &committed_multilins)?;
-	emit_max_rss();
-	drop(commit_span);
+	let prepare_batch = |commit_meta: &piop::CommitMeta,
+	                     oracle_to_commit_index: &SparseIndex<usize>,
+	                     fri_params: FRIParams<FExt<Tower>, FEncode<Tower>>| {
+		limits.check()?;
+		let committed_multilins = piop::collect_committed_witnesses::<U, _>(
+			commit_meta,
+			oracle_to_commit_index,
+			&oracles,
+			&witness,
+		)?;
+
+		let ntt = SingleThreadedNTT::with_subspace(fri_params.rs_code().subspace())?
+			.precompute_twiddles()
+			.multithreaded();
+		Ok::<_, Error>((committed_multilins, fri_params, ntt))
+	};
 
-	// Observe polynomial commitment
+	let committed_batches = if compute_data.device_resident {
+		// The codewords are encoded on the device and stay resident there for the FRI fold rounds.
+		// The device is a single shared resource, so the batches are committed one after another.
+		iter::zip(commit_metas, fri_params)
+			.map(|((commit_meta, oracle_to_commit_index), fri_params)| {
+				let (committed_multilins, fri_params, ntt) =
+					prepare_batch(&commit_meta, &oracle_to_commit_index, fri_params)?;
+				let fri::DeviceCommitOutput {
+					commitment,
+					committed,
+					device_codeword,
+				} = piop::commit_on_device(
+					compute_data.hal,
+					&compute_data.dev_alloc,
+					&fri_params,
+					&ntt,
+					&merkle_prover,
+					&committed_multilins,
+				)?;
+				Ok::<_, Error>((
+					commit_meta,
+					oracle_to_commit_index,
+					committed_multilins,
+					fri_params,
+					ntt,
+					commitment,
+					committed,
+					fri::CommittedCodeword::Device(device_codeword),
+				))
+			})
+			.collect::<Result<Vec<_>, _>>()?
+	} else {
+		// The batches are committed one after another, so that only one codeword is being encoded
+		// at a time. The encoding and Merkle hashing are parallelized internally by `piop::commit`.
+		iter::zip(commit_metas, fri_params)
+			.map(|((commit_meta, oracle_to_commit_index), fri_params)| {
+				let (committed_multilins, fri_params, ntt) =
+					prepare_batch(&commit_meta, &oracle_to_commit_index, fri_params)?;
+				let CommitOutput {
+					commitment,
+					committed,
+					codeword,
+				} = piop::commit(&fri_params, &ntt, &merkle_prover, &committed_multilins)?;
+				Ok::<_, Error>((
+					commit_meta,
+					oracle_to_commit_index,
+					committed_multilins,
+					fri_params,
+					ntt,
+					commitment,
+					committed,
+					fri::CommittedCodeword::Host(codeword),
+				))
+			})
+			.collect::<Result<Vec<_>, _>>()?
+	};
+
+	// Only the commitments are written to the transcript, in the order of the batches.
+	let (commitments, batches): (Vec<_>, Vec<_>) = committed_batches
+		.into_iter()
+		.map(
+			|(
+				commit_meta,
+				oracle_to_commit_index,
+				committed_multilins,
+				fri_params,
+				ntt,
+				commitment,
+				committed,
+				codeword,
+			)| {
+				(
+					commitment,
+					(
+						commit_meta,
+						oracle_to_commit_index,
+						committed_multilins,
+						fri_params,
+						ntt,
+						committed,
+						codeword,
+					),
+				)
+			},
+		)
+		.unzip();
+
+	// Observe polynomial commitments
 	let mut writer = transcript.message();
-	writer.write(&commitment);
+	for commitment in &commitments {
+		writer.write(commitment);
+	}
+	emit_max_rss();
+	drop(commit_span);
 
+	limits.check()?;
+	phase_events.enter(Phase::Exp, transcript.bytes_written());
 	let exp_span = tracing::info_span!(
 		"[phase] Exponentiation",
 		phase = "exp",
@@ -275,6 +527,8 @@ where
 
 	// Grand product arguments
 	// Grand products for non-zero checking
+	limits.check()?;
+	phase_events.enter(Phase::Prodcheck, transcript.bytes_written());
 	let prodcheck_span = tracing::info_span!(
 		"[phase] Product Check",
 		phase = "prodcheck",
@@ -428,6 +682,8 @@ where
 	drop(prodcheck_span);
 
 	// Zerocheck
+	limits.check()?;
+	phase_events.enter(Phase::Zerocheck, transcript.bytes_written());
 	let zerocheck_span = tracing::info_span!(
 		"[phase] Zerocheck",
 		phase = "zerocheck",
@@ -451,6 +707,7 @@ where
 	let mut zerocheck_provers = Vec::with_capacity(table_constraints.len());
 
 	for constraint_set in table_constraints {
+		limits.check()?;
 		let n_vars = constraint_set.n_vars;
 		let (constraints, multilinears) =
 			sumcheck::prove::split_constraint_set(constraint_set, &witness)?;
@@ -506,6 +763,8 @@ where
 	emit_max_rss();
 	drop(zerocheck_span);
 
+	limits.check()?;
+	phase_events.enter(Phase::Evalcheck, transcript.bytes_written());
 	let evalcheck_span = tracing::info_span!(
 		"[phase] Evalcheck",
 		phase = "evalcheck",
@@ -516,7 +775,7 @@ where
 	// Prove evaluation claims
 	let GreedyEvalcheckProveOutput {
 		eval_claims,
-		memoized_data,
+		mut memoized_data,
 	} = greedy_evalcheck::prove::<_, _, FDomain<Tower>, _, _>(
 		&mut oracles,
 		&mut witness,
@@ -527,67 +786,93 @@ where
 		backend,
 	)?;
 
-	// Reduce committed evaluation claims to PIOP sumcheck claims
-	let system = ring_switch::EvalClaimSystem::new(
-		&oracles,
-		&commit_meta,
-		&oracle_to_commit_index,
-		&eval_claims,
-	)?;
-
 	emit_max_rss();
 	drop(evalcheck_span);
 
-	let ring_switch_span = tracing::info_span!(
-		"[phase] Ring Switch",
-		phase = "ring_switch",
-		perfetto_category = "phase.main"
-	)
-	.entered();
-
 	let hal = compute_data.hal;
 
 	let dev_alloc = &compute_data.dev_alloc;
-	let host_alloc = &compute_data.host_alloc;
-
-	let ring_switch::ReducedWitness {
-		transparents: transparent_multilins,
-		sumcheck_claims: piop_sumcheck_claims,
-	} = ring_switch::prove(
-		&system,
-		&committed_multilins,
-		&mut transcript,
-		memoized_data,
-		hal,
-		dev_alloc,
-		host_alloc,
-	)?;
-	emit_max_rss();
-	drop(ring_switch_span);
 
-	// Prove evaluation claims using PIOP compiler
-	let piop_compiler_span = tracing::info_span!(
-		"[phase] PIOP Compiler",
-		phase = "piop_compiler",
-		perfetto_category = "phase.main"
-	)
-	.entered();
+	let batch_eval_claims = split_eval_claims_by_batch(
+		eval_claims,
+		batches
+			.iter()
+			.map(|(_, oracle_to_commit_index, ..)| oracle_to_commit_index),
+	);
+	for (batch_index, (batch, eval_claims)) in iter::zip(batches, batch_eval_claims).enumerate() {
+		let (
+			commit_meta,
+			oracle_to_commit_index,
+			committed_multilins,
+			fri_params,
+			ntt,
+			committed,
+			codeword,
+		) = batch;
+		// The claims on the default batch are always proven, so that claims on oracles that are
+		// not committed are rejected by the ring switch.
+		if batch_index != 0 && eval_claims.is_empty() {
+			merkle_prover.recycle(committed);
+			continue;
+		}
 
-	piop::prove(
-		compute_data,
-		&fri_params,
-		&ntt,
-		&merkle_prover,
-		&commit_meta,
-		committed,
-		&codeword,
-		&committed_multilins,
-		transparent_multilins,
-		&piop_sumcheck_claims,
-		&mut transcript,
-	)?;
-	emit_max_rss();
-	drop(piop_compiler_span);
+		// Reduce committed evaluation claims to PIOP sumcheck claims
+		let system = ring_switch::EvalClaimSystem::new(
+			&oracles,
+			&commit_meta,
+			&oracle_to_commit_index,
+			&eval_claims,
+		)?;
+
+		limits.check()?;
+		phase_events.enter(Phase::RingSwitch, transcript.bytes_written());
+		let ring_switch_span = tracing::info_span!(
+			"[phase] Ring Switch",
+			phase = "ring_switch",
+			perfetto_category = "phase.main"
+		)
+		.entered();
+
+		let ring_switch::ReducedWitness {
+			transparents: transparent_multilins,
+			sumcheck_claims: piop_sumcheck_claims,
+		} = ring_switch::prove(
+			&system,
+			&committed_multilins,
+			&mut transcript,
+			&mut memoized_data,
+			hal,
+			dev_alloc,
+		)?;
+		emit_max_rss();
+		drop(ring_switch_span);
+
+		// Prove evaluation claims using PIOP compiler
+		limits.check()?;
+		phase_events.enter(Phase::PiopCompiler, transcript.bytes_written());
+		let piop_compiler_span = tracing::info_span!(
+			"[phase] PIOP Compiler",
+			phase = "piop_compiler",
+			perfetto_category = "phase.main"
+		)
+		.entered();
+
+		piop::prove(
+			compute_data,
+			&fri_params,
+			&ntt,
+			&merkle_prover,
+			&commit_meta,
+			committed,
+			&codeword,
+			&committed_multilins,
+			transparent_multilins,
+			&piop_sumcheck_claims,
+			&mut transcript,
+		)?;
+		emit_max_rss();
+		drop(piop_compiler_span);
+	}
 
 	let proof = Proof {
 		transcript: transcript.finalize(),
@@ -600,6 +885,7 @@ where
 		value = proof.get_proof_size() as u64,
 		unit = "bytes",
 	);
+	phase_events.finish(proof.get_proof_size());
 
 	Ok(proof)
 }
@@ -650,6 +936,10 @@ where
 	}
 }
 
+/// The resident set size cannot be queried without an operating system.
+#[cfg(target_arch = "wasm32")]
+fn emit_max_rss() {}
+
 fn populate_flush_po2_step_down_witnesses<'a, U, Tower>(
 	step_down_polys: Vec<(OracleId, StepDown)>,
 	witness: &mut MultilinearExtensionIndex<'a, PackedType<U, FExt<Tower>>>,
@@ -1087,25 +1377,15 @@ where
 
 	let mut memoized_data = MemoizedData::new();
 
-	let mut fast_new_evalcheck_claims = Vec::new();
-
-	for ConstraintSetEqIndPoint {
-		eq_ind_challenges,
-		constraint_set,
-	} in new_mlechecks
-	{
-		let evalcheck_claims = prove_mlecheck_with_switchover::<_, _, FFastExt<Tower>, _, _>(
-			&witness_index,
-			constraint_set,
-			eq_ind_challenges,
-			&mut memoized_data,
-			transcript,
-			immediate_switchover_heuristic,
-			domain_factory.clone(),
-			backend,
-		)?;
-		fast_new_evalcheck_claims.extend(evalcheck_claims);
-	}
+	let fast_new_evalcheck_claims = prove_mlechecks_with_switchover::<_, _, FFastExt<Tower>, _, _>(
+		&witness_index,
+		new_mlechecks,
+		&mut memoized_data,
+		transcript,
+		immediate_switchover_heuristic,
+		domain_factory,
+		backend,
+	)?;
 
 	Ok(chain!(
 		fast_new_evalcheck_claims
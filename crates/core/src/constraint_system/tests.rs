@@ -1,17 +1,43 @@
 // Copyright 2025 Irreducible Inc.
 
+use std::iter::{self, repeat_with};
+
+use binius_fast_compute::arith_circuit::ArithCircuitPoly;
 use binius_field::{
-	Field, PackedBinaryField2x128b, PackedBinaryField256x1b, PackedBinaryPolyval2x128b,
-	PackedField, TowerField, arch::OptimalUnderlier256b, tower::CanonicalTowerFamily,
+	BinaryField32b, Field, PackedBinaryField2x128b, PackedBinaryField256x1b,
+	PackedBinaryPolyval2x128b, PackedField, TowerField, arch::OptimalUnderlier256b,
+	tower::CanonicalTowerFamily,
+};
+use binius_hal::make_portable_backend;
+use binius_hash::{
+	groestl::Groestl256,
+	keccak::{Keccak256, Keccak256ByteCompression},
 };
-use binius_math::{B1, B128, MLEDirectAdapter, MLEEmbeddingAdapter, MultilinearExtension};
+use binius_math::{
+	ArithCircuit, ArithExpr, B1, B128, EvaluationOrder, IsomorphicEvaluationDomainFactory,
+	MLEDirectAdapter, MLEEmbeddingAdapter, MultilinearExtension, evaluate_univariate,
+};
+use binius_utils::{SerializationMode, SerializeBytes};
+use rand::{SeedableRng, rngs::StdRng};
 
 use crate::{
 	constraint_system::{
+		CommitRate, ConstraintSystem, PROTOCOL_VERSION, Proof, ProofHeader, TableSizeSpec,
 		channel::{Flush, FlushDirection, OracleOrConst},
+		cost::{EvmGasSchedule, estimate_verifier_cost, plan_log_inv_rate},
+		error::{Error, VerificationError},
 		prove::make_masked_flush_witnesses,
 	},
-	oracle::MultilinearOracleSet,
+	merkle_tree::BinaryMerkleTreeScheme,
+	oracle::{
+		Constraint, ConstraintPredicate, ConstraintSet, MultilinearOracleSet,
+		SymbolicMultilinearOracleSet,
+	},
+	piop::CommitMeta,
+	protocols::sumcheck::{
+		CompositeSumClaim,
+		prove::{SumcheckProver, eq_ind::EqIndSumcheckProverBuilder},
+	},
 	witness::MultilinearExtensionIndex,
 };
 
@@ -60,3 +86,372 @@ fn test_make_masked_flush_witnesses_handles_small_n_vars() {
 	)
 	.unwrap();
 }
+
+#[test]
+fn test_manifest() {
+	let mut oracles = SymbolicMultilinearOracleSet::<B128>::new();
+	let a = oracles.add_oracle(0, 0, "a").committed(5);
+	let b = oracles.add_oracle(0, 0, "b").committed(5);
+	let product = oracles
+		.add_oracle(0, 0, "product")
+		.composite_mle([a, b], ArithCircuit::from(ArithExpr::Var(0) * ArithExpr::Var(1)))
+		.unwrap();
+	let mut cs = ConstraintSystem {
+		oracles,
+		table_constraints: vec![ConstraintSet {
+			table_id: 0,
+			log_values_per_row: 0,
+			oracle_ids: vec![a, b],
+			constraints: vec![Constraint {
+				name: "a * b * b".to_string(),
+				composition: ArithCircuit::from(
+					ArithExpr::Var(0) * ArithExpr::Var(1) * ArithExpr::Var(1),
+				),
+				predicate: ConstraintPredicate::Zero,
+			}],
+		}],
+		non_zero_oracle_ids: vec![],
+		flushes: vec![Flush {
+			table_id: 0,
+			log_values_per_row: 0,
+			oracles: vec![
+				OracleOrConst::Oracle(product),
+				OracleOrConst::Const {
+					base: B128::ONE,
+					tower_level: 0,
+				},
+			],
+			channel_id: 0,
+			direction: FlushDirection::Push,
+			selectors: vec![a],
+			multiplicity: 1,
+		}],
+		exponents: vec![],
+		channel_count: 1,
+		table_size_specs: vec![TableSizeSpec::PowerOfTwo],
+		commit_batches: vec![vec![b]],
+		commit_rates: vec![CommitRate::Default, CommitRate::Fixed { log_inv_rate: 2 }],
+		commit_log_leaf_sizes: vec![],
+	};
+
+	assert_eq!(
+		cs.manifest(),
+		concat!(
+			"table 0 power_of_two\n",
+			"\toracle #0 \"a\" committed tower_level=5 log_values_per_row=0\n",
+			"\toracle #1 \"b\" committed tower_level=5 log_values_per_row=0\n",
+			"\toracle #2 \"product\" composite (#0, #1) degree=2 tower_level=5 log_values_per_row=0\n",
+			"\tconstraint_set log_values_per_row=0 oracles=(#0, #1)\n",
+			"\t\tzero \"a * b * b\" degree=3\n",
+			"\tflush push channel=0 log_values_per_row=0 values=(#2, ",
+			"const(0x00000000000000000000000000000001, tower_level=0)) selectors=(#0) multiplicity=1\n",
+			"channels 1\n",
+			"non_zero ()\n",
+			"commit_batch 0 (#1)\n",
+			"commit_rate 0 Default\n",
+			"commit_rate 1 Fixed { log_inv_rate: 2 }\n",
+		)
+	);
+
+	// Renaming a constraint changes the manifest and the digest.
+	let digest = cs.digest::<Groestl256>();
+	cs.table_constraints[0].constraints[0].name = "renamed".to_string();
+	assert!(cs.manifest().contains("\t\tzero \"renamed\" degree=3\n"));
+	assert_ne!(cs.digest::<Groestl256>(), digest);
+
+	assert_eq!(cs.max_degree(), 3);
+	assert!(cs.check_degree(3).is_ok());
+	assert!(matches!(
+		cs.check_degree(2),
+		Err(Error::ConstraintDegreeTooLarge { table_id: 0, name, degree: 3, max: 2 })
+			if name == "renamed"
+	));
+}
+
+#[test]
+fn test_estimate_verifier_cost() {
+	let mut oracles = SymbolicMultilinearOracleSet::<B128>::new();
+	let a = oracles.add_oracle(0, 0, "a").committed(0);
+	let b = oracles.add_oracle(0, 0, "b").committed(5);
+	let cs = ConstraintSystem {
+		oracles,
+		table_constraints: vec![ConstraintSet {
+			table_id: 0,
+			log_values_per_row: 0,
+			oracle_ids: vec![a, b],
+			constraints: vec![Constraint {
+				name: "a * b * b".to_string(),
+				composition: ArithCircuit::from(
+					ArithExpr::Var(0) * ArithExpr::Var(1) * ArithExpr::Var(1),
+				),
+				predicate: ConstraintPredicate::Zero,
+			}],
+		}],
+		non_zero_oracle_ids: vec![],
+		flushes: vec![Flush {
+			table_id: 0,
+			log_values_per_row: 0,
+			oracles: vec![OracleOrConst::Oracle(b)],
+			channel_id: 0,
+			direction: FlushDirection::Push,
+			selectors: vec![a],
+			multiplicity: 1,
+		}],
+		exponents: vec![],
+		channel_count: 1,
+		table_size_specs: vec![TableSizeSpec::PowerOfTwo],
+		commit_batches: vec![],
+		commit_rates: vec![],
+		commit_log_leaf_sizes: vec![],
+	};
+
+	let estimate = |table_size: usize, security_bits: usize| {
+		estimate_verifier_cost::<CanonicalTowerFamily, Keccak256, Keccak256ByteCompression>(
+			&cs,
+			&[table_size],
+			1,
+			security_bits,
+		)
+		.unwrap()
+	};
+
+	// Larger tables cost more in every measure.
+	let small = estimate(1 << 8, 100);
+	let large = estimate(1 << 14, 100);
+	assert!(large.proof_bytes > small.proof_bytes);
+	assert!(large.hash_invocations > small.hash_invocations);
+	assert!(large.hashed_bytes > small.hashed_bytes);
+	assert!(large.field_mults > small.field_mults);
+
+	// A higher security level requires more FRI queries.
+	let weak = estimate(1 << 14, 64);
+	assert!(large.proof_bytes > weak.proof_bytes);
+	assert!(large.hash_invocations > weak.hash_invocations);
+
+	let schedule = EvmGasSchedule::default();
+	assert_eq!(
+		large.evm_gas(&schedule),
+		21_000
+			+ 16 * large.proof_bytes as u64
+			+ 30 * large.hash_invocations as u64
+			+ 6 * large.hashed_bytes.div_ceil(32) as u64
+			+ 500 * large.field_mults as u64
+	);
+
+	// A lower code rate shortens the FRI opening proof of the batch.
+	let mut low_rate_cs = cs.clone();
+	low_rate_cs.commit_rates = vec![CommitRate::Fixed { log_inv_rate: 3 }];
+	let low_rate = estimate_verifier_cost::<
+		CanonicalTowerFamily,
+		Keccak256,
+		Keccak256ByteCompression,
+	>(&low_rate_cs, &[1 << 14], 1, 100)
+	.unwrap();
+	assert!(low_rate.proof_bytes < large.proof_bytes);
+
+	// Wider Merkle leaves make the FRI oracle trees shallower, so the queries hash fewer nodes.
+	let estimate_with_leaf_size = |log_leaf_size: usize| {
+		let mut wide_leaf_cs = cs.clone();
+		wide_leaf_cs.commit_log_leaf_sizes = vec![Some(log_leaf_size)];
+		estimate_verifier_cost::<CanonicalTowerFamily, Keccak256, Keccak256ByteCompression>(
+			&wide_leaf_cs,
+			&[1 << 14],
+			1,
+			100,
+		)
+	};
+	let wide_leaf = estimate_with_leaf_size(6).unwrap();
+	assert!(wide_leaf.hash_invocations < large.hash_invocations);
+	// A leaf cannot be narrower than a folding coset.
+	assert!(estimate_with_leaf_size(2).is_err());
+
+	assert!(
+		estimate_verifier_cost::<CanonicalTowerFamily, Keccak256, Keccak256ByteCompression>(
+			&cs,
+			&[1 << 8, 1 << 8],
+			1,
+			100,
+		)
+		.is_err()
+	);
+}
+
+#[test]
+fn test_plan_log_inv_rate() {
+	let merkle_scheme = BinaryMerkleTreeScheme::<B128, Keccak256, _>::new(Keccak256ByteCompression);
+	let plan = |n_vars: usize, proof_weight: usize| {
+		plan_log_inv_rate::<B128, BinaryField32b, _>(
+			&CommitMeta::with_vars([n_vars]),
+			&merkle_scheme,
+			100,
+			4,
+			proof_weight,
+		)
+		.unwrap()
+	};
+
+	// The codeword dominates the cost of large batches, which are committed at the highest rate,
+	// while smaller batches trade a longer codeword for a shorter opening proof.
+	assert_eq!(plan(20, 64), 1);
+	assert_eq!(plan(14, 64), 2);
+	assert!(plan(14, 1 << 12) > plan(14, 64));
+	assert_eq!(plan(14, 0), 1);
+}
+
+#[test]
+fn test_proof_header() {
+	let header = ProofHeader::new(1, 100);
+	assert_eq!(header.version, PROTOCOL_VERSION);
+	assert!(header.check(&ProofHeader::new(1, 100)).is_ok());
+	assert!(matches!(
+		header.check(&ProofHeader::new(2, 100)),
+		Err(VerificationError::ProofParametersMismatch {
+			expected_log_inv_rate: 1,
+			got_log_inv_rate: 2,
+			..
+		})
+	));
+
+	let other_version = ProofHeader {
+		version: "binius_core/0.0.0".to_string(),
+		..header
+	};
+	assert!(matches!(
+		header.check(&other_version),
+		Err(VerificationError::ProtocolVersionMismatch { got, .. }) if got == "binius_core/0.0.0"
+	));
+
+	let mut transcript = Vec::new();
+	header
+		.serialize(&mut transcript, SerializationMode::CanonicalTower)
+		.unwrap();
+	assert_eq!(ProofHeader::read(&Proof { transcript }).unwrap(), header);
+	assert!(
+		ProofHeader::read(&Proof {
+			transcript: vec![0xff; 8]
+		})
+		.is_err()
+	);
+}
+
+#[test]
+fn test_evaluate_composition() {
+	let mut oracles = SymbolicMultilinearOracleSet::<B128>::new();
+	let a = oracles.add_oracle(0, 0, "a").committed(7);
+	let b = oracles.add_oracle(0, 0, "b").committed(7);
+	let c = oracles.add_oracle(0, 0, "c").committed(7);
+	let cs = ConstraintSystem {
+		oracles,
+		table_constraints: vec![ConstraintSet {
+			table_id: 0,
+			log_values_per_row: 0,
+			oracle_ids: vec![a, b, c],
+			constraints: vec![
+				Constraint {
+					name: "a * b - c".to_string(),
+					composition: ArithCircuit::from(
+						ArithExpr::Var(0) * ArithExpr::Var(1) - ArithExpr::Var(2),
+					),
+					predicate: ConstraintPredicate::Zero,
+				},
+				Constraint {
+					name: "a * c - a^2 * b".to_string(),
+					composition: ArithCircuit::from(
+						ArithExpr::Var(0) * ArithExpr::Var(2)
+							- ArithExpr::Var(0).pow(2) * ArithExpr::Var(1),
+					),
+					predicate: ConstraintPredicate::Zero,
+				},
+			],
+		}],
+		non_zero_oracle_ids: vec![],
+		flushes: vec![],
+		exponents: vec![],
+		channel_count: 0,
+		table_size_specs: vec![TableSizeSpec::PowerOfTwo],
+		commit_batches: vec![],
+		commit_rates: vec![],
+		commit_log_leaf_sizes: vec![],
+	};
+
+	let evals = [B128::new(3), B128::new(5), B128::new(7)];
+	let batch_coeff = B128::new(11);
+	let [a, b, c] = evals;
+	assert_eq!(
+		cs.evaluate_composition(0, batch_coeff, &evals).unwrap(),
+		batch_coeff * (a * b - c) + batch_coeff.square() * (a * c - a.square() * b)
+	);
+
+	// Run the eq-ind sumcheck that the zerocheck of the constraint set reduces to on a witness
+	// satisfying the constraints, with the compositions that the prover builds. The final claim of
+	// the prover is the batched composition at the multilinear evaluations, times the evaluation
+	// of the equality indicator.
+	let n_vars = 4;
+	let mut rng = StdRng::seed_from_u64(0);
+	let a_column = repeat_with(|| <B128 as Field>::random(&mut rng))
+		.take(1 << n_vars)
+		.collect::<Vec<_>>();
+	let b_column = repeat_with(|| <B128 as Field>::random(&mut rng))
+		.take(1 << n_vars)
+		.collect::<Vec<_>>();
+	let c_column = iter::zip(&a_column, &b_column)
+		.map(|(&a, &b)| a * b)
+		.collect::<Vec<_>>();
+	let eq_ind_challenges = repeat_with(|| <B128 as Field>::random(&mut rng))
+		.take(n_vars)
+		.collect::<Vec<_>>();
+	let batch_coeff = <B128 as Field>::random(&mut rng);
+
+	let backend = make_portable_backend();
+	let composite_claims = cs.table_constraints[0]
+		.constraints
+		.iter()
+		.map(|constraint| CompositeSumClaim {
+			composition: ArithCircuitPoly::with_n_vars(3, constraint.composition.clone()).unwrap(),
+			sum: B128::ZERO,
+		});
+	let mut prover = EqIndSumcheckProverBuilder::without_switchover(
+		n_vars,
+		vec![a_column, b_column, c_column],
+		&backend,
+	)
+	.build::<B128, _>(
+		EvaluationOrder::HighToLow,
+		&eq_ind_challenges,
+		composite_claims,
+		IsomorphicEvaluationDomainFactory::<B128>::default(),
+	)
+	.unwrap();
+
+	let mut claim = B128::ZERO;
+	for _ in 0..n_vars {
+		let coeffs = prover.execute(batch_coeff).unwrap();
+		let challenge = <B128 as Field>::random(&mut rng);
+		claim = evaluate_univariate(&coeffs.0, challenge);
+		prover.fold(challenge).unwrap();
+	}
+	let mut multilinear_evals = Box::new(prover).finish().unwrap();
+	let eq_ind_eval = multilinear_evals.pop().unwrap();
+
+	// The batch prover further scales the round polynomials of each claim by its batching
+	// coefficient.
+	assert_eq!(
+		eq_ind_eval
+			* cs.evaluate_composition(0, batch_coeff, &multilinear_evals)
+				.unwrap(),
+		batch_coeff * claim
+	);
+
+	assert!(matches!(
+		cs.evaluate_composition(1, batch_coeff, &evals),
+		Err(Error::ConstraintSetOutOfRange { index: 1, count: 1 })
+	));
+	assert!(matches!(
+		cs.evaluate_composition(0, batch_coeff, &evals[..2]),
+		Err(Error::ConstraintSetEvalsLenMismatch {
+			index: 0,
+			expected: 3,
+			got: 2
+		})
+	));
+}
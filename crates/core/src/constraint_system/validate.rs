@@ -41,6 +41,9 @@ where
 		channel_count,
 		table_size_specs: _,
 		exponents: _,
+		commit_batches: _,
+		commit_rates: _,
+		commit_log_leaf_sizes: _,
 	} = constraint_system;
 
 	let oracles = unsized_oracles.instantiate(table_sizes)?;
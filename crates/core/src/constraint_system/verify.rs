@@ -1,6 +1,6 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use std::collections::hash_map::Entry;
+use std::{collections::hash_map::Entry, time::Duration};
 
 use binius_field::{
 	BinaryField, PackedField, TowerField,
@@ -8,15 +8,17 @@ use binius_field::{
 };
 use binius_hash::PseudoCompressionFunction;
 use binius_math::{ArithExpr, CompositionPoly, EvaluationOrder};
-use binius_utils::{bail, checked_arithmetics::log2_ceil_usize};
+use binius_utils::{bail, checked_arithmetics::log2_ceil_usize, sparse_index::SparseIndex};
 use digest::{Digest, Output, OutputSizeUser, core_api::BlockSizeUser};
 use itertools::{Itertools, chain, izip};
 use tracing::instrument;
 
 use super::{
-	ConstraintSystem, Proof,
+	ConstraintSystem, MAX_CONSTRAINT_DEGREE, Proof, ProofHeader,
 	channel::{Boundary, OracleOrConst},
+	cost::{VerifierCost, estimate_verifier_cost, make_commit_params},
 	error::{Error, VerificationError},
+	events::{self, Phase, PhaseEvents, PhaseReport, Role},
 	exp::{self, reorder_exponents},
 };
 use crate::{
@@ -33,16 +35,14 @@ use crate::{
 	},
 	piop,
 	protocols::{
-		evalcheck::{EvalPoint, EvalcheckMultilinearClaim},
+		evalcheck::{
+			ConstraintSetsEqIndPoints, EvalPoint, EvalcheckMultilinearClaim,
+			subclaims::verify_mlechecks,
+		},
 		gkr_exp,
 		gkr_gpa::{self},
 		greedy_evalcheck,
-		sumcheck::{
-			self, MLEcheckClaimsWithMeta, ZerocheckClaim, constraint_set_mlecheck_claims,
-			constraint_set_zerocheck_claim,
-			eq_ind::{self, ClaimsSortingOrder, reduce_to_regular_sumchecks},
-			front_loaded,
-		},
+		sumcheck::{self, ZerocheckClaim, constraint_set_zerocheck_claim},
 	},
 	ring_switch,
 	transcript::VerifierTranscript,
@@ -67,6 +67,120 @@ where
 	Hash: Digest + BlockSizeUser + OutputSizeUser,
 	Compress: PseudoCompressionFunction<Output<Hash>, 2> + Default + Sync,
 	Challenger_: Challenger + Default,
+{
+	verify_phases::<Tower, Hash, Compress, Challenger_>(
+		constraint_system,
+		log_inv_rate,
+		security_bits,
+		constraint_system_digest,
+		boundaries,
+		proof,
+		false,
+	)?;
+	Ok(())
+}
+
+/// A summary of a successful verification, returned by [`verify_with_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationReport {
+	/// The header of the proof, which echoes the protocol version and the parameters.
+	pub header: ProofHeader,
+	/// The number of rows of every table.
+	pub table_sizes: Vec<usize>,
+	/// The number of bytes of the proof.
+	pub proof_bytes: usize,
+	/// The wall-clock time of the verification, or `None` on targets without a clock.
+	pub duration: Option<Duration>,
+	/// The protocol phases in the order the verifier ran them.
+	///
+	/// The ring-switching and PIOP compiler phases are repeated for every commit batch. The proof
+	/// bytes of the header and the table sizes are not attributed to any phase.
+	pub phases: Vec<PhaseReport>,
+	/// The hashes and field operations of the verification, as estimated by the cost model of
+	/// [`estimate_verifier_cost`] for the table sizes of the proof.
+	///
+	/// This is not counted while verifying, so it only approximates the work of the verifier.
+	pub estimated_cost: VerifierCost,
+}
+
+/// Verifies a proof against a constraint system like [`verify`], and returns a report on the
+/// verification.
+///
+/// The report is meant for the observability of verification services. Computing the cost of the
+/// verification instantiates the oracles once more, which [`verify`] avoids.
+#[instrument("constraint_system::verify_with_report", skip_all, level = "debug")]
+#[allow(clippy::too_many_arguments)]
+pub fn verify_with_report<U, Tower, Hash, Compress, Challenger_>(
+	constraint_system: &ConstraintSystem<FExt<Tower>>,
+	log_inv_rate: usize,
+	security_bits: usize,
+	constraint_system_digest: &Output<Hash>,
+	boundaries: &[Boundary<FExt<Tower>>],
+	proof: Proof,
+) -> Result<VerificationReport, Error>
+where
+	U: TowerUnderlier<Tower>,
+	Tower: TowerFamily,
+	Tower::B128: binius_math::TowerTop + binius_math::PackedTop + PackedTop<Tower>,
+	Hash: Digest + BlockSizeUser + OutputSizeUser,
+	Compress: PseudoCompressionFunction<Output<Hash>, 2> + Default + Sync,
+	Challenger_: Challenger + Default,
+{
+	let proof_bytes = proof.transcript.len();
+	let VerifiedProof {
+		header,
+		table_sizes,
+		phases,
+		duration,
+	} = verify_phases::<Tower, Hash, Compress, Challenger_>(
+		constraint_system,
+		log_inv_rate,
+		security_bits,
+		constraint_system_digest,
+		boundaries,
+		proof,
+		true,
+	)?;
+	let estimated_cost = estimate_verifier_cost::<Tower, Hash, Compress>(
+		constraint_system,
+		&table_sizes,
+		log_inv_rate,
+		security_bits,
+	)?;
+	Ok(VerificationReport {
+		header,
+		table_sizes,
+		proof_bytes,
+		duration,
+		phases,
+		estimated_cost,
+	})
+}
+
+/// What the verifier learned from a valid proof.
+struct VerifiedProof {
+	header: ProofHeader,
+	table_sizes: Vec<usize>,
+	phases: Vec<PhaseReport>,
+	duration: Option<Duration>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn verify_phases<Tower, Hash, Compress, Challenger_>(
+	constraint_system: &ConstraintSystem<FExt<Tower>>,
+	log_inv_rate: usize,
+	security_bits: usize,
+	constraint_system_digest: &Output<Hash>,
+	boundaries: &[Boundary<FExt<Tower>>],
+	proof: Proof,
+	timed: bool,
+) -> Result<VerifiedProof, Error>
+where
+	Tower: TowerFamily,
+	Tower::B128: binius_math::TowerTop + binius_math::PackedTop + PackedTop<Tower>,
+	Hash: Digest + BlockSizeUser + OutputSizeUser,
+	Compress: PseudoCompressionFunction<Output<Hash>, 2> + Default + Sync,
+	Challenger_: Challenger + Default,
 {
 	let ConstraintSystem {
 		oracles,
@@ -76,11 +190,18 @@ where
 		channel_count,
 		mut exponents,
 		table_size_specs,
+		commit_batches,
+		commit_rates: _,
+		commit_log_leaf_sizes: _,
 	} = constraint_system.clone();
 
+	let mut phase_events = PhaseEvents::new(Role::Verifier, timed);
+
 	let Proof { transcript } = proof;
+	let proof_size = transcript.len();
 
 	let mut transcript = VerifierTranscript::<Challenger_>::new(transcript);
+	let header = read_proof_header(&mut transcript, log_inv_rate, security_bits)?;
 	transcript
 		.observe()
 		.write_slice(constraint_system_digest.as_ref());
@@ -89,8 +210,10 @@ where
 	let table_count = table_size_specs.len();
 	let mut reader = transcript.message();
 	let table_sizes: Vec<usize> = reader.read_vec(table_count)?;
+	events::emit_table_sizes(Role::Verifier, &table_sizes);
 
 	constraint_system.check_table_sizes(&table_sizes)?;
+	constraint_system.check_degree(MAX_CONSTRAINT_DEGREE)?;
 	let mut oracles = oracles.instantiate(&table_sizes)?;
 
 	// Prepare the constraint system for proving:
@@ -122,18 +245,23 @@ where
 	reorder_exponents(&mut exponents, &oracles);
 
 	let merkle_scheme = BinaryMerkleTreeScheme::<_, Hash, _>::new(Compress::default());
-	let (commit_meta, oracle_to_commit_index) = piop::make_oracle_commit_meta(&oracles)?;
-	let fri_params = piop::make_commit_params_with_optimal_arity::<_, FEncode<Tower>, _>(
-		&commit_meta,
+	let commit_metas = piop::make_oracle_commit_metas(&oracles, &commit_batches)?;
+	let fri_params = make_commit_params::<_, FEncode<Tower>, _>(
+		constraint_system,
+		&commit_metas,
 		&merkle_scheme,
 		security_bits,
 		log_inv_rate,
 	)?;
 
 	// Read polynomial commitment polynomials
-	let mut reader = transcript.message();
-	let commitment = reader.read::<Output<Hash>>()?;
+	phase_events.enter(Phase::Commit, proof_size - transcript.bytes_remaining());
+	let commitments = commit_metas
+		.iter()
+		.map(|_| transcript.message().read::<Output<Hash>>())
+		.collect::<Result<Vec<_>, _>>()?;
 
+	phase_events.enter(Phase::Exp, proof_size - transcript.bytes_remaining());
 	let exp_challenge = transcript.sample_vec(exp::max_n_vars(&exponents, &oracles));
 
 	let mut reader = transcript.message();
@@ -149,6 +277,7 @@ where
 	let exp_eval_claims = exp::make_eval_claims(&exponents, base_exp_output)?;
 
 	// Grand product arguments
+	phase_events.enter(Phase::Prodcheck, proof_size - transcript.bytes_remaining());
 	// Grand products for non-zero checks
 	let mut reader = transcript.message();
 	let non_zero_products = reader.read_scalar_slice(non_zero_oracle_ids.len())?;
@@ -215,6 +344,7 @@ where
 	)?;
 
 	// Zerocheck
+	phase_events.enter(Phase::Zerocheck, proof_size - transcript.bytes_remaining());
 	let (zerocheck_claims, zerocheck_oracle_metas) = table_constraints
 		.iter()
 		.cloned()
@@ -233,39 +363,185 @@ where
 		sumcheck::make_zerocheck_eval_claims(zerocheck_oracle_metas, zerocheck_output)?;
 
 	// Evalcheck
+	phase_events.enter(Phase::Evalcheck, proof_size - transcript.bytes_remaining());
 	let eval_claims = greedy_evalcheck::verify(
 		&mut oracles,
 		chain!(flush_eval_claims, prodcheck_eval_claims, zerocheck_eval_claims, exp_eval_claims,),
 		&mut transcript,
 	)?;
 
-	// Reduce committed evaluation claims to PIOP sumcheck claims
-	let system = ring_switch::EvalClaimSystem::new(
-		&oracles,
-		&commit_meta,
-		&oracle_to_commit_index,
-		&eval_claims,
-	)?;
+	let batch_eval_claims = split_eval_claims_by_batch(
+		eval_claims,
+		commit_metas
+			.iter()
+			.map(|(_, oracle_to_commit_index)| oracle_to_commit_index),
+	);
+	for (
+		batch_index,
+		((commit_meta, oracle_to_commit_index), fri_params, commitment, eval_claims),
+	) in izip!(&commit_metas, &fri_params, &commitments, batch_eval_claims).enumerate()
+	{
+		// The claims on the default batch are always verified, so that claims on oracles that are
+		// not committed are rejected by the ring switch.
+		if batch_index != 0 && eval_claims.is_empty() {
+			continue;
+		}
 
-	let ring_switch::ReducedClaim {
-		transparents,
-		sumcheck_claims: piop_sumcheck_claims,
-	} = ring_switch::verify(&system, &mut transcript)?;
+		// Reduce committed evaluation claims to PIOP sumcheck claims
+		phase_events.enter(Phase::RingSwitch, proof_size - transcript.bytes_remaining());
+		let system = ring_switch::EvalClaimSystem::new(
+			&oracles,
+			commit_meta,
+			oracle_to_commit_index,
+			&eval_claims,
+		)?;
 
-	// Prove evaluation claims using PIOP compiler
-	piop::verify(
-		&commit_meta,
+		let ring_switch::ReducedClaim {
+			transparents,
+			sumcheck_claims: piop_sumcheck_claims,
+		} = ring_switch::verify(&system, &mut transcript)?;
+
+		// Prove evaluation claims using PIOP compiler
+		phase_events.enter(Phase::PiopCompiler, proof_size - transcript.bytes_remaining());
+		piop::verify(
+			commit_meta,
+			&merkle_scheme,
+			fri_params,
+			commitment,
+			&transparents,
+			&piop_sumcheck_claims,
+			&mut transcript,
+		)?;
+	}
+
+	let (phases, duration) = phase_events.finish(proof_size - transcript.bytes_remaining());
+	transcript.finalize()?;
+
+	Ok(VerifiedProof {
+		header,
+		table_sizes,
+		phases,
+		duration,
+	})
+}
+
+/// The table sizes and polynomial commitments that a proof binds to, as read by
+/// [`verify_commitments`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofCommitments<Digest> {
+	/// The number of rows of every table.
+	pub table_sizes: Vec<usize>,
+	/// The commitment to every commit batch, in the order of the batches.
+	pub commitments: Vec<Digest>,
+}
+
+/// Checks that a proof is well-formed and bound to the statement, without verifying it.
+///
+/// This checks that the [`ProofHeader`] matches the protocol version and the parameters, that
+/// `constraint_system_digest` is the digest of the constraint system, that the boundaries only
+/// refer to channels of the constraint system, that the table sizes in the proof satisfy the
+/// table size specifications, and that the proof holds a commitment for every commit batch, with
+/// commitment parameters that can be derived for the given rate and security level. It does not
+/// run the GKR, sumcheck or FRI verification, so a proof that passes may still be invalid. This
+/// is meant for cheaply triaging proofs before calling [`verify`].
+#[instrument("constraint_system::verify_commitments", skip_all, level = "debug")]
+pub fn verify_commitments<U, Tower, Hash, Compress, Challenger_>(
+	constraint_system: &ConstraintSystem<FExt<Tower>>,
+	log_inv_rate: usize,
+	security_bits: usize,
+	constraint_system_digest: &Output<Hash>,
+	boundaries: &[Boundary<FExt<Tower>>],
+	proof: &Proof,
+) -> Result<ProofCommitments<Output<Hash>>, Error>
+where
+	U: TowerUnderlier<Tower>,
+	Tower: TowerFamily,
+	Tower::B128: binius_math::TowerTop + binius_math::PackedTop + PackedTop<Tower>,
+	Hash: Digest + BlockSizeUser + OutputSizeUser,
+	Compress: PseudoCompressionFunction<Output<Hash>, 2> + Default + Sync,
+	Challenger_: Challenger + Default,
+{
+	if constraint_system.digest::<Hash>() != *constraint_system_digest {
+		bail!(VerificationError::ConstraintSystemDigestMismatch);
+	}
+
+	let max_channel_id = constraint_system.channel_count.saturating_sub(1);
+	if let Some(boundary) = boundaries
+		.iter()
+		.find(|boundary| boundary.channel_id >= constraint_system.channel_count)
+	{
+		bail!(Error::ChannelIdOutOfRange {
+			max: max_channel_id,
+			got: boundary.channel_id,
+		});
+	}
+
+	let mut transcript = VerifierTranscript::<Challenger_>::new(proof.transcript.clone());
+	read_proof_header(&mut transcript, log_inv_rate, security_bits)?;
+	transcript
+		.observe()
+		.write_slice(constraint_system_digest.as_ref());
+	transcript.observe().write_slice(boundaries);
+
+	let table_count = constraint_system.table_size_specs.len();
+	let table_sizes: Vec<usize> = transcript.message().read_vec(table_count)?;
+	constraint_system.check_table_sizes(&table_sizes)?;
+	constraint_system.check_degree(MAX_CONSTRAINT_DEGREE)?;
+	let oracles = constraint_system.oracles.instantiate(&table_sizes)?;
+
+	let merkle_scheme = BinaryMerkleTreeScheme::<FExt<Tower>, Hash, _>::new(Compress::default());
+	let commit_metas = piop::make_oracle_commit_metas(&oracles, &constraint_system.commit_batches)?;
+	make_commit_params::<_, FEncode<Tower>, _>(
+		constraint_system,
+		&commit_metas,
 		&merkle_scheme,
-		&fri_params,
-		&commitment,
-		&transparents,
-		&piop_sumcheck_claims,
-		&mut transcript,
+		security_bits,
+		log_inv_rate,
 	)?;
 
-	transcript.finalize()?;
+	let commitments = commit_metas
+		.iter()
+		.map(|_| transcript.message().read::<Output<Hash>>())
+		.collect::<Result<Vec<_>, _>>()?;
 
-	Ok(())
+	Ok(ProofCommitments {
+		table_sizes,
+		commitments,
+	})
+}
+
+/// Reads the [`ProofHeader`] at the start of a proof transcript, checks it against the protocol
+/// version and the parameters of the verifier, and returns it.
+pub(super) fn read_proof_header<Challenger_: Challenger>(
+	transcript: &mut VerifierTranscript<Challenger_>,
+	log_inv_rate: usize,
+	security_bits: usize,
+) -> Result<ProofHeader, Error> {
+	let header = transcript.message().read::<ProofHeader>()?;
+	ProofHeader::new(log_inv_rate, security_bits).check(&header)?;
+	Ok(header)
+}
+
+/// Splits the evaluation claims on committed oracles by the commit batch of their oracles, given
+/// the oracle to commit index of every batch.
+///
+/// The claims on oracles that are not in any batch are assigned to the default batch, which is the
+/// first one, where the ring switch rejects them.
+pub fn split_eval_claims_by_batch<'a, F: TowerField>(
+	eval_claims: Vec<EvalcheckMultilinearClaim<F>>,
+	oracle_to_commit_indices: impl IntoIterator<Item = &'a SparseIndex<usize>>,
+) -> Vec<Vec<EvalcheckMultilinearClaim<F>>> {
+	let oracle_to_commit_indices = oracle_to_commit_indices.into_iter().collect::<Vec<_>>();
+	let mut batch_eval_claims = vec![Vec::new(); oracle_to_commit_indices.len()];
+	for eval_claim in eval_claims {
+		let batch = oracle_to_commit_indices
+			.iter()
+			.skip(1)
+			.position(|index| index.get(eval_claim.id.index()).is_some())
+			.map_or(0, |position| position + 1);
+		batch_eval_claims[batch].push(eval_claim);
+	}
+	batch_eval_claims
 }
 
 pub fn max_n_vars_and_skip_rounds<F, Composition>(
@@ -619,8 +895,6 @@ where
 		}
 	}
 
-	let new_mlechecks_constraints = new_mlechecks_constraints;
-
 	let mut eq_ind_challenges = Vec::with_capacity(new_mlechecks_constraints.len());
 	let mut constraint_sets = Vec::with_capacity(new_mlechecks_constraints.len());
 
@@ -629,38 +903,13 @@ where
 		constraint_sets.push(builder.build_one(oracles)?)
 	}
 
-	let MLEcheckClaimsWithMeta {
-		claims: mlecheck_claims,
-		metas,
-	} = constraint_set_mlecheck_claims(constraint_sets)?;
-
-	let mut new_evalcheck_claims = Vec::new();
-
-	for (eq_ind_challenges, mlecheck_claim, meta) in
-		izip!(&eq_ind_challenges, mlecheck_claims, metas)
-	{
-		let mlecheck_claim = vec![mlecheck_claim];
-
-		let batch_sumcheck_verifier = front_loaded::BatchVerifier::new(
-			&reduce_to_regular_sumchecks(&mlecheck_claim)?,
-			transcript,
-		)?;
-		let mut sumcheck_output = batch_sumcheck_verifier.run(transcript)?;
-
-		// Reverse challenges since foldling high-to-low
-		sumcheck_output.challenges.reverse();
-
-		let eq_ind_output = eq_ind::verify_sumcheck_outputs(
-			ClaimsSortingOrder::AscendingVars,
-			&mlecheck_claim,
+	let new_evalcheck_claims = verify_mlechecks(
+		ConstraintSetsEqIndPoints {
 			eq_ind_challenges,
-			sumcheck_output,
-		)?;
-
-		let evalcheck_claims =
-			sumcheck::make_eval_claims(EvaluationOrder::HighToLow, vec![meta], eq_ind_output)?;
-		new_evalcheck_claims.extend(evalcheck_claims)
-	}
+			constraint_sets,
+		},
+		transcript,
+	)?;
 
 	Ok(chain!(new_evalcheck_claims.into_iter(), linear_claims.into_iter()).collect::<Vec<_>>())
 }
@@ -0,0 +1,163 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Framed absorption of data into a [`Challenger`].
+//!
+//! Values observed through [`Challenger::observer`] are concatenated without any separators, so
+//! the boundaries between them are implied by the protocol. The [`Absorb`] methods instead frame
+//! every value with a label and its length, which makes the absorbed byte string unambiguous and
+//! easy to reproduce in external implementations of the verifier. A framed value is absorbed as
+//!
+//! ```text
+//! u64_le(label.len()) || label || u64_le(data.len()) || data
+//! ```
+//!
+//! Because the length precedes the data, large values can be streamed into the challenger in
+//! pieces without an intermediate buffer. The number of bytes absorbed must match the declared
+//! length.
+
+use bytes::{BufMut, buf::UninitSlice};
+
+use super::Challenger;
+
+/// Extension of [`Challenger`] with framed absorption of labeled values.
+pub trait Absorb: Challenger {
+	/// Absorbs `data` framed with `label` and its length.
+	fn absorb_bytes(&mut self, label: &[u8], data: &[u8]) {
+		self.absorb(label, data.len()).put_slice(data);
+	}
+
+	/// Starts absorbing a value of `len` bytes framed with `label`.
+	///
+	/// The value is written incrementally into the returned [`Absorber`].
+	fn absorb(&mut self, label: &[u8], len: usize) -> Absorber<impl BufMut + '_> {
+		let observer = self.observer();
+		observer.put_u64_le(label.len() as u64);
+		observer.put_slice(label);
+		observer.put_u64_le(len as u64);
+		Absorber {
+			observer,
+			remaining: len,
+		}
+	}
+}
+
+impl<C: Challenger + ?Sized> Absorb for C {}
+
+/// A writer of a framed value into a [`Challenger`], returned by [`Absorb::absorb`].
+///
+/// Writing more bytes than the declared length panics, as does dropping the absorber before the
+/// declared number of bytes was written.
+#[derive(Debug)]
+pub struct Absorber<B: BufMut> {
+	observer: B,
+	remaining: usize,
+}
+
+impl<B: BufMut> Absorber<B> {
+	/// Returns the number of bytes that remain to be absorbed.
+	pub const fn remaining(&self) -> usize {
+		self.remaining
+	}
+}
+
+unsafe impl<B: BufMut> BufMut for Absorber<B> {
+	fn remaining_mut(&self) -> usize {
+		self.remaining
+	}
+
+	unsafe fn advance_mut(&mut self, cnt: usize) {
+		assert!(cnt <= self.remaining, "absorbed more bytes than the declared length");
+		self.remaining -= cnt;
+		unsafe { self.observer.advance_mut(cnt) }
+	}
+
+	fn chunk_mut(&mut self) -> &mut UninitSlice {
+		let remaining = self.remaining;
+		let chunk = self.observer.chunk_mut();
+		let len = chunk.len().min(remaining);
+		&mut chunk[..len]
+	}
+}
+
+impl<B: BufMut> Drop for Absorber<B> {
+	fn drop(&mut self) {
+		if !std::thread::panicking() {
+			assert_eq!(self.remaining, 0, "absorbed fewer bytes than the declared length");
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_hash::groestl::Groestl256;
+	use bytes::Buf;
+
+	use super::*;
+	use crate::fiat_shamir::HasherChallenger;
+
+	fn sample_bytes(challenger: &mut impl Challenger) -> [u8; 64] {
+		let mut out = [0; 64];
+		challenger.sampler().copy_to_slice(&mut out);
+		out
+	}
+
+	#[test]
+	fn test_framing_matches_specification() {
+		let data = (0..1000).map(|i| i as u8).collect::<Vec<_>>();
+
+		let mut framed = HasherChallenger::<Groestl256>::default();
+		framed.absorb_bytes(b"codeword", &data);
+		framed.absorb_bytes(b"", &[]);
+
+		let mut manual = HasherChallenger::<Groestl256>::default();
+		let observer = manual.observer();
+		observer.put_slice(&8u64.to_le_bytes());
+		observer.put_slice(b"codeword");
+		observer.put_slice(&1000u64.to_le_bytes());
+		observer.put_slice(&data);
+		observer.put_slice(&[0; 16]);
+
+		assert_eq!(sample_bytes(&mut framed), sample_bytes(&mut manual));
+	}
+
+	#[test]
+	fn test_incremental_absorb_matches_one_shot() {
+		let data = (0..1000).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+
+		let mut one_shot = HasherChallenger::<Groestl256>::default();
+		one_shot.absorb_bytes(b"digests", &data);
+
+		let mut incremental = HasherChallenger::<Groestl256>::default();
+		let mut absorber = incremental.absorb(b"digests", data.len());
+		for chunk in data.chunks(33) {
+			absorber.put_slice(chunk);
+		}
+		assert_eq!(absorber.remaining(), 0);
+		drop(absorber);
+
+		assert_eq!(sample_bytes(&mut one_shot), sample_bytes(&mut incremental));
+	}
+
+	#[test]
+	fn test_labels_separate_values() {
+		let mut a = HasherChallenger::<Groestl256>::default();
+		a.absorb_bytes(b"ab", b"c");
+		let mut b = HasherChallenger::<Groestl256>::default();
+		b.absorb_bytes(b"a", b"bc");
+		assert_ne!(sample_bytes(&mut a), sample_bytes(&mut b));
+	}
+
+	#[test]
+	#[should_panic(expected = "fewer bytes")]
+	fn test_short_absorb_panics() {
+		let mut challenger = HasherChallenger::<Groestl256>::default();
+		challenger.absorb(b"label", 4).put_slice(&[1, 2, 3]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_long_absorb_panics() {
+		let mut challenger = HasherChallenger::<Groestl256>::default();
+		challenger.absorb(b"label", 2).put_slice(&[1, 2, 3]);
+	}
+}
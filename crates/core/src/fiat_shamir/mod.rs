@@ -1,8 +1,10 @@
 // Copyright 2024-2025 Irreducible Inc.
 
+mod absorb;
 mod hasher_challenger;
 mod sampling;
 
+pub use absorb::*;
 use bytes::{Buf, BufMut};
 pub use hasher_challenger::HasherChallenger;
 pub use sampling::*;
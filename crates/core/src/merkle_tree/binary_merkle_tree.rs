@@ -16,12 +16,42 @@ use super::errors::Error;
 /// The vector entries at each index in a batch are hashed together into leaf digests. Then a
 /// Merkle tree is constructed over the leaf digests. The implementation requires that the vector
 /// lengths are all equal to each other and a power of two.
+///
+/// In a salted tree, every leaf digest is compressed together with a random salt before the
+/// Merkle tree is constructed, so that the commitment hides the committed values even if they
+/// have low entropy.
 #[derive(Debug, Clone)]
 pub struct BinaryMerkleTree<D> {
 	/// Base-2 logarithm of the number of leaves
 	pub log_len: usize,
 	/// The inner nodes, arranged as a flattened array of layers with the root at the end
 	pub inner_nodes: Vec<D>,
+	/// The salts of the leaves, or empty if the tree is not salted
+	pub salts: Vec<D>,
+}
+
+/// Parameters of the parallel construction of a [`BinaryMerkleTree`].
+///
+/// The leaves are hashed by parallel tasks that each hash at least `2^log_leaf_batch_size`
+/// leaves. The inner nodes are then computed by independent tasks that each compress a subtree
+/// of `2^log_subtree_size` leaves, which keeps the work of a task local and avoids synchronizing
+/// the threads after every layer. Only the layers above the subtree roots are compressed layer by
+/// layer.
+#[derive(Debug, Clone, Copy)]
+pub struct MerkleTreeBuildOptions {
+	/// Base-2 logarithm of the minimum number of leaves hashed by a task.
+	pub log_leaf_batch_size: usize,
+	/// Base-2 logarithm of the number of leaves of a subtree compressed by a task.
+	pub log_subtree_size: usize,
+}
+
+impl Default for MerkleTreeBuildOptions {
+	fn default() -> Self {
+		Self {
+			log_leaf_batch_size: 2,
+			log_subtree_size: 8,
+		}
+	}
 }
 
 pub fn build<F, H, C>(
@@ -29,6 +59,32 @@ pub fn build<F, H, C>(
 	elements: &[F],
 	batch_size: usize,
 ) -> Result<BinaryMerkleTree<Output<H::Digest>>, Error>
+where
+	F: TowerField,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
+{
+	build_with_options::<F, H, C>(
+		compression,
+		elements,
+		batch_size,
+		&MerkleTreeBuildOptions::default(),
+		Vec::new(),
+		Vec::new(),
+	)
+}
+
+/// Builds a tree over the batched `elements`, storing the nodes in the reused `buffer`.
+///
+/// The leaves are salted with `salts` unless it is empty.
+pub(super) fn build_with_options<F, H, C>(
+	compression: &C,
+	elements: &[F],
+	batch_size: usize,
+	options: &MerkleTreeBuildOptions,
+	buffer: Vec<Output<H::Digest>>,
+	salts: Vec<Output<H::Digest>>,
+) -> Result<BinaryMerkleTree<Output<H::Digest>>, Error>
 where
 	F: TowerField,
 	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
@@ -48,8 +104,11 @@ where
 
 	internal_build(
 		compression,
-		|inner_nodes| hash_interleaved::<_, H>(elements, inner_nodes),
+		|inner_nodes| hash_interleaved::<_, H>(elements, inner_nodes, options.log_leaf_batch_size),
 		log_len,
+		options,
+		buffer,
+		salts,
 	)
 }
 
@@ -58,27 +117,56 @@ fn internal_build<Digest, C>(
 	// Must either successfully initialize the passed in slice or return error
 	hash_leaves: impl FnOnce(&mut [MaybeUninit<Digest>]) -> Result<(), Error>,
 	log_len: usize,
+	options: &MerkleTreeBuildOptions,
+	mut inner_nodes: Vec<Digest>,
+	salts: Vec<Digest>,
 ) -> Result<BinaryMerkleTree<Digest>, Error>
 where
 	Digest: Clone + Send + Sync,
 	C: PseudoCompressionFunction<Digest, 2> + Sync,
 {
+	if !salts.is_empty() && salts.len() != 1 << log_len {
+		bail!(Error::IncorrectVectorLen {
+			expected: 1 << log_len
+		});
+	}
+
 	let total_length = (1 << (log_len + 1)) - 1;
-	let mut inner_nodes = Vec::with_capacity(total_length);
+	inner_nodes.clear();
+	inner_nodes.reserve_exact(total_length);
 
 	hash_leaves(&mut inner_nodes.spare_capacity_mut()[..(1 << log_len)])?;
 
-	let (prev_layer, mut remaining) = inner_nodes.spare_capacity_mut().split_at_mut(1 << log_len);
+	if !salts.is_empty() {
+		let leaves = unsafe {
+			// SAFETY: the leaves were initialized by hash_leaves
+			slice_assume_init_mut(&mut inner_nodes.spare_capacity_mut()[..(1 << log_len)])
+		};
+		salt_leaves(compression, leaves, &salts);
+	}
+
+	let log_subtree_size = options.log_subtree_size.min(log_len);
+	compress_subtrees(
+		compression,
+		&mut inner_nodes.spare_capacity_mut()[..total_length],
+		log_len,
+		log_subtree_size,
+	);
+
+	// The layers above the subtree roots.
+	let subtree_roots_start = total_length + 1 - (1 << (log_len - log_subtree_size + 1));
+	let (prev_layer, mut remaining) = inner_nodes.spare_capacity_mut()[subtree_roots_start..]
+		.split_at_mut(1 << (log_len - log_subtree_size));
 
 	let mut prev_layer = unsafe {
-		// SAFETY: prev-layer was initialized by hash_leaves
+		// SAFETY: prev-layer was initialized by compress_subtrees
 		slice_assume_init_mut(prev_layer)
 	};
-	for i in 1..(log_len + 1) {
+	for i in log_subtree_size + 1..(log_len + 1) {
 		let (next_layer, next_remaining) = remaining.split_at_mut(1 << (log_len - i));
 		remaining = next_remaining;
 
-		compress_layer(compression, prev_layer, next_layer);
+		compress_layer(compression, prev_layer, next_layer, 1 << log_subtree_size);
 
 		prev_layer = unsafe {
 			// SAFETY: next_layer was just initialized by compress_layer
@@ -95,15 +183,114 @@ where
 	Ok(BinaryMerkleTree {
 		log_len,
 		inner_nodes,
+		salts,
 	})
 }
 
+/// Compresses every leaf digest together with its salt.
+#[tracing::instrument("MerkleTree::salt_leaves", skip_all, level = "debug")]
+fn salt_leaves<D, C>(compression: &C, leaves: &mut [D], salts: &[D])
+where
+	D: Clone + Send + Sync,
+	C: PseudoCompressionFunction<D, 2> + Sync,
+{
+	leaves
+		.par_iter_mut()
+		.zip(salts.par_iter())
+		.for_each(|(leaf, salt)| {
+			*leaf = compression.compress([leaf.clone(), salt.clone()]);
+		});
+}
+
+/// Computes the inner nodes of the subtrees with `2^log_subtree_size` leaves in parallel.
+///
+/// The leaves in `nodes` must be initialized, and the nodes of the subtrees are initialized on
+/// return.
+#[tracing::instrument("MerkleTree::compress_subtrees", skip_all, level = "debug")]
+fn compress_subtrees<D, C>(
+	compression: &C,
+	nodes: &mut [MaybeUninit<D>],
+	log_len: usize,
+	log_subtree_size: usize,
+) where
+	D: Clone + Send + Sync,
+	C: PseudoCompressionFunction<D, 2> + Sync,
+{
+	let n_subtrees = 1 << (log_len - log_subtree_size);
+	let (leaves, mut remaining) = nodes.split_at_mut(1 << log_len);
+	let leaves = unsafe {
+		// SAFETY: the leaves are initialized by the caller
+		slice_assume_init_mut(leaves)
+	};
+
+	// Collect the chunks of every layer that belong to each subtree.
+	let mut subtree_layers = (0..n_subtrees)
+		.map(|_| Vec::with_capacity(log_subtree_size))
+		.collect::<Vec<_>>();
+	for i in 1..(log_subtree_size + 1) {
+		let (layer, next_remaining) = remaining.split_at_mut(1 << (log_len - i));
+		remaining = next_remaining;
+		for (layers, chunk) in subtree_layers
+			.iter_mut()
+			.zip(layer.chunks_mut(1 << (log_subtree_size - i)))
+		{
+			layers.push(chunk);
+		}
+	}
+
+	leaves
+		.par_chunks(1 << log_subtree_size)
+		.zip(subtree_layers.into_par_iter())
+		.for_each(|(leaves, layers)| {
+			let mut prev_layer: &[D] = leaves;
+			for next_layer in layers {
+				for (prev_pair, next_digest) in
+					prev_layer.chunks_exact(2).zip(next_layer.iter_mut())
+				{
+					next_digest
+						.write(compression.compress(array::from_fn(|i| prev_pair[i].clone())));
+				}
+				prev_layer = unsafe {
+					// SAFETY: next_layer was just initialized
+					slice_assume_init_mut(next_layer)
+				};
+			}
+		});
+}
+
 #[instrument("BinaryMerkleTree::build", skip_all, level = "debug")]
 pub fn build_from_iterator<F, H, C, ParIter>(
 	compression: &C,
 	iterated_chunks: ParIter,
 	log_len: usize,
 ) -> Result<BinaryMerkleTree<Output<H::Digest>>, Error>
+where
+	F: TowerField,
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
+	ParIter: IndexedParallelIterator<Item: IntoIterator<Item = F>>,
+{
+	build_from_iterator_with_options::<F, H, C, _>(
+		compression,
+		iterated_chunks,
+		log_len,
+		&MerkleTreeBuildOptions::default(),
+		Vec::new(),
+		Vec::new(),
+	)
+}
+
+/// Builds a tree over the iterated leaves, storing the nodes in the reused `buffer`.
+///
+/// The leaves are salted with `salts` unless it is empty.
+pub(super) fn build_from_iterator_with_options<F, H, C, ParIter>(
+	compression: &C,
+	iterated_chunks: ParIter,
+	log_len: usize,
+	options: &MerkleTreeBuildOptions,
+	buffer: Vec<Output<H::Digest>>,
+	salts: Vec<Output<H::Digest>>,
+) -> Result<BinaryMerkleTree<Output<H::Digest>>, Error>
 where
 	F: TowerField,
 	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
@@ -112,8 +299,51 @@ where
 {
 	internal_build(
 		compression,
-		|inner_nodes| hash_iterated::<F, H, _>(iterated_chunks, inner_nodes),
+		|inner_nodes| {
+			hash_iterated::<F, H, _>(
+				iterated_chunks.with_min_len(1 << options.log_leaf_batch_size),
+				inner_nodes,
+			)
+		},
 		log_len,
+		options,
+		buffer,
+		salts,
+	)
+}
+
+/// Builds a tree over leaf digests that `hash_leaves` computes, storing the nodes in the reused
+/// `buffer`.
+///
+/// This is used when the leaves are hashed elsewhere, such as on a compute device. The leaves are
+/// salted with `salts` unless it is empty.
+pub(super) fn build_from_leaf_digests<D, C>(
+	compression: &C,
+	hash_leaves: impl FnOnce(&mut [D]) -> Result<(), Error>,
+	log_len: usize,
+	options: &MerkleTreeBuildOptions,
+	buffer: Vec<D>,
+	salts: Vec<D>,
+) -> Result<BinaryMerkleTree<D>, Error>
+where
+	D: Clone + Default + Send + Sync,
+	C: PseudoCompressionFunction<D, 2> + Sync,
+{
+	internal_build(
+		compression,
+		|leaves| {
+			for leaf in leaves.iter_mut() {
+				leaf.write(D::default());
+			}
+			hash_leaves(unsafe {
+				// SAFETY: the leaves were just initialized
+				slice_assume_init_mut(leaves)
+			})
+		},
+		log_len,
+		options,
+		buffer,
+		salts,
 	)
 }
 
@@ -156,14 +386,19 @@ impl<D: Clone> BinaryMerkleTree<D> {
 }
 
 #[tracing::instrument("MerkleTree::compress_layer", skip_all, level = "debug")]
-fn compress_layer<D, C>(compression: &C, prev_layer: &[D], next_layer: &mut [MaybeUninit<D>])
-where
+fn compress_layer<D, C>(
+	compression: &C,
+	prev_layer: &[D],
+	next_layer: &mut [MaybeUninit<D>],
+	min_batch_size: usize,
+) where
 	D: Clone + Send + Sync,
 	C: PseudoCompressionFunction<D, 2> + Sync,
 {
 	prev_layer
 		.par_chunks_exact(2)
 		.zip(next_layer.par_iter_mut())
+		.with_min_len(min_batch_size)
 		.for_each(|(prev_pair, next_digest)| {
 			next_digest.write(compression.compress(array::from_fn(|i| prev_pair[i].clone())));
 		})
@@ -178,6 +413,7 @@ where
 fn hash_interleaved<F, H>(
 	elems: &[F],
 	digests: &mut [MaybeUninit<Output<H::Digest>>],
+	log_min_batch_size: usize,
 ) -> Result<(), Error>
 where
 	F: TowerField,
@@ -191,6 +427,7 @@ where
 
 	let hash_data_iter = elems
 		.par_chunks(elems.len() / digests.len())
+		.with_min_len(1 << log_min_batch_size)
 		.map(|s| s.iter().copied());
 	hash_iterated::<_, H, _>(hash_data_iter, digests)
 }
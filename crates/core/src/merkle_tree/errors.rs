@@ -14,8 +14,12 @@ pub enum Error {
 	PowerOfTwoLengthRequired,
 	#[error("The layer does not exist in the Merkle tree")]
 	IncorrectLayerDepth,
+	#[error("the operation is not supported with the salting of the scheme")]
+	SaltingMismatch,
 	#[error("transcript error: {0}")]
 	Transcript(#[from] transcript::Error),
+	#[error("compute error: {0}")]
+	Compute(#[from] binius_compute::layer::Error),
 	#[error("verification failure: {0}")]
 	Verification(#[from] VerificationError),
 }
@@ -1,5 +1,7 @@
 // Copyright 2024-2025 Irreducible Inc.
 
+use binius_compute::{FSlice, layer::ComputeLayer};
+use binius_field::TowerField;
 use binius_maybe_rayon::iter::IndexedParallelIterator;
 use bytes::{Buf, BufMut};
 
@@ -20,7 +22,7 @@ pub struct Commitment<Digest> {
 
 /// A Merkle tree scheme.
 pub trait MerkleTreeScheme<T>: Sync {
-	type Digest: Clone + PartialEq + Eq;
+	type Digest: Clone + PartialEq + Eq + Send + Sync;
 
 	/// Returns the optimal layer that the verifier should verify only once.
 	fn optimal_verify_layer(&self, n_queries: usize, tree_depth: usize) -> usize;
@@ -33,6 +35,14 @@ pub trait MerkleTreeScheme<T>: Sync {
 	/// * `n_queries` - the number of opening queries
 	fn proof_size(&self, len: usize, n_queries: usize, layer_depth: usize) -> Result<usize, Error>;
 
+	/// Returns the byte-size of the proof read by [`Self::verify_opening`] for a single entry.
+	///
+	/// ## Arguments
+	///
+	/// * `layer_depth` - depth of the layer the opening is verified against
+	/// * `tree_depth` - depth of the Merkle tree
+	fn opening_proof_size(&self, layer_depth: usize, tree_depth: usize) -> usize;
+
 	/// Verify the opening of the full vector.
 	fn verify_vector(
 		&self,
@@ -95,6 +105,22 @@ pub trait MerkleTreeProver<T>: Sync {
 	where
 		ParIter: IndexedParallelIterator<Item: IntoIterator<Item = T>>;
 
+	/// Commits a vector that resides in the memory of a compute device.
+	///
+	/// The vector is split into `2^log_len` equal-sized leaves, which are hashed on the device, so
+	/// that only the leaf digests are copied back to the host. The commitment is the same as that
+	/// of [`Self::commit`] with the leaf size as the batch size.
+	#[allow(clippy::type_complexity)]
+	fn commit_on_device<Hal>(
+		&self,
+		hal: &Hal,
+		data: FSlice<'_, T, Hal>,
+		log_len: usize,
+	) -> Result<(Commitment<<Self::Scheme as MerkleTreeScheme<T>>::Digest>, Self::Committed), Error>
+	where
+		T: TowerField,
+		Hal: ComputeLayer<T>;
+
 	/// Returns the internal digest layer at the given depth.
 	fn layer<'a>(
 		&self,
@@ -116,4 +142,12 @@ pub trait MerkleTreeProver<T>: Sync {
 		index: usize,
 		proof: &mut TranscriptWriter<B>,
 	) -> Result<(), Error>;
+
+	/// Returns the data of a commitment that is no longer needed, so that the prover may reuse its
+	/// resources for later commitments.
+	///
+	/// The default implementation drops the data.
+	fn recycle(&self, committed: Self::Committed) {
+		drop(committed);
+	}
 }
@@ -1,14 +1,18 @@
 // Copyright 2024-2025 Irreducible Inc.
 
+use std::sync::Mutex;
+
+use binius_compute::{ComputeLayerExecutor, FSlice, layer::ComputeLayer};
 use binius_field::TowerField;
 use binius_hash::{PseudoCompressionFunction, multi_digest::ParallelDigest};
 use binius_maybe_rayon::iter::IndexedParallelIterator;
 use bytes::BufMut;
 use digest::{FixedOutputReset, Output, core_api::BlockSizeUser};
 use getset::Getters;
+use rand::{RngCore, SeedableRng, rngs::StdRng};
 
 use super::{
-	binary_merkle_tree::{self, BinaryMerkleTree},
+	binary_merkle_tree::{self, BinaryMerkleTree, MerkleTreeBuildOptions},
 	errors::Error,
 	merkle_tree_vcs::{Commitment, MerkleTreeProver},
 	scheme::BinaryMerkleTreeScheme,
@@ -19,14 +23,63 @@ use crate::transcript::TranscriptWriter;
 pub struct BinaryMerkleTreeProver<T, H: ParallelDigest, C> {
 	#[getset(get = "pub")]
 	scheme: BinaryMerkleTreeScheme<T, H::Digest, C>,
+	#[getset(get = "pub")]
+	options: MerkleTreeBuildOptions,
+	/// Node buffers of recycled trees, which are reused by later commitments.
+	buffers: Mutex<Vec<Vec<Output<H::Digest>>>>,
+	/// The generator of the leaf salts, if the scheme is salted.
+	salt_rng: Option<Mutex<StdRng>>,
 }
 
 impl<T, C, H: ParallelDigest> BinaryMerkleTreeProver<T, H, C> {
 	pub fn new(compression: C) -> Self {
+		Self::with_options(compression, MerkleTreeBuildOptions::default())
+	}
+
+	pub fn with_options(compression: C, options: MerkleTreeBuildOptions) -> Self {
 		Self {
 			scheme: BinaryMerkleTreeScheme::new(compression),
+			options,
+			buffers: Mutex::new(Vec::new()),
+			salt_rng: None,
 		}
 	}
+
+	/// Salts the leaves of the committed trees with salts drawn from a PRG seeded with `seed`.
+	///
+	/// The salts must be unpredictable for the commitments to be hiding, so the seed must be
+	/// sampled from a cryptographically secure source of randomness.
+	pub fn with_salt_seed(self, seed: [u8; 32]) -> Self {
+		Self {
+			scheme: self.scheme.into_salted(),
+			salt_rng: Some(Mutex::new(StdRng::from_seed(seed))),
+			..self
+		}
+	}
+
+	/// Draws the salts of `n_leaves` leaves, or returns an empty vector if the scheme is not
+	/// salted.
+	fn draw_salts(&self, n_leaves: usize) -> Vec<Output<H::Digest>> {
+		let Some(salt_rng) = &self.salt_rng else {
+			return Vec::new();
+		};
+		let mut salt_rng = salt_rng.lock().expect("mutex is not poisoned");
+		(0..n_leaves)
+			.map(|_| {
+				let mut salt = Output::<H::Digest>::default();
+				salt_rng.fill_bytes(&mut salt);
+				salt
+			})
+			.collect()
+	}
+
+	fn take_buffer(&self) -> Vec<Output<H::Digest>> {
+		self.buffers
+			.lock()
+			.expect("mutex is not poisoned")
+			.pop()
+			.unwrap_or_default()
+	}
 }
 
 impl<F, H, C> MerkleTreeProver<F> for BinaryMerkleTreeProver<F, H, C>
@@ -47,8 +100,14 @@ where
 		data: &[F],
 		batch_size: usize,
 	) -> Result<(Commitment<Output<H::Digest>>, Self::Committed), Error> {
-		let tree =
-			binary_merkle_tree::build::<_, H, _>(self.scheme.compression(), data, batch_size)?;
+		let tree = binary_merkle_tree::build_with_options::<_, H, _>(
+			self.scheme.compression(),
+			data,
+			batch_size,
+			&self.options,
+			self.take_buffer(),
+			self.draw_salts(data.len() / batch_size),
+		)?;
 
 		let commitment = Commitment {
 			root: tree.root(),
@@ -74,10 +133,53 @@ where
 		proof: &mut TranscriptWriter<B>,
 	) -> Result<(), Error> {
 		let branch = committed.branch(index, layer_depth)?;
+		if !committed.salts.is_empty() {
+			proof.write(&committed.salts[index]);
+		}
 		proof.write_slice(&branch);
 		Ok(())
 	}
 
+	/// Keeps the node buffer of the tree, so that a later commitment can reuse its allocation.
+	fn recycle(&self, committed: Self::Committed) {
+		self.buffers
+			.lock()
+			.expect("mutex is not poisoned")
+			.push(committed.inner_nodes);
+	}
+
+	fn commit_on_device<Hal>(
+		&self,
+		hal: &Hal,
+		data: FSlice<'_, F, Hal>,
+		log_len: usize,
+	) -> Result<(Commitment<Output<H::Digest>>, Self::Committed), Error>
+	where
+		Hal: ComputeLayer<F>,
+	{
+		let tree = binary_merkle_tree::build_from_leaf_digests(
+			self.scheme.compression(),
+			|leaves| {
+				hal.execute(|exec| {
+					exec.hash_leaves::<H>(data, leaves)?;
+					Ok(vec![])
+				})?;
+				Ok(())
+			},
+			log_len,
+			&self.options,
+			self.take_buffer(),
+			self.draw_salts(1 << log_len),
+		)?;
+
+		let commitment = Commitment {
+			root: tree.root(),
+			depth: tree.log_len,
+		};
+
+		Ok((commitment, tree))
+	}
+
 	#[allow(clippy::type_complexity)]
 	fn commit_iterated<ParIter>(
 		&self,
@@ -90,10 +192,13 @@ where
 	where
 		ParIter: IndexedParallelIterator<Item: IntoIterator<Item = F>>,
 	{
-		let tree = binary_merkle_tree::build_from_iterator::<F, H, C, _>(
+		let tree = binary_merkle_tree::build_from_iterator_with_options::<F, H, C, _>(
 			self.scheme.compression(),
 			iterated_chunks,
 			log_len,
+			&self.options,
+			self.take_buffer(),
+			self.draw_salts(1 << log_len),
 		)?;
 
 		let commitment = Commitment {
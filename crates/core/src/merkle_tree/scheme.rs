@@ -22,6 +22,8 @@ use crate::transcript::TranscriptReader;
 pub struct BinaryMerkleTreeScheme<T, H, C> {
 	#[getset(get = "pub")]
 	compression: C,
+	/// Whether the leaf digests are compressed with salts, which are included in the openings.
+	salted: bool,
 	// This makes it so that `BinaryMerkleTreeScheme` remains Send + Sync
 	// See https://doc.rust-lang.org/nomicon/phantom-data.html#table-of-phantomdata-patterns
 	_phantom: PhantomData<fn() -> (T, H)>,
@@ -31,9 +33,80 @@ impl<T, H, C> BinaryMerkleTreeScheme<T, H, C> {
 	pub fn new(compression: C) -> Self {
 		Self {
 			compression,
+			salted: false,
 			_phantom: PhantomData,
 		}
 	}
+
+	/// Creates a scheme where every leaf digest is compressed together with a salt.
+	///
+	/// The salt of a leaf precedes the Merkle branch in the opening proof.
+	pub fn new_salted(compression: C) -> Self {
+		Self {
+			compression,
+			salted: true,
+			_phantom: PhantomData,
+		}
+	}
+
+	pub(super) fn into_salted(self) -> Self {
+		Self {
+			salted: true,
+			..self
+		}
+	}
+
+	/// Returns whether the leaves are salted.
+	pub const fn is_salted(&self) -> bool {
+		self.salted
+	}
+}
+
+impl<T, H, C> BinaryMerkleTreeScheme<T, H, C>
+where
+	T: TowerField,
+	H: Digest + BlockSizeUser,
+	C: PseudoCompressionFunction<Output<H>, 2> + Sync,
+{
+	/// Verify the opening of the full vector of a salted commitment.
+	///
+	/// This is the counterpart of [`MerkleTreeScheme::verify_vector`], which is not supported by
+	/// salted schemes because the verifier additionally needs the salts of all leaves.
+	pub fn verify_vector_with_salts(
+		&self,
+		root: &Output<H>,
+		data: &[T],
+		salts: &[Output<H>],
+		batch_size: usize,
+	) -> Result<(), Error> {
+		if !self.salted {
+			bail!(Error::SaltingMismatch);
+		}
+		if data.len() % batch_size != 0 {
+			bail!(Error::IncorrectBatchSize);
+		}
+		if salts.len() != data.len() / batch_size {
+			bail!(Error::IncorrectVectorLen {
+				expected: data.len() / batch_size
+			});
+		}
+
+		let mut digests = data
+			.chunks(batch_size)
+			.zip(salts)
+			.map(|(chunk, salt)| {
+				let digest = hash_serialize::<T, H>(chunk)
+					.expect("values are of TowerField type which we expect to be serializable");
+				self.compression.compress([digest, salt.clone()])
+			})
+			.collect::<Vec<_>>();
+
+		fold_digests_vector_inplace(&self.compression, &mut digests)?;
+		if digests[0] != *root {
+			bail!(VerificationError::InvalidProof)
+		}
+		Ok(())
+	}
 }
 
 impl<F, H, C> MerkleTreeScheme<F> for BinaryMerkleTreeScheme<F, H, C>
@@ -60,16 +133,25 @@ where
 			bail!(Error::IncorrectLayerDepth)
 		}
 
-		Ok(((log_len - layer_depth - 1) * n_queries + (1 << layer_depth))
+		let salt_len = if self.salted { n_queries } else { 0 };
+		Ok(((log_len - layer_depth - 1) * n_queries + salt_len + (1 << layer_depth))
 			* <H as Digest>::output_size())
 	}
 
+	fn opening_proof_size(&self, layer_depth: usize, tree_depth: usize) -> usize {
+		let salt_len = usize::from(self.salted);
+		(tree_depth.saturating_sub(layer_depth) + salt_len) * <H as Digest>::output_size()
+	}
+
 	fn verify_vector(
 		&self,
 		root: &Self::Digest,
 		data: &[F],
 		batch_size: usize,
 	) -> Result<(), Error> {
+		if self.salted {
+			bail!(Error::SaltingMismatch);
+		}
 		if data.len() % batch_size != 0 {
 			bail!(Error::IncorrectBatchSize);
 		}
@@ -130,6 +212,10 @@ where
 
 		let mut leaf_digest = hash_serialize::<F, H>(values)
 			.expect("values are of TowerField type which we expect to be serializable");
+		if self.salted {
+			let salt = proof.read()?;
+			leaf_digest = self.compression.compress([leaf_digest, salt]);
+		}
 		for branch_node in proof.read_vec(tree_depth - layer_depth)? {
 			leaf_digest = self.compression.compress(if index & 1 == 0 {
 				[leaf_digest, branch_node]
@@ -4,12 +4,57 @@ use core::slice;
 use std::iter::repeat_with;
 
 use binius_field::{BinaryField16b, Field};
-use binius_hash::groestl::{Groestl256, Groestl256ByteCompression};
+use binius_hash::{
+	PseudoCompressionFunction, Vision32Compression, Vision32ParallelDigest, VisionHasherDigest,
+	blake3::{Blake3, Blake3Compression, Blake3Parallel},
+	groestl::{Groestl256, Groestl256ByteCompression},
+	keccak::{Keccak256, Keccak256ByteCompression, Sha3_256, Sha3_256ByteCompression},
+	multi_digest::ParallelDigest,
+	sha2::{Sha256, Sha256Compression, Sha512, Sha512Compression},
+};
+use digest::{FixedOutputReset, Output, core_api::BlockSizeUser};
 use rand::{SeedableRng, rngs::StdRng};
 
-use super::{BinaryMerkleTreeProver, MerkleTreeProver, MerkleTreeScheme};
+use super::{BinaryMerkleTreeProver, MerkleTreeBuildOptions, MerkleTreeProver, MerkleTreeScheme};
 use crate::{fiat_shamir::HasherChallenger, transcript::ProverTranscript};
 
+fn check_commit_prove_open<H, C>(compression: C)
+where
+	H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset + Default> + Sync,
+	C: PseudoCompressionFunction<Output<H::Digest>, 2> + Sync,
+{
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = BinaryMerkleTreeProver::<_, H, _>::new(compression);
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(16)
+		.collect::<Vec<BinaryField16b>>();
+	let (commitment, tree) = mr_prover.commit(&data, 1).unwrap();
+
+	assert_eq!(commitment.root, tree.root());
+
+	for (i, value) in data.iter().enumerate() {
+		let mut proof_writer = ProverTranscript::<HasherChallenger<H::Digest>>::new();
+		mr_prover
+			.prove_opening(&tree, 0, i, &mut proof_writer.message())
+			.unwrap();
+
+		let mut proof_reader = proof_writer.into_verifier();
+		mr_prover
+			.scheme()
+			.verify_opening(
+				i,
+				slice::from_ref(value),
+				0,
+				4,
+				slice::from_ref(&commitment.root),
+				&mut proof_reader.message(),
+			)
+			.unwrap();
+	}
+}
+
 #[test]
 fn test_binary_merkle_vcs_commit_prove_open_correctly() {
 	let mut rng = StdRng::seed_from_u64(0);
@@ -44,6 +89,30 @@ fn test_binary_merkle_vcs_commit_prove_open_correctly() {
 	}
 }
 
+#[test]
+fn test_binary_merkle_vcs_sha2() {
+	check_commit_prove_open::<Sha256, _>(Sha256Compression::default());
+	check_commit_prove_open::<Sha512, _>(Sha512Compression::default());
+}
+
+#[test]
+fn test_binary_merkle_vcs_keccak() {
+	check_commit_prove_open::<Keccak256, _>(Keccak256ByteCompression);
+	check_commit_prove_open::<Sha3_256, _>(Sha3_256ByteCompression);
+}
+
+#[test]
+fn test_binary_merkle_vcs_blake3() {
+	check_commit_prove_open::<Blake3, _>(Blake3Compression::default());
+	check_commit_prove_open::<Blake3Parallel, _>(Blake3Compression::default());
+}
+
+#[test]
+fn test_binary_merkle_vcs_vision() {
+	check_commit_prove_open::<VisionHasherDigest, _>(Vision32Compression);
+	check_commit_prove_open::<Vision32ParallelDigest, _>(Vision32Compression);
+}
+
 #[test]
 fn test_binary_merkle_vcs_commit_layer_prove_open_correctly() {
 	let mut rng = StdRng::seed_from_u64(0);
@@ -69,6 +138,10 @@ fn test_binary_merkle_vcs_commit_layer_prove_open_correctly() {
 				.unwrap();
 
 			let mut proof_reader = proof_writer.into_verifier();
+			assert_eq!(
+				proof_reader.bytes_remaining(),
+				mr_prover.scheme().opening_proof_size(layer_depth, 5)
+			);
 			mr_prover
 				.scheme()
 				.verify_opening(
@@ -100,3 +173,153 @@ fn test_binary_merkle_vcs_verify_vector() {
 		.verify_vector(&commitment.root, &data, 1)
 		.unwrap();
 }
+
+#[test]
+fn test_binary_merkle_build_options_do_not_change_tree() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(1 << 10)
+		.collect::<Vec<BinaryField16b>>();
+
+	let default_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let (expected_commitment, expected_tree) = default_prover.commit(&data, 2).unwrap();
+
+	for log_leaf_batch_size in [0, 3, 12] {
+		for log_subtree_size in [0, 1, 4, 9, 12] {
+			let options = MerkleTreeBuildOptions {
+				log_leaf_batch_size,
+				log_subtree_size,
+			};
+			let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::with_options(
+				Groestl256ByteCompression,
+				options,
+			);
+			let (commitment, tree) = mr_prover.commit(&data, 2).unwrap();
+			assert_eq!(commitment, expected_commitment);
+			assert_eq!(tree.inner_nodes, expected_tree.inner_nodes);
+		}
+	}
+}
+
+#[test]
+fn test_binary_merkle_recycled_buffers() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let large_data = repeat_with(|| Field::random(&mut rng))
+		.take(64)
+		.collect::<Vec<BinaryField16b>>();
+	let small_data = repeat_with(|| Field::random(&mut rng))
+		.take(8)
+		.collect::<Vec<BinaryField16b>>();
+
+	let (_, expected_tree) = mr_prover.commit(&small_data, 1).unwrap();
+
+	let (_, large_tree) = mr_prover.commit(&large_data, 1).unwrap();
+	mr_prover.recycle(large_tree);
+
+	let (commitment, tree) = mr_prover.commit(&small_data, 1).unwrap();
+	assert_eq!(commitment.root, expected_tree.root());
+	assert_eq!(tree.inner_nodes, expected_tree.inner_nodes);
+	assert_eq!(tree.log_len, expected_tree.log_len);
+}
+
+#[test]
+fn test_binary_merkle_salted_commit_prove_open() {
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+		.with_salt_seed([1; 32]);
+	assert!(mr_prover.scheme().is_salted());
+
+	let data = repeat_with(|| Field::random(&mut rng))
+		.take(16)
+		.collect::<Vec<BinaryField16b>>();
+	let (commitment, tree) = mr_prover.commit(&data, 1).unwrap();
+	assert_eq!(tree.salts.len(), 16);
+
+	for layer_depth in [0, 2] {
+		let layer = mr_prover.layer(&tree, layer_depth).unwrap();
+		for (i, value) in data.iter().enumerate() {
+			let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+			mr_prover
+				.prove_opening(&tree, layer_depth, i, &mut proof_writer.message())
+				.unwrap();
+
+			let mut proof_reader = proof_writer.into_verifier();
+			assert_eq!(
+				proof_reader.bytes_remaining(),
+				mr_prover.scheme().opening_proof_size(layer_depth, 4)
+			);
+			mr_prover
+				.scheme()
+				.verify_opening(
+					i,
+					slice::from_ref(value),
+					layer_depth,
+					4,
+					layer,
+					&mut proof_reader.message(),
+				)
+				.unwrap();
+		}
+	}
+
+	// The unsalted scheme rejects the salted opening.
+	let unsalted_prover =
+		BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let mut proof_writer = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	mr_prover
+		.prove_opening(&tree, 0, 3, &mut proof_writer.message())
+		.unwrap();
+	let mut proof_reader = proof_writer.into_verifier();
+	assert!(
+		unsalted_prover
+			.scheme()
+			.verify_opening(
+				3,
+				slice::from_ref(&data[3]),
+				0,
+				4,
+				slice::from_ref(&commitment.root),
+				&mut proof_reader.message(),
+			)
+			.is_err()
+	);
+}
+
+#[test]
+fn test_binary_merkle_salted_commitments_hide_data() {
+	let data = vec![BinaryField16b::ZERO; 8];
+
+	let unsalted_prover =
+		BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let (unsalted_commitment, _) = unsalted_prover.commit(&data, 1).unwrap();
+
+	let mr_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+		.with_salt_seed([2; 32]);
+	let (commitment_1, tree) = mr_prover.commit(&data, 1).unwrap();
+	let (commitment_2, _) = mr_prover.commit(&data, 1).unwrap();
+	assert_ne!(commitment_1.root, unsalted_commitment.root);
+	assert_ne!(commitment_1.root, commitment_2.root);
+
+	// The same seed reproduces the same commitments.
+	let reseeded_prover =
+		BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression)
+			.with_salt_seed([2; 32]);
+	let (reseeded_commitment, _) = reseeded_prover.commit(&data, 1).unwrap();
+	assert_eq!(reseeded_commitment, commitment_1);
+
+	let scheme = mr_prover.scheme();
+	assert!(scheme.verify_vector(&commitment_1.root, &data, 1).is_err());
+	scheme
+		.verify_vector_with_salts(&commitment_1.root, &data, &tree.salts, 1)
+		.unwrap();
+	assert!(
+		scheme
+			.verify_vector_with_salts(&commitment_2.root, &data, &tree.salts, 1)
+			.is_err()
+	);
+}
@@ -9,7 +9,7 @@ use binius_utils::sparse_index::SparseIndex;
 
 use super::{error::Error, util::ResizeableIndex, verify::CommitMeta};
 use crate::{
-	oracle::{MultilinearOracleSet, MultilinearPolyOracle, MultilinearPolyVariant},
+	oracle::{MultilinearOracleSet, MultilinearPolyOracle, MultilinearPolyVariant, OracleId},
 	witness::{MultilinearExtensionIndex, MultilinearWitness},
 };
 
@@ -19,6 +19,44 @@ use crate::{
 /// 2. a sparse index mapping oracle IDs to committed IDs in the commit metadata
 pub fn make_oracle_commit_meta<F: TowerField>(
 	oracles: &MultilinearOracleSet<F>,
+) -> Result<(CommitMeta, SparseIndex<usize>), Error> {
+	make_oracle_commit_meta_filtered(oracles, |_| true)
+}
+
+/// Indexes the committed oracles in a [`MultilinearOracleSet`] by commit batch and returns the
+/// commit metadata and the sparse index of every batch, like [`make_oracle_commit_meta`].
+///
+/// The first batch is the default batch, which holds the committed oracles that are not in any of
+/// `batches`. It is followed by one batch for each of `batches`.
+pub fn make_oracle_commit_metas<F: TowerField>(
+	oracles: &MultilinearOracleSet<F>,
+	batches: &[Vec<OracleId>],
+) -> Result<Vec<(CommitMeta, SparseIndex<usize>)>, Error> {
+	let mut batch_index = SparseIndex::with_capacity(oracles.size());
+	for (batch, oracle_ids) in batches.iter().enumerate() {
+		for &id in oracle_ids {
+			if !matches!(oracles[id].variant, MultilinearPolyVariant::Committed) {
+				return Err(Error::BatchedOracleNotCommitted { id });
+			}
+			if batch_index.get(id.index()).is_some() {
+				return Err(Error::OracleInSeveralCommitBatches { id });
+			}
+			batch_index.set(id.index(), batch + 1);
+		}
+	}
+
+	(0..=batches.len())
+		.map(|batch| {
+			make_oracle_commit_meta_filtered(oracles, |id| {
+				batch_index.get(id.index()).copied().unwrap_or(0) == batch
+			})
+		})
+		.collect()
+}
+
+fn make_oracle_commit_meta_filtered<F: TowerField>(
+	oracles: &MultilinearOracleSet<F>,
+	include: impl Fn(OracleId) -> bool,
 ) -> Result<(CommitMeta, SparseIndex<usize>), Error> {
 	// We need to construct two structures:
 	//
@@ -42,7 +80,7 @@ pub fn make_oracle_commit_meta<F: TowerField>(
 	let mut first_pass_index = SparseIndex::with_capacity(oracles.size());
 	let mut n_multilins_by_vars = ResizeableIndex::<usize>::new();
 	for oracle in oracles.polys() {
-		if matches!(oracle.variant, MultilinearPolyVariant::Committed) {
+		if matches!(oracle.variant, MultilinearPolyVariant::Committed) && include(oracle.id()) {
 			let n_packed_vars = n_packed_vars_for_committed_oracle(oracle);
 			let n_multilins_for_vars = n_multilins_by_vars.get_mut(n_packed_vars);
 
@@ -149,4 +187,41 @@ mod tests {
 		assert_eq!(index.get(batch_2_2_ids[1].index()).copied(), Some(11));
 		assert_eq!(index.get(repeat.index()).copied(), None);
 	}
+
+	#[test]
+	fn test_make_oracle_commit_metas() {
+		let mut oracles = MultilinearOracleSet::<BinaryField128b>::new();
+
+		let default_ids = oracles.add_committed_multiple::<2>(8, 0);
+		let batch_1_ids = oracles.add_committed_multiple::<2>(10, 0);
+		let batch_2_id = oracles.add_committed(12, 2);
+		let repeat = oracles.add_repeating(default_ids[0], 5).unwrap();
+
+		let batches = vec![batch_1_ids.to_vec(), vec![batch_2_id]];
+		let metas = make_oracle_commit_metas(&oracles, &batches).unwrap();
+		assert_eq!(metas.len(), 3);
+
+		let (commit_meta, index) = &metas[0];
+		assert_eq!(commit_meta.total_multilins(), 2);
+		assert_eq!(index.get(default_ids[1].index()).copied(), Some(1));
+		assert_eq!(index.get(batch_1_ids[0].index()).copied(), None);
+
+		let (commit_meta, index) = &metas[1];
+		assert_eq!(commit_meta.n_multilins_by_vars(), &[0, 0, 0, 2]);
+		assert_eq!(index.get(batch_1_ids[0].index()).copied(), Some(0));
+		assert_eq!(index.get(batch_2_id.index()).copied(), None);
+
+		let (commit_meta, index) = &metas[2];
+		assert_eq!(commit_meta.total_multilins(), 1);
+		assert_eq!(index.get(batch_2_id.index()).copied(), Some(0));
+
+		assert!(matches!(
+			make_oracle_commit_metas(&oracles, &[vec![repeat]]),
+			Err(Error::BatchedOracleNotCommitted { id }) if id == repeat
+		));
+		assert!(matches!(
+			make_oracle_commit_metas(&oracles, &[vec![batch_2_id], vec![batch_2_id]]),
+			Err(Error::OracleInSeveralCommitBatches { id }) if id == batch_2_id
+		));
+	}
 }
@@ -1,6 +1,7 @@
 // Copyright 2024-2025 Irreducible Inc.
 
 use binius_compute::layer;
+use binius_utils::error_code::{ErrorCode, ErrorDomain, HasErrorCode};
 
 use crate::{
 	oracle::OracleId,
@@ -21,6 +22,10 @@ pub enum Error {
 	InvalidCommittedId { max_index: usize },
 	#[error("invalid transparent ID")]
 	InvalidTransparentId { max_index: usize },
+	#[error("oracle {id} is in a commit batch, but is not committed")]
+	BatchedOracleNotCommitted { id: OracleId },
+	#[error("oracle {id} is in more than one commit batch")]
+	OracleInSeveralCommitBatches { id: OracleId },
 	#[error("the number of variables recorded for oracle {id} is incorrect")]
 	OracleToCommitIndexMalformed { id: OracleId },
 	#[error("the number of variables of the polynomials in sumcheck claim {index} do not match")]
@@ -56,3 +61,41 @@ pub enum VerificationError {
 	#[error("Transcript error: {0}")]
 	Transcript(#[from] transcript::Error),
 }
+
+impl HasErrorCode for Error {
+	fn error_code(&self) -> ErrorCode {
+		let index = match self {
+			Self::CommittedsNotSorted => 1,
+			Self::TransparentsNotSorted => 2,
+			Self::CommittedPackedEvaluationsMissing { .. } => 3,
+			Self::InvalidCommittedId { .. } => 4,
+			Self::InvalidTransparentId { .. } => 5,
+			Self::OracleToCommitIndexMalformed { .. } => 6,
+			Self::SumcheckClaimVariablesMismatch { .. } => 7,
+			Self::Alloc(_) => 8,
+			Self::ComputeError(_) => 9,
+			Self::Math(_) => 10,
+			Self::ReedSolomon(_) => 11,
+			Self::Polynomial(_) => 12,
+			Self::FRI(err) => return err.error_code(),
+			Self::Sumcheck(_) => 13,
+			Self::Witness(_) => 14,
+			Self::NTT(_) => 15,
+			Self::VerificationError(err) => return err.error_code(),
+			Self::BatchedOracleNotCommitted { .. } => 16,
+			Self::OracleInSeveralCommitBatches { .. } => 17,
+		};
+		ErrorCode::new(ErrorDomain::Piop, index)
+	}
+}
+
+impl HasErrorCode for VerificationError {
+	fn error_code(&self) -> ErrorCode {
+		let index = match self {
+			Self::IncorrectTransparentEvaluation { .. } => 101,
+			Self::IncorrectSumcheckEvaluation => 102,
+			Self::Transcript(_) => 103,
+		};
+		ErrorCode::new(ErrorDomain::Piop, index)
+	}
+}
@@ -7,7 +7,7 @@ use binius_compute::{
 	cpu::CpuMemory,
 };
 use binius_field::{
-	BinaryField, PackedExtension, PackedField, PackedFieldIndexable, TowerField,
+	BinaryField, ExtensionField, PackedExtension, PackedField, PackedFieldIndexable, TowerField,
 	packed::PackedSliceMut,
 };
 use binius_math::{MLEDirectAdapter, MultilinearExtension, MultilinearPoly};
@@ -164,12 +164,61 @@ where
 	Ok(output)
 }
 
+/// Commits a batch of multilinear polynomials, encoding them on a compute device.
+///
+/// This is the same commitment as [`commit`], computed with
+/// [`fri::commit_interleaved_on_device`]. The codeword stays in device memory, and is passed to
+/// [`prove`] as a [`fri::CommittedCodeword::Device`].
+#[allow(clippy::type_complexity)]
+pub fn commit_on_device<'b, F, FEncode, P, M, NTT, MTScheme, MTProver, Hal>(
+	hal: &Hal,
+	dev_alloc: &'b impl ComputeAllocator<F, Hal::DevMem>,
+	fri_params: &FRIParams<F, FEncode>,
+	ntt: &NTT,
+	merkle_prover: &MTProver,
+	multilins: &[M],
+) -> Result<fri::DeviceCommitOutput<'b, F, MTScheme::Digest, MTProver::Committed, Hal>, Error>
+where
+	F: TowerField + ExtensionField<FEncode> + PackedExtension<FEncode>,
+	FEncode: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FEncode>,
+	M: MultilinearPoly<P>,
+	NTT: AdditiveNTT<FEncode> + Sync,
+	MTScheme: MerkleTreeScheme<F>,
+	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
+	Hal: ComputeLayer<F>,
+{
+	let packed_multilins = multilins
+		.iter()
+		.enumerate()
+		.map(|(i, unpacked_committed)| {
+			packed_committed(OracleId::from_index(i), unpacked_committed)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	if !is_sorted_ascending(packed_multilins.iter().map(|mle| mle.n_vars())) {
+		return Err(Error::CommittedsNotSorted);
+	}
+
+	let output = fri::commit_interleaved_on_device::<_, FEncode, P, _, _, _, _, _>(
+		hal,
+		dev_alloc,
+		fri_params,
+		ntt,
+		merkle_prover,
+		|message_buffer| merge_multilins(&packed_multilins, message_buffer),
+	)?;
+
+	Ok(output)
+}
+
 /// Proves a batch of sumcheck claims that are products of committed polynomials from a committed
 /// batch and transparent polynomials.
 ///
-/// The arguments corresponding to the committed multilinears must be the output of [`commit`].
+/// The arguments corresponding to the committed multilinears must be the output of [`commit`], or
+/// of [`commit_on_device`], in which case `codeword` is the device-resident codeword.
 #[allow(clippy::too_many_arguments)]
 pub fn prove<
+	'b,
 	Hal,
 	F,
 	FEncode,
@@ -182,13 +231,13 @@ pub fn prove<
 	HostComputeAllocatorType,
 	DeviceComputeAllocatorType,
 >(
-	compute_data: &ComputeData<F, Hal, HostComputeAllocatorType, DeviceComputeAllocatorType>,
+	compute_data: &'b ComputeData<F, Hal, HostComputeAllocatorType, DeviceComputeAllocatorType>,
 	fri_params: &FRIParams<F, FEncode>,
 	ntt: &NTT,
 	merkle_prover: &MTProver,
 	commit_meta: &CommitMeta,
 	committed: MTProver::Committed,
-	codeword: &[P],
+	codeword: &fri::CommittedCodeword<'b, F, P, Hal>,
 	committed_multilins: &[M],
 	transparent_multilins: Vec<FSlice<'_, F, Hal>>,
 	claims: &[PIOPSumcheckClaim<F>],
@@ -298,20 +347,21 @@ where
 		&committed,
 		transcript,
 	)?;
+	merkle_prover.recycle(committed);
 
 	Ok(())
 }
 
 #[allow(clippy::too_many_arguments)]
-fn prove_interleaved_fri_sumcheck<Hal, F, FEncode, P, NTT, MTScheme, MTProver, Challenger_>(
-	hal: &Hal,
-	dev_alloc: &impl ComputeAllocator<F, Hal::DevMem>,
+fn prove_interleaved_fri_sumcheck<'b, Hal, F, FEncode, P, NTT, MTScheme, MTProver, Challenger_>(
+	hal: &'b Hal,
+	dev_alloc: &'b impl ComputeAllocator<F, Hal::DevMem>,
 	n_rounds: usize,
 	fri_params: &FRIParams<F, FEncode>,
 	ntt: &NTT,
 	merkle_prover: &MTProver,
 	sumcheck_provers: Vec<impl SumcheckProver<F>>,
-	codeword: &[P],
+	codeword: &fri::CommittedCodeword<'b, F, P, Hal>,
 	committed: &MTProver::Committed,
 	transcript: &mut ProverTranscript<Challenger_>,
 ) -> Result<(), Error>
@@ -325,7 +375,8 @@ where
 	MTProver: MerkleTreeProver<F, Scheme = MTScheme>,
 	Challenger_: Challenger,
 {
-	let mut fri_prover = FRIFolder::new(hal, fri_params, ntt, merkle_prover, codeword, committed)?;
+	let mut fri_prover =
+		FRIFolder::from_committed(hal, fri_params, ntt, merkle_prover, codeword, committed)?;
 
 	let mut sumcheck_batch_prover = SumcheckBatchProver::new(sumcheck_provers, transcript)?;
 
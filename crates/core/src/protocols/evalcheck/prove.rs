@@ -226,17 +226,30 @@ where
 			.into_iter()
 			.partition(|ep| ep.len().saturating_sub(1) > P::LOG_WIDTH);
 
-		for eval_point in long {
+		let mut new_suffixes = Vec::new();
+		for eval_point in &long {
 			let ep = eval_point.to_vec();
 			let mid = ep.len() / 2;
 			let (low, high) = ep.split_at(mid);
 			let suffix = EvalPoint::from(high);
 			let prefix = EvalPoint::from(low);
 			suffixes.insert(suffix.clone());
-			self.suffixes.insert(suffix);
+			self.suffixes.insert(suffix.clone());
+			new_suffixes.push(suffix);
 			prefixes.insert(prefix);
 		}
 
+		// `make_new_eval_claim` evaluates a point through any suffix that the point ends with,
+		// so a suffix split off one point needs the prefixes of the other points that end with
+		// it as well.
+		for eval_point in chain!(&short, &long) {
+			for suffix in &new_suffixes {
+				if let Some(prefix) = eval_point.try_get_prefix(suffix) {
+					prefixes.insert(prefix);
+				}
+			}
+		}
+
 		let eval_points = chain!(&short, &suffixes, &prefixes)
 			.map(|p| p.as_ref())
 			.collect::<Vec<_>>();
@@ -9,7 +9,7 @@
 //!  * one multilin (the multiplier) is transparent (`shift_ind`, `eq_ind`, or tower basis)
 //!  * other multilin is a projection of one of the evalcheck claim multilins to its first variables
 
-use std::collections::HashSet;
+use std::{collections::HashSet, iter, slice};
 
 use binius_field::{ExtensionField, Field, PackedExtension, PackedField, TowerField};
 use binius_hal::ComputationBackend;
@@ -23,7 +23,10 @@ use bytemuck::zeroed_vec;
 use itertools::izip;
 use tracing::instrument;
 
-use super::{EvalPoint, EvalPointOracleIdMap, error::Error, evalcheck::EvalcheckMultilinearClaim};
+use super::{
+	ConstraintSetEqIndPoint, ConstraintSetsEqIndPoints, EvalPoint, EvalPointOracleIdMap,
+	error::Error, evalcheck::EvalcheckMultilinearClaim,
+};
 use crate::{
 	fiat_shamir::Challenger,
 	oracle::{
@@ -32,7 +35,8 @@ use crate::{
 	},
 	polynomial::MultivariatePoly,
 	protocols::sumcheck::{
-		self, Error as SumcheckError,
+		self, BatchSumcheckOutput, Error as SumcheckError, MLEcheckClaimsWithMeta,
+		eq_ind::{self, ClaimsSortingOrder, reduce_to_regular_sumchecks},
 		prove::{
 			front_loaded,
 			oracles::{
@@ -41,7 +45,7 @@ use crate::{
 			},
 		},
 	},
-	transcript::ProverTranscript,
+	transcript::{ProverTranscript, VerifierTranscript},
 	transparent::{shift_ind::ShiftIndPartialEval, tower_basis::TowerBasis},
 	witness::{MultilinearExtensionIndex, MultilinearWitness},
 };
@@ -585,11 +589,16 @@ where
 	Ok(evalcheck_claims)
 }
 
+/// Proves the MLE-check constraint sets in a single front-loaded batched sumcheck.
+///
+/// The constraint sets may have different numbers of variables and each one its own equality
+/// indicator point. They are batched in ascending order of their number of variables, keeping the
+/// given order among sets with the same number of variables, which
+/// [`verify_mlechecks`] must match.
 #[allow(clippy::too_many_arguments)]
-pub fn prove_mlecheck_with_switchover<'a, F, P, DomainField, Transcript, Backend>(
+pub fn prove_mlechecks_with_switchover<'a, F, P, DomainField, Transcript, Backend>(
 	witness: &MultilinearExtensionIndex<P>,
-	constraint_set: SizedConstraintSet<F>,
-	eq_ind_challenges: EvalPoint<F>,
+	mut mlechecks: Vec<ConstraintSetEqIndPoint<F>>,
 	memoized_data: &mut MemoizedData<'a, P>,
 	transcript: &mut ProverTranscript<Transcript>,
 	switchover_fn: impl Fn(usize) -> usize + 'static,
@@ -605,29 +614,113 @@ where
 	Transcript: Challenger,
 	Backend: ComputationBackend,
 {
-	let MLECheckProverWithMeta { prover, meta } = constraint_sets_mlecheck_prover_meta(
-		EvaluationOrder::HighToLow,
-		constraint_set,
+	mlechecks.sort_by_key(|mlecheck| mlecheck.constraint_set.n_vars);
+
+	let mut provers = Vec::with_capacity(mlechecks.len());
+	let mut metas = Vec::with_capacity(mlechecks.len());
+	for ConstraintSetEqIndPoint {
 		eq_ind_challenges,
-		memoized_data,
-		witness,
-		domain_factory,
-		&switchover_fn,
-		backend,
-	)?;
+		constraint_set,
+	} in mlechecks
+	{
+		let MLECheckProverWithMeta { prover, meta } = constraint_sets_mlecheck_prover_meta(
+			EvaluationOrder::HighToLow,
+			constraint_set,
+			eq_ind_challenges,
+			memoized_data,
+			witness,
+			domain_factory.clone(),
+			&switchover_fn,
+			backend,
+		)?;
+		provers.push(prover);
+		metas.push(meta);
+	}
 
-	let batch_prover = front_loaded::BatchProver::new(vec![prover], transcript)?;
+	let batch_prover = front_loaded::BatchProver::new(provers, transcript)?;
 
 	let mut sumcheck_output = batch_prover.run(transcript)?;
 
 	// Reverse challenges since folding high-to-low
 	sumcheck_output.challenges.reverse();
 
-	// extract eq_ind_eval
-	sumcheck_output.multilinear_evals[0].pop();
+	// extract eq_ind_evals
+	for multilinear_evals in &mut sumcheck_output.multilinear_evals {
+		multilinear_evals.pop();
+	}
 
 	let evalcheck_claims =
-		sumcheck::make_eval_claims(EvaluationOrder::HighToLow, vec![meta], sumcheck_output)?;
+		sumcheck::make_eval_claims(EvaluationOrder::HighToLow, metas, sumcheck_output)?;
+
+	Ok(evalcheck_claims)
+}
+
+/// Verifies the MLE-check constraint sets proven with [`prove_mlechecks_with_switchover`] in a
+/// single front-loaded batched sumcheck.
+///
+/// Every constraint set is reduced with the challenges of the rounds that it takes part in, and
+/// the evaluation of its equality indicator is checked against its own point.
+pub fn verify_mlechecks<F, Transcript>(
+	mlechecks: ConstraintSetsEqIndPoints<F>,
+	transcript: &mut VerifierTranscript<Transcript>,
+) -> Result<SumcheckProofEvalcheckClaims<F>, SumcheckError>
+where
+	F: TowerField,
+	Transcript: Challenger,
+{
+	let ConstraintSetsEqIndPoints {
+		eq_ind_challenges,
+		constraint_sets,
+	} = mlechecks;
+	let mut mlechecks = iter::zip(eq_ind_challenges, constraint_sets).collect::<Vec<_>>();
+	mlechecks.sort_by_key(|(_, constraint_set)| constraint_set.n_vars);
+	let (eq_ind_challenges, constraint_sets): (Vec<_>, Vec<_>) = mlechecks.into_iter().unzip();
+
+	let MLEcheckClaimsWithMeta {
+		claims: mlecheck_claims,
+		metas,
+	} = sumcheck::constraint_set_mlecheck_claims(constraint_sets)?;
+
+	let batch_sumcheck_verifier = sumcheck::front_loaded::BatchVerifier::new(
+		&reduce_to_regular_sumchecks(&mlecheck_claims)?,
+		transcript,
+	)?;
+	let mut sumcheck_output = batch_sumcheck_verifier.run(transcript)?;
+
+	// Reverse challenges since folding high-to-low
+	sumcheck_output.challenges.reverse();
+
+	// Every claim takes part in the rounds of the suffix of the challenges for its number of
+	// variables, which also hold the evaluation point of its equality indicator.
+	let max_n_vars = sumcheck_output.challenges.len();
+	let multilinear_evals =
+		izip!(&mlecheck_claims, &eq_ind_challenges, sumcheck_output.multilinear_evals)
+			.map(|(mlecheck_claim, eq_ind_challenges, multilinear_evals)| {
+				let challenges =
+					&sumcheck_output.challenges[max_n_vars - mlecheck_claim.n_vars()..];
+				let eq_ind_output = eq_ind::verify_sumcheck_outputs(
+					ClaimsSortingOrder::AscendingVars,
+					slice::from_ref(mlecheck_claim),
+					eq_ind_challenges,
+					BatchSumcheckOutput {
+						challenges: challenges.to_vec(),
+						multilinear_evals: vec![multilinear_evals],
+					},
+				)?;
+				let [multilinear_evals] = <[_; 1]>::try_from(eq_ind_output.multilinear_evals)
+					.expect("verify_sumcheck_outputs returns the evaluations of every claim");
+				Ok(multilinear_evals)
+			})
+			.collect::<Result<Vec<_>, SumcheckError>>()?;
+
+	let evalcheck_claims = sumcheck::make_eval_claims(
+		EvaluationOrder::HighToLow,
+		metas,
+		BatchSumcheckOutput {
+			challenges: sumcheck_output.challenges,
+			multilinear_evals,
+		},
+	)?;
 
 	Ok(evalcheck_claims)
 }
@@ -326,16 +326,12 @@ impl<'a, F: TowerField> EvalcheckVerifier<'a, F> {
 		// round claims and return the evaluation. Otherwise, we verify the subclaim recursively.
 		let subproof = deserialize_evalcheck_proof(&mut transcript.message())?;
 		match subproof {
-			EvalcheckHint::DuplicateClaim(index) => {
-				let index = index as usize;
-				if self.round_claims[index].id != oracle_id
-					|| self.round_claims[index].eval_point != eval_point
-				{
-					return Err(VerificationError::DuplicateClaimMismatch.into());
+			EvalcheckHint::DuplicateClaim(index) => match self.round_claims.get(index as usize) {
+				Some(claim) if claim.id == oracle_id && claim.eval_point == eval_point => {
+					Ok(claim.eval)
 				}
-
-				Ok(self.round_claims[index].eval)
-			}
+				_ => Err(VerificationError::DuplicateClaimMismatch.into()),
+			},
 			EvalcheckHint::NewClaim => {
 				let eval = transcript.message().read_scalar()?;
 				let subclaim = EvalcheckMultilinearClaim {
@@ -1,6 +1,6 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use std::marker::PhantomData;
+use std::{iter, marker::PhantomData};
 
 use binius_field::{BinaryField, ExtensionField};
 use binius_ntt::AdditiveNTT;
@@ -30,9 +30,33 @@ where
 	/// The number oracle consistency queries required during the query phase.
 	#[getset(get_copy = "pub")]
 	n_test_queries: usize,
+	/// The depth of the Merkle tree layer that is decommitted for every oracle, if it is not
+	/// chosen by the vector commitment scheme.
+	#[getset(get_copy = "pub")]
+	cap_height: Option<usize>,
+	/// The binary logarithm of the number of codeword values hashed into each Merkle leaf of the
+	/// queried oracles, if it is not the fold arity of the oracle.
+	#[getset(get_copy = "pub")]
+	log_leaf_size: Option<usize>,
+	/// How the decommitted Merkle caps enter the transcript.
+	#[getset(get_copy = "pub")]
+	cap_absorption: MerkleCapAbsorption,
 	_marker: PhantomData<F>,
 }
 
+/// How the Merkle caps of the FRI oracles are written to the transcript.
+///
+/// The verifier checks the caps against the committed roots in either case. Absorbing the caps
+/// binds them into the Fiat-Shamir challenges at the cost of hashing them into the challenger.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MerkleCapAbsorption {
+	/// The caps are written as decommitment advice, which is not observed by the challenger.
+	#[default]
+	Lazy,
+	/// The caps are observed by the challenger before the query indices are sampled.
+	Eager,
+}
+
 impl<F, FA> FRIParams<F, FA>
 where
 	F: BinaryField + ExtensionField<FA>,
@@ -44,8 +68,15 @@ where
 		fold_arities: Vec<usize>,
 		n_test_queries: usize,
 	) -> Result<Self, Error> {
-		if fold_arities.iter().sum::<usize>() >= rs_code.log_dim() + log_batch_size {
-			bail!(Error::InvalidFoldAritySequence)
+		let arities_sum = fold_arities.iter().sum::<usize>();
+		let n_fold_rounds = rs_code.log_dim() + log_batch_size;
+		// A message of a single element is not folded at all, its codeword is the terminal one.
+		let is_unfolded = n_fold_rounds == 0 && fold_arities.is_empty();
+		if arities_sum >= n_fold_rounds && !is_unfolded {
+			bail!(Error::InvalidFoldAritySequence {
+				arities_sum,
+				n_fold_rounds,
+			})
 		}
 
 		Ok(Self {
@@ -53,10 +84,88 @@ where
 			log_batch_size,
 			fold_arities,
 			n_test_queries,
+			cap_height: None,
+			log_leaf_size: None,
+			cap_absorption: MerkleCapAbsorption::default(),
 			_marker: PhantomData,
 		})
 	}
 
+	/// Sets the depth of the Merkle tree layer that is decommitted for every oracle.
+	///
+	/// A higher cap reduces the length of the opening paths, and so the hashing done by the
+	/// verifier per query, at the cost of sending more digests in the proof. By default the depth
+	/// is chosen by [`MerkleTreeScheme::optimal_verify_layer`] to minimize the proof size.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::CapHeightTooLarge`] if a tree committed during the protocol is shallower than the
+	///   cap
+	pub fn with_cap_height(mut self, cap_height: usize) -> Result<Self, Error> {
+		if let Some(tree_depth) = self
+			.oracle_tree_depths()
+			.find(|&tree_depth| tree_depth < cap_height)
+		{
+			bail!(Error::CapHeightTooLarge {
+				cap_height,
+				tree_depth
+			});
+		}
+		self.cap_height = Some(cap_height);
+		Ok(self)
+	}
+
+	/// Sets the number of codeword values hashed into each Merkle leaf of the queried oracles.
+	///
+	/// By default every leaf holds exactly the coset of values that a query folds in the next
+	/// round, so the leaf size of an oracle is its fold arity. A larger leaf bundles several
+	/// adjacent cosets, which makes the trees shallower and so saves hashing in the commitment and
+	/// in the opening paths, at the cost of sending more values per query.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::LeafSizeSmallerThanArity`] if a leaf cannot hold a whole folding coset
+	/// * [`Error::LeafSizeExceedsOracle`] if an oracle has fewer values than a leaf
+	/// * [`Error::CapHeightTooLarge`] if a tree becomes shallower than the configured cap
+	pub fn with_log_leaf_size(mut self, log_leaf_size: usize) -> Result<Self, Error> {
+		if let Some((index, &arity)) = self
+			.fold_arities
+			.iter()
+			.enumerate()
+			.find(|&(_, &arity)| arity > log_leaf_size)
+		{
+			bail!(Error::LeafSizeSmallerThanArity {
+				log_leaf_size,
+				index,
+				arity
+			});
+		}
+		// The last queried oracle is the shortest one.
+		if let Some(log_oracle_len) = self
+			.oracle_log_lens()
+			.last()
+			.filter(|&log_oracle_len| log_oracle_len < log_leaf_size)
+		{
+			bail!(Error::LeafSizeExceedsOracle {
+				log_leaf_size,
+				log_oracle_len
+			});
+		}
+
+		self.log_leaf_size = Some(log_leaf_size);
+		// The trees are shallower now, so the cap must be checked again.
+		match self.cap_height {
+			Some(cap_height) => self.with_cap_height(cap_height),
+			None => Ok(self),
+		}
+	}
+
+	/// Sets how the decommitted Merkle caps enter the transcript.
+	pub fn with_cap_absorption(mut self, cap_absorption: MerkleCapAbsorption) -> Self {
+		self.cap_absorption = cap_absorption;
+		self
+	}
+
 	/// Choose commit parameters based on protocol parameters, using a constant fold arity.
 	///
 	/// ## Arguments
@@ -168,9 +277,35 @@ where
 	pub fn log_len(&self) -> usize {
 		self.rs_code().log_len() + self.log_batch_size()
 	}
+
+	/// The binary logarithms of the lengths of the oracles opened in the query phase.
+	fn oracle_log_lens(&self) -> impl Iterator<Item = usize> + '_ {
+		self.fold_arities
+			.iter()
+			.scan(self.log_len(), |log_oracle_len, arity| {
+				let log_len = *log_oracle_len;
+				*log_oracle_len -= arity;
+				Some(log_len)
+			})
+	}
+
+	/// The binary logarithms of the Merkle leaf sizes of the oracles opened in the query phase.
+	pub fn oracle_log_leaf_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+		self.fold_arities
+			.iter()
+			.map(|&arity| self.log_leaf_size.unwrap_or(arity))
+	}
+
+	/// The depths of the Merkle trees committing the oracles opened in the query phase.
+	pub fn oracle_tree_depths(&self) -> impl Iterator<Item = usize> + '_ {
+		iter::zip(self.oracle_log_lens(), self.oracle_log_leaf_sizes())
+			.map(|(log_oracle_len, log_leaf_size)| log_oracle_len - log_leaf_size)
+	}
 }
 
-/// This layer allows minimizing the proof size.
+/// The depths of the decommitted Merkle tree layers of the oracles.
+///
+/// Unless a cap height is set in the parameters, the layers minimize the proof size.
 pub fn vcs_optimal_layers_depths_iter<'a, F, FA, VCS>(
 	fri_params: &'a FRIParams<F, FA>,
 	vcs: &'a VCS,
@@ -181,11 +316,10 @@ where
 	FA: BinaryField,
 {
 	fri_params
-		.fold_arities()
-		.iter()
-		.scan(fri_params.log_len(), |log_n_cosets, arity| {
-			*log_n_cosets -= arity;
-			Some(vcs.optimal_verify_layer(fri_params.n_test_queries(), *log_n_cosets))
+		.oracle_tree_depths()
+		.map(move |tree_depth| match fri_params.cap_height() {
+			Some(cap_height) => cap_height,
+			None => vcs.optimal_verify_layer(fri_params.n_test_queries(), tree_depth),
 		})
 }
 
@@ -194,8 +328,8 @@ pub type TerminateCodeword<F> = Vec<F>;
 
 /// Calculates the number of test queries required to achieve a target security level.
 ///
-/// Throws [`Error::ParameterError`] if the security level is unattainable given the code
-/// parameters.
+/// Throws [`Error::SecurityTargetUnattainable`] if the security level is unattainable given the
+/// code parameters.
 pub fn calculate_n_test_queries<F, FEncode>(
 	security_bits: usize,
 	code: &ReedSolomonCode<FEncode>,
@@ -212,7 +346,11 @@ where
 	let per_query_err = 0.5 * (1f64 + 2.0f64.powi(-(code.log_inv_rate() as i32)));
 	let allowed_query_err = 2.0_f64.powi(-(security_bits as i32)) - sumcheck_err - folding_err;
 	if allowed_query_err <= 0.0 {
-		return Err(Error::ParameterError);
+		return Err(Error::SecurityTargetUnattainable {
+			security_bits,
+			log_dim: code.log_dim(),
+			log_inv_rate: code.log_inv_rate(),
+		});
 	}
 	let n_queries = allowed_query_err.log(per_query_err).ceil() as usize;
 	Ok(n_queries)
@@ -280,8 +418,26 @@ mod tests {
 		let rs_code = ReedSolomonCode::<BinaryField32b>::new(28, 1).unwrap();
 		assert_matches!(
 			calculate_n_test_queries::<BinaryField128b, _>(security_bits, &rs_code),
-			Err(Error::ParameterError)
+			Err(Error::SecurityTargetUnattainable {
+				security_bits: 128,
+				log_dim: 28,
+				log_inv_rate: 1,
+			})
+		);
+	}
+
+	#[test]
+	fn test_new_rejects_folding_single_element_message() {
+		let rs_code = ReedSolomonCode::<BinaryField32b>::new(0, 1).unwrap();
+		assert_matches!(
+			FRIParams::<BinaryField128b, _>::new(rs_code, 0, vec![1], 1),
+			Err(Error::InvalidFoldAritySequence {
+				arities_sum: 1,
+				n_fold_rounds: 0,
+			})
 		);
+		let rs_code = ReedSolomonCode::<BinaryField32b>::new(0, 1).unwrap();
+		assert!(FRIParams::<BinaryField128b, _>::new(rs_code, 0, vec![], 1).is_ok());
 	}
 
 	#[test]
@@ -1,23 +1,63 @@
 // Copyright 2024-2025 Irreducible Inc.
 
 use binius_ntt::Error as NttError;
+use binius_utils::error_code::{ErrorCode, ErrorDomain, HasErrorCode};
 
 use crate::{reed_solomon, transcript};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-	#[error("cannot calculate parameters satisfying the security target")]
-	ParameterError,
-	#[error("conflicting or incorrect constructor argument: {0}")]
-	InvalidArgs(String),
+	#[error(
+		"cannot attain {security_bits} bits of security with log dimension {log_dim} and log \
+		inverse rate {log_inv_rate}"
+	)]
+	SecurityTargetUnattainable {
+		security_bits: usize,
+		log_dim: usize,
+		log_inv_rate: usize,
+	},
+	#[error("got {actual} round commitments, expected {expected}")]
+	IncorrectRoundCommitmentCount { expected: usize, actual: usize },
+	#[error("got {actual} folding challenges, expected {expected}")]
+	IncorrectFoldChallengeCount { expected: usize, actual: usize },
+	#[error("interleaved message has {actual} elements, the code parameters require {expected}")]
+	IncorrectMessageLength { expected: usize, actual: usize },
+	#[error("codeword has {actual} elements, the code parameters require at least {expected}")]
+	CodewordTooShort { expected: usize, actual: usize },
 	#[error("FRI message dimension is too small")]
 	MessageDimensionIsTooSmall,
-	#[error("fold arities total exceeds the number of fold rounds")]
-	InvalidFoldAritySequence,
+	#[error(
+		"fold arities total {arities_sum} must be less than the number of fold rounds {n_fold_rounds}"
+	)]
+	InvalidFoldAritySequence {
+		arities_sum: usize,
+		n_fold_rounds: usize,
+	},
 	#[error("fold arity at index {index} in sequence is zero")]
 	FoldArityIsZero { index: usize },
 	#[error("the fold arity for the first fold should be at least the log batch size")]
 	FirstFoldArityTooSmall,
+	#[error("Merkle cap height {cap_height} exceeds the depth {tree_depth} of a committed tree")]
+	CapHeightTooLarge {
+		cap_height: usize,
+		tree_depth: usize,
+	},
+	#[error(
+		"Merkle leaf size 2^{log_leaf_size} is smaller than the coset of fold arity {arity} at \
+		index {index}"
+	)]
+	LeafSizeSmallerThanArity {
+		log_leaf_size: usize,
+		index: usize,
+		arity: usize,
+	},
+	#[error("Merkle leaf size 2^{log_leaf_size} exceeds the oracle length 2^{log_oracle_len}")]
+	LeafSizeExceedsOracle {
+		log_leaf_size: usize,
+		log_oracle_len: usize,
+	},
+	#[error("the message of {n_elems} elements does not fill a packed element of width {width}")]
+	MessageSmallerThanPackedWidth { n_elems: usize, width: usize },
 	#[error("attempted to fold more than maximum of {max_folds} times")]
 	TooManyFoldExecutions { max_folds: usize },
 	#[error("attempted to finish prover before executing all fold rounds")]
@@ -57,3 +97,48 @@ pub enum VerificationError {
 	#[error("The dimension-1 codeword must contain the same values")]
 	IncorrectDegree,
 }
+
+impl HasErrorCode for Error {
+	fn error_code(&self) -> ErrorCode {
+		let index = match self {
+			Self::SecurityTargetUnattainable { .. } => 1,
+			Self::IncorrectRoundCommitmentCount { .. } => 2,
+			Self::IncorrectFoldChallengeCount { .. } => 3,
+			Self::IncorrectMessageLength { .. } => 4,
+			Self::CodewordTooShort { .. } => 5,
+			Self::MessageDimensionIsTooSmall => 6,
+			Self::InvalidFoldAritySequence { .. } => 7,
+			Self::FoldArityIsZero { .. } => 8,
+			Self::FirstFoldArityTooSmall => 9,
+			Self::CapHeightTooLarge { .. } => 10,
+			Self::TooManyFoldExecutions { .. } => 11,
+			Self::EarlyProverFinish => 12,
+			Self::RoundVCSLengthsNotDescending => 13,
+			Self::RoundVCSLengthsOutOfRange => 14,
+			Self::RoundVCSLengthsNotPowerOfTwo => 15,
+			Self::CodeError(_) => 16,
+			Self::EncodeError(_) => 17,
+			Self::VectorCommit(_) => 18,
+			Self::Verification(err) => return err.error_code(),
+			Self::TranscriptError(_) => 19,
+			Self::AllocationError(_) => 20,
+			Self::ComputeError(_) => 21,
+			Self::LeafSizeSmallerThanArity { .. } => 22,
+			Self::LeafSizeExceedsOracle { .. } => 23,
+			Self::MessageSmallerThanPackedWidth { .. } => 24,
+		};
+		ErrorCode::new(ErrorDomain::Fri, index)
+	}
+}
+
+impl HasErrorCode for VerificationError {
+	fn error_code(&self) -> ErrorCode {
+		let index = match self {
+			Self::IncorrectFold { .. } => 101,
+			Self::IncorrectQueryProofLength { .. } => 102,
+			Self::IncorrectQueryProofValuesLength { .. } => 103,
+			Self::IncorrectDegree => 104,
+		};
+		ErrorCode::new(ErrorDomain::Fri, index)
+	}
+}
@@ -1,14 +1,16 @@
 // Copyright 2024-2025 Irreducible Inc.
+use std::borrow::Cow;
+
 use binius_compute::{
-	ComputeLayerExecutor,
+	ComputeLayerExecutor, FSlice, FSliceMut,
 	alloc::ComputeAllocator,
 	layer::ComputeLayer,
 	memory::{ComputeMemory, SizedSlice},
 };
 use binius_field::{
 	BinaryField, ExtensionField, PackedExtension, PackedField, TowerField,
-	packed::{iter_packed_slice_with_offset, len_packed_slice},
-	unpack_if_possible,
+	packed::{iter_packed_slice_with_offset, len_packed_slice, set_packed_slice},
+	unpack_if_possible, unpack_if_possible_mut,
 };
 use binius_maybe_rayon::prelude::*;
 use binius_ntt::AdditiveNTT;
@@ -20,7 +22,7 @@ use tracing::instrument;
 
 use super::{
 	TerminateCodeword,
-	common::{FRIParams, vcs_optimal_layers_depths_iter},
+	common::{FRIParams, MerkleCapAbsorption, vcs_optimal_layers_depths_iter},
 	error::Error,
 	logging::{MerkleTreeDimensionData, RSEncodeDimensionData, SortAndMergeDimensionData},
 };
@@ -99,9 +101,10 @@ where
 {
 	let n_elems = rs_code.dim() << params.log_batch_size();
 	if message.len() * P::WIDTH != n_elems {
-		bail!(Error::InvalidArgs(
-			"interleaved message length does not match code parameters".to_string()
-		));
+		bail!(Error::IncorrectMessageLength {
+			expected: n_elems,
+			actual: message.len() * P::WIDTH,
+		});
 	}
 
 	commit_interleaved_with(params, ntt, merkle_prover, move |buffer| {
@@ -161,10 +164,220 @@ where
 	)
 	.in_scope(|| rs_code.encode_ext_batch_inplace(ntt, &mut encoded, log_batch_size))?;
 
-	// Take the first arity as coset_log_len, or use the value such that the number of leaves equals
-	// 1 << log_inv_rate if arities is empty
-	let coset_log_len = params.fold_arities().first().copied().unwrap_or(log_elems);
+	let (commitment, committed) = commit_codeword(params, merkle_prover, &encoded)?;
+	Ok(CommitOutput {
+		commitment,
+		committed,
+		codeword: encoded,
+	})
+}
+
+/// The output of [`commit_interleaved_on_device`].
+pub struct DeviceCommitOutput<'b, F, VCSCommitment, VCSCommitted, Hal>
+where
+	F: BinaryField,
+	Hal: ComputeLayer<F>,
+{
+	pub commitment: VCSCommitment,
+	pub committed: VCSCommitted,
+	/// The codeword, which remains resident in device memory.
+	pub device_codeword: FSlice<'b, F, Hal>,
+}
+
+/// A committed codeword, held either in host memory or resident in device memory.
+pub enum CommittedCodeword<'b, F, P, Hal>
+where
+	F: BinaryField,
+	Hal: ComputeLayer<F>,
+{
+	/// A codeword returned by [`commit_interleaved`] or [`commit_interleaved_with`].
+	Host(Vec<P>),
+	/// A codeword returned by [`commit_interleaved_on_device`].
+	Device(FSlice<'b, F, Hal>),
+}
+
+/// Encodes and commits the input message on a compute device.
+///
+/// This is the device-resident counterpart of [`commit_interleaved_with`]. The message is uploaded
+/// once and Reed–Solomon encoded with [`ComputeLayerExecutor::rs_encode`], and the Merkle tree
+/// leaves are hashed on the device with [`MerkleTreeProver::commit_on_device`]. The codeword is
+/// not copied back to the host, it stays in device memory for the FRI fold rounds of
+/// [`FRIFolder::new_on_device`], which copies it to the host only for the query phase.
+///
+/// ## Arguments
+///
+/// * `hal` - the compute layer that encodes the message
+/// * `allocator` - the device allocator for the message and codeword buffers
+/// * `params` - common FRI protocol parameters.
+/// * `merkle_prover` - the Merkle tree prover to use for committing
+/// * `message_writer` - a closure that writes the interleaved message to encode and commit
+///
+/// ## Throws
+///
+/// * [`Error::MessageSmallerThanPackedWidth`] if the message does not fill a packed element
+#[allow(clippy::type_complexity)]
+pub fn commit_interleaved_on_device<'b, F, FA, P, PA, NTT, MerkleProver, VCS, Hal>(
+	hal: &Hal,
+	allocator: &'b impl ComputeAllocator<F, Hal::DevMem>,
+	params: &FRIParams<F, FA>,
+	ntt: &NTT,
+	merkle_prover: &MerkleProver,
+	message_writer: impl FnOnce(&mut [P]),
+) -> Result<DeviceCommitOutput<'b, F, VCS::Digest, MerkleProver::Committed, Hal>, Error>
+where
+	F: TowerField + ExtensionField<FA> + PackedExtension<FA>,
+	FA: BinaryField,
+	P: PackedField<Scalar = F> + PackedExtension<FA, PackedSubfield = PA>,
+	PA: PackedField<Scalar = FA>,
+	NTT: AdditiveNTT<FA> + Sync,
+	MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+	VCS: MerkleTreeScheme<F>,
+	Hal: ComputeLayer<F>,
+{
+	let rs_code = params.rs_code();
+	let log_batch_size = params.log_batch_size();
+	let log_elems = rs_code.log_dim() + log_batch_size;
+	if log_elems < P::LOG_WIDTH {
+		bail!(Error::MessageSmallerThanPackedWidth {
+			n_elems: 1 << log_elems,
+			width: P::WIDTH,
+		});
+	}
+
+	let mut message = zeroed_vec::<P>(1 << (log_elems - P::LOG_WIDTH));
+	let dimensions_data = SortAndMergeDimensionData::new::<F>(log_elems);
+	tracing::debug_span!(
+		"[task] Sort & Merge",
+		phase = "commit",
+		perfetto_category = "task.main",
+		?dimensions_data
+	)
+	.in_scope(|| message_writer(&mut message));
+
+	let device_message = upload_codeword(hal, allocator, &message)?;
+	drop(message);
+
+	let mut device_codeword = allocator.alloc(1 << (log_elems + rs_code.log_inv_rate()))?;
+	let dimensions_data = RSEncodeDimensionData::new::<F>(log_elems, log_batch_size);
+	tracing::debug_span!(
+		"[task] RS Encode",
+		phase = "commit",
+		perfetto_category = "task.main",
+		?dimensions_data
+	)
+	.in_scope(|| {
+		hal.execute(|exec| {
+			exec.rs_encode::<FA>(
+				ntt,
+				rs_code.log_dim(),
+				rs_code.log_inv_rate(),
+				log_batch_size,
+				Hal::DevMem::as_const(&device_message),
+				&mut device_codeword,
+			)?;
+			Ok(vec![])
+		})
+	})?;
+	let device_codeword = Hal::DevMem::to_const(device_codeword);
+
+	let coset_log_len = codeword_coset_log_len(params);
+	let log_len = params.log_len() - coset_log_len;
+	let dimension_data = MerkleTreeDimensionData::new::<F>(log_len, 1 << coset_log_len);
+	let (commitment, committed) = tracing::debug_span!(
+		"[task] Merkle Tree",
+		phase = "commit",
+		perfetto_category = "task.main",
+		dimensions_data = ?dimension_data
+	)
+	.in_scope(|| merkle_prover.commit_on_device(hal, device_codeword, log_len))
+	.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+
+	Ok(DeviceCommitOutput {
+		commitment: commitment.root,
+		committed,
+		device_codeword,
+	})
+}
+
+/// Copies a packed host buffer into a new device buffer.
+fn upload_codeword<'b, F, P, Hal>(
+	hal: &Hal,
+	allocator: &'b impl ComputeAllocator<F, Hal::DevMem>,
+	codeword: &[P],
+) -> Result<FSliceMut<'b, F, Hal>, Error>
+where
+	F: BinaryField,
+	P: PackedField<Scalar = F>,
+	Hal: ComputeLayer<F>,
+{
+	let mut device_codeword = allocator.alloc(len_packed_slice(codeword))?;
+	let unpacked_scalars = unpack_if_possible(
+		codeword,
+		|scalars| {
+			hal.copy_h2d(scalars, &mut device_codeword)?;
+			Ok::<_, Error>(None)
+		},
+		|packed| Ok(Some(PackedField::iter_slice(packed).collect::<Vec<_>>())),
+	)?;
+	if let Some(scalars) = unpacked_scalars {
+		hal.copy_h2d(&scalars, &mut device_codeword)?;
+	}
+	Ok(device_codeword)
+}
+
+/// Copies a device buffer into a new packed host buffer.
+fn download_codeword<F, P, Hal>(
+	hal: &Hal,
+	device_codeword: FSlice<'_, F, Hal>,
+) -> Result<Vec<P>, Error>
+where
+	F: BinaryField,
+	P: PackedField<Scalar = F>,
+	Hal: ComputeLayer<F>,
+{
+	let len = device_codeword.len();
+	let mut codeword = zeroed_vec::<P>(len.div_ceil(P::WIDTH));
+	unpack_if_possible_mut(
+		&mut codeword,
+		|scalars| hal.copy_d2h(device_codeword, &mut scalars[..len]),
+		|packed| {
+			let mut scalars = vec![F::ZERO; len];
+			hal.copy_d2h(device_codeword, &mut scalars)?;
+			for (i, scalar) in scalars.into_iter().enumerate() {
+				set_packed_slice(packed, i, scalar);
+			}
+			Ok(())
+		},
+	)?;
+	Ok(codeword)
+}
+
+/// The base-2 logarithm of the number of codeword elements in a leaf of the codeword's Merkle tree.
+fn codeword_coset_log_len<F, FA>(params: &FRIParams<F, FA>) -> usize
+where
+	F: BinaryField + ExtensionField<FA>,
+	FA: BinaryField,
+{
+	// Take the first leaf size as coset_log_len, or use the value such that the number of leaves
+	// equals 1 << log_inv_rate if arities is empty
+	let log_elems = params.rs_code().log_dim() + params.log_batch_size();
+	params.oracle_log_leaf_sizes().next().unwrap_or(log_elems)
+}
 
+/// Builds the Merkle tree over the cosets of an encoded codeword and returns its root.
+fn commit_codeword<F, FA, P, MerkleProver, VCS>(
+	params: &FRIParams<F, FA>,
+	merkle_prover: &MerkleProver,
+	encoded: &[P],
+) -> Result<(VCS::Digest, MerkleProver::Committed), Error>
+where
+	F: BinaryField + ExtensionField<FA>,
+	FA: BinaryField,
+	P: PackedField<Scalar = F>,
+	MerkleProver: MerkleTreeProver<F, Scheme = VCS>,
+	VCS: MerkleTreeScheme<F>,
+{
+	let coset_log_len = codeword_coset_log_len(params);
 	let log_len = params.log_len() - coset_log_len;
 	let dimension_data = MerkleTreeDimensionData::new::<F>(log_len, 1 << coset_log_len);
 	let merkle_tree_span = tracing::debug_span!(
@@ -175,13 +388,13 @@ where
 	)
 	.entered();
 	let (commitment, vcs_committed) = if coset_log_len > P::LOG_WIDTH {
-		let iterated_big_chunks = to_par_scalar_big_chunks(&encoded, 1 << coset_log_len);
+		let iterated_big_chunks = to_par_scalar_big_chunks(encoded, 1 << coset_log_len);
 
 		merkle_prover
 			.commit_iterated(iterated_big_chunks, log_len)
 			.map_err(|err| Error::VectorCommit(Box::new(err)))?
 	} else {
-		let iterated_small_chunks = to_par_scalar_small_chunks(&encoded, 1 << coset_log_len);
+		let iterated_small_chunks = to_par_scalar_small_chunks(encoded, 1 << coset_log_len);
 
 		merkle_prover
 			.commit_iterated(iterated_small_chunks, log_len)
@@ -189,11 +402,7 @@ where
 	};
 	drop(merkle_tree_span);
 
-	Ok(CommitOutput {
-		commitment: commitment.root,
-		committed: vcs_committed,
-		codeword: encoded,
-	})
+	Ok((commitment.root, vcs_committed))
 }
 
 pub enum FoldRoundOutput<VCSCommitment> {
@@ -210,12 +419,38 @@ where
 {
 	/// The folded codeword on the host
 	pub host_codeword: Vec<F>,
-	/// The folded codeword on the device
-	pub device_codeword: <Hal::DevMem as ComputeMemory<F>>::FSliceMut<'b>,
+	/// The folded codeword on the device, until the next fold round consumes it
+	pub device_codeword: Option<<Hal::DevMem as ComputeMemory<F>>::FSliceMut<'b>>,
 	/// The Merkle tree commitment
 	pub committed: MerkleProver::Committed,
 }
 
+/// The codeword of a [`FRIFolder`], before the first fold.
+enum FolderCodeword<'a, 'b, F, P, Hal>
+where
+	F: BinaryField,
+	Hal: ComputeLayer<F>,
+{
+	/// A codeword in host memory, which the first fold round uploads to the device.
+	Host(&'a [P]),
+	/// A codeword that is resident in device memory.
+	Device(FSlice<'b, F, Hal>),
+}
+
+impl<F, P, Hal> FolderCodeword<'_, '_, F, P, Hal>
+where
+	F: BinaryField,
+	P: PackedField<Scalar = F>,
+	Hal: ComputeLayer<F>,
+{
+	fn len(&self) -> usize {
+		match self {
+			Self::Host(codeword) => len_packed_slice(codeword),
+			Self::Device(device_codeword) => device_codeword.len(),
+		}
+	}
+}
+
 pub struct FRIFolder<'a, 'b, F, FA, P, NTT, MerkleProver, VCS, Hal>
 where
 	FA: BinaryField,
@@ -228,7 +463,7 @@ where
 	params: &'a FRIParams<F, FA>,
 	ntt: &'a NTT,
 	merkle_prover: &'a MerkleProver,
-	codeword: &'a [P],
+	codeword: FolderCodeword<'a, 'b, F, P, Hal>,
 	codeword_committed: &'a MerkleProver::Committed,
 	round_committed: Vec<SingleRoundCommitted<'b, F, Hal, MerkleProver>>,
 	curr_round: usize,
@@ -257,10 +492,71 @@ where
 		codeword: &'a [P],
 		committed: &'a MerkleProver::Committed,
 	) -> Result<Self, Error> {
-		if len_packed_slice(codeword) < 1 << params.log_len() {
-			bail!(Error::InvalidArgs(
-				"Reed–Solomon code length must match interleaved codeword length".to_string(),
-			));
+		Self::with_codeword(
+			hal,
+			params,
+			ntt,
+			merkle_prover,
+			FolderCodeword::Host(codeword),
+			committed,
+		)
+	}
+
+	/// Constructs a new folder over a codeword that is resident in device memory.
+	///
+	/// The fold rounds read the codeword from the device, such as the one returned by
+	/// [`commit_interleaved_on_device`], and it is copied to the host only by
+	/// [`Self::finalize`], for the query phase.
+	pub fn new_on_device(
+		hal: &'b Hal,
+		params: &'a FRIParams<F, FA>,
+		ntt: &'a NTT,
+		merkle_prover: &'a MerkleProver,
+		device_codeword: FSlice<'b, F, Hal>,
+		committed: &'a MerkleProver::Committed,
+	) -> Result<Self, Error> {
+		Self::with_codeword(
+			hal,
+			params,
+			ntt,
+			merkle_prover,
+			FolderCodeword::Device(device_codeword),
+			committed,
+		)
+	}
+
+	/// Constructs a new folder over a codeword committed on the host or on the device.
+	pub fn from_committed(
+		hal: &'b Hal,
+		params: &'a FRIParams<F, FA>,
+		ntt: &'a NTT,
+		merkle_prover: &'a MerkleProver,
+		codeword: &'a CommittedCodeword<'b, F, P, Hal>,
+		committed: &'a MerkleProver::Committed,
+	) -> Result<Self, Error> {
+		match codeword {
+			CommittedCodeword::Host(codeword) => {
+				Self::new(hal, params, ntt, merkle_prover, codeword, committed)
+			}
+			CommittedCodeword::Device(device_codeword) => {
+				Self::new_on_device(hal, params, ntt, merkle_prover, *device_codeword, committed)
+			}
+		}
+	}
+
+	fn with_codeword(
+		hal: &'b Hal,
+		params: &'a FRIParams<F, FA>,
+		ntt: &'a NTT,
+		merkle_prover: &'a MerkleProver,
+		codeword: FolderCodeword<'a, 'b, F, P, Hal>,
+		committed: &'a MerkleProver::Committed,
+	) -> Result<Self, Error> {
+		if codeword.len() < 1 << params.log_len() {
+			bail!(Error::CodewordTooShort {
+				expected: 1 << params.log_len(),
+				actual: codeword.len(),
+			});
 		}
 
 		let next_commit_round = params.fold_arities().first().copied();
@@ -292,7 +588,7 @@ where
 	pub fn current_codeword_len(&self) -> usize {
 		match self.round_committed.last() {
 			Some(round) => round.host_codeword.len(),
-			None => len_packed_slice(self.codeword),
+			None => self.codeword.len(),
 		}
 	}
 
@@ -339,37 +635,59 @@ where
 		)
 		.entered();
 		// Fold the last codeword with the accumulated folding challenges.
-		let folded_codeword = match self.round_committed.last() {
+		let folded_codeword = match self.round_committed.last_mut() {
 			Some(prev_round) => {
 				// Fold a full codeword committed in the previous FRI round into a codeword with
 				// reduced dimension and rate.
-				let mut folded_codeword = allocator.alloc(
-					prev_round.device_codeword.len() / (1 << self.unprocessed_challenges.len()),
-				)?;
-
-				self.cl.execute(|exec| {
-					exec.fri_fold(
-						self.ntt,
-						log2_strict_usize(prev_round.device_codeword.len()),
-						0,
-						&self.unprocessed_challenges,
-						Hal::DevMem::as_const(&prev_round.device_codeword),
-						&mut folded_codeword,
-					)?;
-
-					Ok(vec![])
-				})?;
-
-				folded_codeword
+				let prev_codeword = prev_round
+					.device_codeword
+					.take()
+					.expect("the codeword of the last round is only folded once");
+				let log_len = log2_strict_usize(prev_codeword.len());
+				let folded_len = prev_codeword.len() >> self.unprocessed_challenges.len();
+
+				// The previous codeword is not read after this fold, so it is folded in place,
+				// unless the folded codeword is too short to be a slice of device memory.
+				if folded_len % <Hal::DevMem as ComputeMemory<F>>::ALIGNMENT == 0 {
+					let mut folded_codeword = None;
+					self.cl.execute(|exec| {
+						folded_codeword = Some(exec.fri_fold_inplace(
+							self.ntt,
+							log_len,
+							0,
+							&self.unprocessed_challenges,
+							prev_codeword,
+						)?);
+
+						Ok(vec![])
+					})?;
+
+					folded_codeword.expect("execute runs the closure")
+				} else {
+					let mut folded_codeword = allocator.alloc(folded_len)?;
+					self.cl.execute(|exec| {
+						exec.fri_fold(
+							self.ntt,
+							log_len,
+							0,
+							&self.unprocessed_challenges,
+							Hal::DevMem::as_const(&prev_codeword),
+							&mut folded_codeword,
+						)?;
+
+						Ok(vec![])
+					})?;
+
+					folded_codeword
+				}
 			}
 			None => {
-				let codeword_len = len_packed_slice(self.codeword);
-				let mut original_codeword = allocator.alloc(codeword_len)?;
-				unpack_if_possible(
-					self.codeword,
-					|scalars| self.cl.copy_h2d(scalars, &mut original_codeword),
-					|_packed| unimplemented!("non-dense packed fields not supported"),
-				)?;
+				let original_codeword = match self.codeword {
+					FolderCodeword::Host(codeword) => {
+						Hal::DevMem::to_const(upload_codeword(self.cl, allocator, codeword)?)
+					}
+					FolderCodeword::Device(device_codeword) => device_codeword,
+				};
 				let mut folded_codeword = allocator.alloc(
 					1 << (self.params.rs_code().log_len()
 						- (self.unprocessed_challenges.len() - self.params.log_batch_size())),
@@ -380,7 +698,7 @@ where
 						self.params.rs_code().log_len(),
 						self.params.log_batch_size(),
 						&self.unprocessed_challenges,
-						Hal::DevMem::as_const(&original_codeword),
+						original_codeword,
 						&mut folded_codeword,
 					)?;
 
@@ -397,11 +715,11 @@ where
 		self.cl
 			.copy_d2h(Hal::DevMem::as_const(&folded_codeword), &mut folded_codeword_host)?;
 
-		// take the first arity as coset_log_len, or use inv_rate if arities are empty
+		// take the leaf size of the next queried oracle, or the final fold if this is the last one
 		let coset_size = self
 			.params
-			.fold_arities()
-			.get(self.round_committed.len() + 1)
+			.oracle_log_leaf_sizes()
+			.nth(self.round_committed.len() + 1)
 			.map(|log| 1 << log)
 			.unwrap_or_else(|| 1 << self.params.n_final_challenges());
 		let dimension_data =
@@ -421,7 +739,7 @@ where
 
 		self.round_committed.push(SingleRoundCommitted {
 			host_codeword: folded_codeword_host,
-			device_codeword: folded_codeword,
+			device_codeword: Some(folded_codeword),
 			committed,
 		});
 
@@ -448,17 +766,29 @@ where
 			bail!(Error::EarlyProverFinish);
 		}
 
-		let terminate_codeword = self
-			.round_committed
-			.last()
-			.map(|round| round.host_codeword.clone())
-			.unwrap_or_else(|| PackedField::iter_slice(self.codeword).collect());
+		let codeword = match self.codeword {
+			FolderCodeword::Host(codeword) => Cow::Borrowed(codeword),
+			FolderCodeword::Device(device_codeword) => {
+				let _span = tracing::debug_span!(
+					"[task] Download Codeword",
+					phase = "piop_compiler",
+					perfetto_category = "task.main",
+				)
+				.entered();
+				Cow::Owned(download_codeword(self.cl, device_codeword)?)
+			}
+		};
+		let terminate_codeword = match self.round_committed.last() {
+			Some(round) => round.host_codeword.clone(),
+			None => iter_packed_slice_with_offset(&codeword, 0)
+				.take(1 << self.params.log_len())
+				.collect(),
+		};
 
 		self.unprocessed_challenges.clear();
 
 		let Self {
 			params,
-			codeword,
 			codeword_committed,
 			round_committed,
 			merkle_prover,
@@ -492,8 +822,18 @@ where
 		advice.write_scalar_slice(&terminate_codeword);
 
 		let layers = query_prover.vcs_optimal_layers()?;
-		for layer in layers {
-			advice.write_slice(&layer);
+		match query_prover.params.cap_absorption() {
+			MerkleCapAbsorption::Lazy => {
+				for layer in layers {
+					advice.write_slice(&layer);
+				}
+			}
+			MerkleCapAbsorption::Eager => {
+				let mut message = transcript.message();
+				for layer in layers {
+					message.write_slice(&layer);
+				}
+			}
 		}
 
 		let params = query_prover.params;
@@ -503,6 +843,11 @@ where
 			query_prover.prove_query(index, transcript.decommitment())?;
 		}
 
+		let merkle_prover = query_prover.merkle_prover;
+		for (_, committed) in query_prover.round_committed {
+			merkle_prover.recycle(committed);
+		}
+
 		Ok(())
 	}
 }
@@ -517,7 +862,7 @@ where
 	VCS: MerkleTreeScheme<F>,
 {
 	params: &'a FRIParams<F, FA>,
-	codeword: &'a [P],
+	codeword: Cow<'a, [P]>,
 	codeword_committed: &'a MerkleProver::Committed,
 	round_committed: Vec<(Vec<F>, MerkleProver::Committed)>,
 	merkle_prover: &'a MerkleProver,
@@ -550,14 +895,13 @@ where
 	where
 		B: BufMut,
 	{
-		let mut arities_and_optimal_layers_depths = self
-			.params
-			.fold_arities()
-			.iter()
-			.copied()
-			.zip(vcs_optimal_layers_depths_iter(self.params, self.merkle_prover.scheme()));
+		let mut arities_and_optimal_layers_depths = izip!(
+			self.params.fold_arities().iter().copied(),
+			self.params.oracle_log_leaf_sizes(),
+			vcs_optimal_layers_depths_iter(self.params, self.merkle_prover.scheme())
+		);
 
-		let Some((first_fold_arity, first_optimal_layer_depth)) =
+		let Some((first_fold_arity, first_log_leaf_size, first_optimal_layer_depth)) =
 			arities_and_optimal_layers_depths.next()
 		else {
 			// If there are no query proofs, that means that no oracles were sent during the FRI
@@ -568,15 +912,16 @@ where
 
 		prove_coset_opening(
 			self.merkle_prover,
-			self.codeword,
+			&self.codeword,
 			self.codeword_committed,
 			index,
 			first_fold_arity,
+			first_log_leaf_size,
 			first_optimal_layer_depth,
 			&mut advice,
 		)?;
 
-		for ((codeword, committed), (arity, optimal_layer_depth)) in
+		for ((codeword, committed), (arity, log_leaf_size, optimal_layer_depth)) in
 			izip!(self.round_committed.iter(), arities_and_optimal_layers_depths)
 		{
 			index >>= arity;
@@ -586,6 +931,7 @@ where
 				committed,
 				index,
 				arity,
+				log_leaf_size,
 				optimal_layer_depth,
 				&mut advice,
 			)?;
@@ -610,12 +956,15 @@ where
 	}
 }
 
+/// Opens the Merkle leaf that contains the coset at `coset_index`.
+#[allow(clippy::too_many_arguments)]
 fn prove_coset_opening<F, P, MTProver, B>(
 	merkle_prover: &MTProver,
 	codeword: &[P],
 	committed: &MTProver::Committed,
 	coset_index: usize,
 	log_coset_size: usize,
+	log_leaf_size: usize,
 	optimal_layer_depth: usize,
 	advice: &mut TranscriptWriter<B>,
 ) -> Result<(), Error>
@@ -625,12 +974,13 @@ where
 	MTProver: MerkleTreeProver<F>,
 	B: BufMut,
 {
-	let values = iter_packed_slice_with_offset(codeword, coset_index << log_coset_size)
-		.take(1 << log_coset_size);
+	let leaf_index = coset_index >> (log_leaf_size - log_coset_size);
+	let values = iter_packed_slice_with_offset(codeword, leaf_index << log_leaf_size)
+		.take(1 << log_leaf_size);
 	advice.write_scalar_iter(values);
 
 	merkle_prover
-		.prove_opening(committed, optimal_layer_depth, coset_index, advice)
+		.prove_opening(committed, optimal_layer_depth, leaf_index, advice)
 		.map_err(|err| Error::VectorCommit(Box::new(err)))?;
 
 	Ok(())
@@ -6,7 +6,7 @@ use binius_compute::{ComputeData, ComputeHolder, cpu::layer::CpuLayerHolder};
 use binius_field::{
 	BinaryField, BinaryField16b, BinaryField32b, BinaryField128b, ExtensionField,
 	PackedBinaryField16x16b, PackedField, TowerField,
-	arch::{OptimalUnderlier128b, packed_64::PackedBinaryField4x16b},
+	arch::{OptimalUnderlier128b, OptimalUnderlier256b, packed_64::PackedBinaryField4x16b},
 	as_packed_field::{PackScalar, PackedType},
 	underlier::UnderlierType,
 };
@@ -25,8 +25,8 @@ use crate::{
 	fiat_shamir::{CanSample, HasherChallenger},
 	merkle_tree::{BinaryMerkleTreeProver, MerkleTreeProver},
 	protocols::fri::{
-		self, CommitOutput, FRIFolder, FRIParams, FRIVerifier, FoldRoundOutput,
-		to_par_scalar_small_chunks,
+		self, CommitOutput, Error, FRIFolder, FRIParams, FRIVerifier, FoldRoundOutput,
+		MerkleCapAbsorption, to_par_scalar_small_chunks,
 	},
 	reed_solomon::reed_solomon::ReedSolomonCode,
 	transcript::ProverTranscript,
@@ -108,10 +108,6 @@ fn test_commit_prove_verify_success<U, F, FA>(
 	PackedType<U, F>: PackedField,
 	PackedType<U, FA>: PackedField,
 {
-	let mut rng = StdRng::seed_from_u64(0);
-
-	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
-
 	let committed_rs_code = ReedSolomonCode::<FA>::new(log_dimension, log_inv_rate).unwrap();
 
 	let n_test_queries = 3;
@@ -119,10 +115,28 @@ fn test_commit_prove_verify_success<U, F, FA>(
 		FRIParams::new(committed_rs_code, log_batch_size, arities.to_vec(), n_test_queries)
 			.unwrap();
 
-	let committed_rs_code = ReedSolomonCode::<FA>::new(log_dimension, log_inv_rate).unwrap();
+	check_commit_prove_verify_success::<U, F, FA>(&params);
+}
+
+fn check_commit_prove_verify_success<U, F, FA>(params: &FRIParams<F, FA>)
+where
+	U: UnderlierType + PackScalar<F> + PackScalar<FA>,
+	F: TowerField + ExtensionField<FA> + PackedField<Scalar = F> + TowerTop,
+	FA: BinaryField,
+	PackedType<U, F>: PackedField,
+	PackedType<U, FA>: PackedField,
+{
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+
+	let log_batch_size = params.log_batch_size();
+	let committed_rs_code =
+		ReedSolomonCode::<FA>::new(params.rs_code().log_dim(), params.rs_code().log_inv_rate())
+			.unwrap();
 	let ntt = SingleThreadedNTT::new(params.rs_code().log_len()).unwrap();
 
-	let n_round_commitments = arities.len();
+	let n_round_commitments = params.n_oracles();
 
 	// Generate a random message
 	let msg = repeat_with(|| <PackedType<U, F>>::random(&mut rng))
@@ -134,14 +148,14 @@ fn test_commit_prove_verify_success<U, F, FA>(
 		commitment: mut codeword_commitment,
 		committed: codeword_committed,
 		codeword,
-	} = fri::commit_interleaved(&committed_rs_code, &params, &ntt, &merkle_prover, &msg).unwrap();
+	} = fri::commit_interleaved(&committed_rs_code, params, &ntt, &merkle_prover, &msg).unwrap();
 
 	let mut compute_holder = CpuLayerHolder::<F>::new(1 << 10, 1 << 20);
 	let ComputeData { hal, dev_alloc, .. } = compute_holder.to_data();
 
 	// Run the prover to generate the proximity proof
 	let mut round_prover =
-		FRIFolder::new(hal, &params, &ntt, &merkle_prover, &codeword, &codeword_committed).unwrap();
+		FRIFolder::new(hal, params, &ntt, &merkle_prover, &codeword, &codeword_committed).unwrap();
 
 	let mut prover_challenger = ProverTranscript::<HasherChallenger<Groestl256>>::new();
 	prover_challenger.message().write(&codeword_commitment);
@@ -189,7 +203,7 @@ fn test_commit_prove_verify_success<U, F, FA>(
 	let computed_eval = multilin.evaluate(&eval_query).unwrap();
 
 	let verifier = FRIVerifier::new(
-		&params,
+		params,
 		merkle_prover.scheme(),
 		&codeword_commitment,
 		&round_commitments,
@@ -225,8 +239,6 @@ fn test_commit_prove_verify_success<U, F, FA>(
 
 #[test]
 fn test_commit_prove_verify_success_128b_full() {
-	binius_utils::rayon::adjust_thread_pool();
-
 	// This tests the case where we have a round commitment for every round
 	let log_dimension = 8;
 	let log_final_dimension = 1;
@@ -299,6 +311,194 @@ fn test_commit_prove_verify_success_without_folding() {
 	);
 }
 
+#[test]
+fn test_commit_prove_verify_success_single_element_message() {
+	test_commit_prove_verify_success::<OptimalUnderlier128b, BinaryField128b, BinaryField16b>(
+		0,
+		1,
+		0,
+		&[],
+	);
+}
+
+#[test]
+fn test_commit_on_device_matches_host() {
+	type U = OptimalUnderlier128b;
+	type F = BinaryField128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let committed_rs_code = ReedSolomonCode::<BinaryField16b>::new(6, 2).unwrap();
+	let params = FRIParams::new(committed_rs_code, 2, vec![3, 2, 1], 3).unwrap();
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let ntt = SingleThreadedNTT::new(params.rs_code().log_len()).unwrap();
+
+	let msg = repeat_with(|| <PackedType<U, F>>::random(&mut rng))
+		.take(params.rs_code().dim() << params.log_batch_size() >> <PackedType<U, F>>::LOG_WIDTH)
+		.collect::<Vec<_>>();
+
+	let mut compute_holder = CpuLayerHolder::<F>::new(1 << 10, 1 << 20);
+	let ComputeData { hal, dev_alloc, .. } = compute_holder.to_data();
+
+	let host_output =
+		fri::commit_interleaved(params.rs_code(), &params, &ntt, &merkle_prover, &msg).unwrap();
+	let fri::DeviceCommitOutput {
+		commitment,
+		committed,
+		device_codeword,
+	} =
+		fri::commit_interleaved_on_device(
+			hal,
+			&dev_alloc,
+			&params,
+			&ntt,
+			&merkle_prover,
+			|buffer| buffer.copy_from_slice(&msg),
+		)
+		.unwrap();
+	assert_eq!(commitment, host_output.commitment);
+	assert_eq!(device_codeword, PackedField::iter_slice(&host_output.codeword).collect::<Vec<_>>());
+
+	let host_folder = FRIFolder::new(
+		hal,
+		&params,
+		&ntt,
+		&merkle_prover,
+		&host_output.codeword,
+		&host_output.committed,
+	)
+	.unwrap();
+	let device_folder =
+		FRIFolder::new_on_device(hal, &params, &ntt, &merkle_prover, device_codeword, &committed)
+			.unwrap();
+
+	// The folders write the same proof, including the query openings of the device codeword.
+	let challenges = repeat_with(|| F::random(&mut rng))
+		.take(params.n_fold_rounds())
+		.collect::<Vec<_>>();
+	let [device_proof, host_proof] = [device_folder, host_folder].map(|mut folder| {
+		let mut transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		for &challenge in &challenges {
+			if let FoldRoundOutput::Commitment(round_commitment) =
+				folder.execute_fold_round(&dev_alloc, challenge).unwrap()
+			{
+				transcript.message().write(&round_commitment);
+			}
+		}
+		folder.finish_proof(&mut transcript).unwrap();
+		transcript.finalize()
+	});
+	assert_eq!(device_proof, host_proof);
+}
+
+#[test]
+fn test_commit_on_device_rejects_small_message() {
+	type U = OptimalUnderlier256b;
+	type F = BinaryField128b;
+
+	// The message of a single element does not fill a packed element of two elements.
+	let params =
+		FRIParams::new(ReedSolomonCode::<BinaryField16b>::new(0, 2).unwrap(), 0, vec![], 1)
+			.unwrap();
+	let merkle_prover = BinaryMerkleTreeProver::<_, Groestl256, _>::new(Groestl256ByteCompression);
+	let ntt = SingleThreadedNTT::new(params.rs_code().log_len()).unwrap();
+
+	let mut compute_holder = CpuLayerHolder::<F>::new(1 << 10, 1 << 20);
+	let ComputeData { hal, dev_alloc, .. } = compute_holder.to_data();
+	let result = fri::commit_interleaved_on_device::<_, _, PackedType<U, F>, _, _, _, _, _>(
+		hal,
+		&dev_alloc,
+		&params,
+		&ntt,
+		&merkle_prover,
+		|_| {},
+	);
+	assert!(matches!(result, Err(Error::MessageSmallerThanPackedWidth { n_elems: 1, .. })));
+}
+
+fn make_cap_test_params(
+	cap_height: usize,
+) -> Result<FRIParams<BinaryField128b, BinaryField16b>, Error> {
+	let committed_rs_code = ReedSolomonCode::<BinaryField16b>::new(8, 2).unwrap();
+	FRIParams::new(committed_rs_code, 0, vec![3, 2, 1], 3)
+		.unwrap()
+		.with_cap_height(cap_height)
+}
+
+#[test]
+fn test_commit_prove_verify_success_with_cap_height() {
+	for cap_height in [0, 1, 4] {
+		let params = make_cap_test_params(cap_height).unwrap();
+		check_commit_prove_verify_success::<OptimalUnderlier128b, _, _>(&params);
+	}
+}
+
+#[test]
+fn test_commit_prove_verify_success_with_eager_cap_absorption() {
+	for cap_height in [0, 2, 4] {
+		let params = make_cap_test_params(cap_height)
+			.unwrap()
+			.with_cap_absorption(MerkleCapAbsorption::Eager);
+		check_commit_prove_verify_success::<OptimalUnderlier128b, _, _>(&params);
+	}
+}
+
+#[test]
+fn test_cap_height_exceeding_tree_depth() {
+	// The last oracle is committed in a tree of depth 8 + 2 - 6 = 4.
+	assert!(matches!(
+		make_cap_test_params(5),
+		Err(Error::CapHeightTooLarge {
+			cap_height: 5,
+			tree_depth: 4
+		})
+	));
+}
+
+#[test]
+fn test_commit_prove_verify_success_with_leaf_bundling() {
+	for (log_leaf_size, cap_height) in [(3, None), (4, Some(1)), (5, None), (5, Some(0))] {
+		let mut params = make_cap_test_params(0)
+			.unwrap()
+			.with_log_leaf_size(log_leaf_size)
+			.unwrap();
+		if let Some(cap_height) = cap_height {
+			params = params.with_cap_height(cap_height).unwrap();
+		}
+		check_commit_prove_verify_success::<OptimalUnderlier128b, _, _>(&params);
+	}
+}
+
+#[test]
+fn test_leaf_bundling_validation() {
+	// The queried oracles have lengths 2^10, 2^7 and 2^5.
+	assert!(matches!(
+		make_cap_test_params(0).unwrap().with_log_leaf_size(2),
+		Err(Error::LeafSizeSmallerThanArity {
+			log_leaf_size: 2,
+			index: 0,
+			arity: 3
+		})
+	));
+	assert!(matches!(
+		make_cap_test_params(0).unwrap().with_log_leaf_size(5),
+		Ok(params) if params.oracle_tree_depths().eq([5, 2, 0])
+	));
+	assert!(matches!(
+		make_cap_test_params(0).unwrap().with_log_leaf_size(6),
+		Err(Error::LeafSizeExceedsOracle {
+			log_leaf_size: 6,
+			log_oracle_len: 5
+		})
+	));
+	assert!(matches!(
+		make_cap_test_params(2).unwrap().with_log_leaf_size(4),
+		Err(Error::CapHeightTooLarge {
+			cap_height: 2,
+			tree_depth: 1
+		})
+	));
+}
+
 #[test]
 fn test_parallel_iterator_for_commitments() {
 	// Compare results for small and large chunk sizes to ensure that they're identical
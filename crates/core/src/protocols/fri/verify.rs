@@ -13,7 +13,9 @@ use bytes::Buf;
 use itertools::izip;
 use tracing::instrument;
 
-use super::{VerificationError, common::vcs_optimal_layers_depths_iter, error::Error};
+use super::{
+	MerkleCapAbsorption, VerificationError, common::vcs_optimal_layers_depths_iter, error::Error,
+};
 use crate::{
 	fiat_shamir::{CanSampleBits, Challenger},
 	merkle_tree::MerkleTreeScheme,
@@ -59,19 +61,17 @@ where
 		challenges: &'a [F],
 	) -> Result<Self, Error> {
 		if round_commitments.len() != params.n_oracles() {
-			bail!(Error::InvalidArgs(format!(
-				"got {} round commitments, expected {}",
-				round_commitments.len(),
-				params.n_oracles(),
-			)));
+			bail!(Error::IncorrectRoundCommitmentCount {
+				expected: params.n_oracles(),
+				actual: round_commitments.len(),
+			});
 		}
 
 		if challenges.len() != params.n_fold_rounds() {
-			bail!(Error::InvalidArgs(format!(
-				"got {} folding challenges, expected {}",
-				challenges.len(),
-				params.n_fold_rounds(),
-			)));
+			bail!(Error::IncorrectFoldChallengeCount {
+				expected: params.n_fold_rounds(),
+				actual: challenges.len(),
+			});
 		}
 
 		let (interleave_challenges, fold_challenges) = challenges.split_at(params.log_batch_size());
@@ -115,9 +115,18 @@ where
 		let final_value = self.verify_last_oracle(&ntt, &terminate_codeword)?;
 
 		// Verify that the provided layers match the commitments.
-		let layers = vcs_optimal_layers_depths_iter(self.params, self.vcs)
-			.map(|layer_depth| advice.read_vec(1 << layer_depth))
-			.collect::<Result<Vec<_>, _>>()?;
+		let layer_depths = vcs_optimal_layers_depths_iter(self.params, self.vcs);
+		let layers = match self.params.cap_absorption() {
+			MerkleCapAbsorption::Lazy => layer_depths
+				.map(|layer_depth| advice.read_vec(1 << layer_depth))
+				.collect::<Result<Vec<_>, _>>()?,
+			MerkleCapAbsorption::Eager => {
+				let mut message = transcript.message();
+				layer_depths
+					.map(|layer_depth| message.read_vec(1 << layer_depth))
+					.collect::<Result<Vec<_>, _>>()?
+			}
+		};
 		for (commitment, layer_depth, layer) in izip!(
 			iter::once(self.codeword_commitment).chain(self.round_commitments),
 			vcs_optimal_layers_depths_iter(self.params, self.vcs),
@@ -129,21 +138,105 @@ where
 		}
 
 		// Verify the random openings against the decommitted layers.
+		self.verify_queries(transcript, &ntt, &terminate_codeword, &layers)?;
+
+		Ok(final_value)
+	}
 
+	#[cfg(not(feature = "parallel_fri_queries"))]
+	fn verify_queries<Challenger_>(
+		&self,
+		transcript: &mut VerifierTranscript<Challenger_>,
+		ntt: &SingleThreadedNTT<FA>,
+		terminate_codeword: &[F],
+		layers: &[Vec<VCS::Digest>],
+	) -> Result<(), Error>
+	where
+		Challenger_: Challenger,
+	{
 		let mut scratch_buffer = self.create_scratch_buffer();
 		for _ in 0..self.params.n_test_queries() {
 			let index = transcript.sample_bits(self.params.index_bits()) as usize;
 			self.verify_query_internal(
 				index,
-				&ntt,
-				&terminate_codeword,
-				&layers,
+				ntt,
+				terminate_codeword,
+				layers,
 				&mut transcript.decommitment(),
 				&mut scratch_buffer,
 			)?
 		}
+		Ok(())
+	}
 
-		Ok(final_value)
+	/// Verifies the test queries in parallel.
+	///
+	/// The query proofs are decommitments, which are not observed by the challenger, so all query
+	/// indices can be sampled before any proof is read. Every query proof has the same size, which
+	/// lets the proofs be split off the transcript up front and checked independently.
+	#[cfg(feature = "parallel_fri_queries")]
+	fn verify_queries<Challenger_>(
+		&self,
+		transcript: &mut VerifierTranscript<Challenger_>,
+		ntt: &SingleThreadedNTT<FA>,
+		terminate_codeword: &[F],
+		layers: &[Vec<VCS::Digest>],
+	) -> Result<(), Error>
+	where
+		Challenger_: Challenger,
+	{
+		use binius_maybe_rayon::prelude::*;
+
+		let indices = (0..self.params.n_test_queries())
+			.map(|_| transcript.sample_bits(self.params.index_bits()) as usize)
+			.collect::<Vec<_>>();
+
+		let query_proof_size = self.query_proof_size();
+		let mut advice = transcript.decommitment();
+		let query_proofs = indices
+			.iter()
+			.map(|_| {
+				let mut proof = vec![0u8; query_proof_size];
+				advice.read_bytes(&mut proof)?;
+				Ok(proof)
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		indices.into_par_iter().zip(query_proofs).try_for_each_init(
+			|| self.create_scratch_buffer(),
+			|scratch_buffer, (index, proof)| {
+				let mut proof = proof.as_slice();
+				self.verify_query_internal(
+					index,
+					ntt,
+					terminate_codeword,
+					layers,
+					&mut advice.with_buffer(&mut proof),
+					scratch_buffer,
+				)?;
+				debug_assert!(proof.is_empty(), "query proof size is computed incorrectly");
+				Ok(())
+			},
+		)
+	}
+
+	/// The byte-size of the decommitment read by a single test query.
+	#[cfg(feature = "parallel_fri_queries")]
+	fn query_proof_size(&self) -> usize {
+		let mut scalar = Vec::new();
+		F::ZERO
+			.serialize(&mut scalar, binius_utils::SerializationMode::CanonicalTower)
+			.expect("serialization into a vector does not fail");
+
+		izip!(
+			self.params.oracle_log_leaf_sizes(),
+			self.params.oracle_tree_depths(),
+			vcs_optimal_layers_depths_iter(self.params, self.vcs)
+		)
+		.map(|(log_leaf_size, tree_depth, layer_depth)| {
+			(scalar.len() << log_leaf_size) + self.vcs.opening_proof_size(layer_depth, tree_depth)
+		})
+		.sum()
 	}
 
 	/// Verifies that the last oracle sent is a codeword.
@@ -260,25 +353,30 @@ where
 		advice: &mut TranscriptReader<B>,
 		scratch_buffer: &mut [F],
 	) -> Result<(), Error> {
-		let mut arities_iter = self.params.fold_arities().iter().copied();
-
-		let mut layer_digest_and_optimal_layer_depth =
-			iter::zip(layers, vcs_optimal_layers_depths_iter(self.params, self.vcs));
+		let mut oracles_iter = izip!(
+			self.params.fold_arities().iter().copied(),
+			self.params.oracle_log_leaf_sizes(),
+			self.params.oracle_tree_depths(),
+			layers,
+			vcs_optimal_layers_depths_iter(self.params, self.vcs)
+		);
 
-		let Some(first_fold_arity) = arities_iter.next() else {
+		let Some((
+			first_fold_arity,
+			first_log_leaf_size,
+			first_tree_depth,
+			first_layer,
+			first_optimal_layer_depth,
+		)) = oracles_iter.next()
+		else {
 			// If there are no query proofs, that means that no oracles were sent during the FRI
 			// fold rounds. In that case, the original interleaved codeword is decommitted and
 			// the only checks that need to be performed are in `verify_last_oracle`.
 			return Ok(());
 		};
 
-		let (first_layer, first_optimal_layer_depth) = layer_digest_and_optimal_layer_depth
-			.next()
-			.expect("The length should be the same as the amount of proofs.");
-
 		// This is the round of the folding phase that the codeword to be folded is committed to.
 		let mut fold_round = 0;
-		let mut log_n_cosets = self.params.index_bits();
 
 		// Check the first fold round before the main loop. It is special because in the first
 		// round we need to fold as an interleaved chunk instead of a regular coset.
@@ -287,8 +385,9 @@ where
 			self.vcs,
 			index,
 			first_fold_arity,
+			first_log_leaf_size,
 			first_optimal_layer_depth,
-			log_n_cosets,
+			first_tree_depth,
 			first_layer,
 			advice,
 		)?;
@@ -304,19 +403,18 @@ where
 		);
 		fold_round += log_coset_size;
 
-		for (i, (arity, (layer, optimal_layer_depth))) in
-			izip!(arities_iter, layer_digest_and_optimal_layer_depth).enumerate()
+		for (i, (arity, log_leaf_size, tree_depth, layer, optimal_layer_depth)) in
+			oracles_iter.enumerate()
 		{
 			let coset_index = index >> arity;
 
-			log_n_cosets -= arity;
-
 			let mut values = verify_coset_opening(
 				self.vcs,
 				coset_index,
 				arity,
+				log_leaf_size,
 				optimal_layer_depth,
-				log_n_cosets,
+				tree_depth,
 				layer,
 				advice,
 			)?;
@@ -366,11 +464,15 @@ where
 }
 
 /// Verifies that the coset opening provided in the proof is consistent with the VCS commitment.
+///
+/// The proof opens the whole Merkle leaf containing the coset, and the values of the coset are
+/// returned.
 #[allow(clippy::too_many_arguments)]
 fn verify_coset_opening<F, MTScheme, B>(
 	vcs: &MTScheme,
 	coset_index: usize,
 	log_coset_size: usize,
+	log_leaf_size: usize,
 	optimal_layer_depth: usize,
 	tree_depth: usize,
 	layer_digests: &[MTScheme::Digest],
@@ -381,9 +483,10 @@ where
 	MTScheme: MerkleTreeScheme<F>,
 	B: Buf,
 {
-	let values = advice.read_scalar_slice::<F>(1 << log_coset_size)?;
+	let log_cosets_per_leaf = log_leaf_size - log_coset_size;
+	let mut values = advice.read_scalar_slice::<F>(1 << log_leaf_size)?;
 	vcs.verify_opening(
-		coset_index,
+		coset_index >> log_cosets_per_leaf,
 		&values,
 		optimal_layer_depth,
 		tree_depth,
@@ -392,5 +495,10 @@ where
 	)
 	.map_err(|err| Error::VectorCommit(Box::new(err)))?;
 
+	if log_cosets_per_leaf != 0 {
+		let offset = (coset_index % (1 << log_cosets_per_leaf)) << log_coset_size;
+		values.drain(..offset);
+		values.truncate(1 << log_coset_size);
+	}
 	Ok(values)
 }
@@ -9,9 +9,10 @@ use crate::{
 	fiat_shamir::Challenger,
 	oracle::MultilinearOracleSet,
 	protocols::evalcheck::{
-		ConstraintSetEqIndPoint, EvalcheckMultilinearClaim, EvalcheckProver,
+		EvalcheckMultilinearClaim, EvalcheckProver,
 		subclaims::{
-			MemoizedData, prove_bivariate_sumchecks_with_switchover, prove_mlecheck_with_switchover,
+			MemoizedData, prove_bivariate_sumchecks_with_switchover,
+			prove_mlechecks_with_switchover,
 		},
 	},
 	transcript::ProverTranscript,
@@ -113,23 +114,16 @@ where
 			)
 			.entered();
 
-			for ConstraintSetEqIndPoint {
-				eq_ind_challenges,
-				constraint_set,
-			} in new_mlechecks
-			{
-				let evalcheck_claims = prove_mlecheck_with_switchover::<_, _, DomainField, _, _>(
-					evalcheck_prover.witness_index,
-					constraint_set,
-					eq_ind_challenges,
-					&mut evalcheck_prover.memoized_data,
-					transcript,
-					switchover_fn.clone(),
-					domain_factory.clone(),
-					backend,
-				)?;
-				new_evalcheck_claims.extend(evalcheck_claims);
-			}
+			let evalcheck_claims = prove_mlechecks_with_switchover::<_, _, DomainField, _, _>(
+				evalcheck_prover.witness_index,
+				new_mlechecks,
+				&mut evalcheck_prover.memoized_data,
+				transcript,
+				switchover_fn.clone(),
+				domain_factory.clone(),
+				backend,
+			)?;
+			new_evalcheck_claims.extend(evalcheck_claims);
 
 			drop(evalcheck_round_mle_fold_high_span);
 		}
@@ -1,5 +1,5 @@
 // Copyright 2024-2025 Irreducible Inc.
-use std::iter::repeat_with;
+use std::iter::{self, repeat_with};
 
 use binius_field::{
 	BinaryField1b, BinaryField32b, BinaryField128b, ExtensionField, Field, PackedBinaryField1x128b,
@@ -180,3 +180,111 @@ where
 fn test_evalcheck_composite_projected() {
 	run_test_evalcheck_composite_projected::<PackedBinaryField128x1b, FExtension, PExtension>(8);
 }
+
+/// Adds a composite of select rows over `n_vars` variables to the oracles and the witness, and
+/// returns a claim on its evaluation at a random point.
+fn add_select_rows_composite(
+	oracles: &mut MultilinearOracleSet<FExtension>,
+	witness_index: &mut MultilinearExtensionIndex<PExtension>,
+	n_vars: usize,
+	rng: &mut StdRng,
+) -> EvalcheckMultilinearClaim<FExtension> {
+	type P = PackedBinaryField128x1b;
+
+	let select_rows = [0, 5, 10].map(|index| SelectRow::new(n_vars, index).unwrap());
+	let select_row_ids = select_rows
+		.clone()
+		.map(|select_row| oracles.add_transparent(select_row).unwrap());
+	let comp: ArithCircuit<FExtension> =
+		ArithCircuit::from(ArithExpr::Var(0) * ArithExpr::Var(1) + ArithExpr::Var(2));
+	let composite_id = oracles
+		.add_composite_mle(n_vars, select_row_ids, comp)
+		.unwrap();
+
+	let eval_point = repeat_with(|| <FExtension as Field>::random(&mut *rng))
+		.take(n_vars)
+		.collect::<Vec<_>>();
+	let select_row_witnesses =
+		select_rows.map(|select_row| select_row.multilinear_extension::<P>().unwrap());
+	let composite_scalars = (0..1 << n_vars)
+		.map(|i| {
+			let [s1, s2, s3] = select_row_witnesses
+				.each_ref()
+				.map(|witness| witness.evaluate_on_hypercube(i).unwrap());
+			s1 * s2 + s3
+		})
+		.collect::<Vec<BinaryField1b>>();
+	let composite_witness =
+		MultilinearExtension::from_values(pack_slice::<P>(&composite_scalars)).unwrap();
+
+	let query = make_portable_backend()
+		.multilinear_query::<FExtension>(&eval_point)
+		.unwrap();
+	let eval = composite_witness.evaluate(query.to_ref()).unwrap();
+
+	witness_index
+		.update_multilin_poly(
+			iter::zip(select_row_ids, select_row_witnesses)
+				.map(|(id, witness)| (id, witness.specialize_arc_dyn()))
+				.chain([(composite_id, composite_witness.specialize_arc_dyn())]),
+		)
+		.unwrap();
+
+	EvalcheckMultilinearClaim {
+		id: composite_id,
+		eval_point: eval_point.into(),
+		eval,
+	}
+}
+
+#[test]
+fn test_evalcheck_composites_different_n_vars() {
+	let mut rng = StdRng::seed_from_u64(0);
+	let mut oracles = MultilinearOracleSet::new();
+	let mut witness_index = MultilinearExtensionIndex::<PExtension>::new();
+
+	// The composite claims are proven in a single batched sumcheck, even though they are over
+	// different numbers of variables and listed out of order.
+	let claims = [9, 7, 8].map(|n_vars| {
+		add_select_rows_composite(&mut oracles, &mut witness_index, n_vars, &mut rng)
+	});
+
+	let backend = make_portable_backend();
+	let domain_factory = DefaultEvaluationDomainFactory::<FDomain>::default();
+
+	let mut transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	let eval_claims = prove::<_, _, FDomain, _, _>(
+		&mut oracles,
+		&mut witness_index,
+		claims.clone(),
+		standard_switchover_heuristic(-2),
+		&mut transcript,
+		&domain_factory,
+		&backend,
+	)
+	.unwrap()
+	.eval_claims;
+	assert!(eval_claims.is_empty());
+
+	let mut verifier_transcript = transcript.into_verifier();
+	let eval_claims = verify(&mut oracles, claims.clone(), &mut verifier_transcript).unwrap();
+	assert!(eval_claims.is_empty());
+	verifier_transcript.finalize().unwrap();
+
+	// A wrong claimed evaluation is rejected.
+	let mut transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+	prove::<_, _, FDomain, _, _>(
+		&mut oracles,
+		&mut witness_index,
+		claims.clone(),
+		standard_switchover_heuristic(-2),
+		&mut transcript,
+		&domain_factory,
+		&backend,
+	)
+	.unwrap();
+	let mut wrong_claims = claims;
+	wrong_claims[1].eval += FExtension::ONE;
+	let mut verifier_transcript = transcript.into_verifier();
+	assert!(verify(&mut oracles, wrong_claims, &mut verifier_transcript).is_err());
+}
@@ -3,20 +3,14 @@
 use binius_field::TowerField;
 use binius_math::EvaluationOrder;
 use binius_utils::bail;
-use itertools::izip;
 
 use super::error::Error;
 use crate::{
 	fiat_shamir::Challenger,
 	oracle::MultilinearOracleSet,
 	protocols::{
-		evalcheck::{ConstraintSetsEqIndPoints, EvalcheckMultilinearClaim, EvalcheckVerifier},
-		sumcheck::{
-			self, MLEcheckClaimsWithMeta, SumcheckClaimsWithMeta, constraint_set_mlecheck_claims,
-			constraint_set_sumcheck_claims,
-			eq_ind::{self, ClaimsSortingOrder, reduce_to_regular_sumchecks},
-			front_loaded,
-		},
+		evalcheck::{EvalcheckMultilinearClaim, EvalcheckVerifier, subclaims::verify_mlechecks},
+		sumcheck::{self, SumcheckClaimsWithMeta, constraint_set_sumcheck_claims, front_loaded},
 	},
 	transcript::VerifierTranscript,
 };
@@ -58,47 +52,13 @@ where
 			new_evalcheck_claims.extend(evalcheck_claims)
 		}
 
-		let ConstraintSetsEqIndPoints {
-			eq_ind_challenges,
-			constraint_sets,
-		} = evalcheck_verifier.take_new_mlechecks_constraints()?;
+		let new_mlechecks = evalcheck_verifier.take_new_mlechecks_constraints()?;
 
-		let MLEcheckClaimsWithMeta {
-			claims: mlecheck_claims,
-			metas,
-		} = constraint_set_mlecheck_claims(constraint_sets)?;
-
-		if !mlecheck_claims.is_empty() {
+		if !new_mlechecks.constraint_sets.is_empty() {
 			// Reduce the new mlecheck claims for virtual polynomial openings to new evalcheck
 			// claims.
-			for (eq_ind_challenges, mlecheck_claim, meta) in
-				izip!(eq_ind_challenges, mlecheck_claims, metas)
-			{
-				let mlecheck_claim = vec![mlecheck_claim];
-
-				let batch_sumcheck_verifier = front_loaded::BatchVerifier::new(
-					&reduce_to_regular_sumchecks(&mlecheck_claim)?,
-					transcript,
-				)?;
-				let mut sumcheck_output = batch_sumcheck_verifier.run(transcript)?;
-
-				// Reverse challenges since foldling high-to-low
-				sumcheck_output.challenges.reverse();
-
-				let eq_ind_output = eq_ind::verify_sumcheck_outputs(
-					ClaimsSortingOrder::AscendingVars,
-					&mlecheck_claim,
-					&eq_ind_challenges,
-					sumcheck_output,
-				)?;
-
-				let evalcheck_claims = sumcheck::make_eval_claims(
-					EvaluationOrder::HighToLow,
-					vec![meta],
-					eq_ind_output,
-				)?;
-				new_evalcheck_claims.extend(evalcheck_claims)
-			}
+			let evalcheck_claims = verify_mlechecks(new_mlechecks, transcript)?;
+			new_evalcheck_claims.extend(evalcheck_claims)
 		}
 
 		if new_evalcheck_claims.is_empty() {
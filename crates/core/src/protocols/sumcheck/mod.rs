@@ -21,7 +21,8 @@ pub mod zerocheck;
 
 pub use common::{
 	BatchSumcheckOutput, CompositeSumClaim, RoundCoeffs, RoundProof, SumcheckClaim,
-	equal_n_vars_check, immediate_switchover_heuristic, standard_switchover_heuristic,
+	batch_weighted_value, equal_n_vars_check, immediate_switchover_heuristic,
+	standard_switchover_heuristic,
 };
 pub use eq_ind::EqIndSumcheckClaim;
 pub use error::*;
@@ -594,22 +594,23 @@ where
 	let ntt = SingleThreadedNTT::with_subspace(&subspace_upcast)
 		.expect("ntt provided is valid; subspace is equivalent but upcast to F");
 
-	// Cache OddInterpolate instances, which, albeit small in practice, take cubic time to create.
+	// Cache OddInterpolate instances, which take cubic time in the odd factor to create.
 	let mut odd_interpolates = HashMap::new();
 
 	for round_evals in &mut round_evals {
 		// Re-add zero evaluations at the beginning.
 		round_evals.splice(0..0, repeat_n(F::ZERO, 1 << skip_rounds));
 
-		let n = round_evals.len();
+		// The round polynomial of a composition of degree d has degree at most d (2^k - 1), while
+		// there are d 2^k evaluations. Interpolating from the shortest prefix that determines the
+		// polynomial keeps the odd factor of the interpolation domain small for any degree.
+		let (d, ell) = interpolation_domain(round_evals.len(), skip_rounds);
+		round_evals.truncate(d << ell);
 
 		// Get OddInterpolate instance of required size.
-		let odd_interpolate = odd_interpolates.entry(n).or_insert_with(|| {
-			let ell = n.trailing_zeros() as usize;
-			assert!(ell >= skip_rounds);
-
+		let odd_interpolate = odd_interpolates.entry((d, ell)).or_insert_with(|| {
 			let coset_bits = ntt.log_domain_size() - ell;
-			OddInterpolate::new(&ntt, n >> ell, ell, coset_bits)
+			OddInterpolate::new(&ntt, d, ell, coset_bits)
 				.expect("domain large enough by construction")
 		});
 
@@ -641,6 +642,19 @@ where
 	Ok(round_evals)
 }
 
+// Returns the shape $(d, \ell)$ of a domain of size $d 2^{\ell}$ that covers enough of the
+// `n_evals` round evaluations to determine the round polynomial. The largest $\ell$ for which the
+// domain fits into the evaluations is chosen, so that $d$ stays small.
+fn interpolation_domain(n_evals: usize, skip_rounds: usize) -> (usize, usize) {
+	let composition_degree = n_evals >> skip_rounds;
+	let n_required = composition_degree * ((1 << skip_rounds) - 1) + 1;
+	(0..=log2_ceil_usize(n_required))
+		.rev()
+		.map(|ell| (n_required.div_ceil(1 << ell), ell))
+		.find(|&(d, ell)| d << ell <= n_evals)
+		.expect("ell = 0 always fits, as n_required <= n_evals")
+}
+
 fn ntt_extrapolate<NTT, P>(
 	ntt: &NTT,
 	skip_rounds: usize,
@@ -704,12 +718,27 @@ mod tests {
 		composition::{IndexComposition, ProductComposition},
 		polynomial::CompositionScalarAdapter,
 		protocols::{
-			sumcheck::prove::univariate::{domain_size, zerocheck_univariate_evals},
+			sumcheck::prove::univariate::{
+				domain_size, interpolation_domain, zerocheck_univariate_evals,
+			},
 			test_utils::generate_zero_product_multilinears,
 		},
 		transparent::eq_ind::EqIndPartialEval,
 	};
 
+	#[test]
+	fn interpolation_domain_covers_round_polynomial() {
+		for skip_rounds in 0..5usize {
+			for composition_degree in 1..256usize {
+				let n_evals = domain_size(composition_degree, skip_rounds);
+				let (d, ell) = interpolation_domain(n_evals, skip_rounds);
+				assert!(d << ell > composition_degree * ((1 << skip_rounds) - 1));
+				assert!(d << ell <= n_evals);
+				assert!(d < 2 << skip_rounds);
+			}
+		}
+	}
+
 	#[test]
 	fn ntt_extrapolate_correctness() {
 		type P = PackedBinaryField4x32b;
@@ -12,9 +12,8 @@ use binius_field::{
 };
 use binius_math::{ArithCircuit, CompositionPoly, EvaluationOrder, evaluate_univariate};
 use binius_utils::bail;
-use itertools::Itertools;
 
-use super::bivariate_product::{PhaseState, SumcheckMultilinear};
+use super::bivariate_product::{PhaseState, SumcheckMultilinear, fold_multilinears_high};
 use crate::{
 	composition::{BivariateProduct, IndexComposition},
 	protocols::sumcheck::{
@@ -143,52 +142,8 @@ where
 	}
 
 	pub fn fold_multilinears(&mut self, challenge: F) -> Result<(), Error> {
-		use binius_compute::{FSlice, FSliceMut};
-
-		type PreparedExtrapolateLineArgs<'a, F, Hal> = (FSliceMut<'a, F, Hal>, FSlice<'a, F, Hal>);
-
-		let prepared_extrapolate_line_ops = self
-			.multilins
-			.drain(..)
-			.map(
-				|multilin| -> Result<
-					PreparedExtrapolateLineArgs<'a, F, Hal>,
-					binius_compute::Error,
-				> {
-					match multilin {
-						SumcheckMultilinear::PreFold(evals) => {
-							debug_assert_eq!(evals.len(), 1 << self.n_vars_remaining);
-							let (evals_0, evals_1) =
-								Hal::DevMem::split_half(evals);
-
-							// Allocate new buffer for the folded evaluations and copy in evals_0.
-							let mut folded_evals =
-								self.dev_alloc.alloc(1 << (self.n_vars_remaining - 1))?;
-							self.hal.copy_d2d(evals_0, &mut folded_evals)?;
-							Ok((folded_evals, evals_1))
-						}
-						SumcheckMultilinear::PostFold(evals) => {
-							debug_assert_eq!(evals.len(), 1 << self.n_vars_remaining);
-							let (evals_0, evals_1) =
-								Hal::DevMem::split_half_mut(evals);
-							Ok((evals_0, Hal::DevMem::to_const(evals_1)))
-						}
-					}
-				},
-			)
-			.collect_vec();
-		self.hal.execute(|exec| {
-			self.multilins = exec.map(
-				prepared_extrapolate_line_ops.into_iter(),
-				|exec, extrapolate_line_args| {
-					let (mut evals_0, evals_1) = extrapolate_line_args?;
-					exec.extrapolate_line(&mut evals_0, evals_1, challenge)?;
-					Ok(SumcheckMultilinear::<F, Hal::DevMem>::PostFold(evals_0))
-				},
-			)?;
-
-			Ok(Vec::new())
-		})?;
+		self.multilins =
+			fold_multilinears_high(self.hal, self.dev_alloc, self.multilins.drain(..), challenge)?;
 		Ok(())
 	}
 
@@ -9,7 +9,6 @@ use binius_compute::{
 use binius_field::{Field, TowerField, util::powers};
 use binius_math::{CompositionPoly, EvaluationOrder, evaluate_univariate};
 use binius_utils::bail;
-use itertools::Itertools;
 
 use crate::{
 	composition::{BivariateProduct, IndexComposition},
@@ -166,10 +165,6 @@ where
 	}
 
 	fn fold(&mut self, challenge: F) -> Result<(), Error> {
-		use binius_compute::{FSlice, FSliceMut};
-
-		type PreparedExtrapolateLineArgs<'a, F, Hal> = (FSliceMut<'a, F, Hal>, FSlice<'a, F, Hal>);
-
 		if self.n_vars_remaining == 0 {
 			bail!(Error::ExpectedFinish);
 		}
@@ -185,47 +180,9 @@ where
 			}
 		}
 
-		let prepared_extrapolate_line_ops =
-			self.multilins
-				.drain(..)
-				.map(
-					|multilin| -> Result<
-						PreparedExtrapolateLineArgs<'b, F, Hal>,
-						binius_compute::Error,
-					> {
-						match multilin {
-							SumcheckMultilinear::PreFold(evals) => {
-								debug_assert_eq!(evals.len(), 1 << self.n_vars_remaining);
-								let (evals_0, evals_1) = Hal::DevMem::split_half(evals);
-								// Allocate new buffer for the folded evaluations and copy in
-								// evals_0.
-								let mut folded_evals= self.dev_alloc.alloc(1 << (self.n_vars_remaining - 1))?;
-								self.hal.copy_d2d(evals_0, &mut folded_evals)?;
-								Ok((folded_evals, evals_1))
-							}
-							SumcheckMultilinear::PostFold(evals) => {
-								debug_assert_eq!(evals.len(), 1 << self.n_vars_remaining);
-								let (evals_0, evals_1) = Hal::DevMem::split_half_mut(evals);
-								Ok((evals_0, Hal::DevMem::to_const(evals_1)))
-							}
-						}
-					},
-				)
-				.collect_vec();
-
 		// Fold the multilinears
-		let _ = self.hal.execute(|exec| {
-			self.multilins = exec.map(
-				prepared_extrapolate_line_ops.into_iter(),
-				|exec, extrapolate_line_args| {
-					let (mut evals_0, evals_1) = extrapolate_line_args?;
-					exec.extrapolate_line(&mut evals_0, evals_1, challenge)?;
-					Ok(SumcheckMultilinear::<F, Hal::DevMem>::PostFold(evals_0))
-				},
-			)?;
-
-			Ok(Vec::new())
-		})?;
+		self.multilins =
+			fold_multilinears_high(self.hal, self.dev_alloc, self.multilins.drain(..), challenge)?;
 
 		self.n_vars_remaining -= 1;
 		Ok(())
@@ -269,6 +226,76 @@ impl<'a, F, Mem: ComputeMemory<F>> SumcheckMultilinear<'a, F, Mem> {
 	}
 }
 
+/// Folds the highest variable of each multilinear at the challenge point.
+///
+/// Multilinears that have not been folded yet are read-only, so they are folded into newly
+/// allocated buffers with [`ComputeLayerExecutor::fold_high`]. Multilinears that were folded in a
+/// previous round own their buffer and are folded in place with
+/// [`ComputeLayerExecutor::extrapolate_line`].
+pub(super) fn fold_multilinears_high<'a, F, Hal, DeviceAllocatorType>(
+	hal: &Hal,
+	dev_alloc: &'a DeviceAllocatorType,
+	multilins: impl IntoIterator<Item = SumcheckMultilinear<'a, F, Hal::DevMem>>,
+	challenge: F,
+) -> Result<Vec<SumcheckMultilinear<'a, F, Hal::DevMem>>, binius_compute::Error>
+where
+	F: TowerField,
+	Hal: ComputeLayer<F>,
+	DeviceAllocatorType: ComputeAllocator<F, Hal::DevMem>,
+{
+	enum PreparedFold<'a, F, Mem: ComputeMemory<F>> {
+		OutOfPlace {
+			evals: Mem::FSlice<'a>,
+			folded: Mem::FSliceMut<'a>,
+		},
+		InPlace {
+			evals_0: Mem::FSliceMut<'a>,
+			evals_1: Mem::FSlice<'a>,
+		},
+	}
+
+	let prepared_folds = multilins
+		.into_iter()
+		.map(|multilin| -> Result<PreparedFold<'a, F, Hal::DevMem>, binius_compute::Error> {
+			match multilin {
+				SumcheckMultilinear::PreFold(evals) => {
+					let folded = dev_alloc.alloc(evals.len() / 2)?;
+					Ok(PreparedFold::OutOfPlace { evals, folded })
+				}
+				SumcheckMultilinear::PostFold(evals) => {
+					let (evals_0, evals_1) = Hal::DevMem::split_half_mut(evals);
+					Ok(PreparedFold::InPlace {
+						evals_0,
+						evals_1: Hal::DevMem::to_const(evals_1),
+					})
+				}
+			}
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut folded_multilins = Vec::new();
+	hal.execute(|exec| {
+		folded_multilins = exec.map(prepared_folds.into_iter(), |exec, prepared_fold| {
+			let folded = match prepared_fold {
+				PreparedFold::OutOfPlace { evals, mut folded } => {
+					exec.fold_high(evals, challenge, &mut folded)?;
+					folded
+				}
+				PreparedFold::InPlace {
+					mut evals_0,
+					evals_1,
+				} => {
+					exec.extrapolate_line(&mut evals_0, evals_1, challenge)?;
+					evals_0
+				}
+			};
+			Ok(SumcheckMultilinear::PostFold(folded))
+		})?;
+		Ok(Vec::new())
+	})?;
+	Ok(folded_multilins)
+}
+
 /// Calculates the evaluations of the products of pairs of partially specialized multilinear
 /// polynomials for sumcheck.
 ///
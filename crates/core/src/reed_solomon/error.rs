@@ -8,6 +8,8 @@ pub enum Error {
 	EncoderSubspaceMismatch,
 	#[error("the dimension of the evaluation domain of the code does not match the parameters")]
 	SubspaceDimensionMismatch,
+	#[error("I/O error: {0}")]
+	Io(#[from] std::io::Error),
 	#[error("math error: {0}")]
 	Math(#[from] binius_math::Error),
 	#[error("NTT error: {0}")]
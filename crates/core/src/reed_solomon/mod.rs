@@ -2,6 +2,10 @@
 
 mod error;
 pub mod reed_solomon;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod streaming;
 
 pub use error::*;
 pub use reed_solomon::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use streaming::*;
@@ -0,0 +1,317 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Out-of-core Reed–Solomon encoding for codewords that are larger than host memory.
+//!
+//! [`ReedSolomonCode::encode_ext_batch_streaming`] encodes an interleaved batch of messages block
+//! by block, where each block is a group of columns of the interleaved message, and writes the
+//! codeword into a [`SpillBuffer`] that is backed by a memory-mapped temporary file. Only the
+//! working buffers for one block of columns are held in memory.
+
+use std::{
+	fmt::{self, Debug},
+	fs::File,
+	io,
+	marker::PhantomData,
+	ops::{Deref, DerefMut},
+	path::{Path, PathBuf},
+};
+
+use binius_field::{BinaryField, ExtensionField, PackedExtension, PackedField};
+use binius_maybe_rayon::prelude::*;
+use binius_ntt::{AdditiveNTT, NTTShape};
+use binius_utils::bail;
+use bytemuck::Pod;
+use memmap2::MmapMut;
+
+use super::{error::Error, reed_solomon::ReedSolomonCode};
+
+/// Default base-2 logarithm of the size in bytes of a block of message columns.
+pub const DEFAULT_LOG_BLOCK_BYTES: usize = 26;
+
+/// A buffer of packed field elements backed by a memory-mapped temporary file.
+///
+/// The file is removed from the file system when it is created, so the storage is released when
+/// the buffer is dropped. The buffer dereferences to a slice, so it can be passed to any
+/// function that takes a slice, for example to commit to a codeword with a Merkle tree.
+pub struct SpillBuffer<P> {
+	mmap: MmapMut,
+	_file: File,
+	_marker: PhantomData<P>,
+}
+
+impl<P: Pod> SpillBuffer<P> {
+	/// Creates a zero-initialized buffer of `len` elements in the default temporary directory.
+	pub fn new_zeroed(len: usize) -> io::Result<Self> {
+		Self::new_zeroed_in(std::env::temp_dir(), len)
+	}
+
+	/// Creates a zero-initialized buffer of `len` elements in the directory `dir`.
+	pub fn new_zeroed_in(dir: impl AsRef<Path>, len: usize) -> io::Result<Self> {
+		let file = tempfile::tempfile_in(dir)?;
+		file.set_len((len * size_of::<P>()) as u64)?;
+		// SAFETY: The file is anonymous, so no other process or mapping can modify it.
+		let mmap = unsafe { MmapMut::map_mut(&file)? };
+		Ok(Self {
+			mmap,
+			_file: file,
+			_marker: PhantomData,
+		})
+	}
+}
+
+impl<P: Pod> Deref for SpillBuffer<P> {
+	type Target = [P];
+
+	fn deref(&self) -> &[P] {
+		// The mapping is page-aligned, which satisfies the alignment of any packed field.
+		bytemuck::cast_slice(&self.mmap)
+	}
+}
+
+impl<P: Pod> DerefMut for SpillBuffer<P> {
+	fn deref_mut(&mut self) -> &mut [P] {
+		bytemuck::cast_slice_mut(&mut self.mmap)
+	}
+}
+
+impl<P: Pod> Debug for SpillBuffer<P> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("SpillBuffer")
+			.field("len", &self.len())
+			.finish_non_exhaustive()
+	}
+}
+
+/// Parameters of [`ReedSolomonCode::encode_ext_batch_streaming`].
+#[derive(Debug, Clone)]
+pub struct StreamingEncodeOptions {
+	/// Base-2 logarithm of the size in bytes of the block of message columns that is encoded at
+	/// once. Each thread holds one working buffer of this size.
+	pub log_block_bytes: usize,
+	/// Directory of the file backing the codeword. The default temporary directory is used if
+	/// not set.
+	pub spill_dir: Option<PathBuf>,
+}
+
+impl Default for StreamingEncodeOptions {
+	fn default() -> Self {
+		Self {
+			log_block_bytes: DEFAULT_LOG_BLOCK_BYTES,
+			spill_dir: None,
+		}
+	}
+}
+
+impl<F: BinaryField> ReedSolomonCode<F> {
+	/// Encode a batch of interleaved messages of extension field elements into a memory-mapped
+	/// buffer.
+	///
+	/// The result is the same as that of
+	/// [`encode_ext_batch_inplace`](Self::encode_ext_batch_inplace) on a buffer holding the
+	/// message, but only the message columns of one block are loaded into memory at a time. The
+	/// message itself may be a memory-mapped buffer too.
+	///
+	/// ## Throws
+	///
+	/// * If the `message` buffer does not hold `dim() << log_batch_size` field elements.
+	/// * If the file backing the codeword cannot be created.
+	pub fn encode_ext_batch_streaming<PE, NTT>(
+		&self,
+		ntt: &NTT,
+		message: &[PE],
+		log_batch_size: usize,
+		options: &StreamingEncodeOptions,
+	) -> Result<SpillBuffer<PE>, Error>
+	where
+		PE: PackedExtension<F> + Pod,
+		NTT: AdditiveNTT<F> + Sync,
+	{
+		let log_len_packed = (self.log_len() + log_batch_size).saturating_sub(PE::LOG_WIDTH);
+		let mut code = match &options.spill_dir {
+			Some(dir) => SpillBuffer::new_zeroed_in(dir, 1 << log_len_packed),
+			None => SpillBuffer::new_zeroed(1 << log_len_packed),
+		}?;
+
+		self.encode_batch_streaming(
+			ntt,
+			PE::cast_bases(message),
+			PE::cast_bases_mut(&mut code),
+			log_batch_size + PE::Scalar::LOG_DEGREE,
+			options.log_block_bytes,
+		)?;
+		Ok(code)
+	}
+
+	fn encode_batch_streaming<P: PackedField<Scalar = F>, NTT: AdditiveNTT<F> + Sync>(
+		&self,
+		ntt: &NTT,
+		message: &[P],
+		code: &mut [P],
+		log_batch_size: usize,
+		log_block_bytes: usize,
+	) -> Result<(), Error> {
+		if ntt.subspace(self.log_len()) != *self.subspace() {
+			bail!(Error::EncoderSubspaceMismatch);
+		}
+		let log_msg_len = self.log_dim() + log_batch_size;
+		let expected_msg_len = 1 << log_msg_len.saturating_sub(P::LOG_WIDTH);
+		if message.len() != expected_msg_len {
+			bail!(Error::IncorrectBufferLength {
+				expected: expected_msg_len,
+				actual: message.len(),
+			});
+		}
+
+		// Messages smaller than a packed element are not worth splitting into blocks.
+		if log_msg_len < P::LOG_WIDTH {
+			code[0] = message[0];
+			return self.encode_batch_inplace(ntt, code, log_batch_size);
+		}
+
+		let _scope = tracing::trace_span!(
+			"Reed–Solomon streaming encode",
+			log_len = self.log_len(),
+			log_batch_size = log_batch_size,
+			symbol_bits = F::N_BITS,
+		)
+		.entered();
+
+		// A block holds the same range of columns from every row of the interleaved message. A
+		// block narrower than the whole row must span full packed elements.
+		let log_block_len_packed = log_block_bytes.saturating_sub(size_of::<P>().ilog2() as usize);
+		let log_block = (log_block_len_packed + P::LOG_WIDTH)
+			.saturating_sub(self.log_dim())
+			.max(P::LOG_WIDTH)
+			.min(log_batch_size);
+		let log_row_len_packed = log_batch_size.saturating_sub(P::LOG_WIDTH);
+		let log_block_width_packed = log_block.saturating_sub(P::LOG_WIDTH);
+
+		let shape = NTTShape {
+			log_x: log_block,
+			log_y: self.log_dim(),
+			..Default::default()
+		};
+		let block_len = 1 << (self.log_dim() + log_block).saturating_sub(P::LOG_WIDTH);
+		let mut block = vec![P::zero(); block_len];
+
+		for block_index in 0..1 << (log_batch_size - log_block) {
+			let columns =
+				block_index << log_block_width_packed..(block_index + 1) << log_block_width_packed;
+			if log_block == log_batch_size {
+				block.copy_from_slice(message);
+			} else {
+				for (dst, src) in block
+					.chunks_exact_mut(1 << log_block_width_packed)
+					.zip(message.chunks_exact(1 << log_row_len_packed))
+				{
+					dst.copy_from_slice(&src[columns.clone()]);
+				}
+			}
+
+			// Every chunk of the codeword is the NTT of the message on a coset of the message
+			// domain, so the chunks are encoded independently.
+			code.par_chunks_mut(1 << (log_msg_len - P::LOG_WIDTH))
+				.enumerate()
+				.try_for_each_init(
+					|| vec![P::zero(); block_len],
+					|work, (coset, chunk)| -> Result<(), Error> {
+						work.copy_from_slice(&block);
+						ntt.forward_transform(work, shape, coset, self.log_inv_rate(), 0)?;
+
+						if log_block == log_batch_size {
+							chunk.copy_from_slice(work);
+						} else {
+							for (dst, src) in chunk
+								.chunks_exact_mut(1 << log_row_len_packed)
+								.zip(work.chunks_exact(1 << log_block_width_packed))
+							{
+								dst[columns.clone()].copy_from_slice(src);
+							}
+						}
+						Ok(())
+					},
+				)?;
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_field::{BinaryField32b, PackedBinaryField1x128b, PackedBinaryField4x32b};
+	use binius_ntt::SingleThreadedNTT;
+	use rand::{SeedableRng, rngs::StdRng};
+
+	use super::*;
+
+	fn check_streaming_matches_inplace<PE: PackedExtension<BinaryField32b> + Pod>(
+		log_dim: usize,
+		log_inv_rate: usize,
+		log_batch_size: usize,
+		log_block_bytes: usize,
+	) {
+		let mut rng = StdRng::seed_from_u64(0);
+		let ntt = SingleThreadedNTT::<BinaryField32b>::new(log_dim + log_inv_rate + 2)
+			.unwrap()
+			.multithreaded();
+		let code = ReedSolomonCode::with_ntt_subspace(&ntt, log_dim, log_inv_rate).unwrap();
+
+		let log_msg_len_packed = (log_dim + log_batch_size).saturating_sub(PE::LOG_WIDTH);
+		let message = (0..1 << log_msg_len_packed)
+			.map(|_| PE::random(&mut rng))
+			.collect::<Vec<_>>();
+
+		let options = StreamingEncodeOptions {
+			log_block_bytes,
+			..Default::default()
+		};
+		let streamed = code
+			.encode_ext_batch_streaming(&ntt, &message, log_batch_size, &options)
+			.unwrap();
+
+		let log_code_len_packed =
+			(log_dim + log_inv_rate + log_batch_size).saturating_sub(PE::LOG_WIDTH);
+		let mut expected = vec![PE::zero(); 1 << log_code_len_packed];
+		expected[..message.len()].copy_from_slice(&message);
+		code.encode_ext_batch_inplace(&ntt, &mut expected, log_batch_size)
+			.unwrap();
+
+		assert_eq!(&*streamed, &expected[..]);
+	}
+
+	#[test]
+	fn test_streaming_encode_matches_inplace() {
+		for log_block_bytes in [0, 6, 9, DEFAULT_LOG_BLOCK_BYTES] {
+			for log_batch_size in [0, 1, 4] {
+				check_streaming_matches_inplace::<PackedBinaryField4x32b>(
+					5,
+					2,
+					log_batch_size,
+					log_block_bytes,
+				);
+				check_streaming_matches_inplace::<PackedBinaryField1x128b>(
+					4,
+					1,
+					log_batch_size,
+					log_block_bytes,
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn test_streaming_encode_small_message() {
+		check_streaming_matches_inplace::<PackedBinaryField4x32b>(0, 2, 1, 6);
+	}
+
+	#[test]
+	fn test_spill_buffer_roundtrip() {
+		let mut buffer = SpillBuffer::<u64>::new_zeroed(1 << 10).unwrap();
+		assert!(buffer.iter().all(|&x| x == 0));
+		for (i, x) in buffer.iter_mut().enumerate() {
+			*x = i as u64;
+		}
+		assert_eq!(buffer[123], 123);
+	}
+}
@@ -3,8 +3,8 @@
 use std::{iter, marker::PhantomData, sync::Arc};
 
 use binius_compute::{
-	ComputeLayer, ComputeLayerExecutor, ComputeMemory, SizedSlice, SubfieldSlice,
-	alloc::ComputeAllocator, cpu::CpuMemory,
+	ComputeLayer, ComputeLayerExecutor, ComputeMemory, FSlice, SizedSlice, SubfieldSlice,
+	alloc::ComputeAllocator,
 };
 use binius_field::{ExtensionField, Field, PackedExtension, PackedField, TowerField};
 use binius_utils::bail;
@@ -47,7 +47,7 @@ pub struct RingSwitchEqInd<FSub, F> {
 }
 
 pub struct RingSwitchEqIndPrecompute<'a, F: Field, Mem: ComputeMemory<F>> {
-	evals: Mem::FSliceMut<'a>,
+	evals: Mem::FSlice<'a>,
 	row_batching_query_expansion: Mem::FSlice<'a>,
 	mle: Mem::FSliceMut<'a>,
 }
@@ -63,11 +63,10 @@ where
 		mixing_coeff: F,
 	) -> Result<Self, Error> {
 		if row_batch_coeffs.coeffs.len() < F::DEGREE {
-			bail!(Error::InvalidArgs(
-				"RingSwitchEqInd::new expects row_batch_coeffs length greater than or equal to \
-				the extension degree"
-					.into()
-			));
+			bail!(Error::InsufficientRowBatchCoeffs {
+				expected: F::DEGREE,
+				actual: row_batch_coeffs.coeffs.len(),
+			});
 		}
 
 		Ok(Self {
@@ -78,17 +77,20 @@ where
 		})
 	}
 
-	pub fn precompute_values<'a, Hal: ComputeLayer<F>, HostAllocatorType, DeviceAllocatorType>(
-		z_vals: Arc<[F]>,
+	/// Prepares the device buffers for [`Self::multilinear_extension`].
+	///
+	/// `evals` must hold the equality indicator expansion of the $z$ values, multiplied by the
+	/// mixing coefficient, as computed by
+	/// [`binius_compute::ops::scaled_eq_ind_partial_eval_batch`], which expands the $z$ values of
+	/// several ring-switching claims at once.
+	pub fn precompute_values<'a, Hal: ComputeLayer<F>, DeviceAllocatorType>(
+		evals: FSlice<'a, F, Hal>,
 		row_batch_coeffs: Arc<RowBatchCoeffs<F>>,
-		mixing_coeff: F,
 		kappa: usize,
 		hal: &Hal,
 		dev_alloc: &'a DeviceAllocatorType,
-		host_alloc: &HostAllocatorType,
 	) -> Result<RingSwitchEqIndPrecompute<'a, F, Hal::DevMem>, Error>
 	where
-		HostAllocatorType: ComputeAllocator<F, CpuMemory>,
 		DeviceAllocatorType: ComputeAllocator<F, Hal::DevMem>,
 	{
 		let extension_degree = 1 << (kappa);
@@ -102,16 +104,6 @@ where
 
 		let row_batching_query_expansion = Hal::DevMem::to_const(row_batching_query_expansion);
 
-		let n_vars = z_vals.len();
-		let mut evals = dev_alloc.alloc(1 << n_vars)?;
-
-		{
-			let host_val = host_alloc.alloc(1)?;
-			host_val[0] = mixing_coeff;
-			let mut dev_val = Hal::DevMem::slice_power_of_two_mut(&mut evals, 1);
-			hal.copy_h2d(host_val, &mut dev_val)?;
-		}
-
 		let mle = dev_alloc.alloc(evals.len())?;
 
 		Ok(RingSwitchEqIndPrecompute {
@@ -132,14 +124,12 @@ where
 		tower_level: usize,
 	) -> Result<Mem::FSlice<'a>, Error> {
 		let RingSwitchEqIndPrecompute {
-			mut evals,
+			evals,
 			row_batching_query_expansion,
 			mut mle,
 		} = precompute;
 
-		exec.tensor_expand(0, &self.z_vals, &mut evals)?;
-
-		let subfield_vector = SubfieldSlice::new(Mem::as_const(&evals), tower_level);
+		let subfield_vector = SubfieldSlice::new(evals, tower_level);
 
 		exec.fold_right(subfield_vector, row_batching_query_expansion, &mut mle)?;
 
@@ -190,7 +180,11 @@ where
 
 #[cfg(test)]
 mod tests {
-	use binius_compute::{ComputeData, ComputeHolder, cpu::layer::CpuLayerHolder};
+	use binius_compute::{
+		ComputeData, ComputeHolder,
+		cpu::{CpuMemory, layer::CpuLayerHolder},
+		ops,
+	};
 	use binius_field::{BinaryField8b, BinaryField128b};
 	use binius_math::{MultilinearQuery, eq_ind_partial_eval};
 	use iter::repeat_with;
@@ -229,21 +223,19 @@ mod tests {
 
 		let compute_data = compute_holder.to_data();
 
-		let ComputeData {
-			hal,
-			dev_alloc,
-			host_alloc,
-			..
-		} = compute_data;
+		let ComputeData { hal, dev_alloc, .. } = compute_data;
 
+		let evals =
+			ops::scaled_eq_ind_partial_eval_batch(hal, &dev_alloc, &[&z_vals], &[mixing_coeff])
+				.unwrap()
+				.pop()
+				.unwrap();
 		let precompute = RingSwitchEqInd::<FS, _>::precompute_values(
-			z_vals.clone(),
+			CpuMemory::to_const(evals),
 			row_batch_coeffs.clone(),
-			mixing_coeff,
 			kappa,
 			hal,
 			&dev_alloc,
-			&host_alloc,
 		)
 		.unwrap();
 
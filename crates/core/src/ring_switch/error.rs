@@ -1,6 +1,7 @@
 // Copyright 2024-2025 Irreducible Inc.
 
 use binius_compute::{alloc, layer};
+use binius_utils::error_code::{ErrorCode, ErrorDomain, HasErrorCode};
 
 use crate::{oracle::OracleId, polynomial, transcript};
 
@@ -12,10 +13,12 @@ pub enum Error {
 	PackingDegreeNotSupported { kappa: usize },
 	#[error("cannot call function when argument tower heights do not match")]
 	TowerLevelMismatch,
-	#[error("invalid arguments: {0}")]
-	InvalidArgs(String),
-	#[error("invalid witness: {0}")]
-	InvalidWitness(String),
+	#[error(
+		"got {actual} row-batching coefficients, expected at least the extension degree {expected}"
+	)]
+	InsufficientRowBatchCoeffs { expected: usize, actual: usize },
+	#[error("got {actual} witness multilinears, expected {expected}")]
+	IncorrectWitnessCount { expected: usize, actual: usize },
 	#[error("the PIOP compiler cannot handle evaluation claim for derived oracle {id}")]
 	EvalcheckClaimForDerivedPoly { id: OracleId },
 	#[error("the committed oracle {id} is missing from the index")]
@@ -45,3 +48,36 @@ pub enum VerificationError {
 	#[error("Transcript error: {0}")]
 	Transcript(#[from] transcript::Error),
 }
+
+impl HasErrorCode for Error {
+	fn error_code(&self) -> ErrorCode {
+		let index = match self {
+			Self::OracleTowerLevelTooHigh { .. } => 1,
+			Self::PackingDegreeNotSupported { .. } => 2,
+			Self::TowerLevelMismatch => 3,
+			Self::InsufficientRowBatchCoeffs { .. } => 4,
+			Self::IncorrectWitnessCount { .. } => 5,
+			Self::EvalcheckClaimForDerivedPoly { .. } => 6,
+			Self::OracleToCommitIndexMissingEntry { .. } => 7,
+			Self::Math(_) => 8,
+			Self::Transcript(_) => 9,
+			Self::Polynomial(_) => 10,
+			Self::HAL(_) => 11,
+			Self::ComputeError(_) => 12,
+			Self::AllocError(_) => 13,
+			Self::VerificationError(err) => return err.error_code(),
+		};
+		ErrorCode::new(ErrorDomain::RingSwitch, index)
+	}
+}
+
+impl HasErrorCode for VerificationError {
+	fn error_code(&self) -> ErrorCode {
+		let index = match self {
+			Self::IncorrectEvaluation => 101,
+			Self::IncorrectRowBatchedSum => 102,
+			Self::Transcript(_) => 103,
+		};
+		ErrorCode::new(ErrorDomain::RingSwitch, index)
+	}
+}
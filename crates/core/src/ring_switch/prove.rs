@@ -3,8 +3,7 @@
 use std::{iter, sync::Arc};
 
 use binius_compute::{
-	ComputeLayer, ComputeLayerExecutor, ComputeMemory, FSlice, alloc::ComputeAllocator,
-	cpu::CpuMemory, layer,
+	ComputeLayer, ComputeLayerExecutor, ComputeMemory, FSlice, alloc::ComputeAllocator, layer, ops,
 };
 use binius_field::{Field, PackedField, PackedFieldIndexable};
 use binius_math::{
@@ -39,14 +38,13 @@ pub struct ReducedWitness<'a, F: Field, Hal: ComputeLayer<F>> {
 	pub sumcheck_claims: Vec<PIOPSumcheckClaim<F>>,
 }
 
-pub fn prove<'a, F, P, M, Challenger_, Hal, HostAllocatorType, DeviceAllocatorType>(
+pub fn prove<'a, F, P, M, Challenger_, Hal, DeviceAllocatorType>(
 	system: &EvalClaimSystem<F>,
 	witnesses: &[M],
 	transcript: &mut ProverTranscript<Challenger_>,
-	memoized_data: MemoizedData<P>,
+	memoized_data: &mut MemoizedData<P>,
 	hal: &Hal,
 	dev_alloc: &'a DeviceAllocatorType,
-	host_alloc: &HostAllocatorType,
 ) -> Result<ReducedWitness<'a, F, Hal>, Error>
 where
 	F: TowerTop + PackedTop<Scalar = F>,
@@ -54,13 +52,13 @@ where
 	M: MultilinearPoly<P> + Sync,
 	Challenger_: Challenger,
 	Hal: ComputeLayer<F>,
-	HostAllocatorType: ComputeAllocator<F, CpuMemory>,
 	DeviceAllocatorType: ComputeAllocator<F, Hal::DevMem>,
 {
 	if witnesses.len() != system.commit_meta.total_multilins() {
-		return Err(Error::InvalidWitness(
-			"witness length does not match the number of multilinears".into(),
-		));
+		return Err(Error::IncorrectWitnessCount {
+			expected: system.commit_meta.total_multilins(),
+			actual: witnesses.len(),
+		});
 	}
 
 	// Sample enough randomness to batch tensor elements corresponding to claims that share an
@@ -120,7 +118,6 @@ where
 		&mixing_coeffs,
 		hal,
 		dev_alloc,
-		host_alloc,
 	)?;
 	drop(calculate_ring_switch_eq_ind_span);
 
@@ -147,7 +144,7 @@ where
 fn compute_partial_evals<F, P, M>(
 	system: &EvalClaimSystem<F>,
 	witnesses: &[M],
-	mut memoized_data: MemoizedData<P>,
+	memoized_data: &mut MemoizedData<P>,
 ) -> Result<Vec<TowerTensorAlgebra<F>>, Error>
 where
 	F: TowerTop,
@@ -263,36 +260,43 @@ where
 		.collect()
 }
 
-fn make_ring_switch_eq_inds<'a, F, Hal, HostAllocatorType, DeviceAllocatorType>(
+fn make_ring_switch_eq_inds<'a, F, Hal, DeviceAllocatorType>(
 	sumcheck_claim_descs: &[PIOPSumcheckClaimDesc<F>],
 	suffix_descs: &[EvalClaimSuffixDesc<F>],
 	row_batch_coeffs: Arc<RowBatchCoeffs<F>>,
 	mixing_coeffs: &[F],
 	hal: &Hal,
 	dev_alloc: &'a DeviceAllocatorType,
-	host_alloc: &HostAllocatorType,
 ) -> Result<Vec<FSlice<'a, F, Hal>>, Error>
 where
 	F: TowerTop,
 	Hal: ComputeLayer<F>,
-	HostAllocatorType: ComputeAllocator<F, CpuMemory>,
 	DeviceAllocatorType: ComputeAllocator<F, Hal::DevMem>,
 {
 	let mut eq_inds = Vec::with_capacity(sumcheck_claim_descs.len());
 
-	let precompute = sumcheck_claim_descs
+	// The equality indicator expansions of all claims, scaled by their mixing coefficients, are
+	// computed together.
+	let points = sumcheck_claim_descs
 		.iter()
-		.zip(mixing_coeffs)
-		.map(|(claim_desc, mixing_coeffs)| {
+		.map(|claim_desc| &*suffix_descs[claim_desc.suffix_desc_idx].suffix)
+		.collect::<Vec<_>>();
+	let evals = ops::scaled_eq_ind_partial_eval_batch(
+		hal,
+		dev_alloc,
+		&points,
+		&mixing_coeffs[..points.len()],
+	)?;
+
+	let precompute = iter::zip(sumcheck_claim_descs, evals)
+		.map(|(claim_desc, evals)| {
 			let suffix_desc = &suffix_descs[claim_desc.suffix_desc_idx];
 			RingSwitchEqInd::<F, F>::precompute_values(
-				suffix_desc.suffix.clone(),
+				Hal::DevMem::to_const(evals),
 				row_batch_coeffs.clone(),
-				*mixing_coeffs,
 				suffix_desc.kappa,
 				hal,
 				dev_alloc,
-				host_alloc,
 			)
 		})
 		.collect::<Result<Vec<_>, Error>>()?;
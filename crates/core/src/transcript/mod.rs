@@ -22,7 +22,7 @@ use bytes::{Buf, BufMut, Bytes, BytesMut, buf::UninitSlice};
 pub use error::Error;
 use tracing::warn;
 
-use crate::fiat_shamir::{CanSample, CanSampleBits, Challenger};
+use crate::fiat_shamir::{Absorb, Absorber, CanSample, CanSampleBits, Challenger};
 
 /// Prover transcript over some Challenger that writes to the internal tape and `CanSample<F:
 /// TowerField>`
@@ -165,6 +165,11 @@ impl<Challenger_: Challenger> ProverTranscript<Challenger_> {
 		self.debug_assertions = debug;
 	}
 
+	/// Returns the number of bytes written to the proof tape so far.
+	pub fn bytes_written(&self) -> usize {
+		self.combined.buffer.len()
+	}
+
 	/// Returns a writeable buffer that only observes the data written, without writing it to the
 	/// proof tape.
 	///
@@ -179,6 +184,14 @@ impl<Challenger_: Challenger> ProverTranscript<Challenger_> {
 		}
 	}
 
+	/// Starts observing a value of `len` bytes framed with `label`, without writing it to the
+	/// proof tape.
+	///
+	/// See [`Absorb::absorb`] for the framing.
+	pub fn absorb(&mut self, label: &[u8], len: usize) -> Absorber<impl BufMut + '_> {
+		self.combined.challenger.absorb(label, len)
+	}
+
 	/// Returns a writeable buffer that only writes the data to the proof tape, without observing
 	/// it.
 	///
@@ -234,6 +247,11 @@ impl<Challenger_: Challenger> VerifierTranscript<Challenger_> {
 		self.debug_assertions = debug;
 	}
 
+	/// Returns the number of bytes that remain to be read from the proof tape.
+	pub fn bytes_remaining(&self) -> usize {
+		self.combined.buffer.remaining()
+	}
+
 	/// Returns a writable buffer that only observes the data written, without reading it from the
 	/// proof tape.
 	///
@@ -248,6 +266,14 @@ impl<Challenger_: Challenger> VerifierTranscript<Challenger_> {
 		}
 	}
 
+	/// Starts observing a value of `len` bytes framed with `label`, without reading it from the
+	/// proof tape.
+	///
+	/// See [`Absorb::absorb`] for the framing.
+	pub fn absorb(&mut self, label: &[u8], len: usize) -> Absorber<impl BufMut + '_> {
+		self.combined.challenger.absorb(label, len)
+	}
+
 	/// Returns a readable buffer that only reads the data from the proof tape, without observing
 	/// it.
 	///
@@ -296,6 +322,14 @@ impl<B: Buf> TranscriptReader<'_, B> {
 		self.buffer
 	}
 
+	/// Returns a reader over a different buffer with the same settings as this reader.
+	pub fn with_buffer<'b, B2: Buf>(&self, buffer: &'b mut B2) -> TranscriptReader<'b, B2> {
+		TranscriptReader {
+			buffer,
+			debug_assertions: self.debug_assertions,
+		}
+	}
+
 	pub fn read<T: DeserializeBytes>(&mut self) -> Result<T, Error> {
 		let mode = SerializationMode::CanonicalTower;
 		T::deserialize(self.buffer(), mode).map_err(Into::into)
@@ -522,7 +556,8 @@ mod tests {
 		AESTowerField8b, AESTowerField16b, AESTowerField32b, AESTowerField128b, BinaryField8b,
 		BinaryField32b, BinaryField64b, BinaryField128b, BinaryField128bPolyval,
 	};
-	use binius_hash::groestl::Groestl256;
+	use binius_hash::{VisionHasherDigest, groestl::Groestl256};
+	use digest::{Digest, FixedOutputReset, core_api::BlockSizeUser};
 	use rand::RngCore;
 
 	use super::*;
@@ -630,11 +665,13 @@ mod tests {
 		verifier_transcript.finalize().unwrap();
 	}
 
-	#[test]
-	fn test_challenger_and_observing() {
-		let mut taped_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
-		let mut untaped_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
-		let mut challenger = HasherChallenger::<Groestl256>::default();
+	fn check_challenger_and_observing<H>()
+	where
+		H: Digest + BlockSizeUser + FixedOutputReset + Default,
+	{
+		let mut taped_transcript = ProverTranscript::<HasherChallenger<H>>::new();
+		let mut untaped_transcript = ProverTranscript::<HasherChallenger<H>>::new();
+		let mut challenger = HasherChallenger::<H>::default();
 
 		const NUM_SAMPLING: usize = 32;
 		let mut random_bytes = [0u8; NUM_SAMPLING * 8];
@@ -679,6 +716,42 @@ mod tests {
 		taped_transcript.finalize().unwrap();
 	}
 
+	#[test]
+	fn test_challenger_and_observing() {
+		check_challenger_and_observing::<Groestl256>();
+	}
+
+	#[test]
+	fn test_vision_challenger_and_observing() {
+		check_challenger_and_observing::<VisionHasherDigest>();
+	}
+
+	#[test]
+	fn test_absorb_matches_between_prover_and_verifier() {
+		let boundaries = [BinaryField32b::new(1), BinaryField32b::new(2)];
+		let len = boundaries.len() * size_of::<BinaryField32b>();
+
+		let mut prover_transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
+		let mut absorber = prover_transcript.absorb(b"boundaries", len);
+		for boundary in boundaries {
+			SerializeBytes::serialize(&boundary, &mut absorber, SerializationMode::CanonicalTower)
+				.unwrap();
+		}
+		drop(absorber);
+		let sampled: BinaryField128b = prover_transcript.sample();
+
+		let mut verifier_transcript = prover_transcript.into_verifier();
+		let mut absorber = verifier_transcript.absorb(b"boundaries", len);
+		for boundary in boundaries {
+			SerializeBytes::serialize(&boundary, &mut absorber, SerializationMode::CanonicalTower)
+				.unwrap();
+		}
+		drop(absorber);
+		let sampled_res: BinaryField128b = verifier_transcript.sample();
+		assert_eq!(sampled_res, sampled);
+		verifier_transcript.finalize().unwrap();
+	}
+
 	#[test]
 	fn test_transcript_debug() {
 		let mut transcript = ProverTranscript::<HasherChallenger<Groestl256>>::new();
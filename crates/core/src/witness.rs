@@ -19,7 +19,7 @@ pub struct IndexEntry<'a, P: PackedField> {
 ///
 /// A [`crate::oracle::MultilinearOracleSet`] indexes multilinear polynomial oracles by assigning
 /// unique, sequential oracle IDs.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct MultilinearExtensionIndex<'a, P>
 where
 	P: PackedField,
@@ -63,12 +63,7 @@ fn test_prove_verify_claim_reduction_with_naive_validation() {
 
 	let mut compute_holder = CpuLayerHolder::<B128>::new(1 << 7, 1 << 12);
 
-	let ComputeData {
-		hal,
-		host_alloc,
-		dev_alloc,
-		..
-	} = compute_holder.to_data();
+	let ComputeData { hal, dev_alloc, .. } = compute_holder.to_data();
 
 	let rng = StdRng::seed_from_u64(0);
 	let oracles = make_test_oracle_set();
@@ -79,8 +74,7 @@ fn test_prove_verify_claim_reduction_with_naive_validation() {
 		let ReducedWitness {
 			transparents: transparent_witnesses,
 			sumcheck_claims: prover_sumcheck_claims,
-		} = prove(&system, &witnesses, &mut proof, MemoizedData::new(), hal, &dev_alloc, &host_alloc)
-			.unwrap();
+		} = prove(&system, &witnesses, &mut proof, &mut MemoizedData::new(), hal, &dev_alloc).unwrap();
 
 		let mut proof = proof.into_verifier();
 		let ReducedClaim {
@@ -12,64 +12,101 @@ use std::{
 use binius_compute::{
 	ComputeData, ComputeHolder, ComputeLayerExecutor, KernelExecutor,
 	alloc::{BumpAllocator, ComputeAllocator, HostBumpAllocator},
-	cpu::layer::count_total_local_buffer_sizes,
+	cpu::layer::{
+		count_total_local_buffer_sizes, mem_map_len, ntt_transform_packed_ext, rs_encode_packed,
+		validate_fold_lengths, validate_fri_fold_lengths, validate_mask_len, validate_ntt_shape,
+		validate_rs_encode_lengths,
+	},
 	each_generic_tower_subfield as each_tower_subfield,
 	layer::{ComputeLayer, Error, FSlice, FSliceMut, KernelBuffer, KernelMemMap},
 	memory::{ComputeMemory, SizedSlice, SlicesBatch, SubfieldSlice},
+	profile::ProfilerHook,
 };
 use binius_field::{
-	AESTowerField8b, AESTowerField128b, BinaryField8b, BinaryField128b, ByteSlicedUnderlier,
-	ExtensionField, Field, PackedBinaryField1x128b, PackedBinaryField2x128b,
-	PackedBinaryField4x128b, PackedExtension, PackedField,
+	AESTowerField8b, AESTowerField128b, BinaryField, BinaryField8b, BinaryField128b,
+	ByteSlicedUnderlier, ExtensionField, Field, PackedBinaryField1x128b, PackedBinaryField2x128b,
+	PackedBinaryField4x128b, PackedExtension, PackedField, PackedSubfield,
 	as_packed_field::{PackScalar, PackedType},
 	linear_transformation::{PackedTransformationFactory, Transformation},
 	make_aes_to_binary_packed_transformer, make_binary_to_aes_packed_transformer,
+	packed::{get_packed_slice, set_packed_slice},
 	tower::{PackedTop, TowerFamily},
 	tower_levels::TowerLevel16,
 	underlier::{NumCast, UnderlierWithBitOps, WithUnderlier},
 	unpack_if_possible, unpack_if_possible_mut,
 	util::inner_product_par,
 };
-use binius_math::{ArithCircuit, CompositionPoly, RowsBatchRef, tensor_prod_eq_ind};
+use binius_hash::multi_digest::ParallelDigest;
+use binius_math::{
+	ArithCircuit, CompositionPoly, RowsBatchRef, extrapolate_line_scalar, tensor_prod_eq_ind,
+};
 use binius_maybe_rayon::{
 	iter::{
-		IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator,
-		ParallelIterator,
+		IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
+		IntoParallelRefMutIterator, ParallelIterator,
 	},
 	prelude::ParallelBridge,
 	slice::{ParallelSlice, ParallelSliceMut},
 };
-use binius_ntt::{AdditiveNTT, fri::fold_interleaved_allocated};
+use binius_ntt::{
+	AdditiveNTT, NTTShape,
+	fri::{fold_interleaved_allocated, fold_interleaved_inplace},
+};
 use binius_utils::{
 	checked_arithmetics::{checked_int_div, strict_log_2},
-	rayon::get_log_max_threads,
+	mem::slice_uninit_mut,
 	strided_array::StridedArray2DViewMut,
 };
 use bytemuck::{Pod, zeroed_vec};
+use digest::{FixedOutputReset, Output, core_api::BlockSizeUser};
 use itertools::izip;
 use thread_local::ThreadLocal;
 
 use crate::{
 	arith_circuit::ArithCircuitPoly,
 	memory::{PackedMemory, PackedMemorySliceMut},
+	threading::{Scheduler, ThreadingConfig},
 };
 
+static GLOBAL_SCHEDULER: Scheduler = Scheduler::GLOBAL;
+
 /// Optimized CPU implementation of the compute layer.
 #[derive(Debug)]
 pub struct FastCpuLayer<T: TowerFamily, P: PackedTop<T>> {
 	kernel_buffers: ThreadLocal<RefCell<Vec<P>>>,
+	profiler: ProfilerHook,
+	scheduler: Scheduler,
 	_phantom: PhantomData<(P, T)>,
 }
 
-impl<T: TowerFamily, P: PackedTop<T>> Default for FastCpuLayer<T, P> {
-	fn default() -> Self {
+impl<T: TowerFamily, P: PackedTop<T>> FastCpuLayer<T, P> {
+	/// Creates a layer with the given profiling hook and thread pool configuration.
+	pub fn new(profiler: ProfilerHook, threading: &ThreadingConfig) -> Self {
+		Self::with_scheduler(profiler, Scheduler::new(threading))
+	}
+
+	/// Creates a layer that reports every operation to the given profiling hook.
+	pub fn with_profiler(profiler: ProfilerHook) -> Self {
+		Self::with_scheduler(profiler, Scheduler::GLOBAL)
+	}
+
+	fn with_scheduler(profiler: ProfilerHook, scheduler: Scheduler) -> Self {
+		let n_threads = scheduler.install(binius_maybe_rayon::current_num_threads);
 		Self {
-			kernel_buffers: ThreadLocal::with_capacity(1 << get_log_max_threads()),
+			kernel_buffers: ThreadLocal::with_capacity(n_threads.next_power_of_two()),
+			profiler,
+			scheduler,
 			_phantom: PhantomData,
 		}
 	}
 }
 
+impl<T: TowerFamily, P: PackedTop<T>> Default for FastCpuLayer<T, P> {
+	fn default() -> Self {
+		Self::with_profiler(ProfilerHook::default())
+	}
+}
+
 impl<T: TowerFamily, P: PackedTop<T>> ComputeLayer<T::B128> for FastCpuLayer<T, P> {
 	type Exec<'b> = FastCpuExecutor<'b, T, P>;
 	type DevMem = PackedMemory<P>;
@@ -79,6 +116,7 @@ impl<T: TowerFamily, P: PackedTop<T>> ComputeLayer<T::B128> for FastCpuLayer<T,
 		src: &[T::B128],
 		dst: &mut FSliceMut<'_, T::B128, Self>,
 	) -> Result<(), Error> {
+		let _op = self.profiler.field_op::<T::B128>("copy_h2d", 2 * src.len());
 		if src.len() != dst.len() {
 			return Err(Error::InputValidation(
 				"precondition: src and dst buffers must have the same length".to_string(),
@@ -92,11 +130,15 @@ impl<T: TowerFamily, P: PackedTop<T>> ComputeLayer<T::B128> for FastCpuLayer<T,
 				Ok(())
 			},
 			|packed| {
-				src.par_chunks_exact(P::WIDTH)
-					.zip(packed.par_iter_mut())
-					.for_each(|(input, output)| {
-						*output = PackedField::from_scalars(input.iter().copied());
-					});
+				let min_len = self.scheduler.min_split_lens().copy.div_ceil(P::WIDTH);
+				self.scheduler.install(|| {
+					src.par_chunks_exact(P::WIDTH)
+						.zip(packed.par_iter_mut())
+						.with_min_len(min_len)
+						.for_each(|(input, output)| {
+							*output = PackedField::from_scalars(input.iter().copied());
+						});
+				});
 
 				Ok(())
 			},
@@ -104,6 +146,7 @@ impl<T: TowerFamily, P: PackedTop<T>> ComputeLayer<T::B128> for FastCpuLayer<T,
 	}
 
 	fn copy_d2h(&self, src: FSlice<'_, T::B128, Self>, dst: &mut [T::B128]) -> Result<(), Error> {
+		let _op = self.profiler.field_op::<T::B128>("copy_d2h", 2 * src.len());
 		if src.len() != dst.len() {
 			return Err(Error::InputValidation(
 				"precondition: src and dst buffers must have the same length".to_string(),
@@ -118,18 +161,21 @@ impl<T: TowerFamily, P: PackedTop<T>> ComputeLayer<T::B128> for FastCpuLayer<T,
 				Ok(())
 			},
 			|packed: &[P]| {
-				(*dst.borrow_mut())
-					.par_chunks_exact_mut(P::WIDTH)
-					.zip(packed.par_iter())
-					.for_each(|(output, input)| {
-						for (input, output) in input.iter().zip(output.iter_mut()) {
-							*output = input;
-						}
-					});
+				let min_len = self.scheduler.min_split_lens().copy.div_ceil(P::WIDTH);
+				let mut dst = dst.borrow_mut();
+				let dst: &mut [T::B128] = &mut dst;
+				self.scheduler.install(|| {
+					dst.par_chunks_exact_mut(P::WIDTH)
+						.zip(packed.par_iter())
+						.with_min_len(min_len)
+						.for_each(|(output, input)| {
+							for (input, output) in input.iter().zip(output.iter_mut()) {
+								*output = input;
+							}
+						});
+				});
 
-				for (input, output) in
-					PackedField::iter_slice(packed).zip(dst.borrow_mut().iter_mut())
-				{
+				for (input, output) in PackedField::iter_slice(packed).zip(dst.iter_mut()) {
 					*output = input;
 				}
 				Ok(())
@@ -142,6 +188,7 @@ impl<T: TowerFamily, P: PackedTop<T>> ComputeLayer<T::B128> for FastCpuLayer<T,
 		src: FSlice<'_, T::B128, Self>,
 		dst: &mut FSliceMut<'_, T::B128, Self>,
 	) -> Result<(), Error> {
+		let _op = self.profiler.field_op::<T::B128>("copy_d2d", 2 * src.len());
 		if src.len() != dst.len() {
 			return Err(Error::InputValidation(
 				"precondition: src and dst buffers must have the same length".to_string(),
@@ -168,7 +215,12 @@ impl<T: TowerFamily, P: PackedTop<T>> ComputeLayer<T::B128> for FastCpuLayer<T,
 	where
 		'b: 'a,
 	{
-		f(&mut FastCpuExecutor::<'a, T, P>::new(&self.kernel_buffers))
+		f(&mut FastCpuExecutor::<'a, T, P> {
+			kernel_buffers: &self.kernel_buffers,
+			profiler: self.profiler.clone(),
+			scheduler: &self.scheduler,
+			_phantom_data: PhantomData,
+		})
 	}
 
 	fn fill(
@@ -176,6 +228,7 @@ impl<T: TowerFamily, P: PackedTop<T>> ComputeLayer<T::B128> for FastCpuLayer<T,
 		slice: &mut <Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'_>,
 		value: T::B128,
 	) -> Result<(), Error> {
+		let _op = self.profiler.field_op::<T::B128>("fill", slice.len());
 		match slice {
 			PackedMemorySliceMut::Slice(items) => {
 				items.fill(P::broadcast(value));
@@ -186,10 +239,39 @@ impl<T: TowerFamily, P: PackedTop<T>> ComputeLayer<T::B128> for FastCpuLayer<T,
 		};
 		Ok(())
 	}
+
+	fn fill_masked(
+		&self,
+		slice: &mut <Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'_>,
+		value: T::B128,
+		len: usize,
+	) -> Result<(), Error> {
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("fill_masked", slice.len());
+		validate_mask_len(len, slice.len())?;
+		let data = slice.as_slice_mut();
+		let (prefix, padding) = data.split_at_mut(len >> P::LOG_WIDTH);
+		prefix.fill(P::broadcast(value));
+		if let Some((boundary, padding)) = padding.split_first_mut() {
+			let boundary_len = len % P::WIDTH;
+			*boundary = P::from_fn(|i| {
+				if i < boundary_len {
+					value
+				} else {
+					T::B128::ZERO
+				}
+			});
+			padding.fill(P::zero());
+		}
+		Ok(())
+	}
 }
 
 pub struct FastCpuExecutor<'a, T: TowerFamily, P: PackedTop<T>> {
 	kernel_buffers: &'a ThreadLocal<RefCell<Vec<P>>>,
+	profiler: ProfilerHook,
+	scheduler: &'a Scheduler,
 	_phantom_data: PhantomData<T>,
 }
 
@@ -197,6 +279,8 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> Clone for FastCpuExecutor<'a, T, P> {
 	fn clone(&self) -> Self {
 		Self {
 			kernel_buffers: self.kernel_buffers,
+			profiler: self.profiler.clone(),
+			scheduler: self.scheduler,
 			_phantom_data: PhantomData,
 		}
 	}
@@ -206,6 +290,8 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> FastCpuExecutor<'a, T, P> {
 	pub fn new(kernel_buffers: &'a ThreadLocal<RefCell<Vec<P>>>) -> Self {
 		Self {
 			kernel_buffers,
+			profiler: ProfilerHook::default(),
+			scheduler: &GLOBAL_SCHEDULER,
 			_phantom_data: PhantomData,
 		}
 	}
@@ -224,8 +310,14 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> FastCpuExecutor<'a, T, P> {
 		let log_chunks_range = KernelMemMap::log_chunks_range(&mem_maps)
 			.ok_or_else(|| Error::InputValidation("no chunks range found".to_string()))?;
 
-		// Choose the number of chunks based on the range and the number of threads available.
-		let log_chunks = (get_log_max_threads() + 1)
+		// Choose the number of chunks based on the range, the number of threads available, and the
+		// minimum chunk length.
+		let total_len = mem_maps.iter().map(mem_map_len).sum::<usize>();
+		let log_max_chunks_by_len = (total_len / self.scheduler.min_split_lens().kernels.max(1))
+			.max(1)
+			.ilog2() as usize;
+		let log_chunks = (self.scheduler.log_max_threads() + 1)
+			.min(log_max_chunks_by_len)
 			.min(log_chunks_range.end)
 			.max(log_chunks_range.start);
 		let total_alloc = count_total_local_buffer_sizes(&mem_maps, log_chunks);
@@ -310,28 +402,88 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> ComputeLayerExecutor<T::B128>
 		a_in: SubfieldSlice<'_, T::B128, Self::DevMem>,
 		b_in: <Self::DevMem as ComputeMemory<T::B128>>::FSlice<'_>,
 	) -> Result<Self::OpValue, Error> {
-		if a_in.slice.len() << (<T::B128 as ExtensionField<T::B1>>::LOG_DEGREE - a_in.tower_level)
-			!= b_in.len()
-		{
-			return Err(Error::InputValidation(
-				"precondition: a_in and b_in must have the same length".to_string(),
-			));
-		}
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("inner_product", a_in.slice.len() + b_in.len());
+		self.scheduler.install(|| {
+			if a_in.slice.len()
+				<< (<T::B128 as ExtensionField<T::B1>>::LOG_DEGREE - a_in.tower_level)
+				!= b_in.len()
+			{
+				return Err(Error::InputValidation(
+					"precondition: a_in and b_in must have the same length".to_string(),
+				));
+			}
 
-		fn inner_product_par_impl<FSub: Field, P: PackedExtension<FSub>>(
-			a_in: &[P],
-			b_in: &[P],
-		) -> P::Scalar {
-			inner_product_par(b_in, PackedExtension::cast_bases(a_in))
-		}
+			fn inner_product_par_impl<FSub: Field, P: PackedExtension<FSub>>(
+				a_in: &[P],
+				b_in: &[P],
+			) -> P::Scalar {
+				inner_product_par(b_in, PackedExtension::cast_bases(a_in))
+			}
 
-		let result = each_tower_subfield!(
-			a_in.tower_level,
-			T,
-			inner_product_par_impl::<_, P>(a_in.slice.as_slice(), b_in.as_slice())
-		);
+			let result = each_tower_subfield!(
+				a_in.tower_level,
+				T,
+				inner_product_par_impl::<_, P>(a_in.slice.as_slice(), b_in.as_slice())
+			);
+
+			Ok(result)
+		})
+	}
+
+	fn inner_product_masked(
+		&mut self,
+		a_in: SubfieldSlice<'_, T::B128, Self::DevMem>,
+		b_in: <Self::DevMem as ComputeMemory<T::B128>>::FSlice<'_>,
+		len: usize,
+	) -> Result<Self::OpValue, Error> {
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("inner_product_masked", a_in.slice.len() + b_in.len());
+		self.scheduler.install(|| {
+			if a_in.slice.len()
+				<< (<T::B128 as ExtensionField<T::B1>>::LOG_DEGREE - a_in.tower_level)
+				!= b_in.len()
+			{
+				return Err(Error::InputValidation(
+					"precondition: a_in and b_in must have the same length".to_string(),
+				));
+			}
+			validate_mask_len(len, b_in.len())?;
+
+			fn inner_product_masked_par_impl<FSub: Field, P: PackedExtension<FSub>>(
+				a_in: &[P],
+				b_in: &[P],
+				len: usize,
+			) -> P::Scalar {
+				let a_in = PackedExtension::cast_bases(a_in);
+
+				// The prefix that fills whole packed elements of both inputs is computed in
+				// parallel, and the few remaining elements one by one.
+				let aligned_len = len - len % PackedSubfield::<P, FSub>::WIDTH;
+				let aligned = if aligned_len == 0 {
+					P::Scalar::ZERO
+				} else {
+					inner_product_par(
+						&b_in[..aligned_len >> P::LOG_WIDTH],
+						&a_in[..aligned_len >> PackedSubfield::<P, FSub>::LOG_WIDTH],
+					)
+				};
+				let rest = (aligned_len..len)
+					.map(|i| get_packed_slice(b_in, i) * get_packed_slice(a_in, i))
+					.sum::<P::Scalar>();
+				aligned + rest
+			}
+
+			let result = each_tower_subfield!(
+				a_in.tower_level,
+				T,
+				inner_product_masked_par_impl::<_, P>(a_in.slice.as_slice(), b_in.as_slice(), len)
+			);
 
-		Ok(result)
+			Ok(result)
+		})
 	}
 
 	fn tensor_expand(
@@ -340,8 +492,13 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> ComputeLayerExecutor<T::B128>
 		coordinates: &[T::B128],
 		data: &mut <Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'_>,
 	) -> Result<(), Error> {
-		tensor_prod_eq_ind(log_n, data.as_slice_mut(), coordinates)
-			.map_err(|_| Error::InputValidation("tensor dimensions are invalid".to_string()))
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("tensor_expand", 2 * data.len());
+		self.scheduler.install(|| {
+			tensor_prod_eq_ind(log_n, data.as_slice_mut(), coordinates)
+				.map_err(|_| Error::InputValidation("tensor dimensions are invalid".to_string()))
+		})
 	}
 
 	fn accumulate_kernels(
@@ -354,19 +511,26 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> ComputeLayerExecutor<T::B128>
 		) -> Result<Vec<T::B128>, Error>,
 		mem_maps: Vec<KernelMemMap<'_, T::B128, Self::DevMem>>,
 	) -> Result<Vec<Self::OpValue>, Error> {
-		self.process_kernels_chunks(
-			map,
-			|mut out1, out2| {
-				let mut out2_iter = out2.into_iter();
-				for (out1_i, out2_i) in std::iter::zip(&mut out1, &mut out2_iter) {
-					*out1_i += out2_i;
-				}
-				out1.extend(out2_iter);
-				out1
-			},
-			mem_maps,
-		)
-		.map(|opt| opt.unwrap_or_default())
+		let n_elements = mem_maps.iter().map(mem_map_len).sum();
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("accumulate_kernels", n_elements);
+		let map = &map;
+		self.scheduler.install(|| {
+			self.process_kernels_chunks(
+				map,
+				|mut out1, out2| {
+					let mut out2_iter = out2.into_iter();
+					for (out1_i, out2_i) in std::iter::zip(&mut out1, &mut out2_iter) {
+						*out1_i += out2_i;
+					}
+					out1.extend(out2_iter);
+					out1
+				},
+				mem_maps,
+			)
+			.map(|opt| opt.unwrap_or_default())
+		})
 	}
 
 	fn map_kernels(
@@ -379,8 +543,13 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> ComputeLayerExecutor<T::B128>
 		) -> Result<(), Error>,
 		mem_maps: Vec<KernelMemMap<'_, T::B128, Self::DevMem>>,
 	) -> Result<(), Error> {
-		self.process_kernels_chunks(map, |_, _| {}, mem_maps)
-			.map(|_| ())
+		let n_elements = mem_maps.iter().map(mem_map_len).sum();
+		let _op = self.profiler.field_op::<T::B128>("map_kernels", n_elements);
+		let map = &map;
+		self.scheduler.install(|| {
+			self.process_kernels_chunks(map, |_, _| {}, mem_maps)
+				.map(|_| ())
+		})
 	}
 
 	fn fold_left(
@@ -389,40 +558,45 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> ComputeLayerExecutor<T::B128>
 		vec: <Self::DevMem as ComputeMemory<T::B128>>::FSlice<'_>,
 		out: &mut <Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'_>,
 	) -> Result<(), Error> {
-		let log_evals_size = strict_log_2(mat.len()).ok_or_else(|| {
-			Error::InputValidation("the length of `mat` must be a power of 2".to_string())
-		})?;
-		let log_query_size = strict_log_2(vec.len()).ok_or_else(|| {
-			Error::InputValidation("the length of `vec` must be a power of 2".to_string())
-		})?;
-
-		let out = binius_utils::mem::slice_uninit_mut(out.as_slice_mut());
-
-		fn fold_left<FSub: Field, P: PackedExtension<FSub>>(
-			mat: &[P],
-			log_evals_size: usize,
-			vec: &[P],
-			log_query_size: usize,
-			out: &mut [MaybeUninit<P>],
-		) -> Result<(), Error> {
-			let mat = PackedExtension::cast_bases(mat);
-
-			binius_math::fold_left(mat, log_evals_size, vec, log_query_size, out).map_err(|_| {
-				Error::InputValidation("the input data dimensions are wrong".to_string())
-			})
-		}
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("fold_left", mat.slice.len() + vec.len() + out.len());
+		self.scheduler.install(|| {
+			let log_evals_size = strict_log_2(mat.len()).ok_or_else(|| {
+				Error::InputValidation("the length of `mat` must be a power of 2".to_string())
+			})?;
+			let log_query_size = strict_log_2(vec.len()).ok_or_else(|| {
+				Error::InputValidation("the length of `vec` must be a power of 2".to_string())
+			})?;
+
+			let out = binius_utils::mem::slice_uninit_mut(out.as_slice_mut());
+
+			fn fold_left<FSub: Field, P: PackedExtension<FSub>>(
+				mat: &[P],
+				log_evals_size: usize,
+				vec: &[P],
+				log_query_size: usize,
+				out: &mut [MaybeUninit<P>],
+			) -> Result<(), Error> {
+				let mat = PackedExtension::cast_bases(mat);
+
+				binius_math::fold_left(mat, log_evals_size, vec, log_query_size, out).map_err(
+					|_| Error::InputValidation("the input data dimensions are wrong".to_string()),
+				)
+			}
 
-		each_tower_subfield!(
-			mat.tower_level,
-			T,
-			fold_left::<_, P>(
-				mat.slice.as_slice(),
-				log_evals_size,
-				vec.as_slice(),
-				log_query_size,
-				out,
+			each_tower_subfield!(
+				mat.tower_level,
+				T,
+				fold_left::<_, P>(
+					mat.slice.as_slice(),
+					log_evals_size,
+					vec.as_slice(),
+					log_query_size,
+					out,
+				)
 			)
-		)
+		})
 	}
 
 	fn fold_right(
@@ -431,38 +605,43 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> ComputeLayerExecutor<T::B128>
 		vec: <Self::DevMem as binius_compute::memory::ComputeMemory<T::B128>>::FSlice<'_>,
 		out: &mut <Self::DevMem as binius_compute::memory::ComputeMemory<T::B128>>::FSliceMut<'_>,
 	) -> Result<(), Error> {
-		let log_evals_size = strict_log_2(mat.len()).ok_or_else(|| {
-			Error::InputValidation("the length of `mat` must be a power of 2".to_string())
-		})?;
-		let log_query_size = strict_log_2(vec.len()).ok_or_else(|| {
-			Error::InputValidation("the length of `vec` must be a power of 2".to_string())
-		})?;
-
-		fn fold_right<FSub: Field, P: PackedExtension<FSub>>(
-			mat: &[P],
-			log_evals_size: usize,
-			vec: &[P],
-			log_query_size: usize,
-			out: &mut [P],
-		) -> Result<(), Error> {
-			let mat = PackedExtension::cast_bases(mat);
-
-			binius_math::fold_right(mat, log_evals_size, vec, log_query_size, out).map_err(|_| {
-				Error::InputValidation("the input data dimensions are wrong".to_string())
-			})
-		}
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("fold_right", mat.slice.len() + vec.len() + out.len());
+		self.scheduler.install(|| {
+			let log_evals_size = strict_log_2(mat.len()).ok_or_else(|| {
+				Error::InputValidation("the length of `mat` must be a power of 2".to_string())
+			})?;
+			let log_query_size = strict_log_2(vec.len()).ok_or_else(|| {
+				Error::InputValidation("the length of `vec` must be a power of 2".to_string())
+			})?;
+
+			fn fold_right<FSub: Field, P: PackedExtension<FSub>>(
+				mat: &[P],
+				log_evals_size: usize,
+				vec: &[P],
+				log_query_size: usize,
+				out: &mut [P],
+			) -> Result<(), Error> {
+				let mat = PackedExtension::cast_bases(mat);
+
+				binius_math::fold_right(mat, log_evals_size, vec, log_query_size, out).map_err(
+					|_| Error::InputValidation("the input data dimensions are wrong".to_string()),
+				)
+			}
 
-		each_tower_subfield!(
-			mat.tower_level,
-			T,
-			fold_right::<_, P>(
-				mat.slice.as_slice(),
-				log_evals_size,
-				vec.as_slice(),
-				log_query_size,
-				out.as_slice_mut()
+			each_tower_subfield!(
+				mat.tower_level,
+				T,
+				fold_right::<_, P>(
+					mat.slice.as_slice(),
+					log_evals_size,
+					vec.as_slice(),
+					log_query_size,
+					out.as_slice_mut()
+				)
 			)
-		)
+		})
 	}
 
 	fn fri_fold<FSub>(
@@ -478,38 +657,200 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> ComputeLayerExecutor<T::B128>
 		FSub: binius_field::BinaryField,
 		T::B128: binius_field::ExtensionField<FSub>,
 	{
-		unpack_if_possible_mut(
-			data_out.as_slice_mut(),
-			|out| {
-				fold_interleaved_allocated(
-					ntt,
-					data_in.as_slice(),
-					challenges,
-					log_len,
-					log_batch_size,
-					out,
-				);
-			},
-			|packed| {
-				let mut out_scalars =
-					zeroed_vec(1 << (log_len - (challenges.len() - log_batch_size)));
-				fold_interleaved_allocated(
-					ntt,
-					packed,
-					challenges,
-					log_len,
-					log_batch_size,
-					&mut out_scalars,
-				);
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("fri_fold", data_in.len() + data_out.len());
+		self.scheduler.install(|| {
+			unpack_if_possible_mut(
+				data_out.as_slice_mut(),
+				|out| {
+					// The packed fold needs at least as many challenges as the packing width, so
+					// the codeword is read as scalars whenever its layout allows.
+					unpack_if_possible(
+						data_in.as_slice(),
+						|codeword| {
+							fold_interleaved_allocated(
+								ntt,
+								codeword,
+								challenges,
+								log_len,
+								log_batch_size,
+								out,
+							)
+						},
+						|_| unreachable!("the input and output have the same packed type"),
+					);
+				},
+				|packed| {
+					let mut out_scalars =
+						zeroed_vec(1 << (log_len - (challenges.len() - log_batch_size)));
+					fold_interleaved_allocated(
+						ntt,
+						data_in.as_slice(),
+						challenges,
+						log_len,
+						log_batch_size,
+						&mut out_scalars,
+					);
+
+					let mut iter = out_scalars.iter().copied();
+					for p in packed {
+						*p = PackedField::from_scalars(&mut iter);
+					}
+				},
+			);
+
+			Ok(())
+		})
+	}
 
-				let mut iter = out_scalars.iter().copied();
-				for p in packed {
-					*p = PackedField::from_scalars(&mut iter);
-				}
-			},
-		);
+	fn fri_fold_inplace<'b, FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		log_len: usize,
+		log_batch_size: usize,
+		challenges: &[T::B128],
+		mut data: <Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'b>,
+	) -> Result<<Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'b>, Error>
+	where
+		FSub: binius_field::BinaryField,
+		T::B128: binius_field::ExtensionField<FSub>,
+	{
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("fri_fold_inplace", data.len());
+		let out_len =
+			validate_fri_fold_lengths(log_len, log_batch_size, challenges.len(), data.len())?;
+		if out_len % P::WIDTH != 0 {
+			return Err(Error::InputValidation(format!(
+				"output length {out_len} is not a multiple of the memory alignment {}",
+				P::WIDTH
+			)));
+		}
 
-		Ok(())
+		self.scheduler.install(|| {
+			unpack_if_possible_mut(
+				data.as_slice_mut(),
+				|scalars| {
+					fold_interleaved_inplace(ntt, scalars, challenges, log_len, log_batch_size);
+				},
+				|packed| {
+					// The scalars of the packed field cannot be written in place, so the values
+					// are folded into a scratch buffer the size of the output instead.
+					let mut out_scalars = zeroed_vec(out_len);
+					fold_interleaved_allocated(
+						ntt,
+						packed,
+						challenges,
+						log_len,
+						log_batch_size,
+						&mut out_scalars,
+					);
+
+					let mut iter = out_scalars.iter().copied();
+					for p in &mut packed[..out_len >> P::LOG_WIDTH] {
+						*p = PackedField::from_scalars(&mut iter);
+					}
+				},
+			);
+		});
+
+		Ok(Self::DevMem::split_at_mut(data, out_len).0)
+	}
+
+	fn forward_ntt<FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+		data: &mut <Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'_>,
+	) -> Result<(), Error>
+	where
+		FSub: BinaryField,
+		T::B128: ExtensionField<FSub> + PackedExtension<FSub>,
+	{
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("forward_ntt", 2 * data.len());
+		self.scheduler.install(|| {
+			validate_ntt_shape(data.len(), shape)?;
+			ntt_transform_packed_ext(
+				ntt,
+				data.as_slice_mut(),
+				shape,
+				coset,
+				coset_bits,
+				skip_rounds,
+				false,
+			)
+		})
+	}
+
+	fn inverse_ntt<FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+		data: &mut <Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'_>,
+	) -> Result<(), Error>
+	where
+		FSub: BinaryField,
+		T::B128: ExtensionField<FSub> + PackedExtension<FSub>,
+	{
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("inverse_ntt", 2 * data.len());
+		self.scheduler.install(|| {
+			validate_ntt_shape(data.len(), shape)?;
+			ntt_transform_packed_ext(
+				ntt,
+				data.as_slice_mut(),
+				shape,
+				coset,
+				coset_bits,
+				skip_rounds,
+				true,
+			)
+		})
+	}
+
+	fn rs_encode<FSub>(
+		&mut self,
+		ntt: &(impl AdditiveNTT<FSub> + Sync),
+		log_dim: usize,
+		log_inv_rate: usize,
+		log_batch_size: usize,
+		message: <Self::DevMem as ComputeMemory<T::B128>>::FSlice<'_>,
+		codeword: &mut <Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'_>,
+	) -> Result<(), Error>
+	where
+		FSub: BinaryField,
+		T::B128: ExtensionField<FSub> + PackedExtension<FSub>,
+	{
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("rs_encode", message.len() + codeword.len());
+		self.scheduler.install(|| {
+			validate_rs_encode_lengths(
+				log_dim,
+				log_inv_rate,
+				log_batch_size,
+				message.len(),
+				codeword.len(),
+			)?;
+			rs_encode_packed(
+				ntt,
+				log_dim,
+				log_inv_rate,
+				log_batch_size,
+				message.as_slice(),
+				codeword.as_slice_mut(),
+			)
+		})
 	}
 
 	fn extrapolate_line(
@@ -518,35 +859,228 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> ComputeLayerExecutor<T::B128>
 		evals_1: <Self::DevMem as ComputeMemory<T::B128>>::FSlice<'_>,
 		z: T::B128,
 	) -> Result<(), Error> {
-		if evals_0.len() != evals_1.len() {
-			return Err(Error::InputValidation(
-				"precondition: evals_0 and evals_1 must have the same length".to_string(),
-			));
-		}
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("extrapolate_line", 2 * evals_0.len() + evals_1.len());
+		self.scheduler.install(|| {
+			if evals_0.len() != evals_1.len() {
+				return Err(Error::InputValidation(
+					"precondition: evals_0 and evals_1 must have the same length".to_string(),
+				));
+			}
 
-		if try_extrapolate_line_byte_sliced::<_, PackedBinaryField1x128b>(
-			evals_0.as_slice_mut(),
-			evals_1.as_slice(),
-			z,
-		) || try_extrapolate_line_byte_sliced::<_, PackedBinaryField2x128b>(
-			evals_0.as_slice_mut(),
-			evals_1.as_slice(),
-			z,
-		) || try_extrapolate_line_byte_sliced::<_, PackedBinaryField4x128b>(
-			evals_0.as_slice_mut(),
-			evals_1.as_slice(),
-			z,
-		) {
-		} else {
-			let z = P::broadcast(z);
-			evals_0
-				.as_slice_mut()
-				.par_iter_mut()
-				.zip(evals_1.as_slice().par_iter())
-				.for_each(|(x0, x1)| *x0 += (*x1 - *x0) * z);
-		}
+			if try_extrapolate_line_byte_sliced::<_, PackedBinaryField1x128b>(
+				evals_0.as_slice_mut(),
+				evals_1.as_slice(),
+				z,
+			) || try_extrapolate_line_byte_sliced::<_, PackedBinaryField2x128b>(
+				evals_0.as_slice_mut(),
+				evals_1.as_slice(),
+				z,
+			) || try_extrapolate_line_byte_sliced::<_, PackedBinaryField4x128b>(
+				evals_0.as_slice_mut(),
+				evals_1.as_slice(),
+				z,
+			) {
+			} else {
+				let z = P::broadcast(z);
+				let min_len = self
+					.scheduler
+					.min_split_lens()
+					.extrapolate_line
+					.div_ceil(P::WIDTH);
+				evals_0
+					.as_slice_mut()
+					.par_iter_mut()
+					.zip(evals_1.as_slice().par_iter())
+					.with_min_len(min_len)
+					.for_each(|(x0, x1)| *x0 += (*x1 - *x0) * z);
+			}
 
-		Ok(())
+			Ok(())
+		})
+	}
+
+	fn fold_low(
+		&mut self,
+		evals: <Self::DevMem as ComputeMemory<T::B128>>::FSlice<'_>,
+		z: T::B128,
+		out: &mut <Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'_>,
+	) -> Result<(), Error> {
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("fold_low", evals.len() + out.len());
+		self.scheduler.install(|| {
+			validate_fold_lengths(evals.len(), out.len())?;
+
+			let out_len = out.len();
+			let evals = evals.as_slice();
+			let out = out.as_slice_mut();
+			let min_len = self
+				.scheduler
+				.min_split_lens()
+				.extrapolate_line
+				.div_ceil(P::WIDTH);
+			if P::LOG_WIDTH == 0 {
+				let z = P::broadcast(z);
+				out.par_iter_mut()
+					.zip(evals.par_chunks_exact(2))
+					.with_min_len(min_len)
+					.for_each(|(out, evals)| *out = evals[0] + (evals[1] - evals[0]) * z);
+			} else if out_len >= P::WIDTH {
+				// Unzipping two consecutive packed elements separates the evaluations at even
+				// and odd indices, which are the evaluations at 0 and 1 of the folded variable.
+				let z = P::broadcast(z);
+				out.par_iter_mut()
+					.zip(evals.par_chunks_exact(2))
+					.with_min_len(min_len)
+					.for_each(|(out, evals)| {
+						let (x0, x1) = evals[0].unzip(evals[1], 0);
+						*out = x0 + (x1 - x0) * z;
+					});
+			} else {
+				for i in 0..out_len {
+					let x0 = get_packed_slice(evals, 2 * i);
+					let x1 = get_packed_slice(evals, 2 * i + 1);
+					set_packed_slice(out, i, extrapolate_line_scalar(x0, x1, z));
+				}
+			}
+
+			Ok(())
+		})
+	}
+
+	fn fold_high(
+		&mut self,
+		evals: <Self::DevMem as ComputeMemory<T::B128>>::FSlice<'_>,
+		z: T::B128,
+		out: &mut <Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'_>,
+	) -> Result<(), Error> {
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("fold_high", evals.len() + out.len());
+		self.scheduler.install(|| {
+			validate_fold_lengths(evals.len(), out.len())?;
+
+			let out_len = out.len();
+			let evals = evals.as_slice();
+			let out = out.as_slice_mut();
+			if out_len >= P::WIDTH {
+				let z = P::broadcast(z);
+				let min_len = self
+					.scheduler
+					.min_split_lens()
+					.extrapolate_line
+					.div_ceil(P::WIDTH);
+				let (evals_0, evals_1) = evals.split_at(out.len());
+				out.par_iter_mut()
+					.zip(evals_0.par_iter().zip(evals_1.par_iter()))
+					.with_min_len(min_len)
+					.for_each(|(out, (x0, x1))| *out = *x0 + (*x1 - *x0) * z);
+			} else {
+				for i in 0..out_len {
+					let x0 = get_packed_slice(evals, i);
+					let x1 = get_packed_slice(evals, i + out_len);
+					set_packed_slice(out, i, extrapolate_line_scalar(x0, x1, z));
+				}
+			}
+
+			Ok(())
+		})
+	}
+
+	fn fold_low_masked(
+		&mut self,
+		evals: <Self::DevMem as ComputeMemory<T::B128>>::FSlice<'_>,
+		len: usize,
+		z: T::B128,
+		out: &mut <Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'_>,
+	) -> Result<(), Error> {
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("fold_low_masked", evals.len() + out.len());
+		self.scheduler.install(|| {
+			validate_fold_lengths(evals.len(), out.len())?;
+			validate_mask_len(len, evals.len())?;
+
+			let out_len = out.len();
+			let evals = evals.as_slice();
+			let out = out.as_slice_mut();
+			if out_len >= P::WIDTH {
+				let z = P::broadcast(z);
+				let min_len = self
+					.scheduler
+					.min_split_lens()
+					.extrapolate_line
+					.div_ceil(P::WIDTH);
+				out.par_iter_mut()
+					.enumerate()
+					.with_min_len(min_len)
+					.for_each(|(i, out)| {
+						let evals_0 = get_packed_masked(evals, 2 * i, len);
+						let evals_1 = get_packed_masked(evals, 2 * i + 1, len);
+						let (x0, x1) = if P::LOG_WIDTH == 0 {
+							(evals_0, evals_1)
+						} else {
+							evals_0.unzip(evals_1, 0)
+						};
+						*out = x0 + (x1 - x0) * z;
+					});
+			} else {
+				for i in 0..out_len {
+					let x0 = get_scalar_masked(evals, 2 * i, len);
+					let x1 = get_scalar_masked(evals, 2 * i + 1, len);
+					set_packed_slice(out, i, extrapolate_line_scalar(x0, x1, z));
+				}
+			}
+
+			Ok(())
+		})
+	}
+
+	fn fold_high_masked(
+		&mut self,
+		evals: <Self::DevMem as ComputeMemory<T::B128>>::FSlice<'_>,
+		len: usize,
+		z: T::B128,
+		out: &mut <Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'_>,
+	) -> Result<(), Error> {
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("fold_high_masked", evals.len() + out.len());
+		self.scheduler.install(|| {
+			validate_fold_lengths(evals.len(), out.len())?;
+			validate_mask_len(len, evals.len())?;
+
+			let out_len = out.len();
+			let evals = evals.as_slice();
+			let out = out.as_slice_mut();
+			if out_len >= P::WIDTH {
+				let z = P::broadcast(z);
+				let min_len = self
+					.scheduler
+					.min_split_lens()
+					.extrapolate_line
+					.div_ceil(P::WIDTH);
+				let half = out.len();
+				out.par_iter_mut()
+					.enumerate()
+					.with_min_len(min_len)
+					.for_each(|(i, out)| {
+						let x0 = get_packed_masked(evals, i, len);
+						let x1 = get_packed_masked(evals, i + half, len);
+						*out = x0 + (x1 - x0) * z;
+					});
+			} else {
+				for i in 0..out_len {
+					let x0 = get_scalar_masked(evals, i, len);
+					let x1 = get_scalar_masked(evals, i + out_len, len);
+					set_packed_slice(out, i, extrapolate_line_scalar(x0, x1, z));
+				}
+			}
+
+			Ok(())
+		})
 	}
 
 	fn compute_composite(
@@ -555,41 +1089,58 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> ComputeLayerExecutor<T::B128>
 		output: &mut <Self::DevMem as ComputeMemory<T::B128>>::FSliceMut<'_>,
 		composition: &ArithCircuitPoly<T::B128>,
 	) -> Result<(), Error> {
-		if inputs.row_len() != output.len() {
-			return Err(Error::InputValidation("inputs and output must be the same length".into()));
-		}
-
-		if CompositionPoly::<P>::n_vars(composition) != inputs.n_rows() {
-			return Err(Error::InputValidation("composition not match with inputs".into()));
-		}
-
-		let rows = inputs
-			.iter()
-			.map(|slice| slice.as_slice())
-			.collect::<Vec<_>>();
+		let _op = self
+			.profiler
+			.field_op::<T::B128>("compute_composite", (inputs.n_rows() + 1) * inputs.row_len());
+		self.scheduler.install(|| {
+			if inputs.row_len() != output.len() {
+				return Err(Error::InputValidation(
+					"inputs and output must be the same length".into(),
+				));
+			}
 
-		let log_chunks = get_log_max_threads() + 1;
+			if CompositionPoly::<P>::n_vars(composition) != inputs.n_rows() {
+				return Err(Error::InputValidation("composition not match with inputs".into()));
+			}
 
-		let chunk_size = (output.len() >> log_chunks).max(1);
+			let rows = inputs
+				.iter()
+				.map(|slice| slice.as_slice())
+				.collect::<Vec<_>>();
 
-		let packed_row_len = checked_int_div(inputs.row_len(), P::WIDTH);
+			let log_chunks = self.scheduler.log_max_threads() + 1;
 
-		let rows_batch = unsafe { RowsBatchRef::new_unchecked(&rows, packed_row_len) };
+			let min_chunk_size = self
+				.scheduler
+				.min_split_lens()
+				.composite
+				.div_ceil(P::WIDTH)
+				.next_power_of_two();
+			let chunk_size = (output.len() >> log_chunks)
+				.max(min_chunk_size)
+				.min(output.as_slice_mut().len())
+				.max(1);
 
-		output
-			.as_slice_mut()
-			.par_chunks_mut(chunk_size)
-			.enumerate()
-			.for_each(|(chunk_idx, output_chunk)| {
-				let offset = chunk_idx * chunk_size;
-				let rows = rows_batch.columns_subrange(offset..offset + chunk_size);
+			// Rows shorter than the packing width are stored in a single packed element.
+			let packed_row_len = inputs.row_len().div_ceil(P::WIDTH);
 
-				composition
-					.batch_evaluate(&rows, output_chunk)
-					.expect("dimensions are correct");
-			});
+			let rows_batch = unsafe { RowsBatchRef::new_unchecked(&rows, packed_row_len) };
 
-		Ok(())
+			output
+				.as_slice_mut()
+				.par_chunks_mut(chunk_size)
+				.enumerate()
+				.for_each(|(chunk_idx, output_chunk)| {
+					let offset = chunk_idx * chunk_size;
+					let rows = rows_batch.columns_subrange(offset..offset + chunk_size);
+
+					composition
+						.batch_evaluate(&rows, output_chunk)
+						.expect("dimensions are correct");
+				});
+
+			Ok(())
+		})
 	}
 
 	fn join<Out1: Send, Out2: Send>(
@@ -597,8 +1148,9 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> ComputeLayerExecutor<T::B128>
 		op1: impl Send + FnOnce(&mut Self) -> Result<Out1, Error>,
 		op2: impl Send + FnOnce(&mut Self) -> Result<Out2, Error>,
 	) -> Result<(Out1, Out2), Error> {
-		let (out1, out2) =
-			binius_maybe_rayon::join(|| op1(&mut self.clone()), || op2(&mut self.clone()));
+		let (out1, out2) = self.scheduler.install(|| {
+			binius_maybe_rayon::join(|| op1(&mut self.clone()), || op2(&mut self.clone()))
+		});
 
 		Ok((out1?, out2?))
 	}
@@ -610,11 +1162,12 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> ComputeLayerExecutor<T::B128>
 	) -> Result<Vec<Out>, Error> {
 		// `par_bridge` doesn't preserve the order of the items in the iterator,
 		// so we need to enumerate the items and sort them back after processing.
-		let mut result = iter
-			.enumerate()
-			.par_bridge()
-			.map(|(index, item)| (index, map(&mut self.clone(), item)))
-			.collect::<Vec<_>>();
+		let mut result = self.scheduler.install(|| {
+			iter.enumerate()
+				.par_bridge()
+				.map(|(index, item)| (index, map(&mut self.clone(), item)))
+				.collect::<Vec<_>>()
+		});
 		result.sort_unstable_by_key(|(index, _)| *index);
 
 		result.into_iter().map(|(_, out)| out).collect()
@@ -628,6 +1181,36 @@ impl<'a, T: TowerFamily, P: PackedTop<T>> ComputeLayerExecutor<T::B128>
 		// TODO(CRY-490)
 		todo!()
 	}
+
+	fn hash_leaves<H>(
+		&mut self,
+		data: <Self::DevMem as ComputeMemory<T::B128>>::FSlice<'_>,
+		digests: &mut [Output<H::Digest>],
+	) -> Result<(), Error>
+	where
+		H: ParallelDigest<Digest: BlockSizeUser + FixedOutputReset>,
+	{
+		let _op = self.profiler.field_op::<T::B128>("hash_leaves", data.len());
+		self.scheduler.install(|| {
+			if digests.is_empty() || data.is_empty() || data.len() % digests.len() != 0 {
+				return Err(Error::InputValidation(format!(
+					"data length {} must be a non-zero multiple of the number of digests {}",
+					data.len(),
+					digests.len()
+				)));
+			}
+
+			let chunk_len = data.len() / digests.len();
+			let packed = data.as_slice();
+			H::new().digest(
+				(0..digests.len()).into_par_iter().map(|i| {
+					(i * chunk_len..(i + 1) * chunk_len).map(|j| get_packed_slice(packed, j))
+				}),
+				slice_uninit_mut(digests),
+			);
+			Ok(())
+		})
+	}
 }
 
 /// In case when `P1` and `P2` are the same type, this function performs the extrapolation
@@ -672,6 +1255,37 @@ where
 	}
 }
 
+/// Returns the packed element at `index` with the scalars at positions `len` and above replaced by
+/// zero.
+#[inline]
+fn get_packed_masked<P: PackedField>(data: &[P], index: usize, len: usize) -> P {
+	let offset = index << P::LOG_WIDTH;
+	if offset + P::WIDTH <= len {
+		data[index]
+	} else if offset >= len {
+		P::zero()
+	} else {
+		let packed = data[index];
+		P::from_fn(|i| {
+			if offset + i < len {
+				packed.get(i)
+			} else {
+				P::Scalar::ZERO
+			}
+		})
+	}
+}
+
+/// Returns the scalar at `index`, or zero if `index` is at least `len`.
+#[inline]
+fn get_scalar_masked<P: PackedField>(data: &[P], index: usize, len: usize) -> P::Scalar {
+	if index < len {
+		get_packed_slice(data, index)
+	} else {
+		P::Scalar::ZERO
+	}
+}
+
 // Extrapolate line function that converts packed field elements to byte-sliced representation and
 // back.
 fn extrapolate_line_byte_sliced<Underlier>(
@@ -910,9 +1524,23 @@ pub struct FastCpuLayerHolder<T: TowerFamily, P: PackedTop<T>> {
 
 impl<T: TowerFamily, P: PackedTop<T>> FastCpuLayerHolder<T, P> {
 	pub fn new(host_mem_size: usize, dev_mem_size: usize) -> Self {
-		let layer = FastCpuLayer::default();
+		Self::with_threading(host_mem_size, dev_mem_size, &ThreadingConfig::default())
+	}
+
+	/// Creates a holder whose layer runs on a thread pool with the given configuration.
+	///
+	/// With [`ThreadingConfig::numa_first_touch`], the device memory is initialized on the pool
+	/// threads.
+	pub fn with_threading(
+		host_mem_size: usize,
+		dev_mem_size: usize,
+		threading: &ThreadingConfig,
+	) -> Self {
+		let layer = FastCpuLayer::new(ProfilerHook::default(), threading);
 		let host_mem = vec![T::B128::zero(); host_mem_size];
-		let dev_mem = vec![P::zero(); (dev_mem_size >> P::LOG_WIDTH).max(1)];
+		let dev_mem = layer
+			.scheduler
+			.alloc_zeroed((dev_mem_size >> P::LOG_WIDTH).max(1));
 
 		Self {
 			layer,
@@ -3,3 +3,4 @@
 pub mod arith_circuit;
 pub mod layer;
 pub mod memory;
+pub mod threading;
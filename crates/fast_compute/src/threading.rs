@@ -0,0 +1,186 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Thread pool configuration for the fast CPU compute layer.
+//!
+//! By default, the fast CPU layer runs its parallel operations on the global rayon thread pool.
+//! [`ThreadingConfig`] lets the caller instead run them on a dedicated [`ThreadPoolHandle`], such
+//! as the pool that the rest of the prover runs on, and tune how finely each operation is split
+//! into parallel work items.
+//!
+//! On multi-socket machines, memory pages are placed on the NUMA node of the thread that first
+//! writes them. With pinned threads and [`ThreadingConfig::numa_first_touch`], the device memory
+//! of [`FastCpuLayerHolder`](crate::layer::FastCpuLayerHolder) is initialized in parallel by the
+//! pool threads, so that it is spread across the nodes of the threads that later process it
+//! instead of residing entirely on the node of the allocating thread.
+//!
+//! Without the `rayon` feature, or on WebAssembly targets without atomics, there are no thread
+//! pools: every operation runs sequentially on the calling thread and the thread counts of the
+//! pool are ignored.
+
+use std::mem::MaybeUninit;
+
+use binius_maybe_rayon::{ThreadPoolBuildError, prelude::*};
+use binius_utils::rayon::ThreadPoolHandle;
+use bytemuck::Zeroable;
+
+/// Minimum number of scalars that a single parallel work item processes, by operation.
+///
+/// Splitting an operation into too many small work items makes the scheduling overhead dominate
+/// the work. The defaults impose no minimum beyond the operations' own heuristics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinSplitLens {
+	/// Minimum split length of host-device copies.
+	pub copy: usize,
+	/// Minimum split length of [`compute_composite`] evaluations.
+	///
+	/// [`compute_composite`]: binius_compute::ComputeLayerExecutor::compute_composite
+	pub composite: usize,
+	/// Minimum chunk length of [`accumulate_kernels`] and [`map_kernels`].
+	///
+	/// [`accumulate_kernels`]: binius_compute::ComputeLayerExecutor::accumulate_kernels
+	/// [`map_kernels`]: binius_compute::ComputeLayerExecutor::map_kernels
+	pub kernels: usize,
+	/// Minimum split length of [`extrapolate_line`], [`fold_low`], and [`fold_high`].
+	///
+	/// [`extrapolate_line`]: binius_compute::ComputeLayerExecutor::extrapolate_line
+	/// [`fold_low`]: binius_compute::ComputeLayerExecutor::fold_low
+	/// [`fold_high`]: binius_compute::ComputeLayerExecutor::fold_high
+	pub extrapolate_line: usize,
+}
+
+impl MinSplitLens {
+	/// Split lengths that impose no minimum.
+	pub const NONE: Self = Self {
+		copy: 1,
+		composite: 1,
+		kernels: 1,
+		extrapolate_line: 1,
+	};
+}
+
+impl Default for MinSplitLens {
+	fn default() -> Self {
+		Self::NONE
+	}
+}
+
+/// Thread pool configuration of the fast CPU compute layer.
+#[derive(Debug, Clone, Default)]
+pub struct ThreadingConfig {
+	/// The thread pool that the operations run on, which is the global rayon thread pool by
+	/// default.
+	pub thread_pool: ThreadPoolHandle,
+	/// Whether to initialize large device allocations in parallel on the pool threads, so that
+	/// their pages are placed on the NUMA nodes of the threads that touch them first.
+	pub numa_first_touch: bool,
+	/// Minimum split lengths of the parallel operations.
+	pub min_split_lens: MinSplitLens,
+}
+
+impl ThreadingConfig {
+	/// A configuration that runs all operations on a dedicated pool with a single thread.
+	///
+	/// This avoids the scheduling overhead of parallel operations when proving small statements.
+	pub fn single_threaded() -> Result<Self, ThreadPoolBuildError> {
+		Ok(Self {
+			thread_pool: ThreadPoolHandle::new(1, false)?,
+			..Default::default()
+		})
+	}
+}
+
+/// The thread pool that the fast CPU layer runs its operations on.
+#[derive(Debug, Clone)]
+pub(crate) struct Scheduler {
+	pool: ThreadPoolHandle,
+	min_split_lens: MinSplitLens,
+	numa_first_touch: bool,
+}
+
+impl Scheduler {
+	/// A scheduler that runs on the global thread pool without split length limits.
+	pub const GLOBAL: Self = Self {
+		pool: ThreadPoolHandle::GLOBAL,
+		min_split_lens: MinSplitLens::NONE,
+		numa_first_touch: false,
+	};
+
+	pub fn new(config: &ThreadingConfig) -> Self {
+		Self {
+			pool: config.thread_pool.clone(),
+			min_split_lens: config.min_split_lens,
+			numa_first_touch: config.numa_first_touch,
+		}
+	}
+
+	pub fn min_split_lens(&self) -> &MinSplitLens {
+		&self.min_split_lens
+	}
+
+	/// Returns the base-2 logarithm of the number of threads of the scheduler's thread pool,
+	/// rounded up.
+	pub fn log_max_threads(&self) -> usize {
+		(2 * self.pool.current_num_threads() - 1).ilog2() as _
+	}
+
+	/// Runs an operation on the scheduler's thread pool.
+	pub fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+		self.pool.install(op)
+	}
+
+	/// Allocates a zero-initialized vector.
+	///
+	/// With NUMA first-touch placement enabled, the vector is zeroed in parallel on the pool
+	/// threads.
+	pub fn alloc_zeroed<T: Zeroable + Send>(&self, len: usize) -> Vec<T> {
+		if !self.numa_first_touch {
+			return bytemuck::zeroed_vec(len);
+		}
+
+		let mut data = Vec::with_capacity(len);
+		let min_len = self.min_split_lens.copy;
+		self.install(|| {
+			data.spare_capacity_mut()
+				.par_iter_mut()
+				.with_min_len(min_len)
+				.for_each(|x: &mut MaybeUninit<T>| {
+					x.write(T::zeroed());
+				});
+		});
+		// Safety: all `len` elements are initialized by the loop above.
+		unsafe {
+			data.set_len(len);
+		}
+		data
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_dedicated_pool() {
+		let scheduler = Scheduler::new(&ThreadingConfig {
+			thread_pool: ThreadPoolHandle::new(2, true).unwrap(),
+			numa_first_touch: true,
+			..Default::default()
+		});
+
+		let data = scheduler.alloc_zeroed::<u64>(1 << 12);
+		assert!(data.iter().all(|&x| x == 0));
+		let sum = scheduler.install(|| (0..1000u64).into_par_iter().sum::<u64>());
+		assert_eq!(sum, 999 * 1000 / 2);
+	}
+
+	#[test]
+	fn test_single_threaded() {
+		let scheduler = Scheduler::new(&ThreadingConfig::single_threaded().unwrap());
+
+		let n_threads = scheduler.install(binius_maybe_rayon::current_num_threads);
+		assert_eq!(n_threads, 1);
+		assert_eq!(scheduler.log_max_threads(), 0);
+		let sum = scheduler.install(|| (0..1000u64).into_par_iter().sum::<u64>());
+		assert_eq!(sum, 999 * 1000 / 2);
+	}
+}
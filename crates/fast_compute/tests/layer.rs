@@ -1,15 +1,26 @@
 // Copyright 2025 Irreducible Inc.
 
-use binius_compute_test_utils::layer::{
-	test_generic_fri_fold, test_generic_kernel_add, test_generic_single_inner_product,
-	test_generic_single_inner_product_using_kernel_accumulator, test_generic_single_left_fold,
-	test_generic_single_right_fold, test_generic_single_tensor_expand,
+use binius_compute::naive::NaiveLayerHolder;
+use binius_compute_test_utils::{
+	conformance::{ConformanceConfig, run_conformance_suite},
+	differential::run_differential_suite,
+	layer::{
+		test_generic_fri_fold, test_generic_fri_fold_inplace, test_generic_kernel_add,
+		test_generic_map_and_fold_ops, test_generic_ntt, test_generic_rs_encode,
+		test_generic_segmented_sum_and_scan, test_generic_single_inner_product,
+		test_generic_single_inner_product_using_kernel_accumulator, test_generic_single_left_fold,
+		test_generic_single_right_fold, test_generic_single_tensor_expand,
+	},
+};
+use binius_fast_compute::{
+	layer::FastCpuLayerHolder,
+	threading::{MinSplitLens, ThreadingConfig},
 };
-use binius_fast_compute::layer::FastCpuLayerHolder;
 use binius_field::{
 	BinaryField16b, BinaryField128b, PackedBinaryField1x128b, PackedBinaryField2x128b,
 	PackedBinaryField4x128b, tower::CanonicalTowerFamily,
 };
+use binius_utils::rayon::ThreadPoolHandle;
 
 #[test]
 fn test_exec_single_tensor_expand() {
@@ -107,6 +118,44 @@ fn test_exec_fri_fold_zero_log_batch() {
 	);
 }
 
+#[test]
+fn test_exec_fri_fold_inplace_non_zero_log_batch() {
+	type F = BinaryField128b;
+	type FSub = BinaryField16b;
+	type P = PackedBinaryField2x128b;
+	let log_len = 10;
+	let log_batch_size = 4;
+	let log_fold_challenges = 2;
+	test_generic_fri_fold_inplace::<F, FSub, _, _>(
+		FastCpuLayerHolder::<CanonicalTowerFamily, P>::new(
+			1 << (log_len + log_batch_size + 2),
+			1 << (log_len + log_batch_size + 1),
+		),
+		log_len,
+		log_batch_size,
+		log_fold_challenges,
+	);
+}
+
+#[test]
+fn test_exec_fri_fold_inplace_zero_log_batch() {
+	type F = BinaryField128b;
+	type FSub = BinaryField16b;
+	type P = PackedBinaryField2x128b;
+	let log_len = 10;
+	let log_batch_size = 0;
+	let log_fold_challenges = 2;
+	test_generic_fri_fold_inplace::<F, FSub, _, _>(
+		FastCpuLayerHolder::<CanonicalTowerFamily, P>::new(
+			1 << (log_len + log_batch_size + 2),
+			1 << (log_len + log_batch_size + 1),
+		),
+		log_len,
+		log_batch_size,
+		log_fold_challenges,
+	);
+}
+
 #[test]
 fn test_exec_kernel_add() {
 	type F = BinaryField128b;
@@ -148,6 +197,73 @@ fn test_extrapolate_line_512b() {
 	);
 }
 
+#[test]
+fn test_fold_low_and_high_128b() {
+	type P = PackedBinaryField1x128b;
+	for log_len in [1, 10] {
+		binius_compute_test_utils::layer::test_fold_low_and_high(
+			FastCpuLayerHolder::<CanonicalTowerFamily, P>::new(
+				1 << (log_len + 3),
+				1 << (log_len + 3),
+			),
+			log_len,
+		);
+	}
+}
+
+#[test]
+fn test_fold_low_and_high_512b() {
+	type P = PackedBinaryField4x128b;
+	for log_len in [1, 2, 3, 10] {
+		binius_compute_test_utils::layer::test_fold_low_and_high(
+			FastCpuLayerHolder::<CanonicalTowerFamily, P>::new(
+				1 << (log_len + 3),
+				1 << (log_len + 3),
+			),
+			log_len,
+		);
+	}
+}
+
+#[test]
+fn test_masked_ops_128b() {
+	type P = PackedBinaryField1x128b;
+	let log_len = 10;
+	for len in [0, 1, 7, 600, 1 << log_len] {
+		binius_compute_test_utils::layer::test_generic_masked_ops::<BinaryField16b, _, _, _>(
+			FastCpuLayerHolder::<CanonicalTowerFamily, P>::new(
+				1 << (log_len + 3),
+				1 << (log_len + 3),
+			),
+			log_len,
+			len,
+		);
+	}
+}
+
+#[test]
+fn test_masked_ops_512b() {
+	type P = PackedBinaryField4x128b;
+	for (log_len, len) in [
+		(3, 0),
+		(3, 3),
+		(3, 5),
+		(10, 1),
+		(10, 7),
+		(10, 13),
+		(10, 601),
+	] {
+		binius_compute_test_utils::layer::test_generic_masked_ops::<BinaryField16b, _, _, _>(
+			FastCpuLayerHolder::<CanonicalTowerFamily, P>::new(
+				1 << (log_len + 3),
+				1 << (log_len + 3),
+			),
+			log_len,
+			len,
+		);
+	}
+}
+
 #[test]
 fn test_compute_composite() {
 	type P = PackedBinaryField2x128b;
@@ -167,3 +283,123 @@ fn test_map_kernels() {
 		log_len,
 	);
 }
+
+#[test]
+fn test_build_merkle_tree() {
+	type P = PackedBinaryField2x128b;
+	let log_len = 6;
+	binius_compute_test_utils::layer::test_generic_build_merkle_tree(
+		FastCpuLayerHolder::<CanonicalTowerFamily, P>::new(1 << (log_len + 4), 1 << (log_len + 3)),
+		log_len,
+		2,
+	);
+}
+
+#[test]
+fn test_exec_ntt() {
+	type F = BinaryField128b;
+	type FSub = BinaryField16b;
+	type P = PackedBinaryField2x128b;
+	let log_len = 8;
+	let log_batch_size = 2;
+	test_generic_ntt::<F, FSub, _, _>(
+		FastCpuLayerHolder::<CanonicalTowerFamily, P>::new(
+			1 << (log_len + log_batch_size + 1),
+			1 << (log_len + log_batch_size + 1),
+		),
+		log_len,
+		log_batch_size,
+	);
+}
+
+#[test]
+fn test_exec_rs_encode() {
+	type F = BinaryField128b;
+	type FSub = BinaryField16b;
+	type P = PackedBinaryField2x128b;
+	for (log_dim, log_inv_rate, log_batch_size) in [(6, 1, 0), (6, 2, 3), (0, 1, 0)] {
+		test_generic_rs_encode::<F, FSub, _, _>(
+			FastCpuLayerHolder::<CanonicalTowerFamily, P>::new(
+				1 << (log_dim + log_inv_rate + log_batch_size + 2),
+				1 << (log_dim + log_inv_rate + log_batch_size + 2),
+			),
+			log_dim,
+			log_inv_rate,
+			log_batch_size,
+		);
+	}
+}
+
+#[test]
+fn test_map_and_fold_ops() {
+	type F = BinaryField128b;
+	type P = PackedBinaryField2x128b;
+	let log_len = 8;
+	test_generic_map_and_fold_ops::<F, _, _>(
+		FastCpuLayerHolder::<CanonicalTowerFamily, P>::new(1 << (log_len + 3), 1 << (log_len + 3)),
+		log_len,
+	);
+}
+
+#[test]
+fn test_segmented_sum_and_scan() {
+	type F = BinaryField128b;
+	type P = PackedBinaryField2x128b;
+	let log_len = 8;
+	for log_segment_len in [0, 3, log_len] {
+		test_generic_segmented_sum_and_scan::<F, _, _>(
+			FastCpuLayerHolder::<CanonicalTowerFamily, P>::new(
+				1 << (log_len + 3),
+				1 << (log_len + 3),
+			),
+			log_len,
+			log_segment_len,
+		);
+	}
+}
+
+#[test]
+fn test_conformance() {
+	type P = PackedBinaryField2x128b;
+	let config = ConformanceConfig {
+		// TODO(CRY-490): remove once the fast layer implements pairwise_product_reduce.
+		skip: vec!["pairwise_product_reduce"],
+		..Default::default()
+	};
+	run_conformance_suite(&config, |host_len, dev_len| {
+		FastCpuLayerHolder::<CanonicalTowerFamily, P>::new(host_len, dev_len)
+	});
+}
+
+#[test]
+fn test_conformance_with_dedicated_thread_pool() {
+	type P = PackedBinaryField2x128b;
+	let threading = ThreadingConfig {
+		thread_pool: ThreadPoolHandle::new(2, true).unwrap(),
+		numa_first_touch: true,
+		min_split_lens: MinSplitLens {
+			copy: 64,
+			composite: 16,
+			kernels: 32,
+			extrapolate_line: 64,
+		},
+	};
+	let config = ConformanceConfig {
+		// TODO(CRY-490): remove once the fast layer implements pairwise_product_reduce.
+		skip: vec!["pairwise_product_reduce"],
+		..Default::default()
+	};
+	run_conformance_suite(&config, |host_len, dev_len| {
+		FastCpuLayerHolder::<CanonicalTowerFamily, P>::with_threading(host_len, dev_len, &threading)
+	});
+}
+
+#[test]
+fn test_differential_against_naive() {
+	type P = PackedBinaryField4x128b;
+	run_differential_suite(
+		&ConformanceConfig::default(),
+		NaiveLayerHolder::<BinaryField128b>::new,
+		FastCpuLayerHolder::<CanonicalTowerFamily, P>::new,
+	);
+}
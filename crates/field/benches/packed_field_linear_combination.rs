@@ -0,0 +1,74 @@
+// Copyright 2025 Irreducible Inc.
+
+use std::{iter::repeat_with, time::Duration};
+
+use binius_field::{
+	Field, PackedAESBinaryField16x8b, PackedBinaryField1x128b, PackedBinaryField2x64b,
+	PackedBinaryField4x32b, PackedBinaryField8x16b,
+	linear_combination::FixedLinearCombination,
+	linear_transformation::PackedTransformationFactory,
+	underlier::{UnderlierType, WithUnderlier},
+};
+use criterion::{
+	BenchmarkGroup, Throughput, criterion_group, criterion_main, measurement::WallTime,
+};
+
+const NUM_COEFFICIENTS: usize = 4;
+const LEN: usize = 1 << 12;
+
+fn bench_linear_combination<P>(group: &mut BenchmarkGroup<'_, WallTime>)
+where
+	P: PackedTransformationFactory<P>,
+{
+	let mut rng = rand::rng();
+	let coefficients = repeat_with(|| <P::Scalar as Field>::random(&mut rng))
+		.take(NUM_COEFFICIENTS)
+		.collect::<Vec<_>>();
+	let inputs = repeat_with(|| {
+		repeat_with(|| P::random(&mut rng))
+			.take(LEN)
+			.collect::<Vec<_>>()
+	})
+	.take(NUM_COEFFICIENTS)
+	.collect::<Vec<_>>();
+	let input_refs = inputs.iter().map(Vec::as_slice).collect::<Vec<_>>();
+	let mut output = vec![P::zero(); LEN];
+
+	group.warm_up_time(Duration::from_secs(1));
+	group.measurement_time(Duration::from_secs(3));
+	group.throughput(Throughput::Elements((NUM_COEFFICIENTS * LEN * P::WIDTH) as _));
+	let bits = <P::Scalar as WithUnderlier>::Underlier::BITS;
+
+	group.bench_function(format!("{}x{bits}b/broadcast_mul", P::WIDTH), |b| {
+		let broadcast = coefficients
+			.iter()
+			.map(|&coeff| P::broadcast(coeff))
+			.collect::<Vec<_>>();
+		b.iter(|| {
+			output.fill(P::zero());
+			for (coeff, input) in broadcast.iter().zip(&inputs) {
+				for (out, &value) in output.iter_mut().zip(input) {
+					*out += value * *coeff;
+				}
+			}
+		})
+	});
+
+	let combination = FixedLinearCombination::<P>::new(coefficients);
+	group.bench_function(format!("{}x{bits}b/fixed", P::WIDTH), |b| {
+		b.iter(|| combination.evaluate(&input_refs, &mut output).unwrap())
+	});
+}
+
+fn linear_combination(c: &mut criterion::Criterion) {
+	let mut group = c.benchmark_group("linear_combination");
+
+	bench_linear_combination::<PackedBinaryField8x16b>(&mut group);
+	bench_linear_combination::<PackedBinaryField4x32b>(&mut group);
+	bench_linear_combination::<PackedBinaryField2x64b>(&mut group);
+	bench_linear_combination::<PackedBinaryField1x128b>(&mut group);
+	bench_linear_combination::<PackedAESBinaryField16x8b>(&mut group);
+}
+
+criterion_group!(benches, linear_combination);
+criterion_main!(benches);
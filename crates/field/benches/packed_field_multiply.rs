@@ -27,7 +27,7 @@ cfg_if! {
 	if #[cfg(feature = "benchmark_alternative_strategies")] {
 		use binius_field::{
 			arch::{HybridRecursiveStrategy, PackedStrategy, PairwiseRecursiveStrategy, PairwiseStrategy,
-				PairwiseTableStrategy, SimdStrategy,},
+				PairwiseTableStrategy, SimdStrategy, SwarStrategy,},
 			arithmetic_traits::TaggedMul
 		};
 
@@ -55,6 +55,10 @@ cfg_if! {
 			TaggedMul::<SimdStrategy>::mul(lhs, rhs)
 		}
 
+		fn mul_swar<T: TaggedMul<SwarStrategy>>(lhs: T, rhs: T) -> T {
+			TaggedMul::<SwarStrategy>::mul(lhs, rhs)
+		}
+
 		benchmark_packed_operation!(
 			op_name @ multiply,
 			bench_type @ binary_op,
@@ -66,6 +70,7 @@ cfg_if! {
 				(hybrid_recursive, TaggedMul::<HybridRecursiveStrategy>, mul_hybrid_recursive),
 				(packed, TaggedMul::<PackedStrategy>, mul_packed),
 				(simd, TaggedMul::<SimdStrategy>, mul_simd),
+				(swar, TaggedMul::<SwarStrategy>, mul_swar),
 			)
 		);
 	} else {
@@ -314,7 +314,7 @@ macro_rules! serialize_deserialize_non_canonical {
 			) -> Result<(), SerializationError> {
 				match mode {
 					SerializationMode::Native => self.0.serialize(write_buf, mode),
-					SerializationMode::CanonicalTower => {
+					SerializationMode::CanonicalTower | SerializationMode::Compact => {
 						$canonical::from(*self).serialize(write_buf, mode)
 					}
 				}
@@ -333,7 +333,7 @@ macro_rules! serialize_deserialize_non_canonical {
 					SerializationMode::Native => {
 						Ok(Self(DeserializeBytes::deserialize(read_buf, mode)?))
 					}
-					SerializationMode::CanonicalTower => {
+					SerializationMode::CanonicalTower | SerializationMode::Compact => {
 						Ok(Self::from($canonical::deserialize(read_buf, mode)?))
 					}
 				}
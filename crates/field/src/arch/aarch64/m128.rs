@@ -27,7 +27,8 @@ use crate::{
 	tower_levels::TowerLevel,
 	underlier::{
 		NumCast, Random, SmallU, U1, U2, U4, UnderlierType, UnderlierWithBitOps, WithUnderlier,
-		impl_divisible, impl_iteration, transpose_128b_values, unpack_lo_128b_fallback,
+		impl_divisible, impl_iteration, spread_fallback, transpose_128b_values,
+		unpack_hi_128b_fallback, unpack_lo_128b_fallback,
 	},
 };
 
@@ -372,6 +373,30 @@ impl UnderlierWithBitOps for M128 {
 		}
 	}
 
+	#[inline(always)]
+	unsafe fn spread<T>(self, log_block_len: usize, block_idx: usize) -> Self
+	where
+		T: UnderlierWithBitOps + NumCast<Self>,
+		Self: From<T>,
+	{
+		// Elements of a byte or more are spread with a single table lookup.
+		let masks: &[[u8; 16]] = match (T::LOG_BITS, log_block_len) {
+			(3, 0) => &LOG_B8_0,
+			(3, 1) => &LOG_B8_1,
+			(3, 2) => &LOG_B8_2,
+			(3, 3) => &LOG_B8_3,
+			(4, 0) => &LOG_B16_0,
+			(4, 1) => &LOG_B16_1,
+			(4, 2) => &LOG_B16_2,
+			(5, 0) => &LOG_B32_0,
+			(5, 1) => &LOG_B32_1,
+			(6, 0) => &LOG_B64_0,
+			(log_bits, log_block_len) if log_bits + log_block_len == 7 => return self,
+			_ => return unsafe { spread_fallback(self, log_block_len, block_idx) },
+		};
+		self.shuffle_u8(masks[block_idx])
+	}
+
 	#[inline(always)]
 	fn shl_128b_lanes(self, rhs: usize) -> Self {
 		Self(self.0 << rhs)
@@ -397,7 +422,7 @@ impl UnderlierWithBitOps for M128 {
 	#[inline(always)]
 	fn unpack_hi_128b_lanes(self, rhs: Self, log_block_len: usize) -> Self {
 		match log_block_len {
-			0..3 => unpack_lo_128b_fallback(self, rhs, log_block_len),
+			0..3 => unpack_hi_128b_fallback(self, rhs, log_block_len),
 			3 => unsafe { vzip2q_u8(self.into(), rhs.into()).into() },
 			4 => unsafe { vzip2q_u16(self.into(), rhs.into()).into() },
 			5 => unsafe { vzip2q_u32(self.into(), rhs.into()).into() },
@@ -590,6 +615,45 @@ where
 	}
 }
 
+static LOG_B8_0: [[u8; 16]; 16] = precompute_spread_mask::<16>(0, 3);
+static LOG_B8_1: [[u8; 16]; 8] = precompute_spread_mask::<8>(1, 3);
+static LOG_B8_2: [[u8; 16]; 4] = precompute_spread_mask::<4>(2, 3);
+static LOG_B8_3: [[u8; 16]; 2] = precompute_spread_mask::<2>(3, 3);
+static LOG_B16_0: [[u8; 16]; 8] = precompute_spread_mask::<8>(0, 4);
+static LOG_B16_1: [[u8; 16]; 4] = precompute_spread_mask::<4>(1, 4);
+static LOG_B16_2: [[u8; 16]; 2] = precompute_spread_mask::<2>(2, 4);
+static LOG_B32_0: [[u8; 16]; 4] = precompute_spread_mask::<4>(0, 5);
+static LOG_B32_1: [[u8; 16]; 2] = precompute_spread_mask::<2>(1, 5);
+static LOG_B64_0: [[u8; 16]; 2] = precompute_spread_mask::<2>(0, 6);
+
+/// Returns the `vqtbl1q_u8` indices that spread every block of `1 << log_block_len` elements of
+/// `1 << t_log_bits` bits over the whole register.
+const fn precompute_spread_mask<const BLOCK_IDX_AMOUNT: usize>(
+	log_block_len: usize,
+	t_log_bits: usize,
+) -> [[u8; 16]; BLOCK_IDX_AMOUNT] {
+	let element_log_width = t_log_bits - 3;
+	let element_width = 1 << element_log_width;
+
+	let block_size = 1 << (log_block_len + element_log_width);
+	let repeat = 1 << (4 - element_log_width - log_block_len);
+	let mut masks = [[0u8; 16]; BLOCK_IDX_AMOUNT];
+
+	let mut block_idx = 0;
+	while block_idx < BLOCK_IDX_AMOUNT {
+		let base = block_idx * block_size;
+		let mut j = 0;
+		while j < 16 {
+			masks[block_idx][j] =
+				(base + ((j / element_width) / repeat) * element_width + j % element_width) as u8;
+			j += 1;
+		}
+		block_idx += 1;
+	}
+
+	masks
+}
+
 impl_iteration!(M128,
 	@strategy BitIterationStrategy, U1,
 	@strategy FallbackStrategy, U2, U4,
@@ -618,4 +682,55 @@ mod tests {
 
 		assert_eq!(original_value, deserialized_value);
 	}
+
+	fn check_spread<T>(value: M128)
+	where
+		T: UnderlierWithBitOps + NumCast<M128>,
+		M128: From<T>,
+	{
+		for log_block_len in 0..=(M128::LOG_BITS - T::LOG_BITS) {
+			for block_idx in 0..1 << (M128::LOG_BITS - T::LOG_BITS - log_block_len) {
+				unsafe {
+					assert_eq!(
+						value.spread::<T>(log_block_len, block_idx),
+						spread_fallback::<_, T>(value, log_block_len, block_idx),
+						"log_block_len: {log_block_len}, block_idx: {block_idx}"
+					);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_spread() {
+		let mut rng = StdRng::from_seed([0; 32]);
+		for _ in 0..16 {
+			let value = M128::from(rng.random::<u128>());
+			check_spread::<U1>(value);
+			check_spread::<U2>(value);
+			check_spread::<U4>(value);
+			check_spread::<u8>(value);
+			check_spread::<u16>(value);
+			check_spread::<u32>(value);
+			check_spread::<u64>(value);
+			check_spread::<u128>(value);
+		}
+	}
+
+	#[test]
+	fn test_unpack_128b_lanes() {
+		let mut rng = StdRng::from_seed([0; 32]);
+		let a = M128::from(rng.random::<u128>());
+		let b = M128::from(rng.random::<u128>());
+		for log_block_len in 0..=6 {
+			assert_eq!(
+				a.unpack_lo_128b_lanes(b, log_block_len),
+				unpack_lo_128b_fallback(a, b, log_block_len)
+			);
+			assert_eq!(
+				a.unpack_hi_128b_lanes(b, log_block_len),
+				unpack_hi_128b_fallback(a, b, log_block_len)
+			);
+		}
+	}
 }
@@ -21,7 +21,7 @@ define_packed_binary_fields!(
 			name: PackedAESBinaryField16x8b,
 			scalar: AESTowerField8b,
 			alpha_idx: _,
-			mul: (PairwiseTableStrategy),
+			mul: (SwarStrategy),
 			square: (PairwiseTableStrategy),
 			invert: (PairwiseTableStrategy),
 			mul_alpha: (PairwiseTableStrategy),
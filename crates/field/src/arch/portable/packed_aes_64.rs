@@ -23,7 +23,7 @@ define_packed_binary_fields!(
 			name: PackedAESBinaryField8x8b,
 			scalar: AESTowerField8b,
 			alpha_idx: _,
-			mul:       (if gfni_x86 PackedAESBinaryField16x8b else PairwiseTableStrategy),
+			mul:       (if gfni_x86 PackedAESBinaryField16x8b else SwarStrategy),
 			square:    (if gfni_x86 PackedAESBinaryField16x8b else PairwiseTableStrategy),
 			invert:    (if gfni_x86 PackedAESBinaryField16x8b else PairwiseTableStrategy),
 			mul_alpha: (PairwiseTableStrategy),
@@ -0,0 +1,85 @@
+// Copyright 2025 Irreducible Inc.
+
+use super::packed::PackedPrimitiveType;
+use crate::{
+	AESTowerField8b,
+	arch::SwarStrategy,
+	arithmetic_traits::TaggedMul,
+	packed::PackedField,
+	underlier::{UnderlierWithBitOps, WithUnderlier},
+};
+
+impl<U> TaggedMul<SwarStrategy> for PackedPrimitiveType<U, AESTowerField8b>
+where
+	U: UnderlierWithBitOps + From<u8>,
+	Self: PackedField<Scalar = AESTowerField8b> + WithUnderlier<Underlier = U>,
+{
+	/// Shift-and-add multiplication of all the bytes at once.
+	#[inline]
+	fn mul(self, rhs: Self) -> Self {
+		let low_bits = U::broadcast_subvalue(0x01u8);
+		let no_high_bits = U::broadcast_subvalue(0x7fu8);
+		// The AES polynomial X^8 + X^4 + X^3 + X + 1 without the leading term
+		let reduction = U::broadcast_subvalue(0x1bu8);
+
+		let mut lhs = self.to_underlier();
+		let rhs = rhs.to_underlier();
+		let mut result = U::ZERO;
+		for i in 0..8 {
+			result ^= lhs & fill_bytes((rhs >> i) & low_bits);
+
+			// Multiply every byte by X
+			lhs = ((lhs & no_high_bits) << 1) ^ (fill_bytes((lhs >> 7) & low_bits) & reduction);
+		}
+
+		Self::from_underlier(result)
+	}
+}
+
+/// Fills every byte with its lowest bit. All the other bits must be zero.
+#[inline(always)]
+fn fill_bytes<U: UnderlierWithBitOps>(mut value: U) -> U {
+	value |= value << 1;
+	value |= value << 2;
+	value |= value << 4;
+
+	value
+}
+
+#[cfg(test)]
+mod tests {
+	use proptest::prelude::*;
+
+	use super::*;
+	use crate::arch::{
+		packed_aes_64::PackedAESBinaryField8x8b, packed_aes_128::PackedAESBinaryField16x8b,
+	};
+
+	fn check_mul<P>(a: P, b: P)
+	where
+		P: PackedField + TaggedMul<SwarStrategy>,
+	{
+		let c = TaggedMul::<SwarStrategy>::mul(a, b);
+		for i in 0..P::WIDTH {
+			assert_eq!(c.get(i), a.get(i) * b.get(i));
+		}
+	}
+
+	proptest! {
+		#[test]
+		fn test_mul_packed_64(a in any::<u64>(), b in any::<u64>()) {
+			check_mul(
+				PackedAESBinaryField8x8b::from_underlier(a),
+				PackedAESBinaryField8x8b::from_underlier(b),
+			);
+		}
+
+		#[test]
+		fn test_mul_packed_128(a in any::<u128>(), b in any::<u128>()) {
+			check_mul(
+				PackedAESBinaryField16x8b::from_underlier(a.into()),
+				PackedAESBinaryField16x8b::from_underlier(b.into()),
+			);
+		}
+	}
+}
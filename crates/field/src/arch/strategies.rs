@@ -13,6 +13,9 @@ pub struct PairwiseRecursiveStrategy;
 pub struct PairwiseStrategy;
 /// Get result of operation from the table for each sub-element
 pub struct PairwiseTableStrategy;
+/// Calculates the result for all packed values at once using SIMD-within-a-register bit tricks
+/// over the plain integer underlier.
+pub struct SwarStrategy;
 /// Similar to `PackedStrategy`, but uses SIMD operations supported by the platform.
 pub struct SimdStrategy;
 /// Applicable only for multiply by alpha and square operations.
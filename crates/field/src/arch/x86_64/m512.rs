@@ -766,30 +766,10 @@ impl UnderlierWithBitOps for M512 {
 
 					Self::from_fn::<u128>(|i| values[i])
 				},
-				3 => unsafe {
-					let bits = get_block_values::<_, U1, 8>(self, block_idx);
-					let values = bits.map(|b| u64::fill_with_bit(b.val()));
-
-					Self::from_fn::<u64>(|i| values[i])
-				},
-				4 => unsafe {
-					let bits = get_block_values::<_, U1, 16>(self, block_idx);
-					let values = bits.map(|b| u32::fill_with_bit(b.val()));
-
-					Self::from_fn::<u32>(|i| values[i])
-				},
-				5 => unsafe {
-					let bits = get_block_values::<_, U1, 32>(self, block_idx);
-					let values = bits.map(|b| u16::fill_with_bit(b.val()));
-
-					Self::from_fn::<u16>(|i| values[i])
-				},
-				6 => unsafe {
-					let bits = get_block_values::<_, U1, 64>(self, block_idx);
-					let values = bits.map(|b| u8::fill_with_bit(b.val()));
-
-					Self::from_fn::<u8>(|i| values[i])
-				},
+				3 => unsafe { _mm512_movm_epi64(self.get_subvalue::<u8>(block_idx)).into() },
+				4 => unsafe { _mm512_movm_epi32(self.get_subvalue::<u16>(block_idx)).into() },
+				5 => unsafe { _mm512_movm_epi16(self.get_subvalue::<u32>(block_idx)).into() },
+				6 => unsafe { _mm512_movm_epi8(self.get_subvalue::<u64>(block_idx)).into() },
 				_ => unsafe { spread_fallback(self, log_block_len, block_idx) },
 			},
 			1 => match log_block_len {
@@ -801,32 +781,32 @@ impl UnderlierWithBitOps for M512 {
 				1 => unsafe {
 					let bytes = get_spread_bytes::<_, U2, 2>(self, block_idx);
 
-					Self::from_fn::<u8>(|i| bytes[i / 32])
+					permute_spread_bytes(bytes, LOG_B8_1[0])
 				},
 				2 => unsafe {
 					let bytes = get_spread_bytes::<_, U2, 4>(self, block_idx);
 
-					Self::from_fn::<u8>(|i| bytes[i / 16])
+					permute_spread_bytes(bytes, LOG_B8_2[0])
 				},
 				3 => unsafe {
 					let bytes = get_spread_bytes::<_, U2, 8>(self, block_idx);
 
-					Self::from_fn::<u8>(|i| bytes[i / 8])
+					permute_spread_bytes(bytes, LOG_B8_3[0])
 				},
 				4 => unsafe {
 					let bytes = get_spread_bytes::<_, U2, 16>(self, block_idx);
 
-					Self::from_fn::<u8>(|i| bytes[i / 4])
+					permute_spread_bytes(bytes, LOG_B8_4[0])
 				},
 				5 => unsafe {
 					let bytes = get_spread_bytes::<_, U2, 32>(self, block_idx);
 
-					Self::from_fn::<u8>(|i| bytes[i / 2])
+					permute_spread_bytes(bytes, LOG_B8_5[0])
 				},
 				6 => unsafe {
 					let bytes = get_spread_bytes::<_, U2, 64>(self, block_idx);
 
-					Self::from_fn::<u8>(|i| bytes[i])
+					_mm512_loadu_si512(bytes.as_ptr() as *const _).into()
 				},
 				_ => unsafe { spread_fallback(self, log_block_len, block_idx) },
 			},
@@ -839,32 +819,32 @@ impl UnderlierWithBitOps for M512 {
 				1 => unsafe {
 					let bytes = get_spread_bytes::<_, U4, 2>(self, block_idx);
 
-					Self::from_fn::<u8>(|i| bytes[i / 32])
+					permute_spread_bytes(bytes, LOG_B8_1[0])
 				},
 				2 => unsafe {
 					let bytes = get_spread_bytes::<_, U4, 4>(self, block_idx);
 
-					Self::from_fn::<u8>(|i| bytes[i / 16])
+					permute_spread_bytes(bytes, LOG_B8_2[0])
 				},
 				3 => unsafe {
 					let bytes = get_spread_bytes::<_, U4, 8>(self, block_idx);
 
-					Self::from_fn::<u8>(|i| bytes[i / 8])
+					permute_spread_bytes(bytes, LOG_B8_3[0])
 				},
 				4 => unsafe {
 					let bytes = get_spread_bytes::<_, U4, 16>(self, block_idx);
 
-					Self::from_fn::<u8>(|i| bytes[i / 4])
+					permute_spread_bytes(bytes, LOG_B8_4[0])
 				},
 				5 => unsafe {
 					let bytes = get_spread_bytes::<_, U4, 32>(self, block_idx);
 
-					Self::from_fn::<u8>(|i| bytes[i / 2])
+					permute_spread_bytes(bytes, LOG_B8_5[0])
 				},
 				6 => unsafe {
 					let bytes = get_spread_bytes::<_, U4, 64>(self, block_idx);
 
-					Self::from_fn::<u8>(|i| bytes[i])
+					_mm512_loadu_si512(bytes.as_ptr() as *const _).into()
 				},
 				_ => unsafe { spread_fallback(self, log_block_len, block_idx) },
 			},
@@ -1383,6 +1363,19 @@ static LOG_B64_2: [__m512i; 2] = precompute_spread_mask::<2>(2, 6);
 static LOG_B128_0: [__m512i; 4] = precompute_spread_mask::<4>(0, 7);
 static LOG_B128_1: [__m512i; 2] = precompute_spread_mask::<2>(1, 7);
 
+/// Loads the spread bytes of a block and repeats each of them according to `spread_mask`.
+#[inline(always)]
+unsafe fn permute_spread_bytes<const BLOCK_LEN: usize>(
+	bytes: [u8; BLOCK_LEN],
+	spread_mask: __m512i,
+) -> M512 {
+	unsafe {
+		let bytes =
+			_mm512_maskz_loadu_epi8(u64::MAX >> (64 - BLOCK_LEN), bytes.as_ptr() as *const _);
+		_mm512_permutexvar_epi8(spread_mask, bytes).into()
+	}
+}
+
 const fn precompute_spread_mask<const BLOCK_IDX_AMOUNT: usize>(
 	log_block_len: usize,
 	t_log_bits: usize,
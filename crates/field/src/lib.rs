@@ -24,6 +24,7 @@ pub mod byte_iteration;
 pub mod error;
 pub mod extension;
 pub mod field;
+pub mod linear_combination;
 pub mod linear_transformation;
 mod macros;
 pub mod packed;
@@ -33,6 +34,8 @@ pub mod packed_extension;
 pub mod packed_extension_ops;
 mod packed_polyval;
 pub mod polyval;
+#[cfg(any(test, feature = "test_utils"))]
+pub mod test_suite;
 #[cfg(test)]
 mod tests;
 pub mod tower;
@@ -0,0 +1,188 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_maybe_rayon::prelude::*;
+
+use crate::{
+	BinaryField1b, Error, ExtensionField, Field,
+	linear_transformation::{
+		FieldLinearTransformation, PackedTransformationFactory, Transformation,
+	},
+};
+
+/// Computes linear combinations $\sum_i c_i \cdot v_i$ of packed slices for a fixed set of scalar
+/// coefficients $c_i$.
+///
+/// Multiplication by a fixed binary field element is $\mathbb{F}_2$-linear, so each coefficient is
+/// turned into a packed linear transformation once, at construction. On targets with SIMD byte
+/// shuffles the packed transformations are table lookups, which makes applying a coefficient to a
+/// long slice cheaper than a generic packed multiplication by a broadcast scalar. This pays off
+/// when the same coefficients are applied to many elements, as in FRI folding with fixed challenges
+/// or in linear code encoding.
+pub struct FixedLinearCombination<P>
+where
+	P: PackedTransformationFactory<P>,
+{
+	coefficients: Vec<P::Scalar>,
+	transformations: Vec<P::PackedTransformation<Vec<P::Scalar>>>,
+}
+
+impl<P> FixedLinearCombination<P>
+where
+	P: PackedTransformationFactory<P>,
+{
+	pub fn new(coefficients: impl IntoIterator<Item = P::Scalar>) -> Self {
+		let coefficients = coefficients.into_iter().collect::<Vec<_>>();
+		let transformations = coefficients
+			.iter()
+			.map(|&coeff| {
+				let bases = (0..<P::Scalar as ExtensionField<BinaryField1b>>::DEGREE)
+					.map(|i| coeff * <P::Scalar as ExtensionField<BinaryField1b>>::basis(i))
+					.collect::<Vec<_>>();
+				P::make_packed_transformation(FieldLinearTransformation::new(bases))
+			})
+			.collect();
+
+		Self {
+			coefficients,
+			transformations,
+		}
+	}
+
+	pub fn coefficients(&self) -> &[P::Scalar] {
+		&self.coefficients
+	}
+
+	/// Multiplies a single packed element by the coefficient at `index`.
+	///
+	/// ## Preconditions
+	///
+	/// * `index` must be less than the number of coefficients
+	#[inline]
+	pub fn mul_by_coefficient(&self, index: usize, value: P) -> P {
+		let coeff = self.coefficients[index];
+		if coeff == P::Scalar::ZERO {
+			P::zero()
+		} else if coeff == P::Scalar::ONE {
+			value
+		} else {
+			self.transformations[index].transform(&value)
+		}
+	}
+
+	/// Writes $\sum_i c_i \cdot v_i$ into `output`, where $v_i$ is `inputs[i]`.
+	///
+	/// Every input slice must have the same length as `output`.
+	pub fn evaluate(&self, inputs: &[&[P]], output: &mut [P]) -> Result<(), Error> {
+		self.check_lengths(inputs, output.len())?;
+		self.evaluate_chunk(inputs, 0, output);
+		Ok(())
+	}
+
+	/// Like [`Self::evaluate`], but splits the output into chunks processed in parallel.
+	pub fn evaluate_par(&self, inputs: &[&[P]], output: &mut [P]) -> Result<(), Error> {
+		self.check_lengths(inputs, output.len())?;
+
+		// Chunks are large enough to amortize the task overhead and small enough that the input
+		// rows of a chunk stay in cache while all coefficients are applied.
+		const CHUNK_SIZE: usize = 1 << 10;
+		output
+			.par_chunks_mut(CHUNK_SIZE)
+			.enumerate()
+			.for_each(|(i, chunk)| self.evaluate_chunk(inputs, i * CHUNK_SIZE, chunk));
+		Ok(())
+	}
+
+	fn check_lengths(&self, inputs: &[&[P]], len: usize) -> Result<(), Error> {
+		if inputs.len() != self.coefficients.len() || inputs.iter().any(|input| input.len() != len)
+		{
+			return Err(Error::MismatchedLengths);
+		}
+		Ok(())
+	}
+
+	fn evaluate_chunk(&self, inputs: &[&[P]], offset: usize, output: &mut [P]) {
+		output.fill(P::zero());
+		for (index, input) in inputs.iter().enumerate() {
+			let input = &input[offset..offset + output.len()];
+			for (out, &value) in output.iter_mut().zip(input) {
+				*out += self.mul_by_coefficient(index, value);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::iter::repeat_with;
+
+	use rand::{SeedableRng, rngs::StdRng};
+
+	use super::*;
+	use crate::{
+		PackedAESBinaryField16x8b, PackedBinaryField2x64b, PackedBinaryField4x32b,
+		PackedBinaryField8x16b, PackedField,
+	};
+
+	fn check_linear_combination<P: PackedTransformationFactory<P>>() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let coefficients = [
+			<P::Scalar as Field>::random(&mut rng),
+			P::Scalar::ZERO,
+			P::Scalar::ONE,
+			<P::Scalar as Field>::random(&mut rng),
+		];
+		let inputs = repeat_with(|| {
+			repeat_with(|| P::random(&mut rng))
+				.take(3000)
+				.collect::<Vec<_>>()
+		})
+		.take(coefficients.len())
+		.collect::<Vec<_>>();
+		let input_refs = inputs.iter().map(Vec::as_slice).collect::<Vec<_>>();
+
+		let expected = (0..3000)
+			.map(|j| {
+				coefficients
+					.iter()
+					.zip(&inputs)
+					.map(|(&coeff, input)| input[j] * coeff)
+					.sum::<P>()
+			})
+			.collect::<Vec<_>>();
+
+		let combination = FixedLinearCombination::<P>::new(coefficients);
+		let mut output = vec![P::zero(); 3000];
+		combination.evaluate(&input_refs, &mut output).unwrap();
+		assert_eq!(output, expected);
+
+		let mut output = vec![P::zero(); 3000];
+		combination.evaluate_par(&input_refs, &mut output).unwrap();
+		assert_eq!(output, expected);
+	}
+
+	#[test]
+	fn test_linear_combination() {
+		check_linear_combination::<PackedBinaryField8x16b>();
+		check_linear_combination::<PackedBinaryField4x32b>();
+		check_linear_combination::<PackedBinaryField2x64b>();
+		check_linear_combination::<PackedAESBinaryField16x8b>();
+	}
+
+	#[test]
+	fn test_mismatched_lengths() {
+		let combination =
+			FixedLinearCombination::<PackedBinaryField4x32b>::new([Field::ONE, Field::ONE]);
+		let input = vec![PackedBinaryField4x32b::zero(); 4];
+		let mut output = vec![PackedBinaryField4x32b::zero(); 4];
+
+		assert!(matches!(
+			combination.evaluate(&[&input], &mut output),
+			Err(Error::MismatchedLengths)
+		));
+		assert!(matches!(
+			combination.evaluate(&[&input, &input[..3]], &mut output),
+			Err(Error::MismatchedLengths)
+		));
+		assert!(combination.evaluate(&[&input, &input], &mut output).is_ok());
+	}
+}
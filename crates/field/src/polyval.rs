@@ -471,7 +471,7 @@ impl SerializeBytes for BinaryField128bPolyval {
 	) -> Result<(), SerializationError> {
 		match mode {
 			SerializationMode::Native => self.0.serialize(write_buf, mode),
-			SerializationMode::CanonicalTower => {
+			SerializationMode::CanonicalTower | SerializationMode::Compact => {
 				BinaryField128b::from(*self).serialize(write_buf, mode)
 			}
 		}
@@ -485,7 +485,7 @@ impl DeserializeBytes for BinaryField128bPolyval {
 	{
 		match mode {
 			SerializationMode::Native => Ok(Self(DeserializeBytes::deserialize(read_buf, mode)?)),
-			SerializationMode::CanonicalTower => {
+			SerializationMode::CanonicalTower | SerializationMode::Compact => {
 				Ok(Self::from(BinaryField128b::deserialize(read_buf, mode)?))
 			}
 		}
@@ -0,0 +1,264 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Reusable conformance checks for packed field implementations.
+//!
+//! New packed types (for example, ones backed by a new SIMD underlier) are expected to agree with
+//! the scalar field arithmetic lane by lane and to satisfy the same packing, interleaving,
+//! spreading, transposition and serialization invariants as the existing implementations. The
+//! functions in this module check those properties for a single packed type on randomly sampled
+//! values, and [`define_packed_field_test_suite`](crate::define_packed_field_test_suite) and
+//! [`define_packed_field_serialization_tests`](crate::define_packed_field_serialization_tests)
+//! generate the corresponding `#[test]` functions.
+//!
+//! The module is compiled for this crate's own tests and, for downstream crates, behind the
+//! `test_utils` feature.
+
+use std::iter;
+
+use binius_utils::{DeserializeBytes, SerializationMode, SerializeBytes, bytes::BytesMut};
+use rand::{RngCore, SeedableRng, rngs::StdRng};
+
+use crate::{Field, PackedField, square_transpose};
+
+/// The number of random samples each check is run on.
+pub const NUM_SAMPLES: usize = 32;
+
+/// Returns a deterministic RNG for the given check so that failures are reproducible.
+pub fn test_rng(seed: u64) -> StdRng {
+	StdRng::seed_from_u64(seed)
+}
+
+/// Checks that the packed arithmetic satisfies the field axioms and matches the scalar
+/// arithmetic in every lane.
+#[allow(clippy::eq_op)]
+pub fn check_field_axioms<P: PackedField>(mut rng: impl RngCore) {
+	for _ in 0..NUM_SAMPLES {
+		let a = P::random(&mut rng);
+		let b = P::random(&mut rng);
+		let c = P::random(&mut rng);
+		let s = <P::Scalar as Field>::random(&mut rng);
+
+		assert_eq!(a + b, b + a);
+		assert_eq!(a * b, b * a);
+		assert_eq!((a + b) + c, a + (b + c));
+		assert_eq!((a * b) * c, a * (b * c));
+		assert_eq!(a * (b + c), a * b + a * c);
+		assert_eq!(a + P::zero(), a);
+		assert_eq!(a * P::one(), a);
+		assert_eq!(a * P::zero(), P::zero());
+		assert_eq!(a - a, P::zero());
+		assert_eq!(a.square(), a * a);
+		assert_eq!(a.pow(3), a * a * a);
+
+		let mut assigned = a;
+		assigned += b;
+		assert_eq!(assigned, a + b);
+		assigned = a;
+		assigned *= b;
+		assert_eq!(assigned, a * b);
+		assigned = a;
+		assigned -= b;
+		assert_eq!(assigned, a - b);
+
+		let inverse = a.invert_or_zero();
+		let by_scalar = a * s;
+		for i in 0..P::WIDTH {
+			assert_eq!((a + b).get(i), a.get(i) + b.get(i));
+			assert_eq!((a - b).get(i), a.get(i) - b.get(i));
+			assert_eq!((a * b).get(i), a.get(i) * b.get(i));
+			assert_eq!(a.square().get(i), a.get(i).square());
+			assert_eq!(inverse.get(i), a.get(i).invert_or_zero());
+			assert_eq!(by_scalar.get(i), a.get(i) * s);
+		}
+	}
+}
+
+/// Checks element access and construction of packed values from scalars.
+pub fn check_packing<P: PackedField>(mut rng: impl RngCore) {
+	for _ in 0..NUM_SAMPLES {
+		let a = P::random(&mut rng);
+		let scalars = a.iter().collect::<Vec<_>>();
+		assert_eq!(scalars.len(), P::WIDTH);
+		assert!(a.into_iter().eq(scalars.iter().copied()));
+		assert_eq!(P::from_scalars(scalars.iter().copied()), a);
+		assert_eq!(P::from_fn(|i| scalars[i]), a);
+
+		let mut b = P::zero();
+		for (i, &scalar) in scalars.iter().enumerate() {
+			b.set(i, scalar);
+			assert_eq!(b.get(i), scalar);
+		}
+		assert_eq!(b, a);
+		assert!(a.get_checked(P::WIDTH).is_err());
+		assert!(b.set_checked(P::WIDTH, P::Scalar::ONE).is_err());
+
+		let s = <P::Scalar as Field>::random(&mut rng);
+		assert!(P::broadcast(s).iter().all(|x| x == s));
+		assert!(
+			P::set_single(s)
+				.iter()
+				.eq(iter::once(s).chain(iter::repeat_n(P::Scalar::ZERO, P::WIDTH - 1)))
+		);
+		assert!(P::zero().iter().all(|x| x == P::Scalar::ZERO));
+		assert!(P::one().iter().all(|x| x == P::Scalar::ONE));
+
+		let slice = [a, P::random(&mut rng)];
+		assert!(P::iter_slice(&slice).eq(slice.iter().flat_map(|p| p.iter())));
+	}
+}
+
+/// Checks `interleave` and `unzip` against their definitions for every block size, and that they
+/// are inverse to each other.
+pub fn check_interleave_unzip<P: PackedField>(mut rng: impl RngCore) {
+	for _ in 0..NUM_SAMPLES {
+		let lhs = P::random(&mut rng);
+		let rhs = P::random(&mut rng);
+		for log_block_len in 0..P::LOG_WIDTH {
+			let block_len = 1 << log_block_len;
+
+			let (a, b) = lhs.interleave(rhs, log_block_len);
+			for i in (0..P::WIDTH).step_by(2 * block_len) {
+				for j in 0..block_len {
+					assert_eq!(a.get(i + j), lhs.get(i + j));
+					assert_eq!(a.get(i + j + block_len), rhs.get(i + j));
+					assert_eq!(b.get(i + j), lhs.get(i + j + block_len));
+					assert_eq!(b.get(i + j + block_len), rhs.get(i + j + block_len));
+				}
+			}
+			assert_eq!(a.interleave(b, log_block_len), (lhs, rhs));
+
+			let (c, d) = lhs.unzip(rhs, log_block_len);
+			for i in (0..P::WIDTH / 2).step_by(block_len) {
+				for j in 0..block_len {
+					assert_eq!(c.get(i + j), lhs.get(2 * i + j));
+					assert_eq!(d.get(i + j), lhs.get(2 * i + j + block_len));
+					assert_eq!(c.get(i + j + P::WIDTH / 2), rhs.get(2 * i + j));
+					assert_eq!(d.get(i + j + P::WIDTH / 2), rhs.get(2 * i + j + block_len));
+				}
+			}
+		}
+	}
+}
+
+/// Checks `spread` against a scalar reference implementation for every block size and index.
+pub fn check_spread<P: PackedField>(mut rng: impl RngCore) {
+	for _ in 0..NUM_SAMPLES {
+		let a = P::random(&mut rng);
+		for log_block_len in 0..=P::LOG_WIDTH {
+			let block_len = 1 << log_block_len;
+			let repeat = 1 << (P::LOG_WIDTH - log_block_len);
+			for block_idx in 0..repeat {
+				let expected = P::from_scalars(
+					a.iter()
+						.skip(block_idx * block_len)
+						.take(block_len)
+						.flat_map(|x| iter::repeat_n(x, repeat)),
+				);
+				assert_eq!(a.spread(log_block_len, block_idx), expected);
+			}
+		}
+	}
+}
+
+/// Checks [`square_transpose`] over square blocks of every supported size, and that transposing
+/// twice is the identity.
+pub fn check_transpose<P: PackedField>(mut rng: impl RngCore) {
+	for _ in 0..NUM_SAMPLES {
+		for log_n in 0..=P::LOG_WIDTH {
+			let n = 1 << log_n;
+			let original = iter::repeat_with(|| P::random(&mut rng))
+				.take(n)
+				.collect::<Vec<_>>();
+
+			let mut transposed = original.clone();
+			square_transpose(log_n, &mut transposed).expect("dimensions are valid");
+			for block in (0..P::WIDTH).step_by(n) {
+				for (i, row) in transposed.iter().enumerate() {
+					for (j, column) in original.iter().enumerate() {
+						assert_eq!(row.get(block + j), column.get(block + i));
+					}
+				}
+			}
+
+			square_transpose(log_n, &mut transposed).expect("dimensions are valid");
+			assert_eq!(transposed, original);
+		}
+	}
+}
+
+/// Checks that values survive a serialization round-trip in every mode.
+pub fn check_serialization<P: PackedField + SerializeBytes + DeserializeBytes>(
+	mut rng: impl RngCore,
+) {
+	for mode in [SerializationMode::Native, SerializationMode::CanonicalTower] {
+		for _ in 0..NUM_SAMPLES {
+			let a = P::random(&mut rng);
+			let mut buffer = BytesMut::new();
+			a.serialize(&mut buffer, mode)
+				.expect("serialization succeeds");
+			let deserialized =
+				P::deserialize(buffer.freeze(), mode).expect("deserialization succeeds");
+			assert_eq!(deserialized, a);
+		}
+	}
+}
+
+/// Defines a test module per packed type running the arithmetic, packing, interleave, spread and
+/// transpose checks from [`crate::test_suite`].
+///
+/// ```ignore
+/// binius_field::define_packed_field_test_suite!(
+///     packed_8x16b => binius_field::PackedBinaryField8x16b,
+///     packed_aes_16x8b => binius_field::PackedAESBinaryField16x8b,
+/// );
+/// ```
+#[macro_export]
+macro_rules! define_packed_field_test_suite {
+	($($name:ident => $packed:ty),* $(,)?) => {
+		$(
+			mod $name {
+				#[allow(unused_imports)]
+				use super::*;
+
+				#[test]
+				fn test_field_axioms() {
+					$crate::test_suite::check_field_axioms::<$packed>($crate::test_suite::test_rng(0));
+				}
+
+				#[test]
+				fn test_packing() {
+					$crate::test_suite::check_packing::<$packed>($crate::test_suite::test_rng(1));
+				}
+
+				#[test]
+				fn test_interleave_unzip() {
+					$crate::test_suite::check_interleave_unzip::<$packed>($crate::test_suite::test_rng(2));
+				}
+
+				#[test]
+				fn test_spread() {
+					$crate::test_suite::check_spread::<$packed>($crate::test_suite::test_rng(3));
+				}
+
+				#[test]
+				fn test_transpose() {
+					$crate::test_suite::check_transpose::<$packed>($crate::test_suite::test_rng(4));
+				}
+			}
+		)*
+	};
+}
+
+/// Defines a serialization round-trip test per packed type, for types that implement
+/// [`SerializeBytes`] and [`DeserializeBytes`].
+#[macro_export]
+macro_rules! define_packed_field_serialization_tests {
+	($($name:ident => $packed:ty),* $(,)?) => {
+		$(
+			#[test]
+			fn $name() {
+				$crate::test_suite::check_serialization::<$packed>($crate::test_suite::test_rng(5));
+			}
+		)*
+	};
+}
@@ -156,3 +156,25 @@ generate_spread_tests_small! {
 	spread_equals_basic_spread_128x1, PackedBinaryField128x1b, BinaryField1b, SmallU<1>, 128;
 	spread_equals_basic_spread_64x1, PackedBinaryField64x1b, BinaryField1b, SmallU<1>, 64;
 }
+
+crate::define_packed_field_test_suite!(
+	test_suite_128x1b => PackedBinaryField128x1b,
+	test_suite_64x2b => PackedBinaryField64x2b,
+	test_suite_32x4b => PackedBinaryField32x4b,
+	test_suite_8x8b => PackedBinaryField8x8b,
+	test_suite_16x16b => PackedBinaryField16x16b,
+	test_suite_4x32b => PackedBinaryField4x32b,
+	test_suite_8x64b => PackedBinaryField8x64b,
+	test_suite_2x128b => PackedBinaryField2x128b,
+	test_suite_4x128b => PackedBinaryField4x128b,
+	test_suite_aes_32x8b => crate::PackedAESBinaryField32x8b,
+	test_suite_polyval_1x128b => crate::PackedBinaryPolyval1x128b,
+	test_suite_byte_sliced_aes_16x8b => crate::ByteSlicedAES16x8b,
+);
+
+crate::define_packed_field_serialization_tests!(
+	test_serialization_128x1b => PackedBinaryField128x1b,
+	test_serialization_16x16b => PackedBinaryField16x16b,
+	test_serialization_2x128b => PackedBinaryField2x128b,
+	test_serialization_4x128b => PackedBinaryField4x128b,
+);
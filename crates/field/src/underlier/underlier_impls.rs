@@ -3,7 +3,10 @@
 use super::{
 	small_uint::{U1, U2, U4},
 	underlier_type::{NumCast, UnderlierType},
-	underlier_with_bit_ops::UnderlierWithBitOps,
+	underlier_with_bit_ops::{
+		UnderlierWithBitOps, spread_swar, unpack_hi_128b_fallback, unpack_hi_128b_swar,
+		unpack_lo_128b_fallback, unpack_lo_128b_swar,
+	},
 };
 
 macro_rules! impl_underlier_type {
@@ -33,6 +36,33 @@ macro_rules! impl_underlier_type {
 			fn shr_128b_lanes(self, rhs: usize) -> Self {
 				self >> rhs
 			}
+
+			#[inline(always)]
+			unsafe fn spread<T>(self, log_block_len: usize, block_idx: usize) -> Self
+			where
+				T: UnderlierWithBitOps + NumCast<Self>,
+				Self: From<T>,
+			{
+				unsafe { spread_swar::<Self, T>(self, log_block_len, block_idx) }
+			}
+
+			#[inline(always)]
+			fn unpack_lo_128b_lanes(self, other: Self, log_block_len: usize) -> Self {
+				if Self::BITS == 128 {
+					unpack_lo_128b_swar(self, other, log_block_len)
+				} else {
+					unpack_lo_128b_fallback(self, other, log_block_len)
+				}
+			}
+
+			#[inline(always)]
+			fn unpack_hi_128b_lanes(self, other: Self, log_block_len: usize) -> Self {
+				if Self::BITS == 128 {
+					unpack_hi_128b_swar(self, other, log_block_len)
+				} else {
+					unpack_hi_128b_fallback(self, other, log_block_len)
+				}
+			}
 		}
 	};
 	() => {};
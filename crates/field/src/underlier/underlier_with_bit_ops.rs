@@ -338,6 +338,112 @@ where
 	result
 }
 
+/// SWAR implementation of `spread` method for the underliers without SIMD specializations.
+///
+/// Instead of moving the elements one by one, the block is split in halves that are moved apart
+/// simultaneously, which takes `log_block_len` steps.
+///
+/// # Safety
+/// `log_block_len + T::LOG_BITS` must be less than or equal to `U::LOG_BITS`.
+/// `block_idx` must be less than `1 << (U::LOG_BITS - log_block_len)`.
+#[inline]
+pub(crate) unsafe fn spread_swar<U, T>(value: U, log_block_len: usize, block_idx: usize) -> U
+where
+	U: UnderlierWithBitOps,
+	T: UnderlierType,
+{
+	debug_assert!(
+		log_block_len + T::LOG_BITS <= U::LOG_BITS,
+		"log_block_len: {}, U::BITS: {}, T::BITS: {}",
+		log_block_len,
+		U::BITS,
+		T::BITS
+	);
+	debug_assert!(
+		block_idx < 1 << (U::LOG_BITS - log_block_len),
+		"block_idx: {}, U::BITS: {}, log_block_len: {}",
+		block_idx,
+		U::BITS,
+		log_block_len
+	);
+
+	let log_block_bits = log_block_len + T::LOG_BITS;
+	let log_repeat = U::LOG_BITS - log_block_bits;
+	let block = (value >> (block_idx << log_block_bits))
+		& single_element_mask_bits::<U>(1 << log_block_bits);
+
+	let mut result = expand_swar(block, T::LOG_BITS, log_block_len, log_repeat);
+	for i in 0..log_repeat {
+		result |= result << (1 << (T::LOG_BITS + i));
+	}
+
+	result
+}
+
+/// Moves `1 << log_len` consecutive elements of `1 << log_elem_bits` bits from the low bits of
+/// `value` apart, so that the element `i` is placed at the bit `i << (log_elem_bits +
+/// log_stride)`. All the bits of `value` above the elements must be zero.
+#[inline(always)]
+fn expand_swar<U: UnderlierWithBitOps>(
+	mut value: U,
+	log_elem_bits: usize,
+	log_len: usize,
+	log_stride: usize,
+) -> U {
+	if log_stride == 0 {
+		return value;
+	}
+
+	// At each step the groups of `2 << log_group_bits` bits are already at their final
+	// positions, and the upper half of every group is moved to its own one.
+	for log_group_bits in (log_elem_bits..log_elem_bits + log_len).rev() {
+		let shift = ((1 << log_stride) - 1) << log_group_bits;
+		let mask = periodic_mask_bits::<U>(log_group_bits, log_group_bits + log_stride);
+		value = (value | value << shift) & mask;
+	}
+
+	value
+}
+
+/// Returns a value with the blocks of `1 << log_block_bits` ones repeated with the period of
+/// `1 << log_period` bits.
+#[inline(always)]
+fn periodic_mask_bits<U: UnderlierWithBitOps>(log_block_bits: usize, log_period: usize) -> U {
+	let mut mask = single_element_mask_bits::<U>(1 << log_block_bits);
+	for i in log_period..U::LOG_BITS {
+		mask |= mask << (1 << i);
+	}
+
+	mask
+}
+
+/// SWAR implementation of `unpack_lo_128b_lanes` for the 128-bit underliers.
+#[inline]
+pub(crate) fn unpack_lo_128b_swar<U: UnderlierWithBitOps>(
+	lhs: U,
+	rhs: U,
+	log_block_len: usize,
+) -> U {
+	debug_assert_eq!(U::BITS, 128);
+	assert!(log_block_len <= 6);
+
+	let low_half = single_element_mask_bits::<U>(64);
+	let lhs = expand_swar(lhs & low_half, log_block_len, 6 - log_block_len, 1);
+	let rhs = expand_swar(rhs & low_half, log_block_len, 6 - log_block_len, 1);
+
+	lhs | rhs << (1 << log_block_len)
+}
+
+/// SWAR implementation of `unpack_hi_128b_lanes` for the 128-bit underliers.
+#[inline]
+pub(crate) fn unpack_hi_128b_swar<U: UnderlierWithBitOps>(
+	lhs: U,
+	rhs: U,
+	log_block_len: usize,
+) -> U {
+	unpack_lo_128b_swar(lhs >> 64, rhs >> 64, log_block_len)
+}
+
 #[inline(always)]
 fn single_element_mask_bits_128b_lanes<T: UnderlierWithBitOps>(log_block_len: usize) -> T {
 	let mut mask = single_element_mask_bits(1 << log_block_len);
@@ -583,6 +689,57 @@ mod tests {
 		}
 	}
 
+	fn check_spread_swar<U, T>(value: U)
+	where
+		U: UnderlierWithBitOps + From<T>,
+		T: UnderlierWithBitOps + NumCast<U>,
+	{
+		for log_block_len in 0..=U::LOG_BITS - T::LOG_BITS {
+			for block_idx in 0..1 << (U::LOG_BITS - T::LOG_BITS - log_block_len) {
+				unsafe {
+					assert_eq!(
+						spread_swar::<U, T>(value, log_block_len, block_idx),
+						spread_fallback::<U, T>(value, log_block_len, block_idx),
+					);
+				}
+			}
+		}
+	}
+
+	proptest! {
+		#[test]
+		fn test_spread_swar_u64(value in any::<u64>()) {
+			check_spread_swar::<u64, U1>(value);
+			check_spread_swar::<u64, U2>(value);
+			check_spread_swar::<u64, U4>(value);
+			check_spread_swar::<u64, u8>(value);
+			check_spread_swar::<u64, u16>(value);
+			check_spread_swar::<u64, u32>(value);
+			check_spread_swar::<u64, u64>(value);
+		}
+
+		#[test]
+		fn test_spread_swar_u128(value in any::<u128>()) {
+			check_spread_swar::<u128, U1>(value);
+			check_spread_swar::<u128, U4>(value);
+			check_spread_swar::<u128, u8>(value);
+			check_spread_swar::<u128, u32>(value);
+			check_spread_swar::<u128, u128>(value);
+		}
+
+		#[test]
+		fn test_unpack_128b_swar(lhs in any::<u128>(), rhs in any::<u128>(), log_block_len in 0usize..=6) {
+			assert_eq!(
+				unpack_lo_128b_swar(lhs, rhs, log_block_len),
+				unpack_lo_128b_fallback(lhs, rhs, log_block_len),
+			);
+			assert_eq!(
+				unpack_hi_128b_swar(lhs, rhs, log_block_len),
+				unpack_hi_128b_fallback(lhs, rhs, log_block_len),
+			);
+		}
+	}
+
 	#[test]
 	fn test_transpose_from_byte_sliced() {
 		let mut value = [0x01234567u32];
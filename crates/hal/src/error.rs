@@ -17,6 +17,8 @@ pub enum Error {
 	NoScratchSpace,
 	#[error("incorrect multilinear access destination slice lengths")]
 	IncorrectDestSliceLengths,
+	#[error("no available backend satisfies the preferences: {0}")]
+	NoSuitableBackend(String),
 	#[error("{0}")]
 	FieldError(#[from] binius_field::Error),
 }
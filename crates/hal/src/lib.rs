@@ -6,12 +6,14 @@
 //! The HAL is consumed by the `binius_core` crate. The interfaces are currently designed around
 //! the architecture of [Irreducible's](https://www.irreducible.com) custom FPGA platform. The
 //! crate exposes a default, portable CPU backend that can be created with
-//! [`crate::make_portable_backend`].
+//! [`crate::make_portable_backend`]. Applications that run on different machines can instead
+//! pick a backend at runtime from the registry with [`crate::select_backend`].
 
 mod backend;
 mod common;
 mod cpu;
 mod error;
+mod registry;
 mod sumcheck_evaluator;
 mod sumcheck_folding;
 mod sumcheck_multilinear;
@@ -20,5 +22,6 @@ mod sumcheck_round_calculation;
 pub use backend::*;
 pub use cpu::*;
 pub use error::*;
+pub use registry::*;
 pub use sumcheck_evaluator::*;
 pub use sumcheck_multilinear::*;
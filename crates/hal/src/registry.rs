@@ -0,0 +1,340 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Registry of the available HAL backends and runtime backend selection.
+//!
+//! Every backend compiled into the crate is described by a [`BackendDescriptor`] in the
+//! registry. Applications describe what they need with [`BackendPreferences`] and call
+//! [`select_backend`] to obtain the most preferred backend that is available on the current
+//! machine, instead of hard-coding [`make_portable_backend`].
+
+use std::{
+	fmt::{self, Debug},
+	ops::{BitOr, BitOrAssign},
+};
+
+use binius_field::{Field, PackedExtension, PackedField};
+use binius_math::{
+	CompositionPoly, EvaluationOrder, MultilinearExtension, MultilinearPoly, MultilinearQueryRef,
+};
+
+use crate::{
+	ComputationBackend, CpuBackend, Error, RoundEvals, SumcheckEvaluator, SumcheckMultilinear,
+	make_portable_backend,
+};
+
+/// A set of backend capability flags.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BackendCapabilities(u32);
+
+impl BackendCapabilities {
+	/// No capabilities.
+	pub const NONE: Self = Self(0);
+	/// The backend runs on any target without architecture-specific code.
+	pub const PORTABLE: Self = Self(1 << 0);
+	/// The backend uses SIMD instructions of the host CPU for packed field arithmetic.
+	pub const SIMD: Self = Self(1 << 1);
+	/// The backend parallelizes operations across multiple CPU threads.
+	pub const MULTI_THREADED: Self = Self(1 << 2);
+	/// The backend offloads operations to an accelerator device.
+	pub const ACCELERATOR: Self = Self(1 << 3);
+
+	const NAMES: [(Self, &'static str); 4] = [
+		(Self::PORTABLE, "PORTABLE"),
+		(Self::SIMD, "SIMD"),
+		(Self::MULTI_THREADED, "MULTI_THREADED"),
+		(Self::ACCELERATOR, "ACCELERATOR"),
+	];
+
+	/// Returns whether all capabilities in `other` are also in `self`.
+	pub const fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	/// Returns the capabilities in `self` that are not in `other`.
+	pub const fn difference(self, other: Self) -> Self {
+		Self(self.0 & !other.0)
+	}
+}
+
+impl BitOr for BackendCapabilities {
+	type Output = Self;
+
+	fn bitor(self, rhs: Self) -> Self {
+		Self(self.0 | rhs.0)
+	}
+}
+
+impl BitOrAssign for BackendCapabilities {
+	fn bitor_assign(&mut self, rhs: Self) {
+		self.0 |= rhs.0;
+	}
+}
+
+impl Debug for BackendCapabilities {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let names = Self::NAMES
+			.iter()
+			.filter(|(flag, _)| self.contains(*flag))
+			.map(|(_, name)| *name);
+		f.debug_set().entries(names).finish()
+	}
+}
+
+/// The class of hardware that a backend targets, used to rank backends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackendKind {
+	/// Architecture-independent CPU code.
+	Portable,
+	/// CPU code specialized for the SIMD instructions of the host.
+	Simd,
+	/// An accelerator device, such as a GPU or FPGA.
+	Accelerator,
+}
+
+/// Describes a backend in the registry.
+#[derive(Debug, Clone, Copy)]
+pub struct BackendDescriptor {
+	/// Unique name of the backend.
+	pub name: &'static str,
+	/// The class of hardware that the backend targets.
+	pub kind: BackendKind,
+	/// The capabilities of the backend.
+	pub capabilities: BackendCapabilities,
+	is_available: fn() -> bool,
+	make: fn() -> AnyBackend,
+}
+
+impl BackendDescriptor {
+	/// Returns whether the backend can run on the current machine.
+	pub fn is_available(&self) -> bool {
+		(self.is_available)()
+	}
+
+	/// Creates an instance of the backend.
+	pub fn make(&self) -> AnyBackend {
+		(self.make)()
+	}
+}
+
+/// The registered backends, in no particular order.
+///
+/// [`CpuBackend`] is generic over the packed field types, so it uses the SIMD instructions of the
+/// host whenever the caller instantiates it with SIMD-backed packed fields. It is therefore
+/// registered once, as the portable backend.
+static REGISTRY: [BackendDescriptor; 1] = [BackendDescriptor {
+	name: "portable",
+	kind: BackendKind::Portable,
+	capabilities: BackendCapabilities(
+		BackendCapabilities::PORTABLE.0 | BackendCapabilities::MULTI_THREADED.0,
+	),
+	is_available: || true,
+	make: || AnyBackend::Cpu(make_portable_backend()),
+}];
+
+/// Returns the descriptors of all backends compiled into the crate, whether or not they are
+/// available on the current machine.
+pub fn registered_backends() -> &'static [BackendDescriptor] {
+	&REGISTRY
+}
+
+/// Requirements and preferences for [`select_backend`].
+#[derive(Debug, Clone)]
+pub struct BackendPreferences {
+	/// Capabilities that the selected backend must have.
+	///
+	/// [`BackendCapabilities::MULTI_THREADED`] is only satisfied when the thread pool has more
+	/// than one thread.
+	pub required: BackendCapabilities,
+	/// Backend kinds in decreasing order of preference. Backends of kinds not in the list are
+	/// never selected.
+	pub kinds: Vec<BackendKind>,
+	/// If set, only the backend with this name is selected.
+	pub name: Option<String>,
+}
+
+impl Default for BackendPreferences {
+	fn default() -> Self {
+		Self {
+			required: BackendCapabilities::NONE,
+			kinds: vec![
+				BackendKind::Accelerator,
+				BackendKind::Simd,
+				BackendKind::Portable,
+			],
+			name: None,
+		}
+	}
+}
+
+impl BackendPreferences {
+	fn accepts(&self, descriptor: &BackendDescriptor) -> bool {
+		let mut capabilities = descriptor.capabilities;
+		if binius_maybe_rayon::current_num_threads() <= 1 {
+			capabilities = capabilities.difference(BackendCapabilities::MULTI_THREADED);
+		}
+
+		self.name
+			.as_ref()
+			.is_none_or(|name| name == descriptor.name)
+			&& capabilities.contains(self.required)
+			&& descriptor.is_available()
+	}
+}
+
+/// Selects the most preferred available backend that satisfies the preferences.
+///
+/// Returns the descriptor of the selected backend along with an instance of it.
+///
+/// ## Throws
+///
+/// * [`Error::NoSuitableBackend`] if no available backend satisfies the preferences
+pub fn select_backend(
+	preferences: &BackendPreferences,
+) -> Result<(&'static BackendDescriptor, AnyBackend), Error> {
+	preferences
+		.kinds
+		.iter()
+		.find_map(|&kind| {
+			registered_backends()
+				.iter()
+				.find(|descriptor| descriptor.kind == kind && preferences.accepts(descriptor))
+		})
+		.map(|descriptor| (descriptor, descriptor.make()))
+		.ok_or_else(|| Error::NoSuitableBackend(format!("{preferences:?}")))
+}
+
+/// A backend selected at runtime from the registry.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum AnyBackend {
+	/// A backend that runs on the host CPU.
+	Cpu(CpuBackend),
+}
+
+impl ComputationBackend for AnyBackend {
+	type Vec<P: Send + Sync + Debug + 'static> = Vec<P>;
+
+	fn to_hal_slice<P: Debug + Send + Sync + 'static>(v: Vec<P>) -> Self::Vec<P> {
+		v
+	}
+
+	fn tensor_product_full_query<P: PackedField>(
+		&self,
+		query: &[P::Scalar],
+	) -> Result<Self::Vec<P>, Error> {
+		match self {
+			Self::Cpu(backend) => backend.tensor_product_full_query(query),
+		}
+	}
+
+	fn sumcheck_compute_round_evals<FDomain, P, M, Evaluator, Composition>(
+		&self,
+		evaluation_order: EvaluationOrder,
+		n_vars: usize,
+		tensor_query: Option<MultilinearQueryRef<P>>,
+		multilinears: &[SumcheckMultilinear<P, M>],
+		evaluators: &[Evaluator],
+		nontrivial_evaluation_points: &[FDomain],
+	) -> Result<Vec<RoundEvals<P::Scalar>>, Error>
+	where
+		FDomain: Field,
+		P: PackedExtension<FDomain>,
+		M: MultilinearPoly<P> + Send + Sync,
+		Evaluator: SumcheckEvaluator<P, Composition> + Sync,
+		Composition: CompositionPoly<P>,
+	{
+		match self {
+			Self::Cpu(backend) => backend.sumcheck_compute_round_evals(
+				evaluation_order,
+				n_vars,
+				tensor_query,
+				multilinears,
+				evaluators,
+				nontrivial_evaluation_points,
+			),
+		}
+	}
+
+	fn sumcheck_fold_multilinears<P, M>(
+		&self,
+		evaluation_order: EvaluationOrder,
+		n_vars: usize,
+		multilinears: &mut [SumcheckMultilinear<P, M>],
+		challenge: P::Scalar,
+		tensor_query: Option<MultilinearQueryRef<P>>,
+	) -> Result<bool, Error>
+	where
+		P: PackedField,
+		M: MultilinearPoly<P> + Send + Sync,
+	{
+		match self {
+			Self::Cpu(backend) => backend.sumcheck_fold_multilinears(
+				evaluation_order,
+				n_vars,
+				multilinears,
+				challenge,
+				tensor_query,
+			),
+		}
+	}
+
+	fn evaluate_partial_high<P: PackedField>(
+		&self,
+		multilinear: &impl MultilinearPoly<P>,
+		query_expansion: MultilinearQueryRef<P>,
+	) -> Result<MultilinearExtension<P>, Error> {
+		match self {
+			Self::Cpu(backend) => backend.evaluate_partial_high(multilinear, query_expansion),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_field::BinaryField32b;
+
+	use super::*;
+
+	#[test]
+	fn test_default_selection() {
+		let (descriptor, backend) = select_backend(&BackendPreferences::default()).unwrap();
+		assert_eq!(descriptor.name, "portable");
+
+		let query = [BinaryField32b::new(3), BinaryField32b::new(5)];
+		assert_eq!(
+			backend
+				.tensor_product_full_query::<BinaryField32b>(&query)
+				.unwrap(),
+			binius_math::eq_ind_partial_eval::<BinaryField32b>(&query)
+		);
+	}
+
+	#[test]
+	fn test_select_by_name_and_capabilities() {
+		let preferences = BackendPreferences {
+			name: Some("portable".to_string()),
+			..Default::default()
+		};
+		let (descriptor, _) = select_backend(&preferences).unwrap();
+		assert_eq!(descriptor.kind, BackendKind::Portable);
+
+		let preferences = BackendPreferences {
+			required: BackendCapabilities::ACCELERATOR,
+			..Default::default()
+		};
+		assert!(matches!(select_backend(&preferences), Err(Error::NoSuitableBackend(_))));
+
+		let preferences = BackendPreferences {
+			kinds: vec![BackendKind::Portable],
+			..Default::default()
+		};
+		let (descriptor, _) = select_backend(&preferences).unwrap();
+		assert_eq!(descriptor.name, "portable");
+	}
+
+	#[test]
+	fn test_capabilities_debug() {
+		let capabilities = BackendCapabilities::SIMD | BackendCapabilities::ACCELERATOR;
+		assert_eq!(format!("{capabilities:?}"), "{\"SIMD\", \"ACCELERATOR\"}");
+	}
+}
@@ -5,8 +5,11 @@ use std::{array, mem::MaybeUninit};
 use binius_field::{BinaryField8b, Field};
 use binius_hash::{
 	VisionHasherDigest, VisionHasherDigestByteSliced,
+	blake3::{Blake3, Blake3Parallel},
 	groestl::{Groestl256, Groestl256Parallel},
+	keccak::{Keccak256, Sha3_256},
 	multi_digest::{MultiDigest, ParallelDigest},
+	sha2::{Sha256, Sha512},
 };
 use binius_maybe_rayon::{iter::IntoParallelRefIterator, prelude::ParallelIterator};
 use criterion::{Criterion, Throughput, criterion_group, criterion_main};
@@ -58,6 +61,62 @@ fn bench_groestl(c: &mut Criterion) {
 	group.finish()
 }
 
+fn bench_sha2(c: &mut Criterion) {
+	let mut group = c.benchmark_group("SHA-2");
+
+	let mut rng = rand::rng();
+
+	const N: usize = 1 << 16;
+	let mut data = vec![0u8; N];
+	rng.fill_bytes(&mut data);
+	group.throughput(Throughput::Bytes(N as u64));
+	group.bench_function("SHA-256", |bench| bench.iter(|| <Sha256 as Digest>::digest(&data)));
+	group.bench_function("SHA-512", |bench| bench.iter(|| <Sha512 as Digest>::digest(&data)));
+
+	group.finish()
+}
+
+fn bench_keccak(c: &mut Criterion) {
+	let mut group = c.benchmark_group("Keccak");
+
+	let mut rng = rand::rng();
+
+	const N: usize = 1 << 16;
+	let mut data = vec![0u8; N];
+	rng.fill_bytes(&mut data);
+	group.throughput(Throughput::Bytes(N as u64));
+	group.bench_function("Keccak-256", |bench| bench.iter(|| <Keccak256 as Digest>::digest(&data)));
+	group.bench_function("SHA3-256", |bench| bench.iter(|| <Sha3_256 as Digest>::digest(&data)));
+
+	group.finish()
+}
+
+fn bench_blake3(c: &mut Criterion) {
+	let mut group = c.benchmark_group("BLAKE3");
+
+	let mut rng = rand::rng();
+
+	const N: usize = 1 << 16;
+	let mut data = vec![0u8; N];
+	rng.fill_bytes(&mut data);
+	group.throughput(Throughput::Bytes(N as u64));
+	group.bench_function("Blake3", |bench| bench.iter(|| <Blake3 as Digest>::digest(&data)));
+
+	let mut multi_digest: [MaybeUninit<GenericArray<u8, U32>>; 64] =
+		unsafe { MaybeUninit::uninit().assume_init() };
+	let hasher = <Blake3Parallel as ParallelDigest>::new();
+	let data = vec![vec![BinaryField8b::ZERO; N / 64]; 64];
+
+	group.bench_function("Blake3Parallel", |bench| {
+		bench.iter(|| {
+			let parallel_borrowed_slices = data.par_iter().map(|x| x.as_slice().iter().copied());
+			hasher.digest(parallel_borrowed_slices, &mut multi_digest)
+		})
+	});
+
+	group.finish()
+}
+
 fn bench_vision32(c: &mut Criterion) {
 	let mut group = c.benchmark_group("Vision Mark-32");
 
@@ -87,5 +146,5 @@ fn bench_vision32(c: &mut Criterion) {
 	group.finish()
 }
 
-criterion_group!(hash, bench_groestl, bench_vision32);
+criterion_group!(hash, bench_groestl, bench_sha2, bench_keccak, bench_blake3, bench_vision32);
 criterion_main!(hash);
@@ -0,0 +1,216 @@
+// Copyright 2025 Irreducible Inc.
+
+//! The [BLAKE3] hash function.
+//!
+//! [`Blake3`] is the hasher of the [`blake3`](::blake3) crate, which uses the SIMD instructions of
+//! the CPU to hash the chunks of long messages in parallel. [`Blake3Parallel`] and [`hash_many`]
+//! additionally hash many independent messages at once, which is the workload of the leaf layer
+//! of a Merkle tree.
+//!
+//! [BLAKE3]: <https://github.com/BLAKE3-team/BLAKE3-specs>
+
+use std::{array, mem::MaybeUninit};
+
+use binius_maybe_rayon::prelude::*;
+use digest::Output;
+
+use crate::{
+	CompressionFunction, PseudoCompressionFunction,
+	multi_digest::{MultiDigest, ParallelMultidigestImpl},
+};
+
+/// The BLAKE3 hasher with a 32-byte output.
+pub type Blake3 = ::blake3::Hasher;
+
+/// The number of messages that [`Blake3Multi`] hashes per invocation.
+pub const BLAKE3_PARALLEL_INSTANCES: usize = 8;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("{n_inputs} messages cannot be hashed into {n_outputs} digests")]
+	OutputLengthMismatch { n_inputs: usize, n_outputs: usize },
+}
+
+/// Computes the BLAKE3 digests of many messages.
+///
+/// The messages are hashed in parallel, and `out[i]` receives the digest of `inputs[i]`.
+///
+/// ## Throws
+///
+/// * [`Error::OutputLengthMismatch`] if `inputs` and `out` have different lengths
+pub fn hash_many(inputs: &[&[u8]], out: &mut [Output<Blake3>]) -> Result<(), Error> {
+	if inputs.len() != out.len() {
+		return Err(Error::OutputLengthMismatch {
+			n_inputs: inputs.len(),
+			n_outputs: out.len(),
+		});
+	}
+
+	inputs
+		.par_iter()
+		.zip(out.par_iter_mut())
+		.for_each(|(input, out)| *out = hash(input));
+	Ok(())
+}
+
+fn hash(input: &[u8]) -> Output<Blake3> {
+	(*::blake3::hash(input).as_bytes()).into()
+}
+
+/// Computes [`BLAKE3_PARALLEL_INSTANCES`] BLAKE3 digests at once.
+#[derive(Clone)]
+pub struct Blake3Multi([Blake3; BLAKE3_PARALLEL_INSTANCES]);
+
+impl MultiDigest<BLAKE3_PARALLEL_INSTANCES> for Blake3Multi {
+	type Digest = Blake3;
+
+	fn new() -> Self {
+		Self(array::from_fn(|_| Blake3::new()))
+	}
+
+	fn update(&mut self, data: [&[u8]; BLAKE3_PARALLEL_INSTANCES]) {
+		for (hasher, data) in self.0.iter_mut().zip(data) {
+			hasher.update(data);
+		}
+	}
+
+	fn finalize_into(self, out: &mut [MaybeUninit<Output<Blake3>>; BLAKE3_PARALLEL_INSTANCES]) {
+		for (hasher, out) in self.0.iter().zip(out) {
+			out.write((*hasher.finalize().as_bytes()).into());
+		}
+	}
+
+	fn finalize_into_reset(
+		&mut self,
+		out: &mut [MaybeUninit<Output<Blake3>>; BLAKE3_PARALLEL_INSTANCES],
+	) {
+		for (hasher, out) in self.0.iter_mut().zip(out) {
+			out.write((*hasher.finalize().as_bytes()).into());
+			hasher.reset();
+		}
+	}
+
+	fn reset(&mut self) {
+		for hasher in &mut self.0 {
+			hasher.reset();
+		}
+	}
+
+	/// Computes the digests one after another, as the groups of messages are already hashed in
+	/// parallel by [`Blake3Parallel`]. Unlike other hashers, the messages may have different
+	/// lengths.
+	fn digest(
+		data: [&[u8]; BLAKE3_PARALLEL_INSTANCES],
+		out: &mut [MaybeUninit<Output<Blake3>>; BLAKE3_PARALLEL_INSTANCES],
+	) {
+		for (out, data) in out.iter_mut().zip(data) {
+			out.write(hash(data));
+		}
+	}
+}
+
+/// A [`ParallelDigest`](crate::multi_digest::ParallelDigest) that hashes groups of
+/// [`BLAKE3_PARALLEL_INSTANCES`] messages with [`Blake3Multi`].
+pub type Blake3Parallel = ParallelMultidigestImpl<Blake3Multi, BLAKE3_PARALLEL_INSTANCES>;
+
+/// A two-to-one compression function for BLAKE3 digests.
+///
+/// The compression is the BLAKE3 hash of the concatenated inputs in key derivation mode, with a
+/// context string that separates it from hashes of leaves.
+#[derive(Debug, Clone)]
+pub struct Blake3Compression {
+	hasher: Blake3,
+}
+
+impl Default for Blake3Compression {
+	fn default() -> Self {
+		Self {
+			hasher: Blake3::new_derive_key("BINIUS BLAKE3 COMPRESS"),
+		}
+	}
+}
+
+impl PseudoCompressionFunction<Output<Blake3>, 2> for Blake3Compression {
+	fn compress(&self, input: [Output<Blake3>; 2]) -> Output<Blake3> {
+		let mut hasher = self.hasher.clone();
+		hasher.update(&input[0]);
+		hasher.update(&input[1]);
+		(*hasher.finalize().as_bytes()).into()
+	}
+}
+
+impl CompressionFunction<Output<Blake3>, 2> for Blake3Compression {}
+
+#[cfg(test)]
+mod tests {
+	use std::iter::repeat_with;
+
+	use digest::Digest;
+	use rand::{RngCore, SeedableRng, rngs::StdRng};
+
+	use super::*;
+
+	fn random_messages(n: usize, len: usize) -> Vec<Vec<u8>> {
+		let mut rng = StdRng::seed_from_u64(0);
+		repeat_with(|| {
+			let mut message = vec![0; len];
+			rng.fill_bytes(&mut message);
+			message
+		})
+		.take(n)
+		.collect()
+	}
+
+	#[test]
+	fn test_hash_many_matches_digest() {
+		for len in [0, 64, 1000, 5000] {
+			let messages = random_messages(13, len);
+			let inputs = messages.iter().map(Vec::as_slice).collect::<Vec<_>>();
+			let mut out = vec![Output::<Blake3>::default(); inputs.len()];
+			hash_many(&inputs, &mut out).unwrap();
+
+			for (input, out) in inputs.iter().zip(&out) {
+				assert_eq!(*out, <Blake3 as Digest>::digest(input));
+			}
+
+			assert!(matches!(
+				hash_many(&inputs, &mut out[1..]),
+				Err(Error::OutputLengthMismatch {
+					n_inputs: 13,
+					n_outputs: 12
+				})
+			));
+		}
+	}
+
+	#[test]
+	fn test_multi_digest_matches_digest() {
+		let messages = random_messages(BLAKE3_PARALLEL_INSTANCES, 200);
+		let data = array::from_fn(|i| messages[i].as_slice());
+
+		let mut out = [MaybeUninit::uninit(); BLAKE3_PARALLEL_INSTANCES];
+		Blake3Multi::digest(data, &mut out);
+		let mut hasher = Blake3Multi::new();
+		hasher.update(data.map(|row| &row[..100]));
+		hasher.update(data.map(|row| &row[100..]));
+		let mut incremental_out = [MaybeUninit::uninit(); BLAKE3_PARALLEL_INSTANCES];
+		hasher.finalize_into(&mut incremental_out);
+
+		for ((message, out), incremental_out) in messages.iter().zip(out).zip(incremental_out) {
+			let expected = <Blake3 as Digest>::digest(message);
+			assert_eq!(unsafe { out.assume_init() }, expected);
+			assert_eq!(unsafe { incremental_out.assume_init() }, expected);
+		}
+	}
+
+	#[test]
+	fn test_compression() {
+		let compression = Blake3Compression::default();
+		let left = <Blake3 as Digest>::digest(b"left");
+		let right = <Blake3 as Digest>::digest(b"right");
+
+		let output = compression.compress([left, right]);
+		assert_ne!(output, compression.compress([right, left]));
+		assert_ne!(output, <Blake3 as Digest>::digest([left, right].concat()));
+	}
+}
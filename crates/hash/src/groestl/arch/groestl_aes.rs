@@ -0,0 +1,544 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Grøstl-256 permutations with the AES round instructions of the CPU.
+//!
+//! Grøstl uses the AES S-box, so the SubBytes step maps to the last-round AES encryption
+//! instruction with a zero round key, which computes the AES ShiftRows of the substituted bytes.
+//! The state is kept in row-major order, two rows per 128-bit register. In this layout the AES
+//! ShiftRows is undone and the Grøstl ShiftBytes is applied with a single byte shuffle per
+//! register, and MixBytes is a linear combination of whole registers.
+//!
+//! The functions operate on the state representation of the portable implementation, and must
+//! only be called when [`is_available`] returns true.
+
+const ROUNDS: usize = 10;
+
+/// The number of 128-bit registers that hold the state.
+const N_REGS: usize = 4;
+
+/// Grøstl ShiftBytes shifts of the P permutation, per row.
+const SHIFTS_P: [usize; 8] = [0, 1, 2, 3, 4, 5, 6, 7];
+/// Grøstl ShiftBytes shifts of the Q permutation, per row.
+const SHIFTS_Q: [usize; 8] = [1, 3, 5, 7, 0, 2, 4, 6];
+
+/// Returns the byte shuffle masks that turn the AES ShiftRows of each register into the Grøstl
+/// ShiftBytes of its two rows.
+const fn shuffle_masks(shifts: [usize; 8]) -> [[u8; 16]; N_REGS] {
+	let mut masks = [[0u8; 16]; N_REGS];
+	let mut reg = 0;
+	while reg < N_REGS {
+		let mut i = 0;
+		while i < 16 {
+			// The byte of the substituted state that ShiftBytes moves to position `i`.
+			let row = 2 * reg + i / 8;
+			let src = (i / 8) * 8 + (i % 8 + shifts[row]) % 8;
+			// AES ShiftRows moves the byte at row `r` and column `c` of the 4x4 column-major AES
+			// state to column `c - r`.
+			let (aes_col, aes_row) = (src / 4, src % 4);
+			masks[reg][i] = (4 * ((aes_col + 4 - aes_row) % 4) + aes_row) as u8;
+			i += 1;
+		}
+		reg += 1;
+	}
+	masks
+}
+
+/// Returns the round constants of the P permutation, which only affect the first row.
+const fn round_constants_p() -> [[u8; 16]; ROUNDS] {
+	let mut constants = [[0u8; 16]; ROUNDS];
+	let mut r = 0;
+	while r < ROUNDS {
+		let mut j = 0;
+		while j < 8 {
+			constants[r][j] = ((j << 4) ^ r) as u8;
+			j += 1;
+		}
+		r += 1;
+	}
+	constants
+}
+
+/// Returns the round constants of the Q permutation for the register holding the last two rows.
+/// The other rows are complemented.
+const fn round_constants_q() -> [[u8; 16]; ROUNDS] {
+	let mut constants = [[0xffu8; 16]; ROUNDS];
+	let mut r = 0;
+	while r < ROUNDS {
+		let mut j = 0;
+		while j < 8 {
+			constants[r][8 + j] = 0xff ^ ((j << 4) ^ r) as u8;
+			j += 1;
+		}
+		r += 1;
+	}
+	constants
+}
+
+/// Byte shuffle of the transposition of a message block.
+const TRANSPOSE_MASK: [u8; 16] = [0, 8, 1, 9, 2, 10, 3, 11, 4, 12, 5, 13, 6, 14, 7, 15];
+/// Byte shuffle of the transposition of a state in the column representation, whose columns are
+/// big-endian words.
+const FROM_COLUMNS_MASK: [u8; 16] = [7, 15, 6, 14, 5, 13, 4, 12, 3, 11, 2, 10, 1, 9, 0, 8];
+/// Byte shuffle of the transposition of a row-major state with the order of the rows reversed.
+const TO_COLUMNS_MASK: [u8; 16] = [8, 0, 9, 1, 10, 2, 11, 3, 12, 4, 13, 5, 14, 6, 15, 7];
+
+static MASKS_P: [[u8; 16]; N_REGS] = shuffle_masks(SHIFTS_P);
+static MASKS_Q: [[u8; 16]; N_REGS] = shuffle_masks(SHIFTS_Q);
+static ROUND_CONSTANTS_P: [[u8; 16]; ROUNDS] = round_constants_p();
+static ROUND_CONSTANTS_Q: [[u8; 16]; ROUNDS] = round_constants_q();
+
+/// The 128-bit vector operations that the permutations are built from.
+///
+/// All methods are unsafe because they require the target features checked by [`is_available`].
+trait Backend {
+	type V: Copy;
+
+	unsafe fn load(bytes: &[u8; 16]) -> Self::V;
+
+	unsafe fn store(v: Self::V, bytes: &mut [u8; 16]);
+
+	unsafe fn xor(a: Self::V, b: Self::V) -> Self::V;
+
+	/// Returns the AES ShiftRows of the AES S-box applied to every byte.
+	unsafe fn aes_sub_shift(v: Self::V) -> Self::V;
+
+	unsafe fn shuffle(v: Self::V, mask: Self::V) -> Self::V;
+
+	/// Returns the high half of `a` followed by the low half of `b`.
+	unsafe fn concat_high_low(a: Self::V, b: Self::V) -> Self::V;
+
+	/// Interleaves the 16-bit lanes of the low halves of `a` and `b`.
+	unsafe fn interleave_low_u16(a: Self::V, b: Self::V) -> Self::V;
+
+	/// Interleaves the 16-bit lanes of the high halves of `a` and `b`.
+	unsafe fn interleave_high_u16(a: Self::V, b: Self::V) -> Self::V;
+
+	/// Interleaves the 32-bit lanes of the low halves of `a` and `b`.
+	unsafe fn interleave_low_u32(a: Self::V, b: Self::V) -> Self::V;
+
+	/// Interleaves the 32-bit lanes of the high halves of `a` and `b`.
+	unsafe fn interleave_high_u32(a: Self::V, b: Self::V) -> Self::V;
+
+	/// Multiplies every byte by 2 in the AES field.
+	unsafe fn xtime(v: Self::V) -> Self::V;
+}
+
+/// The Grøstl state, where register `k` holds the rows `2k` and `2k + 1`.
+type State<B> = [<B as Backend>::V; N_REGS];
+
+#[inline(always)]
+unsafe fn load_state<B: Backend>(bytes: &[[u8; 16]; N_REGS]) -> State<B> {
+	unsafe {
+		[
+			B::load(&bytes[0]),
+			B::load(&bytes[1]),
+			B::load(&bytes[2]),
+			B::load(&bytes[3]),
+		]
+	}
+}
+
+#[inline(always)]
+unsafe fn store_state<B: Backend>(state: State<B>, bytes: &mut [[u8; 16]; N_REGS]) {
+	for (v, bytes) in state.into_iter().zip(bytes) {
+		unsafe { B::store(v, bytes) };
+	}
+}
+
+#[inline(always)]
+unsafe fn xor_state<B: Backend>(mut a: State<B>, b: State<B>) -> State<B> {
+	for (a, b) in a.iter_mut().zip(b) {
+		*a = unsafe { B::xor(*a, b) };
+	}
+	a
+}
+
+/// Transposes an 8x8 byte matrix whose columns are held in the register halves.
+///
+/// `mask` selects the order of the bytes of every column before the transposition, and must
+/// interleave the two columns of a register.
+#[inline(always)]
+unsafe fn transpose<B: Backend>(s: State<B>, mask: B::V) -> State<B> {
+	unsafe {
+		let mut s = s;
+		for v in &mut s {
+			*v = B::shuffle(*v, mask);
+		}
+		let lo_01 = B::interleave_low_u16(s[0], s[1]);
+		let hi_01 = B::interleave_high_u16(s[0], s[1]);
+		let lo_23 = B::interleave_low_u16(s[2], s[3]);
+		let hi_23 = B::interleave_high_u16(s[2], s[3]);
+		[
+			B::interleave_low_u32(lo_01, lo_23),
+			B::interleave_high_u32(lo_01, lo_23),
+			B::interleave_low_u32(hi_01, hi_23),
+			B::interleave_high_u32(hi_01, hi_23),
+		]
+	}
+}
+
+/// Converts the column representation of the portable implementation to the row-major state.
+#[inline(always)]
+unsafe fn load_columns<B: Backend>(columns: &[u64; 8]) -> State<B> {
+	unsafe {
+		let s = load_state::<B>(bytemuck::must_cast_ref(columns));
+		transpose::<B>(s, B::load(&FROM_COLUMNS_MASK))
+	}
+}
+
+/// Converts the row-major state to the column representation of the portable implementation.
+#[inline(always)]
+unsafe fn store_columns<B: Backend>(s: State<B>, columns: &mut [u64; 8]) {
+	unsafe {
+		let reversed = [s[3], s[2], s[1], s[0]];
+		let s = transpose::<B>(reversed, B::load(&TO_COLUMNS_MASK));
+		store_state::<B>(s, bytemuck::must_cast_mut(columns));
+	}
+}
+
+/// Applies MixBytes, which multiplies every column by the circulant matrix
+/// `circ(02, 02, 03, 04, 05, 03, 05, 07)`.
+#[inline(always)]
+unsafe fn mix_bytes<B: Backend>(s: State<B>) -> State<B> {
+	unsafe {
+		// `pairs[j]` holds the rows `j` and `j + 1`.
+		let pairs = [
+			s[0],
+			B::concat_high_low(s[0], s[1]),
+			s[1],
+			B::concat_high_low(s[1], s[2]),
+			s[2],
+			B::concat_high_low(s[2], s[3]),
+			s[3],
+			B::concat_high_low(s[3], s[0]),
+		];
+		let mut out = s;
+		for (reg, out) in out.iter_mut().enumerate() {
+			let row = |t: usize| pairs[(2 * reg + t) % 8];
+			let xor3 = |a, b, c| B::xor(B::xor(a, b), c);
+
+			// Split the coefficients into multiples of 4, 2 and 1 and evaluate with Horner's
+			// rule.
+			let fours = B::xor(B::xor(row(3), row(4)), B::xor(row(6), row(7)));
+			let twos = B::xor(xor3(row(0), row(1), row(2)), xor3(row(5), row(7), B::xtime(fours)));
+			*out = B::xor(xor3(row(2), row(4), row(5)), xor3(row(6), row(7), B::xtime(twos)));
+		}
+		out
+	}
+}
+
+#[inline(always)]
+unsafe fn round<B: Backend>(s: State<B>, constants: State<B>, masks: &State<B>) -> State<B> {
+	unsafe {
+		let mut s = s;
+		for ((v, constant), mask) in s.iter_mut().zip(constants).zip(masks) {
+			*v = B::shuffle(B::aes_sub_shift(B::xor(*v, constant)), *mask);
+		}
+		mix_bytes::<B>(s)
+	}
+}
+
+#[inline(always)]
+unsafe fn constants_p<B: Backend>(r: usize, zero: B::V) -> State<B> {
+	unsafe { [B::load(&ROUND_CONSTANTS_P[r]), zero, zero, zero] }
+}
+
+#[inline(always)]
+unsafe fn constants_q<B: Backend>(r: usize, ones: B::V) -> State<B> {
+	unsafe { [ones, ones, ones, B::load(&ROUND_CONSTANTS_Q[r])] }
+}
+
+#[inline(always)]
+unsafe fn p_generic<B: Backend>(h: &mut [u64; 8]) {
+	unsafe {
+		let masks = load_state::<B>(&MASKS_P);
+		let zero = B::load(&[0; 16]);
+		let mut s = load_columns::<B>(h);
+		for r in 0..ROUNDS {
+			s = round::<B>(s, constants_p::<B>(r, zero), &masks);
+		}
+		store_columns::<B>(s, h);
+	}
+}
+
+#[inline(always)]
+unsafe fn q_generic<B: Backend>(h: &mut [u64; 8]) {
+	unsafe {
+		let masks = load_state::<B>(&MASKS_Q);
+		let ones = B::load(&[0xff; 16]);
+		let mut s = load_columns::<B>(h);
+		for r in 0..ROUNDS {
+			s = round::<B>(s, constants_q::<B>(r, ones), &masks);
+		}
+		store_columns::<B>(s, h);
+	}
+}
+
+#[inline(always)]
+unsafe fn compress_generic<B: Backend>(h: &mut [u64; 8], m: &[u8; 64]) {
+	unsafe {
+		let h_state = load_columns::<B>(h);
+		let m_state = load_state::<B>(bytemuck::must_cast_ref(m));
+		let mut q = transpose::<B>(m_state, B::load(&TRANSPOSE_MASK));
+		let mut p = xor_state::<B>(h_state, q);
+
+		// Interleave the independent P and Q rounds.
+		let masks_p = load_state::<B>(&MASKS_P);
+		let masks_q = load_state::<B>(&MASKS_Q);
+		let zero = B::load(&[0; 16]);
+		let ones = B::load(&[0xff; 16]);
+		for r in 0..ROUNDS {
+			p = round::<B>(p, constants_p::<B>(r, zero), &masks_p);
+			q = round::<B>(q, constants_q::<B>(r, ones), &masks_q);
+		}
+
+		store_columns::<B>(xor_state::<B>(h_state, xor_state::<B>(p, q)), h);
+	}
+}
+
+#[cfg(target_arch = "x86_64")]
+mod backend {
+	use std::arch::x86_64::*;
+
+	use super::Backend;
+
+	pub struct Impl;
+
+	impl Backend for Impl {
+		type V = __m128i;
+
+		#[inline(always)]
+		unsafe fn load(bytes: &[u8; 16]) -> __m128i {
+			unsafe { _mm_loadu_si128(bytes.as_ptr().cast()) }
+		}
+
+		#[inline(always)]
+		unsafe fn store(v: __m128i, bytes: &mut [u8; 16]) {
+			unsafe { _mm_storeu_si128(bytes.as_mut_ptr().cast(), v) }
+		}
+
+		#[inline(always)]
+		unsafe fn xor(a: __m128i, b: __m128i) -> __m128i {
+			unsafe { _mm_xor_si128(a, b) }
+		}
+
+		#[inline(always)]
+		unsafe fn aes_sub_shift(v: __m128i) -> __m128i {
+			unsafe { _mm_aesenclast_si128(v, _mm_setzero_si128()) }
+		}
+
+		#[inline(always)]
+		unsafe fn shuffle(v: __m128i, mask: __m128i) -> __m128i {
+			unsafe { _mm_shuffle_epi8(v, mask) }
+		}
+
+		#[inline(always)]
+		unsafe fn concat_high_low(a: __m128i, b: __m128i) -> __m128i {
+			unsafe { _mm_alignr_epi8::<8>(b, a) }
+		}
+
+		#[inline(always)]
+		unsafe fn interleave_low_u16(a: __m128i, b: __m128i) -> __m128i {
+			unsafe { _mm_unpacklo_epi16(a, b) }
+		}
+
+		#[inline(always)]
+		unsafe fn interleave_high_u16(a: __m128i, b: __m128i) -> __m128i {
+			unsafe { _mm_unpackhi_epi16(a, b) }
+		}
+
+		#[inline(always)]
+		unsafe fn interleave_low_u32(a: __m128i, b: __m128i) -> __m128i {
+			unsafe { _mm_unpacklo_epi32(a, b) }
+		}
+
+		#[inline(always)]
+		unsafe fn interleave_high_u32(a: __m128i, b: __m128i) -> __m128i {
+			unsafe { _mm_unpackhi_epi32(a, b) }
+		}
+
+		#[inline(always)]
+		unsafe fn xtime(v: __m128i) -> __m128i {
+			unsafe {
+				let high_bits = _mm_cmplt_epi8(v, _mm_setzero_si128());
+				let reduction = _mm_and_si128(high_bits, _mm_set1_epi8(0x1b));
+				_mm_xor_si128(_mm_add_epi8(v, v), reduction)
+			}
+		}
+	}
+
+	#[target_feature(enable = "aes,ssse3")]
+	pub unsafe fn compress(h: &mut [u64; 8], m: &[u8; 64]) {
+		unsafe { super::compress_generic::<Impl>(h, m) }
+	}
+
+	#[target_feature(enable = "aes,ssse3")]
+	pub unsafe fn p(h: &mut [u64; 8]) {
+		unsafe { super::p_generic::<Impl>(h) }
+	}
+
+	#[target_feature(enable = "aes,ssse3")]
+	pub unsafe fn q(h: &mut [u64; 8]) {
+		unsafe { super::q_generic::<Impl>(h) }
+	}
+
+	pub fn is_available() -> bool {
+		is_x86_feature_detected!("aes") && is_x86_feature_detected!("ssse3")
+	}
+}
+
+#[cfg(target_arch = "aarch64")]
+mod backend {
+	use std::arch::aarch64::*;
+
+	use super::Backend;
+
+	pub struct Impl;
+
+	impl Backend for Impl {
+		type V = uint8x16_t;
+
+		#[inline(always)]
+		unsafe fn load(bytes: &[u8; 16]) -> uint8x16_t {
+			unsafe { vld1q_u8(bytes.as_ptr()) }
+		}
+
+		#[inline(always)]
+		unsafe fn store(v: uint8x16_t, bytes: &mut [u8; 16]) {
+			unsafe { vst1q_u8(bytes.as_mut_ptr(), v) }
+		}
+
+		#[inline(always)]
+		unsafe fn xor(a: uint8x16_t, b: uint8x16_t) -> uint8x16_t {
+			unsafe { veorq_u8(a, b) }
+		}
+
+		#[inline(always)]
+		unsafe fn aes_sub_shift(v: uint8x16_t) -> uint8x16_t {
+			unsafe { vaeseq_u8(v, vdupq_n_u8(0)) }
+		}
+
+		#[inline(always)]
+		unsafe fn shuffle(v: uint8x16_t, mask: uint8x16_t) -> uint8x16_t {
+			unsafe { vqtbl1q_u8(v, mask) }
+		}
+
+		#[inline(always)]
+		unsafe fn concat_high_low(a: uint8x16_t, b: uint8x16_t) -> uint8x16_t {
+			unsafe { vextq_u8::<8>(a, b) }
+		}
+
+		#[inline(always)]
+		unsafe fn interleave_low_u16(a: uint8x16_t, b: uint8x16_t) -> uint8x16_t {
+			unsafe {
+				vreinterpretq_u8_u16(vzip1q_u16(vreinterpretq_u16_u8(a), vreinterpretq_u16_u8(b)))
+			}
+		}
+
+		#[inline(always)]
+		unsafe fn interleave_high_u16(a: uint8x16_t, b: uint8x16_t) -> uint8x16_t {
+			unsafe {
+				vreinterpretq_u8_u16(vzip2q_u16(vreinterpretq_u16_u8(a), vreinterpretq_u16_u8(b)))
+			}
+		}
+
+		#[inline(always)]
+		unsafe fn interleave_low_u32(a: uint8x16_t, b: uint8x16_t) -> uint8x16_t {
+			unsafe {
+				vreinterpretq_u8_u32(vzip1q_u32(vreinterpretq_u32_u8(a), vreinterpretq_u32_u8(b)))
+			}
+		}
+
+		#[inline(always)]
+		unsafe fn interleave_high_u32(a: uint8x16_t, b: uint8x16_t) -> uint8x16_t {
+			unsafe {
+				vreinterpretq_u8_u32(vzip2q_u32(vreinterpretq_u32_u8(a), vreinterpretq_u32_u8(b)))
+			}
+		}
+
+		#[inline(always)]
+		unsafe fn xtime(v: uint8x16_t) -> uint8x16_t {
+			unsafe {
+				let high_bits = vreinterpretq_u8_s8(vshrq_n_s8::<7>(vreinterpretq_s8_u8(v)));
+				let reduction = vandq_u8(high_bits, vdupq_n_u8(0x1b));
+				veorq_u8(vshlq_n_u8::<1>(v), reduction)
+			}
+		}
+	}
+
+	#[target_feature(enable = "aes")]
+	pub unsafe fn compress(h: &mut [u64; 8], m: &[u8; 64]) {
+		unsafe { super::compress_generic::<Impl>(h, m) }
+	}
+
+	#[target_feature(enable = "aes")]
+	pub unsafe fn p(h: &mut [u64; 8]) {
+		unsafe { super::p_generic::<Impl>(h) }
+	}
+
+	#[target_feature(enable = "aes")]
+	pub unsafe fn q(h: &mut [u64; 8]) {
+		unsafe { super::q_generic::<Impl>(h) }
+	}
+
+	pub fn is_available() -> bool {
+		std::arch::is_aarch64_feature_detected!("aes")
+	}
+}
+
+pub use backend::{compress, is_available, p, q};
+
+#[cfg(test)]
+mod tests {
+	use rand::{Rng, SeedableRng, rngs::StdRng};
+
+	use super::*;
+	use crate::groestl::arch::portable::compress512;
+
+	// The tests pass trivially on CPUs without the AES instructions, where the backend is never
+	// selected.
+
+	#[test]
+	fn test_compress_matches_portable() {
+		if !is_available() {
+			return;
+		}
+
+		let mut rng = StdRng::seed_from_u64(0);
+		for _ in 0..256 {
+			let h = rng.random::<[u64; 8]>();
+			let mut m = [0u8; 64];
+			rng.fill(&mut m);
+
+			let mut expected = h;
+			compress512::compress(&mut expected, &m);
+			let mut result = h;
+			unsafe { compress(&mut result, &m) };
+			assert_eq!(result, expected);
+		}
+	}
+
+	#[test]
+	fn test_permutations_match_portable() {
+		if !is_available() {
+			return;
+		}
+
+		let mut rng = StdRng::seed_from_u64(0);
+		for _ in 0..256 {
+			let h = rng.random::<[u64; 8]>();
+
+			let mut expected = h;
+			compress512::p(&mut expected);
+			let mut result = h;
+			unsafe { p(&mut result) };
+			assert_eq!(result, expected);
+
+			let mut expected = h;
+			compress512::q(&mut expected);
+			let mut result = h;
+			unsafe { q(&mut result) };
+			assert_eq!(result, expected);
+		}
+	}
+}
@@ -3,7 +3,8 @@ use cfg_if::cfg_if;
 
 // We will choose the AVX512 Implementation of Grøstl if our machine supports the various AVX512
 // extensions, otherwise defaults to the portable implementation which was found to be fast in most
-// machines
+// machines. The portable implementation switches to the AES round instructions at runtime when the
+// CPU supports them.
 
 cfg_if! {
 	if #[cfg(all(feature = "nightly_features", target_arch = "x86_64", target_feature = "avx2", target_feature = "gfni",))] {
@@ -20,6 +21,8 @@ cfg_if! {
 		mod groestl_avx512;
 		pub use groestl_avx512::GroestlShortImpl;
 	} else {
+		#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+		mod groestl_aes;
 		mod portable;
 		pub use portable::GroestlShortImpl;
 	}
@@ -1,8 +1,10 @@
 // Copyright 2025 Irreducible Inc.
 
 use super::super::GroestlShortInternal;
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+use super::groestl_aes;
 
-mod compress512;
+pub(super) mod compress512;
 mod table;
 
 #[derive(Debug, Clone)]
@@ -34,14 +36,29 @@ impl GroestlShortInternal for GroestlShortImpl {
 	}
 
 	fn p_perm(h: &mut Self::State) {
+		#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+		if groestl_aes::is_available() {
+			// SAFETY: The required CPU features are available.
+			return unsafe { groestl_aes::p(h) };
+		}
 		compress512::p(h)
 	}
 
 	fn q_perm(h: &mut Self::State) {
+		#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+		if groestl_aes::is_available() {
+			// SAFETY: The required CPU features are available.
+			return unsafe { groestl_aes::q(h) };
+		}
 		compress512::q(h)
 	}
 
 	fn compress(h: &mut Self::State, m: &[u8; 64]) {
+		#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+		if groestl_aes::is_available() {
+			// SAFETY: The required CPU features are available.
+			return unsafe { groestl_aes::compress(h, m) };
+		}
 		compress512::compress(h, m)
 	}
 }
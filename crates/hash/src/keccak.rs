@@ -0,0 +1,90 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Keccak-256 and SHA3-256 hash functions and compression functions.
+//!
+//! [`Keccak256`] is the original Keccak submission with a 256-bit output, which is the hash
+//! function of the Ethereum Virtual Machine, and [`Sha3_256`] is the standardized FIPS 202 variant
+//! that differs only in the padding. Both are the [`sha3`] crate implementations and implement
+//! [`ParallelDigest`](crate::multi_digest::ParallelDigest), so together with the compression
+//! functions in this module they instantiate Merkle tree commitment schemes.
+
+use digest::{Digest, Output};
+pub use sha3::{Keccak256, Sha3_256};
+
+use crate::{CompressionFunction, PseudoCompressionFunction};
+
+/// A two-to-one compression function for Keccak-256 digests.
+///
+/// The compression is the Keccak-256 hash of the concatenated inputs, which matches the
+/// `keccak256(abi.encodePacked(left, right))` hashing of internal nodes in Merkle trees verified on
+/// the EVM. The compression is not domain-separated from the leaf hashes, which is sound for
+/// Merkle trees where openings have a fixed depth.
+#[derive(Debug, Default, Clone)]
+pub struct Keccak256ByteCompression;
+
+impl PseudoCompressionFunction<Output<Keccak256>, 2> for Keccak256ByteCompression {
+	fn compress(&self, input: [Output<Keccak256>; 2]) -> Output<Keccak256> {
+		Keccak256::new()
+			.chain_update(input[0])
+			.chain_update(input[1])
+			.finalize()
+	}
+}
+
+impl CompressionFunction<Output<Keccak256>, 2> for Keccak256ByteCompression {}
+
+/// A two-to-one compression function for SHA3-256 digests.
+///
+/// The compression is the SHA3-256 hash of the concatenated inputs.
+#[derive(Debug, Default, Clone)]
+pub struct Sha3_256ByteCompression;
+
+impl PseudoCompressionFunction<Output<Sha3_256>, 2> for Sha3_256ByteCompression {
+	fn compress(&self, input: [Output<Sha3_256>; 2]) -> Output<Sha3_256> {
+		Sha3_256::new()
+			.chain_update(input[0])
+			.chain_update(input[1])
+			.finalize()
+	}
+}
+
+impl CompressionFunction<Output<Sha3_256>, 2> for Sha3_256ByteCompression {}
+
+#[cfg(test)]
+mod tests {
+	use hex_literal::hex;
+
+	use super::*;
+
+	#[test]
+	fn test_digests_match_known_answers() {
+		assert_eq!(
+			Keccak256::digest(b"").as_slice(),
+			hex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+		);
+		assert_eq!(
+			Sha3_256::digest(b"abc").as_slice(),
+			hex!("3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532")
+		);
+	}
+
+	#[test]
+	fn test_keccak256_compression() {
+		let left = Keccak256::digest(b"left");
+		let right = Keccak256::digest(b"right");
+
+		let output = Keccak256ByteCompression.compress([left, right]);
+		assert_eq!(output, Keccak256::digest([left, right].concat()));
+		assert_ne!(output, Keccak256ByteCompression.compress([right, left]));
+	}
+
+	#[test]
+	fn test_sha3_256_compression() {
+		let left = Sha3_256::digest(b"left");
+		let right = Sha3_256::digest(b"right");
+
+		let output = Sha3_256ByteCompression.compress([left, right]);
+		assert_eq!(output, Sha3_256::digest([left, right].concat()));
+		assert_ne!(output, Sha3_256ByteCompression.compress([right, left]));
+	}
+}
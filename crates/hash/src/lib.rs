@@ -8,7 +8,8 @@
 //! so can be arithmetized efficiently with a Binius constraint system.
 //!
 //! This crate also provides an implementation of [Vision Mark-32], a cryptographic sponge function
-//! designed for efficient Binius arithmetization.
+//! designed for efficient Binius arithmetization, and wraps the standard SHA-2, SHA-3, Keccak-256
+//! and BLAKE3 hash functions for use in commitment schemes.
 //!
 //! [Grøstl-256]: <https://www.groestl.info/>
 //! [Vision Mark-32]: <https://eprint.iacr.org/2024/633>
@@ -18,8 +19,10 @@
 	feature(stdarch_x86_avx512)
 )]
 
+pub mod blake3;
 pub mod compression;
 pub mod groestl;
+pub mod keccak;
 pub mod multi_digest;
 pub mod permutation;
 mod serialization;
@@ -123,11 +123,14 @@ impl<D: MultiDigest<N, Digest: Send> + Send + Sync, const N: usize> ParallelDige
 			buffers,
 			|buffers, (data, out_chunk)| {
 				let mut hasher = self.0.clone();
+				let n_items = data.len();
 				for (buf, chunk) in buffers.iter_mut().zip(data.into_iter()) {
 					buf.clear();
 					chunk.serialize(buf);
 				}
-				let data = array::from_fn(|i| buffers[i].as_ref());
+				// Multi-digests may require all messages to have the same length, so the unused
+				// slots of the last chunk repeat the first message.
+				let data = array::from_fn(|i| buffers[if i < n_items { i } else { 0 }].as_ref());
 				hasher.update(data);
 
 				if out_chunk.len() == N {
@@ -333,4 +336,13 @@ mod tests {
 			check_parallel_digest_consistency::<ParallelMultidigestImpl<MockMultiDigest, 4>>(data);
 		}
 	}
+
+	#[test]
+	fn test_partial_chunk_of_equal_length_multi_digest() {
+		// The byte-sliced Vision hasher requires all messages of a chunk to have the same length.
+		for n_hashes in [1, 5, 33] {
+			let data = generate_mock_data(n_hashes, 16);
+			check_parallel_digest_consistency::<crate::Vision32ParallelDigest>(data);
+		}
+	}
 }
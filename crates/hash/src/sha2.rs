@@ -1,8 +1,18 @@
 // Copyright 2023-2025 Irreducible Inc.
 
+//! SHA-2 hash functions and compression functions.
+//!
+//! The digests are the [`sha2`] crate implementations, which detect the SHA extensions of the
+//! CPU at runtime (SHA-NI on x86-64 and the cryptographic extensions on ARMv8) and fall back to
+//! portable code otherwise. [`Sha256`] and [`Sha512`] implement
+//! [`ParallelDigest`](crate::multi_digest::ParallelDigest), so together with the compression
+//! functions in this module they instantiate Merkle tree commitment schemes, and they can
+//! instantiate Fiat-Shamir challengers over byte-oriented hashers.
+
 use bytemuck::{bytes_of_mut, must_cast};
 use digest::{Digest, core_api::Block};
-use sha2::{Sha256, compress256, digest::Output};
+pub use sha2::{Sha256, Sha512};
+use sha2::{compress256, compress512, digest::Output};
 
 use crate::{CompressionFunction, PseudoCompressionFunction};
 
@@ -33,3 +43,78 @@ impl PseudoCompressionFunction<Output<Sha256>, 2> for Sha256Compression {
 }
 
 impl CompressionFunction<Output<Sha256>, 2> for Sha256Compression {}
+
+/// A two-to-one compression function for SHA-512 digests.
+#[derive(Debug, Clone)]
+pub struct Sha512Compression {
+	initial_state: [u64; 8],
+}
+
+impl Default for Sha512Compression {
+	fn default() -> Self {
+		let initial_state_bytes = Sha512::digest(b"BINIUS SHA-512 COMPRESS");
+		let mut initial_state = [0u64; 8];
+		bytes_of_mut(&mut initial_state).copy_from_slice(&initial_state_bytes);
+		Self { initial_state }
+	}
+}
+
+impl PseudoCompressionFunction<Output<Sha512>, 2> for Sha512Compression {
+	fn compress(&self, input: [Output<Sha512>; 2]) -> Output<Sha512> {
+		let mut ret = self.initial_state;
+		let mut block = <Block<Sha512>>::default();
+		block.as_mut_slice()[..64].copy_from_slice(input[0].as_slice());
+		block.as_mut_slice()[64..].copy_from_slice(input[1].as_slice());
+		compress512(&mut ret, &[block]);
+		must_cast::<[u64; 8], [u8; 64]>(ret).into()
+	}
+}
+
+impl CompressionFunction<Output<Sha512>, 2> for Sha512Compression {}
+
+#[cfg(test)]
+mod tests {
+	use hex_literal::hex;
+
+	use super::*;
+
+	#[test]
+	fn test_sha256_compression() {
+		let compression = Sha256Compression::default();
+		let left = Sha256::digest(b"left");
+		let right = Sha256::digest(b"right");
+
+		let output = compression.compress([left, right]);
+		assert_eq!(output, compression.compress([left, right]));
+		assert_ne!(output, compression.compress([right, left]));
+	}
+
+	#[test]
+	fn test_sha512_compression() {
+		let compression = Sha512Compression::default();
+		let left = Sha512::digest(b"left");
+		let right = Sha512::digest(b"right");
+
+		let output = compression.compress([left, right]);
+		assert_eq!(output, compression.compress([left, right]));
+		assert_ne!(output, compression.compress([right, left]));
+		assert_ne!(output, Sha512::digest([left, right].concat()));
+	}
+
+	#[test]
+	fn test_sha_digests_match_known_answers() {
+		// The digests dispatch to the SHA extensions when they are available, which must not
+		// change the results.
+		assert_eq!(
+			Sha256::digest(b"abc").as_slice(),
+			hex!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+		);
+		assert_eq!(
+			Sha512::digest(b"abc").as_slice(),
+			hex!(
+				"ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a"
+				"2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+			)
+		);
+	}
+}
@@ -0,0 +1,389 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Random small constraint systems with matching witnesses, for fuzzing.
+//!
+//! [`arb_system`] is a proptest strategy for an [`ArbitrarySystem`], a description of a few
+//! tables with random sizes, committed columns, product constraints, linear computed columns,
+//! packed byte columns and channel flushes. [`ArbitrarySystem::check`] builds the constraint
+//! system, fills a satisfying witness, derives the boundaries that balance the flushes, and runs
+//! the system end to end:
+//!
+//! ```ignore
+//! proptest! {
+//!     #[test]
+//!     fn test_arbitrary_systems(system in arb_system()) {
+//!         system.check(true);
+//!     }
+//! }
+//! ```
+//!
+//! The structures are deliberately irregular: table sizes need not be powers of two, columns of
+//! several field sizes are mixed, and flushes pick arbitrary columns with arbitrary
+//! multiplicities, so that the padding and serialization paths see shapes that hand-written tests
+//! do not cover.
+
+use binius_compute::cpu::alloc::CpuComputeAllocator;
+use binius_core::constraint_system::{
+	ConstraintSystem as CompiledConstraintSystem,
+	channel::{Boundary, ChannelId, FlushDirection},
+};
+use binius_field::{arch::OptimalUnderlier, as_packed_field::PackedType};
+use binius_hash::groestl::Groestl256;
+use binius_utils::{DeserializeBytes, SerializationMode, SerializeBytes};
+use proptest::{collection::vec, prelude::*};
+
+use super::{
+	B8, B32, B128, Col, ColumnId, ConstraintSystem, FlushOpts, TableId, WitnessIndex,
+	test_utils::{
+		ClosureFiller, Mutation, find_undetected_mutations,
+		validate_system_witness_with_prove_verify,
+	},
+};
+
+/// A table of an [`ArbitrarySystem`].
+///
+/// The values of a row are, in order, the committed columns, the product columns, the sum
+/// columns and, if the table has one, the packed byte column, see [`Self::row_values`].
+#[derive(Debug, Clone)]
+pub struct ArbitraryTable {
+	/// The values of the committed `B32` columns in every row.
+	pub rows: Vec<Vec<u32>>,
+	/// Whether the table is required to have a power-of-two size.
+	pub power_of_two: bool,
+	/// Committed columns constrained to the product of two committed columns.
+	pub products: Vec<(usize, usize)>,
+	/// Computed columns that are the sum of two committed columns.
+	pub sums: Vec<(usize, usize)>,
+	/// Whether the table has a committed `Col<B8, 4>` that is packed into a `B32` column. The
+	/// bytes are the little-endian bytes of the first committed column rotated by a byte.
+	pub packed_bytes: bool,
+	/// The values that are pushed to the channel of the table.
+	pub flushed: Vec<usize>,
+	/// The multiplicity of the flush.
+	pub multiplicity: u32,
+}
+
+impl ArbitraryTable {
+	/// Returns the number of committed `B32` columns.
+	pub fn n_committed(&self) -> usize {
+		self.rows.first().map_or(0, Vec::len)
+	}
+
+	/// Returns the number of values in a row.
+	pub fn n_values(&self) -> usize {
+		self.n_committed() + self.products.len() + self.sums.len() + self.packed_bytes as usize
+	}
+
+	/// Returns the values of all columns in a row.
+	pub fn row_values(&self, row: &[u32]) -> Vec<B32> {
+		let committed = row.iter().copied().map(B32::new);
+		let products = self
+			.products
+			.iter()
+			.map(|&(a, b)| B32::new(row[a]) * B32::new(row[b]));
+		let sums = self
+			.sums
+			.iter()
+			.map(|&(a, b)| B32::new(row[a]) + B32::new(row[b]));
+		let packed = self.packed_bytes.then(|| B32::new(row[0].rotate_left(8)));
+		committed
+			.chain(products)
+			.chain(sums)
+			.chain(packed)
+			.collect()
+	}
+}
+
+/// A random constraint system together with a satisfying witness, see the [module
+/// documentation](self).
+#[derive(Debug, Clone)]
+pub struct ArbitrarySystem {
+	pub tables: Vec<ArbitraryTable>,
+}
+
+/// The columns that [`ArbitrarySystem::build`] added for a table.
+#[derive(Debug, Clone)]
+pub struct ArbitraryTableCols {
+	pub id: TableId,
+	pub channel: ChannelId,
+	pub committed: Vec<Col<B32>>,
+	pub products: Vec<Col<B32>>,
+	pub sums: Vec<Col<B32>>,
+	pub bytes: Option<Col<B8, 4>>,
+}
+
+impl ArbitrarySystem {
+	/// Builds the constraint system, returning the columns of every table.
+	pub fn build(&self, cs: &mut ConstraintSystem) -> Vec<ArbitraryTableCols> {
+		self.tables
+			.iter()
+			.enumerate()
+			.map(|(i, spec)| {
+				let channel = cs.add_channel(format!("channel_{i}"));
+				let mut table = cs.add_table(format!("table_{i}"));
+				if spec.power_of_two {
+					table.require_power_of_two_size();
+				}
+				let committed = (0..spec.n_committed())
+					.map(|j| table.add_committed::<B32, 1>(format!("committed_{j}")))
+					.collect::<Vec<_>>();
+				let products = spec
+					.products
+					.iter()
+					.enumerate()
+					.map(|(j, &(a, b))| {
+						let product = table.add_committed::<B32, 1>(format!("product_{j}"));
+						table.assert_zero(
+							format!("product_{j}"),
+							committed[a] * committed[b] - product,
+						);
+						product
+					})
+					.collect::<Vec<_>>();
+				let sums = spec
+					.sums
+					.iter()
+					.enumerate()
+					.map(|(j, &(a, b))| {
+						table.add_computed(format!("sum_{j}"), committed[a] + committed[b])
+					})
+					.collect::<Vec<_>>();
+				let (bytes, packed) = if spec.packed_bytes {
+					let bytes = table.add_committed::<B8, 4>("bytes");
+					let packed = table.add_packed::<_, 4, B32, 1>("bytes_packed", bytes);
+					(Some(bytes), Some(packed))
+				} else {
+					(None, None)
+				};
+
+				let values = committed
+					.iter()
+					.chain(&products)
+					.chain(&sums)
+					.chain(&packed)
+					.copied()
+					.collect::<Vec<_>>();
+				if !spec.flushed.is_empty() {
+					table.push_with_opts(
+						channel,
+						spec.flushed.iter().map(|&j| values[j]),
+						FlushOpts {
+							multiplicity: spec.multiplicity,
+							selectors: vec![],
+						},
+					);
+				}
+
+				ArbitraryTableCols {
+					id: table.id(),
+					channel,
+					committed,
+					products,
+					sums,
+					bytes,
+				}
+			})
+			.collect()
+	}
+
+	/// Fills the witness of the tables built by [`Self::build`].
+	pub fn fill_witness(
+		&self,
+		cols: &[ArbitraryTableCols],
+		witness: &mut WitnessIndex<PackedType<OptimalUnderlier, B128>>,
+	) -> anyhow::Result<()> {
+		for (spec, cols) in self.tables.iter().zip(cols) {
+			let filler = ClosureFiller::new(cols.id, |rows: &[Vec<u32>], segment| {
+				let mut values = cols
+					.committed
+					.iter()
+					.chain(&cols.products)
+					.chain(&cols.sums)
+					.map(|&col| segment.get_scalars_mut(col))
+					.collect::<Result<Vec<_>, _>>()?;
+				let mut bytes = cols
+					.bytes
+					.map(|bytes| segment.get_rows_mut_as::<u8, _, 4>(bytes))
+					.transpose()?;
+				for (i, row) in rows.iter().enumerate() {
+					let row_values = spec.row_values(row);
+					for (col, &value) in values.iter_mut().zip(&row_values) {
+						col[i] = value;
+					}
+					if let Some(bytes) = &mut bytes {
+						bytes.set_row(i, &row[0].rotate_left(8).to_le_bytes());
+					}
+				}
+				Ok(())
+			});
+			witness.fill_table_sequential(&filler, &spec.rows)?;
+		}
+		Ok(())
+	}
+
+	/// Returns the boundaries that pull every flushed row from the channels.
+	pub fn boundaries(&self, cols: &[ArbitraryTableCols]) -> Vec<Boundary<B128>> {
+		self.tables
+			.iter()
+			.zip(cols)
+			.filter(|(spec, _)| !spec.flushed.is_empty())
+			.flat_map(|(spec, cols)| {
+				spec.rows.iter().map(move |row| {
+					let row_values = spec.row_values(row);
+					Boundary {
+						values: spec
+							.flushed
+							.iter()
+							.map(|&j| B128::from(row_values[j]))
+							.collect(),
+						channel_id: cols.channel,
+						direction: FlushDirection::Pull,
+						multiplicity: spec.multiplicity as u64,
+					}
+				})
+			})
+			.collect()
+	}
+
+	/// Returns the committed columns in which a mutation of any value must be rejected.
+	///
+	/// These are the columns that are flushed, directly, through a sum with another column or
+	/// through a packed column, and the product columns. A factor of a product is left out, as a
+	/// mutation of it goes undetected where the other factor is zero.
+	pub fn constrained_columns(&self, cols: &[ArbitraryTableCols]) -> Vec<ColumnId> {
+		self.tables
+			.iter()
+			.zip(cols)
+			.flat_map(|(spec, cols)| {
+				let n_committed = spec.n_committed();
+				let sums_offset = n_committed + spec.products.len();
+				let packed_offset = sums_offset + spec.sums.len();
+				let mut constrained = cols.products.iter().map(|col| col.id()).collect::<Vec<_>>();
+				for &j in &spec.flushed {
+					if j < n_committed {
+						constrained.push(cols.committed[j].id());
+					} else if (sums_offset..packed_offset).contains(&j) {
+						let (a, b) = spec.sums[j - sums_offset];
+						if a != b {
+							constrained.extend([cols.committed[a].id(), cols.committed[b].id()]);
+						}
+					} else if j == packed_offset {
+						constrained.extend(cols.bytes.map(|bytes| bytes.id()));
+					}
+				}
+				constrained
+			})
+			.collect()
+	}
+
+	/// Builds the system and checks that every mutation of the witness, the flush and boundary
+	/// multiplicities and, if `prove_verify` is set, the proof is rejected, except for mutations
+	/// of columns outside of [`Self::constrained_columns`] and of the sizes of tables without
+	/// flushes.
+	///
+	/// ## Panics
+	///
+	/// * if a mutation that must be rejected is not
+	pub fn check_mutations(&self, prove_verify: bool) {
+		let mut cs = ConstraintSystem::new();
+		let cols = self.build(&mut cs);
+		let constrained = self.constrained_columns(&cols);
+
+		let mut allocator = CpuComputeAllocator::new(1 << 16);
+		let undetected = find_undetected_mutations::<OptimalUnderlier>(
+			&cs,
+			&mut allocator,
+			|witness| self.fill_witness(&cols, witness),
+			&self.boundaries(&cols),
+			prove_verify,
+		);
+		let missed = undetected
+			.into_iter()
+			.filter(|mutation| match mutation {
+				Mutation::WitnessCell { column, .. } => constrained.contains(column),
+				Mutation::TableSize { table } => !cols
+					.iter()
+					.zip(&self.tables)
+					.any(|(cols, spec)| cols.id == *table && spec.flushed.is_empty()),
+				_ => true,
+			})
+			.collect::<Vec<_>>();
+		assert!(missed.is_empty(), "mutations were not rejected: {missed:?}");
+	}
+
+	/// Builds and compiles the system, checks that the compiled system survives a serialization
+	/// round trip, and validates the witness, proving and verifying it if `prove_verify` is set.
+	///
+	/// ## Panics
+	///
+	/// * if any of the steps fails
+	pub fn check(&self, prove_verify: bool) {
+		let mut cs = ConstraintSystem::new();
+		let cols = self.build(&mut cs);
+
+		let ccs = cs.compile().unwrap();
+		let mut buf = Vec::new();
+		ccs.serialize(&mut buf, SerializationMode::CanonicalTower)
+			.unwrap();
+		let deserialized = CompiledConstraintSystem::<B128>::deserialize(
+			&mut buf.as_slice(),
+			SerializationMode::CanonicalTower,
+		)
+		.unwrap();
+		assert_eq!(
+			deserialized.digest::<Groestl256>(),
+			ccs.digest::<Groestl256>(),
+			"the constraint system changed in a serialization round trip"
+		);
+
+		let mut allocator = CpuComputeAllocator::new(1 << 16);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(&cs, &allocator);
+		self.fill_witness(&cols, &mut witness).unwrap();
+
+		validate_system_witness_with_prove_verify::<OptimalUnderlier>(
+			&cs,
+			witness,
+			self.boundaries(&cols),
+			prove_verify,
+		);
+	}
+}
+
+/// A proptest strategy for a table of an [`ArbitrarySystem`].
+pub fn arb_table() -> impl Strategy<Value = ArbitraryTable> {
+	(1..=4usize, any::<bool>(), any::<bool>(), 1..=3u32)
+		.prop_flat_map(|(n_committed, power_of_two, packed_bytes, multiplicity)| {
+			let n_rows = if power_of_two {
+				(0..=4usize).prop_map(|log_n| 1 << log_n).boxed()
+			} else {
+				(1..=20usize).boxed()
+			};
+			let pair = (0..n_committed, 0..n_committed);
+			(
+				n_rows.prop_flat_map(move |n_rows| vec(vec(any::<u32>(), n_committed), n_rows)),
+				vec(pair.clone(), 0..=2),
+				vec(pair, 0..=2),
+				Just(power_of_two),
+				Just(packed_bytes),
+				Just(multiplicity),
+			)
+		})
+		.prop_flat_map(|(rows, products, sums, power_of_two, packed_bytes, multiplicity)| {
+			let n_values = rows[0].len() + products.len() + sums.len() + packed_bytes as usize;
+			vec(0..n_values, 0..=3).prop_map(move |flushed| ArbitraryTable {
+				rows: rows.clone(),
+				power_of_two,
+				products: products.clone(),
+				sums: sums.clone(),
+				packed_bytes,
+				flushed,
+				multiplicity,
+			})
+		})
+}
+
+/// A proptest strategy for an [`ArbitrarySystem`] of one to three tables.
+pub fn arb_system() -> impl Strategy<Value = ArbitrarySystem> {
+	vec(arb_table(), 1..=3).prop_map(|tables| ArbitrarySystem { tables })
+}
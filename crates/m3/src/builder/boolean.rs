@@ -0,0 +1,195 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Boolean algebra over columns that are constrained to hold bits.
+//!
+//! A column of a field larger than [`B1`] is often used as a flag, for instance to select between
+//! two values with `flag * a + (1 - flag) * b`. Such an expression is only sound if every value of
+//! the column is 0 or 1, which requires the constraint `flag² - flag = 0` that is easy to forget.
+//! [`TableBuilder::assert_boolean`](super::TableBuilder::assert_boolean) adds that constraint
+//! once per column and returns a [`BoolExpr`], the only type with the logical operators, so that
+//! the operators can only be applied to bits.
+//!
+//! The operators are the arithmetic forms of the logical ones in a binary field, where `1 + 1 = 0`:
+//!
+//! * `a & b` is `a * b`
+//! * `a ^ b` is `a + b`
+//! * `a | b` is `a + b + a * b`
+//! * `!a` is `a + 1`
+//!
+//! A conjunction multiplies the degrees of its operands, so the expressions of long conjunctions
+//! quickly exceed the maximum constraint degree and should be committed to a column in steps.
+
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+use binius_field::TowerField;
+
+use super::{B1, column::Col, expr::Expr, table::TableId};
+
+/// A boolean expression over columns that hold bits.
+///
+/// Every value of the expression is 0 or 1, provided the columns it is built from are
+/// constrained with [`TableBuilder::assert_boolean`](super::TableBuilder::assert_boolean) or are
+/// [`B1`] columns, whose values are bits by construction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoolExpr<F: TowerField, const V: usize = 1>(Expr<F, V>);
+
+impl<F: TowerField, const V: usize> BoolExpr<F, V> {
+	/// Wraps a column whose values are known to be bits.
+	pub(super) fn from_boolean_col(col: Col<F, V>) -> Self {
+		Self(col.into())
+	}
+
+	/// The table that the expression is over.
+	pub fn table_id(&self) -> TableId {
+		self.0.table_id()
+	}
+
+	/// Returns the arithmetic expression, which evaluates to 0 or 1.
+	pub fn into_expr(self) -> Expr<F, V> {
+		self.0
+	}
+}
+
+impl<const V: usize> From<Col<B1, V>> for BoolExpr<B1, V> {
+	fn from(col: Col<B1, V>) -> Self {
+		Self::from_boolean_col(col)
+	}
+}
+
+impl<F: TowerField, const V: usize> From<BoolExpr<F, V>> for Expr<F, V> {
+	fn from(value: BoolExpr<F, V>) -> Self {
+		value.0
+	}
+}
+
+impl<F: TowerField, const V: usize> BitAnd for BoolExpr<F, V> {
+	type Output = Self;
+
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn bitand(self, rhs: Self) -> Self {
+		Self(self.0 * rhs.0)
+	}
+}
+
+impl<F: TowerField, const V: usize> BitXor for BoolExpr<F, V> {
+	type Output = Self;
+
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn bitxor(self, rhs: Self) -> Self {
+		Self(self.0 + rhs.0)
+	}
+}
+
+impl<F: TowerField, const V: usize> BitOr for BoolExpr<F, V> {
+	type Output = Self;
+
+	fn bitor(self, rhs: Self) -> Self {
+		let product = self.0.clone() * rhs.0.clone();
+		Self(self.0 + rhs.0 + product)
+	}
+}
+
+impl<F: TowerField, const V: usize> Not for BoolExpr<F, V> {
+	type Output = Self;
+
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn not(self) -> Self {
+		Self(self.0 + F::ONE)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_compute::cpu::alloc::CpuComputeAllocator;
+	use binius_field::{arch::OptimalUnderlier, as_packed_field::PackedType};
+
+	use super::*;
+	use crate::builder::{
+		B32, B128, ConstraintSystem, TableId, WitnessIndex, test_utils::validate_system_witness,
+	};
+
+	/// A table with the flags `a` and `b` and the column `c = (a & b) | !(a ^ b)`.
+	fn logic_system() -> (ConstraintSystem, TableId, [Col<B32>; 3]) {
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("logic");
+		let a = table.add_committed::<B32, 1>("a");
+		let b = table.add_committed::<B32, 1>("b");
+		let c = table.add_committed::<B32, 1>("c");
+		let a_bool = table.assert_boolean(a);
+		let b_bool = table.assert_boolean(b);
+		// A second call does not constrain the column again.
+		let a_bool_again = table.assert_boolean(a);
+		let expected = (a_bool.clone() & b_bool.clone()) | !(a_bool_again ^ b_bool);
+		table.assert_zero("c", expected.into_expr() - c);
+		let table_id = table.id();
+		drop(table);
+		(cs, table_id, [a, b, c])
+	}
+
+	fn fill(
+		witness: &mut WitnessIndex<PackedType<OptimalUnderlier, B128>>,
+		id: TableId,
+		cols: [Col<B32>; 3],
+		rows: &[[u32; 3]],
+	) {
+		let table_witness = witness.init_table(id, rows.len()).unwrap();
+		let segment = table_witness.full_segment();
+		for (i, col) in cols.into_iter().enumerate() {
+			let mut values = segment.get_mut_as::<u32, _, 1>(col).unwrap();
+			for (value, row) in values.iter_mut().zip(rows) {
+				*value = row[i];
+			}
+		}
+	}
+
+	#[test]
+	fn test_boolean_constraint_added_once() {
+		let (cs, table_id, _) = logic_system();
+		let n_constraints = cs.tables[table_id]
+			.partitions
+			.values()
+			.map(|partition| partition.zero_constraints.len())
+			.sum::<usize>();
+		assert_eq!(n_constraints, 3);
+		let names = cs.tables[table_id]
+			.partitions
+			.values()
+			.flat_map(|partition| &partition.zero_constraints)
+			.map(|constraint| constraint.name.as_str())
+			.collect::<Vec<_>>();
+		assert_eq!(names, ["a is boolean", "b is boolean", "c"]);
+	}
+
+	#[test]
+	fn test_logic_operators() {
+		let (cs, table_id, cols) = logic_system();
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(&cs, &allocator);
+		fill(&mut witness, table_id, cols, &[[0, 0, 1], [0, 1, 0], [1, 0, 0], [1, 1, 1]]);
+		validate_system_witness::<OptimalUnderlier>(&cs, witness, vec![]);
+	}
+
+	#[test]
+	fn test_non_boolean_flag_rejected() {
+		// With a = 2 and b = 0, the constraint on c holds for c = 3, since 0 + (2 + 0 + 1) = 3, but
+		// the flag is not a bit.
+		let (cs, table_id, cols) = logic_system();
+		let ccs = cs.compile().unwrap();
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(&cs, &allocator);
+		fill(&mut witness, table_id, cols, &[[2, 0, 3]]);
+		let table_sizes = witness.table_sizes();
+		let witness = witness.into_multilinear_extension_index();
+		assert!(
+			binius_core::constraint_system::validate::validate_witness(
+				&ccs,
+				&[],
+				&table_sizes,
+				&witness,
+			)
+			.is_err()
+		);
+	}
+}
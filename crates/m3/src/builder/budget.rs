@@ -0,0 +1,136 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Size budgets that [`ConstraintSystem::compile`] enforces.
+//!
+//! Circuits that are generated, or that many people contribute gadgets to, tend to grow by
+//! accident. A [`SizeBudget`] set with [`ConstraintSystem::set_size_budget`] bounds the number of
+//! columns and the committed bits per row of every table, and makes [`ConstraintSystem::compile`]
+//! fail with a [`SizeBreakdown`] of the tables when a bound is exceeded, so that a regression is
+//! caught where it is introduced rather than in the proving time.
+//!
+//! The budgets are on the shape of the constraint system, which does not depend on the table
+//! sizes. The committed bits of a table are the committed bits per row times its number of rows.
+
+use std::fmt;
+
+use binius_field::TowerField;
+
+use super::{column::ColumnDef, constraint_system::ConstraintSystem, table::TableId};
+
+/// Upper bounds on the size of a constraint system, see the [module documentation](self).
+///
+/// The default has no bounds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SizeBudget {
+	/// The maximum number of bits per row of the committed columns of any table.
+	pub max_committed_bits_per_row: Option<usize>,
+	/// The maximum number of columns, committed or not, over all tables.
+	pub max_columns: Option<usize>,
+}
+
+impl SizeBudget {
+	/// Returns whether a system with the given breakdown fits in the budget.
+	pub fn is_met_by(&self, breakdown: &SizeBreakdown) -> bool {
+		self.max_columns
+			.is_none_or(|max_columns| breakdown.n_columns <= max_columns)
+			&& breakdown.tables.iter().all(|table| self.fits_table(table))
+	}
+
+	fn fits_table(&self, table: &TableSizeBreakdown) -> bool {
+		self.max_committed_bits_per_row
+			.is_none_or(|max_bits| table.committed_bits_per_row <= max_bits)
+	}
+}
+
+/// The sizes of the tables of a constraint system, as checked against a [`SizeBudget`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeBreakdown {
+	/// The budget that the sizes are checked against.
+	pub budget: SizeBudget,
+	/// The number of columns over all tables.
+	pub n_columns: usize,
+	/// The sizes of every table, in the order of the table IDs.
+	pub tables: Vec<TableSizeBreakdown>,
+}
+
+impl SizeBreakdown {
+	/// Returns the breakdown of a constraint system.
+	pub fn new<F: TowerField>(cs: &ConstraintSystem<F>, budget: SizeBudget) -> Self {
+		let tables = cs
+			.tables
+			.iter()
+			.map(|table| {
+				let mut committed_columns = table
+					.columns
+					.iter()
+					.filter(|column| matches!(column.col, ColumnDef::Committed { .. }))
+					.map(|column| (column.name.clone(), 1 << column.shape.log_cell_size()))
+					.collect::<Vec<_>>();
+				// Stable sort, so that columns of equal size stay in the order they were added.
+				committed_columns.sort_by_key(|&(_, bits)| std::cmp::Reverse(bits));
+				TableSizeBreakdown {
+					table_id: table.id(),
+					name: table.name.clone(),
+					n_columns: table.columns.len(),
+					committed_bits_per_row: committed_columns.iter().map(|(_, bits)| bits).sum(),
+					committed_columns,
+				}
+			})
+			.collect::<Vec<_>>();
+		Self {
+			budget,
+			n_columns: tables.iter().map(|table| table.n_columns).sum(),
+			tables,
+		}
+	}
+}
+
+/// The sizes of a table, as checked against a [`SizeBudget`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableSizeBreakdown {
+	pub table_id: TableId,
+	pub name: String,
+	/// The number of columns, committed or not.
+	pub n_columns: usize,
+	/// The sum of the cell sizes of the committed columns in bits.
+	pub committed_bits_per_row: usize,
+	/// The names and cell sizes in bits of the committed columns, largest first.
+	pub committed_columns: Vec<(String, usize)>,
+}
+
+impl fmt::Display for SizeBreakdown {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let SizeBudget {
+			max_committed_bits_per_row,
+			max_columns,
+		} = self.budget;
+
+		write!(f, "{} columns", self.n_columns)?;
+		if let Some(max_columns) = max_columns {
+			write!(f, " (budget {max_columns})")?;
+		}
+		writeln!(f)?;
+
+		let mut tables = self.tables.iter().collect::<Vec<_>>();
+		tables.sort_by_key(|table| std::cmp::Reverse(table.committed_bits_per_row));
+		for table in tables {
+			write!(
+				f,
+				"* table {} ({}): {} columns, {} committed bits per row",
+				table.table_id, table.name, table.n_columns, table.committed_bits_per_row
+			)?;
+			if self.budget.fits_table(table) {
+				writeln!(f)?;
+				continue;
+			}
+			if let Some(max_bits) = max_committed_bits_per_row {
+				write!(f, " (budget {max_bits})")?;
+			}
+			writeln!(f)?;
+			for (name, bits) in &table.committed_columns {
+				writeln!(f, "  - {name}: {bits} bits")?;
+			}
+		}
+		Ok(())
+	}
+}
@@ -1,12 +1,13 @@
 // Copyright 2025 Irreducible Inc.
 
-use binius_core::constraint_system::channel::{ChannelId, FlushDirection};
+use binius_core::constraint_system::channel::{Boundary, ChannelId, FlushDirection};
+use binius_field::{ExtensionField, TowerField};
 
 use super::ColumnId;
-use crate::builder::{B1, Col};
+use crate::builder::{B1, B8, B128, Col};
 
 /// A flushing rule within a table.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Flush {
 	pub columns: Vec<ColumnId>,
 	pub channel_id: ChannelId,
@@ -46,3 +47,95 @@ impl Default for FlushOpts {
 pub struct Channel {
 	pub name: String,
 }
+
+/// The number of bytes packed into every value of a [`ByteChannel`].
+pub const BYTES_PER_CHANNEL_VALUE: usize = 16;
+
+/// Packs a byte string into the values that represent it on a [`ByteChannel`].
+///
+/// The bytes are split into chunks of [`BYTES_PER_CHANNEL_VALUE`] bytes, the last of which is
+/// padded with zeros, and every chunk is packed into a `B128` in little-endian order. This is the
+/// packing of a `Col<B8, 16>` into a `Col<B128>` with [`TableBuilder::add_packed`], so the values
+/// agree with the ones flushed by [`TableBuilder::push_bytes`] and [`TableBuilder::pull_bytes`].
+///
+/// [`TableBuilder::add_packed`]: super::TableBuilder::add_packed
+/// [`TableBuilder::push_bytes`]: super::TableBuilder::push_bytes
+/// [`TableBuilder::pull_bytes`]: super::TableBuilder::pull_bytes
+pub fn pack_bytes(bytes: &[u8]) -> Vec<B128> {
+	bytes
+		.chunks(BYTES_PER_CHANNEL_VALUE)
+		.map(|chunk| {
+			let mut buf = [0u8; BYTES_PER_CHANNEL_VALUE];
+			buf[..chunk.len()].copy_from_slice(chunk);
+			B128::new(u128::from_le_bytes(buf))
+		})
+		.collect()
+}
+
+/// A channel over byte strings of `N` bytes.
+///
+/// Channels carry tuples of field elements, and a byte string is flushed as the values returned by
+/// [`pack_bytes`]. The table side and the boundary side of the channel both go through the same
+/// packing, so that they can not disagree on the encoding of the bytes.
+///
+/// A byte channel is added with [`ConstraintSystem::add_byte_channel`], and flushed to with
+/// [`TableBuilder::push_bytes`] and [`TableBuilder::pull_bytes`].
+///
+/// [`ConstraintSystem::add_byte_channel`]: super::ConstraintSystem::add_byte_channel
+/// [`TableBuilder::push_bytes`]: super::TableBuilder::push_bytes
+/// [`TableBuilder::pull_bytes`]: super::TableBuilder::pull_bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteChannel<const N: usize> {
+	id: ChannelId,
+}
+
+impl<const N: usize> ByteChannel<N> {
+	/// The number of field elements that a byte string is packed into.
+	pub const N_VALUES: usize = N.div_ceil(BYTES_PER_CHANNEL_VALUE);
+
+	pub(super) fn new(id: ChannelId) -> Self {
+		Self { id }
+	}
+
+	/// Returns the id of the underlying channel.
+	pub fn id(&self) -> ChannelId {
+		self.id
+	}
+
+	/// Returns the boundary that flushes a byte string to the channel.
+	pub fn boundary<F>(
+		&self,
+		bytes: &[u8; N],
+		direction: FlushDirection,
+		multiplicity: u64,
+	) -> Boundary<F>
+	where
+		F: TowerField + ExtensionField<B128>,
+	{
+		Boundary {
+			values: pack_bytes(bytes).into_iter().map(F::from).collect(),
+			channel_id: self.id,
+			direction,
+			multiplicity,
+		}
+	}
+}
+
+/// A byte string column of a table, with the derived columns that pack it into the values of a
+/// [`ByteChannel`].
+///
+/// This is added with [`TableBuilder::add_packed_bytes`], and the derived columns are filled with
+/// [`TableWitnessSegment::fill_packed_bytes`] once the byte column is populated.
+///
+/// [`TableBuilder::add_packed_bytes`]: super::TableBuilder::add_packed_bytes
+/// [`TableWitnessSegment::fill_packed_bytes`]: super::TableWitnessSegment::fill_packed_bytes
+#[derive(Debug, Clone)]
+pub struct PackedBytes<const N: usize> {
+	/// The byte string column.
+	pub bytes: Col<B8, N>,
+	/// The chunks of [`BYTES_PER_CHANNEL_VALUE`] bytes, selected from or zero-padding the byte
+	/// column. This is empty if the byte column is a single chunk already.
+	pub(super) chunks: Vec<Col<B8, BYTES_PER_CHANNEL_VALUE>>,
+	/// The chunks packed into the channel values.
+	pub packed: Vec<Col<B128>>,
+}
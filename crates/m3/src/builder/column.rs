@@ -92,6 +92,9 @@ pub struct ColumnInfo<F: TowerField = B128> {
 	pub shape: ColumnShape,
 	/// Whether the column is constrained to be non-zero.
 	pub is_nonzero: bool,
+	/// Whether the column is constrained to hold only 0 and 1, see
+	/// [`TableBuilder::assert_boolean`](super::TableBuilder::assert_boolean).
+	pub is_boolean: bool,
 }
 
 /// The shape of each cell in a column.
@@ -121,7 +124,7 @@ pub struct ColumnId {
 }
 
 /// A definition of a column in a table.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ColumnDef<F: TowerField = B128> {
 	Committed {
 		tower_level: usize,
@@ -176,3 +179,33 @@ pub enum ColumnDef<F: TowerField = B128> {
 		base_tower_level: usize,
 	},
 }
+
+impl<F: TowerField> ColumnDef<F> {
+	/// Returns the definition with its references to other columns moved to another table, at the
+	/// same indices within the table.
+	pub(crate) fn with_table_id(&self, table_id: TableId) -> Self {
+		let mut def = self.clone();
+		match &mut def {
+			Self::Selected { col, .. }
+			| Self::Projected { col, .. }
+			| Self::ZeroPadded { col, .. }
+			| Self::Shifted { col, .. }
+			| Self::Packed { col, .. } => col.table_id = table_id,
+			Self::Computed { cols, .. } | Self::StaticExp { bit_cols: cols, .. } => {
+				for col in cols {
+					col.table_id = table_id;
+				}
+			}
+			Self::DynamicExp { bit_cols, base, .. } => {
+				for col in bit_cols.iter_mut().chain([base]) {
+					col.table_id = table_id;
+				}
+			}
+			Self::Committed { .. }
+			| Self::Constant { .. }
+			| Self::StructuredDynSize(_)
+			| Self::StructuredFixedSize { .. } => {}
+		}
+		def
+	}
+}
@@ -0,0 +1,277 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Typed two-dimensional views of witness columns.
+//!
+//! [`TableWitnessSegment::get_as`] and [`TableWitnessSegment::get_mut_as`] return the cells of a
+//! column as a flat slice, so a filler of a column with several values per row, such as a
+//! `Col<B8, 8>`, has to compute the index of every value within the slice by hand. The views
+//! returned by [`TableWitnessSegment::get_rows_as`] and [`TableWitnessSegment::get_rows_mut_as`]
+//! instead split the slice into rows, one per table row, that hold the values of the cell.
+//!
+//! [`TableWitnessSegment::get_as`]: super::TableWitnessSegment::get_as
+//! [`TableWitnessSegment::get_mut_as`]: super::TableWitnessSegment::get_mut_as
+//! [`TableWitnessSegment::get_rows_as`]: super::TableWitnessSegment::get_rows_as
+//! [`TableWitnessSegment::get_rows_mut_as`]: super::TableWitnessSegment::get_rows_mut_as
+
+use std::{
+	cell::{Ref, RefMut},
+	ops::{Index, IndexMut},
+	slice::{ChunksExact, ChunksExactMut},
+};
+
+use super::column::ColumnShape;
+
+/// A read-only view of a witness column as rows of `T` values.
+#[derive(Debug)]
+pub struct ColumnRows<'a, T> {
+	data: Ref<'a, [T]>,
+	row_len: usize,
+}
+
+impl<'a, T> ColumnRows<'a, T> {
+	pub(super) fn new(data: Ref<'a, [T]>, shape: ColumnShape) -> Self {
+		let row_len = row_len::<T>(shape);
+		Self { data, row_len }
+	}
+
+	/// Returns the number of rows.
+	pub fn n_rows(&self) -> usize {
+		self.data.len() / self.row_len
+	}
+
+	/// Returns the number of values in a row.
+	pub fn row_len(&self) -> usize {
+		self.row_len
+	}
+
+	/// Returns a row, or `None` if it is out of range.
+	pub fn row(&self, row: usize) -> Option<&[T]> {
+		self.data.get(row * self.row_len..(row + 1) * self.row_len)
+	}
+
+	/// Returns a value of a row, or `None` if the row or the value is out of range.
+	pub fn get(&self, row: usize, index: usize) -> Option<&T> {
+		self.row(row)?.get(index)
+	}
+
+	/// Returns an iterator over the rows.
+	pub fn iter(&self) -> ChunksExact<'_, T> {
+		self.data.chunks_exact(self.row_len)
+	}
+
+	/// Returns the values of all rows as a flat slice.
+	pub fn as_flat(&self) -> &[T] {
+		&self.data
+	}
+}
+
+impl<T> Index<usize> for ColumnRows<'_, T> {
+	type Output = [T];
+
+	fn index(&self, row: usize) -> &[T] {
+		let n_rows = self.n_rows();
+		self.row(row)
+			.unwrap_or_else(|| panic!("row {row} is out of range for {n_rows} rows"))
+	}
+}
+
+impl<T> Index<(usize, usize)> for ColumnRows<'_, T> {
+	type Output = T;
+
+	fn index(&self, (row, index): (usize, usize)) -> &T {
+		let row_len = self.row_len;
+		self[row]
+			.get(index)
+			.unwrap_or_else(|| panic!("index {index} is out of range for rows of {row_len} values"))
+	}
+}
+
+/// A mutable view of a witness column as rows of `T` values.
+#[derive(Debug)]
+pub struct ColumnRowsMut<'a, T> {
+	data: RefMut<'a, [T]>,
+	row_len: usize,
+}
+
+impl<'a, T> ColumnRowsMut<'a, T> {
+	pub(super) fn new(data: RefMut<'a, [T]>, shape: ColumnShape) -> Self {
+		let row_len = row_len::<T>(shape);
+		Self { data, row_len }
+	}
+
+	/// Returns the number of rows.
+	pub fn n_rows(&self) -> usize {
+		self.data.len() / self.row_len
+	}
+
+	/// Returns the number of values in a row.
+	pub fn row_len(&self) -> usize {
+		self.row_len
+	}
+
+	/// Returns a row, or `None` if it is out of range.
+	pub fn row(&self, row: usize) -> Option<&[T]> {
+		self.data.get(row * self.row_len..(row + 1) * self.row_len)
+	}
+
+	/// Returns a mutable row, or `None` if it is out of range.
+	pub fn row_mut(&mut self, row: usize) -> Option<&mut [T]> {
+		self.data
+			.get_mut(row * self.row_len..(row + 1) * self.row_len)
+	}
+
+	/// Returns a value of a row, or `None` if the row or the value is out of range.
+	pub fn get(&self, row: usize, index: usize) -> Option<&T> {
+		self.row(row)?.get(index)
+	}
+
+	/// Returns a mutable value of a row, or `None` if the row or the value is out of range.
+	pub fn get_mut(&mut self, row: usize, index: usize) -> Option<&mut T> {
+		self.row_mut(row)?.get_mut(index)
+	}
+
+	/// Returns an iterator over the rows.
+	pub fn iter(&self) -> ChunksExact<'_, T> {
+		self.data.chunks_exact(self.row_len)
+	}
+
+	/// Returns an iterator over the mutable rows.
+	pub fn iter_mut(&mut self) -> ChunksExactMut<'_, T> {
+		self.data.chunks_exact_mut(self.row_len)
+	}
+
+	/// Returns the values of all rows as a flat slice.
+	pub fn as_flat(&self) -> &[T] {
+		&self.data
+	}
+
+	/// Returns the values of all rows as a flat mutable slice.
+	pub fn as_flat_mut(&mut self) -> &mut [T] {
+		&mut self.data
+	}
+
+	/// Copies `values` into a row.
+	///
+	/// ## Panics
+	///
+	/// * if `row` is out of range
+	/// * if the length of `values` differs from [`Self::row_len`]
+	pub fn set_row(&mut self, row: usize, values: &[T])
+	where
+		T: Copy,
+	{
+		self[row].copy_from_slice(values);
+	}
+}
+
+impl<T> Index<usize> for ColumnRowsMut<'_, T> {
+	type Output = [T];
+
+	fn index(&self, row: usize) -> &[T] {
+		let n_rows = self.n_rows();
+		self.row(row)
+			.unwrap_or_else(|| panic!("row {row} is out of range for {n_rows} rows"))
+	}
+}
+
+impl<T> IndexMut<usize> for ColumnRowsMut<'_, T> {
+	fn index_mut(&mut self, row: usize) -> &mut [T] {
+		let n_rows = self.n_rows();
+		self.row_mut(row)
+			.unwrap_or_else(|| panic!("row {row} is out of range for {n_rows} rows"))
+	}
+}
+
+impl<T> Index<(usize, usize)> for ColumnRowsMut<'_, T> {
+	type Output = T;
+
+	fn index(&self, (row, index): (usize, usize)) -> &T {
+		let row_len = self.row_len;
+		self[row]
+			.get(index)
+			.unwrap_or_else(|| panic!("index {index} is out of range for rows of {row_len} values"))
+	}
+}
+
+impl<T> IndexMut<(usize, usize)> for ColumnRowsMut<'_, T> {
+	fn index_mut(&mut self, (row, index): (usize, usize)) -> &mut T {
+		let row_len = self.row_len;
+		self[row]
+			.get_mut(index)
+			.unwrap_or_else(|| panic!("index {index} is out of range for rows of {row_len} values"))
+	}
+}
+
+/// Returns the number of `T` values in a cell of a column with the given shape.
+///
+/// ## Panics
+///
+/// * if a cell is not a whole, non-zero number of `T` values
+fn row_len<T>(shape: ColumnShape) -> usize {
+	let cell_bits = 1 << shape.log_cell_size();
+	let value_bits = 8 * size_of::<T>();
+	assert!(
+		value_bits != 0 && cell_bits >= value_bits && cell_bits % value_bits == 0,
+		"a cell of {cell_bits} bits is not a whole number of {value_bits}-bit values"
+	);
+	cell_bits / value_bits
+}
+
+#[cfg(test)]
+mod tests {
+	use std::cell::RefCell;
+
+	use super::*;
+
+	fn shape(tower_height: usize, log_values_per_row: usize) -> ColumnShape {
+		ColumnShape {
+			tower_height,
+			log_values_per_row,
+		}
+	}
+
+	#[test]
+	fn test_rows_of_byte_array_column() {
+		let data = RefCell::new((0..32u8).collect::<Vec<_>>());
+		{
+			let mut rows =
+				ColumnRowsMut::new(RefMut::map(data.borrow_mut(), Vec::as_mut_slice), shape(3, 3));
+			assert_eq!(rows.n_rows(), 4);
+			assert_eq!(rows.row_len(), 8);
+			assert_eq!(rows[(2, 1)], 17);
+			rows.set_row(3, &[0; 8]);
+			rows[(0, 7)] = 100;
+			assert!(rows.row_mut(4).is_none());
+			assert!(rows.get_mut(0, 8).is_none());
+		}
+
+		let rows = ColumnRows::new(Ref::map(data.borrow(), Vec::as_slice), shape(3, 3));
+		assert_eq!(rows[0], [0, 1, 2, 3, 4, 5, 6, 100]);
+		assert_eq!(rows.iter().nth(3), Some([0; 8].as_slice()));
+		assert_eq!(rows.get(1, 0), Some(&8));
+		assert!(rows.row(4).is_none());
+	}
+
+	#[test]
+	fn test_rows_of_wide_values() {
+		// A `Col<B8, 8>` viewed as u64 values has one value per row.
+		let data = RefCell::new(vec![0u64; 4]);
+		let rows = ColumnRows::new(Ref::map(data.borrow(), Vec::as_slice), shape(3, 3));
+		assert_eq!((rows.n_rows(), rows.row_len()), (4, 1));
+	}
+
+	#[test]
+	#[should_panic(expected = "row 4 is out of range for 4 rows")]
+	fn test_row_out_of_range() {
+		let data = RefCell::new(vec![0u8; 32]);
+		let rows = ColumnRows::new(Ref::map(data.borrow(), Vec::as_slice), shape(3, 3));
+		let _ = &rows[4];
+	}
+
+	#[test]
+	#[should_panic(expected = "is not a whole number")]
+	fn test_cells_smaller_than_values() {
+		let data = RefCell::new(vec![0u32; 4]);
+		let _ = ColumnRows::new(Ref::map(data.borrow(), Vec::as_slice), shape(3, 1));
+	}
+}
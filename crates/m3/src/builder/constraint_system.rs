@@ -1,6 +1,6 @@
 // Copyright 2025 Irreducible Inc.
 
-use std::{cell, collections::BTreeMap, ops::Index};
+use std::{cell, collections::BTreeMap, iter, ops::Index};
 
 use binius_compute::alloc::HostBumpAllocator;
 pub use binius_core::constraint_system::channel::{
@@ -8,7 +8,8 @@ pub use binius_core::constraint_system::channel::{
 };
 use binius_core::{
 	constraint_system::{
-		ConstraintSystem as CompiledConstraintSystem,
+		CommitRate, ConstraintSystem as CompiledConstraintSystem, MAX_CONSTRAINT_DEGREE,
+		TableSizeSpec,
 		channel::{ChannelId, OracleOrConst},
 		exp::Exp,
 	},
@@ -16,26 +17,70 @@ use binius_core::{
 		Constraint, ConstraintPredicate, ConstraintSet, OracleId, SymbolicMultilinearOracleSet,
 	},
 };
-use binius_field::{PackedField, TowerField};
+use binius_field::{ExtensionField, PackedField, TowerField};
 use binius_math::{ArithCircuit, LinearNormalForm};
 use binius_utils::checked_arithmetics::log2_strict_usize;
 
 use super::{
-	ColumnId, Table, TableBuilder, TableId, ZeroConstraint,
-	channel::{Channel, Flush},
+	Col, ColumnId, Table, TableBuilder, TableId, ZeroConstraint,
+	budget::{SizeBreakdown, SizeBudget},
+	channel::{ByteChannel, Channel, Flush},
 	column::{ColumnDef, ColumnInfo},
 	error::Error,
+	repack::{self, RepackReport},
 	table::TablePartition,
-	types::B128,
+	types::{B32, B128},
 	witness::WitnessIndex,
 };
 use crate::builder::expr::ArithExprNamedVars;
 
+/// A batch of columns that are committed separately from the other columns.
+///
+/// By default, all committed columns are committed in a single batch, which is opened once for
+/// all evaluation claims on them. Separate batches cost a commitment and an opening proof each,
+/// but an opening only involves the columns of its batch, for example to keep large preprocessed
+/// columns, that are reused across statements, apart from the trace columns.
+///
+/// Every batch is committed at its own Reed–Solomon code rate, so that tables of very different
+/// sizes can be committed at the rates that suit them.
+#[derive(Debug, Clone)]
+pub struct CommitBatch {
+	pub name: String,
+	/// Tables whose committed columns are all in the batch, along with the columns of their
+	/// copies.
+	pub tables: Vec<TableId>,
+	/// Committed columns in the batch.
+	pub columns: Vec<ColumnId>,
+	/// The code rate of the batch.
+	pub rate: CommitRate,
+	/// The binary logarithm of the number of codeword values in each Merkle leaf of the FRI
+	/// oracles of the batch, or `None` to hash one folding coset per leaf.
+	pub log_leaf_size: Option<usize>,
+}
+
+/// The committed oracles of the compiled commit batches, with their code rates and FRI leaf
+/// sizes.
+type CompiledCommitBatches = (Vec<Vec<OracleId>>, Vec<CommitRate>, Vec<Option<usize>>);
+
 /// An M3 constraint system, independent of the table sizes.
 #[derive(Debug, Default)]
 pub struct ConstraintSystem<F: TowerField = B128> {
 	pub tables: Vec<Table<F>>,
 	pub channels: Vec<Channel>,
+	pub commit_batches: Vec<CommitBatch>,
+
+	// The highest constraint degree accepted by `ConstraintSystem::compile`, if it is lower than
+	// the supported maximum.
+	max_degree: Option<usize>,
+
+	// The code rate of the default commit batch.
+	default_commit_rate: CommitRate,
+
+	// The FRI leaf size of the default commit batch.
+	default_commit_log_leaf_size: Option<usize>,
+
+	// The size budget enforced by `ConstraintSystem::compile`.
+	size_budget: SizeBudget,
 
 	// This is assigned as part of `ConstraintSystem::compile`.
 	oracle_lookup: cell::RefCell<Option<OracleLookup>>,
@@ -141,7 +186,10 @@ impl<F: TowerField> ConstraintSystem<F> {
 	pub fn add_table(&mut self, name: impl ToString) -> TableBuilder<'_, F> {
 		let id = self.tables.len();
 		self.tables.push(Table::new(id, name.to_string()));
-		TableBuilder::new(self.tables.last_mut().expect("table was just pushed"))
+		TableBuilder::with_channels(
+			self.tables.last_mut().expect("table was just pushed"),
+			&mut self.channels,
+		)
 	}
 
 	pub fn add_channel(&mut self, name: impl ToString) -> ChannelId {
@@ -152,6 +200,243 @@ impl<F: TowerField> ConstraintSystem<F> {
 		id
 	}
 
+	/// Adds a channel over byte strings of `N` bytes, see [`ByteChannel`].
+	///
+	/// ## Throws
+	///
+	/// * [`Error::ByteChannelLengthNotPowerOfTwo`] if `N` is not a power of two, as the byte
+	///   strings are held in table columns of `N` values per row.
+	pub fn add_byte_channel<const N: usize>(
+		&mut self,
+		name: impl ToString,
+	) -> Result<ByteChannel<N>, Error> {
+		if !N.is_power_of_two() {
+			return Err(Error::ByteChannelLengthNotPowerOfTwo {
+				name: name.to_string(),
+				n_bytes: N,
+			});
+		}
+		Ok(ByteChannel::new(self.add_channel(name)))
+	}
+
+	/// Adds a batch of tables and columns that are committed separately from the other columns,
+	/// overriding the default of committing all columns in a single batch. See [`CommitBatch`].
+	///
+	/// All committed columns of the tables and their copies are in the batch, and the columns
+	/// must be committed. A column can be in at most one batch, which is checked by
+	/// [`Self::compile`]. Returns the index of the batch.
+	pub fn add_commit_batch(
+		&mut self,
+		name: impl ToString,
+		tables: impl IntoIterator<Item = TableId>,
+		columns: impl IntoIterator<Item = ColumnId>,
+	) -> usize {
+		let index = self.commit_batches.len();
+		self.commit_batches.push(CommitBatch {
+			name: name.to_string(),
+			tables: tables.into_iter().collect(),
+			columns: columns.into_iter().collect(),
+			rate: CommitRate::Default,
+			log_leaf_size: None,
+		});
+		index
+	}
+
+	/// Sets the code rate of the commit batch at `index`, as returned by
+	/// [`Self::add_commit_batch`].
+	pub fn set_commit_batch_rate(&mut self, index: usize, rate: CommitRate) {
+		self.commit_batches[index].rate = rate;
+	}
+
+	/// Sets the code rate of the default commit batch, which holds the committed columns that are
+	/// not in any of the added batches.
+	///
+	/// By default, all batches are committed at [`CommitRate::Default`], the rate that the proof is
+	/// produced and verified with.
+	pub fn set_default_commit_rate(&mut self, rate: CommitRate) {
+		self.default_commit_rate = rate;
+	}
+
+	/// Sets the binary logarithm of the number of codeword values in each Merkle leaf of the FRI
+	/// oracles of the commit batch at `index`, as returned by [`Self::add_commit_batch`].
+	///
+	/// See [`FRIParams::with_log_leaf_size`] for the trade-off. The leaf size is checked against
+	/// the FRI parameters of the batch when the proof is produced or verified.
+	///
+	/// [`FRIParams::with_log_leaf_size`]: binius_core::protocols::fri::FRIParams::with_log_leaf_size
+	pub fn set_commit_batch_log_leaf_size(&mut self, index: usize, log_leaf_size: usize) {
+		self.commit_batches[index].log_leaf_size = Some(log_leaf_size);
+	}
+
+	/// Sets the binary logarithm of the FRI leaf size of the default commit batch, see
+	/// [`Self::set_commit_batch_log_leaf_size`].
+	pub fn set_default_commit_log_leaf_size(&mut self, log_leaf_size: usize) {
+		self.default_commit_log_leaf_size = Some(log_leaf_size);
+	}
+
+	/// Sets the highest degree of a zero constraint that [`Self::compile`] accepts.
+	///
+	/// By default, constraints are accepted up to [`MAX_CONSTRAINT_DEGREE`], the highest degree
+	/// that the sumcheck protocols support, and a higher limit is capped to it. A lower limit
+	/// bounds the cost of the zerocheck, whose round polynomials have the degree of the
+	/// constraints.
+	pub fn set_max_degree(&mut self, max_degree: usize) {
+		self.max_degree = Some(max_degree);
+	}
+
+	/// Returns the highest degree of a zero constraint that [`Self::compile`] accepts.
+	pub fn max_degree(&self) -> usize {
+		self.max_degree
+			.map_or(MAX_CONSTRAINT_DEGREE, |max_degree| max_degree.min(MAX_CONSTRAINT_DEGREE))
+	}
+
+	/// Sets the [`SizeBudget`] that [`Self::compile`] enforces.
+	///
+	/// By default, there is no budget.
+	pub fn set_size_budget(&mut self, budget: SizeBudget) {
+		self.size_budget = budget;
+	}
+
+	/// Returns the [`SizeBudget`] that [`Self::compile`] enforces.
+	pub fn size_budget(&self) -> SizeBudget {
+		self.size_budget
+	}
+
+	/// Returns the boundaries that bind a public column to the public input values, see
+	/// [`TableBuilder::add_public`].
+	///
+	/// Every value is pulled along with its index from the channel of the column, so the
+	/// boundaries only balance if the table has exactly as many rows as there are values, and the
+	/// column holds the values in order.
+	pub fn public_boundaries<FSub>(
+		&self,
+		col: Col<FSub>,
+		values: impl IntoIterator<Item = FSub>,
+	) -> Result<Vec<Boundary<F>>, Error>
+	where
+		FSub: TowerField,
+		F: ExtensionField<FSub> + ExtensionField<B32>,
+	{
+		let table = self.tables.get(col.table_id).ok_or(Error::MissingTable {
+			table_id: col.table_id,
+		})?;
+		let public_column = table
+			.public_columns()
+			.iter()
+			.find(|public_column| public_column.column == col.id())
+			.ok_or(Error::NotPublicColumn(col.id()))?;
+		let boundaries = values
+			.into_iter()
+			.enumerate()
+			.map(|(i, value)| Boundary {
+				values: vec![F::from(B32::new(i as u32)), F::from(value)],
+				channel_id: public_column.channel_id,
+				direction: FlushDirection::Pull,
+				multiplicity: 1,
+			})
+			.collect();
+		Ok(boundaries)
+	}
+
+	/// Adds `n_copies` copies of a table, among which the rows beyond the first `2^max_log_size`
+	/// are split into chunks of at most `2^max_log_size` rows each, for tables that may have more
+	/// rows than can be committed at once.
+	///
+	/// The copies have the same columns, constraints and flushes as the table, so the table and
+	/// its copies flush the same values to channels as a single table with all the rows would.
+	/// [`WitnessIndex::fill_table_sequential`] and [`WitnessIndex::fill_table_parallel`] split the
+	/// events of the table among the table and its copies, and copies that get no events are left
+	/// empty. The columns of the table access the witness of the copies as well, so table fillers
+	/// work unchanged.
+	///
+	/// The number of copies is fixed here rather than derived from the number of events, as the
+	/// tables of the constraint system must be the same for the prover and the verifier. The table
+	/// holds at most `(n_copies + 1) * 2^max_log_size` rows, and filling it with more events fails
+	/// with [`Error::TableCapacityExceeded`]. Returns the IDs of the copies.
+	///
+	/// ## Preconditions
+	///
+	/// * The table must be fully defined, as later changes to it are not applied to the copies.
+	/// * The table must not have a size requirement, and must not have copies or be a copy already.
+	pub fn add_table_copies(
+		&mut self,
+		table_id: TableId,
+		max_log_size: usize,
+		n_copies: usize,
+	) -> Vec<TableId> {
+		let table = &self.tables[table_id];
+		assert!(
+			matches!(table.size_spec(), TableSizeSpec::Arbitrary),
+			"a table with a size requirement cannot have copies"
+		);
+		assert!(
+			table.max_log_size().is_none() && table.copy_of().is_none(),
+			"the table already has copies or is a copy"
+		);
+
+		let first_copy_id = self.tables.len();
+		let copies = (0..n_copies)
+			.map(|i| table.copy(first_copy_id + i, format!("{}[{}]", table.name, i + 1)))
+			.collect::<Vec<_>>();
+		self.tables.extend(copies);
+
+		let copy_ids = (first_copy_id..self.tables.len()).collect::<Vec<_>>();
+		self.tables[table_id].set_copies(max_log_size, copy_ids.clone());
+		copy_ids
+	}
+
+	/// Finds the committed columns that are declared at a wider field than their values can take,
+	/// without changing the constraint system.
+	///
+	/// See [`Self::repack_columns`].
+	pub fn find_repacked_columns(&self, boundary_channels: &[ChannelId]) -> RepackReport {
+		repack::find_repacked_columns(self, boundary_channels)
+	}
+
+	/// Repacks the committed columns that are declared at a wider field than their values can
+	/// take to the smallest tower level, and returns a report of the repacked columns and the
+	/// savings in commitment data. See the [`repack`] module for the analysis.
+	///
+	/// `boundary_channels` are the channels that the boundaries of the statements push values
+	/// into. Columns pulled from them are not repacked, so the repacked system does not depend on
+	/// the statement.
+	///
+	/// The typed [`Col`] handles of the repacked columns keep their declared field and are
+	/// rejected by the witness accessors. Use [`Self::repacked_col`] to re-type them.
+	pub fn repack_columns(&mut self, boundary_channels: &[ChannelId]) -> RepackReport {
+		let report = repack::find_repacked_columns(self, boundary_channels);
+		for column in &report.columns {
+			let info = &mut self.tables[column.table_id()].columns[column.column_id.table_index.0];
+			info.col = ColumnDef::Committed {
+				tower_level: column.to_tower_level,
+			};
+			info.shape.tower_height = column.to_tower_level;
+		}
+		report
+	}
+
+	/// Returns the handle of a column re-typed at the field of the column, for the columns
+	/// repacked by [`Self::repack_columns`].
+	///
+	/// ## Panics
+	///
+	/// * if `FNew` is not the field of the column
+	pub fn repacked_col<FNew: TowerField, FOld: TowerField, const V: usize>(
+		&self,
+		col: Col<FOld, V>,
+	) -> Col<FNew, V> {
+		let tower_level = self.tables[col.table_id][col.table_index]
+			.shape
+			.tower_height;
+		assert_eq!(
+			tower_level,
+			FNew::TOWER_LEVEL,
+			"the column is at tower level {tower_level}, not {}",
+			FNew::TOWER_LEVEL
+		);
+		Col::new(col.id(), col.partition_index)
+	}
+
 	/// Creates and allocates the witness index.
 	///
 	/// **Deprecated**: This is a thin wrapper over [`WitnessIndex::new`] now, which is preferred.
@@ -180,6 +465,11 @@ impl<F: TowerField> ConstraintSystem<F> {
 	/// definitions is that multilinear oracle definitions have a number of variables, whereas the
 	/// column definitions contained in a [`ConstraintSystem`] do not have size information.
 	pub fn compile(&self) -> Result<CompiledConstraintSystem<F>, Error> {
+		let breakdown = SizeBreakdown::new(self, self.size_budget);
+		if !self.size_budget.is_met_by(&breakdown) {
+			return Err(Error::SizeBudgetExceeded(Box::new(breakdown)));
+		}
+
 		let mut oracles = SymbolicMultilinearOracleSet::new();
 		let mut table_constraints = Vec::new();
 		let mut compiled_flushes = Vec::new();
@@ -281,6 +571,18 @@ impl<F: TowerField> ConstraintSystem<F> {
 					});
 				}
 
+				for constraint in zero_constraints {
+					let degree = constraint.expr.degree();
+					if degree > self.max_degree() {
+						return Err(Error::ConstraintDegreeTooLarge {
+							table_id: table.id(),
+							name: constraint.name.clone(),
+							degree,
+							max: self.max_degree(),
+						});
+					}
+				}
+
 				if !zero_constraints.is_empty() {
 					let constraint_set = translate_constraint_set(
 						table.id(),
@@ -293,6 +595,9 @@ impl<F: TowerField> ConstraintSystem<F> {
 			}
 		}
 
+		let (commit_batches, commit_rates, commit_log_leaf_sizes) =
+			self.compile_commit_batches(&oracle_lookup)?;
+
 		*self.oracle_lookup.borrow_mut() = Some(oracle_lookup);
 
 		Ok(CompiledConstraintSystem {
@@ -303,8 +608,66 @@ impl<F: TowerField> ConstraintSystem<F> {
 			channel_count: self.channels.len(),
 			exponents,
 			table_size_specs,
+			commit_batches,
+			commit_rates,
+			commit_log_leaf_sizes,
 		})
 	}
+
+	/// Translates the commit batches to batches of committed oracles, leaving out the batches
+	/// without committed columns, and returns them with the code rates and FRI leaf sizes of the
+	/// compiled batches, the default batch first.
+	fn compile_commit_batches(
+		&self,
+		oracle_lookup: &OracleLookup,
+	) -> Result<CompiledCommitBatches, Error> {
+		let mut batch_of = BTreeMap::new();
+		let mut compiled_batches = Vec::new();
+		let mut rates = vec![self.default_commit_rate];
+		let mut log_leaf_sizes = vec![self.default_commit_log_leaf_size];
+		for (batch_index, batch) in self.commit_batches.iter().enumerate() {
+			let table_columns = batch
+				.tables
+				.iter()
+				.flat_map(|&table_id| {
+					let table = &self.tables[table_id];
+					iter::once(table_id).chain(table.copies().iter().copied())
+				})
+				.flat_map(|table_id| &self.tables[table_id].columns)
+				.filter(|column| matches!(column.col, ColumnDef::Committed { .. }))
+				.map(|column| column.id);
+
+			let mut oracle_ids = Vec::new();
+			for column_id in table_columns.chain(batch.columns.iter().copied()) {
+				let table = self
+					.tables
+					.get(column_id.table_id)
+					.ok_or(Error::MissingTable {
+						table_id: column_id.table_id,
+					})?;
+				let column = table
+					.columns
+					.get(column_id.table_index.0)
+					.ok_or(Error::MissingColumn(column_id))?;
+				if !matches!(column.col, ColumnDef::Committed { .. }) {
+					return Err(Error::BatchedColumnNotCommitted(column_id));
+				}
+				match batch_of.insert(column_id, batch_index) {
+					// A column may be listed twice in the same batch, like a column of a table in
+					// the batch.
+					Some(other_batch) if other_batch == batch_index => continue,
+					Some(_) => return Err(Error::ColumnInSeveralCommitBatches(column_id)),
+					None => oracle_ids.push(oracle_lookup[column_id]),
+				}
+			}
+			if !oracle_ids.is_empty() {
+				compiled_batches.push(oracle_ids);
+				rates.push(batch.rate);
+				log_leaf_sizes.push(batch.log_leaf_size);
+			}
+		}
+		Ok((compiled_batches, rates, log_leaf_sizes))
+	}
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -646,3 +1009,404 @@ fn translate_constraint_set<F: TowerField>(
 		constraints: compiled_constraints,
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::array;
+
+	use assert_matches::assert_matches;
+	use binius_compute::cpu::alloc::CpuComputeAllocator;
+	use binius_field::{Field, arch::OptimalUnderlier128b};
+
+	use super::*;
+	use crate::builder::{
+		Expr,
+		test_utils::{ClosureFiller, validate_system_witness_with_prove_verify},
+		trace::ChannelTrace,
+		types::B8,
+		witness::TableWitnessSegment,
+	};
+
+	#[test]
+	fn test_commit_log_leaf_size() {
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("words");
+		let table_id = table.id();
+		let words = table.add_committed::<B32, 64>("words");
+		let squares = table.add_committed::<B32, 64>("squares");
+		table.assert_zero("square", words * words - squares);
+
+		// Wider leaves make the FRI oracle trees shallower.
+		cs.set_default_commit_log_leaf_size(6);
+		let compiled = cs.compile().unwrap();
+		assert_eq!(compiled.commit_log_leaf_sizes, [Some(6)]);
+
+		let mut allocator = CpuComputeAllocator::new(1 << 16);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(&cs, &allocator);
+		let rows = (0..256u32).collect::<Vec<_>>();
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(table_id, |rows: &[u32], segment| {
+					let mut words_col = segment.get_scalars_mut(words)?;
+					let mut squares_col = segment.get_scalars_mut(squares)?;
+					for (i, &row) in rows.iter().enumerate() {
+						for j in 0..64 {
+							let x = B32::new(row << 6 | j);
+							words_col[64 * i + j as usize] = x;
+							squares_col[64 * i + j as usize] = x * x;
+						}
+					}
+					Ok(())
+				}),
+				&rows,
+			)
+			.unwrap();
+
+		validate_system_witness_with_prove_verify::<OptimalUnderlier128b>(
+			&cs,
+			witness,
+			vec![],
+			true,
+		);
+	}
+
+	#[test]
+	fn test_commit_batches() {
+		let mut cs = ConstraintSystem::new();
+		let chan = cs.add_channel("values");
+
+		let mut source = cs.add_table("source");
+		let source_id = source.id();
+		let value = source.add_committed::<B32, 1>("value");
+		let square = source.add_committed::<B32, 1>("square");
+		let sum = source.add_computed("sum", value + square);
+		source.assert_zero("square", value * value - square);
+		source.push(chan, [value]);
+		source.push(chan, [sum]);
+
+		let mut sink = cs.add_table("sink");
+		let sink_id = sink.id();
+		let pulled = sink.add_committed::<B32, 1>("pulled");
+		sink.pull(chan, [pulled]);
+
+		let sink_batch = cs.add_commit_batch("sink", [sink_id], []);
+		let squares_batch = cs.add_commit_batch("squares", [], [square.id()]);
+		cs.add_commit_batch("empty", [], []);
+
+		// The batches are committed at different code rates within the proof.
+		let planned = CommitRate::Planned {
+			max_log_inv_rate: 3,
+			proof_weight: 64,
+		};
+		cs.set_default_commit_rate(CommitRate::Fixed { log_inv_rate: 2 });
+		cs.set_commit_batch_rate(sink_batch, planned);
+		cs.set_commit_batch_rate(squares_batch, CommitRate::Fixed { log_inv_rate: 3 });
+		let compiled = cs.compile().unwrap();
+		assert_eq!(compiled.commit_batches.len(), 2);
+		assert_eq!(
+			compiled.commit_rates,
+			[
+				CommitRate::Fixed { log_inv_rate: 2 },
+				planned,
+				CommitRate::Fixed { log_inv_rate: 3 }
+			]
+		);
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(&cs, &allocator);
+		let values = (1..=13).map(B32::new).collect::<Vec<_>>();
+		let fill_source = |rows: &[B32], segment: &mut TableWitnessSegment| {
+			let mut value_col = segment.get_scalars_mut(value)?;
+			let mut square_col = segment.get_scalars_mut(square)?;
+			let mut sum_col = segment.get_scalars_mut(sum)?;
+			for (i, &x) in rows.iter().enumerate() {
+				value_col[i] = x;
+				square_col[i] = x * x;
+				sum_col[i] = x + x * x;
+			}
+			Ok(())
+		};
+		witness
+			.fill_table_sequential(&ClosureFiller::new(source_id, fill_source), &values)
+			.unwrap();
+		let pulled_values = values
+			.iter()
+			.flat_map(|&x| [x, x + x * x])
+			.collect::<Vec<_>>();
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(sink_id, |rows: &[B32], segment| {
+					let mut pulled_col = segment.get_scalars_mut(pulled)?;
+					pulled_col[..rows.len()].copy_from_slice(rows);
+					Ok(())
+				}),
+				&pulled_values,
+			)
+			.unwrap();
+
+		validate_system_witness_with_prove_verify::<OptimalUnderlier128b>(
+			&cs,
+			witness,
+			vec![],
+			true,
+		);
+
+		// Only committed columns can be batched, and only in one batch.
+		cs.add_commit_batch("sums", [], [sum.id()]);
+		assert_matches!(cs.compile(), Err(Error::BatchedColumnNotCommitted(id)) if id == sum.id());
+		cs.commit_batches.pop();
+		cs.add_commit_batch("pulled", [], [pulled.id()]);
+		assert_matches!(
+			cs.compile(),
+			Err(Error::ColumnInSeveralCommitBatches(id)) if id == pulled.id()
+		);
+	}
+
+	#[test]
+	fn test_max_degree() {
+		let mut cs = ConstraintSystem::<B128>::new();
+		let mut table = cs.add_table("powers");
+		let table_id = table.id();
+		let x = table.add_committed::<B32, 1>("x");
+		let cube = table.add_committed::<B32, 1>("cube");
+		table.assert_zero("cube", x * x * x - cube);
+
+		assert_eq!(cs.max_degree(), MAX_CONSTRAINT_DEGREE);
+		assert_eq!(cs.compile().unwrap().max_degree(), 3);
+
+		cs.set_max_degree(2);
+		assert_matches!(
+			cs.compile(),
+			Err(Error::ConstraintDegreeTooLarge { table_id: id, name, degree: 3, max: 2 })
+				if id == table_id && name == "cube"
+		);
+
+		cs.set_max_degree(usize::MAX);
+		assert_eq!(cs.max_degree(), MAX_CONSTRAINT_DEGREE);
+		assert!(cs.compile().is_ok());
+	}
+
+	#[test]
+	fn test_prove_max_degree() {
+		// Every nonzero element of B8 is a root of x^255 - 1, so the constraint has the highest
+		// supported degree, which leaves no room for univariate skip rounds in the zerocheck.
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("units");
+		table.require_power_of_two_size();
+		let table_id = table.id();
+		let x = table.add_committed::<B8, 1>("x");
+		table.assert_zero("unit", Expr::from(x).pow(MAX_CONSTRAINT_DEGREE as u64) - B8::ONE);
+		assert_eq!(cs.compile().unwrap().max_degree(), MAX_CONSTRAINT_DEGREE);
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(&cs, &allocator);
+		let values = (1..17).map(B8::new).collect::<Vec<_>>();
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(table_id, |rows: &[B8], segment| {
+					let mut x_col = segment.get_scalars_mut(x)?;
+					x_col.copy_from_slice(rows);
+					Ok(())
+				}),
+				&values,
+			)
+			.unwrap();
+
+		validate_system_witness_with_prove_verify::<OptimalUnderlier128b>(
+			&cs,
+			witness,
+			vec![],
+			true,
+		);
+	}
+
+	#[test]
+	fn test_size_budget() {
+		let mut cs = ConstraintSystem::<B128>::new();
+		let mut table = cs.add_table("wide");
+		table.add_committed::<B32, 1>("narrow");
+		table.add_committed::<B32, 4>("wide");
+		table.add_committed::<B8, 1>("byte");
+		drop(table);
+		let mut table = cs.add_table("small");
+		table.add_committed::<B32, 1>("x");
+		table.add_constant("one", [B32::new(1)]);
+		drop(table);
+
+		assert_eq!(cs.size_budget(), SizeBudget::default());
+		assert!(cs.compile().is_ok());
+
+		cs.set_size_budget(SizeBudget {
+			max_committed_bits_per_row: Some(192),
+			max_columns: Some(5),
+		});
+		assert!(cs.compile().is_ok());
+
+		cs.set_size_budget(SizeBudget {
+			max_committed_bits_per_row: Some(128),
+			max_columns: Some(4),
+		});
+		let Err(Error::SizeBudgetExceeded(breakdown)) = cs.compile() else {
+			panic!("the budget is exceeded");
+		};
+		assert_eq!(breakdown.n_columns, 5);
+		assert_eq!(breakdown.tables[0].committed_bits_per_row, 168);
+		assert_eq!(
+			breakdown.tables[0].committed_columns,
+			[
+				("wide".to_string(), 128),
+				("narrow".to_string(), 32),
+				("byte".to_string(), 8)
+			]
+		);
+		assert_eq!(breakdown.tables[1].n_columns, 2);
+		assert_eq!(breakdown.tables[1].committed_bits_per_row, 32);
+		assert_eq!(
+			breakdown.to_string(),
+			"5 columns (budget 4)\n\
+			 * table 0 (wide): 3 columns, 168 committed bits per row (budget 128)\n\
+			 \x20 - wide: 128 bits\n\
+			 \x20 - narrow: 32 bits\n\
+			 \x20 - byte: 8 bits\n\
+			 * table 1 (small): 2 columns, 32 committed bits per row\n"
+		);
+	}
+
+	#[test]
+	fn test_byte_channels() {
+		let mut cs = ConstraintSystem::new();
+		let digests = cs.add_byte_channel::<32>("digests").unwrap();
+		let tags = cs.add_byte_channel::<4>("tags").unwrap();
+		assert_matches!(
+			cs.add_byte_channel::<24>("nonces"),
+			Err(Error::ByteChannelLengthNotPowerOfTwo { n_bytes: 24, .. })
+		);
+		assert_eq!(cs.channels.len(), 2);
+
+		let mut table = cs.add_table("messages");
+		let table_id = table.id();
+		let digest = table.add_committed::<B8, 32>("digest");
+		let tag = table.add_committed::<B8, 4>("tag");
+		let digest = table.add_packed_bytes("digest", digest);
+		let tag = table.add_packed_bytes("tag", tag);
+		table.push_bytes(digests, &digest);
+		table.push_bytes(tags, &tag);
+		assert_eq!(digest.packed.len(), ByteChannel::<32>::N_VALUES);
+		assert_eq!(tag.packed.len(), ByteChannel::<4>::N_VALUES);
+
+		let events = (0..5u8)
+			.map(|i| (array::from_fn::<u8, 32, _>(|j| i ^ (j as u8 * 7)), [i, 1, 2, 3]))
+			.collect::<Vec<_>>();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(&cs, &allocator);
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(table_id, |rows: &[([u8; 32], [u8; 4])], segment| {
+					{
+						let mut digest_col = segment.get_rows_mut_as::<u8, _, 32>(digest.bytes)?;
+						let mut tag_col = segment.get_rows_mut_as::<u8, _, 4>(tag.bytes)?;
+						for (i, (digest, tag)) in rows.iter().enumerate() {
+							digest_col.set_row(i, digest);
+							tag_col.set_row(i, tag);
+						}
+					}
+					segment.fill_packed_bytes(&digest)?;
+					segment.fill_packed_bytes(&tag)?;
+					Ok(())
+				}),
+				&events,
+			)
+			.unwrap();
+
+		let boundaries = events
+			.iter()
+			.flat_map(|(digest, tag)| {
+				[
+					digests.boundary(digest, FlushDirection::Pull, 1),
+					tags.boundary(tag, FlushDirection::Pull, 1),
+				]
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(boundaries[1].values, vec![B128::new(0x03020100)]);
+
+		validate_system_witness_with_prove_verify::<OptimalUnderlier128b>(
+			&cs, witness, boundaries, true,
+		);
+	}
+
+	#[test]
+	fn test_public_columns() {
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("squares");
+		table.require_power_of_two_size();
+		let table_id = table.id();
+		let value = table.add_public::<B32>("value");
+		let square = table.add_public::<B32>("square");
+		let cube = table.add_committed::<B32, 1>("cube");
+		table.assert_zero("square", value * value - square);
+		table.assert_zero("cube", value * square - cube);
+		assert_eq!(cs.channels.len(), 2);
+		assert_eq!(cs.channels[0].name, "squares::value");
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(&cs, &allocator);
+		let values = (3..11).map(B32::new).collect::<Vec<_>>();
+		let squares = values.iter().map(|&x| x * x).collect::<Vec<_>>();
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(table_id, |rows: &[B32], segment| {
+					let mut value_col = segment.get_scalars_mut(value)?;
+					let mut square_col = segment.get_scalars_mut(square)?;
+					let mut cube_col = segment.get_scalars_mut(cube)?;
+					for (i, &x) in rows.iter().enumerate() {
+						value_col[i] = x;
+						square_col[i] = x * x;
+						cube_col[i] = x * x * x;
+					}
+					segment.fill_public_index()?;
+					Ok(())
+				}),
+				&values,
+			)
+			.unwrap();
+
+		let boundaries = |values: &[B32], squares: &[B32]| {
+			let mut boundaries = cs.public_boundaries(value, values.iter().copied()).unwrap();
+			boundaries.extend(
+				cs.public_boundaries(square, squares.iter().copied())
+					.unwrap(),
+			);
+			boundaries
+		};
+
+		// The public values must be given in order and for all rows.
+		let mut swapped = values.clone();
+		swapped.swap(0, 1);
+		let trace =
+			ChannelTrace::collect(&cs, &mut witness, &boundaries(&swapped, &squares)).unwrap();
+		assert!(!trace.unbalanced(0).is_empty());
+		assert!(trace.unbalanced(1).is_empty());
+		let trace =
+			ChannelTrace::collect(&cs, &mut witness, &boundaries(&values, &squares[1..])).unwrap();
+		assert!(trace.unbalanced(0).is_empty());
+		assert!(!trace.unbalanced(1).is_empty());
+
+		assert_matches!(
+			cs.public_boundaries(cube, []),
+			Err(Error::NotPublicColumn(id)) if id == cube.id()
+		);
+
+		validate_system_witness_with_prove_verify::<OptimalUnderlier128b>(
+			&cs,
+			witness,
+			boundaries(&values, &squares),
+			true,
+		);
+	}
+}
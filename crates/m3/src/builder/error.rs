@@ -2,10 +2,16 @@
 
 use std::cell::{BorrowError, BorrowMutError};
 
-use binius_core::{oracle::Error as OracleError, polynomial::Error as PolynomialError};
+use binius_core::{
+	constraint_system::channel::ChannelId, oracle::Error as OracleError,
+	polynomial::Error as PolynomialError,
+};
 use binius_math::Error as MathError;
+use binius_utils::error_code::{ErrorCode, ErrorDomain, HasErrorCode};
 
-use super::{column::ColumnId, structured::Error as StructuredError, table::TableId};
+use super::{
+	budget::SizeBreakdown, column::ColumnId, structured::Error as StructuredError, table::TableId,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -13,6 +19,8 @@ pub enum Error {
 	MissingTable { table_id: TableId },
 	#[error("missing column with ID: {0:?}")]
 	MissingColumn(ColumnId),
+	#[error("missing channel with ID: {0}")]
+	MissingChannel(ChannelId),
 	#[error("missing partition with log_vals_per_row={log_vals_per_row} in table {table_id}")]
 	MissingPartition {
 		table_id: TableId,
@@ -50,8 +58,84 @@ pub enum Error {
 		"the table index was initialized for {expected} events; attempted to fill with {actual}"
 	)]
 	IncorrectNumberOfTableEvents { expected: usize, actual: usize },
-	#[error("table fill error: {0}")]
-	TableFill(anyhow::Error),
+	#[error(
+		"table {table_id} has {size} rows, which is not a multiple of {rows_per_event} rows per event"
+	)]
+	IncompatibleRowsPerEvent {
+		table_id: TableId,
+		size: usize,
+		rows_per_event: usize,
+	},
+	#[error("event {index} of the event stream is not accepted by any route")]
+	UnroutedEvent { index: usize },
+	#[error("failed to fill table {table_id} ({table_name}): {source}")]
+	TableFill {
+		table_id: TableId,
+		table_name: String,
+		#[source]
+		source: anyhow::Error,
+	},
+	#[error(
+		"table {table_id} has {size} rows, which exceeds the capacity of {capacity} rows of its copies"
+	)]
+	TableCapacityExceeded {
+		table_id: TableId,
+		size: usize,
+		capacity: usize,
+	},
+	#[error(
+		"column {column:?} is at tower level {tower_level}, but the handle is typed at tower level {handle_tower_level}"
+	)]
+	ColumnTowerLevelMismatch {
+		column: ColumnId,
+		tower_level: usize,
+		handle_tower_level: usize,
+	},
+	#[error("column {0:?} is not a shift of a bit column with a block size of 8 to 64 bits")]
+	UnsupportedShiftedColumn(ColumnId),
+	#[error("column {column:?} is populated by both {first} and {second}")]
+	ColumnPopulatedTwice {
+		column: ColumnId,
+		first: String,
+		second: String,
+	},
+	#[error("the populate steps {steps:?} of table {table_id} depend on each other in a cycle")]
+	PopulateCycle {
+		table_id: TableId,
+		steps: Vec<String>,
+	},
+	#[error("the columns {columns:?} of table {table_id} are not populated")]
+	UnfilledColumns {
+		table_id: TableId,
+		columns: Vec<String>,
+	},
+	#[error("column {0:?} is in a commit batch, but is not committed")]
+	BatchedColumnNotCommitted(ColumnId),
+	#[error("column {0:?} is in more than one commit batch")]
+	ColumnInSeveralCommitBatches(ColumnId),
+	#[error("column {0:?} is not a public column")]
+	NotPublicColumn(ColumnId),
+	#[error("table {table_id} does not use the row-major witness layout")]
+	RowMajorLayoutDisabled { table_id: TableId },
+	#[error("column {0:?} has no cells in the row-major witness layout")]
+	NotRowMajorColumn(ColumnId),
+	#[error(
+		"constraint {name} of table {table_id} has degree {degree}, which exceeds the maximum degree {max}"
+	)]
+	ConstraintDegreeTooLarge {
+		table_id: TableId,
+		name: String,
+		degree: usize,
+		max: usize,
+	},
+	#[error("byte channel {name} carries {n_bytes} bytes per string, which is not a power of two")]
+	ByteChannelLengthNotPowerOfTwo { name: String, n_bytes: usize },
+	#[error(
+		"the read count of entry {index} of the indexed lookup table on channel {channel_id} does not fit in 32 bits"
+	)]
+	LookupCountOverflow { channel_id: ChannelId, index: usize },
+	#[error("the constraint system exceeds its size budget:\n{0}")]
+	SizeBudgetExceeded(Box<SizeBreakdown>),
 	#[error("math error: {0}")]
 	Math(#[from] MathError),
 	#[error("oracle error: {0}")]
@@ -59,3 +143,46 @@ pub enum Error {
 	#[error("polynomial error: {0}")]
 	Polynomial(#[from] PolynomialError),
 }
+
+impl HasErrorCode for Error {
+	fn error_code(&self) -> ErrorCode {
+		let index = match self {
+			Self::MissingTable { .. } => 1,
+			Self::MissingColumn(_) => 2,
+			Self::MissingPartition { .. } => 3,
+			Self::EmptyTable { .. } => 4,
+			Self::FieldElementTooBig => 5,
+			Self::Structured(_) => 6,
+			Self::TableIndexAlreadyInitialized { .. } => 7,
+			Self::TableMismatch { .. } => 8,
+			Self::TableSizePowerOfTwoRequired { .. } => 9,
+			Self::TableSizeFixedRequired { .. } => 10,
+			Self::WitnessBorrow(_) => 11,
+			Self::WitnessBorrowMut(_) => 12,
+			Self::IncorrectNumberOfTableEvents { .. } => 13,
+			Self::TableFill { .. } => 14,
+			Self::Math(_) => 15,
+			Self::Oracle(_) => 16,
+			Self::Polynomial(_) => 17,
+			Self::UnsupportedShiftedColumn(_) => 18,
+			Self::TableCapacityExceeded { .. } => 19,
+			Self::ColumnPopulatedTwice { .. } => 20,
+			Self::PopulateCycle { .. } => 21,
+			Self::UnfilledColumns { .. } => 22,
+			Self::MissingChannel(_) => 23,
+			Self::BatchedColumnNotCommitted(_) => 24,
+			Self::ColumnInSeveralCommitBatches(_) => 25,
+			Self::NotPublicColumn(_) => 26,
+			Self::IncompatibleRowsPerEvent { .. } => 27,
+			Self::UnroutedEvent { .. } => 28,
+			Self::RowMajorLayoutDisabled { .. } => 29,
+			Self::NotRowMajorColumn(_) => 30,
+			Self::ConstraintDegreeTooLarge { .. } => 31,
+			Self::SizeBudgetExceeded(_) => 32,
+			Self::ByteChannelLengthNotPowerOfTwo { .. } => 33,
+			Self::LookupCountOverflow { .. } => 34,
+			Self::ColumnTowerLevelMismatch { .. } => 35,
+		};
+		ErrorCode::new(ErrorDomain::M3Builder, index)
+	}
+}
@@ -0,0 +1,285 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Construction of a witness directly from a stream of execution events.
+//!
+//! A virtual machine run usually produces a single stream of events, such as one event per
+//! executed instruction, where each kind of event is proven by a different table. An
+//! [`EventRouter`] takes a [`TableFiller`] for every table, along with a function that picks out
+//! the table's events from the stream, consumes the stream one event at a time, and then fills
+//! every table and collects the channel boundaries in one go.
+
+use binius_compute::alloc::HostBumpAllocator;
+use binius_field::{PackedField, TowerField};
+
+use super::{
+	Boundary, ConstraintSystem,
+	error::Error,
+	witness::{TableFiller, WitnessIndex},
+};
+
+/// A route of the events of a stream to the table that proves them.
+trait Route<P, E>
+where
+	P: PackedField,
+	P::Scalar: TowerField,
+{
+	/// Takes the table's event from a stream event and returns whether there was one.
+	fn accept(&mut self, event: &E) -> bool;
+
+	/// Fills the table with the accepted events.
+	fn fill(&self, witness: &mut WitnessIndex<'_, '_, P>) -> Result<(), Error>;
+}
+
+struct FillerRoute<'a, T, Event, Extract> {
+	filler: &'a T,
+	extract: Extract,
+	events: Vec<Event>,
+}
+
+impl<P, E, T, Event, Extract> Route<P, E> for FillerRoute<'_, T, Event, Extract>
+where
+	P: PackedField<Scalar: TowerField>,
+	T: TableFiller<P, Event = Event> + Sync,
+	Event: Clone + Sync,
+	Extract: Fn(&E) -> Option<Event>,
+{
+	fn accept(&mut self, event: &E) -> bool {
+		match (self.extract)(event) {
+			Some(event) => {
+				self.events.push(event);
+				true
+			}
+			None => false,
+		}
+	}
+
+	fn fill(&self, witness: &mut WitnessIndex<'_, '_, P>) -> Result<(), Error> {
+		witness.fill_table_parallel(self.filler, &self.events)
+	}
+}
+
+/// Routes a stream of execution events of type `E` to the table fillers that prove them.
+///
+/// Every event must be accepted by at least one route or boundary hook; an event may also feed
+/// several tables, such as an instruction that is proven by an instruction table and a memory
+/// table.
+#[allow(clippy::type_complexity)]
+pub struct EventRouter<'a, P, E>
+where
+	P: PackedField,
+	P::Scalar: TowerField,
+{
+	routes: Vec<Box<dyn Route<P, E> + 'a>>,
+	boundary_hooks: Vec<Box<dyn Fn(&E) -> Option<Boundary<P::Scalar>> + 'a>>,
+	boundaries: Vec<Boundary<P::Scalar>>,
+	n_events: usize,
+}
+
+impl<'a, F, P, E> EventRouter<'a, P, E>
+where
+	F: TowerField,
+	P: PackedField<Scalar = F>,
+{
+	pub fn new() -> Self {
+		Self {
+			routes: Vec::new(),
+			boundary_hooks: Vec::new(),
+			boundaries: Vec::new(),
+			n_events: 0,
+		}
+	}
+
+	/// Routes the events for which `extract` returns a table event to `filler`.
+	///
+	/// The table events are filled in the order of the stream.
+	pub fn route<T>(
+		&mut self,
+		filler: &'a T,
+		extract: impl Fn(&E) -> Option<T::Event> + 'a,
+	) -> &mut Self
+	where
+		T: TableFiller<P> + Sync,
+		T::Event: Sync + 'a,
+	{
+		self.routes.push(Box::new(FillerRoute {
+			filler,
+			extract,
+			events: Vec::new(),
+		}));
+		self
+	}
+
+	/// Collects a channel boundary from the events for which `boundary` returns one.
+	///
+	/// This is how events such as the initial and final states of a run, which are flushed by the
+	/// verifier rather than by a table, enter the boundary list.
+	pub fn route_boundary(
+		&mut self,
+		boundary: impl Fn(&E) -> Option<Boundary<F>> + 'a,
+	) -> &mut Self {
+		self.boundary_hooks.push(Box::new(boundary));
+		self
+	}
+
+	/// Routes an event to every route and boundary hook that accepts it.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::UnroutedEvent`] if no route or boundary hook accepts the event
+	pub fn push(&mut self, event: &E) -> Result<(), Error> {
+		let mut accepted = false;
+		for route in &mut self.routes {
+			accepted |= route.accept(event);
+		}
+		for hook in &self.boundary_hooks {
+			if let Some(boundary) = hook(event) {
+				self.boundaries.push(boundary);
+				accepted = true;
+			}
+		}
+		if !accepted {
+			return Err(Error::UnroutedEvent {
+				index: self.n_events,
+			});
+		}
+		self.n_events += 1;
+		Ok(())
+	}
+
+	/// Routes all events of a stream, in order.
+	pub fn extend<'e>(&mut self, events: impl IntoIterator<Item = &'e E>) -> Result<(), Error>
+	where
+		E: 'e,
+	{
+		events.into_iter().try_for_each(|event| self.push(event))
+	}
+
+	/// Returns the number of events routed so far.
+	pub fn n_events(&self) -> usize {
+		self.n_events
+	}
+
+	/// Fills the tables of the routes into a witness index and returns the collected boundaries.
+	///
+	/// Tables that received no events are left empty.
+	pub fn fill(self, witness: &mut WitnessIndex<'_, '_, P>) -> Result<Vec<Boundary<F>>, Error> {
+		for route in &self.routes {
+			route.fill(witness)?;
+		}
+		Ok(self.boundaries)
+	}
+
+	/// Creates the witness index for a constraint system from the routed events and returns it
+	/// along with the collected boundaries.
+	pub fn build<'cs, 'alloc>(
+		self,
+		cs: &'cs ConstraintSystem<F>,
+		allocator: &'alloc HostBumpAllocator<'alloc, P>,
+	) -> Result<(WitnessIndex<'cs, 'alloc, P>, Vec<Boundary<F>>), Error> {
+		let mut witness = WitnessIndex::new(cs, allocator);
+		let boundaries = self.fill(&mut witness)?;
+		Ok((witness, boundaries))
+	}
+}
+
+impl<F, P, E> Default for EventRouter<'_, P, E>
+where
+	F: TowerField,
+	P: PackedField<Scalar = F>,
+{
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use assert_matches::assert_matches;
+	use binius_compute::cpu::alloc::CpuComputeAllocator;
+	use binius_core::constraint_system::channel::FlushDirection;
+	use binius_field::{arch::OptimalUnderlier128b, as_packed_field::PackedType};
+
+	use super::*;
+	use crate::builder::{
+		B32, B128, TableWitnessSegment,
+		test_utils::{ClosureFiller, validate_system_witness},
+	};
+
+	type P = PackedType<OptimalUnderlier128b, B128>;
+
+	enum Op {
+		Init(u32),
+		Push(u32),
+		Pull(u32),
+	}
+
+	#[test]
+	fn test_event_router() {
+		let mut cs = ConstraintSystem::new();
+		let channel = cs.add_channel("values");
+
+		let mut pushes = cs.add_table("pushes");
+		let pushes_id = pushes.id();
+		let pushed = pushes.add_committed::<B32, 1>("value");
+		pushes.push(channel, [pushed]);
+
+		let mut pulls = cs.add_table("pulls");
+		let pulls_id = pulls.id();
+		let pulled = pulls.add_committed::<B32, 1>("value");
+		pulls.pull(channel, [pulled]);
+
+		let fill_values = |col| {
+			move |values: &[u32], segment: &mut TableWitnessSegment<P>| {
+				let mut col = segment.get_mut_as::<u32, _, 1>(col)?;
+				for (dst, &value) in col.iter_mut().zip(values) {
+					*dst = value;
+				}
+				Ok(())
+			}
+		};
+		let pushes_filler = ClosureFiller::new(pushes_id, fill_values(pushed));
+		let pulls_filler = ClosureFiller::new(pulls_id, fill_values(pulled));
+
+		let ops = [
+			Op::Init(5),
+			Op::Push(7),
+			Op::Pull(7),
+			Op::Push(9),
+			Op::Pull(5),
+			Op::Pull(9),
+		];
+
+		let mut router = EventRouter::<P, Op>::new();
+		router
+			.route(&pushes_filler, |op| match op {
+				Op::Push(value) => Some(*value),
+				_ => None,
+			})
+			.route(&pulls_filler, |op| match op {
+				Op::Pull(value) => Some(*value),
+				_ => None,
+			});
+		// Without a boundary hook, the initial value is not routed.
+		assert_matches!(router.push(&ops[0]), Err(Error::UnroutedEvent { index: 0 }));
+
+		router.route_boundary(|op| match op {
+			Op::Init(value) => Some(Boundary {
+				values: vec![B32::new(*value).into()],
+				channel_id: channel,
+				direction: FlushDirection::Push,
+				multiplicity: 1,
+			}),
+			_ => None,
+		});
+		router.extend(&ops).unwrap();
+		assert_eq!(router.n_events(), ops.len());
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let (witness, boundaries) = router.build(&cs, &allocator).unwrap();
+		assert_eq!(witness.table_sizes(), vec![2, 3]);
+		assert_eq!(boundaries.len(), 1);
+
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, boundaries);
+	}
+}
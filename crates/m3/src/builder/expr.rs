@@ -1,13 +1,16 @@
 // Copyright 2025 Irreducible Inc.
 
-use binius_field::{ExtensionField, Field, TowerField};
+use std::{cmp::Ordering, collections::HashMap, sync::Arc};
+
+use binius_field::{ExtensionField, Field, PackedField, TowerField};
 use binius_math::{ArithCircuit, ArithCircuitStep, ArithExpr};
+use binius_utils::SerializationMode;
 use getset::{CopyGetters, Getters};
 
 use super::{column::Col, table::TableId};
 
 /// A constraint that the evaluation of an expression over a table is zero at every row.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ZeroConstraint<F: Field> {
 	pub name: String,
 	pub expr: ArithCircuit<F>,
@@ -17,7 +20,10 @@ pub struct ZeroConstraint<F: Field> {
 /// A type representing an arithmetic expression composed over some table columns.
 ///
 /// If the expression degree is 1, then it is a linear expression.
-#[derive(Debug, Clone, Getters, CopyGetters)]
+///
+/// Expressions compare and hash structurally. Use [`Self::canonicalize`] to compare expressions
+/// that are built differently but are equal up to constant folding and reordering of terms.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Getters, CopyGetters)]
 pub struct Expr<F: TowerField, const V: usize> {
 	#[get_copy = "pub"]
 	table_id: TableId,
@@ -38,6 +44,16 @@ impl<F: TowerField, const V: usize> Expr<F, V> {
 			expr: self.expr.pow(exp),
 		}
 	}
+
+	/// Returns the canonical form of the expression.
+	///
+	/// See [`canonicalize`] for the normalizations that are applied.
+	pub fn canonicalize(&self) -> Self {
+		Self {
+			table_id: self.table_id,
+			expr: canonicalize(&self.expr),
+		}
+	}
 }
 
 impl<F: TowerField, const V: usize> From<Col<F, V>> for Expr<F, V> {
@@ -235,6 +251,93 @@ impl<F: TowerField, const V: usize> std::ops::Mul<F> for Col<F, V> {
 	}
 }
 
+/// Expression sugar for arrays of columns or expressions, such as the wide states created by
+/// [`TableBuilder::add_committed_multiple`](super::TableBuilder::add_committed_multiple).
+///
+/// The elementwise operations combine the elements at equal indices, the reductions combine all
+/// elements into one expression, and [`Self::permute`] reorders the elements by a constant index
+/// map. The reductions panic if the array is empty, because an expression belongs to a table.
+pub trait ExprArray<F: TowerField, const V: usize, const N: usize>: Sized {
+	/// Converts the elements to expressions.
+	fn exprs(self) -> [Expr<F, V>; N];
+
+	/// Returns the elementwise sum with another array.
+	fn add_elementwise(self, rhs: impl ExprArray<F, V, N>) -> [Expr<F, V>; N] {
+		let mut rhs = rhs.exprs().into_iter();
+		self.exprs()
+			.map(|lhs| lhs + rhs.next().expect("arrays have equal lengths"))
+	}
+
+	/// Returns the elementwise difference with another array.
+	fn sub_elementwise(self, rhs: impl ExprArray<F, V, N>) -> [Expr<F, V>; N] {
+		let mut rhs = rhs.exprs().into_iter();
+		self.exprs()
+			.map(|lhs| lhs - rhs.next().expect("arrays have equal lengths"))
+	}
+
+	/// Returns the elementwise product with another array.
+	fn mul_elementwise(self, rhs: impl ExprArray<F, V, N>) -> [Expr<F, V>; N] {
+		let mut rhs = rhs.exprs().into_iter();
+		self.exprs()
+			.map(|lhs| lhs * rhs.next().expect("arrays have equal lengths"))
+	}
+
+	/// Adds the constant at every index to the element at that index.
+	fn add_scalars(self, scalars: [F; N]) -> [Expr<F, V>; N] {
+		let mut scalars = scalars.into_iter();
+		self.exprs()
+			.map(|expr| expr + scalars.next().expect("arrays have equal lengths"))
+	}
+
+	/// Multiplies the element at every index by the constant at that index.
+	fn mul_scalars(self, scalars: [F; N]) -> [Expr<F, V>; N] {
+		let mut scalars = scalars.into_iter();
+		self.exprs()
+			.map(|expr| expr * scalars.next().expect("arrays have equal lengths"))
+	}
+
+	/// Returns the sum of the elements.
+	fn sum(self) -> Expr<F, V> {
+		self.exprs()
+			.into_iter()
+			.reduce(|acc, expr| acc + expr)
+			.expect("array must not be empty")
+	}
+
+	/// Returns the product of the elements.
+	fn product(self) -> Expr<F, V> {
+		self.exprs()
+			.into_iter()
+			.reduce(|acc, expr| acc * expr)
+			.expect("array must not be empty")
+	}
+
+	/// Returns the linear combination of the elements with constant coefficients.
+	fn dot(self, coeffs: [F; N]) -> Expr<F, V> {
+		self.mul_scalars(coeffs).sum()
+	}
+
+	/// Returns the array whose element at index `i` is the element at index `index_map(i)`.
+	///
+	/// ## Preconditions
+	///
+	/// * `index_map` must map every index to an index less than `N`
+	fn permute(self, index_map: impl Fn(usize) -> usize) -> [Expr<F, V>; N] {
+		let exprs = self.exprs();
+		std::array::from_fn(|i| exprs[index_map(i)].clone())
+	}
+}
+
+impl<F, T, const V: usize, const N: usize> ExprArray<F, V, N> for [T; N]
+where
+	F: TowerField,
+	T: Into<Expr<F, V>>,
+{
+	fn exprs(self) -> [Expr<F, V>; N] {
+		self.map(Into::into)
+	}
+}
+
 /// Upcast an expression from a subfield to an extension field.
 pub fn upcast_expr<F, FSub, const V: usize>(expr: Expr<FSub, V>) -> Expr<F, V>
 where
@@ -248,6 +351,230 @@ where
 	}
 }
 
+/// Returns the canonical form of an arithmetic expression.
+///
+/// The canonical form is a sum of products, in which
+///
+/// * constant subexpressions are folded,
+/// * nested sums are flattened, and the coefficients of equal terms are added, so that terms cancel
+///   out in characteristic 2, like `x + x` or `x - x`,
+/// * nested products and powers are flattened, and equal factors are merged into powers,
+/// * the terms of sums and the factors of products are sorted, with the constant coefficient of a
+///   product first and the constant term of a sum last.
+///
+/// Products of sums are not expanded, so expressions that are only equal after distributing
+/// products over sums can have different canonical forms. Expressions with equal canonical forms
+/// compare and hash equally.
+pub fn canonicalize<F: Field>(expr: &ArithExpr<F>) -> ArithExpr<F> {
+	Canonicalizer::default().visit(expr)
+}
+
+/// Canonicalizes the subexpressions of an expression, each shared subexpression once.
+struct Canonicalizer<F: Field> {
+	canonical: HashMap<*const ArithExpr<F>, ArithExpr<F>>,
+}
+
+impl<F: Field> Default for Canonicalizer<F> {
+	fn default() -> Self {
+		Self {
+			canonical: HashMap::new(),
+		}
+	}
+}
+
+impl<F: Field> Canonicalizer<F> {
+	fn visit(&mut self, expr: &ArithExpr<F>) -> ArithExpr<F> {
+		match expr {
+			ArithExpr::Const(_) | ArithExpr::Var(_) => expr.clone(),
+			ArithExpr::Add(left, right) => {
+				let mut sum = Sum::default();
+				sum.add(self.visit_shared(left));
+				sum.add(self.visit_shared(right));
+				sum.build()
+			}
+			ArithExpr::Mul(left, right) => {
+				let mut product = Product::default();
+				product.mul(self.visit_shared(left), 1);
+				product.mul(self.visit_shared(right), 1);
+				product.build()
+			}
+			ArithExpr::Pow(base, exp) => {
+				let mut product = Product::default();
+				product.mul(self.visit_shared(base), *exp);
+				product.build()
+			}
+		}
+	}
+
+	fn visit_shared(&mut self, expr: &Arc<ArithExpr<F>>) -> ArithExpr<F> {
+		if let Some(canonical) = self.canonical.get(&Arc::as_ptr(expr)) {
+			return canonical.clone();
+		}
+		let canonical = self.visit(expr);
+		self.canonical.insert(Arc::as_ptr(expr), canonical.clone());
+		canonical
+	}
+}
+
+/// A sum of canonical terms with their coefficients and a constant term.
+struct Sum<F: Field> {
+	constant: F,
+	terms: HashMap<ArithExpr<F>, F>,
+}
+
+impl<F: Field> Default for Sum<F> {
+	fn default() -> Self {
+		Self {
+			constant: F::ZERO,
+			terms: HashMap::new(),
+		}
+	}
+}
+
+impl<F: Field> Sum<F> {
+	/// Adds a canonical expression.
+	fn add(&mut self, expr: ArithExpr<F>) {
+		match expr {
+			ArithExpr::Const(value) => self.constant += value,
+			ArithExpr::Add(left, right) => {
+				self.add(Arc::unwrap_or_clone(left));
+				self.add(Arc::unwrap_or_clone(right));
+			}
+			ArithExpr::Mul(left, right) if matches!(*left, ArithExpr::Const(_)) => {
+				let ArithExpr::Const(coeff) = *left else {
+					unreachable!("the left factor is a constant")
+				};
+				*self
+					.terms
+					.entry(Arc::unwrap_or_clone(right))
+					.or_insert(F::ZERO) += coeff;
+			}
+			term => *self.terms.entry(term).or_insert(F::ZERO) += F::ONE,
+		}
+	}
+
+	fn build(self) -> ArithExpr<F> {
+		let mut terms = self
+			.terms
+			.into_iter()
+			.filter(|(_, coeff)| *coeff != F::ZERO)
+			.collect::<Vec<_>>();
+		terms.sort_by(|(lhs, _), (rhs, _)| cmp_canonical(lhs, rhs));
+		let terms = terms
+			.into_iter()
+			.map(|(term, coeff)| {
+				if coeff == F::ONE {
+					term
+				} else {
+					ArithExpr::Const(coeff) * term
+				}
+			})
+			.chain((self.constant != F::ZERO).then_some(ArithExpr::Const(self.constant)));
+		terms
+			.reduce(|sum, term| sum + term)
+			.unwrap_or(ArithExpr::Const(F::ZERO))
+	}
+}
+
+/// A product of canonical factors with their exponents and a constant coefficient.
+struct Product<F: Field> {
+	coeff: F,
+	factors: HashMap<ArithExpr<F>, u64>,
+}
+
+impl<F: Field> Default for Product<F> {
+	fn default() -> Self {
+		Self {
+			coeff: F::ONE,
+			factors: HashMap::new(),
+		}
+	}
+}
+
+impl<F: Field> Product<F> {
+	/// Multiplies by a power of a canonical expression.
+	fn mul(&mut self, expr: ArithExpr<F>, exp: u64) {
+		match expr {
+			ArithExpr::Const(value) => self.coeff *= PackedField::pow(value, exp),
+			ArithExpr::Mul(left, right) => {
+				self.mul(Arc::unwrap_or_clone(left), exp);
+				self.mul(Arc::unwrap_or_clone(right), exp);
+			}
+			ArithExpr::Pow(base, base_exp) => {
+				let exp = base_exp
+					.checked_mul(exp)
+					.expect("the exponent must fit into 64 bits");
+				self.mul(Arc::unwrap_or_clone(base), exp);
+			}
+			factor => {
+				let factor_exp = self.factors.entry(factor).or_insert(0);
+				*factor_exp = factor_exp
+					.checked_add(exp)
+					.expect("the exponent must fit into 64 bits");
+			}
+		}
+	}
+
+	fn build(self) -> ArithExpr<F> {
+		if self.coeff == F::ZERO {
+			return ArithExpr::Const(F::ZERO);
+		}
+		let mut factors = self
+			.factors
+			.into_iter()
+			.filter(|(_, exp)| *exp != 0)
+			.collect::<Vec<_>>();
+		factors.sort_by(|(lhs, _), (rhs, _)| cmp_canonical(lhs, rhs));
+		let product = factors
+			.into_iter()
+			.map(|(factor, exp)| if exp == 1 { factor } else { factor.pow(exp) })
+			.reduce(|product, factor| product * factor);
+		match product {
+			None => ArithExpr::Const(self.coeff),
+			Some(product) if self.coeff == F::ONE => product,
+			Some(product) => ArithExpr::Const(self.coeff) * product,
+		}
+	}
+}
+
+/// A total order of expressions that is used to sort the terms and factors of canonical forms.
+///
+/// Variables come first, ordered by index, then sums, products, and powers, ordered
+/// lexicographically by their operands. Constants, which only appear as coefficients and constant
+/// terms, are ordered by their byte representation.
+fn cmp_canonical<F: Field>(lhs: &ArithExpr<F>, rhs: &ArithExpr<F>) -> Ordering {
+	fn rank<F: Field>(expr: &ArithExpr<F>) -> u8 {
+		match expr {
+			ArithExpr::Const(_) => 0,
+			ArithExpr::Var(_) => 1,
+			ArithExpr::Add(..) => 2,
+			ArithExpr::Mul(..) => 3,
+			ArithExpr::Pow(..) => 4,
+		}
+	}
+
+	fn const_bytes<F: Field>(value: F) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		value
+			.serialize(&mut bytes, SerializationMode::Native)
+			.expect("serializing to a vector cannot fail");
+		bytes
+	}
+
+	match (lhs, rhs) {
+		(ArithExpr::Const(lhs), ArithExpr::Const(rhs)) => const_bytes(*lhs).cmp(&const_bytes(*rhs)),
+		(ArithExpr::Var(lhs), ArithExpr::Var(rhs)) => lhs.cmp(rhs),
+		(ArithExpr::Add(lhs_left, lhs_right), ArithExpr::Add(rhs_left, rhs_right))
+		| (ArithExpr::Mul(lhs_left, lhs_right), ArithExpr::Mul(rhs_left, rhs_right)) => {
+			cmp_canonical(lhs_left, rhs_left).then_with(|| cmp_canonical(lhs_right, rhs_right))
+		}
+		(ArithExpr::Pow(lhs_base, lhs_exp), ArithExpr::Pow(rhs_base, rhs_exp)) => {
+			cmp_canonical(lhs_base, rhs_base).then_with(|| lhs_exp.cmp(rhs_exp))
+		}
+		_ => rank(lhs).cmp(&rank(rhs)),
+	}
+}
+
 /// This exists only to implement Display for ArithExpr with named variables.
 pub struct ArithExprNamedVars<'a, F: TowerField>(pub &'a ArithCircuit<F>, pub &'a [String]);
 
@@ -285,3 +612,91 @@ impl<F: TowerField> std::fmt::Display for ArithExprNamedVars<'_, F> {
 		write_step(f, 0, self.0.steps(), self.1)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		collections::hash_map::DefaultHasher,
+		hash::{Hash, Hasher},
+	};
+
+	use super::*;
+	use crate::builder::{B32, B128, ConstraintSystem};
+
+	fn hash<T: Hash>(value: &T) -> u64 {
+		let mut hasher = DefaultHasher::new();
+		value.hash(&mut hasher);
+		hasher.finish()
+	}
+
+	#[test]
+	fn test_canonicalize() {
+		let mut cs = ConstraintSystem::<B128>::new();
+		let mut table = cs.add_table("test");
+		let x = table.add_committed::<B32, 1>("x");
+		let y = table.add_committed::<B32, 1>("y");
+		let z = table.add_committed::<B32, 1>("z");
+		let c = B32::new(0x1234);
+
+		let equal_pairs = [
+			// Terms and factors are sorted.
+			(x * y + z, z + y * x),
+			// Constants are folded.
+			(
+				x * c + B32::new(3),
+				(x * B32::new(2) + B32::new(1)) * B32::new(1) + x * (c + B32::new(2)) + B32::new(2),
+			),
+			// Subtraction is addition, and equal terms cancel out in characteristic 2.
+			(x - y, y + x),
+			(x + z, x + y + z + y),
+			(x * y - z * B32::ONE, x * y + z + y * z - z * y),
+			// Equal factors are merged into powers, and nested powers are flattened.
+			(x * x * y, y * Expr::from(x).pow(2)),
+			(Expr::from(x).pow(2).pow(3), (x * x * x).pow(2)),
+			((x * y).pow(2), x * y * y * x),
+		];
+		for (lhs, rhs) in equal_pairs {
+			assert_ne!(lhs, rhs);
+			let (lhs, rhs) = (lhs.canonicalize(), rhs.canonicalize());
+			assert_eq!(lhs, rhs);
+			assert_eq!(hash(&lhs), hash(&rhs));
+			assert_eq!(lhs.canonicalize(), lhs);
+		}
+
+		assert_eq!(*(x * c - x * c + B32::ONE).canonicalize().expr(), ArithExpr::Const(B32::ONE));
+		assert_eq!(
+			*(Expr::from(x).pow(0) + B32::ONE).canonicalize().expr(),
+			ArithExpr::Const(B32::ZERO)
+		);
+		assert_ne!((x + y).canonicalize(), (x + z).canonicalize());
+		// Products of sums are not expanded.
+		assert_ne!(((x + y) * z).canonicalize(), (x * z + y * z).canonicalize());
+	}
+
+	#[test]
+	fn test_expr_array() {
+		let mut cs = ConstraintSystem::<B128>::new();
+		let mut table = cs.add_table("test");
+		let xs = table.add_committed_multiple::<B32, 1, 3>("x");
+		let ys = table.add_committed_multiple::<B32, 1, 3>("y");
+		let c = [B32::new(2), B32::new(3), B32::new(5)];
+
+		assert_eq!(xs.add_elementwise(ys), [0, 1, 2].map(|i| xs[i] + ys[i]));
+		assert_eq!(xs.sub_elementwise(ys), [0, 1, 2].map(|i| xs[i] - ys[i]));
+		assert_eq!(xs.mul_elementwise(ys), [0, 1, 2].map(|i| xs[i] * ys[i]));
+		assert_eq!(xs.add_scalars(c), [0, 1, 2].map(|i| xs[i] + c[i]));
+		assert_eq!(xs.mul_scalars(c), [0, 1, 2].map(|i| xs[i] * c[i]));
+
+		assert_eq!(xs.sum(), xs[0] + xs[1] + xs[2]);
+		assert_eq!(xs.product(), xs[0] * xs[1] * xs[2]);
+		assert_eq!(xs.dot(c), xs[0] * c[0] + xs[1] * c[1] + xs[2] * c[2]);
+		assert_eq!(xs.dot(c).degree(), 1);
+
+		// Expressions and columns mix, and index maps reorder the elements.
+		assert_eq!(
+			xs.add_elementwise(ys).sum().canonicalize(),
+			(xs.sum() + ys.sum()).canonicalize()
+		);
+		assert_eq!(xs.permute(|i| (i + 1) % 3), [xs[1], xs[2], xs[0]].exprs());
+	}
+}
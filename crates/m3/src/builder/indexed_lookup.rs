@@ -43,11 +43,17 @@ pub trait IndexedLookup<F: TowerField> {
 ///
 /// The method to tally counts is to scan all tables in the constraint system and boundaries
 /// values, and identify those that pull from the lookup table's channel. Then we iterate over the
-/// values read from the table and count all the indices.
+/// values read from the table and count all the indices. Reads are counted with the multiplicity
+/// of their flush, and selected reads only on the rows where all selectors are set, so any number
+/// of tables can share a single lookup table.
 ///
 /// ## Returns
 ///
 /// A vector of counts, whose length is equal to `1 << indexed_lookup.log_size()`.
+///
+/// ## Throws
+///
+/// * [`Error::LookupCountOverflow`] if the read count of an entry does not fit in a `u32`.
 pub fn tally<P>(
 	cs: &ConstraintSystem<B128>,
 	// TODO: This doesn't actually need mutable access. But must of the WitnessIndex methods only
@@ -66,7 +72,11 @@ where
 		+ PackedExtension<B64>
 		+ PackedExtension<B128>,
 {
-	let mut counts = vec![0; 1 << indexed_lookup.log_size()];
+	let mut counts = vec![0u32; 1 << indexed_lookup.log_size()];
+	let overflow = |index| Error::LookupCountOverflow {
+		channel_id: chan,
+		index,
+	};
 
 	// Tally counts from the tables
 	for table in &cs.tables {
@@ -83,21 +93,31 @@ where
 							.map(|&col_index| segment.get_dyn(col_index))
 							.collect::<Result<Vec<_>, _>>()?;
 
-						if !flush.selectors.is_empty() {
-							// TODO: check flush selectors
-							todo!("tally does not support selected table reads yet");
-						}
+						let selectors = flush
+							.selectors
+							.iter()
+							.map(|&col_index| segment.get_dyn(col_index))
+							.collect::<Result<Vec<_>, _>>()?;
 
 						let mut elems = vec![B128::ZERO; cols.len()];
 						// It's important that this is only the unpacked table size(rows * values
 						// per row in the partition), not the full segment size. The entries
 						// after the table size are not flushed.
 						for i in 0..table_size * partition.values_per_row {
+							let row = i / partition.values_per_row;
+							if selectors
+								.iter()
+								.any(|selector| selector.get(row) == B128::ZERO)
+							{
+								continue;
+							}
 							for (elem, col) in iter::zip(&mut elems, &cols) {
 								*elem = col.get(i);
 							}
 							let index = indexed_lookup.entry_to_index(&elems);
-							counts[index] += 1;
+							counts[index] = counts[index]
+								.checked_add(flush.multiplicity)
+								.ok_or_else(|| overflow(index))?;
 						}
 					}
 				}
@@ -109,7 +129,10 @@ where
 	for boundary in boundaries {
 		if boundary.channel_id == chan && boundary.direction == FlushDirection::Pull {
 			let index = indexed_lookup.entry_to_index(&boundary.values);
-			counts[index] += 1;
+			counts[index] = u32::try_from(boundary.multiplicity)
+				.ok()
+				.and_then(|multiplicity| counts[index].checked_add(multiplicity))
+				.ok_or_else(|| overflow(index))?;
 		}
 	}
 
@@ -1,28 +1,47 @@
 // Copyright 2025 Irreducible Inc.
 
+#[cfg(feature = "test_utils")]
+pub mod arbitrary;
+pub mod boolean;
+pub mod budget;
 pub mod channel;
 pub mod column;
+pub mod column_rows;
 pub mod constraint_system;
 pub mod error;
+pub mod event_router;
 pub mod expr;
 pub mod indexed_lookup;
 mod multi_iter;
+pub mod preflight;
+pub mod repack;
+pub mod schedule;
 pub mod stat;
 pub mod structured;
 pub mod table;
 #[cfg(feature = "test_utils")]
 pub mod test_utils;
+pub mod trace;
 pub mod types;
 pub mod witness;
 
+pub use binius_core::constraint_system::MAX_CONSTRAINT_DEGREE;
+pub use boolean::BoolExpr;
+pub use budget::{SizeBreakdown, SizeBudget, TableSizeBreakdown};
 pub use channel::*;
 pub use column::*;
+pub use column_rows::{ColumnRows, ColumnRowsMut};
 pub use constraint_system::*;
 pub use error::*;
+pub use event_router::EventRouter;
 pub use expr::*;
 pub use indexed_lookup::*;
+pub use preflight::PreflightFinding;
+pub use repack::{RepackReport, RepackedColumn};
+pub use schedule::{PopulateColumns, PopulateStep, ScheduledFiller};
 pub use stat::*;
 pub use structured::StructuredDynSize;
 pub use table::*;
+pub use trace::{ChannelTrace, FlushOrigin, TraceEntry};
 pub use types::*;
 pub use witness::*;
@@ -0,0 +1,204 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Cheap checks of a witness that run before the constraint system is compiled and proven.
+//!
+//! Compiling and proving a constraint system is expensive, and a mistake in the witness usually
+//! surfaces only at the end of that, as a failed consistency check. The checks here catch common
+//! mistakes up front from the table sizes, the boundaries and a scan of the column data, and
+//! report them as [`PreflightFinding`]s. An empty list of findings does not imply that the
+//! witness is valid.
+
+use std::fmt::{self, Display};
+
+use binius_core::constraint_system::{
+	TableSizeSpec,
+	channel::{Boundary, ChannelId, FlushDirection},
+};
+use binius_field::TowerField;
+
+use super::{ConstraintSystem, table::TableId};
+
+/// A problem with a witness found by [`WitnessIndex::preflight`].
+///
+/// [`WitnessIndex::preflight`]: super::WitnessIndex::preflight
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreflightFinding {
+	/// The size of a table does not satisfy its size specification.
+	TableSize {
+		table_id: TableId,
+		table_name: String,
+		size: usize,
+		/// A description of the required size.
+		required: String,
+	},
+	/// A column of a filled table holds only zeros, although it cannot be zero.
+	///
+	/// This is reported for columns that are constrained to be non-zero and for constant columns
+	/// with a non-zero value, which are filled by [`WitnessIndex::fill_constant_cols`].
+	///
+	/// [`WitnessIndex::fill_constant_cols`]: super::WitnessIndex::fill_constant_cols
+	UnfilledColumn {
+		table_id: TableId,
+		table_name: String,
+		column_name: String,
+	},
+	/// Values are flushed to a channel in one direction, but never in the other.
+	///
+	/// The counts are upper bounds, as they do not take flush selectors into account.
+	UnbalancedChannel {
+		channel_id: ChannelId,
+		channel_name: String,
+		max_pushes: u64,
+		max_pulls: u64,
+	},
+	/// A zero constraint has a degree above [`ConstraintSystem::max_degree`].
+	ConstraintDegree {
+		table_id: TableId,
+		table_name: String,
+		constraint_name: String,
+		degree: usize,
+		max: usize,
+	},
+}
+
+impl Display for PreflightFinding {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::TableSize {
+				table_name,
+				size,
+				required,
+				..
+			} => write!(f, "table {table_name} has {size} rows, but {required}"),
+			Self::UnfilledColumn {
+				table_name,
+				column_name,
+				..
+			} => write!(
+				f,
+				"column {column_name} of table {table_name} is all zeros, although it cannot be \
+				 zero; it was likely not filled"
+			),
+			Self::UnbalancedChannel {
+				channel_name,
+				max_pushes,
+				max_pulls,
+				..
+			} => write!(
+				f,
+				"channel {channel_name} receives up to {max_pushes} pushes and {max_pulls} pulls, \
+				 so it cannot balance"
+			),
+			Self::ConstraintDegree {
+				table_name,
+				constraint_name,
+				degree,
+				max,
+				..
+			} => write!(
+				f,
+				"zero constraint {constraint_name} of table {table_name} has degree {degree}, \
+				 which exceeds the maximum of {max}"
+			),
+		}
+	}
+}
+
+/// Checks the table sizes against the size specifications of the tables.
+pub(super) fn check_table_sizes<F: TowerField>(
+	cs: &ConstraintSystem<F>,
+	table_sizes: &[usize],
+) -> impl Iterator<Item = PreflightFinding> {
+	cs.tables
+		.iter()
+		.zip(table_sizes)
+		.filter_map(|(table, &size)| {
+			let required = match table.size_spec() {
+				TableSizeSpec::PowerOfTwo if !size.is_power_of_two() => {
+					"its size must be a power of two".to_string()
+				}
+				TableSizeSpec::Fixed { log_size } if size != 1 << log_size => {
+					format!("its size must be {}", 1 << log_size)
+				}
+				_ => return None,
+			};
+			Some(PreflightFinding::TableSize {
+				table_id: table.id,
+				table_name: table.name.clone(),
+				size,
+				required,
+			})
+		})
+}
+
+/// Counts the values flushed to every channel by the tables and the boundaries, and reports the
+/// channels that only have flushes in one direction.
+pub(super) fn check_channels<F: TowerField>(
+	cs: &ConstraintSystem<F>,
+	table_sizes: &[usize],
+	boundaries: &[Boundary<F>],
+) -> impl Iterator<Item = PreflightFinding> {
+	let mut counts = vec![(0u64, 0u64); cs.channels.len()];
+	let mut count = |channel_id: ChannelId, direction: FlushDirection, n: u64| {
+		if let Some((pushes, pulls)) = counts.get_mut(channel_id) {
+			match direction {
+				FlushDirection::Push => *pushes = pushes.saturating_add(n),
+				FlushDirection::Pull => *pulls = pulls.saturating_add(n),
+			}
+		}
+	};
+
+	for (table, &size) in cs.tables.iter().zip(table_sizes) {
+		for partition in table.partitions.values() {
+			let n_values = (size * partition.values_per_row) as u64;
+			for flush in &partition.flushes {
+				count(flush.channel_id, flush.direction, n_values * flush.multiplicity as u64);
+			}
+		}
+	}
+	for boundary in boundaries {
+		count(boundary.channel_id, boundary.direction, boundary.multiplicity);
+	}
+
+	cs.channels
+		.iter()
+		.zip(counts)
+		.enumerate()
+		.filter(|(_, (_, (pushes, pulls)))| (*pushes == 0) != (*pulls == 0))
+		.map(|(channel_id, (channel, (max_pushes, max_pulls)))| {
+			PreflightFinding::UnbalancedChannel {
+				channel_id,
+				channel_name: channel.name.clone(),
+				max_pushes,
+				max_pulls,
+			}
+		})
+}
+
+/// Checks the degrees of the zero constraints of the tables that are not empty.
+pub(super) fn check_constraint_degrees<'a, F: TowerField>(
+	cs: &'a ConstraintSystem<F>,
+	table_sizes: &'a [usize],
+) -> impl Iterator<Item = PreflightFinding> + 'a {
+	let max = cs.max_degree();
+	cs.tables
+		.iter()
+		.zip(table_sizes)
+		.filter(|(_, size)| **size > 0)
+		.flat_map(move |(table, _)| {
+			table
+				.partitions
+				.values()
+				.flat_map(|partition| &partition.zero_constraints)
+				.filter_map(move |constraint| {
+					let degree = constraint.expr.degree();
+					(degree > max).then(|| PreflightFinding::ConstraintDegree {
+						table_id: table.id,
+						table_name: table.name.clone(),
+						constraint_name: constraint.name.clone(),
+						degree,
+						max,
+					})
+				})
+		})
+}
@@ -0,0 +1,408 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Repacking of committed columns to the smallest tower level that their values can take.
+//!
+//! Columns are often declared at a wider field than necessary, for example a [`B128`] column that
+//! is pulled from a channel into which only bytes are pushed. The channel only balances if every
+//! pulled value is one of the pushed values, so the column holds bytes in any valid witness and
+//! can be committed as a [`B8`] column instead, which is 16 times less commitment data.
+//!
+//! The pass propagates the tower levels of the pushed values through the channels until a fixed
+//! point is reached. A committed column is narrowed to the highest tower level of the values
+//! pushed at its position of a channel that it is pulled from without selectors. Linear
+//! combinations of narrowed columns narrow along with them.
+//!
+//! The analysis only depends on the constraint system, so that the repacked system and its digest
+//! are the same for every statement. The boundaries of a statement may push values of any tower
+//! level, so columns pulled from the channels that boundaries push into are never narrowed.
+//!
+//! Zero constraints and computed columns refer to columns by index, so they remain valid when the
+//! columns they refer to are repacked. Columns that are the source of packed, shifted, projected
+//! or exponentiation columns are not repacked, because those depend on the tower level of the
+//! source.
+//!
+//! [`B128`]: super::B128
+//! [`B8`]: super::B8
+
+use std::fmt::{self, Display};
+
+use binius_core::constraint_system::channel::{ChannelId, FlushDirection};
+use binius_field::TowerField;
+use binius_math::LinearNormalForm;
+
+use super::{
+	ConstraintSystem,
+	column::{ColumnDef, ColumnId},
+	table::{Table, TableId},
+};
+
+/// A committed column that is repacked to a smaller tower level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepackedColumn {
+	pub column_id: ColumnId,
+	pub table_name: String,
+	pub column_name: String,
+	/// The declared tower level of the column.
+	pub from_tower_level: usize,
+	/// The smallest tower level that the values of the column can take.
+	pub to_tower_level: usize,
+	pub log_values_per_row: usize,
+}
+
+impl RepackedColumn {
+	pub fn table_id(&self) -> TableId {
+		self.column_id.table_id
+	}
+
+	/// The number of committed bits that the repacking saves per table row.
+	pub fn bits_saved_per_row(&self) -> usize {
+		((1 << self.from_tower_level) - (1 << self.to_tower_level)) << self.log_values_per_row
+	}
+}
+
+/// The columns found by [`ConstraintSystem::repack_columns`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepackReport {
+	pub columns: Vec<RepackedColumn>,
+}
+
+impl RepackReport {
+	/// The number of committed bits that the repacking saves per row of a table.
+	pub fn bits_saved_per_row(&self, table_id: TableId) -> usize {
+		self.columns
+			.iter()
+			.filter(|column| column.table_id() == table_id)
+			.map(RepackedColumn::bits_saved_per_row)
+			.sum()
+	}
+
+	/// The number of committed bits that the repacking saves for the given table sizes.
+	pub fn bits_saved(&self, table_sizes: &[usize]) -> usize {
+		self.columns
+			.iter()
+			.map(|column| column.bits_saved_per_row() * table_sizes[column.table_id()])
+			.sum()
+	}
+}
+
+impl Display for RepackReport {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for column in &self.columns {
+			writeln!(
+				f,
+				"{}::{}: B{} -> B{}, saves {} bits per row",
+				column.table_name,
+				column.column_name,
+				1 << column.from_tower_level,
+				1 << column.to_tower_level,
+				column.bits_saved_per_row()
+			)?;
+		}
+		Ok(())
+	}
+}
+
+/// Finds the committed columns that can be repacked to a smaller tower level.
+pub(super) fn find_repacked_columns<F: TowerField>(
+	cs: &ConstraintSystem<F>,
+	boundary_channels: &[ChannelId],
+) -> RepackReport {
+	let mut levels = cs
+		.tables
+		.iter()
+		.map(|table| {
+			table
+				.columns
+				.iter()
+				.map(|column| column.shape.tower_height)
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>();
+	let repackable = cs.tables.iter().map(repackable_columns).collect::<Vec<_>>();
+
+	loop {
+		// The highest tower level of the values pushed at every position of every channel.
+		let mut pushed_levels = vec![Vec::<usize>::new(); cs.channels.len()];
+		let mut push = |channel_id: usize, position: usize, level: usize| {
+			let channel_levels = &mut pushed_levels[channel_id];
+			if channel_levels.len() <= position {
+				channel_levels.resize(position + 1, 0);
+			}
+			channel_levels[position] = channel_levels[position].max(level);
+		};
+		for (table, table_levels) in cs.tables.iter().zip(&levels) {
+			for partition in table.partitions.values() {
+				for flush in &partition.flushes {
+					if flush.direction == FlushDirection::Push {
+						for (position, col) in flush.columns.iter().enumerate() {
+							let level = column_level(table, table_levels, col.table_index.0);
+							push(flush.channel_id, position, level);
+						}
+					}
+				}
+			}
+		}
+		let mut changed = false;
+		for (table_index, table) in cs.tables.iter().enumerate() {
+			for partition in table.partitions.values() {
+				for flush in &partition.flushes {
+					if flush.direction != FlushDirection::Pull
+						|| !flush.selectors.is_empty()
+						|| boundary_channels.contains(&flush.channel_id)
+					{
+						continue;
+					}
+					for (position, col) in flush.columns.iter().enumerate() {
+						let index = col.table_index.0;
+						let Some(&pushed_level) = pushed_levels[flush.channel_id].get(position)
+						else {
+							continue;
+						};
+						if repackable[table_index][index]
+							&& pushed_level < levels[table_index][index]
+						{
+							levels[table_index][index] = pushed_level;
+							changed = true;
+						}
+					}
+				}
+			}
+		}
+		if !changed {
+			break;
+		}
+	}
+
+	let columns = cs
+		.tables
+		.iter()
+		.zip(levels)
+		.flat_map(|(table, table_levels)| {
+			table
+				.columns
+				.iter()
+				.zip(table_levels)
+				.filter(|(column, level)| *level < column.shape.tower_height)
+				.map(|(column, level)| RepackedColumn {
+					column_id: column.id,
+					table_name: table.name.clone(),
+					column_name: column.name.clone(),
+					from_tower_level: column.shape.tower_height,
+					to_tower_level: level,
+					log_values_per_row: column.shape.log_values_per_row,
+				})
+		})
+		.collect();
+	RepackReport { columns }
+}
+
+/// Returns for every column of the table whether it is a committed column that can be repacked.
+fn repackable_columns<F: TowerField>(table: &Table<F>) -> Vec<bool> {
+	let mut repackable = table
+		.columns
+		.iter()
+		.map(|column| matches!(column.col, ColumnDef::Committed { .. }))
+		.collect::<Vec<_>>();
+	for column in &table.columns {
+		let sources = match &column.col {
+			ColumnDef::Selected { col, .. }
+			| ColumnDef::Projected { col, .. }
+			| ColumnDef::ZeroPadded { col, .. }
+			| ColumnDef::Shifted { col, .. }
+			| ColumnDef::Packed { col, .. } => vec![*col],
+			ColumnDef::StaticExp { bit_cols, .. } => bit_cols.clone(),
+			ColumnDef::DynamicExp { bit_cols, base, .. } => {
+				bit_cols.iter().chain([base]).copied().collect()
+			}
+			ColumnDef::Committed { .. }
+			| ColumnDef::Computed { .. }
+			| ColumnDef::Constant { .. }
+			| ColumnDef::StructuredDynSize(_)
+			| ColumnDef::StructuredFixedSize { .. } => vec![],
+		};
+		for source in sources {
+			repackable[source.table_index.0] = false;
+		}
+	}
+	repackable
+}
+
+/// Returns the tower level of the values of a column, given the levels of the committed columns.
+fn column_level<F: TowerField>(table: &Table<F>, levels: &[usize], index: usize) -> usize {
+	let column = &table.columns[index];
+	let level = match &column.col {
+		ColumnDef::Committed { .. } => levels[index],
+		ColumnDef::Computed { cols, expr } => match expr.linear_normal_form() {
+			Ok(LinearNormalForm {
+				constant,
+				var_coeffs,
+			}) => cols
+				.iter()
+				.zip(var_coeffs)
+				.filter(|(_, coeff)| *coeff != F::ZERO)
+				.map(|(col, coeff)| {
+					column_level(table, levels, col.table_index.0).max(coeff.min_tower_level())
+				})
+				.fold(constant.min_tower_level(), usize::max),
+			Err(_) => column.shape.tower_height,
+		},
+		_ => column.shape.tower_height,
+	};
+	level.min(column.shape.tower_height)
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_compute::cpu::alloc::CpuComputeAllocator;
+	use binius_core::constraint_system::channel::Boundary;
+	use binius_field::{Field, arch::OptimalUnderlier128b, as_packed_field::PackedType};
+
+	use super::*;
+	use crate::builder::{
+		B1, B8, B16, B32, B128, FlushOpts, WitnessIndex,
+		error::Error,
+		test_utils::{ClosureFiller, validate_system_witness},
+	};
+
+	#[test]
+	fn test_repack_columns() {
+		let mut cs = ConstraintSystem::new();
+		let bytes = cs.add_channel("bytes");
+		let words = cs.add_channel("words");
+		let wide = cs.add_channel("wide");
+
+		let mut producer = cs.add_table("producer");
+		let producer_id = producer.id();
+		let byte = producer.add_committed::<B8, 1>("byte");
+		producer.push(bytes, [byte]);
+
+		// A byte pulled into a B128 column, which is pushed to another channel shifted by a byte.
+		let mut consumer = cs.add_table("consumer");
+		let consumer_id = consumer.id();
+		let x = consumer.add_committed::<B128, 1>("x");
+		consumer.pull(bytes, [x]);
+		let shifted = consumer.add_computed("shifted", x * B128::new(1 << 8));
+		consumer.push(words, [shifted]);
+		// Selected pulls do not bound the unselected rows.
+		let z = consumer.add_committed::<B128, 1>("z");
+		let selector = consumer.add_committed::<B1, 1>("selector");
+		consumer.pull_with_opts(
+			bytes,
+			[z],
+			FlushOpts {
+				multiplicity: 1,
+				selectors: vec![selector],
+			},
+		);
+
+		let mut words_consumer = cs.add_table("words_consumer");
+		let words_consumer_id = words_consumer.id();
+		let v = words_consumer.add_committed::<B32, 1>("v");
+		words_consumer.pull(words, [v]);
+
+		// The boundaries push values of any tower level.
+		let mut wide_consumer = cs.add_table("wide_consumer");
+		let wide_consumer_id = wide_consumer.id();
+		let u = wide_consumer.add_committed::<B128, 1>("u");
+		wide_consumer.pull(wide, [u]);
+		let wide_value = 0x0123_4567_89ab_cdef_0123_4567_89ab_cdef_u128;
+		let boundaries = vec![Boundary {
+			values: vec![B128::new(wide_value)],
+			channel_id: wide,
+			direction: FlushDirection::Push,
+			multiplicity: 1,
+		}];
+
+		let report = cs.repack_columns(&[wide]);
+		assert_eq!(
+			report
+				.columns
+				.iter()
+				.map(|column| (
+					column.column_name.as_str(),
+					column.from_tower_level,
+					column.to_tower_level
+				))
+				.collect::<Vec<_>>(),
+			[("x", 7, 3), ("v", 5, 4)]
+		);
+		assert_eq!(report.bits_saved_per_row(consumer_id), 120);
+		assert_eq!(report.bits_saved_per_row(words_consumer_id), 16);
+		assert_eq!(report.bits_saved(&[0, 4, 4, 0]), 4 * 120 + 4 * 16);
+		assert!(cs.find_repacked_columns(&[wide]).columns.is_empty());
+
+		// The handles of the repacked columns are re-typed at their repacked tower level.
+		let x_repacked = cs.repacked_col::<B8, _, 1>(x);
+		let v_repacked = cs.repacked_col::<B16, _, 1>(v);
+
+		let xs = [0x12u8, 0x34, 0x56, 0x78];
+		let zs = [0x9au8, 0xbc, 0xde];
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness =
+			WitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(&cs, &allocator);
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(producer_id, |rows: &[u8], segment| {
+					segment.get_mut_as::<u8, _, 1>(byte)?[..rows.len()].copy_from_slice(rows);
+					Ok(())
+				}),
+				&[xs.as_slice(), zs.as_slice()].concat(),
+			)
+			.unwrap();
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(consumer_id, |rows: &[(u8, Option<u8>)], segment| {
+					// The repacked column is filled with bytes, and its stale handle is rejected.
+					assert!(matches!(
+						segment.get_mut_as::<u8, _, 1>(x),
+						Err(Error::ColumnTowerLevelMismatch { .. })
+					));
+					let mut x_col = segment.get_mut_as::<u8, _, 1>(x_repacked)?;
+					let mut shifted_col = segment.get_mut_as::<u128, _, 1>(shifted)?;
+					let mut z_col = segment.get_mut_as::<u128, _, 1>(z)?;
+					let mut selector_col = segment.get_mut(selector)?;
+					for (i, &(x, z)) in rows.iter().enumerate() {
+						x_col[i] = x;
+						shifted_col[i] = (x as u128) << 8;
+						if let Some(z) = z {
+							z_col[i] = z as u128;
+							binius_field::packed::set_packed_slice(&mut selector_col, i, B1::ONE);
+						}
+					}
+					Ok(())
+				}),
+				&[
+					(xs[0], Some(zs[0])),
+					(xs[1], Some(zs[1])),
+					(xs[2], Some(zs[2])),
+					(xs[3], None),
+				],
+			)
+			.unwrap();
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(words_consumer_id, |rows: &[u8], segment| {
+					let mut v_col = segment.get_mut_as::<u16, _, 1>(v_repacked)?;
+					for (i, &x) in rows.iter().enumerate() {
+						v_col[i] = (x as u16) << 8;
+					}
+					Ok(())
+				}),
+				&xs,
+			)
+			.unwrap();
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(wide_consumer_id, |rows: &[u128], segment| {
+					segment.get_mut_as::<u128, _, 1>(u)?[..rows.len()].copy_from_slice(rows);
+					Ok(())
+				}),
+				&[wide_value],
+			)
+			.unwrap();
+
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, boundaries);
+	}
+}
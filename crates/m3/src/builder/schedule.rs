@@ -0,0 +1,552 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Ordering of the populate calls of the gadgets in a table by their column dependencies.
+//!
+//! A table filler usually fills the input columns of a table from its events and then calls the
+//! `populate` methods of the gadgets in the table, each of which reads some columns and writes
+//! others. The calls must be ordered so that every gadget runs after the gadgets that write the
+//! columns it reads, which is easy to get wrong when gadgets are composed. A [`ScheduledFiller`]
+//! instead takes the populate calls as [`PopulateStep`]s that declare the columns they read and
+//! write, orders them by their dependencies, and checks that every column of the table is filled
+//! before the witness is proven. Steps that do not depend on each other run in parallel.
+
+use std::collections::BTreeMap;
+
+use binius_field::{PackedField, TowerField};
+
+use super::{
+	ConstraintSystem,
+	column::{ColumnDef, ColumnId},
+	error::Error,
+	table::TableId,
+	witness::{TableFiller, TableWitnessSegment},
+};
+
+/// The columns that a gadget reads and writes when it populates a table witness.
+pub trait PopulateColumns {
+	/// The columns that must be populated before the gadget populates the table.
+	fn reads(&self) -> Vec<ColumnId>;
+
+	/// The columns that the gadget populates.
+	fn writes(&self) -> Vec<ColumnId>;
+}
+
+/// A populate call that declares the columns it reads and writes.
+///
+/// When the step runs in parallel with other steps, the witness segment passed to it only holds
+/// the columns that it declares.
+#[allow(clippy::type_complexity)]
+pub struct PopulateStep<'a, P>
+where
+	P: PackedField,
+	P::Scalar: TowerField,
+{
+	name: String,
+	reads: Vec<ColumnId>,
+	writes: Vec<ColumnId>,
+	populate: Box<dyn Fn(&mut TableWitnessSegment<P>) -> anyhow::Result<()> + Sync + 'a>,
+}
+
+impl<'a, P: PackedField<Scalar: TowerField>> PopulateStep<'a, P> {
+	pub fn new(
+		name: impl ToString,
+		reads: impl IntoIterator<Item = ColumnId>,
+		writes: impl IntoIterator<Item = ColumnId>,
+		populate: impl Fn(&mut TableWitnessSegment<P>) -> anyhow::Result<()> + Sync + 'a,
+	) -> Self {
+		Self {
+			name: name.to_string(),
+			reads: reads.into_iter().collect(),
+			writes: writes.into_iter().collect(),
+			populate: Box::new(populate),
+		}
+	}
+
+	/// Creates a step for a gadget with the columns that the gadget declares.
+	pub fn for_gadget(
+		name: impl ToString,
+		gadget: &impl PopulateColumns,
+		populate: impl Fn(&mut TableWitnessSegment<P>) -> anyhow::Result<()> + Sync + 'a,
+	) -> Self {
+		Self::new(name, gadget.reads(), gadget.writes(), populate)
+	}
+
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+}
+
+/// A [`TableFiller`] that fills the input columns of a table from the events and then runs the
+/// populate steps in the order of their dependencies.
+#[allow(clippy::type_complexity)]
+pub struct ScheduledFiller<'a, P, Event>
+where
+	P: PackedField,
+	P::Scalar: TowerField,
+{
+	table_id: TableId,
+	inputs: Vec<ColumnId>,
+	fill_inputs: Box<
+		dyn for<'b> Fn(&'b [Event], &'b mut TableWitnessSegment<P>) -> anyhow::Result<()>
+			+ Sync
+			+ 'a,
+	>,
+	steps: Vec<PopulateStep<'a, P>>,
+	/// The stages of the schedule, see [`Self::schedule`].
+	stages: Vec<Vec<usize>>,
+	log_rows_per_event: usize,
+}
+
+impl<'a, P: PackedField<Scalar: TowerField>, Event> ScheduledFiller<'a, P, Event> {
+	/// Creates a filler whose `fill_inputs` closure fills the `inputs` columns from the events,
+	/// and which then runs the populate steps, which may be given in any order.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::ColumnPopulatedTwice`] if several steps write the same column
+	/// * [`Error::PopulateCycle`] if the steps cannot be ordered by their dependencies
+	pub fn new(
+		table_id: TableId,
+		inputs: impl IntoIterator<Item = ColumnId>,
+		fill_inputs: impl for<'b> Fn(&'b [Event], &'b mut TableWitnessSegment<P>) -> anyhow::Result<()>
+		+ Sync
+		+ 'a,
+		steps: impl IntoIterator<Item = PopulateStep<'a, P>>,
+	) -> Result<Self, Error> {
+		let steps = steps.into_iter().collect::<Vec<_>>();
+		let stages = schedule(table_id, &steps)?;
+		Ok(Self {
+			table_id,
+			inputs: inputs.into_iter().collect(),
+			fill_inputs: Box::new(fill_inputs),
+			steps,
+			stages,
+			log_rows_per_event: 0,
+		})
+	}
+
+	/// Makes every event span `2^log_rows_per_event` consecutive rows of the table.
+	///
+	/// See [`TableFiller::log_rows_per_event`].
+	pub fn with_log_rows_per_event(self, log_rows_per_event: usize) -> Self {
+		Self {
+			log_rows_per_event,
+			..self
+		}
+	}
+
+	/// Returns the stages of the schedule, each of which holds the indices of steps that only
+	/// depend on the steps of earlier stages, in the order they were given.
+	pub fn schedule(&self) -> &[Vec<usize>] {
+		&self.stages
+	}
+
+	/// Checks that the inputs and steps fill every column of the table that has witness data of
+	/// its own.
+	///
+	/// Constant columns are filled by [`WitnessIndex::fill_constant_cols`], and packed columns
+	/// share the witness data of the column they pack.
+	///
+	/// [`WitnessIndex::fill_constant_cols`]: super::WitnessIndex::fill_constant_cols
+	pub fn check<F: TowerField>(&self, cs: &ConstraintSystem<F>) -> Result<(), Error> {
+		let table = cs.tables.get(self.table_id).ok_or(Error::MissingTable {
+			table_id: self.table_id,
+		})?;
+		let mut filled = vec![false; table.columns.len()];
+		for column in self
+			.inputs
+			.iter()
+			.chain(self.steps.iter().flat_map(|step| &step.writes))
+		{
+			if table.has_columns_of(column.table_id) {
+				filled[column.table_index.0] = true;
+			}
+		}
+
+		let columns = table
+			.columns
+			.iter()
+			.filter(|column| {
+				let mut index = column.id.table_index.0;
+				// Packed columns are filled along with the column they pack.
+				while let ColumnDef::Packed { col, .. } = &table.columns[index].col {
+					index = col.table_index.0;
+				}
+				let needs_fill = !matches!(
+					table.columns[index].col,
+					ColumnDef::Constant { .. }
+						| ColumnDef::StructuredDynSize(_)
+						| ColumnDef::StructuredFixedSize { .. }
+				);
+				needs_fill && !filled[index]
+			})
+			.map(|column| column.name.clone())
+			.collect::<Vec<_>>();
+		if !columns.is_empty() {
+			return Err(Error::UnfilledColumns {
+				table_id: self.table_id,
+				columns,
+			});
+		}
+		Ok(())
+	}
+}
+
+/// Orders the populate steps by their dependencies into stages, see
+/// [`ScheduledFiller::schedule`].
+fn schedule<P: PackedField<Scalar: TowerField>>(
+	table_id: TableId,
+	steps: &[PopulateStep<'_, P>],
+) -> Result<Vec<Vec<usize>>, Error> {
+	let mut writers = BTreeMap::new();
+	for (i, step) in steps.iter().enumerate() {
+		for &column in &step.writes {
+			if let Some(first) = writers.insert(column, i)
+				&& first != i
+			{
+				return Err(Error::ColumnPopulatedTwice {
+					column,
+					first: steps[first].name.clone(),
+					second: step.name.clone(),
+				});
+			}
+		}
+	}
+
+	let dependencies = steps
+		.iter()
+		.enumerate()
+		.map(|(i, step)| {
+			let mut dependencies = step
+				.reads
+				.iter()
+				.filter_map(|column| writers.get(column).copied())
+				.filter(|&writer| writer != i)
+				.collect::<Vec<_>>();
+			dependencies.sort_unstable();
+			dependencies.dedup();
+			dependencies
+		})
+		.collect::<Vec<_>>();
+
+	let mut stage_of = vec![None; steps.len()];
+	let mut stages = Vec::new();
+	let mut n_scheduled = 0;
+	while n_scheduled < steps.len() {
+		let stage = (0..steps.len())
+			.filter(|&i| {
+				stage_of[i].is_none()
+					&& dependencies[i]
+						.iter()
+						.all(|&dependency| stage_of[dependency].is_some())
+			})
+			.collect::<Vec<_>>();
+		if stage.is_empty() {
+			return Err(Error::PopulateCycle {
+				table_id,
+				steps: (0..steps.len())
+					.filter(|&i| stage_of[i].is_none())
+					.map(|i| steps[i].name.clone())
+					.collect(),
+			});
+		}
+		for &i in &stage {
+			stage_of[i] = Some(stages.len());
+		}
+		n_scheduled += stage.len();
+		stages.push(stage);
+	}
+	Ok(stages)
+}
+
+impl<P: PackedField<Scalar: TowerField>, Event: Clone> TableFiller<P>
+	for ScheduledFiller<'_, P, Event>
+{
+	type Event = Event;
+
+	fn id(&self) -> TableId {
+		self.table_id
+	}
+
+	fn log_rows_per_event(&self) -> usize {
+		self.log_rows_per_event
+	}
+
+	fn fill(&self, rows: &[Event], witness: &mut TableWitnessSegment<P>) -> anyhow::Result<()> {
+		(*self.fill_inputs)(rows, witness)?;
+		// The steps read their inputs from the columns, so cells written through the row-major
+		// rows are scattered before every stage.
+		witness.scatter_rows()?;
+		for stage in &self.stages {
+			if let [i] = stage[..] {
+				(*self.steps[i].populate)(witness)?;
+				witness.scatter_rows()?;
+				continue;
+			}
+
+			// The steps of a stage do not read the columns that the other steps of the stage
+			// write, so they can run in parallel on their own columns.
+			let parts = stage
+				.iter()
+				.map(|&i| (self.steps[i].reads.clone(), self.steps[i].writes.clone()))
+				.collect::<Vec<_>>();
+			witness.par_for_each_part(&parts, |part, segment| {
+				(*self.steps[stage[part]].populate)(segment)
+			})?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use assert_matches::assert_matches;
+	use binius_compute::cpu::alloc::CpuComputeAllocator;
+	use binius_field::{arch::OptimalUnderlier128b, as_packed_field::PackedType};
+	use rand::{Rng, SeedableRng, rngs::StdRng};
+
+	use super::*;
+	use crate::{
+		builder::{B1, B128, WitnessIndex, WitnessLayout, test_utils::validate_system_witness},
+		gadgets::add::{U32Add, U32AddFlags},
+	};
+
+	type P = PackedType<OptimalUnderlier128b, B128>;
+
+	#[test]
+	fn test_scheduled_filler() {
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("sums");
+		let table_id = table.id();
+		let a = table.add_committed::<B1, 32>("a");
+		let b = table.add_committed::<B1, 32>("b");
+		let c = table.add_committed::<B1, 32>("c");
+		let add_1 = U32Add::new(&mut table, a, b, U32AddFlags::default());
+		let add_2 = U32Add::new(&mut table, add_1.zout, c, U32AddFlags::default());
+		let add_3 = U32Add::new(&mut table, a, c, U32AddFlags::default());
+
+		let new_filler = |steps: Vec<_>| {
+			ScheduledFiller::<P, (u32, u32, u32)>::new(
+				table_id,
+				[a.id(), b.id(), c.id()],
+				|rows, segment| {
+					let mut a_col = segment.get_mut_as::<u32, _, 32>(a)?;
+					let mut b_col = segment.get_mut_as::<u32, _, 32>(b)?;
+					let mut c_col = segment.get_mut_as::<u32, _, 32>(c)?;
+					for (i, &(a, b, c)) in rows.iter().enumerate() {
+						a_col[i] = a;
+						b_col[i] = b;
+						c_col[i] = c;
+					}
+					Ok(())
+				},
+				steps,
+			)
+		};
+		let add_1_step =
+			|| PopulateStep::for_gadget("add_1", &add_1, |segment| add_1.populate(segment));
+		let add_2_step =
+			|| PopulateStep::for_gadget("add_2", &add_2, |segment| add_2.populate(segment));
+		let add_3_step =
+			|| PopulateStep::for_gadget("add_3", &add_3, |segment| add_3.populate(segment));
+
+		let filler = new_filler(vec![add_2_step(), add_3_step()]).unwrap();
+		assert_matches!(
+			filler.check(&cs),
+			Err(Error::UnfilledColumns { columns, .. }) if columns.contains(&"cout".to_string())
+		);
+
+		// The second addition depends on the first one, so it is scheduled after it although it
+		// is given first. The third addition is independent of both, so it runs in parallel with
+		// the first one.
+		let filler = new_filler(vec![add_2_step(), add_1_step(), add_3_step()]).unwrap();
+		assert_eq!(filler.schedule(), [vec![1, 2], vec![0]]);
+		filler.check(&cs).unwrap();
+
+		let cyclic = new_filler(vec![
+			PopulateStep::new("first", [add_1.zout.id()], [add_2.zout.id()], |_| Ok(())),
+			PopulateStep::new("second", [add_2.zout.id()], [add_1.zout.id()], |_| Ok(())),
+		]);
+		assert_matches!(cyclic.err(), Some(Error::PopulateCycle { steps, .. }) if steps.len() == 2);
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let rows = (0..27)
+			.map(|_| rng.random::<(u32, u32, u32)>())
+			.collect::<Vec<_>>();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::<P>::new(&cs, &allocator);
+		witness.fill_table_parallel(&filler, &rows).unwrap();
+
+		{
+			let table_witness = witness.get_table(table_id).unwrap();
+			let segment = table_witness.full_segment();
+			let zout_2 = segment.get_as::<u32, _, 32>(add_2.zout).unwrap();
+			let zout_3 = segment.get_as::<u32, _, 32>(add_3.zout).unwrap();
+			for (i, &(a, b, c)) in rows.iter().enumerate() {
+				assert_eq!(zout_2[i], a.wrapping_add(b).wrapping_add(c));
+				assert_eq!(zout_3[i], a.wrapping_add(c));
+			}
+		}
+
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
+	}
+
+	#[test]
+	fn test_scheduled_filler_rows_per_event() {
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("pairs");
+		let table_id = table.id();
+		let a = table.add_committed::<B1, 32>("a");
+		let b = table.add_committed::<B1, 32>("b");
+		let add = U32Add::new(&mut table, a, b, U32AddFlags::default());
+
+		// Every event spans two rows, which hold the sums of the event words and of their
+		// complements.
+		let filler = ScheduledFiller::<P, (u32, u32)>::new(
+			table_id,
+			[a.id(), b.id()],
+			|events, segment| {
+				let mut a_col = segment.get_mut_as::<u32, _, 32>(a)?;
+				let mut b_col = segment.get_mut_as::<u32, _, 32>(b)?;
+				for (i, &(a, b)) in events.iter().enumerate() {
+					let rows = segment.event_rows(i);
+					a_col[rows.start] = a;
+					b_col[rows.start] = b;
+					a_col[rows.start + 1] = !a;
+					b_col[rows.start + 1] = !b;
+				}
+				Ok(())
+			},
+			[PopulateStep::for_gadget("add", &add, |segment| {
+				add.populate(segment)
+			})],
+		)
+		.unwrap()
+		.with_log_rows_per_event(1);
+		filler.check(&cs).unwrap();
+		assert_eq!(TableFiller::log_rows_per_event(&filler), 1);
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let events = (0..13)
+			.map(|_| rng.random::<(u32, u32)>())
+			.collect::<Vec<_>>();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::<P>::new(&cs, &allocator);
+		witness.fill_table_parallel(&filler, &events).unwrap();
+
+		{
+			let table_witness = witness.get_table(table_id).unwrap();
+			let segment = table_witness.full_segment();
+			let zout = segment.get_as::<u32, _, 32>(add.zout).unwrap();
+			for (i, &(a, b)) in events.iter().enumerate() {
+				assert_eq!(zout[2 * i], a.wrapping_add(b));
+				assert_eq!(zout[2 * i + 1], (!a).wrapping_add(!b));
+			}
+		}
+
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
+	}
+
+	#[test]
+	fn test_scheduled_filler_row_major_inputs() {
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("row major sums");
+		let table_id = table.id();
+		table.set_witness_layout(WitnessLayout::RowMajor);
+		let a = table.add_committed::<B1, 32>("a");
+		let b = table.add_committed::<B1, 32>("b");
+		let add = U32Add::new(&mut table, a, b, U32AddFlags::default());
+
+		let filler = ScheduledFiller::<P, (u32, u32)>::new(
+			table_id,
+			[a.id(), b.id()],
+			|rows, segment| {
+				let mut row_major = segment.rows_mut()?;
+				for (i, &(a_val, b_val)) in rows.iter().enumerate() {
+					row_major.set(i, a, a_val)?;
+					row_major.set(i, b, b_val)?;
+				}
+				Ok(())
+			},
+			[PopulateStep::for_gadget("add", &add, |segment| {
+				add.populate(segment)
+			})],
+		)
+		.unwrap();
+		filler.check(&cs).unwrap();
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let rows = (0..100)
+			.map(|_| rng.random::<(u32, u32)>())
+			.collect::<Vec<_>>();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::<P>::new(&cs, &allocator);
+		witness.fill_table_parallel(&filler, &rows).unwrap();
+
+		{
+			let table_witness = witness.get_table(table_id).unwrap();
+			let segment = table_witness.full_segment();
+			let zout = segment.get_as::<u32, _, 32>(add.zout).unwrap();
+			for (i, &(a, b)) in rows.iter().enumerate() {
+				assert_eq!(zout[i], a.wrapping_add(b));
+			}
+		}
+
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
+	}
+
+	#[test]
+	fn test_parallel_steps_only_access_declared_columns() {
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("undeclared");
+		let table_id = table.id();
+		let a = table.add_committed::<B1, 32>("a");
+		let b = table.add_committed::<B1, 32>("b");
+		let c = table.add_committed::<B1, 32>("c");
+
+		let filler = ScheduledFiller::<P, u32>::new(
+			table_id,
+			[a.id()],
+			|rows, segment| {
+				let mut a_col = segment.get_mut_as::<u32, _, 32>(a)?;
+				a_col[..rows.len()].copy_from_slice(rows);
+				Ok(())
+			},
+			[
+				PopulateStep::new("b", [a.id()], [b.id()], |segment| {
+					let a_col = segment.get_as::<u32, _, 32>(a)?;
+					let mut b_col = segment.get_mut_as::<u32, _, 32>(b)?;
+					for (b, &a) in b_col.iter_mut().zip(a_col.iter()) {
+						*b = !a;
+					}
+					Ok(())
+				}),
+				// Reads `b` without declaring it.
+				PopulateStep::new("c", [a.id()], [c.id()], |segment| {
+					segment.get_as::<u32, _, 32>(b)?;
+					Ok(())
+				}),
+			],
+		)
+		.unwrap();
+		assert_eq!(filler.schedule(), [vec![0, 1]]);
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::<P>::new(&cs, &allocator);
+		let err = witness
+			.fill_table_sequential(&filler, &[1, 2, 3, 4])
+			.unwrap_err();
+		assert_matches!(
+			err,
+			Error::TableFill { source, .. }
+				if matches!(source.downcast_ref(), Some(Error::MissingColumn(id)) if *id == b.id())
+		);
+	}
+}
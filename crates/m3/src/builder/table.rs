@@ -1,6 +1,6 @@
 // Copyright 2025 Irreducible Inc.
 
-use std::{ops::Index, sync::Arc};
+use std::{cmp::Ordering, ops::Index, sync::Arc};
 
 pub use binius_core::constraint_system::TableId;
 use binius_core::{
@@ -12,20 +12,21 @@ use binius_core::{
 	transparent::MultilinearExtensionTransparent,
 };
 use binius_field::{
-	ExtensionField, TowerField,
+	BinaryField, ExtensionField, TowerField,
 	arch::OptimalUnderlier,
 	as_packed_field::{PackScalar, PackedType},
 	packed::pack_slice,
 };
-use binius_math::ArithCircuit;
+use binius_math::{ArithCircuit, ArithExpr};
 use binius_utils::{
 	checked_arithmetics::{checked_log_2, log2_ceil_usize, log2_strict_usize},
 	sparse_index::SparseIndex,
 };
 
 use super::{
-	B1, ColumnIndex, ColumnPartitionIndex, FlushOpts,
-	channel::Flush,
+	B1, B8, B32, ColumnIndex, ColumnPartitionIndex, FlushOpts,
+	boolean::BoolExpr,
+	channel::{BYTES_PER_CHANNEL_VALUE, ByteChannel, Channel, Flush, PackedBytes},
 	column::{Col, ColumnDef, ColumnId, ColumnInfo, ColumnShape},
 	expr::{Expr, ZeroConstraint},
 	stat::TableStat,
@@ -38,14 +39,32 @@ use super::{
 pub struct TableBuilder<'a, F: TowerField = B128> {
 	namespace: Option<String>,
 	table: &'a mut Table<F>,
+	/// The channels of the constraint system the table belongs to, for the columns that add
+	/// channels of their own.
+	channels: Option<&'a mut Vec<Channel>>,
 }
 
 impl<'a, F: TowerField> TableBuilder<'a, F> {
 	/// Returns a new `TableBuilder` for the given table.
+	///
+	/// A builder that is not created by [`ConstraintSystem::add_table`] cannot add public
+	/// columns.
+	///
+	/// [`ConstraintSystem::add_table`]: super::ConstraintSystem::add_table
 	pub fn new(table: &'a mut Table<F>) -> Self {
 		Self {
 			namespace: None,
 			table,
+			channels: None,
+		}
+	}
+
+	/// Returns a new `TableBuilder` for a table of a constraint system with the given channels.
+	pub(super) fn with_channels(table: &'a mut Table<F>, channels: &'a mut Vec<Channel>) -> Self {
+		Self {
+			namespace: None,
+			table,
+			channels: Some(channels),
 		}
 	}
 
@@ -73,6 +92,14 @@ impl<'a, F: TowerField> TableBuilder<'a, F> {
 		self.table.table_size_spec = TableSizeSpec::Fixed { log_size };
 	}
 
+	/// Sets the physical layout that the table's witness segments are filled in.
+	///
+	/// See [`WitnessLayout`]. The layout only affects witness generation, not the constraint
+	/// system.
+	pub fn set_witness_layout(&mut self, layout: WitnessLayout) {
+		self.table.witness_layout = layout;
+	}
+
 	/// Returns a new `TableBuilder` with the specified namespace.
 	///
 	/// A namespace is a prefix that will be prepended to all column names and zero constraints
@@ -105,6 +132,7 @@ impl<'a, F: TowerField> TableBuilder<'a, F> {
 		TableBuilder {
 			namespace: Some(self.namespaced_name(namespace)),
 			table: self.table,
+			channels: self.channels.as_deref_mut(),
 		}
 	}
 
@@ -165,6 +193,42 @@ impl<'a, F: TowerField> TableBuilder<'a, F> {
 		)
 	}
 
+	/// Adds a derived column that rotates every cell of a vertically-packed bit column to the left.
+	///
+	/// Each cell of `V` bits is rotated by `offset` bits as a `V`-bit integer, so a rotation to the
+	/// right by `r` is a rotation to the left by `V - r`. The column is a virtual shift of `col`,
+	/// which costs no commitment and no constraints. Its witness can be filled with
+	/// [`TableWitnessSegment::fill_shifted`].
+	///
+	/// ## Preconditions
+	///
+	/// * `offset` must be in the range `1..V`.
+	///
+	/// [`TableWitnessSegment::fill_shifted`]: super::TableWitnessSegment::fill_shifted
+	pub fn add_rotated<const V: usize>(
+		&mut self,
+		name: impl ToString,
+		col: Col<B1, V>,
+		offset: usize,
+	) -> Col<B1, V>
+	where
+		F: ExtensionField<B1>,
+	{
+		assert!(0 < offset && offset < V, "rotation offset must be in the range 1..V");
+		self.add_shifted(name, col, log2_strict_usize(V), offset, ShiftVariant::CircularLeft)
+	}
+
+	/// Adds a derived column that packs groups of consecutive values of a column into elements of
+	/// an extension field, for example the 32 bits of a `Col<B1, 32>` into a `Col<B32>` or pairs of
+	/// the bytes of a `Col<B8, 8>` into a `Col<B16, 4>`.
+	///
+	/// The packed column shares the witness data of `col`, so it costs no commitment and needs no
+	/// filling.
+	///
+	/// ## Preconditions
+	///
+	/// * `FSub` must be a strict extension of `FSubSub`.
+	/// * The cells of both columns must have the same number of bits.
 	pub fn add_packed<FSubSub, const VALUES_PER_ROW_SUB: usize, FSub, const VALUES_PER_ROW: usize>(
 		&mut self,
 		name: impl ToString,
@@ -175,11 +239,15 @@ impl<'a, F: TowerField> TableBuilder<'a, F> {
 		FSubSub: TowerField,
 		F: ExtensionField<FSub>,
 	{
-		assert!(FSubSub::TOWER_LEVEL < FSub::TOWER_LEVEL);
-		assert!(VALUES_PER_ROW_SUB > VALUES_PER_ROW);
+		assert!(
+			FSubSub::TOWER_LEVEL < FSub::TOWER_LEVEL,
+			"the packed column must be over a strict extension of the field of the column"
+		);
+		let packed_bits = (1 << FSub::TOWER_LEVEL) * VALUES_PER_ROW;
+		let col_bits = (1 << FSubSub::TOWER_LEVEL) * VALUES_PER_ROW_SUB;
 		assert_eq!(
-			FSub::TOWER_LEVEL + log2_strict_usize(VALUES_PER_ROW),
-			FSubSub::TOWER_LEVEL + log2_strict_usize(VALUES_PER_ROW_SUB)
+			packed_bits, col_bits,
+			"the packed column has {packed_bits} bits per row, but the column has {col_bits}"
 		);
 		self.table.new_column(
 			self.namespaced_name(name),
@@ -190,6 +258,58 @@ impl<'a, F: TowerField> TableBuilder<'a, F> {
 		)
 	}
 
+	/// Adds a derived column that packs every row of a column into a single element of an
+	/// extension field, for example the 8 bytes of a `Col<B8, 8>` into a `Col<B64>`.
+	///
+	/// This is [`Self::add_packed`] with one value per row, so that only the field of the packed
+	/// column needs to be specified.
+	pub fn add_packed_row<FSub, FSubSub, const VALUES_PER_ROW_SUB: usize>(
+		&mut self,
+		name: impl ToString,
+		col: Col<FSubSub, VALUES_PER_ROW_SUB>,
+	) -> Col<FSub>
+	where
+		FSub: TowerField + ExtensionField<FSubSub>,
+		FSubSub: TowerField,
+		F: ExtensionField<FSub>,
+	{
+		self.add_packed(name, col)
+	}
+
+	/// Adds a committed column of the subfield values that are packed in a column, for example the
+	/// bytes of a `Col<B64>` as a `Col<B8, 8>`.
+	///
+	/// This is the inverse of [`Self::add_packed`]. The column is constrained to pack to `col`,
+	/// and its witness can be filled with [`TableWitnessSegment::fill_unpacked`].
+	///
+	/// ## Preconditions
+	///
+	/// * `FSub` must be a strict extension of `FSubSub`.
+	/// * The cells of both columns must have the same number of bits.
+	///
+	/// [`TableWitnessSegment::fill_unpacked`]: super::TableWitnessSegment::fill_unpacked
+	pub fn add_unpacked<
+		FSubSub,
+		const VALUES_PER_ROW_SUB: usize,
+		FSub,
+		const VALUES_PER_ROW: usize,
+	>(
+		&mut self,
+		name: impl ToString,
+		col: Col<FSub, VALUES_PER_ROW>,
+	) -> Col<FSubSub, VALUES_PER_ROW_SUB>
+	where
+		FSub: TowerField + ExtensionField<FSubSub>,
+		FSubSub: TowerField,
+		F: ExtensionField<FSub> + ExtensionField<FSubSub>,
+	{
+		let name = name.to_string();
+		let unpacked = self.add_committed(name.clone());
+		let packed: Col<FSub, VALUES_PER_ROW> = self.add_packed(format!("{name}_packed"), unpacked);
+		self.assert_zero(format!("{name}_unpacked"), packed - col);
+		unpacked
+	}
+
 	/// Adds a derived column that is computed as an expression over other columns in the table.
 	///
 	/// The derived column has the same vertical stacking factor as the input columns and its
@@ -523,6 +643,78 @@ impl<'a, F: TowerField> TableBuilder<'a, F> {
 			.new_column(namespaced_name, ColumnDef::StructuredDynSize(variant))
 	}
 
+	/// Adds a committed column whose values are public inputs, known to the verifier.
+	///
+	/// Every row of the column is pushed along with its index to a channel of its own, which is
+	/// added to the constraint system. The verifier pulls the public values with their indices
+	/// as boundaries, see [`ConstraintSystem::public_boundaries`], so the channel only balances
+	/// if the table has as many rows as there are public values and the column holds them in
+	/// order.
+	///
+	/// The row indices are held by a structured column that all public columns of the table
+	/// share, see [`Table::public_index`], which the table filler must fill with
+	/// [`TableWitnessSegment::fill_public_index`].
+	///
+	/// ## Pre-conditions
+	///
+	/// The table must be required to have a power-of-two size, and the builder must be created by
+	/// [`ConstraintSystem::add_table`].
+	///
+	/// [`ConstraintSystem::add_table`]: super::ConstraintSystem::add_table
+	/// [`ConstraintSystem::public_boundaries`]: super::ConstraintSystem::public_boundaries
+	/// [`TableWitnessSegment::fill_public_index`]: super::TableWitnessSegment::fill_public_index
+	pub fn add_public<FSub>(&mut self, name: impl ToString) -> Col<FSub>
+	where
+		FSub: TowerField,
+		F: ExtensionField<FSub> + ExtensionField<B32>,
+	{
+		assert!(
+			self.table.requires_any_po2_size(),
+			"Public columns may only be added to tables that are power of two sized"
+		);
+		let index = match self.table.public_index {
+			Some(index) => index,
+			None => {
+				let index = self.table.new_column(
+					"public_index",
+					ColumnDef::StructuredDynSize(StructuredDynSize::Incrementing {
+						max_size_log: B32::N_BITS,
+					}),
+				);
+				self.table.public_index = Some(index);
+				index
+			}
+		};
+
+		let namespaced_name = self.namespaced_name(name);
+		let channels = self
+			.channels
+			.as_deref_mut()
+			.expect("public columns can only be added to the tables of a constraint system");
+		let channel_id = channels.len();
+		channels.push(Channel {
+			name: format!("{}::{}", self.table.name, namespaced_name),
+		});
+
+		let col = self.table.new_column::<FSub, 1>(
+			namespaced_name,
+			ColumnDef::Committed {
+				tower_level: FSub::TOWER_LEVEL,
+			},
+		);
+		self.table.partition_mut(1).flush(
+			channel_id,
+			FlushDirection::Push,
+			[upcast_col(index), upcast_col(col)],
+			FlushOpts::default(),
+		);
+		self.table.public_columns.push(PublicColumn {
+			column: col.id(),
+			channel_id,
+		});
+		col
+	}
+
 	/// Add a structured fixed-size column to a table.
 	pub fn add_fixed<FSub>(&mut self, name: impl ToString, expr: ArithCircuit<F>) -> Col<FSub>
 	where
@@ -567,6 +759,28 @@ impl<'a, F: TowerField> TableBuilder<'a, F> {
 		self.table.columns[expr.table_index.0].is_nonzero = true;
 	}
 
+	/// Constrains that all values contained in this column are 0 or 1, and returns the column as a
+	/// [`BoolExpr`] for the logical operators.
+	///
+	/// The constraint `col² - col = 0` is added on the first call for a column only. A [`B1`]
+	/// column holds bits by construction and needs no constraint.
+	pub fn assert_boolean<FSub, const V: usize>(&mut self, col: Col<FSub, V>) -> BoolExpr<FSub, V>
+	where
+		FSub: TowerField,
+		F: ExtensionField<FSub>,
+	{
+		assert_eq!(col.table_id, self.id());
+		let info = &mut self.table.columns[col.table_index.0];
+		if FSub::TOWER_LEVEL > 0 && !info.is_boolean {
+			info.is_boolean = true;
+			let name = format!("{} is boolean", info.name);
+			self.table
+				.partition_mut(V)
+				.assert_zero(name, Expr::from(col) * col - col);
+		}
+		BoolExpr::from_boolean_col(col)
+	}
+
 	pub fn pull<FSub>(&mut self, channel: ChannelId, cols: impl IntoIterator<Item = Col<FSub>>)
 	where
 		FSub: TowerField,
@@ -617,6 +831,63 @@ impl<'a, F: TowerField> TableBuilder<'a, F> {
 		);
 	}
 
+	/// Adds the derived columns that pack a byte string column into the values of a
+	/// [`ByteChannel`], see [`pack_bytes`].
+	///
+	/// A byte string of less than [`BYTES_PER_CHANNEL_VALUE`] bytes is zero-padded, and a longer
+	/// one is split into chunks. The derived columns must be filled with
+	/// [`TableWitnessSegment::fill_packed_bytes`].
+	///
+	/// [`pack_bytes`]: super::pack_bytes
+	/// [`TableWitnessSegment::fill_packed_bytes`]: super::TableWitnessSegment::fill_packed_bytes
+	pub fn add_packed_bytes<const N: usize>(
+		&mut self,
+		name: impl ToString,
+		bytes: Col<B8, N>,
+	) -> PackedBytes<N>
+	where
+		F: ExtensionField<B8> + ExtensionField<B128>,
+	{
+		let name = name.to_string();
+		let chunks = match N.cmp(&BYTES_PER_CHANNEL_VALUE) {
+			Ordering::Less => vec![self.add_zero_pad(format!("{name}_padded"), bytes, 0)],
+			Ordering::Equal => vec![],
+			Ordering::Greater => (0..N / BYTES_PER_CHANNEL_VALUE)
+				.map(|i| self.add_selected_block(format!("{name}_chunk[{i}]"), bytes, i))
+				.collect(),
+		};
+		let packed = if chunks.is_empty() {
+			vec![self.add_packed(format!("{name}_packed"), bytes)]
+		} else {
+			chunks
+				.iter()
+				.enumerate()
+				.map(|(i, &chunk)| self.add_packed(format!("{name}_packed[{i}]"), chunk))
+				.collect()
+		};
+		PackedBytes {
+			bytes,
+			chunks,
+			packed,
+		}
+	}
+
+	/// Pulls a byte string from a [`ByteChannel`].
+	pub fn pull_bytes<const N: usize>(&mut self, channel: ByteChannel<N>, bytes: &PackedBytes<N>)
+	where
+		F: ExtensionField<B128>,
+	{
+		self.pull(channel.id(), bytes.packed.iter().copied());
+	}
+
+	/// Pushes a byte string to a [`ByteChannel`].
+	pub fn push_bytes<const N: usize>(&mut self, channel: ByteChannel<N>, bytes: &PackedBytes<N>)
+	where
+		F: ExtensionField<B128>,
+	{
+		self.push(channel.id(), bytes.packed.iter().copied());
+	}
+
 	/// Reads a group of columns from a specified lookup table.
 	///
 	/// This method enforces that the values of the provided columns are obtained from a lookup
@@ -685,13 +956,49 @@ pub struct Table<F: TowerField = B128> {
 	/// the size specification of a table
 	table_size_spec: TableSizeSpec,
 	pub(super) partitions: SparseIndex<TablePartition<F>>,
+	/// The base-2 logarithm of the maximum number of rows, if the table has copies.
+	max_log_size: Option<usize>,
+	/// The copies holding the rows beyond the maximum size of the table.
+	copies: Vec<TableId>,
+	/// The table that this table is a copy of.
+	copy_of: Option<TableId>,
+	/// The columns holding public inputs.
+	public_columns: Vec<PublicColumn>,
+	/// The row indices that the public columns are flushed with.
+	public_index: Option<Col<B32>>,
+	/// The layout that witness segments of the table are filled in.
+	witness_layout: WitnessLayout,
+}
+
+/// The physical layout that table fillers write witness segments in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WitnessLayout {
+	/// Each column of a segment is a contiguous slice, which is also the layout that gets
+	/// committed.
+	#[default]
+	ColumnMajor,
+	/// Fillers may additionally write the byte-aligned columns of a segment row by row through
+	/// [`TableWitnessSegment::rows_mut`], which keeps the cells of one row adjacent in memory.
+	/// This suits fillers that write many columns per event. The rows are scattered into the
+	/// column-major layout after the segment is filled.
+	///
+	/// [`TableWitnessSegment::rows_mut`]: super::TableWitnessSegment::rows_mut
+	RowMajor,
+}
+
+/// A column whose values are public inputs, see [`TableBuilder::add_public`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicColumn {
+	pub column: ColumnId,
+	/// The channel that the column is pushed to along with the row indices.
+	pub channel_id: ChannelId,
 }
 
 /// A table partition describes a part of a table where everything has the same pack factor (as well
 /// as height) Tower level does not need to be the same.
 ///
 /// Zerocheck constraints can only be defined within table partitions.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(super) struct TablePartition<F: TowerField = B128> {
 	pub table_id: TableId,
 	pub values_per_row: usize,
@@ -719,6 +1026,15 @@ impl<F: TowerField> TablePartition<F> {
 		FSub: TowerField,
 		F: ExtensionField<FSub>,
 	{
+		// Constraints are stored in canonical form, so that equal constraints are compiled
+		// identically. Constraints that fold to a constant are kept as they are, so that they
+		// still reference the columns they were built from.
+		let canonical = expr.canonicalize();
+		let expr = if matches!(canonical.expr(), ArithExpr::Const(_)) {
+			expr
+		} else {
+			canonical
+		};
 		self.zero_constraints.push(ZeroConstraint {
 			tower_level: FSub::TOWER_LEVEL,
 			name: name.to_string(),
@@ -766,6 +1082,12 @@ impl<F: TowerField> Table<F> {
 			columns: Vec::new(),
 			table_size_spec: TableSizeSpec::Arbitrary,
 			partitions: SparseIndex::new(),
+			max_log_size: None,
+			copies: Vec::new(),
+			copy_of: None,
+			public_columns: Vec::new(),
+			public_index: None,
+			witness_layout: WitnessLayout::ColumnMajor,
 		}
 	}
 
@@ -773,6 +1095,103 @@ impl<F: TowerField> Table<F> {
 		self.id
 	}
 
+	/// Returns the base-2 logarithm of the maximum number of rows of a table with copies and of
+	/// each of its copies.
+	///
+	/// See [`ConstraintSystem::add_table_copies`].
+	///
+	/// [`ConstraintSystem::add_table_copies`]: super::ConstraintSystem::add_table_copies
+	pub fn max_log_size(&self) -> Option<usize> {
+		self.max_log_size
+	}
+
+	/// Returns the columns of the table that hold public inputs.
+	pub fn public_columns(&self) -> &[PublicColumn] {
+		&self.public_columns
+	}
+
+	/// Returns the column holding the row indices that the public columns are flushed with, if
+	/// the table has public columns.
+	pub fn public_index(&self) -> Option<Col<B32>> {
+		self.public_index
+	}
+
+	/// Returns the layout that witness segments of the table are filled in.
+	pub fn witness_layout(&self) -> WitnessLayout {
+		self.witness_layout
+	}
+
+	/// Returns the IDs of the copies of the table.
+	pub fn copies(&self) -> &[TableId] {
+		&self.copies
+	}
+
+	/// Returns the ID of the table that this table is a copy of.
+	pub fn copy_of(&self) -> Option<TableId> {
+		self.copy_of
+	}
+
+	/// Returns whether the columns of the table with the given ID are columns of this table, which
+	/// is the case for this table and the table it is a copy of.
+	pub(crate) fn has_columns_of(&self, table_id: TableId) -> bool {
+		table_id == self.id || self.copy_of == Some(table_id)
+	}
+
+	/// Sets the copies of the table, which hold the rows beyond `2^max_log_size`.
+	pub(super) fn set_copies(&mut self, max_log_size: usize, copies: Vec<TableId>) {
+		self.max_log_size = Some(max_log_size);
+		self.copies = copies;
+	}
+
+	/// Returns a copy of the table with the given ID, which has the same columns, constraints and
+	/// flushes.
+	pub(super) fn copy(&self, id: TableId, name: impl ToString) -> Self {
+		let columns = self
+			.columns
+			.iter()
+			.map(|info| ColumnInfo {
+				id: ColumnId {
+					table_id: id,
+					..info.id
+				},
+				col: info.col.with_table_id(id),
+				name: info.name.clone(),
+				shape: info.shape,
+				is_nonzero: info.is_nonzero,
+				is_boolean: info.is_boolean,
+			})
+			.collect();
+
+		let mut partitions = self.partitions.clone();
+		for partition in partitions.values_mut() {
+			partition.table_id = id;
+			let column_ids = partition.columns.iter_mut().chain(
+				partition
+					.flushes
+					.iter_mut()
+					.flat_map(|flush| flush.columns.iter_mut().chain(&mut flush.selectors)),
+			);
+			for column_id in column_ids {
+				column_id.table_id = id;
+			}
+		}
+
+		Self {
+			id,
+			name: name.to_string(),
+			columns,
+			table_size_spec: self.table_size_spec,
+			partitions,
+			max_log_size: self.max_log_size,
+			copies: Vec::new(),
+			copy_of: Some(self.id),
+			// Tables with public columns are power-of-two sized, so they are never split.
+			public_columns: Vec::new(),
+			public_index: None,
+			witness_layout: self.witness_layout,
+		}
+	}
+
 	fn new_column<FSub, const V: usize>(
 		&mut self,
 		name: impl ToString,
@@ -798,6 +1217,7 @@ impl<F: TowerField> Table<F> {
 				log_values_per_row: log2_strict_usize(V),
 			},
 			is_nonzero: false,
+			is_boolean: false,
 		};
 
 		let partition_index = ColumnPartitionIndex(partition.columns.len());
@@ -2,23 +2,35 @@
 
 //! Utilities for testing M3 constraint systems and gadgets.
 use anyhow::Result;
-use binius_compute::ComputeHolder;
-use binius_core::{constraint_system::channel::Boundary, fiat_shamir::HasherChallenger};
+use binius_compute::{ComputeHolder, cpu::alloc::CpuComputeAllocator};
+use binius_core::{
+	constraint_system::{
+		Proof, ProofHeader,
+		channel::Boundary,
+		error::{Error, VerificationError},
+		hash_scheme::{self, TaggedProof},
+		opening,
+	},
+	fiat_shamir::HasherChallenger,
+};
 use binius_fast_compute::layer::FastCpuLayerHolder;
 use binius_field::{
-	BinaryField128bPolyval, PackedField, PackedFieldIndexable, TowerField,
+	BinaryField128bPolyval, Field, PackedField, PackedFieldIndexable, TowerField,
 	as_packed_field::{PackScalar, PackedType},
 	linear_transformation::PackedTransformationFactory,
 	tower::CanonicalTowerFamily,
 	underlier::UnderlierType,
 };
 use binius_hash::groestl::{Groestl256, Groestl256ByteCompression};
-use binius_utils::env::boolean_env_flag_set;
+use binius_utils::{SerializationMode, SerializeBytes, env::boolean_env_flag_set};
+use itertools::Itertools;
 
 use super::{
 	B1, B8, B16, B32, B64,
+	column::{ColumnDef, ColumnId},
 	constraint_system::ConstraintSystem,
 	table::TableId,
+	trace::ChannelTrace,
 	witness::{TableFiller, TableWitnessSegment},
 };
 use crate::builder::{B128, WitnessIndex};
@@ -65,6 +77,9 @@ impl<P: PackedField<Scalar: TowerField>, Event: Clone> TableFiller<P>
 }
 
 /// Utility for M3 tests to validate a constraint system and witness.
+///
+/// If the `BINIUS_M3_CHANNEL_TRACE` environment variable is set to a file path, the
+/// [`ChannelTrace`] of the witness is written to that file before the witness is validated.
 pub fn validate_system_witness<U>(
 	cs: &ConstraintSystem<B128>,
 	witness: WitnessIndex<PackedType<U, B128>>,
@@ -93,7 +108,7 @@ pub fn validate_system_witness<U>(
 
 pub fn validate_system_witness_with_prove_verify<U>(
 	cs: &ConstraintSystem<B128>,
-	witness: WitnessIndex<PackedType<U, B128>>,
+	mut witness: WitnessIndex<PackedType<U, B128>>,
 	boundaries: Vec<Boundary<B128>>,
 	prove_verify: bool,
 ) where
@@ -109,6 +124,14 @@ pub fn validate_system_witness_with_prove_verify<U>(
 		PackedFieldIndexable + PackedTransformationFactory<PackedType<U, BinaryField128bPolyval>>,
 	PackedType<U, BinaryField128bPolyval>: PackedTransformationFactory<PackedType<U, B128>>,
 {
+	const CHANNEL_TRACE_ENV_NAME: &str = "BINIUS_M3_CHANNEL_TRACE";
+	if let Some(path) = std::env::var_os(CHANNEL_TRACE_ENV_NAME) {
+		ChannelTrace::collect(cs, &mut witness, &boundaries)
+			.unwrap()
+			.write_to_file(path)
+			.unwrap();
+	}
+
 	let table_sizes = witness.table_sizes();
 	let ccs = cs.compile().unwrap();
 	let witness = witness.into_multilinear_extension_index();
@@ -147,18 +170,450 @@ pub fn validate_system_witness_with_prove_verify<U>(
 			&ccs_digest,
 			&boundaries,
 			&table_sizes,
-			witness,
+			witness.clone(),
 			&binius_hal::make_portable_backend(),
 		)
 		.unwrap();
 
-		binius_core::constraint_system::verify::<
+		// Keeping the codewords resident on the device produces the same proof.
+		let device_resident_proof = binius_core::constraint_system::prove::<
+			_,
+			U,
+			CanonicalTowerFamily,
+			Groestl256,
+			Groestl256ByteCompression,
+			HasherChallenger<Groestl256>,
+			_,
+			_,
+			_,
+		>(
+			&mut compute_holder.to_data().with_device_resident(true),
+			&ccs,
+			LOG_INV_RATE,
+			SECURITY_BITS,
+			&ccs_digest,
+			&boundaries,
+			&table_sizes,
+			witness.clone(),
+			&binius_hal::make_portable_backend(),
+		)
+		.unwrap();
+		assert_eq!(device_resident_proof.transcript, proof.transcript);
+
+		let commitments = binius_core::constraint_system::verify_commitments::<
+			U,
+			CanonicalTowerFamily,
+			Groestl256,
+			Groestl256ByteCompression,
+			HasherChallenger<Groestl256>,
+		>(&ccs, LOG_INV_RATE, SECURITY_BITS, &ccs_digest, &boundaries, &proof)
+		.unwrap();
+		assert_eq!(commitments.table_sizes, table_sizes);
+
+		// The header records the protocol version and the parameters, and a verifier with other
+		// parameters rejects the proof at the header.
+		let header = ProofHeader::read(&proof).unwrap();
+		assert_eq!(header, ProofHeader::new(LOG_INV_RATE, SECURITY_BITS));
+		assert!(matches!(
+			binius_core::constraint_system::verify_commitments::<
+				U,
+				CanonicalTowerFamily,
+				Groestl256,
+				Groestl256ByteCompression,
+				HasherChallenger<Groestl256>,
+			>(&ccs, LOG_INV_RATE + 1, SECURITY_BITS, &ccs_digest, &boundaries, &proof),
+			Err(Error::Verification(VerificationError::ProofParametersMismatch { .. }))
+		));
+
+		// A proof cut off before its commitments must be rejected by the cheap check.
+		let mut header_bytes = Vec::new();
+		header
+			.serialize(&mut header_bytes, SerializationMode::CanonicalTower)
+			.unwrap();
+		let truncated_proof = Proof {
+			transcript: proof.transcript[..header_bytes.len() + table_sizes.len() * 8].to_vec(),
+		};
+		assert!(
+			binius_core::constraint_system::verify_commitments::<
+				U,
+				CanonicalTowerFamily,
+				Groestl256,
+				Groestl256ByteCompression,
+				HasherChallenger<Groestl256>,
+			>(&ccs, LOG_INV_RATE, SECURITY_BITS, &ccs_digest, &boundaries, &truncated_proof)
+			.is_err()
+		);
+
+		// The report echoes the parameters of the proof and attributes its bytes to the phases.
+		let report = binius_core::constraint_system::verify_with_report::<
+			U,
+			CanonicalTowerFamily,
+			Groestl256,
+			Groestl256ByteCompression,
+			HasherChallenger<Groestl256>,
+		>(&ccs, LOG_INV_RATE, SECURITY_BITS, &ccs_digest, &boundaries, proof.clone())
+		.unwrap();
+		assert_eq!(report.header, header);
+		assert_eq!(report.table_sizes, table_sizes);
+		assert_eq!(report.proof_bytes, proof.get_proof_size());
+		let phase_bytes = report
+			.phases
+			.iter()
+			.map(|phase| phase.proof_bytes)
+			.sum::<usize>();
+		// The header and the table sizes, which are written as 32-bit integers, precede the phases.
+		assert_eq!(
+			phase_bytes,
+			proof.get_proof_size() - header_bytes.len() - 4 * table_sizes.len()
+		);
+		assert!(report.estimated_cost.hash_invocations > 0);
+
+		// The estimate is derived from the table sizes alone. The costs of the interactive
+		// reductions are approximate, so it only has to be within a factor of two of the real
+		// proof size.
+		let estimated_cost = binius_core::constraint_system::cost::estimate_verifier_cost::<
+			CanonicalTowerFamily,
+			Groestl256,
+			Groestl256ByteCompression,
+		>(&ccs, &table_sizes, LOG_INV_RATE, SECURITY_BITS)
+		.unwrap();
+		assert_eq!(estimated_cost, report.estimated_cost);
+		let proof_size = proof.get_proof_size();
+		assert!(
+			estimated_cost.proof_bytes <= 2 * proof_size
+				&& proof_size <= 2 * estimated_cost.proof_bytes,
+			"estimated {} proof bytes, but the proof has {proof_size}",
+			estimated_cost.proof_bytes
+		);
+
+		// Open the first and last cell of a few committed oracles against the proof commitments.
+		let oracles = ccs.oracles.instantiate(&table_sizes).unwrap();
+		let indices = oracles
+			.ids()
+			.filter(|&id| oracles[id].variant.is_committed() && !oracles.is_zero_sized(id))
+			.take(3)
+			.flat_map(|id| [(id, 0), (id, (1 << oracles.n_vars(id)) - 1)])
+			.collect::<Vec<_>>();
+		let opening::ProvenOpenings {
+			openings,
+			proof: opening_proof,
+		} = opening::prove_openings::<
+			_,
+			U,
+			CanonicalTowerFamily,
+			Groestl256,
+			Groestl256ByteCompression,
+			HasherChallenger<Groestl256>,
+			_,
+			_,
+		>(
+			&mut compute_holder.to_data(),
+			&ccs,
+			LOG_INV_RATE,
+			SECURITY_BITS,
+			&ccs_digest,
+			&table_sizes,
+			witness,
+			&indices,
+		)
+		.unwrap();
+		opening::verify_openings::<
+			U,
+			CanonicalTowerFamily,
+			Groestl256,
+			Groestl256ByteCompression,
+			HasherChallenger<Groestl256>,
+		>(
+			&ccs,
+			LOG_INV_RATE,
+			SECURITY_BITS,
+			&ccs_digest,
+			&commitments,
+			&openings,
+			opening_proof.clone(),
+		)
+		.unwrap();
+		if let Some(first) = openings.first() {
+			let mut tampered = openings.clone();
+			tampered[0].value = first.value + B128::ONE;
+			assert!(
+				opening::verify_openings::<
+					U,
+					CanonicalTowerFamily,
+					Groestl256,
+					Groestl256ByteCompression,
+					HasherChallenger<Groestl256>,
+				>(
+					&ccs,
+					LOG_INV_RATE,
+					SECURITY_BITS,
+					&ccs_digest,
+					&commitments,
+					&tampered,
+					opening_proof,
+				)
+				.is_err()
+			);
+		}
+
+		let tagged_proof = TaggedProof {
+			hash_scheme: "groestl256".to_string(),
+			proof,
+		};
+		hash_scheme::verify_tagged(&ccs, LOG_INV_RATE, SECURITY_BITS, &boundaries, tagged_proof)
+			.unwrap();
+	}
+}
+
+/// A corruption of a valid witness, of the flushes of a constraint system or of a valid proof.
+///
+/// A sound constraint system rejects a mutation unless the mutated value is not constrained at
+/// all, see [`find_undetected_mutations`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mutation {
+	/// Adds one to the value at `index` of a committed column, counting the values of all rows.
+	WitnessCell { column: ColumnId, index: usize },
+	/// Adds one to the multiplicity of the flush at index `flush` of the compiled constraint
+	/// system.
+	FlushMultiplicity { flush: usize },
+	/// Adds one to the multiplicity of the boundary at index `boundary`.
+	BoundaryMultiplicity { boundary: usize },
+	/// Adds one to the size of the table `table` that the proof transcript records.
+	TableSize { table: TableId },
+	/// Flips the lowest bit of the byte at `offset` of the proof transcript, after the header and
+	/// the table sizes.
+	ProofByte { offset: usize },
+}
+
+/// Applies the mutations of a valid witness systematically and returns the ones that are not
+/// rejected.
+///
+/// The witness is filled by `fill` once for every mutated cell, in a fresh bump allocator over
+/// `allocator`. The harness mutates
+///
+/// * the first value of the first row and the last value of the last row of every committed column
+///   of every non-empty table,
+/// * the multiplicity of every flush of a non-empty table,
+/// * the multiplicity of every boundary,
+///
+/// and checks that the witness is rejected by `validate_witness`. If `prove_verify` is set, it
+/// also proves the unmutated witness and checks that the verifier rejects the proof with the size
+/// of any table increased, or with any of a few bytes spread over the rest of the transcript
+/// flipped.
+///
+/// A mutation of a value that is not constrained, such as a committed column that takes part in
+/// neither a constraint nor a flush, or the size of a table without flushes, is not detected by a
+/// sound system either, so callers are expected to filter the returned mutations by what they
+/// know is unconstrained.
+///
+/// ## Panics
+///
+/// * if the unmutated witness is rejected
+pub fn find_undetected_mutations<U>(
+	cs: &ConstraintSystem<B128>,
+	allocator: &mut CpuComputeAllocator<PackedType<U, B128>>,
+	fill: impl Fn(&mut WitnessIndex<PackedType<U, B128>>) -> Result<()>,
+	boundaries: &[Boundary<B128>],
+	prove_verify: bool,
+) -> Vec<Mutation>
+where
+	U: UnderlierType
+		+ PackScalar<B1>
+		+ PackScalar<B8>
+		+ PackScalar<B16>
+		+ PackScalar<B32>
+		+ PackScalar<B64>
+		+ PackScalar<B128>
+		+ PackScalar<BinaryField128bPolyval>,
+	PackedType<U, B128>:
+		PackedFieldIndexable + PackedTransformationFactory<PackedType<U, BinaryField128bPolyval>>,
+	PackedType<U, BinaryField128bPolyval>: PackedTransformationFactory<PackedType<U, B128>>,
+{
+	const LOG_INV_RATE: usize = 1;
+	const SECURITY_BITS: usize = 100;
+	const N_PROOF_BYTE_MUTATIONS: usize = 64;
+
+	let ccs = cs.compile().unwrap();
+	let mut undetected = Vec::new();
+
+	let table_sizes = {
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(cs, &allocator);
+		fill(&mut witness).unwrap();
+		witness.table_sizes()
+	};
+
+	let cell_mutations = cs
+		.tables
+		.iter()
+		.filter(|table| table_sizes[table.id] != 0)
+		.flat_map(|table| {
+			let size = table_sizes[table.id];
+			table
+				.columns
+				.iter()
+				.filter(|col| matches!(col.col, ColumnDef::Committed { .. }))
+				.flat_map(move |col| {
+					let values_per_row = 1 << col.shape.log_values_per_row;
+					[0, size * values_per_row - 1].map(|index| Mutation::WitnessCell {
+						column: col.id,
+						index,
+					})
+				})
+		})
+		.dedup()
+		.collect::<Vec<_>>();
+	for mutation in cell_mutations {
+		let Mutation::WitnessCell { column, index } = mutation else {
+			unreachable!("only witness cells are collected");
+		};
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(cs, &allocator);
+		fill(&mut witness).unwrap();
+		{
+			let segment = witness
+				.get_table(column.table_id)
+				.expect("the table is not empty")
+				.full_segment();
+			let mut values = segment.get_dyn_mut(column).unwrap();
+			let value = values.get(index);
+			values.set(index, value + B128::ONE).unwrap();
+		}
+		let witness = witness.into_multilinear_extension_index();
+		if binius_core::constraint_system::validate::validate_witness(
+			&ccs,
+			boundaries,
+			&table_sizes,
+			&witness,
+		)
+		.is_ok()
+		{
+			undetected.push(mutation);
+		}
+	}
+
+	let allocator = allocator.into_bump_allocator();
+	let mut witness = WitnessIndex::new(cs, &allocator);
+	fill(&mut witness).unwrap();
+	let witness = witness.into_multilinear_extension_index();
+	binius_core::constraint_system::validate::validate_witness(
+		&ccs,
+		boundaries,
+		&table_sizes,
+		&witness,
+	)
+	.expect("the unmutated witness must be valid");
+
+	for (flush, _) in ccs
+		.flushes
+		.iter()
+		.enumerate()
+		.filter(|(_, flush)| table_sizes[flush.table_id] != 0)
+	{
+		let mut mutated = ccs.clone();
+		mutated.flushes[flush].multiplicity += 1;
+		if binius_core::constraint_system::validate::validate_witness(
+			&mutated,
+			boundaries,
+			&table_sizes,
+			&witness,
+		)
+		.is_ok()
+		{
+			undetected.push(Mutation::FlushMultiplicity { flush });
+		}
+	}
+
+	for boundary in 0..boundaries.len() {
+		let mut mutated = boundaries.to_vec();
+		mutated[boundary].multiplicity += 1;
+		if binius_core::constraint_system::validate::validate_witness(
+			&ccs,
+			&mutated,
+			&table_sizes,
+			&witness,
+		)
+		.is_ok()
+		{
+			undetected.push(Mutation::BoundaryMultiplicity { boundary });
+		}
+	}
+
+	if prove_verify {
+		let mut compute_holder =
+			FastCpuLayerHolder::<CanonicalTowerFamily, PackedType<U, B128>>::new(1 << 16, 1 << 24);
+
+		let ccs_digest = ccs.digest::<Groestl256>();
+		let proof = binius_core::constraint_system::prove::<
+			_,
 			U,
 			CanonicalTowerFamily,
 			Groestl256,
 			Groestl256ByteCompression,
 			HasherChallenger<Groestl256>,
-		>(&ccs, LOG_INV_RATE, SECURITY_BITS, &ccs_digest, &boundaries, proof)
+			_,
+			_,
+			_,
+		>(
+			&mut compute_holder.to_data(),
+			&ccs,
+			LOG_INV_RATE,
+			SECURITY_BITS,
+			&ccs_digest,
+			boundaries,
+			&table_sizes,
+			witness,
+			&binius_hal::make_portable_backend(),
+		)
 		.unwrap();
+
+		let verifies = |proof: Proof| {
+			binius_core::constraint_system::verify::<
+				U,
+				CanonicalTowerFamily,
+				Groestl256,
+				Groestl256ByteCompression,
+				HasherChallenger<Groestl256>,
+			>(&ccs, LOG_INV_RATE, SECURITY_BITS, &ccs_digest, boundaries, proof)
+			.is_ok()
+		};
+
+		// The transcript starts with the header and the table sizes, which are serialized with a
+		// fixed width.
+		let mut header_and_sizes = Vec::new();
+		ProofHeader::new(LOG_INV_RATE, SECURITY_BITS)
+			.serialize(&mut header_and_sizes, SerializationMode::CanonicalTower)
+			.unwrap();
+		for (table, &size) in table_sizes.iter().enumerate() {
+			let offset = header_and_sizes.len();
+			let mut size_bytes = Vec::new();
+			(size + 1)
+				.serialize(&mut size_bytes, SerializationMode::CanonicalTower)
+				.unwrap();
+			let mut mutated = proof.clone();
+			mutated.transcript[offset..offset + size_bytes.len()].copy_from_slice(&size_bytes);
+			if verifies(mutated) {
+				undetected.push(Mutation::TableSize { table });
+			}
+			size.serialize(&mut header_and_sizes, SerializationMode::CanonicalTower)
+				.unwrap();
+		}
+		debug_assert!(proof.transcript.starts_with(&header_and_sizes));
+
+		let start = header_and_sizes.len();
+		let len = proof.transcript.len() - start;
+		let offsets = (0..N_PROOF_BYTE_MUTATIONS)
+			.map(|i| start + i * len / N_PROOF_BYTE_MUTATIONS)
+			.chain([start + len - 1])
+			.dedup();
+		for offset in offsets {
+			let mut mutated = proof.clone();
+			mutated.transcript[offset] ^= 1;
+			if verifies(mutated) {
+				undetected.push(Mutation::ProofByte { offset });
+			}
+		}
 	}
+
+	undetected
 }
@@ -0,0 +1,341 @@
+// Copyright 2025 Irreducible Inc.
+
+//! A trace of the values that are flushed to the channels of a constraint system.
+//!
+//! The channel balancing argument only shows whether the pushed and pulled multisets of a
+//! channel are equal. A [`ChannelTrace`] lists every tuple that a witness pushes to or pulls from
+//! every channel, with the table row or the boundary it comes from, so that the balance can be
+//! re-checked independently and the flushed values can be inspected when it fails.
+
+use std::{
+	collections::HashMap,
+	fmt, fs,
+	io::{self, Write},
+	iter,
+	path::Path,
+};
+
+use binius_core::constraint_system::channel::{Boundary, ChannelId, FlushDirection};
+use binius_field::{Field, PackedExtension, PackedField};
+
+use super::{
+	B1, B8, B16, B32, B64, B128, constraint_system::ConstraintSystem, error::Error, table::TableId,
+	witness::WitnessIndex,
+};
+
+/// Where a flushed tuple comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlushOrigin {
+	/// The tuple is flushed by a table.
+	Table {
+		table_id: TableId,
+		/// The row of the table.
+		row: usize,
+		/// The index of the value within the row, for partitions with several values per row.
+		value_index: usize,
+	},
+	/// The tuple is a boundary value, given by its index in the boundaries.
+	Boundary(usize),
+}
+
+/// A tuple that is pushed to or pulled from a channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+	pub direction: FlushDirection,
+	/// The number of times the tuple is flushed.
+	pub multiplicity: u64,
+	pub values: Vec<B128>,
+	pub origin: FlushOrigin,
+}
+
+/// The tuples that a witness flushes to every channel.
+#[derive(Debug, Clone)]
+pub struct ChannelTrace {
+	channel_names: Vec<String>,
+	table_names: Vec<String>,
+	/// The entries of every channel, indexed by channel ID.
+	entries: Vec<Vec<TraceEntry>>,
+}
+
+impl ChannelTrace {
+	/// Collects the tuples that the tables of a filled witness and the boundaries flush.
+	///
+	/// Table rows are only flushed up to the table size, and only on the rows where all the
+	/// selectors of the flush are set.
+	pub fn collect<P>(
+		cs: &ConstraintSystem<B128>,
+		witness: &mut WitnessIndex<P>,
+		boundaries: &[Boundary<B128>],
+	) -> Result<Self, Error>
+	where
+		P: PackedField<Scalar = B128>
+			+ PackedExtension<B1>
+			+ PackedExtension<B8>
+			+ PackedExtension<B16>
+			+ PackedExtension<B32>
+			+ PackedExtension<B64>
+			+ PackedExtension<B128>,
+	{
+		let mut entries = vec![Vec::new(); cs.channels.len()];
+
+		for table in &cs.tables {
+			let Some(table_index) = witness.get_table(table.id()) else {
+				continue;
+			};
+			let table_size = table_index.size();
+			let segment = table_index.full_segment();
+			for partition in table.partitions.values() {
+				for flush in &partition.flushes {
+					let cols = flush
+						.columns
+						.iter()
+						.map(|&col_id| segment.get_dyn(col_id))
+						.collect::<Result<Vec<_>, _>>()?;
+					let selectors = flush
+						.selectors
+						.iter()
+						.map(|&col_id| segment.get_dyn(col_id))
+						.collect::<Result<Vec<_>, _>>()?;
+
+					let channel_entries = entries
+						.get_mut(flush.channel_id)
+						.ok_or(Error::MissingChannel(flush.channel_id))?;
+					for i in 0..table_size * partition.values_per_row {
+						let row = i / partition.values_per_row;
+						if selectors
+							.iter()
+							.any(|selector| selector.get(row) == B128::ZERO)
+						{
+							continue;
+						}
+						channel_entries.push(TraceEntry {
+							direction: flush.direction,
+							multiplicity: flush.multiplicity as u64,
+							values: cols.iter().map(|col| col.get(i)).collect(),
+							origin: FlushOrigin::Table {
+								table_id: table.id(),
+								row,
+								value_index: i % partition.values_per_row,
+							},
+						});
+					}
+				}
+			}
+		}
+
+		for (i, boundary) in boundaries.iter().enumerate() {
+			entries
+				.get_mut(boundary.channel_id)
+				.ok_or(Error::MissingChannel(boundary.channel_id))?
+				.push(TraceEntry {
+					direction: boundary.direction,
+					multiplicity: boundary.multiplicity,
+					values: boundary.values.clone(),
+					origin: FlushOrigin::Boundary(i),
+				});
+		}
+
+		Ok(Self {
+			channel_names: cs
+				.channels
+				.iter()
+				.map(|channel| channel.name.clone())
+				.collect(),
+			table_names: cs.tables.iter().map(|table| table.name.clone()).collect(),
+			entries,
+		})
+	}
+
+	/// The tuples flushed to a channel, in the order of the tables and then the boundaries.
+	pub fn entries(&self, channel_id: ChannelId) -> &[TraceEntry] {
+		&self.entries[channel_id]
+	}
+
+	/// Returns the tuples of a channel that are pushed a different number of times than they are
+	/// pulled, with the number of pushes minus the number of pulls, in the order of their first
+	/// flush.
+	///
+	/// The channel is balanced if the result is empty.
+	pub fn unbalanced(&self, channel_id: ChannelId) -> Vec<(Vec<B128>, i128)> {
+		let mut counts = HashMap::<&[B128], i128>::new();
+		let mut order = Vec::new();
+		for entry in &self.entries[channel_id] {
+			let count = counts.entry(&entry.values).or_insert_with(|| {
+				order.push(entry.values.as_slice());
+				0
+			});
+			match entry.direction {
+				FlushDirection::Push => *count += entry.multiplicity as i128,
+				FlushDirection::Pull => *count -= entry.multiplicity as i128,
+			}
+		}
+		order
+			.into_iter()
+			.filter_map(|values| {
+				let count = counts[values];
+				(count != 0).then(|| (values.to_vec(), count))
+			})
+			.collect()
+	}
+
+	/// Writes the trace in a line-based text format.
+	///
+	/// Every channel starts with a `channel <id> <name>` line, followed by one line per tuple
+	/// with the direction, the multiplicity, the origin, and the values, separated by tabs. The
+	/// origin is either `table <name> row <row>`, with a `.<value index>` suffix for partitions
+	/// with several values per row, or `boundary <index>`.
+	pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+		write!(writer, "{self}")
+	}
+
+	/// Writes the trace to a file, see [`Self::write`] for the format.
+	pub fn write_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+		let mut writer = io::BufWriter::new(fs::File::create(path)?);
+		self.write(&mut writer)?;
+		writer.flush()
+	}
+}
+
+impl fmt::Display for ChannelTrace {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for (channel_id, (name, entries)) in
+			iter::zip(&self.channel_names, &self.entries).enumerate()
+		{
+			writeln!(f, "channel {channel_id} {name}")?;
+			for entry in entries {
+				let direction = match entry.direction {
+					FlushDirection::Push => "push",
+					FlushDirection::Pull => "pull",
+				};
+				write!(f, "{direction}\t{}\t", entry.multiplicity)?;
+				match entry.origin {
+					FlushOrigin::Table {
+						table_id,
+						row,
+						value_index,
+					} => {
+						write!(f, "table {} row {row}", self.table_names[table_id])?;
+						if value_index != 0 {
+							write!(f, ".{value_index}")?;
+						}
+					}
+					FlushOrigin::Boundary(index) => write!(f, "boundary {index}")?,
+				}
+				for value in &entry.values {
+					write!(f, "\t{value}")?;
+				}
+				writeln!(f)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_compute::cpu::alloc::CpuComputeAllocator;
+
+	use super::*;
+	use crate::builder::{
+		FlushOpts,
+		test_utils::{ClosureFiller, validate_system_witness},
+	};
+
+	#[test]
+	fn test_channel_trace() {
+		let mut cs = ConstraintSystem::new();
+		let chan = cs.add_channel("values");
+
+		let mut pusher = cs.add_table("pusher");
+		let pusher_id = pusher.id();
+		let pushed = pusher.add_committed::<B32, 1>("pushed");
+		let selector = pusher.add_committed::<B1, 1>("selector");
+		pusher.push_with_opts(
+			chan,
+			[pushed],
+			FlushOpts {
+				multiplicity: 1,
+				selectors: vec![selector],
+			},
+		);
+
+		// The reader pulls two values per row.
+		let mut reader = cs.add_table("reader");
+		let reader_id = reader.id();
+		let pulled = reader.add_committed::<B32, 2>("pulled");
+		reader.read(chan, [pulled]);
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(&cs, &allocator);
+		// The unselected row is not flushed.
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(pusher_id, |rows: &[(u32, bool)], segment| {
+					let mut pushed = segment.get_mut_as::<u32, _, 1>(pushed)?;
+					let mut selector = segment.get_mut(selector)?;
+					for (i, &(value, selected)) in rows.iter().enumerate() {
+						pushed[i] = value;
+						binius_field::packed::set_packed_slice(
+							&mut selector,
+							i,
+							B1::from(selected),
+						);
+					}
+					Ok(())
+				}),
+				&[(4, true), (5, false), (2, true), (1, true)],
+			)
+			.unwrap();
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(reader_id, |rows: &[[u32; 2]], segment| {
+					let mut pulled = segment.get_mut_as::<[u32; 2], _, 2>(pulled)?;
+					pulled[..rows.len()].copy_from_slice(rows);
+					Ok(())
+				}),
+				&[[1, 2], [3, 4]],
+			)
+			.unwrap();
+		let boundaries = vec![Boundary {
+			values: vec![B32::new(3).into()],
+			channel_id: chan,
+			direction: FlushDirection::Push,
+			multiplicity: 1,
+		}];
+
+		let trace = ChannelTrace::collect(&cs, &mut witness, &boundaries).unwrap();
+		let entries = trace.entries(chan);
+		assert_eq!(entries.len(), 8);
+		assert_eq!(
+			entries[6],
+			TraceEntry {
+				direction: FlushDirection::Pull,
+				multiplicity: 1,
+				values: vec![B32::new(4).into()],
+				origin: FlushOrigin::Table {
+					table_id: reader_id,
+					row: 1,
+					value_index: 1,
+				},
+			}
+		);
+		assert_eq!(entries[7].origin, FlushOrigin::Boundary(0));
+		assert!(trace.unbalanced(chan).is_empty());
+
+		let text = trace.to_string();
+		assert!(text.starts_with("channel 0 values\n"));
+		assert!(text.contains("push\t1\ttable pusher row 3\t"));
+		assert!(text.contains("pull\t1\ttable reader row 1.1\t"));
+		assert!(!text.contains("table pusher row 1\t"));
+		assert_eq!(text.lines().count(), 9);
+
+		// Without the boundary, the pulled 3 is not pushed.
+		let trace = ChannelTrace::collect(&cs, &mut witness, &[]).unwrap();
+		assert_eq!(trace.unbalanced(chan), vec![(vec![B32::new(3).into()], -1)]);
+
+		validate_system_witness::<binius_field::arch::OptimalUnderlier128b>(
+			&cs, witness, boundaries,
+		);
+	}
+}
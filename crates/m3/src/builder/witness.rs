@@ -3,14 +3,16 @@
 use std::{
 	cell::{Ref, RefCell, RefMut},
 	iter,
-	ops::{Deref, DerefMut},
+	ops::{Deref, DerefMut, Range},
 	slice,
 	sync::Arc,
 };
 
 use binius_compute::alloc::{ComputeAllocator, HostBumpAllocator};
-use binius_core::witness::{MultilinearExtensionIndex, MultilinearWitness};
-use binius_fast_compute::arith_circuit::ArithCircuitPoly;
+use binius_core::{
+	oracle::ShiftVariant,
+	witness::{MultilinearExtensionIndex, MultilinearWitness},
+};
 use binius_field::{
 	ExtensionField, PackedExtension, PackedField, PackedFieldIndexable, PackedSubfield, TowerField,
 	arch::OptimalUnderlier,
@@ -18,21 +20,24 @@ use binius_field::{
 	packed::{get_packed_slice, set_packed_slice},
 };
 use binius_math::{
-	ArithCircuit, CompositionPoly, MultilinearExtension, MultilinearPoly, RowsBatchRef,
+	ArithCircuit, ArithCircuitEvaluator, MultilinearExtension, MultilinearPoly, RowsBatchRef,
 };
 use binius_maybe_rayon::prelude::*;
-use binius_utils::checked_arithmetics::checked_log_2;
+use binius_utils::{checked_arithmetics::checked_log_2, mem::fill_zeroes, rayon::ThreadPoolHandle};
 use bytemuck::{Pod, must_cast_slice, must_cast_slice_mut, zeroed_vec};
 use either::Either;
 use getset::CopyGetters;
 use itertools::Itertools;
 
 use super::{
-	ColumnDef, ColumnId, ColumnInfo, ConstraintSystem, Expr,
+	Boundary, ColumnDef, ColumnId, ColumnInfo, ConstraintSystem, Expr,
+	channel::{BYTES_PER_CHANNEL_VALUE, PackedBytes},
 	column::{Col, ColumnShape},
+	column_rows::{ColumnRows, ColumnRowsMut},
 	constraint_system::OracleMapping,
 	error::Error,
-	table::{self, Table, TableId},
+	preflight::{self, PreflightFinding},
+	table::{self, Table, TableId, WitnessLayout},
 	types::{B1, B8, B16, B32, B64, B128},
 };
 use crate::builder::multi_iter::MultiIterator;
@@ -53,6 +58,8 @@ where
 	allocator: &'alloc HostBumpAllocator<'alloc, P>,
 	/// Each entry is Left if the index hasn't been initialized & filled, and Right if it has.
 	tables: Vec<Either<&'cs Table<P::Scalar>, TableWitnessIndex<'cs, 'alloc, P>>>,
+	/// The thread pool that tables are filled on in parallel.
+	thread_pool: ThreadPoolHandle,
 }
 
 impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> WitnessIndex<'cs, 'alloc, P> {
@@ -65,9 +72,17 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> WitnessIndex<'cs, '
 			cs,
 			allocator,
 			tables: cs.tables.iter().map(Either::Left).collect(),
+			thread_pool: ThreadPoolHandle::GLOBAL,
 		}
 	}
 
+	/// Fills the tables with [`Self::fill_table_parallel`] on the given thread pool instead of
+	/// the global one.
+	pub fn with_thread_pool(mut self, thread_pool: ThreadPoolHandle) -> Self {
+		self.thread_pool = thread_pool;
+		self
+	}
+
 	pub fn init_table(
 		&mut self,
 		table_id: TableId,
@@ -110,6 +125,7 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> WitnessIndex<'cs, '
 	) -> Result<(), Error> {
 		self.init_and_fill_table(
 			filler.id(),
+			filler.log_rows_per_event(),
 			|table_witness, rows| table_witness.fill_sequential(filler, rows),
 			rows,
 		)
@@ -120,9 +136,11 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> WitnessIndex<'cs, '
 		T: TableFiller<P> + Sync,
 		T::Event: Sync,
 	{
+		let thread_pool = self.thread_pool.clone();
 		self.init_and_fill_table(
 			filler.id(),
-			|table_witness, rows| table_witness.fill_parallel(filler, rows),
+			filler.log_rows_per_event(),
+			|table_witness, rows| thread_pool.install(|| table_witness.fill_parallel(filler, rows)),
 			rows,
 		)
 	}
@@ -130,18 +148,26 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> WitnessIndex<'cs, '
 	fn init_and_fill_table<Event>(
 		&mut self,
 		table_id: TableId,
-		fill: impl FnOnce(&mut TableWitnessIndex<'cs, 'alloc, P>, &[Event]) -> Result<(), Error>,
+		log_rows_per_event: usize,
+		fill: impl Fn(&mut TableWitnessIndex<'cs, 'alloc, P>, &[Event]) -> Result<(), Error>,
 		rows: &[Event],
 	) -> Result<(), Error> {
 		match self.tables.get_mut(table_id) {
 			Some(entry) => match entry {
 				Either::Right(witness) => fill(witness, rows),
+				Either::Left(table) if table.max_log_size().is_some() => {
+					let table = *table;
+					self.fill_table_and_copies(table, log_rows_per_event, fill, rows)
+				}
 				Either::Left(table) => {
 					if rows.is_empty() {
 						Ok(())
 					} else {
-						let mut table_witness =
-							TableWitnessIndex::new(self.allocator, table, rows.len())?;
+						let mut table_witness = TableWitnessIndex::new(
+							self.allocator,
+							table,
+							rows.len() << log_rows_per_event,
+						)?;
 						fill(&mut table_witness, rows)?;
 						*entry = Either::Right(table_witness);
 						Ok(())
@@ -152,6 +178,44 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> WitnessIndex<'cs, '
 		}
 	}
 
+	/// Fills a table with copies and its copies with consecutive chunks of the rows.
+	///
+	/// Every event spans `2^log_rows_per_event` rows, so the rows of one event are never split
+	/// across copies.
+	fn fill_table_and_copies<Event>(
+		&mut self,
+		table: &Table<F>,
+		log_rows_per_event: usize,
+		fill: impl Fn(&mut TableWitnessIndex<'cs, 'alloc, P>, &[Event]) -> Result<(), Error>,
+		rows: &[Event],
+	) -> Result<(), Error> {
+		let max_log_size = table.max_log_size().expect("the table has copies");
+		if log_rows_per_event > max_log_size {
+			return Err(Error::IncompatibleRowsPerEvent {
+				table_id: table.id(),
+				size: 1 << max_log_size,
+				rows_per_event: 1 << log_rows_per_event,
+			});
+		}
+		let max_size = 1 << max_log_size;
+		let capacity = (table.copies().len() + 1) * max_size;
+		if rows.len() << log_rows_per_event > capacity {
+			return Err(Error::TableCapacityExceeded {
+				table_id: table.id(),
+				size: rows.len() << log_rows_per_event,
+				capacity,
+			});
+		}
+
+		let table_ids = iter::once(table.id()).chain(table.copies().iter().copied());
+		let max_events = max_size >> log_rows_per_event;
+		for (table_id, rows) in iter::zip(table_ids, rows.chunks(max_events)) {
+			let table_witness = self.init_table(table_id, rows.len() << log_rows_per_event)?;
+			fill(table_witness, rows)?;
+		}
+		Ok(())
+	}
+
 	/// Returns the sizes of all tables in the witness, indexed by table ID.
 	pub fn table_sizes(&self) -> Vec<usize> {
 		self.tables
@@ -163,6 +227,28 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> WitnessIndex<'cs, '
 			.collect()
 	}
 
+	/// Runs cheap checks of the witness before the constraint system is compiled and proven.
+	///
+	/// The checks compare the table sizes with their size specifications, look for columns that
+	/// were evidently not filled, look for channels that are only flushed in one direction by the
+	/// tables and the `boundaries`, and look for zero constraints with a degree that is too high
+	/// to be proven. The findings are returned in that order, and are meant to be shown to the
+	/// developer of the constraint system. See [`PreflightFinding`] for the details.
+	pub fn preflight(&self, boundaries: &[Boundary<F>]) -> Vec<PreflightFinding> {
+		let table_sizes = self.table_sizes();
+		let unfilled_columns = self
+			.tables
+			.iter()
+			.filter_map(|entry| entry.as_ref().right())
+			.flat_map(|table_witness| table_witness.unfilled_columns());
+
+		preflight::check_table_sizes(self.cs, &table_sizes)
+			.chain(unfilled_columns)
+			.chain(preflight::check_channels(self.cs, &table_sizes, boundaries))
+			.chain(preflight::check_constraint_degrees(self.cs, &table_sizes))
+			.collect()
+	}
+
 	fn mk_column_witness<'a>(
 		log_capacity: usize,
 		shape: ColumnShape,
@@ -184,6 +270,11 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> WitnessIndex<'cs, '
 
 	/// Converts this witness into binius_core's [`MultilinearExtensionIndex`].
 	///
+	/// The conversion does not copy the witness data. The multilinears of the index borrow the
+	/// column data from the allocator, which is why the index lives for `'alloc`, so the memory
+	/// held by the witness does not grow at the handoff to the prover. Columns that share data,
+	/// such as packed columns and the columns they pack, share it in the index as well.
+	///
 	/// Note that this function must be called only after the [`ConstraintSystem::compile`].
 	pub fn into_multilinear_extension_index(self) -> MultilinearExtensionIndex<'alloc, P>
 	where
@@ -371,10 +462,15 @@ enum WitnessColumnInfo<T> {
 type WitnessDataMut<'a, P> = WitnessColumnInfo<&'a mut [P]>;
 
 impl<'a, P: PackedField> WitnessDataMut<'a, P> {
+	/// Allocates a column of zeroes.
+	///
+	/// The allocator may hand out memory that held an earlier witness, so the column is zeroed
+	/// explicitly.
 	pub fn new_owned(allocator: &'a HostBumpAllocator<'a, P>, log_underlier_count: usize) -> Self {
 		let slice = allocator
 			.alloc(1 << log_underlier_count)
 			.expect("failed to allocate witness data slice");
+		fill_zeroes(slice);
 
 		Self::Owned(slice)
 	}
@@ -408,6 +504,15 @@ fn immutable_witness_index_columns<P: PackedField>(
 	result
 }
 
+/// Wraps an error returned by a [`TableFiller`] with the table it was filling.
+fn table_fill_error<F: TowerField>(table: &Table<F>, source: anyhow::Error) -> Error {
+	Error::TableFill {
+		table_id: table.id,
+		table_name: table.name.clone(),
+		source,
+	}
+}
+
 impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> TableWitnessIndex<'cs, 'alloc, P> {
 	pub(crate) fn new(
 		allocator: &'alloc HostBumpAllocator<'alloc, P>,
@@ -469,6 +574,27 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> TableWitnessIndex<'
 		self.table.id
 	}
 
+	/// Returns the columns that hold only zeros although they cannot be zero.
+	fn unfilled_columns(&self) -> impl Iterator<Item = PreflightFinding> + '_ {
+		self.table
+			.columns
+			.iter()
+			.zip(&self.cols)
+			.filter(|(info, _)| match &info.col {
+				ColumnDef::Constant { data, .. } => data.iter().any(|value| *value != F::ZERO),
+				_ => info.is_nonzero,
+			})
+			.filter(|(_, col)| match &col.data {
+				WitnessDataMut::Owned(data) => data.iter().all(|packed| *packed == P::zero()),
+				WitnessDataMut::SameAsIndex(_) => false,
+			})
+			.map(|(info, _)| PreflightFinding::UnfilledColumn {
+				table_id: self.table.id,
+				table_name: self.table.name.clone(),
+				column_name: info.name.clone(),
+			})
+	}
+
 	pub fn capacity(&self) -> usize {
 		1 << self.log_capacity
 	}
@@ -486,8 +612,10 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> TableWitnessIndex<'
 		TableWitnessSegment {
 			table: self.table,
 			cols,
+			rows: RefCell::new(None),
 			log_size: self.log_capacity,
 			index: 0,
+			log_rows_per_event: 0,
 		}
 	}
 
@@ -515,6 +643,24 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> TableWitnessIndex<'
 		self.fill_parallel_with_segment_size(table, rows, log_size)
 	}
 
+	/// Checks that `n_events` events of `2^log_rows_per_event` rows each fill the table.
+	fn check_event_count(&self, log_rows_per_event: usize, n_events: usize) -> Result<(), Error> {
+		if self.size % (1 << log_rows_per_event) != 0 {
+			return Err(Error::IncompatibleRowsPerEvent {
+				table_id: self.table.id(),
+				size: self.size,
+				rows_per_event: 1 << log_rows_per_event,
+			});
+		}
+		if n_events != self.size >> log_rows_per_event {
+			return Err(Error::IncorrectNumberOfTableEvents {
+				expected: self.size >> log_rows_per_event,
+				actual: n_events,
+			});
+		}
+		Ok(())
+	}
+
 	fn optimal_segment_size_heuristic(&self) -> usize {
 		// As a heuristic, choose log_size so that the median column segment size is 4 KiB.
 		const TARGET_SEGMENT_LOG_BITS: usize = 12 + 3;
@@ -541,20 +687,20 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> TableWitnessIndex<'
 		rows: &[T::Event],
 		log_size: usize,
 	) -> Result<(), Error> {
-		if rows.len() != self.size {
-			return Err(Error::IncorrectNumberOfTableEvents {
-				expected: self.size,
-				actual: rows.len(),
-			});
-		}
+		let log_rows_per_event = table.log_rows_per_event();
+		self.check_event_count(log_rows_per_event, rows.len())?;
 
-		let mut segmented_view = TableWitnessSegmentedView::new(self, log_size);
+		let table_info = self.table;
+		// A segment must hold at least one event.
+		let mut segmented_view =
+			TableWitnessSegmentedView::new(self, log_size.max(log_rows_per_event));
 
 		// Overwrite log_size because it may need to get clamped.
 		let log_size = segmented_view.log_segment_size;
-		let segment_size = 1 << log_size;
+		let segment_size = 1 << (log_size - log_rows_per_event);
 
-		// rows.len() equals self.size and self.size is check to be non-zero in the constructor
+		// rows.len() is self.size divided by the rows per event and self.size is checked to be
+		// non-zero in the constructor
 		debug_assert_ne!(rows.len(), 0);
 		// number of chunks is rounded up
 		let n_chunks = (rows.len() - 1) / segment_size + 1;
@@ -567,9 +713,11 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> TableWitnessIndex<'
 			// by taking n_chunks - 1, we guarantee that all row chunks are full
 			.zip(rows.chunks(segment_size).take(n_chunks - 1))
 			.try_for_each(|(mut witness_segment, row_chunk)| {
+				witness_segment.log_rows_per_event = log_rows_per_event;
 				table
 					.fill(row_chunk, &mut witness_segment)
-					.map_err(Error::TableFill)
+					.map_err(|source| table_fill_error(table_info, source))?;
+				witness_segment.scatter_rows()
 			})?;
 
 		// Fill the last segment. There may not be enough events to match the size of the segment,
@@ -596,9 +744,11 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> TableWitnessIndex<'
 		let mut witness_segment = partial_chunk_segment_iter.next().expect(
 			"segmented_view.split_at called with 1 must return a view with exactly one segment",
 		);
+		witness_segment.log_rows_per_event = log_rows_per_event;
 		table
 			.fill(row_chunk, &mut witness_segment)
-			.map_err(Error::TableFill)?;
+			.map_err(|source| table_fill_error(table_info, source))?;
+		witness_segment.scatter_rows()?;
 		assert!(partial_chunk_segment_iter.next().is_none());
 
 		// Finally, copy the last filled segment to the remaining segments. This should satisfy all
@@ -637,24 +787,24 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> TableWitnessIndex<'
 		T: TableFiller<P> + Sync,
 		T::Event: Sync,
 	{
-		if rows.len() != self.size {
-			return Err(Error::IncorrectNumberOfTableEvents {
-				expected: self.size,
-				actual: rows.len(),
-			});
-		}
+		let log_rows_per_event = table.log_rows_per_event();
+		self.check_event_count(log_rows_per_event, rows.len())?;
 
 		// This implementation duplicates a lot of code with `fill_sequential_with_segment_size`.
 		// We could either refactor to deduplicate or just remove `fill_sequential` once this
 		// method is more battle-tested.
 
-		let mut segmented_view = TableWitnessSegmentedView::new(self, log_size);
+		let table_info = self.table;
+		// A segment must hold at least one event.
+		let mut segmented_view =
+			TableWitnessSegmentedView::new(self, log_size.max(log_rows_per_event));
 
 		// Overwrite log_size because it may need to get clamped.
 		let log_size = segmented_view.log_segment_size;
-		let segment_size = 1 << log_size;
+		let segment_size = 1 << (log_size - log_rows_per_event);
 
-		// rows.len() equals self.size and self.size is check to be non-zero in the constructor
+		// rows.len() is self.size divided by the rows per event and self.size is checked to be
+		// non-zero in the constructor
 		debug_assert_ne!(rows.len(), 0);
 		// number of chunks is rounded up
 		let n_chunks = (rows.len() - 1) / segment_size + 1;
@@ -667,9 +817,11 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> TableWitnessIndex<'
 			// by taking n_chunks - 1, we guarantee that all row chunks are full
 			.zip(rows.par_chunks(segment_size).take(n_chunks - 1))
 			.try_for_each(|(mut witness_segment, row_chunk)| {
+				witness_segment.log_rows_per_event = log_rows_per_event;
 				table
 					.fill(row_chunk, &mut witness_segment)
-					.map_err(Error::TableFill)
+					.map_err(|source| table_fill_error(table_info, source))?;
+				witness_segment.scatter_rows()
 			})?;
 
 		// Fill the last segment. There may not be enough events to match the size of the segment,
@@ -696,9 +848,11 @@ impl<'cs, 'alloc, F: TowerField, P: PackedField<Scalar = F>> TableWitnessIndex<'
 		let mut witness_segment = partial_chunk_segment_iter.next().expect(
 			"segmented_view.split_at called with 1 must return a view with exactly one segment",
 		);
+		witness_segment.log_rows_per_event = log_rows_per_event;
 		table
 			.fill(row_chunk, &mut witness_segment)
-			.map_err(Error::TableFill)?;
+			.map_err(|source| table_fill_error(table_info, source))?;
+		witness_segment.scatter_rows()?;
 		assert!(partial_chunk_segment_iter.next().is_none());
 
 		// Finally, copy the last filled segment to the remaining segments. This should satisfy all
@@ -855,8 +1009,10 @@ impl<'a, F: TowerField, P: PackedField<Scalar = F>> TableWitnessSegmentedView<'a
 			.map(move |(index, cols)| TableWitnessSegment {
 				table,
 				cols,
+				rows: RefCell::new(None),
 				log_size: log_segment_size,
 				index: start_index + index,
+				log_rows_per_event: 0,
 			});
 			itertools::Either::Right(iter)
 		}
@@ -914,8 +1070,10 @@ impl<'a, F: TowerField, P: PackedField<Scalar = F>> TableWitnessSegmentedView<'a
 			TableWitnessSegment {
 				table,
 				cols: col_strides,
+				rows: RefCell::new(None),
 				log_size: log_segment_size,
 				index: start_index + i,
+				log_rows_per_event: 0,
 			}
 		})
 	}
@@ -936,11 +1094,17 @@ where
 	///
 	/// The order of the columns corresponds to the same order as defined in the table.
 	cols: Vec<RefCellData<'a, P>>,
+	/// The row-major cells written through [`Self::rows_mut`], which are not yet scattered into
+	/// `cols`.
+	rows: RefCell<Option<RowMajorRows<'a, P>>>,
 	#[get_copy = "pub"]
 	log_size: usize,
 	/// The index of the segment in the segmented table witness.
 	#[get_copy = "pub"]
 	index: usize,
+	/// The binary logarithm of the number of consecutive rows that every event spans.
+	#[get_copy = "pub"]
+	log_rows_per_event: usize,
 }
 
 impl<'a, F: TowerField, P: PackedField<Scalar = F>> TableWitnessSegment<'a, P> {
@@ -951,12 +1115,13 @@ impl<'a, F: TowerField, P: PackedField<Scalar = F>> TableWitnessSegment<'a, P> {
 	where
 		P: PackedExtension<FSub>,
 	{
-		if col.table_id != self.table.id() {
+		if !self.table.has_columns_of(col.table_id) {
 			return Err(Error::TableMismatch {
 				column_table_id: col.table_id,
 				witness_table_id: self.table.id(),
 			});
 		}
+		check_tower_level(self.table, col)?;
 
 		let col = self
 			.get_col_data(col.id())
@@ -973,12 +1138,13 @@ impl<'a, F: TowerField, P: PackedField<Scalar = F>> TableWitnessSegment<'a, P> {
 		P: PackedExtension<FSub>,
 		F: ExtensionField<FSub>,
 	{
-		if col.table_id != self.table.id() {
+		if !self.table.has_columns_of(col.table_id) {
 			return Err(Error::TableMismatch {
 				column_table_id: col.table_id,
 				witness_table_id: self.table.id(),
 			});
 		}
+		check_tower_level(self.table, col)?;
 
 		let col = self
 			.get_col_data(col.id())
@@ -1021,6 +1187,7 @@ impl<'a, F: TowerField, P: PackedField<Scalar = F>> TableWitnessSegment<'a, P> {
 		P: PackedExtension<FSub> + PackedFieldIndexable,
 		F: ExtensionField<FSub> + Pod,
 	{
+		check_tower_level(self.table, col)?;
 		let col = self
 			.get_col_data(col.id())
 			.ok_or_else(|| Error::MissingColumn(col.id()))?;
@@ -1036,12 +1203,13 @@ impl<'a, F: TowerField, P: PackedField<Scalar = F>> TableWitnessSegment<'a, P> {
 		P: PackedExtension<FSub> + PackedFieldIndexable,
 		F: ExtensionField<FSub> + Pod,
 	{
-		if col.table_id != self.table.id() {
+		if !self.table.has_columns_of(col.table_id) {
 			return Err(Error::TableMismatch {
 				column_table_id: col.table_id,
 				witness_table_id: self.table.id(),
 			});
 		}
+		check_tower_level(self.table, col)?;
 
 		let col = self
 			.get_col_data(col.id())
@@ -1050,6 +1218,169 @@ impl<'a, F: TowerField, P: PackedField<Scalar = F>> TableWitnessSegment<'a, P> {
 		Ok(RefMut::map(col_ref, |col| must_cast_slice_mut(P::unpack_scalars_mut(col))))
 	}
 
+	/// Returns the cells of a column as rows of `T` values, see [`ColumnRows`].
+	///
+	/// For example, the rows of a `Col<B8, 8>` viewed as `u8` hold the 8 bytes of each cell.
+	///
+	/// ## Panics
+	///
+	/// * if a cell of the column is not a whole number of `T` values
+	pub fn get_rows_as<T: Pod, FSub: TowerField, const V: usize>(
+		&self,
+		col: Col<FSub, V>,
+	) -> Result<ColumnRows<T>, Error>
+	where
+		P: PackedExtension<FSub> + PackedFieldIndexable,
+		F: ExtensionField<FSub> + Pod,
+	{
+		Ok(ColumnRows::new(self.get_as(col)?, col.shape()))
+	}
+
+	/// Returns the cells of a column as mutable rows of `T` values, see [`ColumnRowsMut`].
+	///
+	/// ## Panics
+	///
+	/// * if a cell of the column is not a whole number of `T` values
+	pub fn get_rows_mut_as<T: Pod, FSub: TowerField, const V: usize>(
+		&self,
+		col: Col<FSub, V>,
+	) -> Result<ColumnRowsMut<T>, Error>
+	where
+		P: PackedExtension<FSub> + PackedFieldIndexable,
+		F: ExtensionField<FSub> + Pod,
+	{
+		Ok(ColumnRowsMut::new(self.get_mut_as(col)?, col.shape()))
+	}
+
+	/// Fills a column of subfield values, such as one added with [`TableBuilder::add_unpacked`],
+	/// from the column that packs them, which must already be populated.
+	///
+	/// [`TableBuilder::add_unpacked`]: super::TableBuilder::add_unpacked
+	pub fn fill_unpacked<
+		FSubSub,
+		const VALUES_PER_ROW_SUB: usize,
+		FSub,
+		const VALUES_PER_ROW: usize,
+	>(
+		&self,
+		unpacked: Col<FSubSub, VALUES_PER_ROW_SUB>,
+		packed: Col<FSub, VALUES_PER_ROW>,
+	) -> Result<(), Error>
+	where
+		FSub: TowerField + ExtensionField<FSubSub>,
+		FSubSub: TowerField,
+		P: PackedExtension<FSub> + PackedExtension<FSubSub> + PackedFieldIndexable,
+		F: ExtensionField<FSub> + Pod,
+	{
+		assert_eq!(
+			unpacked.shape().log_cell_size(),
+			packed.shape().log_cell_size(),
+			"the columns must have the same number of bits per row"
+		);
+		// The subfield values are packed in little-endian order in the tower basis, so the packed
+		// column and the unpacked column have the same bytes.
+		let packed = self.get_as::<u8, _, VALUES_PER_ROW>(packed)?;
+		let mut unpacked = self.get_mut_as::<u8, _, VALUES_PER_ROW_SUB>(unpacked)?;
+		unpacked.copy_from_slice(&packed);
+		Ok(())
+	}
+
+	/// Fills the derived columns of a byte string column added with
+	/// [`TableBuilder::add_packed_bytes`], from the byte column, which must already be populated.
+	///
+	/// [`TableBuilder::add_packed_bytes`]: super::TableBuilder::add_packed_bytes
+	pub fn fill_packed_bytes<const N: usize>(&self, bytes: &PackedBytes<N>) -> Result<(), Error>
+	where
+		P: PackedExtension<B8> + PackedFieldIndexable,
+		F: ExtensionField<B8> + Pod,
+	{
+		let source = self.get_rows_as::<u8, B8, N>(bytes.bytes)?;
+		for (i, &chunk) in bytes.chunks.iter().enumerate() {
+			let mut chunk = self.get_rows_mut_as::<u8, B8, BYTES_PER_CHANNEL_VALUE>(chunk)?;
+			for (row, chunk_row) in iter::zip(source.iter(), chunk.iter_mut()) {
+				if N < BYTES_PER_CHANNEL_VALUE {
+					chunk_row[..N].copy_from_slice(row);
+					chunk_row[N..].fill(0);
+				} else {
+					chunk_row.copy_from_slice(
+						&row[i * BYTES_PER_CHANNEL_VALUE..(i + 1) * BYTES_PER_CHANNEL_VALUE],
+					);
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Fills the row indices of the public columns of the table, see [`Table::public_index`].
+	///
+	/// This does nothing if the table has no public columns.
+	pub fn fill_public_index(&self) -> Result<(), Error>
+	where
+		P: PackedExtension<B32> + PackedFieldIndexable,
+		F: ExtensionField<B32> + Pod,
+	{
+		let Some(public_index) = self.table.public_index() else {
+			return Ok(());
+		};
+		let mut indices = self.get_mut_as::<u32, B32, 1>(public_index)?;
+		let start_index = self.index << self.log_size;
+		for (i, index) in indices.iter_mut().enumerate() {
+			*index = (start_index + i) as u32;
+		}
+		Ok(())
+	}
+
+	/// Fills a shifted bit column, such as one added with [`TableBuilder::add_rotated`], from the
+	/// column it is derived from.
+	///
+	/// The shift must be within blocks of 8, 16, 32 or 64 bits, and the column it is derived from
+	/// must already be populated.
+	///
+	/// [`TableBuilder::add_rotated`]: super::TableBuilder::add_rotated
+	pub fn fill_shifted<const V: usize>(&self, col: Col<B1, V>) -> Result<(), Error>
+	where
+		P: PackedExtension<B1> + PackedFieldIndexable,
+		F: Pod,
+	{
+		let mut dst = self.get_mut_as::<u8, B1, V>(col)?;
+		let ColumnDef::Shifted {
+			col: src,
+			offset,
+			log_block_size,
+			variant,
+		} = self.table[col.table_index].col
+		else {
+			return Err(Error::UnsupportedShiftedColumn(col.id()));
+		};
+		let width = 1 << log_block_size;
+		if !(8..=64).contains(&width) || offset == 0 || offset >= width {
+			return Err(Error::UnsupportedShiftedColumn(col.id()));
+		}
+
+		let src = self
+			.get_col_data(src)
+			.ok_or(Error::MissingColumn(src))?
+			.try_borrow()
+			.map_err(Error::WitnessBorrow)?;
+		let src: &[u8] = must_cast_slice(P::unpack_scalars(&src));
+
+		let mask = u64::MAX >> (64 - width);
+		let shift = |word: u64| match variant {
+			ShiftVariant::CircularLeft => ((word << offset) | (word >> (width - offset))) & mask,
+			ShiftVariant::LogicalLeft => (word << offset) & mask,
+			ShiftVariant::LogicalRight => word >> offset,
+		};
+
+		// Bits are packed in little-endian order, so a block is a little-endian integer.
+		let n_bytes = width / 8;
+		for (dst, src) in iter::zip(dst.chunks_exact_mut(n_bytes), src.chunks_exact(n_bytes)) {
+			let mut word = [0u8; 8];
+			word[..n_bytes].copy_from_slice(src);
+			dst.copy_from_slice(&shift(u64::from_le_bytes(word)).to_le_bytes()[..n_bytes]);
+		}
+		Ok(())
+	}
+
 	/// Evaluate an expression over columns that are assumed to be already populated.
 	///
 	/// This function evaluates an expression over the columns in the segment and returns an
@@ -1073,7 +1404,7 @@ impl<'a, F: TowerField, P: PackedField<Scalar = F>> TableWitnessSegment<'a, P> {
 					table_id: self.table.id(),
 					log_vals_per_row,
 				})?;
-		let expr_circuit = ArithCircuit::from(expr.expr());
+		let expr_circuit = ArithCircuit::from(expr.expr()).optimize();
 
 		let col_refs = partition
 			.columns
@@ -1109,14 +1440,217 @@ impl<'a, F: TowerField, P: PackedField<Scalar = F>> TableWitnessSegment<'a, P> {
 		// get split up in practice, it's not a problem yet. If we see stack overflows, we should
 		// split up the evaluation into multiple batches.
 		let mut evals = zeroed_vec(1 << log_packed_elems);
-		ArithCircuitPoly::new(expr_circuit).batch_evaluate(&cols, &mut evals)?;
+		ArithCircuitEvaluator::new(&expr_circuit).batch_evaluate(&cols, &mut evals)?;
 		Ok(evals.into_iter())
 	}
 
+	/// Returns the byte-aligned columns of the segment in row-major order.
+	///
+	/// This requires the table to use the [`WitnessLayout::RowMajor`] layout. Cells written
+	/// through the rows are scattered into the columns after the table filler returns, or by
+	/// [`Self::scatter_rows`] when the segment is filled outside of a table filler. Columns that
+	/// are not written through the rows keep the values written with [`Self::get_mut`].
+	pub fn rows_mut(&self) -> Result<RefMut<RowMajorRows<'a, P>>, Error>
+	where
+		P: PackedFieldIndexable,
+		F: Pod,
+	{
+		if self.table.witness_layout() != WitnessLayout::RowMajor {
+			return Err(Error::RowMajorLayoutDisabled {
+				table_id: self.table.id(),
+			});
+		}
+		let rows = self
+			.rows
+			.try_borrow_mut()
+			.map_err(Error::WitnessBorrowMut)?;
+		Ok(RefMut::map(rows, |rows| {
+			rows.get_or_insert_with(|| {
+				RowMajorRows::new(self.table, self.log_size, packed_bytes_mut::<P>)
+			})
+		}))
+	}
+
+	/// Scatters the cells written through [`Self::rows_mut`] into the columns.
+	///
+	/// This does nothing if no rows were written.
+	pub fn scatter_rows(&mut self) -> Result<(), Error> {
+		match self.rows.get_mut().take() {
+			Some(rows) => rows.scatter(&self.cols),
+			None => Ok(()),
+		}
+	}
+
+	/// Calls `f` in parallel on segments that each hold some of the columns of this segment.
+	///
+	/// `parts[i]` holds the columns that part `i` reads and the columns that it writes. The
+	/// segment passed with index `i` holds these columns, and accessing any other column through
+	/// it returns [`Error::MissingColumn`]. Columns that several parts read are copied for all but
+	/// one of them. If a column that one part writes is accessed by another part, possibly through
+	/// a column sharing its data, `f` is instead called on this segment for every part in order.
+	/// Cells that a part writes through [`Self::rows_mut`] are scattered into the columns right
+	/// after the part is filled.
+	pub(super) fn par_for_each_part(
+		&mut self,
+		parts: &[(Vec<ColumnId>, Vec<ColumnId>)],
+		f: impl Fn(usize, &mut TableWitnessSegment<P>) -> anyhow::Result<()> + Sync,
+	) -> anyhow::Result<()>
+	where
+		P: Send,
+	{
+		let n_cols = self.cols.len();
+		let same_as = self
+			.cols
+			.iter()
+			.map(|col| match col {
+				RefCellData::Owned(_) => None,
+				RefCellData::SameAsIndex(index) => Some(*index),
+			})
+			.collect::<Vec<_>>();
+
+		// Resolves columns to the indices of the columns that own their data.
+		let data_indices = |cols: &[ColumnId]| {
+			let mut indices = cols
+				.iter()
+				.filter(|col| self.table.has_columns_of(col.table_id))
+				.map(|col| {
+					let mut index = col.table_index.0;
+					while let Some(same_as) = same_as[index] {
+						index = same_as;
+					}
+					index
+				})
+				.collect::<Vec<_>>();
+			indices.sort_unstable();
+			indices.dedup();
+			indices
+		};
+		let part_writes = parts
+			.iter()
+			.map(|(_, writes)| data_indices(writes))
+			.collect::<Vec<_>>();
+		let part_cols = parts
+			.iter()
+			.map(|(reads, writes)| data_indices(&[&reads[..], &writes[..]].concat()))
+			.collect::<Vec<_>>();
+
+		let mut owners = vec![None; n_cols];
+		let mut n_accesses = vec![0; n_cols];
+		for (part, indices) in part_cols.iter().enumerate() {
+			for &index in indices {
+				owners[index].get_or_insert(part);
+				n_accesses[index] += 1;
+			}
+		}
+		if part_writes
+			.iter()
+			.flatten()
+			.any(|&index| n_accesses[index] > 1)
+		{
+			for part in 0..parts.len() {
+				f(part, self)?;
+				self.scatter_rows()?;
+			}
+			return Ok(());
+		}
+
+		let mut data = self
+			.cols
+			.iter_mut()
+			.map(|col| match col {
+				RefCellData::Owned(data) => Some(&mut **data.get_mut()),
+				RefCellData::SameAsIndex(_) => None,
+			})
+			.collect::<Vec<_>>();
+		let mut copies = part_cols
+			.iter()
+			.enumerate()
+			.map(|(part, indices)| {
+				indices
+					.iter()
+					.filter(|&&index| owners[index] != Some(part))
+					.map(|&index| {
+						let col = data[index].as_deref().expect("indices own their data");
+						(index, col.to_vec())
+					})
+					.collect::<Vec<_>>()
+			})
+			.collect::<Vec<_>>();
+
+		let mut part_data = (0..parts.len())
+			.map(|_| (0..n_cols).map(|_| None).collect::<Vec<_>>())
+			.collect::<Vec<_>>();
+		for (index, owner) in owners.into_iter().enumerate() {
+			if let Some(part) = owner {
+				part_data[part][index] = data[index].take();
+			}
+		}
+		for (part_data, copies) in iter::zip(&mut part_data, &mut copies) {
+			for (index, copy) in copies {
+				part_data[*index] = Some(&mut copy[..]);
+			}
+		}
+
+		let segments = part_data
+			.into_iter()
+			.map(|data| TableWitnessSegment {
+				table: self.table,
+				cols: iter::zip(data, &same_as)
+					.map(|(data, same_as)| match (data, same_as) {
+						(Some(data), _) => RefCellData::Owned(RefCell::new(data)),
+						(None, Some(index)) => RefCellData::SameAsIndex(*index),
+						// The index is out of range, so accessing the column reports it missing.
+						(None, None) => RefCellData::SameAsIndex(n_cols),
+					})
+					.collect(),
+				rows: RefCell::new(None),
+				log_size: self.log_size,
+				index: self.index,
+				log_rows_per_event: self.log_rows_per_event,
+			})
+			.collect::<Vec<_>>();
+
+		segments
+			.into_par_iter()
+			.enumerate()
+			.try_for_each(|(part, mut segment)| {
+				f(part, &mut segment)?;
+				segment.scatter_rows()?;
+				Ok(())
+			})
+	}
+
 	pub fn size(&self) -> usize {
 		1 << self.log_size
 	}
 
+	/// Returns the number of events in the segment.
+	pub fn n_events(&self) -> usize {
+		1 << (self.log_size - self.log_rows_per_event)
+	}
+
+	/// Returns the range of segment rows that the event with the given index in the segment spans.
+	///
+	/// ## Panics
+	///
+	/// * if `event_index` is not less than [`Self::n_events`]
+	pub fn event_rows(&self, event_index: usize) -> Range<usize> {
+		assert!(event_index < self.n_events(), "event index {event_index} is out of range");
+		let start = event_index << self.log_rows_per_event;
+		start..start + (1 << self.log_rows_per_event)
+	}
+
+	/// Returns the index of the event that spans a segment row and the offset of the row within the
+	/// event.
+	///
+	/// ## Panics
+	///
+	/// * if `row` is not less than [`Self::size`]
+	pub fn row_event(&self, row: usize) -> (usize, usize) {
+		assert!(row < self.size(), "row {row} is out of range");
+		(row >> self.log_rows_per_event, row & ((1 << self.log_rows_per_event) - 1))
+	}
+
 	fn get_col_data(&self, column_id: ColumnId) -> Option<&RefCell<&'a mut [P]>> {
 		let column_index = column_id.table_index.0;
 		self.get_col_data_by_index(column_index)
@@ -1150,7 +1684,7 @@ where
 			.get_col_data(col_id)
 			.ok_or_else(|| Error::MissingColumn(col_id))?;
 		let col_ref = col.try_borrow().map_err(Error::WitnessBorrow)?;
-		let tower_level = self.table[col_id].shape.tower_height;
+		let tower_level = self.table[col_id.table_index].shape.tower_height;
 		let ret: Box<dyn WitnessColView<_>> = match tower_level {
 			0 => Box::new(WitnessColViewImpl(Ref::map(col_ref, |packed| {
 				PackedExtension::<B1>::cast_bases(packed)
@@ -1184,7 +1718,7 @@ where
 			.get_col_data(col_id)
 			.ok_or_else(|| Error::MissingColumn(col_id))?;
 		let col_ref = col.try_borrow_mut().map_err(Error::WitnessBorrowMut)?;
-		let tower_level = self.table[col_id].shape.tower_height;
+		let tower_level = self.table[col_id.table_index].shape.tower_height;
 		let ret: Box<dyn WitnessColViewMut<_>> = match tower_level {
 			0 => Box::new(WitnessColViewImpl(RefMut::map(col_ref, |packed| {
 				PackedExtension::<B1>::cast_bases_mut(packed)
@@ -1256,6 +1790,133 @@ where
 	}
 }
 
+/// The byte-aligned columns of a witness segment in row-major order, see
+/// [`WitnessLayout::RowMajor`].
+///
+/// Every row holds one cell of each column whose cells are a whole number of bytes. Cells use
+/// the same little-endian tower basis representation as the columns, so setting a cell here is
+/// equivalent to writing it through [`TableWitnessSegment::get_mut_as`].
+#[derive(Debug)]
+pub struct RowMajorRows<'a, P>
+where
+	P: PackedField,
+	P::Scalar: TowerField,
+{
+	table: &'a Table<P::Scalar>,
+	data: Vec<u8>,
+	row_bytes: usize,
+	/// The byte range within a row of the cell of every table column, if the column has one.
+	cells: Vec<Option<Range<usize>>>,
+	/// Whether each column was written through the rows.
+	written: Vec<bool>,
+	/// Views a column of the segment as bytes.
+	as_bytes: fn(&mut [P]) -> &mut [u8],
+}
+
+impl<'a, P> RowMajorRows<'a, P>
+where
+	P: PackedField,
+	P::Scalar: TowerField,
+{
+	fn new(
+		table: &'a Table<P::Scalar>,
+		log_size: usize,
+		as_bytes: fn(&mut [P]) -> &mut [u8],
+	) -> Self {
+		let mut row_bytes = 0;
+		let cells = table
+			.columns
+			.iter()
+			.map(|info| {
+				let log_cell_bits = info.shape.log_cell_size();
+				if matches!(info.col, ColumnDef::Packed { .. }) || log_cell_bits < 3 {
+					return None;
+				}
+				let start = row_bytes;
+				row_bytes += 1 << (log_cell_bits - 3);
+				Some(start..row_bytes)
+			})
+			.collect::<Vec<_>>();
+
+		Self {
+			table,
+			data: vec![0; row_bytes << log_size],
+			row_bytes,
+			written: vec![false; cells.len()],
+			cells,
+			as_bytes,
+		}
+	}
+
+	/// Returns the number of rows.
+	pub fn n_rows(&self) -> usize {
+		self.data.len().checked_div(self.row_bytes).unwrap_or(0)
+	}
+
+	/// Sets the cell of a column in a row.
+	///
+	/// `T` must have the size of a cell of the column, as with [`TableWitnessSegment::get_mut_as`].
+	///
+	/// ## Panics
+	///
+	/// * if `row` is not less than [`Self::n_rows`]
+	/// * if the size of `T` differs from the size of a cell of the column
+	pub fn set<T: Pod, FSub: TowerField, const V: usize>(
+		&mut self,
+		row: usize,
+		col: Col<FSub, V>,
+		value: T,
+	) -> Result<(), Error> {
+		if !self.table.has_columns_of(col.table_id) {
+			return Err(Error::TableMismatch {
+				column_table_id: col.table_id,
+				witness_table_id: self.table.id(),
+			});
+		}
+		check_tower_level(self.table, col)?;
+		let cell = self.cells[col.table_index.0]
+			.clone()
+			.ok_or_else(|| Error::NotRowMajorColumn(col.id()))?;
+		let value = bytemuck::bytes_of(&value);
+		assert_eq!(value.len(), cell.len(), "the value must have the size of a cell of the column");
+		assert!(row < self.n_rows(), "row {row} is out of range");
+
+		let row_start = row * self.row_bytes;
+		self.data[row_start + cell.start..row_start + cell.end].copy_from_slice(value);
+		self.written[col.table_index.0] = true;
+		Ok(())
+	}
+
+	/// Copies the written cells of every column into the column-major segment data.
+	fn scatter(self, cols: &[RefCellData<'_, P>]) -> Result<(), Error> {
+		for (index, cell) in self.cells.iter().enumerate() {
+			let Some(cell) = cell.as_ref().filter(|_| self.written[index]) else {
+				continue;
+			};
+			// Columns with row-major cells own their data, unless the segment only holds some of
+			// the columns of the table.
+			let Some(RefCellData::Owned(col)) = cols.get(index) else {
+				return Err(Error::MissingColumn(self.table.columns[index].id));
+			};
+			let mut col = col.try_borrow_mut().map_err(Error::WitnessBorrowMut)?;
+			let col = (self.as_bytes)(&mut col);
+			for (dst, row) in
+				iter::zip(col.chunks_exact_mut(cell.len()), self.data.chunks_exact(self.row_bytes))
+			{
+				dst.copy_from_slice(&row[cell.clone()]);
+			}
+		}
+		Ok(())
+	}
+}
+
+fn packed_bytes_mut<P>(packed: &mut [P]) -> &mut [u8]
+where
+	P: PackedFieldIndexable<Scalar: Pod>,
+{
+	must_cast_slice_mut(P::unpack_scalars_mut(packed))
+}
+
 /// A struct that can populate segments of a table witness using row descriptors.
 pub trait TableFiller<P = PackedType<OptimalUnderlier, B128>>
 where
@@ -1268,11 +1929,21 @@ where
 	/// Returns the table ID.
 	fn id(&self) -> TableId;
 
+	/// Returns the binary logarithm of the number of consecutive rows that every event spans.
+	///
+	/// Gadgets whose events span several rows, such as the rounds of a hash schedule, override
+	/// this. The witness segments passed to [`Self::fill`] then always hold whole events, and
+	/// [`TableWitnessSegment::event_rows`] gives the rows of each event. Defaults to one row per
+	/// event.
+	fn log_rows_per_event(&self) -> usize {
+		0
+	}
+
 	/// Fill the table witness with data derived from the given rows.
 	///
 	/// ## Preconditions
 	///
-	/// * the number of elements in `rows` must equal `witness.size()`
+	/// * the number of elements in `rows` must equal `witness.n_events()`
 	fn fill(
 		&self,
 		rows: &[Self::Event],
@@ -1280,6 +1951,25 @@ where
 	) -> anyhow::Result<()>;
 }
 
+/// Rejects column handles whose field is not the one of the column, which is the case for the
+/// handles of columns repacked by [`ConstraintSystem::repack_columns`].
+///
+/// [`ConstraintSystem::repack_columns`]: super::ConstraintSystem::repack_columns
+fn check_tower_level<F: TowerField, FSub: TowerField, const V: usize>(
+	table: &Table<F>,
+	col: Col<FSub, V>,
+) -> Result<(), Error> {
+	let tower_level = table[col.table_index].shape.tower_height;
+	if tower_level != FSub::TOWER_LEVEL {
+		return Err(Error::ColumnTowerLevelMismatch {
+			column: col.id(),
+			tower_level,
+			handle_tower_level: FSub::TOWER_LEVEL,
+		});
+	}
+	Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 	use std::{array, iter::repeat_with};
@@ -1291,12 +1981,14 @@ mod tests {
 		arch::{OptimalUnderlier128b, OptimalUnderlier256b},
 		packed::{len_packed_slice, set_packed_slice},
 	};
+	use binius_utils::{error_code::HasErrorCode, rayon::ThreadPoolHandle};
 	use rand::{Rng, SeedableRng, rngs::StdRng};
 
 	use super::*;
 	use crate::builder::{
-		ConstraintSystem, TableBuilder,
-		types::{B1, B8, B16, B32},
+		ConstraintSystem, FlushDirection, MAX_CONSTRAINT_DEGREE, TableBuilder,
+		test_utils::{ClosureFiller, validate_system_witness},
+		types::{B1, B8, B16, B32, B64},
 	};
 
 	#[test]
@@ -1337,6 +2029,29 @@ mod tests {
 		assert_eq!(len_packed_slice(&segment.get_mut(col3).unwrap()), 1 << 6);
 	}
 
+	#[test]
+	fn test_table_witness_zeroed_on_reused_allocator() {
+		let mut inner_table = Table::<B128>::new(0, "table".to_string());
+		let mut table = TableBuilder::new(&mut inner_table);
+		let col = table.add_committed::<B32, 1>("col");
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		for _ in 0..2 {
+			let allocator = allocator.into_bump_allocator();
+			let mut index = TableWitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(
+				&allocator,
+				&inner_table,
+				64,
+			)
+			.unwrap();
+			let segment = index.full_segment();
+			let mut values = segment.get_mut_as::<u32, _, 1>(col).unwrap();
+			// The memory of the first table is handed out again for the second.
+			assert!(values.iter().all(|&value| value == 0));
+			values.fill(u32::MAX);
+		}
+	}
+
 	#[test]
 	fn test_table_witness_segments() {
 		let table_id = 0;
@@ -1477,6 +2192,267 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn test_fill_shifted() {
+		let table_id = 0;
+		let mut inner_table = Table::<B128>::new(table_id, "table".to_string());
+		let mut table = TableBuilder::new(&mut inner_table);
+		let col32 = table.add_committed::<B1, 32>("col32");
+		let col64 = table.add_committed::<B1, 64>("col64");
+		let rotl32 = table.add_rotated("rotl32", col32, 7);
+		let rotr64 = table.add_rotated("rotr64", col64, 64 - 13);
+		let shr64 = table.add_shifted("shr64", col64, 6, 3, ShiftVariant::LogicalRight);
+		let shl16 = table.add_shifted("shl16", col32, 4, 5, ShiftVariant::LogicalLeft);
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let table_size = 1 << 6;
+		let mut index = TableWitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(
+			&allocator,
+			&inner_table,
+			table_size,
+		)
+		.unwrap();
+		let segment = index.full_segment();
+
+		let mut rng = StdRng::seed_from_u64(0);
+		segment
+			.get_mut_as::<u32, _, 32>(col32)
+			.unwrap()
+			.fill_with(|| rng.random());
+		segment
+			.get_mut_as::<u64, _, 64>(col64)
+			.unwrap()
+			.fill_with(|| rng.random());
+
+		for col in [rotl32, shl16] {
+			segment.fill_shifted(col).unwrap();
+		}
+		for col in [rotr64, shr64] {
+			segment.fill_shifted(col).unwrap();
+		}
+
+		let values32 = segment.get_as::<u32, _, 32>(col32).unwrap();
+		let rotl32 = segment.get_as::<u32, _, 32>(rotl32).unwrap();
+		let shl16 = segment.get_as::<u16, _, 32>(shl16).unwrap();
+		for (i, &x) in values32.iter().enumerate() {
+			assert_eq!(rotl32[i], x.rotate_left(7));
+			assert_eq!(shl16[2 * i], (x as u16) << 5);
+			assert_eq!(shl16[2 * i + 1], ((x >> 16) as u16) << 5);
+		}
+
+		let values64 = segment.get_as::<u64, _, 64>(col64).unwrap();
+		let rotr64 = segment.get_as::<u64, _, 64>(rotr64).unwrap();
+		let shr64 = segment.get_as::<u64, _, 64>(shr64).unwrap();
+		for (i, &x) in values64.iter().enumerate() {
+			assert_eq!(rotr64[i], x.rotate_right(13));
+			assert_eq!(shr64[i], x >> 3);
+		}
+		drop(values64);
+
+		assert_matches!(
+			segment.fill_shifted(col64),
+			Err(Error::UnsupportedShiftedColumn(id)) if id == col64.id()
+		);
+	}
+
+	#[test]
+	fn test_fill_unpacked() {
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("packing");
+		let table_id = table.id();
+		let bits = table.add_committed::<B1, 16>("bits");
+		let bytes = table.add_committed::<B8, 8>("bytes");
+		let word16 = table.add_packed_row::<B16, _, 16>("word16", bits);
+		let word64 = table.add_packed_row::<B64, _, 8>("word64", bytes);
+		let halves = table.add_packed::<_, 8, B16, 4>("halves", bytes);
+		let word128 = table.add_committed::<B128, 1>("word128");
+		let word128_bytes = table.add_unpacked::<B8, 16, _, 1>("word128_bytes", word128);
+		let word64_bits = table.add_unpacked::<B1, 64, _, 1>("word64_bits", word64);
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let rows = repeat_with(|| rng.random::<(u16, u64, u128)>())
+			.take(23)
+			.collect::<Vec<_>>();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(&cs, &allocator);
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(table_id, |rows: &[(u16, u64, u128)], segment| {
+					{
+						let mut bits = segment.get_mut_as::<u16, _, 16>(bits)?;
+						let mut bytes = segment.get_mut_as::<u64, _, 8>(bytes)?;
+						let mut word128 = segment.get_mut_as::<u128, _, 1>(word128)?;
+						for (i, &(x, y, z)) in rows.iter().enumerate() {
+							bits[i] = x;
+							bytes[i] = y;
+							word128[i] = z;
+						}
+					}
+					segment.fill_unpacked(word128_bytes, word128)?;
+					segment.fill_unpacked(word64_bits, word64)?;
+
+					let word16 = segment.get_scalars(word16)?;
+					let word64 = segment.get_scalars(word64)?;
+					let halves = segment.get_as::<u16, _, 4>(halves)?;
+					let word128_bytes = segment.get_as::<u8, _, 16>(word128_bytes)?;
+					for (i, &(x, y, z)) in rows.iter().enumerate() {
+						assert_eq!(word16[i], B16::new(x));
+						assert_eq!(word64[i], B64::new(y));
+						assert_eq!(halves[4 * i + 1], (y >> 16) as u16);
+						assert_eq!(word128_bytes[16 * i + 15], (z >> 120) as u8);
+					}
+					Ok(())
+				}),
+				&rows,
+			)
+			.unwrap();
+
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
+	}
+
+	#[test]
+	fn test_fill_row_major() {
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("row major");
+		let table_id = table.id();
+		table.set_witness_layout(WitnessLayout::RowMajor);
+		let a = table.add_committed::<B32, 1>("a");
+		let b = table.add_committed::<B32, 1>("b");
+		let c = table.add_committed::<B32, 1>("c");
+		let bits = table.add_committed::<B1, 16>("bits");
+		let flags = table.add_committed::<B1, 8>("flags");
+		let bit = table.add_committed::<B1, 1>("bit");
+		table.assert_zero("c = a + b", a + b - c);
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let rows = repeat_with(|| rng.random::<(u32, u32, u16, u8)>())
+			.take(1000)
+			.collect::<Vec<_>>();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 14);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(&cs, &allocator);
+		witness
+			.fill_table_parallel(
+				&ClosureFiller::new(table_id, |rows: &[(u32, u32, u16, u8)], segment| {
+					{
+						let mut row_major = segment.rows_mut()?;
+						assert_eq!(row_major.n_rows(), segment.size());
+						for (i, &(x, y, z, _)) in rows.iter().enumerate() {
+							row_major.set(i, a, x)?;
+							row_major.set(i, b, y)?;
+							row_major.set(i, c, x ^ y)?;
+							row_major.set(i, bits, z)?;
+						}
+						assert_matches!(
+							row_major.set(0, bit, 0u8),
+							Err(Error::NotRowMajorColumn(id)) if id == bit.id()
+						);
+					}
+					let mut flags = segment.get_mut_as::<u8, _, 8>(flags)?;
+					for (i, &(.., w)) in rows.iter().enumerate() {
+						flags[i] = w;
+					}
+					Ok(())
+				}),
+				&rows,
+			)
+			.unwrap();
+
+		let segment = witness.get_table(table_id).unwrap().full_segment();
+		let a_vals = segment.get_as::<u32, _, 1>(a).unwrap();
+		let c_vals = segment.get_as::<u32, _, 1>(c).unwrap();
+		let bits_vals = segment.get_as::<u16, _, 16>(bits).unwrap();
+		let flags_vals = segment.get_as::<u8, _, 8>(flags).unwrap();
+		for (i, &(x, y, z, w)) in rows.iter().enumerate() {
+			assert_eq!(a_vals[i], x);
+			assert_eq!(c_vals[i], x ^ y);
+			assert_eq!(bits_vals[i], z);
+			assert_eq!(flags_vals[i], w);
+		}
+		drop((a_vals, c_vals, bits_vals, flags_vals));
+		drop(segment);
+
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
+	}
+
+	#[test]
+	fn test_rows_mut_requires_row_major_layout() {
+		let mut inner_table = Table::<B128>::new(0, "table");
+		let mut table = TableBuilder::new(&mut inner_table);
+		let _col = table.add_committed::<B32, 1>("col");
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut index = TableWitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(
+			&allocator,
+			&inner_table,
+			16,
+		)
+		.unwrap();
+		let segment = index.full_segment();
+		assert_matches!(segment.rows_mut(), Err(Error::RowMajorLayoutDisabled { table_id: 0 }));
+	}
+
+	#[test]
+	fn test_multilinear_extension_index_borrows_witness() {
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("borrow");
+		let table_id = table.id();
+		let bits = table.add_committed::<B1, 32>("bits");
+		let word = table.add_packed::<_, 32, B32, 1>("word", bits);
+		let wide = table.add_committed::<B128, 1>("wide");
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let rows = repeat_with(|| rng.random::<(u32, u128)>())
+			.take(64)
+			.collect::<Vec<_>>();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness =
+			WitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(&cs, &allocator);
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(table_id, |rows: &[(u32, u128)], segment| {
+					let mut bits_vals = segment.get_mut_as::<u32, _, 32>(bits)?;
+					let mut wide_vals = segment.get_mut_as::<u128, _, 1>(wide)?;
+					for (i, &(x, y)) in rows.iter().enumerate() {
+						bits_vals[i] = x;
+						wide_vals[i] = y;
+					}
+					Ok(())
+				}),
+				&rows,
+			)
+			.unwrap();
+
+		let segment = witness.get_table(table_id).unwrap().full_segment();
+		let bits_ptr = segment.get(bits).unwrap().as_ptr() as usize;
+		let wide_ptr = segment.get(wide).unwrap().as_ptr() as usize;
+		drop(segment);
+
+		cs.compile().unwrap();
+		let oracle_id = |column_id| match *cs.oracle_lookup().lookup(column_id) {
+			OracleMapping::Regular(oracle_id) => oracle_id,
+			OracleMapping::TransparentCompound { .. } => unreachable!("no constant columns"),
+		};
+		let (bits_oracle, word_oracle, wide_oracle) =
+			(oracle_id(bits.id()), oracle_id(word.id()), oracle_id(wide.id()));
+
+		let index = witness.into_multilinear_extension_index();
+		let evals_ptr = |oracle_id| {
+			let poly = index.get_multilin_poly(oracle_id).unwrap();
+			poly.packed_evals().unwrap().as_ptr() as usize
+		};
+		assert_eq!(evals_ptr(bits_oracle), bits_ptr);
+		assert_eq!(evals_ptr(word_oracle), bits_ptr);
+		assert_eq!(evals_ptr(wide_oracle), wide_ptr);
+	}
+
 	#[test]
 	fn test_small_tables() {
 		let table_id = 0;
@@ -1646,6 +2622,288 @@ mod tests {
 		assert_eq!(col0[15].val(), rows[10]);
 	}
 
+	/// Fills every event into 4 consecutive rows, writing the event value plus the row offset.
+	struct MultiRowTestTable {
+		id: TableId,
+		col: Col<B32>,
+	}
+
+	impl TableFiller<PackedType<OptimalUnderlier128b, B128>> for MultiRowTestTable {
+		type Event = u32;
+
+		fn id(&self) -> TableId {
+			self.id
+		}
+
+		fn log_rows_per_event(&self) -> usize {
+			2
+		}
+
+		fn fill(
+			&self,
+			rows: &[Self::Event],
+			witness: &mut TableWitnessSegment<PackedType<OptimalUnderlier128b, B128>>,
+		) -> anyhow::Result<()> {
+			assert_eq!(rows.len(), witness.n_events());
+			let mut col = witness.get_scalars_mut(self.col)?;
+			for (i, &val) in rows.iter().enumerate() {
+				for row in witness.event_rows(i) {
+					let (event, offset) = witness.row_event(row);
+					assert_eq!(event, i);
+					col[row] = B32::new(val + offset as u32);
+				}
+			}
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn test_fill_multi_row_events() {
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("multi-row");
+		let test_table = MultiRowTestTable {
+			id: table.id(),
+			col: table.add_committed("col"),
+		};
+		drop(table);
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let events = repeat_with(|| rng.random_range(0..1 << 16))
+			.take(5)
+			.collect::<Vec<_>>();
+
+		let mut index = WitnessIndex::new(&cs, &allocator);
+		index.fill_table_parallel(&test_table, &events).unwrap();
+		assert_eq!(index.table_sizes(), vec![20]);
+
+		let table_index = index.get_table(test_table.id).unwrap();
+		let segment = table_index.full_segment();
+		let col = segment.get_scalars(test_table.col).unwrap();
+		for row in 0..32 {
+			// The padding rows repeat the last event.
+			let event = events[(row / 4).min(4)];
+			assert_eq!(col[row].val(), event + (row % 4) as u32);
+		}
+		drop(col);
+		drop(segment);
+
+		let mut index = WitnessIndex::new(&cs, &allocator);
+		let table_index = index.init_table(test_table.id, 18).unwrap();
+		assert_matches!(
+			table_index.fill_sequential(&test_table, &events),
+			Err(Error::IncompatibleRowsPerEvent {
+				size: 18,
+				rows_per_event: 4,
+				..
+			})
+		);
+
+		let mut index = WitnessIndex::new(&cs, &allocator);
+		let table_index = index.init_table(test_table.id, 16).unwrap();
+		assert_matches!(
+			table_index.fill_sequential(&test_table, &events),
+			Err(Error::IncorrectNumberOfTableEvents {
+				expected: 4,
+				actual: 5
+			})
+		);
+	}
+
+	#[test]
+	fn test_fill_table_on_dedicated_thread_pool() {
+		let mut cs = ConstraintSystem::new();
+		let test_table = TestTable::new(&mut cs);
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+
+		let thread_pool = ThreadPoolHandle::new(2, false).unwrap();
+		let mut index = WitnessIndex::new(&cs, &allocator).with_thread_pool(thread_pool);
+
+		let rows = (0..11).collect::<Vec<u32>>();
+		index.fill_table_parallel(&test_table, &rows).unwrap();
+
+		let table_index = index.get_table(test_table.id()).unwrap();
+		let segment = table_index.full_segment();
+		let col0 = segment.get_scalars(test_table.col0).unwrap();
+		for (i, &row) in rows.iter().enumerate() {
+			assert_eq!(col0[i].val(), row);
+		}
+	}
+
+	#[test]
+	fn test_table_copies() {
+		let mut cs = ConstraintSystem::new();
+		let channel = cs.add_channel("values");
+		let test_table = TestTable::new(&mut cs);
+		TableBuilder::new(&mut cs.tables[test_table.id]).push(channel, [test_table.col1]);
+		let copies = cs.add_table_copies(test_table.id, 3, 3);
+		assert_eq!(copies, vec![1, 2, 3]);
+		assert_eq!(cs.tables[3].name, "test[3]");
+		assert_eq!(cs.tables[3].copy_of(), Some(test_table.id));
+
+		let mut sink = cs.add_table("sink");
+		let sink_id = sink.id();
+		let sink_col = sink.add_committed::<B32, 1>("col1");
+		sink.pull(channel, [sink_col]);
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+
+		let mut index = WitnessIndex::new(&cs, &allocator);
+		assert_matches!(
+			index.fill_table_sequential(&test_table, &(0..33).collect::<Vec<u32>>()),
+			Err(Error::TableCapacityExceeded {
+				size: 33,
+				capacity: 32,
+				..
+			})
+		);
+		assert_eq!(index.table_sizes(), vec![0; 5]);
+
+		let rows = (0..27).collect::<Vec<u32>>();
+		index.fill_table_parallel(&test_table, &rows).unwrap();
+		index
+			.fill_table_sequential(
+				&ClosureFiller::new(sink_id, |rows: &[u32], witness| {
+					let mut sink_col = witness.get_scalars_mut(sink_col)?;
+					for (i, &val) in rows.iter().enumerate() {
+						sink_col[i] = B32::new(val).pow(2) + B32::new(0x03);
+					}
+					Ok(())
+				}),
+				&rows,
+			)
+			.unwrap();
+		assert_eq!(index.table_sizes(), vec![8, 8, 8, 3, 27]);
+
+		let copy_index = index.get_table(copies[2]).unwrap();
+		let segment = copy_index.full_segment();
+		let col0 = segment.get_scalars(test_table.col0).unwrap();
+		for (i, &row) in rows[24..].iter().enumerate() {
+			assert_eq!(col0[i].val(), row);
+		}
+		drop(col0);
+		drop(segment);
+
+		validate_system_witness::<OptimalUnderlier128b>(&cs, index, vec![]);
+	}
+
+	struct FailingTable(TestTable);
+
+	impl TableFiller<PackedType<OptimalUnderlier128b, B128>> for FailingTable {
+		type Event = u32;
+
+		fn id(&self) -> TableId {
+			self.0.id
+		}
+
+		fn fill(
+			&self,
+			_rows: &[Self::Event],
+			_witness: &mut TableWitnessSegment<PackedType<OptimalUnderlier128b, B128>>,
+		) -> anyhow::Result<()> {
+			anyhow::bail!("event out of range")
+		}
+	}
+
+	#[test]
+	fn test_fill_error_has_table_context() {
+		let mut cs = ConstraintSystem::new();
+		let test_table = FailingTable(TestTable::new(&mut cs));
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+
+		let mut index = WitnessIndex::new(&cs, &allocator);
+		let table_index = index.init_table(test_table.id(), 4).unwrap();
+
+		let err = table_index
+			.fill_parallel_with_segment_size(&test_table, &[0, 1, 2, 3], 2)
+			.unwrap_err();
+		assert_matches!(
+			&err,
+			Error::TableFill { table_id, table_name, .. }
+				if *table_id == test_table.id() && table_name == "test"
+		);
+		assert_eq!(err.to_string(), "failed to fill table 0 (test): event out of range");
+		assert_eq!(err.error_code().to_string(), "E16014");
+	}
+
+	#[test]
+	fn test_preflight() {
+		let mut cs = ConstraintSystem::new();
+		let channel = cs.add_channel("channel");
+		let unused_channel = cs.add_channel("unused");
+
+		let mut table = cs.add_table("table");
+		table.require_power_of_two_size();
+		let col = table.add_committed::<B32, 1>("col");
+		let nonzero = table.add_committed::<B32, 1>("nonzero");
+		table.assert_nonzero(nonzero);
+		let _constant = table.add_constant("constant", [B32::new(1)]);
+		table.assert_zero("high_degree", (col - nonzero).pow(MAX_CONSTRAINT_DEGREE as u64 + 1));
+		table.push(channel, [col]);
+		let table_id = table.id();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut index =
+			WitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(&cs, &allocator);
+		index.init_table(table_id, 3).unwrap();
+
+		let findings = index.preflight(&[]);
+		assert_eq!(
+			findings
+				.iter()
+				.map(|finding| finding.to_string())
+				.collect::<Vec<_>>(),
+			[
+				"table table has 3 rows, but its size must be a power of two",
+				"column nonzero of table table is all zeros, although it cannot be zero; it was \
+				 likely not filled",
+				"column constant of table table is all zeros, although it cannot be zero; it was \
+				 likely not filled",
+				"channel channel receives up to 3 pushes and 0 pulls, so it cannot balance",
+				"zero constraint high_degree of table table has degree 256, which exceeds the \
+				 maximum of 255",
+			]
+		);
+		assert!(findings.iter().all(|finding| !matches!(
+			finding,
+			PreflightFinding::UnbalancedChannel { channel_id, .. } if *channel_id == unused_channel
+		)));
+
+		// Filling the columns and balancing the channel with a boundary resolves the findings
+		// about them.
+		index.fill_constant_cols().unwrap();
+		{
+			let segment = index.get_table(table_id).unwrap().full_segment();
+			segment.get_scalars_mut(nonzero).unwrap().fill(B32::new(1));
+		}
+		let boundary = Boundary {
+			values: vec![B128::new(0)],
+			channel_id: channel,
+			direction: FlushDirection::Pull,
+			multiplicity: 3,
+		};
+		let findings = index.preflight(&[boundary]);
+		assert_matches!(
+			findings.as_slice(),
+			[
+				PreflightFinding::TableSize { size: 3, .. },
+				PreflightFinding::ConstraintDegree {
+					degree: 256,
+					max: 255,
+					..
+				},
+			]
+		);
+	}
+
 	#[test]
 	fn test_fill_empty_rows_non_empty_table() {
 		let mut cs = ConstraintSystem::new();
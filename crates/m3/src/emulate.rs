@@ -2,6 +2,9 @@
 
 use std::{collections::BTreeMap, fmt::Debug};
 
+use binius_core::constraint_system::channel::{Boundary, ChannelId, FlushDirection};
+use binius_field::TowerField;
+
 /// A channel used to validate a high-level M3 trace.
 #[derive(Debug)]
 pub struct Channel<T> {
@@ -52,6 +55,34 @@ impl<T: Eq + PartialEq + Ord + PartialOrd> Channel<T> {
 	pub fn is_balanced(&self) -> bool {
 		self.net_multiplicities.is_empty()
 	}
+
+	/// Returns the boundaries that balance the channel, as flushes on the channel `channel_id` of
+	/// the constraint system.
+	///
+	/// After the events of a model run have been fired, the values with a net multiplicity are
+	/// the ones that the statement provides or consumes: a value that was pulled more often than
+	/// pushed becomes a boundary push, and a value that was pushed more often than pulled becomes
+	/// a boundary pull. `encode` maps a value to the field elements of the flushed columns, in
+	/// the order of the columns of the flushes on the channel.
+	pub fn boundaries<F: TowerField>(
+		&self,
+		channel_id: ChannelId,
+		encode: impl Fn(&T) -> Vec<F>,
+	) -> Vec<Boundary<F>> {
+		self.net_multiplicities
+			.iter()
+			.map(|(val, &multiplicity)| Boundary {
+				values: encode(val),
+				channel_id,
+				direction: if multiplicity.is_negative() {
+					FlushDirection::Push
+				} else {
+					FlushDirection::Pull
+				},
+				multiplicity: multiplicity.unsigned_abs() as u64,
+			})
+			.collect()
+	}
 }
 
 impl<T: Debug + Ord + PartialOrd> Channel<T> {
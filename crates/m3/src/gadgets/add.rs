@@ -1,16 +1,17 @@
 // Copyright 2025 Irreducible Inc.
 
-use std::{array, marker::PhantomData, ops::Deref};
+use std::{array, collections::VecDeque, marker::PhantomData, ops::Deref};
 
 use binius_core::oracle::ShiftVariant;
 use binius_field::{
 	Field, PackedExtension, PackedField, PackedFieldIndexable, PackedSubfield, TowerField,
-	packed::set_packed_slice,
+	packed::{get_packed_slice, set_packed_slice},
 };
 use itertools::izip;
 
 use crate::builder::{
-	B32, B64, B128, TableBuilder, column::Col, types::B1, witness::TableWitnessSegment,
+	B32, B64, B128, ColumnId, PopulateColumns, TableBuilder, column::Col, types::B1,
+	witness::TableWitnessSegment,
 };
 
 /// A gadget for performing 32-bit integer addition on vertically-packed bit columns.
@@ -27,6 +28,8 @@ pub struct U32Add {
 	cin: Col<B1, 32>,
 	cout: Col<B1, 32>,
 	cout_shl: Col<B1, 32>,
+	carry_in_bit: Option<Col<B1, 32>>,
+	carry_in_padded: Option<Col<B1, 32>>,
 
 	// Outputs
 	/// The output column, either committed if `flags.commit_zout` is set, otherwise a linear
@@ -38,12 +41,36 @@ pub struct U32Add {
 	pub flags: U32AddFlags,
 }
 
+impl PopulateColumns for U32Add {
+	fn reads(&self) -> Vec<ColumnId> {
+		[self.xin, self.yin]
+			.into_iter()
+			.chain(self.flags.carry_in_bit)
+			.map(|col| col.id())
+			.chain(self.flags.carry_in.map(|col| col.id()))
+			.collect()
+	}
+
+	fn writes(&self) -> Vec<ColumnId> {
+		[self.cin, self.cout, self.cout_shl, self.zout]
+			.into_iter()
+			.chain(self.carry_in_padded)
+			.map(|col| col.id())
+			.chain(self.final_carry.map(|col| col.id()))
+			.collect()
+	}
+}
+
 /// Flags modifying the behavior of the [`U32Add`] gadget.
 #[derive(Debug, Default, Clone)]
 pub struct U32AddFlags {
 	// Optionally a column for a dynamic carry in bit. This *must* be zero in all bits except the
 	// 0th.
 	pub carry_in_bit: Option<Col<B1, 32>>,
+	// Optionally a column of carry in bits, such as the `final_carry` of another instance, which
+	// chains the additions of the limbs of a wider integer. It is zero-padded to the carry in bit
+	// of the lowest bit without committing a column. This must not be set with `carry_in_bit`.
+	pub carry_in: Option<Col<B1>>,
 	pub commit_zout: bool,
 	pub expose_final_carry: bool,
 }
@@ -55,10 +82,19 @@ impl U32Add {
 		yin: Col<B1, 32>,
 		flags: U32AddFlags,
 	) -> Self {
+		assert!(
+			flags.carry_in_bit.is_none() || flags.carry_in.is_none(),
+			"at most one of carry_in_bit and carry_in may be set"
+		);
+
 		let cout = table.add_committed::<B1, 32>("cout");
 		let cout_shl = table.add_shifted("cout_shl", cout, 5, 1, ShiftVariant::LogicalLeft);
 
-		let cin = if let Some(carry_in_bit) = flags.carry_in_bit {
+		let carry_in_padded = flags
+			.carry_in
+			.map(|carry_in| table.add_zero_pad("carry_in_bit", carry_in, 0));
+		let carry_in_bit = flags.carry_in_bit.or(carry_in_padded);
+		let cin = if let Some(carry_in_bit) = carry_in_bit {
 			table.add_computed("cin", cout_shl + carry_in_bit)
 		} else {
 			cout_shl
@@ -84,6 +120,8 @@ impl U32Add {
 			cin,
 			cout,
 			cout_shl,
+			carry_in_bit,
+			carry_in_padded,
 			final_carry,
 			zout,
 			flags,
@@ -94,6 +132,15 @@ impl U32Add {
 	where
 		P: PackedFieldIndexable<Scalar = B128> + PackedExtension<B1>,
 	{
+		if let (Some(carry_in), Some(carry_in_padded)) = (self.flags.carry_in, self.carry_in_padded)
+		{
+			let carry_in = index.get(carry_in)?;
+			let mut carry_in_padded = index.get_mut_as::<u32, _, 32>(carry_in_padded)?;
+			for (i, carry_in_padded) in carry_in_padded.iter_mut().enumerate() {
+				*carry_in_padded = u32::from(get_packed_slice(&carry_in, i) == B1::ONE);
+			}
+		}
+
 		let xin: std::cell::RefMut<'_, [u32]> = index.get_mut_as(self.xin)?;
 		let yin = index.get_mut_as(self.yin)?;
 		let mut cout = index.get_mut_as(self.cout)?;
@@ -105,7 +152,7 @@ impl U32Add {
 			None
 		};
 
-		if let Some(carry_in_bit_col) = self.flags.carry_in_bit {
+		if let Some(carry_in_bit_col) = self.carry_in_bit {
 			// This is u32 assumed to be either 0 or 1.
 			let carry_in_bit = index.get_mut_as(carry_in_bit_col)?;
 
@@ -142,6 +189,62 @@ impl U32Add {
 	}
 }
 
+/// A gadget for incrementing 32-bit integers on vertically-packed bit columns.
+///
+/// This gadget has an input column `xin` and an output column `zout`, and it constrains that
+/// `xin + 1 = zout` as integers, or `xin + 1 + carry_in = zout` if a dynamic carry in bit is given
+/// in the flags. It is a [`U32Add`] with a constant second operand, which the gadget populates.
+///
+/// See [`U32Incr`] for an incrementer on columns of individual bits.
+#[derive(Debug)]
+pub struct U32Inc {
+	// Inputs
+	pub xin: Col<B1, 32>,
+
+	// Private
+	one: Col<B1, 32>,
+	add: U32Add,
+
+	// Outputs
+	/// The output column, either committed if `flags.commit_zout` is set, otherwise a linear
+	/// combination derived column.
+	pub zout: Col<B1, 32>,
+	/// This is `Some` if `flags.expose_final_carry` is set, otherwise it is `None`.
+	pub final_carry: Option<Col<B1>>,
+}
+
+impl U32Inc {
+	pub fn new(table: &mut TableBuilder, xin: Col<B1, 32>, flags: U32AddFlags) -> Self {
+		// The columns are namespaced by the input, so that several incrementers in one table have
+		// distinct column names.
+		let mut table = table.with_namespace(format!("u32_inc[{}]", xin.table_index.0));
+		let one = table
+			.add_constant("one", array::from_fn(|bit| if bit == 0 { B1::ONE } else { B1::ZERO }));
+		let add = U32Add::new(&mut table, xin, one, flags);
+
+		Self {
+			xin,
+			one,
+			zout: add.zout,
+			final_carry: add.final_carry,
+			add,
+		}
+	}
+
+	/// Returns the flags modifying the gadget's behavior.
+	pub fn flags(&self) -> &U32AddFlags {
+		&self.add.flags
+	}
+
+	pub fn populate<P>(&self, index: &mut TableWitnessSegment<P>) -> Result<(), anyhow::Error>
+	where
+		P: PackedFieldIndexable<Scalar = B128> + PackedExtension<B1>,
+	{
+		index.get_mut_as::<u32, _, 32>(self.one)?.fill(1);
+		self.add.populate(index)
+	}
+}
+
 /// A gadget for performing SIMD 32-bit integer addition on vertically-packed bit columns.
 ///
 /// This gadget has input columns `xin` and `yin` for the two 32-bit adjacent integers to be added,
@@ -252,6 +355,199 @@ impl<const V: usize> U32AddStacked<V> {
 		Ok(())
 	}
 }
+
+/// A gadget for summing several 32-bit or 64-bit integers on vertically-packed bit columns.
+///
+/// This gadget has input columns `xins` for the integers to be added and an output column `zout`,
+/// and it constrains that the sum of the inputs is `zout` modulo `2^V`. The generic parameter `V`
+/// is the bit width of the integers and must be 32 or 64.
+///
+/// The operands are first reduced to two with carry-save compressors, each of which turns three
+/// operands into a bitwise sum and a shifted majority carry without propagating carries. The
+/// intermediate sums are linear combinations, so every compressor only commits its carry column,
+/// and a single carry-propagate addition of the remaining two operands produces the output.
+#[derive(Debug)]
+pub struct MultiAdd<const V: usize> {
+	// Inputs
+	pub xins: Vec<Col<B1, V>>,
+
+	// Private
+	compressors: Vec<CarrySaveCompressor<V>>,
+	xin: Col<B1, V>,
+	yin: Col<B1, V>,
+	cout: Col<B1, V>,
+	cout_shl: Col<B1, V>,
+
+	// Outputs
+	/// The output column, either committed if `commit_zout` is set, otherwise a linear
+	/// combination derived column.
+	pub zout: Col<B1, V>,
+}
+
+/// A 3:2 compressor of a [`MultiAdd`], with `inputs[0] + inputs[1] + inputs[2] = sum + carry_shl`
+/// modulo `2^V`.
+#[derive(Debug)]
+struct CarrySaveCompressor<const V: usize> {
+	inputs: [Col<B1, V>; 3],
+	sum: Col<B1, V>,
+	carry: Col<B1, V>,
+	carry_shl: Col<B1, V>,
+}
+
+pub type U32MultiAdd = MultiAdd<32>;
+pub type U64MultiAdd = MultiAdd<64>;
+
+impl<const V: usize> MultiAdd<V> {
+	pub fn new(table: &mut TableBuilder, xins: &[Col<B1, V>], commit_zout: bool) -> Self {
+		assert!(V == 32 || V == 64, "V must be 32 or 64");
+		assert!(xins.len() >= 2, "MultiAdd requires at least two operands");
+		let log_block_size = V.ilog2() as usize;
+
+		// Compress the oldest operands first, which keeps the compressor tree shallow.
+		let mut operands = xins.iter().copied().collect::<VecDeque<_>>();
+		let mut compressors = Vec::with_capacity(xins.len() - 2);
+		while operands.len() > 2 {
+			let i = compressors.len();
+			let inputs @ [a, b, c] = array::from_fn(|_| {
+				operands
+					.pop_front()
+					.expect("more than two operands are left")
+			});
+			let sum = table.add_computed(format!("csa_sum[{i}]"), a + b + c);
+			let carry = table.add_committed::<B1, V>(format!("csa_carry[{i}]"));
+			table.assert_zero(format!("csa_carry[{i}]"), a * b + a * c + b * c - carry);
+			let carry_shl = table.add_shifted(
+				format!("csa_carry_shl[{i}]"),
+				carry,
+				log_block_size,
+				1,
+				ShiftVariant::LogicalLeft,
+			);
+			operands.extend([sum, carry_shl]);
+			compressors.push(CarrySaveCompressor {
+				inputs,
+				sum,
+				carry,
+				carry_shl,
+			});
+		}
+		let [xin, yin] = [operands[0], operands[1]];
+
+		let cout = table.add_committed::<B1, V>("cout");
+		let cout_shl =
+			table.add_shifted("cout_shl", cout, log_block_size, 1, ShiftVariant::LogicalLeft);
+		let cin = cout_shl;
+
+		table.assert_zero("carry_out", (xin + cin) * (yin + cin) + cin - cout);
+
+		let zout = if commit_zout {
+			let zout = table.add_committed::<B1, V>("zout");
+			table.assert_zero("zout", xin + yin + cin - zout);
+			zout
+		} else {
+			table.add_computed("zout", xin + yin + cin)
+		};
+
+		Self {
+			xins: xins.to_vec(),
+			compressors,
+			xin,
+			yin,
+			cout,
+			cout_shl,
+			zout,
+		}
+	}
+
+	pub fn populate<P>(&self, index: &mut TableWitnessSegment<P>) -> Result<(), anyhow::Error>
+	where
+		P: PackedFieldIndexable<Scalar = B128> + PackedExtension<B1>,
+	{
+		let mask = u64::MAX >> (64 - V);
+
+		for compressor in &self.compressors {
+			let [a, b, c] = array_util::try_map(compressor.inputs, |col| read_words(index, col))?;
+			let sum = izip!(&a, &b, &c)
+				.map(|(a, b, c)| a ^ b ^ c)
+				.collect::<Vec<_>>();
+			let carry = izip!(&a, &b, &c)
+				.map(|(a, b, c)| (a & b) | (a & c) | (b & c))
+				.collect::<Vec<_>>();
+			let carry_shl = carry.iter().map(|carry| (carry << 1) & mask);
+
+			write_words(index, compressor.sum, sum)?;
+			write_words(index, compressor.carry_shl, carry_shl)?;
+			write_words(index, compressor.carry, carry)?;
+		}
+
+		let xin = read_words(index, self.xin)?;
+		let yin = read_words(index, self.yin)?;
+		let zout = izip!(&xin, &yin)
+			.map(|(x, y)| x.wrapping_add(*y) & mask)
+			.collect::<Vec<_>>();
+		let (cin, cout): (Vec<_>, Vec<_>) = izip!(&xin, &yin, &zout)
+			.map(|(x, y, z)| {
+				let cin = x ^ y ^ z;
+				// The carry out of the top bit is set if the sum overflows.
+				let carry = (x & y) | ((x | y) & !z);
+				(cin, (carry >> (V - 1)) << (V - 1) | cin >> 1)
+			})
+			.unzip();
+
+		write_words(index, self.zout, zout)?;
+		write_words(index, self.cout_shl, cin)?;
+		write_words(index, self.cout, cout)?;
+		Ok(())
+	}
+}
+
+/// Reads the integers of a column of vertically-packed 32-bit or 64-bit integers.
+fn read_words<P, const V: usize>(
+	index: &TableWitnessSegment<P>,
+	col: Col<B1, V>,
+) -> Result<Vec<u64>, anyhow::Error>
+where
+	P: PackedFieldIndexable<Scalar = B128> + PackedExtension<B1>,
+{
+	let words = match V {
+		32 => index
+			.get_as::<u32, _, V>(col)?
+			.iter()
+			.map(|&word| word as u64)
+			.collect(),
+		64 => index.get_as::<u64, _, V>(col)?.to_vec(),
+		_ => unreachable!("V is checked on construction"),
+	};
+	Ok(words)
+}
+
+/// Writes the integers of a column of vertically-packed 32-bit or 64-bit integers.
+fn write_words<P, const V: usize>(
+	index: &TableWitnessSegment<P>,
+	col: Col<B1, V>,
+	words: impl IntoIterator<Item = u64>,
+) -> Result<(), anyhow::Error>
+where
+	P: PackedFieldIndexable<Scalar = B128> + PackedExtension<B1>,
+{
+	match V {
+		32 => {
+			let mut dst = index.get_mut_as::<u32, _, V>(col)?;
+			for (dst, word) in dst.iter_mut().zip(words) {
+				*dst = word as u32;
+			}
+		}
+		64 => {
+			let mut dst = index.get_mut_as::<u64, _, V>(col)?;
+			for (dst, word) in dst.iter_mut().zip(words) {
+				*dst = word;
+			}
+		}
+		_ => unreachable!("V is checked on construction"),
+	}
+	Ok(())
+}
+
 /// Gadget for unsigned addition using non-packed one-bit columns generic over `u32` and `u64`
 #[derive(Debug)]
 pub struct WideAdd<UX: UnsignedAddPrimitives, const BIT_LENGTH: usize> {
@@ -483,7 +779,10 @@ mod tests {
 	use rand::{Rng as _, SeedableRng, prelude::StdRng};
 
 	use super::*;
-	use crate::builder::{ConstraintSystem, WitnessIndex, test_utils::validate_system_witness};
+	use crate::builder::{
+		ConstraintSystem, WitnessIndex,
+		test_utils::{find_undetected_mutations, validate_system_witness},
+	};
 
 	#[test]
 	fn prop_test_no_carry() {
@@ -553,6 +852,65 @@ mod tests {
 		.execute();
 	}
 
+	#[test]
+	fn test_inc() {
+		// (xin, carry_in, zout, final_carry)
+		let test_vector = [
+			(0x00000000, 0, 0x00000001, false),
+			(0x7FFFFFFF, 0, 0x80000000, false),
+			(0xFFFFFFFF, 0, 0x00000000, true),
+			(0x00000000, 1, 0x00000002, false),
+			(0xFFFFFFFE, 1, 0x00000000, true),
+			(0xFFFFFFFF, 1, 0x00000001, true),
+		];
+
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("u32_inc");
+		let xin = table.add_committed::<B1, 32>("xin");
+		let carry_in = table.add_committed::<B1, 32>("carry_in");
+		let inc = U32Inc::new(
+			&mut table,
+			xin,
+			U32AddFlags {
+				carry_in_bit: Some(carry_in),
+				carry_in: None,
+				commit_zout: true,
+				expose_final_carry: true,
+			},
+		);
+		let table_id = table.id();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness =
+			WitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(&cs, &allocator);
+		let table_witness = witness.init_table(table_id, test_vector.len()).unwrap();
+		let mut segment = table_witness.full_segment();
+
+		{
+			let mut xin_bits = segment.get_mut_as::<u32, _, 32>(xin).unwrap();
+			let mut carry_in_bits = segment.get_mut_as::<u32, _, 32>(carry_in).unwrap();
+			for (i, &(x, carry_in, _, _)) in test_vector.iter().enumerate() {
+				xin_bits[i] = x;
+				carry_in_bits[i] = carry_in;
+			}
+		}
+
+		inc.populate(&mut segment).unwrap();
+
+		{
+			let zout_bits = segment.get_as::<u32, _, 32>(inc.zout).unwrap();
+			let final_carry = segment.get(inc.final_carry.unwrap()).unwrap();
+			for (i, &(_, _, zout, expected_carry)) in test_vector.iter().enumerate() {
+				assert_eq!(zout_bits[i], zout);
+				assert_eq!(get_packed_slice(&final_carry, i), B1::from(expected_carry));
+			}
+		}
+
+		witness.fill_constant_cols().unwrap();
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
+	}
+
 	struct TestPlan {
 		dyn_carry_in: bool,
 		expose_final_carry: bool,
@@ -575,6 +933,7 @@ mod tests {
 
 			let flags = U32AddFlags {
 				carry_in_bit: carry_in,
+				carry_in: None,
 				expose_final_carry: self.expose_final_carry,
 				commit_zout: self.commit_zout,
 			};
@@ -694,6 +1053,86 @@ mod tests {
 		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
 	}
 
+	#[test]
+	fn test_u256_add_chained_limbs() {
+		const N_LIMBS: usize = 8;
+		const TABLE_SIZE: usize = 1 << 5;
+
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("u256_add");
+		let mut carry_in = None;
+		let limbs: [U32Add; N_LIMBS] = array::from_fn(|i| {
+			let mut table = table.with_namespace(format!("limb_{i}"));
+			let xin = table.add_committed::<B1, 32>("xin");
+			let yin = table.add_committed::<B1, 32>("yin");
+			let limb = U32Add::new(
+				&mut table,
+				xin,
+				yin,
+				U32AddFlags {
+					carry_in,
+					expose_final_carry: true,
+					..Default::default()
+				},
+			);
+			carry_in = limb.final_carry;
+			limb
+		});
+		let table_id = table.id();
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let mut test_values = (0..TABLE_SIZE)
+			.map(|_| (rng.random::<[u32; N_LIMBS]>(), rng.random::<[u32; N_LIMBS]>()))
+			.collect::<Vec<_>>();
+		// Carries through all limbs.
+		test_values[0] = ([u32::MAX; N_LIMBS], [0; N_LIMBS]);
+		test_values[0].1[0] = 1;
+
+		let mut allocator = CpuComputeAllocator::new(1 << 14);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness =
+			WitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(&cs, &allocator);
+		let table_witness = witness.init_table(table_id, TABLE_SIZE).unwrap();
+		let mut segment = table_witness.full_segment();
+
+		for (i, limb) in limbs.iter().enumerate() {
+			{
+				let mut xin = segment.get_mut_as::<u32, _, 32>(limb.xin).unwrap();
+				let mut yin = segment.get_mut_as::<u32, _, 32>(limb.yin).unwrap();
+				for (row, (x, y)) in test_values.iter().enumerate() {
+					xin[row] = x[i];
+					yin[row] = y[i];
+				}
+			}
+			// The limbs are populated from the least significant one, so that the carry in of a
+			// limb is populated before it is read.
+			limb.populate(&mut segment).unwrap();
+		}
+
+		for (row, (x, y)) in test_values.iter().enumerate() {
+			let mut carry = false;
+			for (i, limb) in limbs.iter().enumerate() {
+				let (sum, carry0) = x[i].overflowing_add(y[i]);
+				let (sum, carry1) = sum.overflowing_add(carry as u32);
+				carry = carry0 | carry1;
+				assert_eq!(segment.get_as::<u32, _, 32>(limb.zout).unwrap()[row], sum);
+			}
+			let final_carry = segment
+				.get(limbs[N_LIMBS - 1].final_carry.unwrap())
+				.unwrap();
+			assert_eq!(get_packed_slice(&final_carry, row), B1::from(carry));
+		}
+		assert_eq!(
+			segment
+				.get_as::<u32, _, 32>(limbs[N_LIMBS - 1].zout)
+				.unwrap()[0],
+			0,
+			"u256::MAX + 1 wraps to zero"
+		);
+
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
+	}
+
 	#[test]
 	fn test_u32_add_stacked() {
 		let mut cs = ConstraintSystem::new();
@@ -740,4 +1179,106 @@ mod tests {
 
 		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
 	}
+
+	#[test]
+	fn test_u32_add_stacked_rejects_mutations() {
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("u32_add_stacked");
+		const TABLE_SIZE: usize = 1 << 3;
+		const TOTAL_BITS: usize = 32 << 2;
+		let xin = table.add_committed::<B1, TOTAL_BITS>("xin");
+		let yin = table.add_committed::<B1, TOTAL_BITS>("yin");
+		let add = U32AddStacked::new(&mut table, xin, yin, true, None);
+		let table_id = table.id();
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let test_values: Vec<(u32, u32)> = (0..TABLE_SIZE * TOTAL_BITS / 32)
+			.map(|_| (rng.random::<u32>(), rng.random::<u32>()))
+			.collect();
+
+		// Every committed column takes part in the addition, so every mutation must be rejected.
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let undetected = find_undetected_mutations::<OptimalUnderlier128b>(
+			&cs,
+			&mut allocator,
+			|witness| {
+				let table_witness = witness.init_table(table_id, TABLE_SIZE)?;
+				let mut segment = table_witness.full_segment();
+				{
+					let mut xin_bits = segment.get_mut_as::<u32, _, TOTAL_BITS>(add.xin)?;
+					let mut yin_bits = segment.get_mut_as::<u32, _, TOTAL_BITS>(add.yin)?;
+					for (i, &(x, y)) in test_values.iter().enumerate() {
+						xin_bits[i] = x;
+						yin_bits[i] = y;
+					}
+				}
+				add.populate(&mut segment)
+			},
+			&[],
+			true,
+		);
+		assert_eq!(undetected, vec![]);
+	}
+
+	fn check_multi_add<const V: usize>(n_operands: usize, commit_zout: bool) {
+		const TABLE_SIZE: usize = 1 << 6;
+
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("multi_add");
+		let xins = (0..n_operands)
+			.map(|i| table.add_committed::<B1, V>(format!("xin[{i}]")))
+			.collect::<Vec<_>>();
+		let add = MultiAdd::new(&mut table, &xins, commit_zout);
+		let table_id = table.id();
+
+		let mask = u64::MAX >> (64 - V);
+		let mut rng = StdRng::seed_from_u64(0);
+		// The first row adds the maximum values, which carries out of every compressor.
+		let test_values = (0..TABLE_SIZE)
+			.map(|row| {
+				(0..n_operands)
+					.map(|_| {
+						if row == 0 {
+							mask
+						} else {
+							rng.random::<u64>() & mask
+						}
+					})
+					.collect::<Vec<_>>()
+			})
+			.collect::<Vec<_>>();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 14);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness =
+			WitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(&cs, &allocator);
+		let table_witness = witness.init_table(table_id, TABLE_SIZE).unwrap();
+		let mut segment = table_witness.full_segment();
+
+		for (i, &xin) in add.xins.iter().enumerate() {
+			write_words(&segment, xin, test_values.iter().map(|row| row[i])).unwrap();
+		}
+		add.populate(&mut segment).unwrap();
+
+		let zout = read_words(&segment, add.zout).unwrap();
+		for (row, (values, z)) in test_values.iter().zip(zout).enumerate() {
+			let expected = values.iter().fold(0u64, |acc, x| acc.wrapping_add(*x)) & mask;
+			assert_eq!(z, expected, "row {row}");
+		}
+		drop(segment);
+
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
+	}
+
+	#[test]
+	fn test_u32_multi_add() {
+		check_multi_add::<32>(2, false);
+		check_multi_add::<32>(3, true);
+		check_multi_add::<32>(8, false);
+	}
+
+	#[test]
+	fn test_u64_multi_add() {
+		check_multi_add::<64>(5, false);
+	}
 }
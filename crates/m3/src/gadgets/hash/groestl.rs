@@ -10,15 +10,18 @@ use anyhow::Result;
 use array_util::ArrayExt;
 use binius_core::oracle::ShiftVariant;
 use binius_field::{
-	AESTowerField8b, ExtensionField, PackedExtension, PackedField, PackedFieldIndexable,
-	PackedSubfield, TowerField, ext_basis,
+	AESTowerField8b, PackedExtension, PackedField, PackedFieldIndexable, PackedSubfield,
+	TowerField, ext_basis,
+	linear_combination::FixedLinearCombination,
 	linear_transformation::{
 		FieldLinearTransformation, PackedTransformationFactory, Transformation,
 	},
-	packed::{get_packed_slice, len_packed_slice, set_packed_slice},
+	packed::get_packed_slice,
 };
 
-use crate::builder::{B1, B8, B128, Col, Expr, TableBuilder, TableWitnessSegment, upcast_col};
+use crate::builder::{
+	B1, B8, B128, Col, Expr, ExprArray, TableBuilder, TableWitnessSegment, upcast_col,
+};
 
 /// The first row of the circulant matrix defining the MixBytes step in Grøstl.
 const MIX_BYTES_VEC: [u8; 8] = [0x02, 0x02, 0x03, 0x04, 0x05, 0x03, 0x05, 0x07];
@@ -103,17 +106,15 @@ impl Permutation {
 		states: impl IntoIterator<Item = &'a [B8; 64]>,
 	) -> Result<()>
 	where
-		P: PackedExtension<B8>,
-		P::Scalar: TowerField,
+		P: PackedFieldIndexable<Scalar = B128> + PackedExtension<B8>,
 	{
+		// Column `i` holds the bytes `i, 8 + i, ..., 56 + i` of every state.
 		let mut state_in = self
 			.state_in()
-			.try_map_ext(|state_in_i| index.get_mut(state_in_i))?;
+			.try_map_ext(|state_in_i| index.get_rows_mut_as::<u8, _, 8>(state_in_i))?;
 		for (k, state_k) in states.into_iter().enumerate() {
 			for (i, state_in_i) in state_in.iter_mut().enumerate() {
-				for j in 0..8 {
-					set_packed_slice(state_in_i, k * 8 + j, state_k[j * 8 + i]);
-				}
+				state_in_i.set_row(k, &array::from_fn::<_, 8, _>(|j| state_k[j * 8 + i].val()));
 			}
 		}
 		Ok(())
@@ -230,15 +231,8 @@ impl PermutationRound {
 		// MixBytes
 		let mix_bytes_scalars = MIX_BYTES_VEC.map(|byte| B8::from(AESTowerField8b::new(byte)));
 		let state_out = array::from_fn(|j| {
-			let mix_bytes: [_; 8] =
-				array::from_fn(|i| shift[i] * mix_bytes_scalars[(8 + i - j) % 8]);
-			table.add_computed(
-				format!("MixBytes[{j}]"),
-				mix_bytes
-					.into_iter()
-					.reduce(|a, b| a + b)
-					.expect("mix_bytes has length 8"),
-			)
+			let coeffs = array::from_fn(|i| mix_bytes_scalars[(8 + i - j) % 8]);
+			table.add_computed(format!("MixBytes[{j}]"), shift.dot(coeffs))
 		});
 
 		Self {
@@ -258,11 +252,8 @@ impl PermutationRound {
 		PackedSubfield<P, B8>: PackedTransformationFactory<PackedSubfield<P, B8>>,
 	{
 		{
-			let mut round_const = index.get_mut(self.round_const)?;
-			let round_consts = round_consts(self.round);
-			for k in 0..len_packed_slice(&round_const) {
-				set_packed_slice(&mut round_const, k, round_consts[k % 8]);
-			}
+			let mut round_const = index.get_mut_as::<u64, _, 8>(self.round_const)?;
+			round_const.fill(u64::from_le_bytes(round_consts(self.round).map(|b8| b8.val())));
 		}
 
 		// AddRoundConstant + SubBytes
@@ -292,13 +283,13 @@ impl PermutationRound {
 		// multiplications.
 		let mix_bytes_scalars = MIX_BYTES_VEC.map(|byte| B8::from(AESTowerField8b::new(byte)));
 		let shift: [_; 8] = array_util::try_from_fn(|i| index.get(self.shift[i]))?;
+		let shift = shift.each_ref().map(|shift_i| &**shift_i);
 		for j in 0..8 {
+			let mix_bytes = FixedLinearCombination::<PackedSubfield<P, B8>>::new(
+				(0..8).map(|i| mix_bytes_scalars[(8 + i - j) % 8]),
+			);
 			let mut mix_bytes_out = index.get_mut(self.state_out[j])?;
-			for (k, mix_bytes_out_k) in mix_bytes_out.iter_mut().enumerate() {
-				*mix_bytes_out_k = (0..8)
-					.map(|i| shift[i][k] * mix_bytes_scalars[(8 + i - j) % 8])
-					.sum();
-			}
+			mix_bytes.evaluate(&shift, &mut mix_bytes_out)?;
 		}
 
 		Ok(())
@@ -351,28 +342,34 @@ impl<const V: usize> SBox<V> {
 
 	pub fn populate<P>(&self, index: &mut TableWitnessSegment<P>) -> Result<()>
 	where
-		P: PackedField<Scalar = B128> + PackedExtension<B1> + PackedExtension<B8>,
+		P: PackedFieldIndexable<Scalar = B128> + PackedExtension<B1> + PackedExtension<B8>,
 		PackedSubfield<P, B8>: PackedTransformationFactory<PackedSubfield<P, B8>>,
 	{
-		let mut inv = index.get_mut(self.inv)?;
-
 		// Populate the inverse of the input.
-		for (inv_i, val_i) in iter::zip(&mut *inv, index.eval_expr(&self.input)?) {
-			*inv_i = val_i.invert_or_zero();
+		{
+			let mut inv = index.get_mut(self.inv)?;
+			for (inv_i, val_i) in iter::zip(&mut *inv, index.eval_expr(&self.input)?) {
+				*inv_i = val_i.invert_or_zero();
+			}
 		}
 
-		// Decompose the inverse bits.
-		let mut inv_bits = self
-			.inv_bits
-			.try_map_ext(|inv_bits_i| index.get_mut(inv_bits_i))?;
-		for i in 0..index.size() * V {
-			let inv_val = get_packed_slice(&inv, i);
-			for (j, inv_bit_j) in ExtensionField::<B1>::iter_bases(&inv_val).enumerate() {
-				set_packed_slice(&mut inv_bits[j], i, inv_bit_j);
+		// Decompose the inverse bits. Every 8 consecutive inverse bytes form an 8x8 bit matrix,
+		// and the rows of its transpose are the next byte of each of the 8 bit columns.
+		{
+			let inv = index.get_as::<u64, _, V>(self.inv)?;
+			let mut inv_bits = self
+				.inv_bits
+				.try_map_ext(|inv_bits_i| index.get_mut_as::<u8, _, V>(inv_bits_i))?;
+			for (k, &inv_k) in inv.iter().enumerate() {
+				let bits_k = transpose_bits_8x8(inv_k).to_le_bytes();
+				for (inv_bits_j, bits_kj) in iter::zip(&mut inv_bits, bits_k) {
+					inv_bits_j[k] = bits_kj;
+				}
 			}
 		}
 
 		// Apply the F2-linear transformation and populate the output.
+		let inv = index.get(self.inv)?;
 		let mut output = index.get_mut(self.output)?;
 
 		let transform_matrix =
@@ -386,6 +383,20 @@ impl<const V: usize> SBox<V> {
 	}
 }
 
+/// Transposes a u64 viewed as an 8x8 bit matrix, where bit `8 * i + j` is the entry in row `i`
+/// and column `j`.
+///
+/// See Hacker's Delight, Section 7-3.
+fn transpose_bits_8x8(mut x: u64) -> u64 {
+	let t = (x ^ (x >> 7)) & 0x00AA_00AA_00AA_00AA;
+	x ^= t ^ (t << 7);
+	let t = (x ^ (x >> 14)) & 0x0000_CCCC_0000_CCCC;
+	x ^= t ^ (t << 14);
+	let t = (x ^ (x >> 28)) & 0x0000_0000_F0F0_F0F0;
+	x ^= t ^ (t << 28);
+	x
+}
+
 fn pack_b8<const V: usize>(bits: [Col<B1, V>; 8]) -> Expr<B8, V> {
 	let b8_basis: [_; 8] = array::from_fn(ext_basis::<B8, B1>);
 	bits.into_iter()
@@ -404,7 +415,7 @@ mod tests {
 		arch::OptimalUnderlier128b, arithmetic_traits::InvertOrZero, as_packed_field::PackedType,
 	};
 	use binius_hash::groestl::{GroestlShortImpl, GroestlShortInternal};
-	use rand::{SeedableRng, prelude::StdRng};
+	use rand::{Rng, SeedableRng, prelude::StdRng};
 
 	use super::*;
 	use crate::builder::{ConstraintSystem, WitnessIndex};
@@ -448,6 +459,20 @@ mod tests {
 		.unwrap();
 	}
 
+	#[test]
+	fn test_transpose_bits_8x8() {
+		let mut rng = StdRng::seed_from_u64(0);
+		for _ in 0..16 {
+			let x = rng.random::<u64>();
+			let transposed = transpose_bits_8x8(x);
+			for i in 0..8 {
+				for j in 0..8 {
+					assert_eq!((transposed >> (8 * i + j)) & 1, (x >> (8 * j + i)) & 1);
+				}
+			}
+		}
+	}
+
 	#[test]
 	fn test_p_permutation() {
 		let mut cs = ConstraintSystem::new();
@@ -6,15 +6,13 @@
 use std::{iter, slice};
 
 use binius_core::constraint_system::channel::ChannelId;
-use binius_field::ext_basis;
 use binius_math::{ArithCircuit, ArithExpr};
 
 use crate::{
 	builder::{
 		B8, B32, B128, Col, IndexedLookup, TableBuilder, TableFiller, TableId, TableWitnessSegment,
-		column::upcast_col,
 	},
-	gadgets::lookup::LookupProducer,
+	gadgets::{indexed_lookup::tuple::TupleCols, lookup::LookupProducer},
 };
 
 /// A gadget that computes the logical AND of two boolean columns using a lookup table.
@@ -102,12 +100,11 @@ pub fn merge_and_columns<const V: usize>(
 	in_b: Col<B8, V>,
 	output: Col<B8, V>,
 ) -> Col<B32, V> {
-	table.add_computed(
-		"merged",
-		upcast_col(in_a)
-			+ upcast_col(in_b) * ext_basis::<B32, B8>(1)
-			+ upcast_col(output) * ext_basis::<B32, B8>(2),
-	)
+	TupleCols::new()
+		.with(in_a)
+		.with(in_b)
+		.with(output)
+		.pack(table, "merged")
 }
 
 /// Merges the input and output values into a single u32 for lookup.
@@ -8,7 +8,7 @@ use std::{iter, slice};
 
 use binius_core::constraint_system::channel::ChannelId;
 use binius_field::{
-	PackedExtension, PackedFieldIndexable, ext_basis,
+	PackedExtension, PackedFieldIndexable,
 	packed::{get_packed_slice, set_packed_slice},
 };
 use binius_math::{ArithCircuit, ArithExpr};
@@ -16,9 +16,9 @@ use binius_math::{ArithCircuit, ArithExpr};
 use crate::{
 	builder::{
 		B1, B8, B32, B128, Col, IndexedLookup, TableBuilder, TableFiller, TableId,
-		TableWitnessSegment, upcast_col,
+		TableWitnessSegment,
 	},
-	gadgets::lookup::LookupProducer,
+	gadgets::{indexed_lookup::tuple::TupleCols, lookup::LookupProducer},
 };
 
 /// Represents an increment operation with carry in a lookup table.
@@ -305,16 +305,12 @@ pub fn merge_incr_cols(
 	output: Col<B8>,
 	carry_out: Col<B1>,
 ) -> Col<B32> {
-	let beta_1 = ext_basis::<B32, B8>(1);
-	let beta_2_0 = ext_basis::<B32, B8>(2);
-	let beta_2_1 = beta_2_0 * ext_basis::<B8, B1>(1);
-	table.add_computed(
-		"merged",
-		upcast_col(input)
-			+ upcast_col(output) * beta_1
-			+ upcast_col(carry_in) * beta_2_0
-			+ upcast_col(carry_out) * beta_2_1,
-	)
+	TupleCols::new()
+		.with(input)
+		.with(output)
+		.with(carry_in)
+		.with(carry_out)
+		.pack(table, "merged")
 }
 
 /// Merges the input, output, carry-in, and carry-out values into a single u32 for lookup.
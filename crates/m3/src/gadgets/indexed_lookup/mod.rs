@@ -1,3 +1,5 @@
 // Copyright 2025 Irreducible Inc.
 pub mod and;
 pub mod incr;
+pub mod rom;
+pub mod tuple;
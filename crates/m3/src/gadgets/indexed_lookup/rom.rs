@@ -0,0 +1,285 @@
+// Copyright 2025 Irreducible Inc.
+
+//! A read-only memory that is shared by all the tables of a constraint system.
+//!
+//! An indexed lookup table is committed once, with a single multiplicity column, no matter how
+//! many tables read from it. The tables read the entries from the lookup channel with
+//! [`TableBuilder::read`], or with [`TableBuilder::pull_with_opts`] for selected reads, and the
+//! read counts of all of them are combined by [`tally`] when the ROM is filled.
+
+use std::{cmp::Reverse, iter, slice};
+
+use binius_core::constraint_system::channel::{Boundary, ChannelId};
+use binius_math::ArithCircuit;
+use itertools::Itertools;
+
+use crate::{
+	builder::{
+		B32, B128, Col, ConstraintSystem, IndexedLookup, TableBuilder, TableFiller, TableId,
+		TableWitnessSegment, WitnessIndex, error::Error, tally,
+	},
+	gadgets::lookup::LookupProducer,
+};
+
+/// A read-only memory holding the entries of an indexed lookup table.
+///
+/// The entries are packed into a single [`B32`] column, which is fixed by an arithmetic circuit
+/// over the bits of the entry index. The circuit must agree with
+/// [`IndexedLookup::index_to_entry`].
+pub struct SharedRom<L> {
+	table_id: TableId,
+	chan: ChannelId,
+	lookup: L,
+	entries_ordered: Col<B32>,
+	entries_sorted: Col<B32>,
+	lookup_producer: LookupProducer,
+}
+
+impl<L: IndexedLookup<B128>> SharedRom<L> {
+	/// Constructs a ROM in the given table, which must not have other columns.
+	///
+	/// # Arguments
+	/// * `table` - The table builder of the ROM.
+	/// * `chan` - The lookup channel that the tables read the entries from.
+	/// * `permutation_chan` - The channel for the permutation check of the sorted entries.
+	/// * `lookup` - The indexed lookup that determines the entries.
+	/// * `entries` - The circuit of the entries over the bits of their indices.
+	/// * `n_multiplicity_bits` - Number of bits for the combined multiplicity of all reads.
+	pub fn new(
+		table: &mut TableBuilder,
+		chan: ChannelId,
+		permutation_chan: ChannelId,
+		lookup: L,
+		entries: ArithCircuit<B128>,
+		n_multiplicity_bits: usize,
+	) -> Self {
+		table.require_fixed_size(lookup.log_size());
+
+		let entries_ordered = table.add_fixed("entries_ordered", entries);
+		let entries_sorted = table.add_committed::<B32, 1>("entries_sorted");
+
+		// The entries are sorted by read count, so that the multiplicity bits are sorted too.
+		table.push(permutation_chan, [entries_ordered]);
+		table.pull(permutation_chan, [entries_sorted]);
+
+		let lookup_producer =
+			LookupProducer::new(table, chan, &[entries_sorted], n_multiplicity_bits);
+		Self {
+			table_id: table.id(),
+			chan,
+			lookup,
+			entries_ordered,
+			entries_sorted,
+			lookup_producer,
+		}
+	}
+
+	/// The lookup channel of the ROM.
+	pub fn chan(&self) -> ChannelId {
+		self.chan
+	}
+
+	/// Fills the ROM with the combined read counts of all tables and boundaries.
+	///
+	/// All tables that read from the ROM must be filled before.
+	pub fn fill(
+		&self,
+		cs: &ConstraintSystem<B128>,
+		witness: &mut WitnessIndex,
+		boundaries: &[Boundary<B128>],
+	) -> Result<(), Error>
+	where
+		L: Sync,
+	{
+		let counts = tally(cs, witness, boundaries, self.chan, &self.lookup)?;
+		let sorted_counts = counts
+			.into_iter()
+			.enumerate()
+			.sorted_by_key(|(_, count)| Reverse(*count))
+			.collect::<Vec<_>>();
+		witness.fill_table_parallel(self, &sorted_counts)
+	}
+
+	fn entry(&self, index: usize) -> B32 {
+		let mut entry = B128::default();
+		self.lookup
+			.index_to_entry(index, slice::from_mut(&mut entry));
+		B32::try_from(entry).expect("ROM entries must fit into 32 bits")
+	}
+}
+
+impl<L: IndexedLookup<B128> + Sync> TableFiller for SharedRom<L> {
+	// Tuple of index and count
+	type Event = (usize, u32);
+
+	fn id(&self) -> TableId {
+		self.table_id
+	}
+
+	fn fill(&self, rows: &[Self::Event], witness: &mut TableWitnessSegment) -> anyhow::Result<()> {
+		{
+			let mut entries_ordered = witness.get_scalars_mut(self.entries_ordered)?;
+			let start_index = witness.index() << witness.log_size();
+			for (i, entry) in entries_ordered.iter_mut().enumerate() {
+				*entry = self.entry(start_index + i);
+			}
+		}
+
+		{
+			let mut entries_sorted = witness.get_scalars_mut(self.entries_sorted)?;
+			for (entry, &(index, _)) in iter::zip(&mut *entries_sorted, rows) {
+				*entry = self.entry(index);
+			}
+		}
+
+		self.lookup_producer
+			.populate(witness, rows.iter().map(|&(_, count)| count))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::iter::repeat_with;
+
+	use assert_matches::assert_matches;
+	use binius_compute::cpu::alloc::CpuComputeAllocator;
+	use binius_core::constraint_system::channel::FlushDirection;
+	use binius_field::arch::OptimalUnderlier;
+	use rand::{Rng, SeedableRng, rngs::StdRng};
+
+	use super::*;
+	use crate::{
+		builder::{
+			B1, B8, FlushOpts,
+			test_utils::{ClosureFiller, validate_system_witness},
+		},
+		gadgets::indexed_lookup::{
+			and::{BitAndIndexedLookup, bitand_circuit, merge_bitand_vals},
+			tuple::TupleCols,
+		},
+	};
+
+	#[test]
+	fn test_shared_rom() {
+		let mut cs = ConstraintSystem::new();
+		let lookup_chan = cs.add_channel("lookup");
+		let permutation_chan = cs.add_channel("permutation");
+
+		let mut rom_table = cs.add_table("bitand_rom");
+		let rom = SharedRom::new(
+			&mut rom_table,
+			lookup_chan,
+			permutation_chan,
+			BitAndIndexedLookup,
+			bitand_circuit(),
+			10,
+		);
+
+		// Two tables read from the same ROM, the second one only on the selected rows.
+		let mut looker_1 = cs.add_table("looker_1");
+		let looker_1_id = looker_1.id();
+		let a_1 = looker_1.add_committed::<B8, 1>("a");
+		let b_1 = looker_1.add_committed::<B8, 1>("b");
+		let and_1 = looker_1.add_committed::<B8, 1>("and");
+		let tuple_1 = TupleCols::new().with(a_1).with(b_1).with(and_1);
+		let merged_1 = tuple_1.pack(&mut looker_1, "merged");
+		looker_1.read(lookup_chan, [merged_1]);
+
+		let mut looker_2 = cs.add_table("looker_2");
+		let looker_2_id = looker_2.id();
+		let a_2 = looker_2.add_committed::<B8, 1>("a");
+		let b_2 = looker_2.add_committed::<B8, 1>("b");
+		let and_2 = looker_2.add_committed::<B8, 1>("and");
+		let selector = looker_2.add_committed::<B1, 1>("selector");
+		let tuple_2 = TupleCols::new().with(a_2).with(b_2).with(and_2);
+		let merged_2 = tuple_2.pack(&mut looker_2, "merged");
+		looker_2.pull_with_opts(
+			lookup_chan,
+			[merged_2],
+			FlushOpts {
+				multiplicity: 2,
+				selectors: vec![selector],
+			},
+		);
+
+		let mut allocator = CpuComputeAllocator::new(1 << 16);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(&cs, &allocator);
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let rows_1 = repeat_with(|| {
+			let (a, b) = rng.random::<(u8, u8)>();
+			(a, b, a & b, true)
+		})
+		.take(29)
+		.collect::<Vec<_>>();
+		let rows_2 = repeat_with(|| {
+			let (a, b, selected) = rng.random::<(u8, u8, bool)>();
+			// Unselected rows hold a wrong AND, which must not be counted.
+			let and = if selected { a & b } else { !(a & b) };
+			(a, b, and, selected)
+		})
+		.take(41)
+		.collect::<Vec<_>>();
+
+		for (table_id, (a, b, and), tuple, merged, selector, rows) in [
+			(looker_1_id, (a_1, b_1, and_1), &tuple_1, merged_1, None, &rows_1),
+			(looker_2_id, (a_2, b_2, and_2), &tuple_2, merged_2, Some(selector), &rows_2),
+		] {
+			witness
+				.fill_table_parallel(
+					&ClosureFiller::new(table_id, |rows: &[(u8, u8, u8, bool)], segment| {
+						{
+							let mut a_col = segment.get_mut_as::<u8, _, 1>(a)?;
+							let mut b_col = segment.get_mut_as::<u8, _, 1>(b)?;
+							let mut and_col = segment.get_mut_as::<u8, _, 1>(and)?;
+							for (i, &(a, b, and, _)) in rows.iter().enumerate() {
+								a_col[i] = a;
+								b_col[i] = b;
+								and_col[i] = and;
+							}
+						}
+						if let Some(selector) = selector {
+							let mut selector_col = segment.get_mut(selector)?;
+							for (i, &(.., selected)) in rows.iter().enumerate() {
+								binius_field::packed::set_packed_slice(
+									&mut selector_col,
+									i,
+									B1::from(selected),
+								);
+							}
+						}
+						tuple.populate_packed(segment, merged)?;
+						Ok(())
+					}),
+					rows,
+				)
+				.unwrap();
+		}
+
+		let boundaries = repeat_with(|| rng.random::<(u8, u8)>())
+			.take(3)
+			.map(|(a, b)| Boundary {
+				values: vec![B32::new(merge_bitand_vals(a, b, a & b)).into()],
+				direction: FlushDirection::Pull,
+				channel_id: rom.chan(),
+				multiplicity: 1,
+			})
+			.collect::<Vec<_>>();
+
+		rom.fill(&cs, &mut witness, &boundaries).unwrap();
+
+		// A boundary multiplicity beyond the range of the counts is rejected, not truncated.
+		let overflowing = Boundary {
+			multiplicity: 1 << 32,
+			..boundaries[0].clone()
+		};
+		assert_matches!(
+			tally(&cs, &mut witness, &[overflowing], rom.chan(), &BitAndIndexedLookup),
+			Err(Error::LookupCountOverflow { .. })
+		);
+
+		validate_system_witness::<OptimalUnderlier>(&cs, witness, boundaries);
+	}
+}
@@ -0,0 +1,343 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Packing of tuples of columns into a single column for indexed lookups.
+//!
+//! The entries of an indexed lookup table are usually tuples of small values, like the inputs and
+//! the output of a bitwise AND. The lookup channel carries a single [`B32`] column per entry, in
+//! which the values of the tuple are concatenated. [`TupleLayout`] describes where every value
+//! lands in the 32 bits of the packed entry, and [`TupleCols`] constrains a packed column to be
+//! the concatenation of a tuple of columns.
+//!
+//! In the tower basis, a value of a field with `n` bits multiplied by the basis element `1 << o`
+//! of [`B32`] occupies exactly the bits `o..o + n`, provided that `o` is a multiple of `n`. The
+//! concatenation is therefore a linear expression in the columns of the tuple, and every value
+//! is placed at the next offset that is aligned to its size.
+
+use binius_field::{ExtensionField, PackedExtension, PackedField, TowerField};
+
+use crate::builder::{
+	B1, B8, B16, B32, B64, B128, Col, Expr, TableBuilder, TableWitnessSegment, column::upcast_col,
+	error::Error,
+};
+
+/// The offsets and sizes of the values of a tuple that is packed into 32 bits.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TupleLayout {
+	/// The bit offset and the number of bits of every value.
+	fields: Vec<(usize, usize)>,
+	n_bits: usize,
+}
+
+impl TupleLayout {
+	/// Creates a layout of values with the given numbers of bits, in order.
+	pub fn new(field_bits: impl IntoIterator<Item = usize>) -> Self {
+		let mut layout = Self::default();
+		for n_bits in field_bits {
+			layout.push(n_bits);
+		}
+		layout
+	}
+
+	/// Appends a value with `n_bits` bits and returns its offset.
+	///
+	/// ## Preconditions
+	///
+	/// * `n_bits` must be the number of bits of a tower field, from 1 up to 32.
+	/// * The value must fit into the 32 bits after the previous values.
+	pub fn push(&mut self, n_bits: usize) -> usize {
+		assert!(
+			n_bits.is_power_of_two() && n_bits <= u32::BITS as usize,
+			"values must have the size of a tower field of at most 32 bits"
+		);
+		let offset = self.n_bits.next_multiple_of(n_bits);
+		assert!(offset + n_bits <= u32::BITS as usize, "the tuple does not fit into 32 bits");
+		self.fields.push((offset, n_bits));
+		self.n_bits = offset + n_bits;
+		offset
+	}
+
+	/// The number of values in the tuple.
+	pub fn len(&self) -> usize {
+		self.fields.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.fields.is_empty()
+	}
+
+	/// The number of bits of the packed tuple, including the padding for alignment.
+	pub fn n_bits(&self) -> usize {
+		self.n_bits
+	}
+
+	/// The bit offset of the `i`-th value.
+	pub fn offset(&self, i: usize) -> usize {
+		self.fields[i].0
+	}
+
+	/// The number of bits of the `i`-th value.
+	pub fn field_bits(&self, i: usize) -> usize {
+		self.fields[i].1
+	}
+
+	/// Packs the values of a tuple into a single integer.
+	///
+	/// ## Preconditions
+	///
+	/// * `values` must have one value per field of the layout, and every value must fit into the
+	///   number of bits of its field.
+	pub fn pack(&self, values: &[u32]) -> u32 {
+		assert_eq!(values.len(), self.len(), "the tuple must have one value per field");
+		self.fields
+			.iter()
+			.zip(values)
+			.fold(0, |packed, (&(offset, n_bits), &value)| {
+				debug_assert!(
+					n_bits == u32::BITS as usize || value >> n_bits == 0,
+					"value {value} does not fit into {n_bits} bits"
+				);
+				packed | (value << offset)
+			})
+	}
+
+	/// Unpacks the values of a tuple from a packed integer.
+	pub fn unpack(&self, packed: u32) -> impl Iterator<Item = u32> + '_ {
+		self.fields.iter().map(move |&(offset, n_bits)| {
+			let value = packed >> offset;
+			if n_bits == u32::BITS as usize {
+				value
+			} else {
+				value & ((1 << n_bits) - 1)
+			}
+		})
+	}
+}
+
+/// A tuple of columns that is packed into a single [`B32`] column.
+///
+/// The columns are appended with [`Self::with`]. A packed column is then either computed from the
+/// tuple with [`Self::pack`], which is the usual case for the tables that read from a lookup
+/// table, or constrained to be equal to the packing of the tuple with [`Self::unpack`], which
+/// allows the columns of the tuple to be committed from the values of a packed column.
+#[derive(Debug, Clone)]
+pub struct TupleCols<const V: usize = 1> {
+	layout: TupleLayout,
+	/// The columns of the tuple, upcast to [`B32`].
+	cols: Vec<Col<B32, V>>,
+}
+
+impl<const V: usize> Default for TupleCols<V> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<const V: usize> TupleCols<V> {
+	pub fn new() -> Self {
+		Self {
+			layout: TupleLayout::default(),
+			cols: Vec::new(),
+		}
+	}
+
+	/// Appends a column to the tuple, at the next offset aligned to the size of its field.
+	pub fn with<FSub>(mut self, col: Col<FSub, V>) -> Self
+	where
+		FSub: TowerField,
+		B32: ExtensionField<FSub>,
+	{
+		self.layout.push(FSub::N_BITS);
+		self.cols.push(upcast_col(col));
+		self
+	}
+
+	pub fn layout(&self) -> &TupleLayout {
+		&self.layout
+	}
+
+	/// Returns the linear expression of the packed tuple.
+	pub fn expr(&self) -> Expr<B32, V> {
+		let mut terms = self
+			.cols
+			.iter()
+			.zip(&self.layout.fields)
+			.map(|(&col, &(offset, _))| col * B32::new(1 << offset));
+		let first = terms
+			.next()
+			.expect("the tuple must have at least one column");
+		terms.fold(first, |expr, term| expr + term)
+	}
+
+	/// Adds a column that is the packing of the tuple.
+	///
+	/// The column is populated by [`Self::populate_packed`].
+	pub fn pack(&self, table: &mut TableBuilder, name: impl ToString + Clone) -> Col<B32, V> {
+		table.add_computed(name, self.expr())
+	}
+
+	/// Constrains a packed column to be the packing of the tuple.
+	///
+	/// The columns of the tuple can be populated from the packed column with
+	/// [`Self::populate_unpacked`].
+	pub fn unpack(&self, table: &mut TableBuilder, name: impl ToString, packed: Col<B32, V>) {
+		table.assert_zero(name, packed - self.expr());
+	}
+
+	/// Populates a packed column from the columns of the tuple, which must be populated first.
+	pub fn populate_packed<P>(
+		&self,
+		witness: &TableWitnessSegment<P>,
+		packed: Col<B32, V>,
+	) -> Result<(), Error>
+	where
+		P: PackedField<Scalar = B128>
+			+ PackedExtension<B1>
+			+ PackedExtension<B8>
+			+ PackedExtension<B16>
+			+ PackedExtension<B32>
+			+ PackedExtension<B64>
+			+ PackedExtension<B128>,
+	{
+		let cols = self
+			.cols
+			.iter()
+			.map(|col| witness.get_dyn(col.id()))
+			.collect::<Result<Vec<_>, _>>()?;
+		let mut packed = witness.get_dyn_mut(packed.id())?;
+		for i in 0..packed.size() {
+			let value = cols
+				.iter()
+				.zip(&self.layout.fields)
+				.map(|(col, &(offset, _))| col.get(i) * B128::new(1 << offset))
+				.sum();
+			packed.set(i, value)?;
+		}
+		Ok(())
+	}
+
+	/// Populates the columns of the tuple from a packed column, which must be populated first.
+	pub fn populate_unpacked<P>(
+		&self,
+		witness: &TableWitnessSegment<P>,
+		packed: Col<B32, V>,
+	) -> Result<(), Error>
+	where
+		P: PackedField<Scalar = B128>
+			+ PackedExtension<B1>
+			+ PackedExtension<B8>
+			+ PackedExtension<B16>
+			+ PackedExtension<B32>
+			+ PackedExtension<B64>
+			+ PackedExtension<B128>,
+	{
+		let packed = witness.get_dyn(packed.id())?;
+		let mut cols = self
+			.cols
+			.iter()
+			.map(|col| witness.get_dyn_mut(col.id()))
+			.collect::<Result<Vec<_>, _>>()?;
+		for i in 0..packed.size() {
+			let packed_val = packed.get(i).val() as u32;
+			for (col, value) in cols.iter_mut().zip(self.layout.unpack(packed_val)) {
+				col.set(i, B128::new(value as u128))?;
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::iter::repeat_with;
+
+	use binius_compute::cpu::alloc::CpuComputeAllocator;
+	use binius_field::arch::OptimalUnderlier128b;
+	use rand::{Rng, SeedableRng, rngs::StdRng};
+
+	use super::*;
+	use crate::builder::{
+		ConstraintSystem, WitnessIndex,
+		test_utils::{ClosureFiller, validate_system_witness},
+	};
+
+	#[test]
+	fn test_tuple_layout() {
+		let layout = TupleLayout::new([8, 1, 8, 1, 4]);
+		assert_eq!(
+			(0..layout.len())
+				.map(|i| layout.offset(i))
+				.collect::<Vec<_>>(),
+			[0, 8, 16, 24, 28]
+		);
+		assert_eq!(layout.n_bits(), 32);
+
+		let values = [0xab, 1, 0x12, 0, 0xf];
+		let packed = layout.pack(&values);
+		assert_eq!(packed, 0xf012_01ab);
+		assert_eq!(layout.unpack(packed).collect::<Vec<_>>(), values);
+	}
+
+	#[test]
+	fn test_tuple_cols() {
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("tuples");
+		let table_id = table.id();
+		let a = table.add_committed::<B8, 1>("a");
+		let bit = table.add_committed::<B1, 1>("bit");
+		let c = table.add_committed::<B16, 1>("c");
+		let tuple = TupleCols::new().with(a).with(bit).with(c);
+		let packed = tuple.pack(&mut table, "packed");
+
+		// A copy of the tuple that is unpacked from a committed column.
+		let unpacked_a = table.add_committed::<B8, 1>("unpacked_a");
+		let unpacked_bit = table.add_committed::<B1, 1>("unpacked_bit");
+		let unpacked_c = table.add_committed::<B16, 1>("unpacked_c");
+		let unpacked_tuple = TupleCols::new()
+			.with(unpacked_a)
+			.with(unpacked_bit)
+			.with(unpacked_c);
+		let committed = table.add_committed::<B32, 1>("committed");
+		unpacked_tuple.unpack(&mut table, "unpacked", committed);
+		assert_eq!(tuple.layout(), unpacked_tuple.layout());
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let rows = repeat_with(|| rng.random::<(u8, bool, u16)>())
+			.take(45)
+			.collect::<Vec<_>>();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness = WitnessIndex::new(&cs, &allocator);
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(table_id, |rows: &[(u8, bool, u16)], segment| {
+					{
+						let mut a_col = segment.get_mut_as::<u8, _, 1>(a)?;
+						let mut c_col = segment.get_mut_as::<u16, _, 1>(c)?;
+						let mut committed_col = segment.get_mut_as::<u32, _, 1>(committed)?;
+						for (i, &(a, bit, c)) in rows.iter().enumerate() {
+							a_col[i] = a;
+							c_col[i] = c;
+							committed_col[i] =
+								tuple.layout().pack(&[a as u32, bit as u32, c as u32]);
+						}
+						let mut bit_col = segment.get_mut(bit)?;
+						for (i, &(_, bit, _)) in rows.iter().enumerate() {
+							binius_field::packed::set_packed_slice(&mut bit_col, i, B1::from(bit));
+						}
+					}
+					tuple.populate_packed(segment, packed)?;
+					unpacked_tuple.populate_unpacked(segment, committed)?;
+
+					let packed_col = segment.get_as::<u32, _, 1>(packed)?;
+					let committed_col = segment.get_as::<u32, _, 1>(committed)?;
+					assert_eq!(&packed_col[..rows.len()], &committed_col[..rows.len()]);
+					Ok(())
+				}),
+				&rows,
+			)
+			.unwrap();
+
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
+	}
+}
@@ -0,0 +1,516 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Loads and stores between byte-addressed memory and word registers.
+//!
+//! Memory is modelled as a channel of `[address, byte]` pairs, with the address as a [`B32`]
+//! element and the byte upcast to [`B32`]. A [`MemoryAccess`] splits a register into the bytes of
+//! an access, flushes each byte together with its address, and constrains the bytes of the
+//! register that are not accessed according to the sign extension of a load.
+
+use binius_core::constraint_system::channel::ChannelId;
+use binius_field::{Field, PackedExtension, PackedFieldIndexable, packed::set_packed_slice};
+
+use super::{
+	add::{U32AddFlags, U32Inc},
+	util::pack_fp,
+};
+use crate::builder::{
+	B1, B8, B32, B128, Col, Expr, FlushOpts, TableBuilder, TableWitnessSegment, upcast_col,
+};
+
+/// The number of bytes of a memory access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessWidth {
+	U8,
+	U16,
+	U32,
+	U64,
+}
+
+impl AccessWidth {
+	/// Returns the number of bytes accessed.
+	pub const fn n_bytes(self) -> usize {
+		match self {
+			Self::U8 => 1,
+			Self::U16 => 2,
+			Self::U32 => 4,
+			Self::U64 => 8,
+		}
+	}
+}
+
+/// The order of the bytes of a register in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+	/// The least significant byte is stored at the lowest address.
+	Little,
+	/// The most significant byte is stored at the lowest address.
+	Big,
+}
+
+/// The direction of a memory access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryOp {
+	/// Reads bytes from memory into the low bytes of the register, which pulls them from the
+	/// memory channel. The high bytes of the register are copies of the sign bit of the accessed
+	/// bytes if `sign_extend` is set, otherwise they are zero.
+	Load { sign_extend: bool },
+	/// Writes the low bytes of the register to memory, which pushes them to the memory channel.
+	/// The high bytes of the register are not constrained.
+	Store,
+}
+
+/// Flags modifying the behavior of the [`MemoryAccess`] gadget.
+#[derive(Debug, Clone)]
+pub struct MemoryAccessFlags {
+	pub width: AccessWidth,
+	pub endianness: Endianness,
+	pub op: MemoryOp,
+	/// Whether the address is constrained to be a multiple of the access width.
+	///
+	/// The addresses of the bytes of an aligned access are linear in the address, whereas an
+	/// unaligned access needs an incrementer for every byte after the first, as the addresses may
+	/// carry into the higher bits.
+	pub aligned: bool,
+	/// A selector of the rows that access memory. All rows access memory if it is `None`.
+	pub selector: Option<Col<B1>>,
+}
+
+/// A gadget for loading and storing a register of `R` bits from and to byte-addressed memory.
+///
+/// The gadget has input columns `addr` for the address of the first byte and `value` for the
+/// register, and flushes the `[address, byte]` pairs of the accessed bytes to the memory channel.
+/// Addresses wrap around modulo `2^32`. The bytes are selected from the register and cost no
+/// commitments.
+#[derive(Debug)]
+pub struct MemoryAccess<const R: usize> {
+	// Inputs
+	pub addr: Col<B1, 32>,
+	pub value: Col<B1, R>,
+
+	// Private
+	value_bits: Vec<Col<B1>>,
+	addr_bits: Vec<Col<B1>>,
+	incs: Vec<U32Inc>,
+	byte_addrs: Vec<Col<B32>>,
+	/// The accessed bytes, in order of increasing address.
+	bytes: Vec<Col<B8>>,
+
+	/// Flags modifying the gadget's behavior.
+	pub flags: MemoryAccessFlags,
+}
+
+impl<const R: usize> MemoryAccess<R> {
+	pub fn new(
+		table: &mut TableBuilder,
+		memory: ChannelId,
+		addr: Col<B1, 32>,
+		value: Col<B1, R>,
+		flags: MemoryAccessFlags,
+	) -> Self {
+		let n_bytes = flags.width.n_bytes();
+		assert!(R % 8 == 0 && n_bytes * 8 <= R, "the access must fit in the register");
+
+		// Only a load constrains the bits above the accessed bytes.
+		let n_value_bits = match flags.op {
+			MemoryOp::Load { .. } => R,
+			MemoryOp::Store => n_bytes * 8,
+		};
+		let value_bits = (0..n_value_bits)
+			.map(|i| table.add_selected(format!("value_bits[{i}]"), value, i))
+			.collect::<Vec<_>>();
+
+		// The constraints only apply to the rows that access memory.
+		let selected = |expr: Expr<B1, 1>| match flags.selector {
+			Some(selector) => expr * selector,
+			None => expr,
+		};
+
+		if let MemoryOp::Load { sign_extend } = flags.op {
+			let sign = value_bits[n_bytes * 8 - 1];
+			for (i, &bit) in value_bits.iter().enumerate().skip(n_bytes * 8) {
+				if sign_extend {
+					table.assert_zero(format!("extension[{i}]"), selected(bit - sign));
+				} else {
+					table.assert_zero(format!("extension[{i}]"), selected(bit.into()));
+				}
+			}
+		}
+
+		let addr_packed = table.add_packed::<_, 32, B32, 1>("addr_packed", addr);
+		let mut addr_bits = Vec::new();
+		let mut incs = Vec::new();
+		let byte_addrs = if flags.aligned {
+			for i in 0..n_bytes.trailing_zeros() as usize {
+				let bit = table.add_selected(format!("addr_bits[{i}]"), addr, i);
+				table.assert_zero(format!("alignment[{i}]"), selected(bit.into()));
+				addr_bits.push(bit);
+			}
+			(0..n_bytes)
+				.map(|i| {
+					if i == 0 {
+						addr_packed
+					} else {
+						table.add_computed(
+							format!("byte_addrs[{i}]"),
+							addr_packed + B32::new(i as u32),
+						)
+					}
+				})
+				.collect::<Vec<_>>()
+		} else {
+			let mut byte_addr = addr;
+			let mut byte_addrs = vec![addr_packed];
+			for i in 1..n_bytes {
+				let inc = U32Inc::new(
+					&mut table.with_namespace(format!("inc[{i}]")),
+					byte_addr,
+					U32AddFlags::default(),
+				);
+				byte_addr = inc.zout;
+				byte_addrs.push(table.add_packed(format!("byte_addrs[{i}]"), byte_addr));
+				incs.push(inc);
+			}
+			byte_addrs
+		};
+
+		let bytes = (0..n_bytes)
+			.map(|i| {
+				let lane = match flags.endianness {
+					Endianness::Little => i,
+					Endianness::Big => n_bytes - 1 - i,
+				};
+				let bits = std::array::from_fn(|bit| value_bits[lane * 8 + bit]);
+				table.add_computed(format!("bytes[{i}]"), pack_fp::<B8, 8>(bits))
+			})
+			.collect::<Vec<_>>();
+
+		let opts = || FlushOpts {
+			multiplicity: 1,
+			selectors: flags.selector.into_iter().collect(),
+		};
+		for (&byte_addr, &byte) in byte_addrs.iter().zip(&bytes) {
+			let values = [byte_addr, upcast_col(byte)];
+			match flags.op {
+				MemoryOp::Load { .. } => table.pull_with_opts(memory, values, opts()),
+				MemoryOp::Store => table.push_with_opts(memory, values, opts()),
+			}
+		}
+
+		Self {
+			addr,
+			value,
+			value_bits,
+			addr_bits,
+			incs,
+			byte_addrs,
+			bytes,
+			flags,
+		}
+	}
+
+	/// Populates the columns of the gadget from the `addr` and `value` columns, which the caller
+	/// must populate first.
+	///
+	/// For a load, `value` is the register after the load, including the extended high bytes.
+	pub fn populate<P>(&self, index: &mut TableWitnessSegment<P>) -> Result<(), anyhow::Error>
+	where
+		P: PackedFieldIndexable<Scalar = B128>
+			+ PackedExtension<B1>
+			+ PackedExtension<B8>
+			+ PackedExtension<B32>,
+	{
+		let n_bytes = self.flags.width.n_bytes();
+		let value = index.get_as::<u8, B1, R>(self.value)?;
+		let registers = value.chunks_exact(R / 8).collect::<Vec<_>>();
+
+		for (i, &bit_col) in self.value_bits.iter().enumerate() {
+			let mut bit_col = index.get_mut(bit_col)?;
+			for (row, register) in registers.iter().enumerate() {
+				let bit = (register[i / 8] >> (i % 8)) & 1;
+				set_packed_slice(&mut bit_col, row, if bit == 1 { B1::ONE } else { B1::ZERO });
+			}
+		}
+
+		for (i, &byte_col) in self.bytes.iter().enumerate() {
+			let lane = match self.flags.endianness {
+				Endianness::Little => i,
+				Endianness::Big => n_bytes - 1 - i,
+			};
+			let mut byte_col = index.get_mut_as::<u8, B8, 1>(byte_col)?;
+			for (dst, register) in byte_col.iter_mut().zip(&registers) {
+				*dst = register[lane];
+			}
+		}
+		drop(registers);
+		drop(value);
+
+		if self.flags.aligned {
+			let addr = index.get_as::<u32, B1, 32>(self.addr)?;
+			for (i, &bit_col) in self.addr_bits.iter().enumerate() {
+				let mut bit_col = index.get_mut(bit_col)?;
+				for (row, &addr) in addr.iter().enumerate() {
+					set_packed_slice(&mut bit_col, row, B1::from((addr >> i) & 1 == 1));
+				}
+			}
+			for (i, &byte_addr) in self.byte_addrs.iter().enumerate().skip(1) {
+				let mut byte_addr = index.get_mut_as::<u32, B32, 1>(byte_addr)?;
+				for (dst, &addr) in byte_addr.iter_mut().zip(addr.iter()) {
+					*dst = addr ^ i as u32;
+				}
+			}
+		} else {
+			for inc in &self.incs {
+				inc.populate(index)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::iter;
+
+	use binius_compute::cpu::alloc::CpuComputeAllocator;
+	use binius_field::{arch::OptimalUnderlier128b, as_packed_field::PackedType};
+	use rand::{Rng as _, SeedableRng, prelude::StdRng};
+
+	use super::*;
+	use crate::builder::{
+		ConstraintSystem, WitnessIndex,
+		test_utils::{ClosureFiller, validate_system_witness},
+	};
+
+	/// Encodes the accessed bytes of a register in order of increasing address.
+	fn encode(value: u64, width: AccessWidth, endianness: Endianness) -> Vec<u8> {
+		let mut bytes = value.to_le_bytes()[..width.n_bytes()].to_vec();
+		if endianness == Endianness::Big {
+			bytes.reverse();
+		}
+		bytes
+	}
+
+	/// Returns the register of `R` bits loaded from the accessed bytes.
+	fn decode<const R: usize>(bytes: &[u8], endianness: Endianness, sign_extend: bool) -> u64 {
+		let mut le_bytes = bytes.to_vec();
+		if endianness == Endianness::Big {
+			le_bytes.reverse();
+		}
+		let n_bits = bytes.len() * 8;
+		let value = le_bytes
+			.iter()
+			.rev()
+			.fold(0u64, |acc, &byte| (acc << 8) | byte as u64);
+		let value = if sign_extend && n_bits < 64 && (value >> (n_bits - 1)) & 1 == 1 {
+			value | (u64::MAX << n_bits)
+		} else {
+			value
+		};
+		value & (u64::MAX >> (64 - R))
+	}
+
+	/// Checks the accesses of a table against a memory table. If `with_selector` is set, every
+	/// third row of the access table is unselected and holds an arbitrary address and register,
+	/// which need not be aligned or extended.
+	fn check_access<const R: usize>(mut flags: MemoryAccessFlags, with_selector: bool) {
+		const N_ACCESSES: usize = 29;
+
+		let mut cs = ConstraintSystem::new();
+		let memory = cs.add_channel("memory");
+
+		let mut access_table = cs.add_table("access");
+		let access_table_id = access_table.id();
+		let addr = access_table.add_committed::<B1, 32>("addr");
+		let value = access_table.add_committed::<B1, R>("value");
+		let selector = with_selector.then(|| access_table.add_committed::<B1, 1>("selector"));
+		flags.selector = selector;
+		let access = MemoryAccess::new(&mut access_table, memory, addr, value, flags.clone());
+
+		let mut memory_table = cs.add_table("memory");
+		let memory_table_id = memory_table.id();
+		let mem_addr = memory_table.add_committed::<B32, 1>("addr");
+		let mem_byte = memory_table.add_committed::<B8, 1>("byte");
+		let mem_values = [mem_addr, upcast_col(mem_byte)];
+		match flags.op {
+			MemoryOp::Load { .. } => memory_table.push(memory, mem_values),
+			MemoryOp::Store => memory_table.pull(memory, mem_values),
+		}
+
+		let n_bytes = flags.width.n_bytes();
+		let mut rng = StdRng::seed_from_u64(0);
+		let accesses = (0..N_ACCESSES)
+			.map(|i| {
+				if with_selector && i % 3 == 2 {
+					return (rng.random(), rng.random::<u64>() & (u64::MAX >> (64 - R)), false);
+				}
+				let addr = match (flags.aligned, i) {
+					(false, 0) => u32::MAX,
+					(false, _) => rng.random(),
+					(true, _) => rng.random::<u32>() & !(n_bytes as u32 - 1),
+				};
+				let value = match flags.op {
+					MemoryOp::Load { sign_extend } => {
+						let bytes = iter::repeat_with(|| rng.random())
+							.take(n_bytes)
+							.collect::<Vec<_>>();
+						decode::<R>(&bytes, flags.endianness, sign_extend)
+					}
+					MemoryOp::Store => rng.random::<u64>() & (u64::MAX >> (64 - R)),
+				};
+				(addr, value, true)
+			})
+			.collect::<Vec<_>>();
+		let memory_bytes = accesses
+			.iter()
+			.filter(|&&(_, _, selected)| selected)
+			.flat_map(|&(addr, value, _)| {
+				let bytes = encode(value, flags.width, flags.endianness);
+				iter::zip(0.., bytes).map(move |(i, byte)| (addr.wrapping_add(i), byte))
+			})
+			.collect::<Vec<_>>();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 16);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness =
+			WitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(&cs, &allocator);
+
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(access_table_id, |accesses: &[(u32, u64, bool)], segment| {
+					{
+						let mut addr = segment.get_mut_as::<u32, _, 32>(access.addr)?;
+						let mut value = segment.get_mut_as::<u8, _, R>(access.value)?;
+						for (i, (a, v, _)) in accesses.iter().enumerate() {
+							addr[i] = *a;
+							value[i * R / 8..(i + 1) * R / 8]
+								.copy_from_slice(&v.to_le_bytes()[..R / 8]);
+						}
+					}
+					if let Some(selector) = selector {
+						let mut selector = segment.get_mut(selector)?;
+						for (i, &(_, _, selected)) in accesses.iter().enumerate() {
+							set_packed_slice(&mut selector, i, B1::from(selected));
+						}
+					}
+					access.populate(segment)
+				}),
+				&accesses,
+			)
+			.unwrap();
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(memory_table_id, |memory_bytes, segment| {
+					let mut addr = segment.get_mut_as::<u32, _, 1>(mem_addr)?;
+					let mut byte = segment.get_mut_as::<u8, _, 1>(mem_byte)?;
+					for (i, (a, b)) in memory_bytes.iter().enumerate() {
+						addr[i] = *a;
+						byte[i] = *b;
+					}
+					Ok(())
+				}),
+				&memory_bytes,
+			)
+			.unwrap();
+
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
+	}
+
+	#[test]
+	fn test_load_u8_sign_extended() {
+		check_access::<32>(
+			MemoryAccessFlags {
+				width: AccessWidth::U8,
+				endianness: Endianness::Little,
+				op: MemoryOp::Load { sign_extend: true },
+				aligned: false,
+				selector: None,
+			},
+			false,
+		);
+	}
+
+	#[test]
+	fn test_load_u16_big_endian_sign_extended() {
+		check_access::<64>(
+			MemoryAccessFlags {
+				width: AccessWidth::U16,
+				endianness: Endianness::Big,
+				op: MemoryOp::Load { sign_extend: true },
+				aligned: true,
+				selector: None,
+			},
+			false,
+		);
+	}
+
+	#[test]
+	fn test_load_u32_unaligned() {
+		check_access::<64>(
+			MemoryAccessFlags {
+				width: AccessWidth::U32,
+				endianness: Endianness::Little,
+				op: MemoryOp::Load { sign_extend: false },
+				aligned: false,
+				selector: None,
+			},
+			false,
+		);
+	}
+
+	#[test]
+	fn test_store_u64_big_endian() {
+		check_access::<64>(
+			MemoryAccessFlags {
+				width: AccessWidth::U64,
+				endianness: Endianness::Big,
+				op: MemoryOp::Store,
+				aligned: true,
+				selector: None,
+			},
+			false,
+		);
+	}
+
+	#[test]
+	fn test_store_u16_unaligned() {
+		check_access::<32>(
+			MemoryAccessFlags {
+				width: AccessWidth::U16,
+				endianness: Endianness::Little,
+				op: MemoryOp::Store,
+				aligned: false,
+				selector: None,
+			},
+			false,
+		);
+	}
+
+	#[test]
+	fn test_load_u32_aligned_selected() {
+		check_access::<64>(
+			MemoryAccessFlags {
+				width: AccessWidth::U32,
+				endianness: Endianness::Little,
+				op: MemoryOp::Load { sign_extend: true },
+				aligned: true,
+				selector: None,
+			},
+			true,
+		);
+	}
+
+	#[test]
+	fn test_store_u16_unaligned_selected() {
+		check_access::<32>(
+			MemoryAccessFlags {
+				width: AccessWidth::U16,
+				endianness: Endianness::Big,
+				op: MemoryOp::Store,
+				aligned: false,
+				selector: None,
+			},
+			true,
+		);
+	}
+}
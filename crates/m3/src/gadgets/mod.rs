@@ -6,8 +6,10 @@ pub mod div;
 pub mod hash;
 pub mod indexed_lookup;
 pub mod lookup;
+pub mod memory;
 pub mod merkle_tree;
 pub mod mul;
+pub mod sparse;
 pub mod structured;
 pub mod sub;
 mod util;
@@ -1,11 +1,15 @@
 // Copyright 2025 Irreducible Inc.
 
-use std::{array, marker::PhantomData};
+use std::{
+	array,
+	collections::{BTreeMap, VecDeque},
+	marker::PhantomData,
+};
 
 use anyhow::Result;
 use binius_field::{
-	BinaryField, ExtensionField, Field, PackedExtension, PackedField, PackedSubfield, TowerField,
-	packed::set_packed_slice,
+	BinaryField, ExtensionField, Field, PackedExtension, PackedField, PackedFieldIndexable,
+	PackedSubfield, TowerField, packed::set_packed_slice,
 };
 use itertools::izip;
 
@@ -721,6 +725,227 @@ impl MulSU32 {
 	}
 }
 
+/// A gadget for 32-bit unsigned integer multiplication on vertically-packed bit columns.
+///
+/// This gadget has input columns `xin` and `yin` for the two 32-bit integers to be multiplied, and
+/// output columns `zout_low` and `zout_high` for the low and high halves of the 64-bit product, and
+/// it constrains that `xin * yin = zout_high * 2^32 + zout_low` as integers.
+///
+/// Unlike [`MulUU32`], which checks the product with exponentiations, this gadget reduces the
+/// 1024 partial products `xin[i] * yin[j]` with a tree of carry-save full and half adders. Every
+/// carry bit is committed, and so is every sum bit that adds partial products. The constraints
+/// have degree at most 4.
+#[derive(Debug)]
+pub struct U32Mul {
+	// Inputs
+	pub xin: Col<B1, 32>,
+	pub yin: Col<B1, 32>,
+
+	// Private
+	x_bits: [Col<B1>; 32],
+	y_bits: [Col<B1>; 32],
+	adders: Vec<CarrySaveAdder>,
+	product_bits: [Col<B1>; 64],
+
+	// Outputs
+	/// The low 32 bits of the product.
+	pub zout_low: Col<B1, 32>,
+	/// The high 32 bits of the product.
+	pub zout_high: Col<B1, 32>,
+}
+
+/// A bit of the carry-save reduction in [`U32Mul`].
+#[derive(Debug, Clone, Copy)]
+enum CarrySaveBit {
+	/// The product of the `i`-th bit of `xin` and the `j`-th bit of `yin`.
+	PartialProduct(usize, usize),
+	/// An output of a previous adder.
+	Col(Col<B1>),
+}
+
+/// A full adder, or a half adder if `c` is `None`.
+#[derive(Debug)]
+struct CarrySaveAdder {
+	a: CarrySaveBit,
+	b: CarrySaveBit,
+	c: Option<CarrySaveBit>,
+	sum: Col<B1>,
+	/// The carry, which is `None` if it would be added at bit 64, where it is known to be zero.
+	carry: Option<Col<B1>>,
+}
+
+impl U32Mul {
+	pub fn new(table: &mut TableBuilder, xin: Col<B1, 32>, yin: Col<B1, 32>) -> Self {
+		let x_bits = array::from_fn(|i| table.add_selected(format!("x[{i}]"), xin, i));
+		let y_bits = array::from_fn(|j| table.add_selected(format!("y[{j}]"), yin, j));
+
+		let zout_low = table.add_committed::<B1, 32>("zout_low");
+		let zout_high = table.add_committed::<B1, 32>("zout_high");
+		let product_bits = array::from_fn(|k| {
+			if k < 32 {
+				table.add_selected(format!("z[{k}]"), zout_low, k)
+			} else {
+				table.add_selected(format!("z[{k}]"), zout_high, k - 32)
+			}
+		});
+
+		let expr = |bit: CarrySaveBit| -> Expr<B1, 1> {
+			match bit {
+				CarrySaveBit::PartialProduct(i, j) => x_bits[i] * y_bits[j],
+				CarrySaveBit::Col(col) => col.into(),
+			}
+		};
+
+		// The bits of every weight of the product, which are reduced from the lowest weight up,
+		// so that every carry is added to its weight before that weight is reduced.
+		let mut weights = vec![VecDeque::new(); 64];
+		for i in 0..32 {
+			for j in 0..32 {
+				weights[i + j].push_back(CarrySaveBit::PartialProduct(i, j));
+			}
+		}
+
+		let mut adders = Vec::new();
+		for k in 0..64 {
+			let mut bits = std::mem::take(&mut weights[k]);
+			let mut n_adders = 0;
+			while bits.len() > 1 {
+				let (Some(a), Some(b)) = (bits.pop_front(), bits.pop_front()) else {
+					unreachable!("the loop condition ensures that there are at least two bits");
+				};
+				let c = bits.pop_front();
+
+				let (sum, carry) = match c {
+					Some(c) => (
+						expr(a) + expr(b) + expr(c),
+						expr(a) * expr(b) + expr(a) * expr(c) + expr(b) * expr(c),
+					),
+					None => (expr(a) + expr(b), expr(a) * expr(b)),
+				};
+				let sum = table.add_computed(format!("sum[{k}][{n_adders}]"), sum);
+				let carry = (k < 63).then(|| {
+					let carry = table.add_computed(format!("carry[{k}][{n_adders}]"), carry);
+					weights[k + 1].push_back(CarrySaveBit::Col(carry));
+					carry
+				});
+				bits.push_back(CarrySaveBit::Col(sum));
+				adders.push(CarrySaveAdder {
+					a,
+					b,
+					c,
+					sum,
+					carry,
+				});
+				n_adders += 1;
+			}
+
+			match bits.pop_front() {
+				Some(bit) => table.assert_zero(format!("z[{k}]"), expr(bit) - product_bits[k]),
+				None => table.assert_zero(format!("z[{k}]"), product_bits[k].into()),
+			}
+		}
+
+		Self {
+			xin,
+			yin,
+			x_bits,
+			y_bits,
+			adders,
+			product_bits,
+			zout_low,
+			zout_high,
+		}
+	}
+
+	/// Populates the product and all internal columns from the input columns, which must be
+	/// populated before.
+	///
+	/// The adders are evaluated on bit-sliced words, which hold the bits of 32 rows each.
+	pub fn populate<P>(&self, index: &mut TableWitnessSegment<P>) -> Result<()>
+	where
+		P: PackedFieldIndexable<Scalar = B128> + PackedExtension<B1>,
+	{
+		let n_words = index.get_as::<u32, B1, 1>(self.x_bits[0])?.len();
+
+		let (x_words, y_words, z_words) = {
+			let xin = index.get_as::<u32, B1, 32>(self.xin)?;
+			let yin = index.get_as::<u32, B1, 32>(self.yin)?;
+			let mut zout_low = index.get_mut_as::<u32, B1, 32>(self.zout_low)?;
+			let mut zout_high = index.get_mut_as::<u32, B1, 32>(self.zout_high)?;
+
+			let mut products = Vec::with_capacity(xin.len());
+			for (i, (&x, &y)) in xin.iter().zip(yin.iter()).enumerate() {
+				let product = x as u64 * y as u64;
+				zout_low[i] = product as u32;
+				zout_high[i] = (product >> 32) as u32;
+				products.push(product);
+			}
+			(
+				bit_slice::<_, 32>(&xin, n_words),
+				bit_slice::<_, 32>(&yin, n_words),
+				bit_slice::<_, 64>(&products, n_words),
+			)
+		};
+
+		for (col, words) in izip!(self.x_bits, &x_words)
+			.chain(izip!(self.y_bits, &y_words))
+			.chain(izip!(self.product_bits, &z_words))
+		{
+			index.get_mut_as::<u32, B1, 1>(col)?.copy_from_slice(words);
+		}
+
+		let mut col_words = BTreeMap::new();
+		let words = |bit: CarrySaveBit, col_words: &BTreeMap<_, Vec<u32>>| match bit {
+			CarrySaveBit::PartialProduct(i, j) => x_words[i]
+				.iter()
+				.zip(&y_words[j])
+				.map(|(x, y)| x & y)
+				.collect::<Vec<_>>(),
+			CarrySaveBit::Col(col) => col_words[&col.id()].clone(),
+		};
+		for adder in &self.adders {
+			let a = words(adder.a, &col_words);
+			let b = words(adder.b, &col_words);
+			let c = match adder.c {
+				Some(c) => words(c, &col_words),
+				None => vec![0; n_words],
+			};
+			let sum = izip!(&a, &b, &c)
+				.map(|(a, b, c)| a ^ b ^ c)
+				.collect::<Vec<_>>();
+			index
+				.get_mut_as::<u32, B1, 1>(adder.sum)?
+				.copy_from_slice(&sum);
+			col_words.insert(adder.sum.id(), sum);
+
+			if let Some(carry_col) = adder.carry {
+				let carry = izip!(&a, &b, &c)
+					.map(|(a, b, c)| (a & b) | (a & c) | (b & c))
+					.collect::<Vec<_>>();
+				index
+					.get_mut_as::<u32, B1, 1>(carry_col)?
+					.copy_from_slice(&carry);
+				col_words.insert(carry_col.id(), carry);
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Transposes the integer values of consecutive rows into `N` bit-sliced columns of `n_words`
+/// words, where bit `r % 32` of word `r / 32` of column `i` is bit `i` of the value in row `r`.
+fn bit_slice<T: Copy + Into<u64>, const N: usize>(values: &[T], n_words: usize) -> [Vec<u32>; N] {
+	let mut columns = array::from_fn(|_| vec![0u32; n_words]);
+	for (row, &value) in values.iter().enumerate() {
+		let value = value.into();
+		for (bit, column) in columns.iter_mut().enumerate() {
+			column[row / 32] |= (((value >> bit) & 1) as u32) << (row % 32);
+		}
+	}
+	columns
+}
+
 /// Simple struct to convert to and from Two's complement representation based on bits. See
 /// [`SignConverter::new`]
 ///
@@ -0,0 +1,179 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Tables that hold only the non-default rows of a sparse trace.
+//!
+//! When almost every row of a trace has default values, for example the rows of an opcode that
+//! is rarely executed, it is cheaper to commit a table of just the non-default rows, each tagged
+//! with its index in the dense trace. The [`SparseRows`] gadget constrains the indices of such a
+//! table to be distinct and within a public range.
+//!
+//! Columns cannot refer to the adjacent row, so the indices are ordered through a channel instead.
+//! Every row holds its index and the index of the following row, pulls its index from the channel
+//! and pushes the next index. With the boundaries pushing the first index and pulling the end of
+//! the range, the channel only balances if the rows form a chain from the first index to the end,
+//! and every link of the chain is constrained to be strictly increasing.
+
+use binius_core::constraint_system::channel::{Boundary, ChannelId, FlushDirection};
+use binius_field::{ExtensionField, Field, PackedExtension, PackedFieldIndexable, TowerField};
+
+use super::sub::{U32Sub, U32SubFlags};
+use crate::builder::{B1, B32, B128, Col, TableBuilder, TableWitnessSegment};
+
+/// A gadget for the row indices of a sparse table.
+///
+/// The gadget has input columns `index` for the index of the row in the dense trace and
+/// `next_index` for the index of the following row of the table, or the end of the range for the
+/// last row. The rows may be in any order in the table, but the indices are constrained to be
+/// strictly increasing along the chain of `next_index` links, so no two rows have the same index.
+///
+/// The links are flushed to a dedicated channel, which must be balanced by the boundaries returned
+/// by [`Self::boundaries`].
+#[derive(Debug)]
+pub struct SparseRows {
+	// Inputs
+	pub index: Col<B1, 32>,
+	pub next_index: Col<B1, 32>,
+
+	// Private
+	sub: U32Sub,
+
+	/// The channel of the links between consecutive rows.
+	pub links: ChannelId,
+}
+
+impl SparseRows {
+	pub fn new(table: &mut TableBuilder, links: ChannelId) -> Self {
+		let index = table.add_committed("index");
+		let next_index = table.add_committed("next_index");
+
+		// index < next_index if and only if index - next_index borrows.
+		let sub = U32Sub::new(
+			table,
+			index,
+			next_index,
+			U32SubFlags {
+				expose_final_borrow: true,
+				..Default::default()
+			},
+		);
+		let final_borrow = sub
+			.final_borrow
+			.expect("final borrow is exposed by the flags");
+		table.assert_zero("increasing", final_borrow - B1::ONE);
+
+		let index_packed = table.add_packed::<_, 32, B32, 1>("index_packed", index);
+		let next_index_packed = table.add_packed::<_, 32, B32, 1>("next_index_packed", next_index);
+		table.pull(links, [index_packed]);
+		table.push(links, [next_index_packed]);
+
+		Self {
+			index,
+			next_index,
+			sub,
+			links,
+		}
+	}
+
+	/// Returns the boundaries that balance the links of a table whose indices are in the range
+	/// `first..end`, and which has a row with index `first`.
+	///
+	/// An empty table balances with any `first` that is equal to `end`.
+	pub fn boundaries<F>(&self, first: u32, end: u32) -> [Boundary<F>; 2]
+	where
+		F: TowerField + ExtensionField<B32>,
+	{
+		[(first, FlushDirection::Push), (end, FlushDirection::Pull)].map(|(value, direction)| {
+			Boundary {
+				values: vec![B32::new(value).into()],
+				channel_id: self.links,
+				direction,
+				multiplicity: 1,
+			}
+		})
+	}
+
+	/// Populates the columns of the gadget from the `index` and `next_index` columns, which the
+	/// caller must populate first.
+	pub fn populate<P>(&self, index: &mut TableWitnessSegment<P>) -> Result<(), anyhow::Error>
+	where
+		P: PackedFieldIndexable<Scalar = B128> + PackedExtension<B1>,
+	{
+		self.sub.populate(index)
+	}
+}
+
+/// Returns the `(index, next_index)` pairs of the rows of a sparse table with the given indices,
+/// which must be strictly increasing and less than `end`.
+pub fn sparse_links(indices: &[u32], end: u32) -> Vec<(u32, u32)> {
+	indices
+		.iter()
+		.zip(indices.iter().skip(1).chain([&end]))
+		.map(|(&index, &next_index)| {
+			assert!(index < next_index, "indices must be strictly increasing and less than end");
+			(index, next_index)
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeSet;
+
+	use binius_compute::cpu::alloc::CpuComputeAllocator;
+	use binius_field::{arch::OptimalUnderlier128b, as_packed_field::PackedType};
+	use rand::{Rng as _, SeedableRng, prelude::StdRng};
+
+	use super::*;
+	use crate::builder::{
+		ConstraintSystem, WitnessIndex,
+		test_utils::{ClosureFiller, validate_system_witness},
+	};
+
+	#[test]
+	fn test_sparse_rows() {
+		const END: u32 = 1 << 20;
+
+		let mut cs = ConstraintSystem::new();
+		let links = cs.add_channel("links");
+		let mut table = cs.add_table("sparse");
+		let table_id = table.id();
+		let sparse = SparseRows::new(&mut table, links);
+		let value = table.add_committed::<B1, 32>("value");
+
+		// The rows are shuffled to check that the order of the rows in the table does not matter.
+		let mut rng = StdRng::seed_from_u64(0);
+		let indices = (0..37)
+			.map(|_| rng.random_range(0..END))
+			.collect::<BTreeSet<_>>()
+			.into_iter()
+			.collect::<Vec<_>>();
+		let mut rows = sparse_links(&indices, END);
+		rows.reverse();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness =
+			WitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(&cs, &allocator);
+		witness
+			.fill_table_sequential(
+				&ClosureFiller::new(table_id, |rows: &[(u32, u32)], segment| {
+					{
+						let mut index = segment.get_mut_as::<u32, _, 32>(sparse.index)?;
+						let mut next_index = segment.get_mut_as::<u32, _, 32>(sparse.next_index)?;
+						let mut value = segment.get_mut_as::<u32, _, 32>(value)?;
+						for (i, &(row_index, row_next_index)) in rows.iter().enumerate() {
+							index[i] = row_index;
+							next_index[i] = row_next_index;
+							value[i] = row_index.wrapping_mul(0x9e3779b9);
+						}
+					}
+					sparse.populate(segment)
+				}),
+				&rows,
+			)
+			.unwrap();
+
+		let boundaries = sparse.boundaries(indices[0], END).to_vec();
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, boundaries);
+	}
+}
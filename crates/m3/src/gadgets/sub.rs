@@ -4,7 +4,8 @@ use std::{array, marker::PhantomData};
 
 use binius_core::oracle::ShiftVariant;
 use binius_field::{
-	Field, PackedExtension, PackedField, PackedFieldIndexable, packed::set_packed_slice,
+	Field, PackedExtension, PackedField, PackedFieldIndexable,
+	packed::{get_packed_slice, set_packed_slice},
 };
 use itertools::izip;
 
@@ -27,6 +28,8 @@ pub struct U32Sub {
 	bout: Col<B1, 32>,
 	bout_shl: Col<B1, 32>,
 	bin: Col<B1, 32>,
+	borrow_in_bit: Option<Col<B1, 32>>,
+	borrow_in_padded: Option<Col<B1, 32>>,
 
 	// Outputs
 	/// The output column, either committed if `flags.commit_zout` is set, otherwise a linear
@@ -44,6 +47,11 @@ pub struct U32SubFlags {
 	// Optionally a column for a dynamic borrow in bit. This *must* be zero in all bits except the
 	// 0th.
 	pub borrow_in_bit: Option<Col<B1, 32>>,
+	// Optionally a column of borrow in bits, such as the `final_borrow` of another instance, which
+	// chains the subtractions of the limbs of a wider integer. It is zero-padded to the borrow in
+	// bit of the lowest bit without committing a column. This must not be set with
+	// `borrow_in_bit`.
+	pub borrow_in: Option<Col<B1>>,
 	pub expose_final_borrow: bool,
 	pub commit_zout: bool,
 }
@@ -55,10 +63,19 @@ impl U32Sub {
 		yin: Col<B1, 32>,
 		flags: U32SubFlags,
 	) -> Self {
+		assert!(
+			flags.borrow_in_bit.is_none() || flags.borrow_in.is_none(),
+			"at most one of borrow_in_bit and borrow_in may be set"
+		);
+
 		let bout = table.add_committed("bout");
 		let bout_shl = table.add_shifted("bout_shl", bout, 5, 1, ShiftVariant::LogicalLeft);
 
-		let bin = if let Some(borrow_in_bit) = flags.borrow_in_bit {
+		let borrow_in_padded = flags
+			.borrow_in
+			.map(|borrow_in| table.add_zero_pad("borrow_in_bit", borrow_in, 0));
+		let borrow_in_bit = flags.borrow_in_bit.or(borrow_in_padded);
+		let bin = if let Some(borrow_in_bit) = borrow_in_bit {
 			table.add_computed("bin", bout_shl + borrow_in_bit)
 		} else {
 			bout_shl
@@ -90,6 +107,8 @@ impl U32Sub {
 			bout,
 			bout_shl,
 			bin,
+			borrow_in_bit,
+			borrow_in_padded,
 			zout,
 			final_borrow,
 			flags,
@@ -102,6 +121,16 @@ impl U32Sub {
 	where
 		P: PackedFieldIndexable<Scalar = B128> + PackedExtension<B1>,
 	{
+		if let (Some(borrow_in), Some(borrow_in_padded)) =
+			(self.flags.borrow_in, self.borrow_in_padded)
+		{
+			let borrow_in = index.get(borrow_in)?;
+			let mut borrow_in_padded = index.get_mut_as::<u32, _, 32>(borrow_in_padded)?;
+			for (i, borrow_in_padded) in borrow_in_padded.iter_mut().enumerate() {
+				*borrow_in_padded = u32::from(get_packed_slice(&borrow_in, i) == B1::ONE);
+			}
+		}
+
 		let xin: std::cell::RefMut<'_, [u32]> = index.get_mut_as(self.xin)?;
 		let yin: std::cell::RefMut<'_, [u32]> = index.get_mut_as(self.yin)?;
 		let mut bout: std::cell::RefMut<'_, [u32]> = index.get_mut_as(self.bout)?;
@@ -114,7 +143,7 @@ impl U32Sub {
 			None
 		};
 
-		if let Some(borrow_in_bit) = self.flags.borrow_in_bit {
+		if let Some(borrow_in_bit) = self.borrow_in_bit {
 			// This is u32 assumed to be either 0 or 1.
 			let borrow_in_bit = index.get_mut_as(borrow_in_bit)?;
 			let mut bout_shl = index.get_mut_as(self.bout_shl)?;
@@ -159,6 +188,60 @@ impl U32Sub {
 	}
 }
 
+/// A gadget for negating 32-bit integers on vertically-packed bit columns.
+///
+/// This gadget has an input column `yin` and an output column `zout`, and it constrains that
+/// `0 - yin = zout` as integers, or `0 - yin - borrow_in = zout` if a dynamic borrow in bit is
+/// given in the flags. The final borrow is set unless both `yin` and the borrow in are zero. It is
+/// a [`U32Sub`] with a constant zero first operand, which the gadget populates.
+#[derive(Debug)]
+pub struct U32Neg {
+	// Inputs
+	pub yin: Col<B1, 32>,
+
+	// Private
+	zero: Col<B1, 32>,
+	sub: U32Sub,
+
+	// Outputs
+	/// The output column, either committed if `flags.commit_zout` is set, otherwise a linear
+	/// combination derived column.
+	pub zout: Col<B1, 32>,
+	/// This is `Some` if `flags.expose_final_borrow` is set, otherwise it is `None`.
+	pub final_borrow: Option<Col<B1>>,
+}
+
+impl U32Neg {
+	pub fn new(table: &mut TableBuilder, yin: Col<B1, 32>, flags: U32SubFlags) -> Self {
+		// The columns are namespaced by the input, so that several negations in one table have
+		// distinct column names.
+		let mut table = table.with_namespace(format!("u32_neg[{}]", yin.table_index.0));
+		let zero = table.add_constant("zero", [B1::ZERO; 32]);
+		let sub = U32Sub::new(&mut table, zero, yin, flags);
+
+		Self {
+			yin,
+			zero,
+			zout: sub.zout,
+			final_borrow: sub.final_borrow,
+			sub,
+		}
+	}
+
+	/// Returns the flags modifying the gadget's behavior.
+	pub fn flags(&self) -> &U32SubFlags {
+		&self.sub.flags
+	}
+
+	pub fn populate<P>(&self, index: &mut TableWitnessSegment<P>) -> Result<(), anyhow::Error>
+	where
+		P: PackedFieldIndexable<Scalar = B128> + PackedExtension<B1>,
+	{
+		index.get_mut_as::<u32, _, 32>(self.zero)?.fill(0);
+		self.sub.populate(index)
+	}
+}
+
 /// Gadget for unsigned subtraction using non-packed one-bit columns generic over `u32` and `u64`
 #[derive(Debug)]
 pub struct WideSub<UX: UnsignedAddPrimitives, const BIT_LENGTH: usize> {
@@ -285,7 +368,7 @@ mod tests {
 	use rand::{Rng as _, SeedableRng, prelude::StdRng};
 
 	use super::*;
-	use crate::builder::{ConstraintSystem, WitnessIndex};
+	use crate::builder::{ConstraintSystem, WitnessIndex, test_utils::validate_system_witness};
 
 	#[test]
 	fn prop_test_no_borrow() {
@@ -356,6 +439,134 @@ mod tests {
 		.execute();
 	}
 
+	#[test]
+	fn test_u128_sub_chained_limbs() {
+		const N_LIMBS: usize = 4;
+		const TABLE_SIZE: usize = 1 << 5;
+
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("u128_sub");
+		let mut borrow_in = None;
+		let limbs: [U32Sub; N_LIMBS] = array::from_fn(|i| {
+			let mut table = table.with_namespace(format!("limb_{i}"));
+			let xin = table.add_committed::<B1, 32>("xin");
+			let yin = table.add_committed::<B1, 32>("yin");
+			let limb = U32Sub::new(
+				&mut table,
+				xin,
+				yin,
+				U32SubFlags {
+					borrow_in,
+					expose_final_borrow: true,
+					..Default::default()
+				},
+			);
+			borrow_in = limb.final_borrow;
+			limb
+		});
+		let table_id = table.id();
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let test_values = (0..TABLE_SIZE)
+			.map(|_| (rng.random::<u128>(), rng.random::<u128>()))
+			.chain([(0, 1)])
+			.collect::<Vec<_>>();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 14);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness =
+			WitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(&cs, &allocator);
+		let table_witness = witness.init_table(table_id, test_values.len()).unwrap();
+		let mut segment = table_witness.full_segment();
+
+		// The limbs are populated from the least significant one, so that the borrow in of a limb
+		// is populated before it is read.
+		for (i, limb) in limbs.iter().enumerate() {
+			{
+				let mut xin = segment.get_mut_as::<u32, _, 32>(limb.xin).unwrap();
+				let mut yin = segment.get_mut_as::<u32, _, 32>(limb.yin).unwrap();
+				for (row, &(x, y)) in test_values.iter().enumerate() {
+					xin[row] = (x >> (32 * i)) as u32;
+					yin[row] = (y >> (32 * i)) as u32;
+				}
+			}
+			limb.populate(&mut segment).unwrap();
+		}
+
+		for (row, &(x, y)) in test_values.iter().enumerate() {
+			let (diff, borrow) = x.overflowing_sub(y);
+			for (i, limb) in limbs.iter().enumerate() {
+				let zout = segment.get_as::<u32, _, 32>(limb.zout).unwrap();
+				assert_eq!(zout[row], (diff >> (32 * i)) as u32);
+			}
+			let final_borrow = segment
+				.get(limbs[N_LIMBS - 1].final_borrow.unwrap())
+				.unwrap();
+			assert_eq!(get_packed_slice(&final_borrow, row), B1::from(borrow));
+		}
+
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
+	}
+
+	#[test]
+	fn test_neg() {
+		// (yin, borrow_in, zout, final_borrow)
+		let test_vector = [
+			(0x00000000, 0, 0x00000000, false),
+			(0x00000001, 0, 0xFFFFFFFF, true),
+			(0x80000000, 0, 0x80000000, true),
+			(0xFFFFFFFF, 0, 0x00000001, true),
+			(0x00000000, 1, 0xFFFFFFFF, true),
+			(0x00000005, 1, 0xFFFFFFFA, true),
+		];
+
+		let mut cs = ConstraintSystem::new();
+		let mut table = cs.add_table("u32_neg");
+		let yin = table.add_committed::<B1, 32>("yin");
+		let borrow_in = table.add_committed::<B1, 32>("borrow_in");
+		let neg = U32Neg::new(
+			&mut table,
+			yin,
+			U32SubFlags {
+				borrow_in_bit: Some(borrow_in),
+				borrow_in: None,
+				expose_final_borrow: true,
+				commit_zout: false,
+			},
+		);
+		let table_id = table.id();
+
+		let mut allocator = CpuComputeAllocator::new(1 << 12);
+		let allocator = allocator.into_bump_allocator();
+		let mut witness =
+			WitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(&cs, &allocator);
+		let table_witness = witness.init_table(table_id, test_vector.len()).unwrap();
+		let mut segment = table_witness.full_segment();
+
+		{
+			let mut yin_bits = segment.get_mut_as::<u32, _, 32>(yin).unwrap();
+			let mut borrow_in_bits = segment.get_mut_as::<u32, _, 32>(borrow_in).unwrap();
+			for (i, &(y, borrow_in, _, _)) in test_vector.iter().enumerate() {
+				yin_bits[i] = y;
+				borrow_in_bits[i] = borrow_in;
+			}
+		}
+
+		neg.populate(&mut segment).unwrap();
+
+		{
+			let zout_bits = segment.get_as::<u32, _, 32>(neg.zout).unwrap();
+			let final_borrow = segment.get(neg.final_borrow.unwrap()).unwrap();
+			for (i, &(_, _, zout, expected_borrow)) in test_vector.iter().enumerate() {
+				assert_eq!(zout_bits[i], zout);
+				assert_eq!(get_packed_slice(&final_borrow, i), B1::from(expected_borrow));
+			}
+		}
+
+		witness.fill_constant_cols().unwrap();
+		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
+	}
+
 	struct TestPlan {
 		dyn_borrow_in: bool,
 		expose_final_borrow: bool,
@@ -378,6 +589,7 @@ mod tests {
 
 			let flags = U32SubFlags {
 				borrow_in_bit: borrow_in,
+				borrow_in: None,
 				expose_final_borrow: self.expose_final_borrow,
 				commit_zout: self.commit_zout,
 			};
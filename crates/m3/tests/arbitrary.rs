@@ -0,0 +1,31 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_m3::builder::arbitrary::arb_system;
+use proptest::prelude::*;
+
+proptest! {
+	#![proptest_config(ProptestConfig::with_cases(32))]
+
+	#[test]
+	fn test_arbitrary_systems_validate(system in arb_system()) {
+		system.check(false);
+	}
+}
+
+proptest! {
+	#![proptest_config(ProptestConfig::with_cases(32))]
+
+	#[test]
+	fn test_arbitrary_systems_prove_verify(system in arb_system()) {
+		system.check(true);
+	}
+}
+
+proptest! {
+	#![proptest_config(ProptestConfig::with_cases(8))]
+
+	#[test]
+	fn test_arbitrary_systems_reject_mutations(system in arb_system()) {
+		system.check_mutations(true);
+	}
+}
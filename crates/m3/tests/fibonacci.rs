@@ -25,13 +25,19 @@ mod model {
 			trace
 		}
 
-		pub fn validate(&self, start: (u32, u32), end: (u32, u32)) {
+		/// Fires the events of the trace on a new sequence channel.
+		pub fn sequence_channel(&self) -> Channel<(u32, u32)> {
 			let mut sequence_chan = Channel::default();
-			sequence_chan.push(start);
-			sequence_chan.pull(end);
 			for event in self.rows.iter() {
 				event.fire(&mut sequence_chan);
 			}
+			sequence_chan
+		}
+
+		pub fn validate(&self, start: (u32, u32), end: (u32, u32)) {
+			let mut sequence_chan = self.sequence_channel();
+			sequence_chan.push(start);
+			sequence_chan.pull(end);
 			sequence_chan.assert_balanced();
 		}
 	}
@@ -160,6 +166,17 @@ mod arithmetization {
 		}
 	}
 
+	/// Extracts the boundaries of the fibonacci pairs channel from a model run, with the pair
+	/// encoded as the packed 32-bit columns of the flushes.
+	fn sequence_boundaries(
+		trace: &FibonacciTrace,
+		fibonacci_pairs: ChannelId,
+	) -> Vec<Boundary<B128>> {
+		trace
+			.sequence_channel()
+			.boundaries(fibonacci_pairs, |&(f0, f1)| vec![B32::new(f0).into(), B32::new(f1).into()])
+	}
+
 	#[test]
 	fn test_fibonacci() {
 		let mut cs = ConstraintSystem::new();
@@ -189,6 +206,7 @@ mod arithmetization {
 				multiplicity: 1,
 			},
 		];
+		assert_eq!(sequence_boundaries(&trace, fibonacci_pairs), boundaries);
 		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, boundaries);
 	}
 
@@ -221,6 +239,7 @@ mod arithmetization {
 				multiplicity: 1,
 			},
 		];
+		assert_eq!(sequence_boundaries(&trace, fibonacci_pairs), boundaries);
 		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, boundaries);
 	}
 
@@ -257,6 +276,7 @@ mod arithmetization {
 				multiplicity: 1,
 			},
 		];
+		assert_eq!(sequence_boundaries(&trace, fibonacci_pairs), boundaries);
 		validate_system_witness::<OptimalUnderlier128b>(&cs, witness, boundaries);
 	}
 }
@@ -10,12 +10,13 @@ use binius_field::{
 };
 use binius_m3::{
 	builder::{
-		B32, B64, B128, ConstraintSystem, TableFiller, TableId, TableWitnessSegment, WitnessIndex,
+		B1, B32, B64, B128, ConstraintSystem, TableFiller, TableId, TableWitnessSegment,
+		WitnessIndex,
 		test_utils::{ClosureFiller, validate_system_witness},
 	},
 	gadgets::{
 		div::{DivSS32, DivUU32},
-		mul::{MulSS32, MulSU32, MulUU32, MulUU64},
+		mul::{MulSS32, MulSU32, MulUU32, MulUU64, U32Mul},
 	},
 };
 use bytemuck::Contiguous;
@@ -392,3 +393,59 @@ fn test_mul_next_to_stacked_col() {
 
 	validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
 }
+
+#[test]
+fn test_u32_mul() {
+	let mut cs = ConstraintSystem::new();
+	let mut table = cs.add_table("u32_mul");
+	let table_id = table.id();
+	let xin = table.add_committed::<B1, 32>("xin");
+	let yin = table.add_committed::<B1, 32>("yin");
+	let mul = U32Mul::new(&mut table, xin, yin);
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let test_inputs = chain!(
+		[
+			(0, 0),
+			(u32::MAX, u32::MAX),
+			(u32::MAX, 1),
+			(1 << 31, 2),
+			(0, u32::MAX)
+		],
+		repeat_with(|| (rng.random::<u32>(), rng.random::<u32>())).take(100)
+	)
+	.collect::<Vec<_>>();
+
+	let mut allocator = CpuComputeAllocator::new(1 << 16);
+	let allocator = allocator.into_bump_allocator();
+	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier128b, B128>>::new(&cs, &allocator);
+	witness
+		.fill_table_sequential(
+			&ClosureFiller::new(table_id, |events: &[(u32, u32)], witness| {
+				{
+					let mut xin = witness.get_mut_as::<u32, _, 32>(mul.xin)?;
+					let mut yin = witness.get_mut_as::<u32, _, 32>(mul.yin)?;
+					for (i, &(x, y)) in events.iter().enumerate() {
+						xin[i] = x;
+						yin[i] = y;
+					}
+				}
+				mul.populate(witness)
+			}),
+			&test_inputs,
+		)
+		.unwrap();
+
+	{
+		let segment = witness.get_table(table_id).unwrap().full_segment();
+		let zout_low = segment.get_as::<u32, _, 32>(mul.zout_low).unwrap();
+		let zout_high = segment.get_as::<u32, _, 32>(mul.zout_high).unwrap();
+		for (i, &(x, y)) in test_inputs.iter().enumerate() {
+			let product = x as u64 * y as u64;
+			assert_eq!(zout_low[i], product as u32);
+			assert_eq!(zout_high[i], (product >> 32) as u32);
+		}
+	}
+
+	validate_system_witness::<OptimalUnderlier128b>(&cs, witness, vec![]);
+}
@@ -0,0 +1,123 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Tests that the prover stops at its resource limits.
+
+use std::time::Duration;
+
+use binius_compute::{ComputeHolder, cpu::alloc::CpuComputeAllocator};
+use binius_core::{
+	constraint_system::{
+		Proof,
+		error::Error,
+		limits::{CancellationToken, ProverLimits},
+		prove_with_limits, verify,
+	},
+	fiat_shamir::HasherChallenger,
+};
+use binius_fast_compute::layer::FastCpuLayerHolder;
+use binius_field::{
+	arch::OptimalUnderlier, as_packed_field::PackedType, tower::CanonicalTowerFamily,
+};
+use binius_hash::groestl::{Groestl256, Groestl256ByteCompression};
+use binius_m3::builder::{
+	B128, ConstraintSystem, WitnessIndex,
+	arbitrary::{ArbitrarySystem, ArbitraryTable},
+};
+
+const LOG_INV_RATE: usize = 1;
+const SECURITY_BITS: usize = 100;
+
+fn prove_system(limits: &ProverLimits) -> Result<(), Error> {
+	let system = ArbitrarySystem {
+		tables: vec![ArbitraryTable {
+			rows: (0..32).map(|i| vec![i, 3 * i + 1]).collect(),
+			power_of_two: false,
+			products: vec![(0, 1)],
+			sums: vec![(0, 1)],
+			packed_bytes: false,
+			flushed: vec![0, 2],
+			multiplicity: 1,
+		}],
+	};
+	let mut cs = ConstraintSystem::new();
+	let cols = system.build(&mut cs);
+	let boundaries = system.boundaries(&cols);
+	let ccs = cs.compile().unwrap();
+	let ccs_digest = ccs.digest::<Groestl256>();
+
+	let mut allocator = CpuComputeAllocator::new(1 << 16);
+	let allocator = allocator.into_bump_allocator();
+	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier, B128>>::new(&cs, &allocator);
+	system.fill_witness(&cols, &mut witness).unwrap();
+	let table_sizes = witness.table_sizes();
+	let witness = witness.into_multilinear_extension_index();
+
+	let mut compute_holder = FastCpuLayerHolder::<
+		CanonicalTowerFamily,
+		PackedType<OptimalUnderlier, B128>,
+	>::new(1 << 16, 1 << 24);
+	let proof: Proof = prove_with_limits::<
+		_,
+		OptimalUnderlier,
+		CanonicalTowerFamily,
+		Groestl256,
+		Groestl256ByteCompression,
+		HasherChallenger<Groestl256>,
+		_,
+		_,
+		_,
+	>(
+		&mut compute_holder.to_data(),
+		&ccs,
+		LOG_INV_RATE,
+		SECURITY_BITS,
+		&ccs_digest,
+		&boundaries,
+		&table_sizes,
+		witness,
+		&binius_hal::make_portable_backend(),
+		limits,
+	)?;
+
+	verify::<
+		OptimalUnderlier,
+		CanonicalTowerFamily,
+		Groestl256,
+		Groestl256ByteCompression,
+		HasherChallenger<Groestl256>,
+	>(&ccs, LOG_INV_RATE, SECURITY_BITS, &ccs_digest, &boundaries, proof)
+}
+
+#[test]
+fn test_prove_within_limits() {
+	let token = CancellationToken::new();
+	let limits = ProverLimits::new()
+		.with_cancellation(token)
+		.with_time_limit(Duration::from_secs(3600))
+		.with_memory_limit(usize::MAX);
+	prove_system(&limits).unwrap();
+}
+
+#[test]
+fn test_prove_cancelled() {
+	let token = CancellationToken::new();
+	token.cancel();
+	let limits = ProverLimits::new().with_cancellation(token);
+	assert!(matches!(prove_system(&limits), Err(Error::ProverCancelled)));
+}
+
+#[test]
+fn test_prove_time_limit_exceeded() {
+	let limits = ProverLimits::new().with_time_limit(Duration::ZERO);
+	assert!(matches!(prove_system(&limits), Err(Error::ProverTimeLimitExceeded { .. })));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_prove_memory_limit_exceeded() {
+	let limits = ProverLimits::new().with_memory_limit(1 << 20);
+	assert!(matches!(
+		prove_system(&limits),
+		Err(Error::ProverMemoryLimitExceeded { limit, .. }) if limit == 1 << 20
+	));
+}
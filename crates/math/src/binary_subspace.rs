@@ -1,7 +1,11 @@
 // Copyright 2024-2025 Irreducible Inc.
 
 use binius_field::BinaryField;
-use binius_utils::{bail, iter::IterExtensions};
+use binius_utils::{
+	DeserializeBytes, SerializationError, SerializationMode, SerializeBytes, bail,
+	bytes::{Buf, BufMut},
+	iter::IterExtensions,
+};
 
 use super::error::Error;
 
@@ -117,6 +121,26 @@ impl<F: BinaryField> Default for BinarySubspace<F> {
 	}
 }
 
+impl<F: BinaryField> SerializeBytes for BinarySubspace<F> {
+	fn serialize(
+		&self,
+		write_buf: impl BufMut,
+		mode: SerializationMode,
+	) -> Result<(), SerializationError> {
+		self.basis.serialize(write_buf, mode)
+	}
+}
+
+impl<F: BinaryField> DeserializeBytes for BinarySubspace<F> {
+	fn deserialize(read_buf: impl Buf, mode: SerializationMode) -> Result<Self, SerializationError>
+	where
+		Self: Sized,
+	{
+		let basis = Vec::<F>::deserialize(read_buf, mode)?;
+		Ok(Self { basis })
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use assert_matches::assert_matches;
@@ -138,6 +162,21 @@ mod tests {
 		assert_matches!(subspace.get_checked(256), Err(Error::ArgumentRangeError { .. }));
 	}
 
+	#[test]
+	fn test_serialization_roundtrip() {
+		let subspace = BinarySubspace::<BinaryField128b>::with_dim(10).unwrap();
+		let mut buf = Vec::new();
+		subspace
+			.serialize(&mut buf, SerializationMode::Native)
+			.unwrap();
+		let deserialized = BinarySubspace::<BinaryField128b>::deserialize(
+			buf.as_slice(),
+			SerializationMode::Native,
+		)
+		.unwrap();
+		assert_eq!(deserialized, subspace);
+	}
+
 	#[test]
 	fn test_default_large_binary_subspace_iterates_elements() {
 		let subspace = BinarySubspace::<BinaryField128b>::default();
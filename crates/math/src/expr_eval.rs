@@ -0,0 +1,1042 @@
+// Copyright 2024-2025 Irreducible Inc.
+
+//! Compiled evaluation of arithmetic circuits.
+//!
+//! An [`ArithCircuitEvaluator`] lowers an [`ArithCircuit`] into a straight-line sequence of
+//! additions, multiplications and squarings, and assigns every intermediate value a scratch
+//! register. It then evaluates the circuit either at a single point or over whole batches of
+//! packed columns, processing the rows in tiles so that the temporaries of a tile stay in cache.
+
+use std::{mem::MaybeUninit, sync::Arc};
+
+use binius_field::{ExtensionField, Field, PackedField};
+use binius_utils::{
+	bail,
+	mem::{slice_assume_init_mut, slice_assume_init_ref},
+};
+
+use crate::{ArithCircuit, ArithCircuitStep, Error, RowsBatchRef};
+
+/// An evaluator of an [`ArithCircuit`] over packed field elements.
+///
+/// The circuit is compiled once on construction, and the evaluator can then be shared to evaluate
+/// it at any number of points or row batches. The circuit is compiled as given, so callers should
+/// optimize it with [`ArithCircuit::optimize`] beforehand.
+#[derive(Debug, Clone)]
+pub struct ArithCircuitEvaluator<F: Field> {
+	steps: Arc<[CircuitStep<F>]>,
+	/// The "top level expression", which depends on circuit expression evaluations
+	retval: CircuitStepArgument<F>,
+	/// The scratch register holding the result of each step in the batch evaluation
+	registers: Arc<[usize]>,
+	n_registers: usize,
+	n_vars: usize,
+}
+
+impl<F: Field> ArithCircuitEvaluator<F> {
+	pub fn new(expr: &ArithCircuit<F>) -> Self {
+		let (steps, retval) = convert_circuit_steps(expr);
+		let (registers, n_registers) = allocate_registers(&steps, retval);
+
+		Self {
+			steps: steps.into(),
+			retval,
+			registers: registers.into(),
+			n_registers,
+			n_vars: expr.n_vars(),
+		}
+	}
+
+	/// Returns the number of variables read by the circuit.
+	pub fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	/// Returns the number of arithmetic operations executed per evaluation.
+	pub fn n_steps(&self) -> usize {
+		self.steps.len()
+	}
+
+	/// Returns the number of scratch registers, each one tile of rows wide, that batch evaluation
+	/// uses for temporaries.
+	pub fn n_registers(&self) -> usize {
+		self.n_registers
+	}
+
+	/// Evaluates the circuit at a single point.
+	///
+	/// The query may contain more values than the circuit has variables; the extra values are
+	/// ignored.
+	pub fn evaluate<P>(&self, query: &[P]) -> Result<P, Error>
+	where
+		P: PackedField<Scalar: ExtensionField<F>>,
+	{
+		if query.len() < self.n_vars {
+			bail!(Error::IncorrectQuerySize {
+				expected: self.n_vars,
+				actual: query.len(),
+			});
+		}
+
+		fn write_result<T>(target: &mut [MaybeUninit<T>], value: T) {
+			// Safety: The index is guaranteed to be within bounds because
+			// we initialize at least `self.steps.len()` using `stackalloc`.
+			unsafe {
+				target.get_unchecked_mut(0).write(value);
+			}
+		}
+
+		alloc_scratch_space::<P, _, _>(self.steps.len(), |evals| {
+			let get_argument_value = |input: CircuitStepArgument<F>, evals: &[P]| match input {
+				// Safety: The index is guaranteed to be within bounds by the construction of the
+				// circuit and the query size check above
+				CircuitStepArgument::Expr(CircuitNode::Var(index)) => unsafe {
+					*query.get_unchecked(index)
+				},
+				// Safety: The index is guaranteed to be within bounds by the circuit evaluation
+				// order
+				CircuitStepArgument::Expr(CircuitNode::Slot(slot)) => unsafe {
+					*evals.get_unchecked(slot)
+				},
+				CircuitStepArgument::Const(value) => P::broadcast(value.into()),
+			};
+
+			for (i, expr) in self.steps.iter().enumerate() {
+				// Safety: previous evaluations are initialized by the previous loop iterations (if
+				// dereferenced)
+				let (before, after) = unsafe { evals.split_at_mut_unchecked(i) };
+				let before = unsafe { slice_assume_init_mut(before) };
+				match expr {
+					CircuitStep::Add(x, y) => write_result(
+						after,
+						get_argument_value(*x, before) + get_argument_value(*y, before),
+					),
+					CircuitStep::AddMul(target_slot, x, y) => {
+						let intermediate =
+							get_argument_value(*x, before) * get_argument_value(*y, before);
+						// Safety: we know by evaluation order and construction of steps that
+						// `target.slot` is initialized
+						let target_slot = unsafe { before.get_unchecked_mut(*target_slot) };
+						*target_slot += intermediate;
+					}
+					CircuitStep::Mul(x, y) => write_result(
+						after,
+						get_argument_value(*x, before) * get_argument_value(*y, before),
+					),
+					CircuitStep::Square(x) => {
+						write_result(after, get_argument_value(*x, before).square())
+					}
+				};
+			}
+
+			// Some slots in `evals` might be empty, but we're guaranteed that
+			// if `self.retval` points to a slot, that this slot is initialized.
+			unsafe {
+				let evals = slice_assume_init_ref(evals);
+				Ok(get_argument_value(self.retval, evals))
+			}
+		})
+	}
+
+	/// Evaluates the circuit at every row of a batch of packed columns, writing the results to
+	/// `evals`.
+	///
+	/// All steps are fused into a single pass over the rows: each tile of rows runs through the
+	/// whole circuit while its intermediate values are held in cache-resident registers.
+	pub fn batch_evaluate<P>(
+		&self,
+		batch_query: &RowsBatchRef<P>,
+		evals: &mut [P],
+	) -> Result<(), Error>
+	where
+		P: PackedField<Scalar: ExtensionField<F>>,
+	{
+		let row_len = evals.len();
+		if batch_query.row_len() != row_len {
+			bail!(Error::BatchEvaluateSizeMismatch {
+				expected: row_len,
+				actual: batch_query.row_len(),
+			});
+		}
+		if batch_query.n_rows() < self.n_vars {
+			bail!(Error::IncorrectQuerySize {
+				expected: self.n_vars,
+				actual: batch_query.n_rows(),
+			});
+		}
+
+		if row_len == 0 {
+			return Ok(());
+		}
+
+		let tile_len = row_len.min(BATCH_EVALUATE_TILE_LEN);
+		alloc_scratch_space::<P, (), _>(self.n_registers * tile_len, |registers| {
+			for register in registers.iter_mut() {
+				register.write(P::zero());
+			}
+			// Safety: all elements were initialized above.
+			let registers = unsafe { slice_assume_init_mut(registers) };
+
+			for (tile_index, evals_tile) in evals.chunks_mut(tile_len).enumerate() {
+				let tile = FusedTile {
+					inputs: batch_query,
+					offset: tile_index * tile_len,
+					len: evals_tile.len(),
+					stride: tile_len,
+					registers: &self.registers,
+				};
+
+				for (i, step) in self.steps.iter().enumerate() {
+					let dst = self.registers[i] * tile_len;
+					match step {
+						CircuitStep::Add(x, y) => {
+							tile.apply(registers, dst, *x, *y, |x, y, out| *out = x + y)
+						}
+						CircuitStep::Mul(x, y) => {
+							tile.apply(registers, dst, *x, *y, |x, y, out| *out = x * y)
+						}
+						CircuitStep::Square(x) => {
+							tile.apply_unary(registers, dst, *x, |x, out| *out = x.square())
+						}
+						CircuitStep::AddMul(_, x, y) => {
+							tile.apply(registers, dst, *x, *y, |x, y, out| *out += x * y)
+						}
+					}
+				}
+
+				for (j, eval) in evals_tile.iter_mut().enumerate() {
+					*eval = tile.load(registers, self.retval, j);
+				}
+			}
+		});
+
+		Ok(())
+	}
+}
+
+/// Convert the expression to a sequence of arithmetic operations that can be evaluated in sequence.
+fn convert_circuit_steps<F: Field>(
+	expr: &ArithCircuit<F>,
+) -> (Vec<CircuitStep<F>>, CircuitStepArgument<F>) {
+	/// This struct is used to the steps in the original circuit and the converted one and back.
+	struct StepsMapping {
+		original_to_converted: Vec<Option<usize>>,
+		converted_to_original: Vec<Option<usize>>,
+	}
+
+	impl StepsMapping {
+		fn new(original_size: usize) -> Self {
+			Self {
+				original_to_converted: vec![None; original_size],
+				// The size of this vector isn't known at the start, but that's a reasonable guess
+				converted_to_original: vec![None; original_size],
+			}
+		}
+
+		fn register(&mut self, original_step: usize, converted_step: usize) {
+			self.original_to_converted[original_step] = Some(converted_step);
+
+			if converted_step >= self.converted_to_original.len() {
+				self.converted_to_original.resize(converted_step + 1, None);
+			}
+			self.converted_to_original[converted_step] = Some(original_step);
+		}
+
+		fn clear_step(&mut self, converted_step: usize) {
+			if self.converted_to_original.len() <= converted_step {
+				return;
+			}
+
+			if let Some(original_step) = self.converted_to_original[converted_step].take() {
+				self.original_to_converted[original_step] = None;
+			}
+		}
+
+		fn get_converted_step(&self, original_step: usize) -> Option<usize> {
+			self.original_to_converted[original_step]
+		}
+	}
+
+	fn convert_step<F: Field>(
+		original_step: usize,
+		original_steps: &[ArithCircuitStep<F>],
+		result: &mut Vec<CircuitStep<F>>,
+		node_to_step: &mut StepsMapping,
+	) -> CircuitStepArgument<F> {
+		if let Some(converted_step) = node_to_step.get_converted_step(original_step) {
+			return CircuitStepArgument::Expr(CircuitNode::Slot(converted_step));
+		}
+
+		match &original_steps[original_step] {
+			ArithCircuitStep::Const(constant) => CircuitStepArgument::Const(*constant),
+			ArithCircuitStep::Var(var) => CircuitStepArgument::Expr(CircuitNode::Var(*var)),
+			ArithCircuitStep::Add(left, right) => {
+				let left = convert_step(*left, original_steps, result, node_to_step);
+
+				if let CircuitStepArgument::Expr(CircuitNode::Slot(left)) = left {
+					if let ArithCircuitStep::Mul(mleft, mright) = &original_steps[*right] {
+						// Only handling e1 + (e2 * e3), not (e1 * e2) + e3, as latter was not
+						// observed in practice (the former can be enforced by rewriting
+						// expression)
+						let mleft = convert_step(*mleft, original_steps, result, node_to_step);
+						let mright = convert_step(*mright, original_steps, result, node_to_step);
+
+						// Since we'we changed the value of `left` to a new value, we need to clear
+						// the cache for it
+						node_to_step.clear_step(left);
+						node_to_step.register(original_step, left);
+						result.push(CircuitStep::AddMul(left, mleft, mright));
+
+						return CircuitStepArgument::Expr(CircuitNode::Slot(left));
+					}
+				}
+
+				let right = convert_step(*right, original_steps, result, node_to_step);
+
+				node_to_step.register(original_step, result.len());
+				result.push(CircuitStep::Add(left, right));
+				CircuitStepArgument::Expr(CircuitNode::Slot(result.len() - 1))
+			}
+			ArithCircuitStep::Mul(left, right) => {
+				let left = convert_step(*left, original_steps, result, node_to_step);
+				let right = convert_step(*right, original_steps, result, node_to_step);
+
+				node_to_step.register(original_step, result.len());
+				result.push(CircuitStep::Mul(left, right));
+				CircuitStepArgument::Expr(CircuitNode::Slot(result.len() - 1))
+			}
+			ArithCircuitStep::Pow(base, exp) => {
+				let mut acc = convert_step(*base, original_steps, result, node_to_step);
+				let base_expr = acc;
+				let highest_bit = exp.ilog2();
+
+				for i in (0..highest_bit).rev() {
+					if i == 0 {
+						node_to_step.register(original_step, result.len());
+					}
+					result.push(CircuitStep::Square(acc));
+					acc = CircuitStepArgument::Expr(CircuitNode::Slot(result.len() - 1));
+
+					if (exp >> i) & 1 != 0 {
+						result.push(CircuitStep::Mul(acc, base_expr));
+						acc = CircuitStepArgument::Expr(CircuitNode::Slot(result.len() - 1));
+					}
+				}
+
+				acc
+			}
+		}
+	}
+
+	let mut steps = Vec::new();
+	let mut steps_mapping = StepsMapping::new(expr.steps().len());
+	let ret = convert_step(expr.steps().len() - 1, expr.steps(), &mut steps, &mut steps_mapping);
+	(steps, ret)
+}
+
+/// Assigns a scratch register to the result of every circuit step.
+///
+/// The batch evaluation processes the rows in tiles and keeps the intermediate values of a tile
+/// in registers. A register is released once the last step reading its value has executed, so
+/// that the number of registers, and hence the working set of a tile, is proportional to the
+/// maximum number of simultaneously live values rather than to the number of steps.
+///
+/// Returns the register index for every step along with the total number of registers. An
+/// [`CircuitStep::AddMul`] step updates its target slot in place and shares its register.
+fn allocate_registers<F: Field>(
+	steps: &[CircuitStep<F>],
+	retval: CircuitStepArgument<F>,
+) -> (Vec<usize>, usize) {
+	fn slot_of<F>(arg: &CircuitStepArgument<F>) -> Option<usize> {
+		match arg {
+			CircuitStepArgument::Expr(CircuitNode::Slot(slot)) => Some(*slot),
+			_ => None,
+		}
+	}
+
+	fn read_slots<F: Field>(step: &CircuitStep<F>) -> [Option<usize>; 3] {
+		match step {
+			CircuitStep::Add(x, y) | CircuitStep::Mul(x, y) => [slot_of(x), slot_of(y), None],
+			CircuitStep::Square(x) => [slot_of(x), None, None],
+			CircuitStep::AddMul(target, x, y) => [Some(*target), slot_of(x), slot_of(y)],
+		}
+	}
+
+	let mut last_use = vec![0; steps.len()];
+	for (i, step) in steps.iter().enumerate() {
+		for slot in read_slots(step).into_iter().flatten() {
+			last_use[slot] = i;
+		}
+	}
+	if let Some(slot) = slot_of(&retval) {
+		last_use[slot] = usize::MAX;
+	}
+
+	let mut registers = Vec::with_capacity(steps.len());
+	let mut free_registers = Vec::new();
+	let mut n_registers = 0;
+	for (i, step) in steps.iter().enumerate() {
+		let register = match step {
+			CircuitStep::AddMul(target, _, _) => registers[*target],
+			_ => free_registers.pop().unwrap_or_else(|| {
+				n_registers += 1;
+				n_registers - 1
+			}),
+		};
+		registers.push(register);
+
+		let mut released = read_slots(step)
+			.into_iter()
+			.flatten()
+			.filter(|&slot| last_use[slot] == i)
+			.map(|slot| registers[slot])
+			.filter(|&released| released != register)
+			.collect::<Vec<_>>();
+		released.sort_unstable();
+		released.dedup();
+		free_registers.extend(released);
+	}
+
+	(registers, n_registers)
+}
+
+/// Input of the circuit calculation step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CircuitNode {
+	/// Input variable
+	Var(usize),
+	/// Evaluation at one of the previous steps
+	Slot(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CircuitStepArgument<F> {
+	Expr(CircuitNode),
+	Const(F),
+}
+
+/// Describes computation symbolically. This is used internally by ArithCircuitEvaluator.
+///
+/// ExprIds used by an Expr has to be less than the index of the Expr itself within the
+/// ArithCircuitEvaluator, to ensure it represents a directed acyclic graph that can be computed in
+/// sequence.
+#[derive(Debug)]
+enum CircuitStep<F: Field> {
+	Add(CircuitStepArgument<F>, CircuitStepArgument<F>),
+	Mul(CircuitStepArgument<F>, CircuitStepArgument<F>),
+	Square(CircuitStepArgument<F>),
+	AddMul(usize, CircuitStepArgument<F>, CircuitStepArgument<F>),
+}
+
+/// The number of packed rows evaluated together in one pass over the circuit steps.
+///
+/// The tile is small enough that the registers of typical constraint compositions stay in the L1
+/// cache, and large enough to amortize the dispatch over the circuit steps.
+const BATCH_EVALUATE_TILE_LEN: usize = 64;
+
+/// A tile of rows being evaluated by [`ArithCircuitEvaluator::batch_evaluate`].
+///
+/// Register `r` of the tile occupies the range `r * stride..r * stride + len` of the scratch
+/// buffer.
+struct FusedTile<'a, 'b, P: PackedField> {
+	inputs: &'a RowsBatchRef<'b, P>,
+	offset: usize,
+	len: usize,
+	stride: usize,
+	registers: &'a [usize],
+}
+
+impl<P: PackedField> FusedTile<'_, '_, P> {
+	/// Loads the `j`-th value of a step argument within the tile.
+	#[inline(always)]
+	fn load<F: Field>(&self, scratch: &[P], arg: CircuitStepArgument<F>, j: usize) -> P
+	where
+		P::Scalar: ExtensionField<F>,
+	{
+		match arg {
+			CircuitStepArgument::Expr(CircuitNode::Var(index)) => {
+				self.inputs.row(index)[self.offset + j]
+			}
+			CircuitStepArgument::Expr(CircuitNode::Slot(slot)) => {
+				scratch[self.registers[slot] * self.stride + j]
+			}
+			CircuitStepArgument::Const(value) => P::broadcast(value.into()),
+		}
+	}
+
+	/// Applies an elementwise operation to a step argument, updating the register that starts at
+	/// `dst` in the scratch buffer.
+	#[inline(always)]
+	fn apply_unary<F: Field>(
+		&self,
+		scratch: &mut [P],
+		dst: usize,
+		x: CircuitStepArgument<F>,
+		op: impl Fn(P, &mut P),
+	) where
+		P::Scalar: ExtensionField<F>,
+	{
+		for j in 0..self.len {
+			let x = self.load(scratch, x, j);
+			op(x, &mut scratch[dst + j]);
+		}
+	}
+
+	/// Applies an elementwise operation to two step arguments, updating the register that
+	/// starts at `dst` in the scratch buffer.
+	///
+	/// The output register may coincide with an argument register, as every element is read
+	/// before it is written.
+	#[inline(always)]
+	fn apply<F: Field>(
+		&self,
+		scratch: &mut [P],
+		dst: usize,
+		x: CircuitStepArgument<F>,
+		y: CircuitStepArgument<F>,
+		op: impl Fn(P, P, &mut P),
+	) where
+		P::Scalar: ExtensionField<F>,
+	{
+		for j in 0..self.len {
+			let x = self.load(scratch, x, j);
+			let y = self.load(scratch, y, j);
+			op(x, y, &mut scratch[dst + j]);
+		}
+	}
+}
+
+fn alloc_scratch_space<T, U, F>(size: usize, callback: F) -> U
+where
+	F: FnOnce(&mut [MaybeUninit<T>]) -> U,
+{
+	use std::mem;
+	// We don't want to deal with running destructors.
+	assert!(!mem::needs_drop::<T>());
+
+	#[cfg(miri)]
+	{
+		let mut scratch_space = Vec::<T>::with_capacity(size);
+		let out = callback(scratch_space.spare_capacity_mut());
+		drop(scratch_space);
+		out
+	}
+	#[cfg(not(miri))]
+	{
+		// `stackalloc_uninit` throws a debug assert if `size` is 0, so set minimum of 1.
+		let size = size.max(1);
+		stackalloc::stackalloc_uninit(size, callback)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::iter::repeat_with;
+
+	use assert_matches::assert_matches;
+	use binius_field::{BinaryField8b, PackedBinaryField8x16b};
+	use rand::{SeedableRng, rngs::StdRng};
+
+	use super::*;
+	use crate::{ArithExpr, RowsBatch};
+
+	#[test]
+	fn test_circuit_steps_for_expr_constant() {
+		type F = BinaryField8b;
+
+		let expr = ArithExpr::Const(F::new(5));
+		let (steps, retval) = convert_circuit_steps(&expr.into());
+
+		assert!(steps.is_empty(), "No steps should be generated for a constant");
+		assert_eq!(retval, CircuitStepArgument::Const(F::new(5)));
+	}
+
+	#[test]
+	fn test_circuit_steps_for_expr_variable() {
+		type F = BinaryField8b;
+
+		let expr = ArithExpr::<F>::Var(18);
+		let (steps, retval) = convert_circuit_steps(&expr.into());
+
+		assert!(steps.is_empty(), "No steps should be generated for a variable");
+		assert!(matches!(retval, CircuitStepArgument::Expr(CircuitNode::Var(18))));
+	}
+
+	#[test]
+	fn test_circuit_steps_for_expr_addition() {
+		type F = BinaryField8b;
+
+		let expr = ArithExpr::<F>::Var(14) + ArithExpr::<F>::Var(56);
+		let (steps, retval) = convert_circuit_steps(&expr.into());
+
+		assert_eq!(steps.len(), 1, "One addition step should be generated");
+		assert!(matches!(
+			steps[0],
+			CircuitStep::Add(
+				CircuitStepArgument::Expr(CircuitNode::Var(14)),
+				CircuitStepArgument::Expr(CircuitNode::Var(56))
+			)
+		));
+		assert!(matches!(retval, CircuitStepArgument::Expr(CircuitNode::Slot(0))));
+	}
+
+	#[test]
+	fn test_circuit_steps_for_expr_multiplication() {
+		type F = BinaryField8b;
+
+		let expr = ArithExpr::<F>::Var(36) * ArithExpr::Var(26);
+		let (steps, retval) = convert_circuit_steps(&expr.into());
+
+		assert_eq!(steps.len(), 1, "One multiplication step should be generated");
+		assert!(matches!(
+			steps[0],
+			CircuitStep::Mul(
+				CircuitStepArgument::Expr(CircuitNode::Var(36)),
+				CircuitStepArgument::Expr(CircuitNode::Var(26))
+			)
+		));
+		assert!(matches!(retval, CircuitStepArgument::Expr(CircuitNode::Slot(0))));
+	}
+
+	#[test]
+	fn test_circuit_steps_for_expr_pow_1() {
+		type F = BinaryField8b;
+
+		let expr = ArithExpr::<F>::Var(12).pow(1);
+		let (steps, retval) = convert_circuit_steps(&expr.into());
+
+		// No steps should be generated for x^1
+		assert_eq!(steps.len(), 0, "Pow(1) should not generate any computation steps");
+
+		// The return value should just be the variable itself
+		assert!(matches!(retval, CircuitStepArgument::Expr(CircuitNode::Var(12))));
+	}
+
+	#[test]
+	fn test_circuit_steps_for_expr_pow_2() {
+		type F = BinaryField8b;
+
+		let expr = ArithExpr::<F>::Var(10).pow(2);
+		let (steps, retval) = convert_circuit_steps(&expr.into());
+
+		assert_eq!(steps.len(), 1, "Pow(2) should generate one squaring step");
+		assert!(matches!(
+			steps[0],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Var(10)))
+		));
+		assert!(matches!(retval, CircuitStepArgument::Expr(CircuitNode::Slot(0))));
+	}
+
+	#[test]
+	fn test_circuit_steps_for_expr_pow_3() {
+		type F = BinaryField8b;
+
+		let expr = ArithExpr::<F>::Var(5).pow(3);
+		let (steps, retval) = convert_circuit_steps(&expr.into());
+
+		assert_eq!(
+			steps.len(),
+			2,
+			"Pow(3) should generate one squaring and one multiplication step"
+		);
+		assert!(matches!(
+			steps[0],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Var(5)))
+		));
+		assert!(matches!(
+			steps[1],
+			CircuitStep::Mul(
+				CircuitStepArgument::Expr(CircuitNode::Slot(0)),
+				CircuitStepArgument::Expr(CircuitNode::Var(5))
+			)
+		));
+		assert!(matches!(retval, CircuitStepArgument::Expr(CircuitNode::Slot(1))));
+	}
+
+	#[test]
+	fn test_circuit_steps_for_expr_pow_4() {
+		type F = BinaryField8b;
+
+		let expr = ArithExpr::<F>::Var(7).pow(4);
+		let (steps, retval) = convert_circuit_steps(&expr.into());
+
+		assert_eq!(steps.len(), 2, "Pow(4) should generate two squaring steps");
+		assert!(matches!(
+			steps[0],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Var(7)))
+		));
+
+		assert!(matches!(
+			steps[1],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Slot(0)))
+		));
+
+		assert!(matches!(retval, CircuitStepArgument::Expr(CircuitNode::Slot(1))));
+	}
+
+	#[test]
+	fn test_circuit_steps_for_expr_pow_5() {
+		type F = BinaryField8b;
+
+		let expr = ArithExpr::<F>::Var(3).pow(5);
+		let (steps, retval) = convert_circuit_steps(&expr.into());
+
+		assert_eq!(
+			steps.len(),
+			3,
+			"Pow(5) should generate two squaring steps and one multiplication"
+		);
+		assert!(matches!(
+			steps[0],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Var(3)))
+		));
+		assert!(matches!(
+			steps[1],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Slot(0)))
+		));
+		assert!(matches!(
+			steps[2],
+			CircuitStep::Mul(
+				CircuitStepArgument::Expr(CircuitNode::Slot(1)),
+				CircuitStepArgument::Expr(CircuitNode::Var(3))
+			)
+		));
+
+		assert!(matches!(retval, CircuitStepArgument::Expr(CircuitNode::Slot(2))));
+	}
+
+	#[test]
+	fn test_circuit_steps_for_expr_pow_8() {
+		type F = BinaryField8b;
+
+		let expr = ArithExpr::<F>::Var(4).pow(8);
+		let (steps, retval) = convert_circuit_steps(&expr.into());
+
+		assert_eq!(steps.len(), 3, "Pow(8) should generate three squaring steps");
+		assert!(matches!(
+			steps[0],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Var(4)))
+		));
+		assert!(matches!(
+			steps[1],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Slot(0)))
+		));
+		assert!(matches!(
+			steps[2],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Slot(1)))
+		));
+
+		assert!(matches!(retval, CircuitStepArgument::Expr(CircuitNode::Slot(2))));
+	}
+
+	#[test]
+	fn test_circuit_steps_for_expr_pow_9() {
+		type F = BinaryField8b;
+
+		let expr = ArithExpr::<F>::Var(8).pow(9);
+		let (steps, retval) = convert_circuit_steps(&expr.into());
+
+		assert_eq!(
+			steps.len(),
+			4,
+			"Pow(9) should generate three squaring steps and one multiplication"
+		);
+		assert!(matches!(
+			steps[0],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Var(8)))
+		));
+		assert!(matches!(
+			steps[1],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Slot(0)))
+		));
+		assert!(matches!(
+			steps[2],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Slot(1)))
+		));
+		assert!(matches!(
+			steps[3],
+			CircuitStep::Mul(
+				CircuitStepArgument::Expr(CircuitNode::Slot(2)),
+				CircuitStepArgument::Expr(CircuitNode::Var(8))
+			)
+		));
+
+		assert!(matches!(retval, CircuitStepArgument::Expr(CircuitNode::Slot(3))));
+	}
+
+	#[test]
+	fn test_circuit_steps_for_expr_pow_12() {
+		type F = BinaryField8b;
+		let expr = ArithExpr::<F>::Var(6).pow(12);
+		let (steps, retval) = convert_circuit_steps(&expr.into());
+
+		assert_eq!(steps.len(), 4, "Pow(12) should use 4 steps.");
+
+		assert!(matches!(
+			steps[0],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Var(6)))
+		));
+		assert!(matches!(
+			steps[1],
+			CircuitStep::Mul(
+				CircuitStepArgument::Expr(CircuitNode::Slot(0)),
+				CircuitStepArgument::Expr(CircuitNode::Var(6))
+			)
+		));
+		assert!(matches!(
+			steps[2],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Slot(1)))
+		));
+		assert!(matches!(
+			steps[3],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Slot(2)))
+		));
+
+		assert!(matches!(retval, CircuitStepArgument::Expr(CircuitNode::Slot(3))));
+	}
+
+	#[test]
+	fn test_circuit_steps_for_expr_pow_13() {
+		type F = BinaryField8b;
+		let expr = ArithExpr::<F>::Var(7).pow(13);
+		let (steps, retval) = convert_circuit_steps(&expr.into());
+
+		assert_eq!(steps.len(), 5, "Pow(13) should use 5 steps.");
+		assert!(matches!(
+			steps[0],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Var(7)))
+		));
+		assert!(matches!(
+			steps[1],
+			CircuitStep::Mul(
+				CircuitStepArgument::Expr(CircuitNode::Slot(0)),
+				CircuitStepArgument::Expr(CircuitNode::Var(7))
+			)
+		));
+		assert!(matches!(
+			steps[2],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Slot(1)))
+		));
+		assert!(matches!(
+			steps[3],
+			CircuitStep::Square(CircuitStepArgument::Expr(CircuitNode::Slot(2)))
+		));
+		assert!(matches!(
+			steps[4],
+			CircuitStep::Mul(
+				CircuitStepArgument::Expr(CircuitNode::Slot(3)),
+				CircuitStepArgument::Expr(CircuitNode::Var(7))
+			)
+		));
+		assert!(matches!(retval, CircuitStepArgument::Expr(CircuitNode::Slot(4))));
+	}
+
+	#[test]
+	fn test_circuit_steps_for_expr_complex() {
+		type F = BinaryField8b;
+
+		let expr = (ArithExpr::<F>::Var(0) * ArithExpr::Var(1))
+			+ (ArithExpr::Const(F::ONE) - ArithExpr::Var(0)) * ArithExpr::Var(2)
+			- ArithExpr::Var(3);
+
+		let (steps, retval) = convert_circuit_steps(&expr.into());
+
+		assert_eq!(steps.len(), 4, "Expression should generate 4 computation steps");
+
+		assert!(
+			matches!(
+				steps[0],
+				CircuitStep::Mul(
+					CircuitStepArgument::Expr(CircuitNode::Var(0)),
+					CircuitStepArgument::Expr(CircuitNode::Var(1))
+				)
+			),
+			"First step should be multiplication x0 * x1"
+		);
+
+		assert!(
+			matches!(
+				steps[1],
+				CircuitStep::Add(
+					CircuitStepArgument::Const(F::ONE),
+					CircuitStepArgument::Expr(CircuitNode::Var(0))
+				)
+			),
+			"Second step should be (1 - x0)"
+		);
+
+		assert!(
+			matches!(
+				steps[2],
+				CircuitStep::AddMul(
+					0,
+					CircuitStepArgument::Expr(CircuitNode::Slot(1)),
+					CircuitStepArgument::Expr(CircuitNode::Var(2))
+				)
+			),
+			"Third step should be (1 - x0) * x2"
+		);
+
+		assert!(
+			matches!(
+				steps[3],
+				CircuitStep::Add(
+					CircuitStepArgument::Expr(CircuitNode::Slot(0)),
+					CircuitStepArgument::Expr(CircuitNode::Var(3))
+				)
+			),
+			"Fourth step should be x0 * x1 + (1 - x0) * x2 + x3"
+		);
+
+		assert!(
+			matches!(retval, CircuitStepArgument::Expr(CircuitNode::Slot(3))),
+			"Final result should be stored in Slot(3)"
+		);
+	}
+
+	#[test]
+	fn test_registers_are_reused() {
+		type F = BinaryField8b;
+
+		// ((x0 * x1) * x2) * x3 alternates between two registers
+		let expr =
+			ArithExpr::<F>::Var(0) * ArithExpr::Var(1) * ArithExpr::Var(2) * ArithExpr::Var(3);
+		let evaluator = ArithCircuitEvaluator::new(&ArithCircuit::from(&expr));
+
+		assert_eq!(evaluator.n_steps(), 3);
+		assert_eq!(evaluator.n_registers(), 2);
+	}
+
+	#[test]
+	fn test_batch_evaluate_multiple_tiles() {
+		type F = BinaryField8b;
+		type P = PackedBinaryField8x16b;
+
+		let mut rng = StdRng::seed_from_u64(0);
+
+		// x0 * x1 + (1 - x0) * x2 + x3^5 + x1 * x2 * x3
+		let expr = ArithExpr::<F>::Var(0) * ArithExpr::Var(1)
+			+ (ArithExpr::Const(F::ONE) - ArithExpr::Var(0)) * ArithExpr::Var(2)
+			+ ArithExpr::Var(3).pow(5)
+			+ ArithExpr::Var(1) * ArithExpr::Var(2) * ArithExpr::Var(3);
+		let evaluator = ArithCircuitEvaluator::new(&ArithCircuit::from(&expr).optimize());
+
+		let row_len = 3 * BATCH_EVALUATE_TILE_LEN + 5;
+		let rows = (0..4)
+			.map(|_| {
+				repeat_with(|| P::random(&mut rng))
+					.take(row_len)
+					.collect::<Vec<_>>()
+			})
+			.collect::<Vec<_>>();
+		let batch_query = RowsBatch::new_from_iter(rows.iter().map(|row| row.as_slice()), row_len);
+
+		let mut evals = vec![P::zero(); row_len];
+		evaluator
+			.batch_evaluate(&batch_query.get_ref(), &mut evals)
+			.unwrap();
+
+		for (i, eval) in evals.iter().enumerate() {
+			let query = rows.iter().map(|row| row[i]).collect::<Vec<_>>();
+			assert_eq!(*eval, evaluator.evaluate(&query).unwrap());
+		}
+	}
+
+	#[test]
+	fn test_batch_evaluate_empty_batch() {
+		type F = BinaryField8b;
+		type P = PackedBinaryField8x16b;
+
+		let expr = ArithExpr::<F>::Var(0) * ArithExpr::Var(1);
+		let evaluator = ArithCircuitEvaluator::new(&ArithCircuit::from(&expr));
+
+		let rows = [Vec::<P>::new(), Vec::new()];
+		let batch_query = RowsBatch::new_from_iter(rows.iter().map(|row| row.as_slice()), 0);
+		evaluator
+			.batch_evaluate(&batch_query.get_ref(), &mut [])
+			.unwrap();
+	}
+
+	#[test]
+	fn test_evaluate_matches_circuit() {
+		type F = BinaryField8b;
+
+		let mut rng = StdRng::seed_from_u64(0);
+
+		let expr = (ArithExpr::<F>::Var(0) + ArithExpr::Var(2)).pow(3) * ArithExpr::Var(1)
+			+ ArithExpr::Const(F::new(7));
+		let circuit = ArithCircuit::from(&expr);
+		let evaluator = ArithCircuitEvaluator::new(&circuit);
+		assert_eq!(evaluator.n_vars(), 3);
+
+		for _ in 0..16 {
+			// Values beyond the number of variables are ignored.
+			let query = repeat_with(|| <F as Field>::random(&mut rng))
+				.take(4)
+				.collect::<Vec<_>>();
+			assert_eq!(evaluator.evaluate(&query).unwrap(), circuit.evaluate(&query[..3]).unwrap());
+		}
+
+		assert_matches!(
+			evaluator.evaluate(&[F::ONE; 2]),
+			Err(Error::IncorrectQuerySize {
+				expected: 3,
+				actual: 2
+			})
+		);
+	}
+
+	#[test]
+	fn check_deduplication_in_steps() {
+		type F = BinaryField8b;
+
+		let expr = (ArithExpr::<F>::Var(0) * ArithExpr::Var(1))
+			+ (ArithExpr::<F>::Var(0) * ArithExpr::Var(1)) * ArithExpr::Var(2)
+			- ArithExpr::Var(3);
+		let expr = ArithCircuit::from(&expr);
+		let expr = expr.optimize();
+
+		let (steps, retval) = convert_circuit_steps(&expr);
+
+		assert_eq!(steps.len(), 3, "Expression should generate 3 computation steps");
+
+		assert!(
+			matches!(
+				steps[0],
+				CircuitStep::Mul(
+					CircuitStepArgument::Expr(CircuitNode::Var(0)),
+					CircuitStepArgument::Expr(CircuitNode::Var(1))
+				)
+			),
+			"First step should be multiplication x0 * x1"
+		);
+
+		assert!(
+			matches!(
+				steps[1],
+				CircuitStep::AddMul(
+					0,
+					CircuitStepArgument::Expr(CircuitNode::Slot(0)),
+					CircuitStepArgument::Expr(CircuitNode::Var(2))
+				)
+			),
+			"Second step should be (x0 * x1) * x2"
+		);
+
+		assert!(
+			matches!(
+				steps[2],
+				CircuitStep::Add(
+					CircuitStepArgument::Expr(CircuitNode::Slot(0)),
+					CircuitStepArgument::Expr(CircuitNode::Var(3))
+				)
+			),
+			"Third step should be x0 * x1 + (x0 * x1) * x2 + x3"
+		);
+
+		assert!(
+			matches!(retval, CircuitStepArgument::Expr(CircuitNode::Slot(2))),
+			"Final result should be stored in Slot(2)"
+		);
+	}
+}
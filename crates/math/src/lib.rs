@@ -18,6 +18,7 @@ mod binary_subspace;
 mod composition_poly;
 mod error;
 mod evaluation_order;
+mod expr_eval;
 mod fold;
 mod matrix;
 mod mle_adapters;
@@ -36,6 +37,7 @@ pub use binary_subspace::*;
 pub use composition_poly::*;
 pub use error::*;
 pub use evaluation_order::*;
+pub use expr_eval::*;
 pub use fold::*;
 pub use matrix::*;
 pub use mle_adapters::*;
@@ -30,7 +30,7 @@ cfg_if::cfg_if! {
 			}
 
 			#[inline(always)]
-			pub const fn build(self) -> Result<ThreadPool, ::core::convert::Infallible> {
+			pub const fn build(self) -> Result<ThreadPool, ThreadPoolBuildError> {
 				Ok(ThreadPool())
 			}
 
@@ -38,6 +38,29 @@ cfg_if::cfg_if! {
 			pub const fn num_threads(self, _num_threads: usize) -> Self {
 				Self()
 			}
+
+			#[inline(always)]
+			pub const fn stack_size(self, _stack_size: usize) -> Self {
+				Self()
+			}
+
+			#[inline(always)]
+			pub const fn use_current_thread(self) -> Self {
+				Self()
+			}
+
+			#[inline(always)]
+			pub const fn build_global(self) -> Result<(), ThreadPoolBuildError> {
+				Ok(())
+			}
+
+			#[inline(always)]
+			pub fn start_handler<H>(self, _start_handler: H) -> Self
+			where
+				H: Fn(usize) + Send + Sync + 'static,
+			{
+				Self()
+			}
 		}
 
 		#[derive(Debug)]
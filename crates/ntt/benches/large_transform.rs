@@ -44,6 +44,14 @@ fn bench_large_transform<F: TowerField, PE: PackedExtension<F>>(c: &mut Criterio
 			group.bench_function(BenchmarkId::new("multithread/precompute", &params), |b| {
 				b.iter(|| ntt.forward_transform_ext(&mut data, shape, 0, 0, 0));
 			});
+
+			let ntt = SingleThreadedNTT::<F>::new(log_dim)
+				.unwrap()
+				.precompute_twiddles()
+				.four_step();
+			group.bench_function(BenchmarkId::new("four-step/precompute", &params), |b| {
+				b.iter(|| ntt.forward_transform_ext(&mut data, shape, 0, 0, 0));
+			});
 		}
 	}
 }
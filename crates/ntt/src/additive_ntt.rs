@@ -1,9 +1,13 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use binius_field::{BinaryField, ExtensionField, PackedExtension, PackedField};
+use binius_field::{
+	BinaryField, ExtensionField, PackedExtension, PackedField,
+	packed::{get_packed_slice, set_packed_slice},
+};
 use binius_math::BinarySubspace;
+use binius_utils::bail;
 
-use super::error::Error;
+use super::{error::Error, single_threaded::check_batch_transform_inputs_and_params};
 
 /// Shape of a batched NTT operation on a 3-dimensional tensor.
 ///
@@ -134,6 +138,164 @@ pub trait AdditiveNTT<F: BinaryField> {
 		skip_rounds: usize,
 	) -> Result<(), Error>;
 
+	/// Batched forward transformation that only computes a prefix of each output column.
+	///
+	/// Computes the first `1 << log_output_y` rows along the Y axis of
+	/// [`Self::forward_transform`] applied to `data`, without modifying `data`, and writes them
+	/// to `output`. The output adheres to `shape` with `log_y` replaced by `log_output_y`. The
+	/// remaining parameters have the same meaning as in [`Self::forward_transform`].
+	///
+	/// The evaluations of the output prefix only depend on the reduction of the input polynomial
+	/// modulo the vanishing polynomial of the smaller subspace, so the transform folds the input
+	/// into the output once and then runs a transform of size `1 << log_output_y`.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::OutputTooLarge`] if `log_output_y` is greater than `shape.log_y`
+	/// * [`Error::IncorrectBufferLength`] if `output` does not have the length of the output shape
+	#[allow(clippy::too_many_arguments)]
+	fn forward_transform_truncated<P: PackedField<Scalar = F>>(
+		&self,
+		data: &[P],
+		output: &mut [P],
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+		log_output_y: usize,
+	) -> Result<(), Error> {
+		let NTTShape {
+			log_x,
+			log_y,
+			log_z,
+		} = shape;
+
+		check_batch_transform_inputs_and_params(
+			self.log_domain_size(),
+			data,
+			shape,
+			coset,
+			coset_bits,
+			skip_rounds,
+		)?;
+		if log_output_y > log_y {
+			bail!(Error::OutputTooLarge {
+				log_output_y,
+				log_y
+			});
+		}
+		let log_block = log_x + log_output_y;
+		let expected_output_len = 1 << (log_block + log_z).saturating_sub(P::LOG_WIDTH);
+		if output.len() != expected_output_len {
+			bail!(Error::IncorrectBufferLength {
+				expected: expected_output_len,
+				actual: output.len(),
+			});
+		}
+
+		// The truncated layers only need the butterfly outputs in the lower half of their blocks,
+		// and all of these butterflies use the twiddle of the first block. Skipped layers drop the
+		// upper half without adding it.
+		let n_folds = log_y - log_output_y;
+		let fold_coeffs =
+			(log_output_y..log_y - skip_rounds.min(n_folds)).fold(vec![F::ONE], |mut coeffs, i| {
+				let twiddle = self
+					.subspace(log_y + coset_bits - i)
+					.get((coset << (log_y - 1 - i)) << 1);
+				let products = coeffs
+					.iter()
+					.map(|&coeff| coeff * twiddle)
+					.collect::<Vec<_>>();
+				coeffs.extend(products);
+				coeffs
+			});
+
+		for z in 0..1 << log_z {
+			let dst = z << log_block;
+			for (fold, &coeff) in fold_coeffs.iter().enumerate() {
+				let src = z << (log_x + log_y) | fold << log_block;
+				if log_block >= P::LOG_WIDTH {
+					let output =
+						&mut output[dst >> P::LOG_WIDTH..][..1 << (log_block - P::LOG_WIDTH)];
+					let input = &data[src >> P::LOG_WIDTH..][..output.len()];
+					if fold == 0 {
+						output.copy_from_slice(input);
+					} else {
+						let coeff = P::broadcast(coeff);
+						for (out, &x) in output.iter_mut().zip(input) {
+							*out += x * coeff;
+						}
+					}
+				} else {
+					for k in 0..1 << log_block {
+						let x = get_packed_slice(data, src | k);
+						let out = if fold == 0 {
+							x
+						} else {
+							get_packed_slice(output, dst | k) + x * coeff
+						};
+						set_packed_slice(output, dst | k, out);
+					}
+				}
+			}
+		}
+
+		self.forward_transform(
+			output,
+			NTTShape {
+				log_y: log_output_y,
+				..shape
+			},
+			coset << n_folds,
+			coset_bits + n_folds,
+			skip_rounds.saturating_sub(n_folds),
+		)
+	}
+
+	/// Batched forward transformation of rows that are laid out with a stride.
+	///
+	/// The tensor consists of `1 << shape.log_z` rows, each holding a tensor with the X and Y
+	/// dimensions of `shape`. Row `i` starts at packed element `i * row_stride` of `data`. The
+	/// elements between the rows are not accessed. The remaining parameters have the same meaning
+	/// as in [`Self::forward_transform`].
+	///
+	/// ## Throws
+	///
+	/// * [`Error::RowSmallerThanPackedWidth`] if a row is smaller than a packed element
+	/// * [`Error::RowStrideTooSmall`] if `row_stride` is less than the row length
+	/// * [`Error::StridedBufferTooSmall`] if `data` is too short to hold all rows
+	fn forward_transform_rows<P: PackedField<Scalar = F>>(
+		&self,
+		data: &mut [P],
+		row_stride: usize,
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+	) -> Result<(), Error> {
+		for_each_strided_row(data, row_stride, shape, |rows, shape| {
+			self.forward_transform(rows, shape, coset, coset_bits, skip_rounds)
+		})
+	}
+
+	/// Batched inverse transformation of rows that are laid out with a stride.
+	///
+	/// See [`Self::forward_transform_rows`] for the data layout and
+	/// [`Self::inverse_transform`] for the remaining parameters.
+	fn inverse_transform_rows<P: PackedField<Scalar = F>>(
+		&self,
+		data: &mut [P],
+		row_stride: usize,
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+	) -> Result<(), Error> {
+		for_each_strided_row(data, row_stride, shape, |rows, shape| {
+			self.inverse_transform(rows, shape, coset, coset_bits, skip_rounds)
+		})
+	}
+
 	fn forward_transform_ext<PE: PackedExtension<F>>(
 		&self,
 		data: &mut [PE],
@@ -164,3 +326,48 @@ pub trait AdditiveNTT<F: BinaryField> {
 		self.inverse_transform(PE::cast_bases_mut(data), shape_ext, coset, coset_bits, skip_rounds)
 	}
 }
+
+/// Calls `f` on the strided rows of `data`, with the shape of the rows that `f` receives.
+///
+/// Contiguous rows are passed to `f` in a single call.
+fn for_each_strided_row<P: PackedField>(
+	data: &mut [P],
+	row_stride: usize,
+	shape: NTTShape,
+	mut f: impl FnMut(&mut [P], NTTShape) -> Result<(), Error>,
+) -> Result<(), Error> {
+	let NTTShape {
+		log_x,
+		log_y,
+		log_z,
+	} = shape;
+
+	let Some(log_row_len) = (log_x + log_y).checked_sub(P::LOG_WIDTH) else {
+		bail!(Error::RowSmallerThanPackedWidth);
+	};
+	let row_len = 1 << log_row_len;
+	if row_stride < row_len {
+		bail!(Error::RowStrideTooSmall {
+			stride: row_stride,
+			row_len,
+		});
+	}
+	let n_rows = 1 << log_z;
+	if data.len() < (n_rows - 1) * row_stride + row_len {
+		bail!(Error::StridedBufferTooSmall {
+			len: data.len(),
+			n_rows,
+			stride: row_stride,
+		});
+	}
+
+	if row_stride == row_len {
+		return f(&mut data[..n_rows * row_len], shape);
+	}
+
+	let row_shape = NTTShape { log_z: 0, ..shape };
+	for row in data.chunks_mut(row_stride).take(n_rows) {
+		f(&mut row[..row_len], row_shape)?;
+	}
+	Ok(())
+}
@@ -7,6 +7,7 @@ use binius_utils::rayon::get_log_max_threads;
 use super::{
 	additive_ntt::{AdditiveNTT, NTTShape},
 	error::Error,
+	four_step::{DEFAULT_LOG_TILE_BYTES, FourStepNTT},
 	multithreaded::MultithreadedNTT,
 	single_threaded::SingleThreadedNTT,
 	twiddle::PrecomputedTwiddleAccess,
@@ -48,6 +49,8 @@ impl ThreadingSettings {
 pub struct NTTOptions {
 	pub precompute_twiddles: bool,
 	pub thread_settings: ThreadingSettings,
+	/// Use the cache-blocked four-step decomposition, see [`FourStepNTT`].
+	pub four_step: bool,
 }
 
 /// An enum that can be used to switch between different NTT implementations without passing
@@ -58,6 +61,8 @@ pub enum DynamicDispatchNTT<F: BinaryField> {
 	SingleThreadedPrecompute(SingleThreadedNTT<F, PrecomputedTwiddleAccess<F>>),
 	MultiThreaded(MultithreadedNTT<F>),
 	MultiThreadedPrecompute(MultithreadedNTT<F, PrecomputedTwiddleAccess<F>>),
+	FourStep(FourStepNTT<F>),
+	FourStepPrecompute(FourStepNTT<F, PrecomputedTwiddleAccess<F>>),
 }
 
 impl<F: BinaryField> DynamicDispatchNTT<F> {
@@ -65,6 +70,15 @@ impl<F: BinaryField> DynamicDispatchNTT<F> {
 	pub fn new(log_domain_size: usize, options: &NTTOptions) -> Result<Self, Error> {
 		let log_threads = options.thread_settings.log_threads_count();
 		let result = match (options.precompute_twiddles, log_threads) {
+			(false, _) if options.four_step => Self::FourStep(
+				SingleThreadedNTT::new(log_domain_size)?
+					.four_step_with_params(log_threads, DEFAULT_LOG_TILE_BYTES),
+			),
+			(true, _) if options.four_step => Self::FourStepPrecompute(
+				SingleThreadedNTT::new(log_domain_size)?
+					.precompute_twiddles()
+					.four_step_with_params(log_threads, DEFAULT_LOG_TILE_BYTES),
+			),
 			(false, 0) => Self::SingleThreaded(SingleThreadedNTT::new(log_domain_size)?),
 			(true, 0) => Self::SingleThreadedPrecompute(
 				SingleThreadedNTT::new(log_domain_size)?.precompute_twiddles(),
@@ -91,6 +105,8 @@ impl<F: BinaryField> AdditiveNTT<F> for DynamicDispatchNTT<F> {
 			Self::SingleThreadedPrecompute(ntt) => ntt.log_domain_size(),
 			Self::MultiThreaded(ntt) => ntt.log_domain_size(),
 			Self::MultiThreadedPrecompute(ntt) => ntt.log_domain_size(),
+			Self::FourStep(ntt) => ntt.log_domain_size(),
+			Self::FourStepPrecompute(ntt) => ntt.log_domain_size(),
 		}
 	}
 
@@ -100,6 +116,8 @@ impl<F: BinaryField> AdditiveNTT<F> for DynamicDispatchNTT<F> {
 			Self::SingleThreadedPrecompute(ntt) => ntt.subspace(i),
 			Self::MultiThreaded(ntt) => ntt.subspace(i),
 			Self::MultiThreadedPrecompute(ntt) => ntt.subspace(i),
+			Self::FourStep(ntt) => ntt.subspace(i),
+			Self::FourStepPrecompute(ntt) => ntt.subspace(i),
 		}
 	}
 
@@ -109,6 +127,8 @@ impl<F: BinaryField> AdditiveNTT<F> for DynamicDispatchNTT<F> {
 			Self::SingleThreadedPrecompute(ntt) => ntt.get_subspace_eval(i, j),
 			Self::MultiThreaded(ntt) => ntt.get_subspace_eval(i, j),
 			Self::MultiThreadedPrecompute(ntt) => ntt.get_subspace_eval(i, j),
+			Self::FourStep(ntt) => ntt.get_subspace_eval(i, j),
+			Self::FourStepPrecompute(ntt) => ntt.get_subspace_eval(i, j),
 		}
 	}
 
@@ -133,6 +153,12 @@ impl<F: BinaryField> AdditiveNTT<F> for DynamicDispatchNTT<F> {
 			Self::MultiThreadedPrecompute(ntt) => {
 				ntt.forward_transform(data, shape, coset, coset_bits, skip_rounds)
 			}
+			Self::FourStep(ntt) => {
+				ntt.forward_transform(data, shape, coset, coset_bits, skip_rounds)
+			}
+			Self::FourStepPrecompute(ntt) => {
+				ntt.forward_transform(data, shape, coset, coset_bits, skip_rounds)
+			}
 		}
 	}
 
@@ -157,6 +183,12 @@ impl<F: BinaryField> AdditiveNTT<F> for DynamicDispatchNTT<F> {
 			Self::MultiThreadedPrecompute(ntt) => {
 				ntt.inverse_transform(data, shape, coset, coset_bits, skip_rounds)
 			}
+			Self::FourStep(ntt) => {
+				ntt.inverse_transform(data, shape, coset, coset_bits, skip_rounds)
+			}
+			Self::FourStepPrecompute(ntt) => {
+				ntt.inverse_transform(data, shape, coset, coset_bits, skip_rounds)
+			}
 		}
 	}
 }
@@ -176,12 +208,14 @@ mod tests {
 		let ntt = make_ntt(&NTTOptions {
 			precompute_twiddles: false,
 			thread_settings: ThreadingSettings::SingleThreaded,
+			four_step: false,
 		});
 		assert!(matches!(ntt, DynamicDispatchNTT::SingleThreaded(_)));
 
 		let ntt = make_ntt(&NTTOptions {
 			precompute_twiddles: true,
 			thread_settings: ThreadingSettings::SingleThreaded,
+			four_step: false,
 		});
 		assert!(matches!(ntt, DynamicDispatchNTT::SingleThreadedPrecompute(_)));
 
@@ -189,6 +223,7 @@ mod tests {
 		let ntt = make_ntt(&NTTOptions {
 			precompute_twiddles: false,
 			thread_settings: ThreadingSettings::MultithreadedDefault,
+			four_step: false,
 		});
 		if multithreaded {
 			assert!(matches!(ntt, DynamicDispatchNTT::MultiThreaded(_)));
@@ -199,6 +234,7 @@ mod tests {
 		let ntt = make_ntt(&NTTOptions {
 			precompute_twiddles: true,
 			thread_settings: ThreadingSettings::MultithreadedDefault,
+			four_step: false,
 		});
 		if multithreaded {
 			assert!(matches!(ntt, DynamicDispatchNTT::MultiThreadedPrecompute(_)));
@@ -209,19 +245,36 @@ mod tests {
 		let ntt = make_ntt(&NTTOptions {
 			precompute_twiddles: false,
 			thread_settings: ThreadingSettings::ExplicitThreadsCount { log_threads: 2 },
+			four_step: false,
 		});
 		assert!(matches!(ntt, DynamicDispatchNTT::MultiThreaded(_)));
 
 		let ntt = make_ntt(&NTTOptions {
 			precompute_twiddles: true,
 			thread_settings: ThreadingSettings::ExplicitThreadsCount { log_threads: 0 },
+			four_step: false,
 		});
 		assert!(matches!(ntt, DynamicDispatchNTT::SingleThreadedPrecompute(_)));
 
 		let ntt = make_ntt(&NTTOptions {
 			precompute_twiddles: false,
 			thread_settings: ThreadingSettings::ExplicitThreadsCount { log_threads: 0 },
+			four_step: false,
 		});
 		assert!(matches!(ntt, DynamicDispatchNTT::SingleThreaded(_)));
+
+		let ntt = make_ntt(&NTTOptions {
+			precompute_twiddles: false,
+			thread_settings: ThreadingSettings::SingleThreaded,
+			four_step: true,
+		});
+		assert!(matches!(ntt, DynamicDispatchNTT::FourStep(_)));
+
+		let ntt = make_ntt(&NTTOptions {
+			precompute_twiddles: true,
+			thread_settings: ThreadingSettings::MultithreadedDefault,
+			four_step: true,
+		});
+		assert!(matches!(ntt, DynamicDispatchNTT::FourStepPrecompute(_)));
 	}
 }
@@ -22,6 +22,20 @@ pub enum Error {
 	SkipRoundsTooLarge,
 	#[error("coset index must be less than 2**{coset_bits}, got {coset}")]
 	CosetIndexOutOfBounds { coset: usize, coset_bits: usize },
+	#[error("the output size 2**{log_output_y} exceeds the transform size 2**{log_y}")]
+	OutputTooLarge { log_output_y: usize, log_y: usize },
+	#[error("the buffer must have length {expected}, got {actual}")]
+	IncorrectBufferLength { expected: usize, actual: usize },
+	#[error("rows must span at least one packed element")]
+	RowSmallerThanPackedWidth,
+	#[error("the row stride {stride} is less than the row length {row_len}")]
+	RowStrideTooSmall { stride: usize, row_len: usize },
+	#[error("buffer of length {len} is too small for {n_rows} rows with stride {stride}")]
+	StridedBufferTooSmall {
+		len: usize,
+		n_rows: usize,
+		stride: usize,
+	},
 	#[error("odd interpolation length mismatch, expected to be exactly {expected_len}")]
 	OddInterpolateIncorrectLength { expected_len: usize },
 	#[error("math error: {0}")]
@@ -0,0 +1,221 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Convenience functions for evaluating and interpolating polynomials in the novel polynomial
+//! basis.
+//!
+//! The [`AdditiveNTT`] transforms operate in place on batched tensors. These functions wrap them
+//! for the common cases of converting between coefficients and evaluations, and evaluating a
+//! polynomial in the novel basis at a point outside of the NTT domain.
+
+use std::iter;
+
+use binius_field::{BinaryField, PackedField};
+use binius_math::BinarySubspace;
+use binius_utils::bail;
+
+use super::{
+	additive_ntt::{AdditiveNTT, NTTShape},
+	error::Error,
+};
+
+/// Evaluates polynomials given by their novel basis coefficients over a coset of the NTT domain.
+///
+/// This is the out-of-place version of [`AdditiveNTT::forward_transform`], with no skipped
+/// rounds.
+pub fn evaluate<F, P>(
+	ntt: &impl AdditiveNTT<F>,
+	coeffs: &[P],
+	shape: NTTShape,
+	coset: usize,
+	coset_bits: usize,
+) -> Result<Vec<P>, Error>
+where
+	F: BinaryField,
+	P: PackedField<Scalar = F>,
+{
+	let mut evals = coeffs.to_vec();
+	ntt.forward_transform(&mut evals, shape, coset, coset_bits, 0)?;
+	Ok(evals)
+}
+
+/// Interpolates the novel basis coefficients of polynomials from their evaluations over a coset
+/// of the NTT domain.
+///
+/// This is the out-of-place version of [`AdditiveNTT::inverse_transform`], with no skipped
+/// rounds.
+pub fn interpolate<F, P>(
+	ntt: &impl AdditiveNTT<F>,
+	evals: &[P],
+	shape: NTTShape,
+	coset: usize,
+	coset_bits: usize,
+) -> Result<Vec<P>, Error>
+where
+	F: BinaryField,
+	P: PackedField<Scalar = F>,
+{
+	let mut coeffs = evals.to_vec();
+	ntt.inverse_transform(&mut coeffs, shape, coset, coset_bits, 0)?;
+	Ok(coeffs)
+}
+
+/// Evaluates the first `1 << log_n` novel basis polynomials of a subspace at a point.
+///
+/// With the basis $\gamma_0, \ldots, \gamma_{\ell-1}$ of the subspace, the $j$'th novel basis
+/// polynomial is $X_j(X) = \prod_{i} \hat{W}_i(X)^{j_i}$, where $j_i$ are the bits of $j$ and
+/// $\hat{W}_i$ is the normalized subspace polynomial that vanishes on the span of
+/// $\gamma_0, \ldots, \gamma_{i-1}$ and is $1$ on $\gamma_i$. See [DP24] Section 2.3.
+///
+/// ## Throws
+///
+/// * [`Error::DomainTooSmall`] if `log_n` is greater than the subspace dimension
+/// * [`Error::DomainMustIncludeOne`] if the first basis element is not $1$
+///
+/// [DP24]: <https://eprint.iacr.org/2024/504>
+pub fn novel_basis_evals<F: BinaryField>(
+	subspace: &BinarySubspace<F>,
+	log_n: usize,
+	point: F,
+) -> Result<Vec<F>, Error> {
+	if log_n > subspace.dim() {
+		bail!(Error::DomainTooSmall {
+			log_required_domain_size: log_n,
+		});
+	}
+	if log_n == 0 {
+		return Ok(vec![F::ONE]);
+	}
+	if subspace.basis()[0] != F::ONE {
+		bail!(Error::DomainMustIncludeOne);
+	}
+
+	// `w_evals` holds the evaluations of $\hat{W}_i$ at the point followed by the basis elements
+	// $\gamma_{i+1}, \ldots, \gamma_{\ell-1}$. Since $\gamma_0 = 1$, $\hat{W}_0$ is the identity.
+	let mut w_evals = Vec::with_capacity(log_n);
+	w_evals.push(point);
+	w_evals.extend_from_slice(&subspace.basis()[1..log_n]);
+
+	let mut evals = Vec::with_capacity(1 << log_n);
+	evals.push(F::ONE);
+	for i in 0..log_n {
+		let w_i = w_evals[0];
+		for j in 0..evals.len() {
+			evals.push(evals[j] * w_i);
+		}
+
+		if i + 1 < log_n {
+			// $\hat{W}_{i+1}(X) = \hat{W}_i(X)(\hat{W}_i(X) + 1)$ normalized by its value at
+			// $\gamma_{i+1}$.
+			let norm = w_evals[1].square() + w_evals[1];
+			let norm_inv = norm
+				.invert()
+				.expect("basis elements are linearly independent");
+			w_evals = iter::once(w_evals[0])
+				.chain(w_evals[2..].iter().copied())
+				.map(|w| (w.square() + w) * norm_inv)
+				.collect();
+		}
+	}
+
+	Ok(evals)
+}
+
+/// Evaluates a polynomial given by its novel basis coefficients at an arbitrary point.
+///
+/// The novel basis is the one of the NTT domain with dimension
+/// `log2(coeffs.len()) + coset_bits`, matching [`AdditiveNTT::forward_transform`] with the same
+/// `coset_bits`. At the points of the NTT domain, this agrees with the forward transform.
+///
+/// ## Throws
+///
+/// * [`Error::PowerOfTwoLengthRequired`] if the number of coefficients is not a power of two
+/// * [`Error::DomainTooSmall`] if the NTT domain is too small for the polynomial and coset bits
+pub fn evaluate_novel_polynomial<F: BinaryField>(
+	ntt: &impl AdditiveNTT<F>,
+	coeffs: &[F],
+	coset_bits: usize,
+	point: F,
+) -> Result<F, Error> {
+	if !coeffs.len().is_power_of_two() {
+		bail!(Error::PowerOfTwoLengthRequired);
+	}
+	let log_n = coeffs.len().ilog2() as usize;
+	let log_required_domain_size = log_n + coset_bits;
+	if log_required_domain_size > ntt.log_domain_size() {
+		bail!(Error::DomainTooSmall {
+			log_required_domain_size
+		});
+	}
+
+	let subspace = ntt.subspace(log_required_domain_size);
+	let basis_evals = novel_basis_evals(&subspace, log_n, point)?;
+	Ok(coeffs
+		.iter()
+		.zip(basis_evals)
+		.map(|(&coeff, basis_eval)| coeff * basis_eval)
+		.sum())
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_field::{BinaryField16b, Field, PackedBinaryField8x16b};
+	use rand::{SeedableRng, rngs::StdRng};
+
+	use super::*;
+	use crate::SingleThreadedNTT;
+
+	#[test]
+	fn test_evaluate_interpolate_roundtrip() {
+		let ntt = SingleThreadedNTT::<BinaryField16b>::new(10).unwrap();
+		let mut rng = StdRng::seed_from_u64(0);
+		let coeffs = (0..1 << 5)
+			.map(|_| PackedBinaryField8x16b::random(&mut rng))
+			.collect::<Vec<_>>();
+		let shape = NTTShape {
+			log_x: 1,
+			log_y: 7,
+			..Default::default()
+		};
+
+		let evals = evaluate(&ntt, &coeffs, shape, 3, 2).unwrap();
+		assert_ne!(evals, coeffs);
+		assert_eq!(interpolate(&ntt, &evals, shape, 3, 2).unwrap(), coeffs);
+	}
+
+	#[test]
+	fn test_evaluate_novel_polynomial_on_domain() {
+		let ntt = SingleThreadedNTT::<BinaryField16b>::new(10).unwrap();
+		let mut rng = StdRng::seed_from_u64(0);
+		let log_n = 6;
+		let coeffs = (0..1 << log_n)
+			.map(|_| <BinaryField16b as Field>::random(&mut rng))
+			.collect::<Vec<_>>();
+
+		for coset_bits in [0, 2] {
+			let subspace = ntt.subspace(log_n + coset_bits);
+			for coset in [0, (1 << coset_bits) - 1] {
+				let shape = NTTShape {
+					log_y: log_n,
+					..Default::default()
+				};
+				let evals = evaluate(&ntt, &coeffs, shape, coset, coset_bits).unwrap();
+				for (i, &eval) in evals.iter().enumerate() {
+					let point = subspace.get(coset << log_n | i);
+					assert_eq!(
+						evaluate_novel_polynomial(&ntt, &coeffs, coset_bits, point).unwrap(),
+						eval
+					);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_novel_basis_evals_domain_too_small() {
+		let subspace = BinarySubspace::<BinaryField16b>::with_dim(3).unwrap();
+		assert!(matches!(
+			novel_basis_evals(&subspace, 4, BinaryField16b::ONE),
+			Err(Error::DomainTooSmall { .. })
+		));
+	}
+}
@@ -0,0 +1,291 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::{BinaryField, PackedField};
+use binius_math::BinarySubspace;
+use binius_maybe_rayon::prelude::*;
+use binius_utils::{rayon::get_log_max_threads, strided_array::StridedArray2DViewMut};
+
+use super::{
+	additive_ntt::{AdditiveNTT, NTTShape},
+	error::Error,
+	single_threaded::{SingleThreadedNTT, check_batch_transform_inputs_and_params},
+	twiddle::TwiddleAccess,
+};
+use crate::twiddle::OnTheFlyTwiddleAccess;
+
+/// Default base-2 logarithm of the tile size in bytes, chosen to fit in a typical L2 cache.
+pub const DEFAULT_LOG_TILE_BYTES: usize = 18;
+
+/// Implementation of `AdditiveNTT` that performs the computation multithreaded with a
+/// cache-blocked four-step decomposition.
+///
+/// The data is viewed as a matrix whose rows fit in a tile of `1 << log_tile_bytes` bytes. The
+/// first NTT layers run as column-wise NTTs and the remaining layers as row-wise NTTs on
+/// independent cosets. Blocks of columns are gathered into contiguous, tile-sized buffers before
+/// they are transformed, so that every layer of the transform runs on cache-resident data. When
+/// the columns are too tall to fit in a tile, the column buffers are transformed recursively
+/// with the same decomposition.
+///
+/// Unlike [`MultithreadedNTT`](crate::MultithreadedNTT), which splits the data into as many rows
+/// as there are threads, the number of rows here is determined by the tile size. This makes a
+/// difference for transforms that are much larger than the cache.
+#[derive(Debug)]
+pub struct FourStepNTT<F: BinaryField, TA: TwiddleAccess<F> = OnTheFlyTwiddleAccess<F, Vec<F>>> {
+	single_threaded: SingleThreadedNTT<F, TA>,
+	log_max_threads: usize,
+	log_tile_bytes: usize,
+}
+
+impl<F: BinaryField, TA: TwiddleAccess<F> + Sync> SingleThreadedNTT<F, TA> {
+	/// Returns a cache-blocked four-step NTT implementation which uses the default number of
+	/// threads and tile size.
+	pub fn four_step(self) -> FourStepNTT<F, TA> {
+		let log_max_threads = get_log_max_threads();
+		self.four_step_with_params(log_max_threads as _, DEFAULT_LOG_TILE_BYTES)
+	}
+
+	/// Returns a cache-blocked four-step NTT implementation which uses `1 << log_max_threads`
+	/// threads and tiles of `1 << log_tile_bytes` bytes.
+	pub const fn four_step_with_params(
+		self,
+		log_max_threads: usize,
+		log_tile_bytes: usize,
+	) -> FourStepNTT<F, TA> {
+		FourStepNTT {
+			single_threaded: self,
+			log_max_threads,
+			log_tile_bytes,
+		}
+	}
+}
+
+impl<F, TA> AdditiveNTT<F> for FourStepNTT<F, TA>
+where
+	F: BinaryField,
+	TA: TwiddleAccess<F> + Sync,
+{
+	fn log_domain_size(&self) -> usize {
+		self.single_threaded.log_domain_size()
+	}
+
+	fn subspace(&self, i: usize) -> BinarySubspace<F> {
+		self.single_threaded.subspace(i)
+	}
+
+	fn get_subspace_eval(&self, i: usize, j: usize) -> F {
+		self.single_threaded.get_subspace_eval(i, j)
+	}
+
+	fn forward_transform<P: PackedField<Scalar = F>>(
+		&self,
+		data: &mut [P],
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+	) -> Result<(), Error> {
+		check_batch_transform_inputs_and_params(
+			self.log_domain_size(),
+			data,
+			shape,
+			coset,
+			coset_bits,
+			skip_rounds,
+		)?;
+
+		self.transform(data, shape, coset, coset_bits, skip_rounds, self.log_max_threads, true)
+	}
+
+	fn inverse_transform<P: PackedField<Scalar = F>>(
+		&self,
+		data: &mut [P],
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+	) -> Result<(), Error> {
+		check_batch_transform_inputs_and_params(
+			self.log_domain_size(),
+			data,
+			shape,
+			coset,
+			coset_bits,
+			skip_rounds,
+		)?;
+
+		self.transform(data, shape, coset, coset_bits, skip_rounds, self.log_max_threads, false)
+	}
+}
+
+impl<F, TA> FourStepNTT<F, TA>
+where
+	F: BinaryField,
+	TA: TwiddleAccess<F> + Sync,
+{
+	/// Base-2 logarithm of the number of packed elements that fit in a tile.
+	fn log_tile_len<P>(&self) -> usize {
+		self.log_tile_bytes
+			.saturating_sub(size_of::<P>().ilog2() as usize)
+			.max(1)
+	}
+
+	/// Transforms a tensor that is known to satisfy the input checks, either forward or inverse.
+	#[allow(clippy::too_many_arguments)]
+	fn transform<P: PackedField<Scalar = F>>(
+		&self,
+		data: &mut [P],
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+		log_max_threads: usize,
+		forward: bool,
+	) -> Result<(), Error> {
+		let log_tile_len = self.log_tile_len::<P>();
+		if data.len() <= 1 << log_tile_len && log_max_threads == 0 {
+			return self.transform_single_threaded(
+				data,
+				shape,
+				coset,
+				coset_bits,
+				skip_rounds,
+				forward,
+			);
+		}
+
+		let NTTShape {
+			log_x,
+			log_y,
+			log_z,
+		} = shape;
+
+		let log_w = P::LOG_WIDTH;
+		let log_len = log_x + log_y + log_z;
+
+		// The decomposition needs rows of at least two packed elements that hold full batches of
+		// the X axis, as in the multithreaded NTT.
+		let min_log_width_scalars = (log_w + 1).max(log_x);
+		if log_len <= min_log_width_scalars {
+			return self.transform_single_threaded(
+				data,
+				shape,
+				coset,
+				coset_bits,
+				skip_rounds,
+				forward,
+			);
+		}
+		let log_len_packed = log_len - log_w;
+		let max_log_height = log_len - min_log_width_scalars;
+
+		// Choose the rows to fit in a tile, but use at least as many rows as there are threads.
+		let log_height = log_len_packed
+			.saturating_sub(log_tile_len)
+			.max(log_max_threads)
+			.min(max_log_height);
+		let log_width = log_len_packed - log_height;
+
+		// par_rounds is the number of NTT layers that are performed by the column-wise NTTs.
+		let par_rounds = log_height.saturating_sub(log_z);
+		let column_skip_rounds = skip_rounds.min(par_rounds);
+
+		// Gather as many columns into a block as fit in a tile, while keeping enough blocks for
+		// all threads.
+		let log_block = log_tile_len
+			.saturating_sub(log_height)
+			.min(log_width.saturating_sub(log_max_threads));
+		let column_shape = NTTShape {
+			log_x: log_block + log_w,
+			log_y: par_rounds,
+			log_z: log_height - par_rounds,
+		};
+
+		let log_row_z = log_z.saturating_sub(log_height);
+		let row_shape = NTTShape {
+			log_x,
+			log_y: log_width + log_w - log_x - log_row_z,
+			log_z: log_row_z,
+		};
+		let row_skip_rounds = skip_rounds.saturating_sub(par_rounds);
+
+		let transform_columns = |data: &mut [P]| -> Result<(), Error> {
+			if par_rounds == column_skip_rounds {
+				return Ok(());
+			}
+
+			let matrix =
+				StridedArray2DViewMut::without_stride(data, 1 << log_height, 1 << log_width)
+					.expect("dimensions are correct");
+			matrix.into_par_strides(1 << log_block).try_for_each_init(
+				|| vec![P::zero(); 1 << (log_height + log_block)],
+				|buffer, mut block| {
+					for (row, chunk) in buffer.chunks_exact_mut(1 << log_block).enumerate() {
+						for (col, dst) in chunk.iter_mut().enumerate() {
+							*dst = block[(row, col)];
+						}
+					}
+
+					self.transform(
+						buffer,
+						column_shape,
+						coset,
+						coset_bits,
+						column_skip_rounds,
+						0,
+						forward,
+					)?;
+
+					for (row, chunk) in buffer.chunks_exact(1 << log_block).enumerate() {
+						for (col, src) in chunk.iter().enumerate() {
+							block[(row, col)] = *src;
+						}
+					}
+					Ok(())
+				},
+			)
+		};
+
+		let transform_rows = |data: &mut [P]| -> Result<(), Error> {
+			data.par_chunks_mut(1 << (log_width + par_rounds))
+				.flat_map(|large_chunk| large_chunk.par_chunks_mut(1 << log_width).enumerate())
+				.try_for_each(|(inner_coset, chunk)| {
+					self.transform_single_threaded(
+						chunk,
+						row_shape,
+						coset << par_rounds | inner_coset,
+						coset_bits + par_rounds,
+						row_skip_rounds,
+						forward,
+					)
+				})
+		};
+
+		// The forward NTT runs the high layers, which are the column-wise NTTs, first. The
+		// inverse NTT runs the layers in the reverse order.
+		if forward {
+			transform_columns(data)?;
+			transform_rows(data)
+		} else {
+			transform_rows(data)?;
+			transform_columns(data)
+		}
+	}
+
+	fn transform_single_threaded<P: PackedField<Scalar = F>>(
+		&self,
+		data: &mut [P],
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+		forward: bool,
+	) -> Result<(), Error> {
+		if forward {
+			self.single_threaded
+				.forward_transform(data, shape, coset, coset_bits, skip_rounds)
+		} else {
+			self.single_threaded
+				.inverse_transform(data, shape, coset, coset_bits, skip_rounds)
+		}
+	}
+}
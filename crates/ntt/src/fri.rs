@@ -87,6 +87,81 @@ where
 	result
 }
 
+/// Binary logarithm of the number of codeword elements that [`fold_interleaved_inplace`] folds
+/// per task.
+const FOLD_INPLACE_LOG_BLOCK_LEN: usize = 12;
+
+/// FRI-fold the interleaved codeword in place, using the given challenges.
+///
+/// This computes the same values as [`fold_interleaved`], but writes them over the front of the
+/// codeword instead of allocating an output buffer, and returns the folded prefix. The rest of the
+/// codeword is left with unspecified contents.
+///
+/// The codeword is split into blocks that are folded in parallel, each into the front of its own
+/// memory, and the folded blocks are then moved to the front of the codeword, which touches only
+/// as many elements as the output has.
+///
+/// See [`fold_interleaved_allocated`] for the arguments.
+#[instrument(skip_all, level = "debug")]
+pub fn fold_interleaved_inplace<'a, F, FS, NTT>(
+	ntt: &NTT,
+	codeword: &'a mut [F],
+	challenges: &[F],
+	log_len: usize,
+	log_batch_size: usize,
+) -> &'a mut [F]
+where
+	F: BinaryField + ExtensionField<FS>,
+	FS: BinaryField,
+	NTT: AdditiveNTT<FS> + Sync,
+{
+	assert_eq!(codeword.len(), 1 << (log_len + log_batch_size));
+	assert!(challenges.len() >= log_batch_size);
+	assert!(challenges.len() <= log_len + log_batch_size);
+
+	let (interleave_challenges, fold_challenges) = challenges.split_at(log_batch_size);
+	let tensor = MultilinearQuery::<F>::expand(interleave_challenges);
+
+	let log_chunk_len = challenges.len();
+	let log_out_len = log_len + log_batch_size - log_chunk_len;
+	let log_block_chunks = FOLD_INPLACE_LOG_BLOCK_LEN
+		.saturating_sub(log_chunk_len)
+		.min(log_out_len);
+	let block_len = 1 << (log_chunk_len + log_block_chunks);
+	let block_out_len = 1 << log_block_chunks;
+
+	codeword
+		.par_chunks_mut(block_len)
+		.enumerate()
+		.for_each_init(
+			|| vec![F::default(); 1 << fold_challenges.len()],
+			|scratch_buffer, (block_index, block)| {
+				// The chunk at index `i` starts at `i << log_chunk_len`, which is not before `i`,
+				// so the folded values never overwrite a chunk that is yet to be read.
+				for i in 0..block_out_len {
+					block[i] = fold_interleaved_chunk(
+						ntt,
+						log_len,
+						log_batch_size,
+						(block_index << log_block_chunks) | i,
+						&block[i << log_chunk_len..(i + 1) << log_chunk_len],
+						tensor.expansion(),
+						fold_challenges,
+						scratch_buffer,
+					);
+				}
+			},
+		);
+
+	// Likewise, the destination of every block is not after its own and is before the next one.
+	for block_index in 1..1 << (log_out_len - log_block_chunks) {
+		let src = block_index * block_len;
+		codeword.copy_within(src..src + block_out_len, block_index * block_out_len);
+	}
+
+	&mut codeword[..1 << log_out_len]
+}
+
 /// Calculate fold of `values` at `index` with `r` random coefficient.
 ///
 /// See [DP24], Def. 3.6.
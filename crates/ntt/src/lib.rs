@@ -10,17 +10,22 @@
 mod additive_ntt;
 mod dynamic_dispatch;
 mod error;
+pub mod evaluation;
+mod four_step;
 pub mod fri;
 mod multithreaded;
 mod odd_interpolate;
 mod single_threaded;
 #[cfg(test)]
 mod tests;
+mod tower_ntt;
 pub mod twiddle;
 
 pub use additive_ntt::{AdditiveNTT, NTTShape};
 pub use dynamic_dispatch::{DynamicDispatchNTT, NTTOptions, ThreadingSettings};
 pub use error::Error;
+pub use four_step::{DEFAULT_LOG_TILE_BYTES, FourStepNTT};
 pub use multithreaded::MultithreadedNTT;
 pub use odd_interpolate::OddInterpolate;
 pub use single_threaded::SingleThreadedNTT;
+pub use tower_ntt::TowerNTT;
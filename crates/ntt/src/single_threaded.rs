@@ -4,14 +4,20 @@ use std::{cmp, marker::PhantomData};
 
 use binius_field::{BinaryField, PackedField, TowerField};
 use binius_math::BinarySubspace;
-use binius_utils::bail;
+use binius_utils::{
+	DeserializeBytes, SerializationError, SerializationMode, SerializeBytes, bail,
+	bytes::{Buf, BufMut},
+};
 
 use super::{
 	additive_ntt::{AdditiveNTT, NTTShape},
 	error::Error,
 	twiddle::TwiddleAccess,
 };
-use crate::twiddle::{OnTheFlyTwiddleAccess, PrecomputedTwiddleAccess, expand_subspace_evals};
+use crate::twiddle::{
+	OnTheFlyTwiddleAccess, PrecomputedTwiddleAccess, TabulatedTwiddleAccess, expand_subspace_evals,
+	tabulate_subspace_evals,
+};
 
 /// Implementation of `AdditiveNTT` that performs the computation single-threaded.
 #[derive(Debug)]
@@ -48,6 +54,21 @@ impl<F: BinaryField> SingleThreadedNTT<F> {
 	pub fn precompute_twiddles(&self) -> SingleThreadedNTT<F, PrecomputedTwiddleAccess<F>> {
 		SingleThreadedNTT::with_twiddle_access(expand_subspace_evals(&self.s_evals))
 	}
+
+	/// Returns an NTT that precomputes at most `1 << log_max_table_len` twiddle factors per round
+	/// and computes the rest on the fly.
+	///
+	/// The full tables of [`Self::precompute_twiddles`] take as much memory as the data of a
+	/// transform over the whole domain, which is prohibitive for very large domains.
+	pub fn tabulate_twiddles(
+		&self,
+		log_max_table_len: usize,
+	) -> SingleThreadedNTT<F, TabulatedTwiddleAccess<F>> {
+		SingleThreadedNTT::with_twiddle_access(tabulate_subspace_evals(
+			&self.s_evals,
+			log_max_table_len,
+		))
+	}
 }
 
 impl<F: TowerField> SingleThreadedNTT<F> {
@@ -73,6 +94,47 @@ impl<F: BinaryField, TA: TwiddleAccess<F>> SingleThreadedNTT<F, TA> {
 	}
 }
 
+/// Serializes the twiddle factors, so that an NTT with precomputed twiddles can be restored
+/// without repeating the precomputation.
+impl<F, TA> SerializeBytes for SingleThreadedNTT<F, TA>
+where
+	F: BinaryField,
+	TA: TwiddleAccess<F> + SerializeBytes,
+{
+	fn serialize(
+		&self,
+		write_buf: impl BufMut,
+		mode: SerializationMode,
+	) -> Result<(), SerializationError> {
+		self.s_evals.serialize(write_buf, mode)
+	}
+}
+
+impl<F, TA> DeserializeBytes for SingleThreadedNTT<F, TA>
+where
+	F: BinaryField,
+	TA: TwiddleAccess<F> + DeserializeBytes,
+{
+	fn deserialize(read_buf: impl Buf, mode: SerializationMode) -> Result<Self, SerializationError>
+	where
+		Self: Sized,
+	{
+		let s_evals = Vec::<TA>::deserialize(read_buf, mode)?;
+		// Round i of the NTT must have twiddles for the remaining `log_domain_size - 1 - i` rounds.
+		let log_domain_size = s_evals.len();
+		if s_evals
+			.iter()
+			.enumerate()
+			.any(|(i, twiddles)| twiddles.log_n() != log_domain_size - 1 - i)
+		{
+			return Err(SerializationError::InvalidConstruction {
+				name: "SingleThreadedNTT",
+			});
+		}
+		Ok(Self::with_twiddle_access(s_evals))
+	}
+}
+
 impl<F, TA> AdditiveNTT<F> for SingleThreadedNTT<F, TA>
 where
 	F: BinaryField,
@@ -627,6 +689,63 @@ mod tests {
 		}
 	}
 
+	fn check_serialization_roundtrip<TA>(ntt: &SingleThreadedNTT<BinaryField16b, TA>)
+	where
+		TA: TwiddleAccess<BinaryField16b> + SerializeBytes + DeserializeBytes,
+	{
+		let mut buf = Vec::new();
+		ntt.serialize(&mut buf, SerializationMode::Native).unwrap();
+		let deserialized = SingleThreadedNTT::<BinaryField16b, TA>::deserialize(
+			buf.as_slice(),
+			SerializationMode::Native,
+		)
+		.unwrap();
+		assert_eq!(deserialized.log_domain_size(), ntt.log_domain_size());
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let data = repeat_with(|| PackedBinaryField8x16b::random(&mut rng))
+			.take(1 << 5)
+			.collect::<Vec<_>>();
+		let shape = NTTShape {
+			log_y: 8,
+			..Default::default()
+		};
+		let mut expected = data.clone();
+		ntt.forward_transform(&mut expected, shape, 1, 2, 0)
+			.unwrap();
+		let mut result = data;
+		deserialized
+			.forward_transform(&mut result, shape, 1, 2, 0)
+			.unwrap();
+		assert_eq!(result, expected);
+	}
+
+	#[test]
+	fn test_serialization_roundtrip() {
+		let ntt = SingleThreadedNTT::<BinaryField16b>::new(10).unwrap();
+		check_serialization_roundtrip(&ntt);
+		check_serialization_roundtrip(&ntt.precompute_twiddles());
+		check_serialization_roundtrip(&ntt.tabulate_twiddles(4));
+	}
+
+	#[test]
+	fn test_deserialize_inconsistent_twiddles() {
+		let ntt = SingleThreadedNTT::<BinaryField16b>::new(10).unwrap();
+		// Drop the twiddles of the last round, which makes the domain one dimension too small for
+		// the twiddles of the first round.
+		let mut buf = Vec::new();
+		ntt.twiddles()[..9]
+			.serialize(&mut buf, SerializationMode::Native)
+			.unwrap();
+		assert_matches!(
+			SingleThreadedNTT::<BinaryField16b>::deserialize(
+				buf.as_slice(),
+				SerializationMode::Native
+			),
+			Err(SerializationError::InvalidConstruction { .. })
+		);
+	}
+
 	// TODO: Write test that compares polynomial evaluation via additive NTT with naive Lagrange
 	// polynomial interpolation. A randomized test should suffice for larger NTT sizes.
 }
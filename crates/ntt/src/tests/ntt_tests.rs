@@ -2,21 +2,25 @@
 
 use std::ops::Range;
 
+use assert_matches::assert_matches;
 use binius_field::{
-	AESTowerField8b, BinaryField, BinaryField8b, ByteSlicedAES8x16x16b, ByteSlicedAES16x32x8b,
-	PackedBinaryField8x32b, PackedBinaryField16x32b, PackedBinaryField32x16b, PackedExtension,
-	PackedField, RepackedExtension,
+	AESTowerField8b, BinaryField, BinaryField8b, BinaryField32b, ByteSlicedAES8x16x16b,
+	ByteSlicedAES16x32x8b, PackedBinaryField8x32b, PackedBinaryField16x32b,
+	PackedBinaryField32x16b, PackedExtension, PackedField, RepackedExtension,
 	arch::{
 		packed_8::PackedBinaryField1x8b,
 		packed_16::{PackedBinaryField1x16b, PackedBinaryField2x8b},
 		packed_32::PackedBinaryField2x16b,
 		packed_64::{PackedBinaryField2x32b, PackedBinaryField4x16b},
 	},
+	packed::get_packed_slice,
 	underlier::{NumCast, WithUnderlier},
 };
 use rand::{SeedableRng, rngs::StdRng};
 
-use crate::{AdditiveNTT, NTTShape, SingleThreadedNTT, dynamic_dispatch::DynamicDispatchNTT};
+use crate::{
+	AdditiveNTT, Error, NTTShape, SingleThreadedNTT, dynamic_dispatch::DynamicDispatchNTT,
+};
 
 /// Check that forward and inverse transformation of `ntt` on `data` is the same as forward and
 /// inverse transformation of `reference_ntt` on `data` and that the result of the roundtrip is the
@@ -97,6 +101,24 @@ fn check_roundtrip_all_ntts<P>(
 		.unwrap()
 		.precompute_twiddles()
 		.multithreaded_with_max_threads(1);
+	let tabulated_ntt = SingleThreadedNTT::<P::Scalar>::new(log_domain_size)
+		.unwrap()
+		.tabulate_twiddles(2);
+	let multithreaded_tabulated_ntt_4 = SingleThreadedNTT::<P::Scalar>::new(log_domain_size)
+		.unwrap()
+		.tabulate_twiddles(3)
+		.multithreaded_with_max_threads(2);
+	// Use small tiles, so that the decomposition is exercised recursively on the test sizes.
+	let four_step_ntt = SingleThreadedNTT::<P::Scalar>::new(log_domain_size)
+		.unwrap()
+		.four_step_with_params(0, 4);
+	let four_step_ntt_4 = SingleThreadedNTT::<P::Scalar>::new(log_domain_size)
+		.unwrap()
+		.four_step_with_params(2, 5);
+	let four_step_precompute_ntt_2 = SingleThreadedNTT::<P::Scalar>::new(log_domain_size)
+		.unwrap()
+		.precompute_twiddles()
+		.four_step_with_params(1, 6);
 	let dynamic_dispatch_ntt = DynamicDispatchNTT::SingleThreaded(
 		SingleThreadedNTT::<P::Scalar>::new(log_domain_size).unwrap(),
 	);
@@ -170,6 +192,51 @@ fn check_roundtrip_all_ntts<P>(
 						max_log_coset,
 						skip_rounds,
 					);
+					check_roundtrip_with_reference(
+						&simple_ntt,
+						&tabulated_ntt,
+						&data,
+						shape,
+						cosets.clone(),
+						max_log_coset,
+						skip_rounds,
+					);
+					check_roundtrip_with_reference(
+						&simple_ntt,
+						&multithreaded_tabulated_ntt_4,
+						&data,
+						shape,
+						cosets.clone(),
+						max_log_coset,
+						skip_rounds,
+					);
+					check_roundtrip_with_reference(
+						&simple_ntt,
+						&four_step_ntt,
+						&data,
+						shape,
+						cosets.clone(),
+						max_log_coset,
+						skip_rounds,
+					);
+					check_roundtrip_with_reference(
+						&simple_ntt,
+						&four_step_ntt_4,
+						&data,
+						shape,
+						cosets.clone(),
+						max_log_coset,
+						skip_rounds,
+					);
+					check_roundtrip_with_reference(
+						&simple_ntt,
+						&four_step_precompute_ntt_2,
+						&data,
+						shape,
+						cosets.clone(),
+						max_log_coset,
+						skip_rounds,
+					);
 					check_roundtrip_with_reference(
 						&simple_ntt,
 						&dynamic_dispatch_ntt,
@@ -474,6 +541,20 @@ fn test_transform_multithread_on_the_fly() {
 	)
 }
 
+#[test]
+fn test_transform_four_step() {
+	check_ntt_with_transform::<BinaryField8b, AESTowerField8b>(
+		&SingleThreadedNTT::<BinaryField8b>::new(8).unwrap(),
+		&SingleThreadedNTT::<AESTowerField8b>::new(8)
+			.unwrap()
+			.four_step_with_params(1, 3),
+		&SingleThreadedNTT::<AESTowerField8b, _>::with_domain_field::<BinaryField8b>(8)
+			.unwrap()
+			.four_step_with_params(1, 3),
+		64,
+	)
+}
+
 #[test]
 fn test_transform_multithread_precompute() {
 	check_ntt_with_transform::<BinaryField8b, AESTowerField8b>(
@@ -489,3 +570,132 @@ fn test_transform_multithread_precompute() {
 		64,
 	)
 }
+
+fn check_truncated_matches_prefix<P>(log_x: usize, log_y: usize, log_z: usize)
+where
+	P: PackedField<Scalar: BinaryField>,
+{
+	let coset_bits = 2;
+	let ntt = SingleThreadedNTT::<P::Scalar>::new(log_y + coset_bits).unwrap();
+	let mut rng = StdRng::seed_from_u64(0);
+	let data = (0..1 << (log_x + log_y + log_z).saturating_sub(P::LOG_WIDTH))
+		.map(|_| P::random(&mut rng))
+		.collect::<Vec<_>>();
+	let shape = NTTShape {
+		log_x,
+		log_y,
+		log_z,
+	};
+
+	for coset in [0, 3] {
+		for skip_rounds in [0, 1, log_y] {
+			let mut expected = data.clone();
+			ntt.forward_transform(&mut expected, shape, coset, coset_bits, skip_rounds)
+				.unwrap();
+
+			for log_output_y in 0..=log_y {
+				let log_output_len = log_x + log_output_y + log_z;
+				let mut output = vec![P::zero(); 1 << log_output_len.saturating_sub(P::LOG_WIDTH)];
+				ntt.forward_transform_truncated(
+					&data,
+					&mut output,
+					shape,
+					coset,
+					coset_bits,
+					skip_rounds,
+					log_output_y,
+				)
+				.unwrap();
+
+				let log_block = log_x + log_output_y;
+				for z in 0..1 << log_z {
+					for k in 0..1 << log_block {
+						assert_eq!(
+							get_packed_slice(&output, z << log_block | k),
+							get_packed_slice(&expected, z << (log_x + log_y) | k),
+						);
+					}
+				}
+			}
+		}
+	}
+}
+
+#[test]
+fn test_truncated_transform() {
+	check_truncated_matches_prefix::<BinaryField8b>(0, 6, 0);
+	check_truncated_matches_prefix::<BinaryField8b>(2, 5, 2);
+	check_truncated_matches_prefix::<PackedBinaryField16x32b>(0, 7, 0);
+	check_truncated_matches_prefix::<PackedBinaryField16x32b>(1, 6, 2);
+	check_truncated_matches_prefix::<PackedBinaryField2x16b>(0, 2, 0);
+}
+
+#[test]
+fn test_truncated_transform_output_too_large() {
+	let ntt = SingleThreadedNTT::<BinaryField8b>::new(8).unwrap();
+	let data = vec![<BinaryField8b as binius_field::Field>::ZERO; 16];
+	let mut output = vec![<BinaryField8b as binius_field::Field>::ZERO; 32];
+	let shape = NTTShape {
+		log_y: 4,
+		..Default::default()
+	};
+	assert_matches!(
+		ntt.forward_transform_truncated(&data, &mut output, shape, 0, 0, 0, 5),
+		Err(Error::OutputTooLarge { .. })
+	);
+	assert_matches!(
+		ntt.forward_transform_truncated(&data, &mut output, shape, 0, 0, 0, 3),
+		Err(Error::IncorrectBufferLength { .. })
+	);
+}
+
+#[test]
+fn test_strided_rows_transform() {
+	type P = PackedBinaryField8x32b;
+
+	let ntt = SingleThreadedNTT::<BinaryField32b>::new(10).unwrap();
+	let mut rng = StdRng::seed_from_u64(0);
+	let shape = NTTShape {
+		log_x: 1,
+		log_y: 5,
+		log_z: 2,
+	};
+	let row_len = 1 << (shape.log_x + shape.log_y - P::LOG_WIDTH);
+
+	for row_stride in [row_len, row_len + 3] {
+		let orig_data = (0..3 * row_stride + row_len)
+			.map(|_| P::random(&mut rng))
+			.collect::<Vec<_>>();
+
+		let mut expected = orig_data.clone();
+		for row in 0..4 {
+			ntt.forward_transform(
+				&mut expected[row * row_stride..][..row_len],
+				NTTShape { log_z: 0, ..shape },
+				1,
+				1,
+				0,
+			)
+			.unwrap();
+		}
+
+		let mut data = orig_data.clone();
+		ntt.forward_transform_rows(&mut data, row_stride, shape, 1, 1, 0)
+			.unwrap();
+		assert_eq!(data, expected);
+
+		ntt.inverse_transform_rows(&mut data, row_stride, shape, 1, 1, 0)
+			.unwrap();
+		assert_eq!(data, orig_data);
+	}
+
+	let mut data = vec![P::zero(); 2 * row_len];
+	assert_matches!(
+		ntt.forward_transform_rows(&mut data, row_len - 1, shape, 0, 0, 0),
+		Err(Error::RowStrideTooSmall { .. })
+	);
+	assert_matches!(
+		ntt.forward_transform_rows(&mut data, row_len, shape, 0, 0, 0),
+		Err(Error::StridedBufferTooSmall { .. })
+	);
+}
@@ -0,0 +1,190 @@
+// Copyright 2025 Irreducible Inc.
+
+use binius_field::{
+	BinaryField,
+	tower::{PackedTop, TowerFamily},
+};
+use binius_utils::bail;
+
+use super::{
+	additive_ntt::{AdditiveNTT, NTTShape},
+	dynamic_dispatch::{DynamicDispatchNTT, NTTOptions},
+	error::Error,
+};
+
+/// An additive NTT over the smallest field of a tower that is large enough for the domain.
+///
+/// The NTT domain is a subspace of the field that the twiddles are computed in, so an NTT with
+/// twiddles in a field of `n` bits supports domains of at most `2^n` elements. This type picks
+/// the 32-bit field of the tower when it suffices, and switches to the 64-bit or 128-bit field
+/// for larger domains. The transforms operate on data in the top field of the tower, which is an
+/// extension of all of the candidate twiddle fields.
+#[derive(Debug)]
+pub enum TowerNTT<T: TowerFamily> {
+	B32(DynamicDispatchNTT<T::B32>),
+	B64(DynamicDispatchNTT<T::B64>),
+	B128(DynamicDispatchNTT<T::B128>),
+}
+
+impl<T: TowerFamily> TowerNTT<T> {
+	/// Creates an NTT for a domain of size `2^log_domain_size` with the given settings.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::FieldTooSmall`] if the domain is larger than the top field of the tower
+	pub fn new(log_domain_size: usize, options: &NTTOptions) -> Result<Self, Error> {
+		let ntt = if log_domain_size <= T::B32::N_BITS {
+			Self::B32(DynamicDispatchNTT::new(log_domain_size, options)?)
+		} else if log_domain_size <= T::B64::N_BITS {
+			Self::B64(DynamicDispatchNTT::new(log_domain_size, options)?)
+		} else if log_domain_size <= T::B128::N_BITS {
+			Self::B128(DynamicDispatchNTT::new(log_domain_size, options)?)
+		} else {
+			bail!(Error::FieldTooSmall { log_domain_size });
+		};
+		Ok(ntt)
+	}
+
+	/// Base-2 logarithm of the size of the NTT domain.
+	pub fn log_domain_size(&self) -> usize {
+		match self {
+			Self::B32(ntt) => ntt.log_domain_size(),
+			Self::B64(ntt) => ntt.log_domain_size(),
+			Self::B128(ntt) => ntt.log_domain_size(),
+		}
+	}
+
+	/// The bit width of the field that the twiddles are computed in.
+	pub fn twiddle_field_bits(&self) -> usize {
+		match self {
+			Self::B32(_) => T::B32::N_BITS,
+			Self::B64(_) => T::B64::N_BITS,
+			Self::B128(_) => T::B128::N_BITS,
+		}
+	}
+
+	/// Forward transformation of data in the top field of the tower.
+	///
+	/// See [`AdditiveNTT::forward_transform_ext`].
+	pub fn forward_transform<P: PackedTop<T>>(
+		&self,
+		data: &mut [P],
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+	) -> Result<(), Error> {
+		match self {
+			Self::B32(ntt) => {
+				ntt.forward_transform_ext(data, shape, coset, coset_bits, skip_rounds)
+			}
+			Self::B64(ntt) => {
+				ntt.forward_transform_ext(data, shape, coset, coset_bits, skip_rounds)
+			}
+			Self::B128(ntt) => {
+				ntt.forward_transform_ext(data, shape, coset, coset_bits, skip_rounds)
+			}
+		}
+	}
+
+	/// Inverse transformation of data in the top field of the tower.
+	///
+	/// See [`AdditiveNTT::inverse_transform_ext`].
+	pub fn inverse_transform<P: PackedTop<T>>(
+		&self,
+		data: &mut [P],
+		shape: NTTShape,
+		coset: usize,
+		coset_bits: usize,
+		skip_rounds: usize,
+	) -> Result<(), Error> {
+		match self {
+			Self::B32(ntt) => {
+				ntt.inverse_transform_ext(data, shape, coset, coset_bits, skip_rounds)
+			}
+			Self::B64(ntt) => {
+				ntt.inverse_transform_ext(data, shape, coset, coset_bits, skip_rounds)
+			}
+			Self::B128(ntt) => {
+				ntt.inverse_transform_ext(data, shape, coset, coset_bits, skip_rounds)
+			}
+		}
+	}
+
+	/// Returns the element of the NTT domain at index `j` of the `i`'th subspace, embedded into
+	/// the top field of the tower.
+	pub fn get_subspace_eval(&self, i: usize, j: usize) -> T::B128 {
+		match self {
+			Self::B32(ntt) => ntt.get_subspace_eval(i, j).into(),
+			Self::B64(ntt) => ntt.get_subspace_eval(i, j).into(),
+			Self::B128(ntt) => ntt.get_subspace_eval(i, j),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use binius_field::{
+		BinaryField32b, BinaryField64b, PackedBinaryField2x128b, PackedField,
+		tower::CanonicalTowerFamily,
+	};
+	use rand::{SeedableRng, rngs::StdRng};
+
+	use super::*;
+	use crate::SingleThreadedNTT;
+
+	type Tower = CanonicalTowerFamily;
+
+	#[test]
+	fn test_twiddle_field_selection() {
+		let options = NTTOptions::default();
+		for (log_domain_size, bits) in [(10, 32), (32, 32), (33, 64), (64, 64), (65, 128)] {
+			let ntt = TowerNTT::<Tower>::new(log_domain_size, &options).unwrap();
+			assert_eq!(ntt.log_domain_size(), log_domain_size);
+			assert_eq!(ntt.twiddle_field_bits(), bits);
+		}
+
+		assert!(matches!(
+			TowerNTT::<Tower>::new(129, &options),
+			Err(Error::FieldTooSmall {
+				log_domain_size: 129
+			})
+		));
+	}
+
+	#[test]
+	fn test_transform_matches_twiddle_field_ntt() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let shape = NTTShape {
+			log_y: 6,
+			..Default::default()
+		};
+		let data = (0..1 << 5)
+			.map(|_| PackedBinaryField2x128b::random(&mut rng))
+			.collect::<Vec<_>>();
+
+		let ntt = TowerNTT::<Tower>::new(20, &NTTOptions::default()).unwrap();
+		let reference = SingleThreadedNTT::<BinaryField32b>::new(20).unwrap();
+		let mut result = data.clone();
+		let mut expected = data.clone();
+		ntt.forward_transform(&mut result, shape, 3, 2, 0).unwrap();
+		reference
+			.forward_transform_ext(&mut expected, shape, 3, 2, 0)
+			.unwrap();
+		assert_eq!(result, expected);
+
+		let ntt = TowerNTT::<Tower>::new(40, &NTTOptions::default()).unwrap();
+		let reference = SingleThreadedNTT::<BinaryField64b>::new(40).unwrap();
+		let mut result = data.clone();
+		let mut expected = data.clone();
+		ntt.forward_transform(&mut result, shape, 5, 34, 0).unwrap();
+		reference
+			.forward_transform_ext(&mut expected, shape, 5, 34, 0)
+			.unwrap();
+		assert_eq!(result, expected);
+		assert_eq!(ntt.get_subspace_eval(3, 7), reference.get_subspace_eval(3, 7).into());
+
+		ntt.inverse_transform(&mut result, shape, 5, 34, 0).unwrap();
+		assert_eq!(result, data);
+	}
+}
@@ -4,7 +4,10 @@ use std::{iter, marker::PhantomData, ops::Deref};
 
 use binius_field::{BinaryField, Field};
 use binius_math::BinarySubspace;
-use binius_utils::bail;
+use binius_utils::{
+	DeserializeBytes, SerializationError, SerializationMode, SerializeBytes, bail,
+	bytes::{Buf, BufMut},
+};
 
 use crate::Error;
 
@@ -160,6 +163,46 @@ where
 	}
 }
 
+impl<F, SEvals> SerializeBytes for OnTheFlyTwiddleAccess<F, SEvals>
+where
+	F: BinaryField,
+	SEvals: Deref<Target = [F]>,
+{
+	fn serialize(
+		&self,
+		mut write_buf: impl BufMut,
+		mode: SerializationMode,
+	) -> Result<(), SerializationError> {
+		self.log_n.serialize(&mut write_buf, mode)?;
+		self.offset.serialize(&mut write_buf, mode)?;
+		self.s_evals.serialize(write_buf, mode)
+	}
+}
+
+impl<F: BinaryField> DeserializeBytes for OnTheFlyTwiddleAccess<F> {
+	fn deserialize(
+		mut read_buf: impl Buf,
+		mode: SerializationMode,
+	) -> Result<Self, SerializationError>
+	where
+		Self: Sized,
+	{
+		let log_n = usize::deserialize(&mut read_buf, mode)?;
+		let offset = F::deserialize(&mut read_buf, mode)?;
+		let s_evals = Vec::<F>::deserialize(read_buf, mode)?;
+		if s_evals.len() != log_n {
+			return Err(SerializationError::InvalidConstruction {
+				name: "OnTheFlyTwiddleAccess",
+			});
+		}
+		Ok(Self {
+			log_n,
+			offset,
+			s_evals,
+		})
+	}
+}
+
 fn subset_sum<F: Field>(values: &[F], n_bits: usize, index: usize) -> F {
 	(0..n_bits)
 		.filter(|b| (index >> b) & 1 != 0)
@@ -233,6 +276,191 @@ where
 	}
 }
 
+impl<F, SEvals> SerializeBytes for PrecomputedTwiddleAccess<F, SEvals>
+where
+	F: BinaryField,
+	SEvals: Deref<Target = [F]>,
+{
+	fn serialize(
+		&self,
+		mut write_buf: impl BufMut,
+		mode: SerializationMode,
+	) -> Result<(), SerializationError> {
+		self.log_n.serialize(&mut write_buf, mode)?;
+		self.s_evals.serialize(write_buf, mode)
+	}
+}
+
+impl<F: BinaryField> DeserializeBytes for PrecomputedTwiddleAccess<F> {
+	fn deserialize(
+		mut read_buf: impl Buf,
+		mode: SerializationMode,
+	) -> Result<Self, SerializationError>
+	where
+		Self: Sized,
+	{
+		let log_n = usize::deserialize(&mut read_buf, mode)?;
+		let s_evals = Vec::<F>::deserialize(read_buf, mode)?;
+		if log_n >= usize::BITS as usize || s_evals.len() != 1 << log_n {
+			return Err(SerializationError::InvalidConstruction {
+				name: "PrecomputedTwiddleAccess",
+			});
+		}
+		Ok(Self {
+			log_n,
+			s_evals,
+			_marker: PhantomData,
+		})
+	}
+}
+
+/// Twiddle access method with a bounded table of precomputed constants.
+///
+/// This implementation precomputes the twiddle factors for the low `log_table_len` bits of the
+/// index, and adds the contributions of the remaining bits on the fly. It trades computation for
+/// memory between [`OnTheFlyTwiddleAccess`] and [`PrecomputedTwiddleAccess`]: each round stores
+/// at most $2^k$ field elements for a table limit of $k$, and a lookup takes $O(\ell - k)$ field
+/// additions for a domain of size $2^\ell$.
+#[derive(Debug)]
+pub struct TabulatedTwiddleAccess<F, SEvals = Vec<F>> {
+	log_n: usize,
+	/// `offset` is a constant that is added to all twiddle factors.
+	offset: F,
+	/// Base-2 logarithm of the table length.
+	log_table_len: usize,
+	/// The twiddle factors of the indices below `1 << log_table_len`, without the offset.
+	table: SEvals,
+	/// `s_evals` is $<\hat{W}\_i(\beta_{i+1}),\ldots ,\hat{W}\_i(\beta_{d-1})>$ for the implicit
+	/// round $i$.
+	s_evals: SEvals,
+}
+
+impl<F: BinaryField> TabulatedTwiddleAccess<F> {
+	/// Generate a vector of TabulatedTwiddleAccess objects, one for each NTT round, with tables of
+	/// at most `1 << log_max_table_len` elements.
+	pub fn generate(
+		subspace: &BinarySubspace<F>,
+		log_max_table_len: usize,
+	) -> Result<Vec<Self>, Error> {
+		let on_the_fly = OnTheFlyTwiddleAccess::generate(subspace)?;
+		Ok(tabulate_subspace_evals(&on_the_fly, log_max_table_len))
+	}
+}
+
+impl<F, SEvals> TabulatedTwiddleAccess<F, SEvals>
+where
+	F: BinaryField,
+	SEvals: Deref<Target = [F]>,
+{
+	/// Returns the twiddle factor at `index` without the offset, where `index` has `n_bits` bits.
+	#[inline]
+	fn linear_part(&self, n_bits: usize, index: usize) -> F {
+		if n_bits <= self.log_table_len {
+			self.table[index]
+		} else {
+			let low = index & ((1 << self.log_table_len) - 1);
+			self.table[low]
+				+ subset_sum(
+					&self.s_evals[self.log_table_len..],
+					n_bits - self.log_table_len,
+					index >> self.log_table_len,
+				)
+		}
+	}
+}
+
+impl<F, SEvals> TwiddleAccess<F> for TabulatedTwiddleAccess<F, SEvals>
+where
+	F: BinaryField,
+	SEvals: Deref<Target = [F]>,
+{
+	#[inline]
+	fn log_n(&self) -> usize {
+		self.log_n
+	}
+
+	fn affine_subspace(&self) -> (BinarySubspace<F>, F) {
+		let subspace = BinarySubspace::new_unchecked(
+			iter::once(F::ONE)
+				.chain(self.s_evals.iter().copied())
+				.collect(),
+		);
+		(subspace, self.offset)
+	}
+
+	#[inline]
+	fn get(&self, i: usize) -> F {
+		self.offset + self.linear_part(self.log_n, i)
+	}
+
+	#[inline]
+	fn get_pair(&self, index_bits: usize, i: usize) -> (F, F) {
+		let t0 = self.offset + self.linear_part(index_bits, i);
+		(t0, t0 + self.s_evals[index_bits])
+	}
+
+	#[inline]
+	fn coset(&self, coset_bits: usize, coset: usize) -> impl TwiddleAccess<F> {
+		let log_n = self.log_n - coset_bits;
+		let offset = subset_sum(&self.s_evals[log_n..], coset_bits, coset);
+		let log_table_len = self.log_table_len.min(log_n);
+		TabulatedTwiddleAccess {
+			log_n,
+			offset: self.offset + offset,
+			log_table_len,
+			// The table is in binary counting order, so its prefix is the table of the low bits.
+			table: &self.table[..1 << log_table_len],
+			s_evals: &self.s_evals[..log_n],
+		}
+	}
+}
+
+impl<F, SEvals> SerializeBytes for TabulatedTwiddleAccess<F, SEvals>
+where
+	F: BinaryField,
+	SEvals: Deref<Target = [F]>,
+{
+	fn serialize(
+		&self,
+		mut write_buf: impl BufMut,
+		mode: SerializationMode,
+	) -> Result<(), SerializationError> {
+		self.log_n.serialize(&mut write_buf, mode)?;
+		self.offset.serialize(&mut write_buf, mode)?;
+		self.log_table_len.serialize(&mut write_buf, mode)?;
+		self.table.serialize(&mut write_buf, mode)?;
+		self.s_evals.serialize(write_buf, mode)
+	}
+}
+
+impl<F: BinaryField> DeserializeBytes for TabulatedTwiddleAccess<F> {
+	fn deserialize(
+		mut read_buf: impl Buf,
+		mode: SerializationMode,
+	) -> Result<Self, SerializationError>
+	where
+		Self: Sized,
+	{
+		let log_n = usize::deserialize(&mut read_buf, mode)?;
+		let offset = F::deserialize(&mut read_buf, mode)?;
+		let log_table_len = usize::deserialize(&mut read_buf, mode)?;
+		let table = Vec::<F>::deserialize(&mut read_buf, mode)?;
+		let s_evals = Vec::<F>::deserialize(read_buf, mode)?;
+		if log_table_len > log_n || table.len() != 1 << log_table_len || s_evals.len() != log_n {
+			return Err(SerializationError::InvalidConstruction {
+				name: "TabulatedTwiddleAccess",
+			});
+		}
+		Ok(Self {
+			log_n,
+			offset,
+			log_table_len,
+			table,
+			s_evals,
+		})
+	}
+}
+
 /// Precompute the evaluations of the normalized subspace polynomials $\hat{W}_i$ on a basis.
 ///
 /// Let $K/\mathbb{F}_2$ be a finite extension of degree $d$, and let $\beta_0,\ldots ,\beta_{d-1}$
@@ -352,6 +580,48 @@ where
 		.collect()
 }
 
+/// Given `OnTheFlyTwiddleAccess` instances for each NTT round, returns a vector of
+/// `TabulatedTwiddleAccess` objects with tables of at most `1 << log_max_table_len` elements, one
+/// for each NTT round.
+///
+/// The table of round $i$ contains the evaluations of $\hat{W}\_i$ on the span of the first
+/// basis elements $\beta_{i+1},\ldots$ in "binary counting order", like the full tables of
+/// [`expand_subspace_evals`].
+pub fn tabulate_subspace_evals<F, SEvals>(
+	on_the_fly: &[OnTheFlyTwiddleAccess<F, SEvals>],
+	log_max_table_len: usize,
+) -> Vec<TabulatedTwiddleAccess<F>>
+where
+	F: BinaryField,
+	SEvals: Deref<Target = [F]>,
+{
+	let log_domain_size = on_the_fly.len();
+	on_the_fly
+		.iter()
+		.enumerate()
+		.map(|(i, on_the_fly_i)| {
+			let s_evals_i = &on_the_fly_i.s_evals;
+			let log_table_len = log_max_table_len.min(s_evals_i.len());
+
+			let mut table = Vec::with_capacity(1 << log_table_len);
+			table.push(F::ZERO);
+			for &eval in &s_evals_i[..log_table_len] {
+				for i in 0..table.len() {
+					table.push(table[i] + eval);
+				}
+			}
+
+			TabulatedTwiddleAccess {
+				log_n: log_domain_size - 1 - i,
+				offset: F::ZERO,
+				log_table_len,
+				table,
+				s_evals: s_evals_i.to_vec(),
+			}
+		})
+		.collect()
+}
+
 #[cfg(test)]
 mod tests {
 	use binius_field::{BinaryField, BinaryField8b, BinaryField16b, BinaryField32b};
@@ -359,7 +629,9 @@ mod tests {
 	use lazy_static::lazy_static;
 	use proptest::prelude::*;
 
-	use super::{OnTheFlyTwiddleAccess, PrecomputedTwiddleAccess, TwiddleAccess};
+	use super::{
+		OnTheFlyTwiddleAccess, PrecomputedTwiddleAccess, TabulatedTwiddleAccess, TwiddleAccess,
+	};
 
 	lazy_static! {
 		// Precomputed and OnTheFlytwiddle access objects for various binary field sizes.
@@ -378,6 +650,12 @@ mod tests {
 
 		static ref OTF_TWIDDLE_ACCESS_32B: Vec<OnTheFlyTwiddleAccess<BinaryField32b>> =
 			OnTheFlyTwiddleAccess::<BinaryField32b>::generate(&BinarySubspace::default()).unwrap();
+
+		static ref TABULATED_TWIDDLE_ACCESS_16B: Vec<TabulatedTwiddleAccess<BinaryField16b>> =
+			TabulatedTwiddleAccess::<BinaryField16b>::generate(&BinarySubspace::default(), 5).unwrap();
+
+		static ref TABULATED_TWIDDLE_ACCESS_32B: Vec<TabulatedTwiddleAccess<BinaryField32b>> =
+			TabulatedTwiddleAccess::<BinaryField32b>::generate(&BinarySubspace::default(), 8).unwrap();
 	}
 
 	// Tests that `PrecomputedTwiddleAccess` and `OnTheFlyTwiddleAccess`is linear.
@@ -416,6 +694,28 @@ mod tests {
 			test_linearity::<BinaryField32b, _>(twiddle_access, x, y);
 		}
 
+		#[test]
+		fn test_linearity_tabulated_32b((x, y, layer) in generate_layer_and_indices(32)) {
+			let twiddle_access = &TABULATED_TWIDDLE_ACCESS_32B[layer];
+			test_linearity::<BinaryField32b, _>(twiddle_access, x, y);
+		}
+
+		#[test]
+		fn test_tabulated_matches_otf_16b((x, layer) in generate_layer_and_index(16)) {
+			let tabulated = &TABULATED_TWIDDLE_ACCESS_16B[layer];
+			let otf = &OTF_TWIDDLE_ACCESS_16B[layer];
+			prop_assert_eq!(tabulated.get(x), otf.get(x));
+			prop_assert_eq!(tabulated.get_pair(0, 0), otf.get_pair(0, 0));
+
+			let coset_bits = otf.log_n() / 2;
+			let coset = x >> (otf.log_n() - coset_bits);
+			let index = x & ((1 << (otf.log_n() - coset_bits)) - 1);
+			prop_assert_eq!(
+				tabulated.coset(coset_bits, coset).get(index),
+				otf.coset(coset_bits, coset).get(index)
+			);
+		}
+
 	}
 
 	// Test compatibility between layers for a `TwiddleAccess` object. More precisely,
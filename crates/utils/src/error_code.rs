@@ -0,0 +1,73 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Error codes shared by the error types of the Binius crates.
+//!
+//! Every error type that implements [`HasErrorCode`] maps its variants to an [`ErrorCode`], which
+//! consists of the [`ErrorDomain`] of the error type and an index that is unique within the
+//! domain. Error types that wrap the errors of another domain forward to the code of the wrapped
+//! error, so that the code identifies the root cause rather than the layer it surfaced through.
+//!
+//! Codes are stable: variants may be added with fresh indices, but the index of an existing
+//! variant is never reused for a different error.
+
+use std::fmt::{self, Display};
+
+/// The part of the system that raised an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum ErrorDomain {
+	/// `binius_core::constraint_system`.
+	ConstraintSystem = 1,
+	/// `binius_core::piop`.
+	Piop = 2,
+	/// `binius_core::protocols::fri`.
+	Fri = 3,
+	/// `binius_core::ring_switch`.
+	RingSwitch = 4,
+	/// `binius_m3::builder`.
+	M3Builder = 16,
+}
+
+/// A stable identifier of an error, formatted as `E` followed by the two-digit domain and the
+/// three-digit index, e.g. `E03001`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ErrorCode {
+	domain: ErrorDomain,
+	index: u16,
+}
+
+impl ErrorCode {
+	pub const fn new(domain: ErrorDomain, index: u16) -> Self {
+		Self { domain, index }
+	}
+
+	pub const fn domain(self) -> ErrorDomain {
+		self.domain
+	}
+
+	pub const fn index(self) -> u16 {
+		self.index
+	}
+}
+
+impl Display for ErrorCode {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "E{:02}{:03}", self.domain as u8, self.index)
+	}
+}
+
+/// An error with a stable [`ErrorCode`].
+pub trait HasErrorCode: std::error::Error {
+	fn error_code(&self) -> ErrorCode;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_error_code_display() {
+		assert_eq!(ErrorCode::new(ErrorDomain::Fri, 1).to_string(), "E03001");
+		assert_eq!(ErrorCode::new(ErrorDomain::M3Builder, 120).to_string(), "E16120");
+	}
+}
@@ -5,6 +5,7 @@
 pub mod array_2d;
 pub mod checked_arithmetics;
 pub mod env;
+pub mod error_code;
 pub mod error_utils;
 pub mod felts;
 pub mod formatting;
@@ -17,6 +18,7 @@ pub mod serialization;
 pub mod sorting;
 pub mod sparse_index;
 pub mod strided_array;
+pub mod time;
 
 pub use bytes;
 pub use serialization::{DeserializeBytes, SerializationError, SerializationMode, SerializeBytes};
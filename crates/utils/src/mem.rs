@@ -2,6 +2,13 @@
 
 use std::mem::MaybeUninit;
 
+use binius_maybe_rayon::prelude::*;
+use bytemuck::Zeroable;
+
+/// The number of bytes that [`fill_zeroes`] zeroes per task, and the size above which it
+/// parallelizes.
+const FILL_ZEROES_CHUNK_BYTES: usize = 1 << 20;
+
 /// Turn a given slice of `T` into a slice of `MaybeUninit<T>`.
 ///
 /// # Panics
@@ -62,3 +69,74 @@ pub const unsafe fn slice_assume_init_mut<T>(slice: &mut [MaybeUninit<T>]) -> &m
 pub const unsafe fn slice_assume_init_ref<T>(slice: &[MaybeUninit<T>]) -> &[T] {
 	unsafe { std::mem::transmute(slice) }
 }
+
+/// Sets every element of a slice to zero.
+///
+/// Slices larger than a megabyte are zeroed in parallel chunks. On x86_64 those chunks are written
+/// with non-temporal stores, which bypass the cache: a large slice does not fit in it anyway, and
+/// zeroing it would otherwise evict the data other threads are working on. Small slices are
+/// zeroed with a plain `memset`, as the stored zeroes are likely to be read again soon.
+pub fn fill_zeroes<T: Zeroable + Send + Sync>(slice: &mut [T]) {
+	if std::mem::size_of_val(slice) < FILL_ZEROES_CHUNK_BYTES {
+		slice.fill_with(T::zeroed);
+		return;
+	}
+
+	let chunk_len = FILL_ZEROES_CHUNK_BYTES / size_of::<T>();
+	slice
+		.par_chunks_mut(chunk_len.max(1))
+		.for_each(|chunk| fill_zeroes_streaming(chunk));
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+fn fill_zeroes_streaming<T: Zeroable>(slice: &mut [T]) {
+	use std::arch::x86_64::{__m128i, _mm_setzero_si128, _mm_sfence, _mm_stream_si128};
+
+	// SAFETY: `T` is `Zeroable`, so writing zero bytes over its whole representation leaves valid
+	// values, and every bit pattern is a valid `u8` and `__m128i`.
+	let bytes = unsafe {
+		std::slice::from_raw_parts_mut(slice.as_mut_ptr().cast::<u8>(), size_of_val(slice))
+	};
+	let (head, body, tail) = unsafe { bytes.align_to_mut::<__m128i>() };
+	head.fill(0);
+	// SAFETY (for the intrinsics below): SSE2 is enabled in the build configuration, and every
+	// `dst` is a valid and 16-byte aligned reference.
+	unsafe {
+		let zero = _mm_setzero_si128();
+		for dst in body {
+			_mm_stream_si128(dst, zero);
+		}
+	}
+	tail.fill(0);
+	// Non-temporal stores are weakly ordered, the fence makes them visible before the chunk is
+	// handed back to other threads.
+	unsafe { _mm_sfence() };
+}
+
+#[cfg(not(all(target_arch = "x86_64", target_feature = "sse2")))]
+fn fill_zeroes_streaming<T: Zeroable>(slice: &mut [T]) {
+	slice.fill_with(T::zeroed);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_fill_zeroes() {
+		// Lengths below and above the parallel threshold, with an unaligned start so that the
+		// streaming path has a head and a tail.
+		for len in [0, 1, 1000, 3 * FILL_ZEROES_CHUNK_BYTES / 8 + 3] {
+			let mut values = vec![u64::MAX; len + 1];
+			fill_zeroes(&mut values[1..]);
+			assert_eq!(values[0], u64::MAX);
+			assert!(values[1..].iter().all(|&value| value == 0));
+
+			let mut bytes = vec![0xffu8; 8 * len + 2];
+			let end = bytes.len() - 1;
+			fill_zeroes(&mut bytes[1..end]);
+			assert_eq!((bytes[0], bytes[end]), (0xff, 0xff));
+			assert!(bytes[1..end].iter().all(|&byte| byte == 0));
+		}
+	}
+}
@@ -1,45 +1,145 @@
 // Copyright 2024-2025 Irreducible Inc.
 
-use cfg_if::cfg_if;
+//! Thread pools for the parallel parts of the prover.
+//!
+//! By default, parallel operations run on the global rayon thread pool. Libraries that embed the
+//! prover may not want it to depend on, or to modify, this process-global state. Instead, they
+//! create a dedicated [`ThreadPoolHandle`] and pass it to the APIs that accept one, for example
+//! the compute data of the prover, the fast CPU compute layer or the witness filling methods of
+//! M3.
 
-/// In case when number of threads is set to 1, use rayon thread pool with
-/// `use_current_thread` set to true. This is solves two problems:
-/// 1. The performance is almost the same as if rayon wasn't used at all
-/// 2. Makes profiling and debugging results less noisy
+use std::sync::Arc;
+
+use binius_maybe_rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+
+/// The thread pool that parallel operations run on.
 ///
-/// NOTE: rayon doesn't allow initializing global thread pool several times, so
-/// in case when it was initialized before this function returns an error.
-/// The typical usage of the function is to place it's call in the beginning of the `main`.
-/// The function returns reference to the result because `ThreadPoolBuildError`
-/// doesn't implement `Clone`.
-pub fn adjust_thread_pool() -> &'static Result<(), binius_maybe_rayon::ThreadPoolBuildError> {
-	cfg_if! {
-		if #[cfg(feature = "rayon")] {
-			use std::sync::OnceLock;
-
-			static ONCE_GUARD: OnceLock<Result<(), binius_maybe_rayon::ThreadPoolBuildError>> = OnceLock::new();
-
-			ONCE_GUARD.get_or_init(|| {
-				// We cannot use `binius_maybe_rayon::get_current_threads` because it would force the global thread pool
-				// to initialize, so we won't be able to override it.
-				match std::env::var("RAYON_NUM_THREADS") {
-					Ok(v) if v == "1" => binius_maybe_rayon::ThreadPoolBuilder::new()
-						.num_threads(1)
-						.use_current_thread()
-						.build_global(),
-					_ => Ok(()),
-				}
-			})
+/// The default handle uses the global rayon thread pool. The handle is cheap to clone, and all
+/// clones run operations on the same threads.
+#[derive(Debug, Clone, Default)]
+pub struct ThreadPoolHandle(Option<Arc<ThreadPool>>);
+
+impl ThreadPoolHandle {
+	/// The handle of the global rayon thread pool.
+	pub const GLOBAL: Self = Self(None);
+
+	/// Builds a dedicated thread pool with `n_threads` threads.
+	///
+	/// If `pin_threads` is set, the threads are pinned to CPU cores in order of core index, which
+	/// is only supported on Linux and is ignored on other platforms.
+	pub fn new(n_threads: usize, pin_threads: bool) -> Result<Self, ThreadPoolBuildError> {
+		let builder = ThreadPoolBuilder::new().num_threads(n_threads);
+		if pin_threads {
+			Self::from_builder(builder.start_handler(pin_current_thread))
+		} else {
+			Self::from_builder(builder)
+		}
+	}
+
+	/// Builds a dedicated thread pool with the settings of a rayon builder, such as the stack
+	/// size of the threads.
+	pub fn from_builder(builder: ThreadPoolBuilder) -> Result<Self, ThreadPoolBuildError> {
+		Ok(Self(Some(Arc::new(builder.build()?))))
+	}
+
+	/// Returns the thread pool selected by the `RAYON_NUM_THREADS` environment variable.
+	///
+	/// The global rayon thread pool already reads this variable, so for any number of threads
+	/// other than one this returns the handle of the global pool. For a single thread, a dedicated
+	/// pool is built with [`ThreadPoolBuilder::use_current_thread`], which keeps the performance
+	/// close to running without rayon and makes profiling and debugging results less noisy. The
+	/// current thread becomes the worker of that pool, so the function should be called on the
+	/// thread that later runs the operations, typically at the beginning of `main`.
+	pub fn from_env() -> Result<Self, ThreadPoolBuildError> {
+		match std::env::var("RAYON_NUM_THREADS") {
+			Ok(value) if value == "1" => {
+				Self::from_builder(ThreadPoolBuilder::new().num_threads(1).use_current_thread())
+			}
+			_ => Ok(Self::GLOBAL),
 		}
-		else {
-			static RESULT: Result<(), binius_maybe_rayon::ThreadPoolBuildError> = Ok(());
+	}
+
+	/// Returns whether the handle uses the global rayon thread pool.
+	pub fn is_global(&self) -> bool {
+		self.0.is_none()
+	}
 
-			&RESULT
+	/// Runs an operation on the thread pool.
+	///
+	/// Parallel iterators and joins within the operation run on the threads of the pool.
+	pub fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+		match &self.0 {
+			Some(pool) => pool.install(op),
+			None => op(),
 		}
 	}
+
+	/// Returns the number of threads of the pool.
+	pub fn current_num_threads(&self) -> usize {
+		self.install(binius_maybe_rayon::current_num_threads)
+	}
 }
 
+/// Pins the current thread to the CPU core with the given index, modulo the number of cores
+/// available to the process.
+#[cfg(target_os = "linux")]
+pub fn pin_current_thread(index: usize) {
+	use std::mem::MaybeUninit;
+
+	// Safety: `cpu_set_t` is a plain bitmask and the libc calls only read and write it.
+	unsafe {
+		let mut available = MaybeUninit::<libc::cpu_set_t>::zeroed().assume_init();
+		if libc::sched_getaffinity(0, size_of::<libc::cpu_set_t>(), &raw mut available) != 0 {
+			return;
+		}
+		let cpus = (0..libc::CPU_SETSIZE as usize)
+			.filter(|&cpu| libc::CPU_ISSET(cpu, &available))
+			.collect::<Vec<_>>();
+		if cpus.is_empty() {
+			return;
+		}
+
+		let mut pinned = MaybeUninit::<libc::cpu_set_t>::zeroed().assume_init();
+		libc::CPU_SET(cpus[index % cpus.len()], &mut pinned);
+		// Pinning is a performance hint, so a failure is not an error.
+		let _ = libc::sched_setaffinity(0, size_of::<libc::cpu_set_t>(), &raw const pinned);
+	}
+}
+
+/// Pins the current thread to a CPU core, which is not supported on this platform.
+#[cfg(not(target_os = "linux"))]
+pub fn pin_current_thread(_index: usize) {}
+
 /// Returns the base-2 logarithm of the number of threads that should be used for the task
 pub fn get_log_max_threads() -> usize {
 	(2 * binius_maybe_rayon::current_num_threads() - 1).ilog2() as _
 }
+
+#[cfg(test)]
+mod tests {
+	use binius_maybe_rayon::prelude::*;
+
+	use super::*;
+
+	#[test]
+	fn test_dedicated_pool() {
+		let pool = ThreadPoolHandle::new(2, true).unwrap();
+		assert!(!pool.is_global());
+
+		let sum = pool.install(|| (0..1000u64).into_par_iter().sum::<u64>());
+		assert_eq!(sum, 999 * 1000 / 2);
+		if cfg!(feature = "rayon") {
+			assert_eq!(pool.current_num_threads(), 2);
+		}
+	}
+
+	#[test]
+	fn test_pool_from_builder() {
+		let pool = ThreadPoolHandle::from_builder(
+			ThreadPoolBuilder::new().num_threads(1).stack_size(1 << 21),
+		)
+		.unwrap();
+		assert_eq!(pool.install(|| (0..10u64).into_par_iter().sum::<u64>()), 45);
+		assert!(ThreadPoolHandle::default().is_global());
+	}
+}
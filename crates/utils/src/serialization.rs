@@ -1,7 +1,9 @@
 // Copyright 2024-2025 Irreducible Inc.
 
+use std::collections::HashMap;
+
 use auto_impl::auto_impl;
-use bytes::{Buf, BufMut};
+use bytes::{Buf, BufMut, Bytes};
 use thiserror::Error;
 
 /// Serialize data according to Mode param
@@ -28,6 +30,10 @@ pub enum SerializationMode {
 	Native,
 	/// Will first convert any tower fields into the Fan-Paar field equivalent
 	CanonicalTower,
+	/// Like [`CanonicalTower`](Self::CanonicalTower), but with variable-length encodings of
+	/// lengths and with repeated items of sequences, such as digests or field constants, encoded
+	/// as references to their first occurrence
+	Compact,
 }
 
 #[derive(Error, Debug, Clone)]
@@ -50,6 +56,12 @@ pub enum SerializationError {
 	InvalidConstruction { name: &'static str },
 	#[error("usize {size} is too large to serialize (max is {max})", max = u32::MAX)]
 	UsizeTooLarge { size: usize },
+	#[error("Variable-length integer is malformed or too large")]
+	InvalidVarint,
+	#[error("Reference to the repeated item {index} precedes its occurrence")]
+	InvalidItemReference { index: usize },
+	#[error("The repeated item {index} has {n_bytes} bytes left over after its encoding")]
+	TrailingItemBytes { index: usize, n_bytes: usize },
 }
 
 // Copyright 2025 Irreducible Inc.
@@ -74,7 +86,10 @@ impl SerializeBytes for usize {
 		let value: u32 = (*self)
 			.try_into()
 			.map_err(|_| SerializationError::UsizeTooLarge { size: *self })?;
-		SerializeBytes::serialize(&value, &mut write_buf, mode)
+		match mode {
+			SerializationMode::Compact => serialize_varint(value, write_buf),
+			_ => SerializeBytes::serialize(&value, &mut write_buf, mode),
+		}
 	}
 }
 
@@ -86,7 +101,10 @@ impl DeserializeBytes for usize {
 	where
 		Self: Sized,
 	{
-		let value: u32 = DeserializeBytes::deserialize(&mut read_buf, mode)?;
+		let value: u32 = match mode {
+			SerializationMode::Compact => deserialize_varint(read_buf)?,
+			_ => DeserializeBytes::deserialize(&mut read_buf, mode)?,
+		};
 		Ok(value as Self)
 	}
 }
@@ -302,6 +320,9 @@ impl<T: SerializeBytes> SerializeBytes for [T] {
 		mode: SerializationMode,
 	) -> Result<(), SerializationError> {
 		SerializeBytes::serialize(&self.len(), &mut write_buf, mode)?;
+		if mode == SerializationMode::Compact && !self.is_empty() {
+			return serialize_compact_items(self, write_buf);
+		}
 		self.iter()
 			.try_for_each(|item| SerializeBytes::serialize(item, &mut write_buf, mode))
 	}
@@ -326,6 +347,9 @@ impl<T: DeserializeBytes> DeserializeBytes for Vec<T> {
 		Self: Sized,
 	{
 		let len: usize = DeserializeBytes::deserialize(&mut read_buf, mode)?;
+		if mode == SerializationMode::Compact && len != 0 {
+			return deserialize_compact_items(len, read_buf);
+		}
 		(0..len)
 			.map(|_| DeserializeBytes::deserialize(&mut read_buf, mode))
 			.collect()
@@ -413,6 +437,144 @@ impl<N: ArrayLength<u8>> DeserializeBytes for GenericArray<u8, N> {
 	}
 }
 
+/// Writes a LEB128 variable-length encoding of `value`.
+fn serialize_varint(mut value: u32, mut write_buf: impl BufMut) -> Result<(), SerializationError> {
+	loop {
+		assert_enough_space_for(&write_buf, 1)?;
+		if value < 0x80 {
+			write_buf.put_u8(value as u8);
+			return Ok(());
+		}
+		write_buf.put_u8(value as u8 | 0x80);
+		value >>= 7;
+	}
+}
+
+/// Reads a LEB128 variable-length encoding of a `u32`.
+///
+/// Encodings that are longer than necessary are rejected, so that every value has a unique
+/// encoding.
+fn deserialize_varint(mut read_buf: impl Buf) -> Result<u32, SerializationError> {
+	let mut value = 0u32;
+	for shift in (0..32).step_by(7) {
+		assert_enough_data_for(&read_buf, 1)?;
+		let byte = read_buf.get_u8();
+		let bits = (byte & 0x7f) as u32;
+		if bits << shift >> shift != bits || (shift != 0 && byte == 0) {
+			return Err(SerializationError::InvalidVarint);
+		}
+		value |= bits << shift;
+		if byte & 0x80 == 0 {
+			return Ok(value);
+		}
+	}
+	Err(SerializationError::InvalidVarint)
+}
+
+/// Tag of the items of a sequence in [`SerializationMode::Compact`] that are written one after
+/// another.
+const COMPACT_ITEMS_PLAIN: u8 = 0;
+/// Tag of the items of a sequence in [`SerializationMode::Compact`] that are interned.
+///
+/// Every item is preceded by a varint, which is zero for the first occurrence of an item and
+/// `i + 1` for a repetition of the `i`-th distinct item. The first occurrence is followed by the
+/// varint length of the item encoding and the encoding.
+const COMPACT_ITEMS_INTERNED: u8 = 1;
+
+/// Writes the items of a non-empty sequence in [`SerializationMode::Compact`].
+///
+/// The items are interned if that makes the encoding shorter.
+fn serialize_compact_items<T: SerializeBytes>(
+	items: &[T],
+	mut write_buf: impl BufMut,
+) -> Result<(), SerializationError> {
+	let mode = SerializationMode::Compact;
+
+	let mut plain = Vec::new();
+	let mut offsets = Vec::with_capacity(items.len() + 1);
+	offsets.push(0);
+	for item in items {
+		item.serialize(&mut plain, mode)?;
+		offsets.push(plain.len());
+	}
+
+	let mut interned = Vec::new();
+	let mut distinct = HashMap::<&[u8], usize>::new();
+	for item in offsets.windows(2).map(|range| &plain[range[0]..range[1]]) {
+		if interned.len() >= plain.len() {
+			break;
+		}
+		match distinct.get(item) {
+			Some(&index) => serialize_varint(index as u32 + 1, &mut interned)?,
+			None => {
+				distinct.insert(item, distinct.len());
+				serialize_varint(0, &mut interned)?;
+				item.len().serialize(&mut interned, mode)?;
+				interned.extend_from_slice(item);
+			}
+		}
+	}
+
+	let (tag, encoding) = if interned.len() < plain.len() {
+		(COMPACT_ITEMS_INTERNED, interned)
+	} else {
+		(COMPACT_ITEMS_PLAIN, plain)
+	};
+	assert_enough_space_for(&write_buf, 1 + encoding.len())?;
+	write_buf.put_u8(tag);
+	write_buf.put_slice(&encoding);
+	Ok(())
+}
+
+/// Reads the `len` items of a non-empty sequence in [`SerializationMode::Compact`].
+fn deserialize_compact_items<T: DeserializeBytes>(
+	len: usize,
+	mut read_buf: impl Buf,
+) -> Result<Vec<T>, SerializationError> {
+	let mode = SerializationMode::Compact;
+
+	match u8::deserialize(&mut read_buf, mode)? {
+		COMPACT_ITEMS_PLAIN => (0..len)
+			.map(|_| T::deserialize(&mut read_buf, mode))
+			.collect(),
+		COMPACT_ITEMS_INTERNED => {
+			let mut distinct = Vec::<Bytes>::new();
+			(0..len)
+				.map(|_| {
+					let index = match deserialize_varint(&mut read_buf)? as usize {
+						0 => {
+							let item_len = usize::deserialize(&mut read_buf, mode)?;
+							assert_enough_data_for(&read_buf, item_len)?;
+							distinct.push(read_buf.copy_to_bytes(item_len));
+							distinct.len() - 1
+						}
+						reference if reference <= distinct.len() => reference - 1,
+						reference => {
+							return Err(SerializationError::InvalidItemReference {
+								index: reference - 1,
+							});
+						}
+					};
+					// The item length is part of the encoding, so the item must use all of it.
+					let mut item = distinct[index].clone();
+					let value = T::deserialize(&mut item, mode)?;
+					if item.has_remaining() {
+						return Err(SerializationError::TrailingItemBytes {
+							index,
+							n_bytes: item.remaining(),
+						});
+					}
+					Ok(value)
+				})
+				.collect()
+		}
+		index => Err(SerializationError::UnknownEnumVariant {
+			name: "CompactItems",
+			index,
+		}),
+	}
+}
+
 #[inline]
 pub fn assert_enough_space_for(
 	write_buf: &impl BufMut,
@@ -454,4 +616,117 @@ mod tests {
 				.unwrap();
 		assert_eq!(data_deserialized, data);
 	}
+
+	fn roundtrip<T: SerializeBytes + DeserializeBytes + PartialEq + std::fmt::Debug>(
+		value: &T,
+		mode: SerializationMode,
+	) -> Vec<u8> {
+		let mut buf = Vec::new();
+		value.serialize(&mut buf, mode).unwrap();
+		let mut read_buf = buf.as_slice();
+		assert_eq!(&T::deserialize(&mut read_buf, mode).unwrap(), value);
+		assert!(read_buf.is_empty());
+		buf
+	}
+
+	#[test]
+	fn test_compact_usize_is_varint() {
+		for (value, len) in [
+			(0, 1),
+			(127, 1),
+			(128, 2),
+			(16383, 2),
+			(16384, 3),
+			(u32::MAX as usize, 5),
+		] {
+			assert_eq!(roundtrip(&value, SerializationMode::Compact).len(), len);
+		}
+		assert_eq!(roundtrip(&300usize, SerializationMode::Compact), [0xac, 0x02]);
+	}
+
+	#[test]
+	fn test_compact_rejects_invalid_varints() {
+		let mode = SerializationMode::Compact;
+		// Non-minimal encoding of zero
+		assert!(matches!(
+			usize::deserialize([0x80, 0x00].as_slice(), mode),
+			Err(SerializationError::InvalidVarint)
+		));
+		// Larger than u32::MAX
+		assert!(matches!(
+			usize::deserialize([0xff, 0xff, 0xff, 0xff, 0x1f].as_slice(), mode),
+			Err(SerializationError::InvalidVarint)
+		));
+		assert!(matches!(
+			usize::deserialize([0x80].as_slice(), mode),
+			Err(SerializationError::NotEnoughBytes)
+		));
+	}
+
+	#[test]
+	fn test_compact_interns_repeated_items() {
+		let mut rng = StdRng::seed_from_u64(0);
+
+		let distinct = (0..4)
+			.map(|_| {
+				let mut digest = GenericArray::<u8, U32>::default();
+				rng.fill_bytes(&mut digest);
+				digest
+			})
+			.collect::<Vec<_>>();
+		let digests = (0..100).map(|i| distinct[i % 7 % 4]).collect::<Vec<_>>();
+
+		let canonical = roundtrip(&digests, SerializationMode::CanonicalTower);
+		let compact = roundtrip(&digests, SerializationMode::Compact);
+		assert_eq!(compact[1], COMPACT_ITEMS_INTERNED);
+		assert!(compact.len() < canonical.len() / 10);
+	}
+
+	#[test]
+	fn test_compact_keeps_distinct_items_plain() {
+		let bytes = (0..=255u8).collect::<Vec<_>>();
+		let compact = roundtrip(&bytes, SerializationMode::Compact);
+		assert_eq!(compact.len(), 2 + 1 + 256);
+		assert_eq!(compact[2], COMPACT_ITEMS_PLAIN);
+
+		let nested = vec![vec![1u32, 1, 1, 1], vec![], vec![1u32, 1, 1, 1], vec![2u32]];
+		roundtrip(&nested, SerializationMode::Compact);
+		roundtrip(&vec!["a".to_string(), "a".to_string()], SerializationMode::Compact);
+		roundtrip(&Vec::<u64>::new(), SerializationMode::Compact);
+	}
+
+	#[test]
+	fn test_compact_rejects_forward_references() {
+		// One item which refers to the distinct item 0 before it occurs.
+		let buf = [0x01, COMPACT_ITEMS_INTERNED, 0x01];
+		assert!(matches!(
+			Vec::<u32>::deserialize(buf.as_slice(), SerializationMode::Compact),
+			Err(SerializationError::InvalidItemReference { index: 0 })
+		));
+	}
+
+	#[test]
+	fn test_compact_rejects_trailing_item_bytes() {
+		// Two items, the first of which is a 5-byte encoding of a 4-byte u32, and a reference to
+		// it.
+		let buf = [
+			0x02,
+			COMPACT_ITEMS_INTERNED,
+			0x00,
+			0x05,
+			1,
+			0,
+			0,
+			0,
+			0xff,
+			0x01,
+		];
+		assert!(matches!(
+			Vec::<u32>::deserialize(buf.as_slice(), SerializationMode::Compact),
+			Err(SerializationError::TrailingItemBytes {
+				index: 0,
+				n_bytes: 1
+			})
+		));
+	}
 }
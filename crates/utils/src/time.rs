@@ -0,0 +1,17 @@
+// Copyright 2025 Irreducible Inc.
+
+//! Wall-clock time on targets that may not have a clock.
+//!
+//! [`Instant::now`] panics on `wasm32-unknown-unknown`, so code that only measures time for
+//! reporting should obtain instants through [`now`] and skip the measurement when there is none.
+
+use std::time::Instant;
+
+/// Whether the target provides a monotonic clock.
+pub const HAS_CLOCK: bool = !cfg!(all(target_arch = "wasm32", target_os = "unknown"));
+
+/// Returns the current time, or `None` if the target has no clock.
+#[inline]
+pub fn now() -> Option<Instant> {
+	HAS_CLOCK.then(Instant::now)
+}
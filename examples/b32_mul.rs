@@ -10,7 +10,7 @@ use binius_field::{
 use binius_hal::make_portable_backend;
 use binius_hash::groestl::{Groestl256, Groestl256ByteCompression, Groestl256Parallel};
 use binius_m3::builder::{B32, B128, ConstraintSystem, WitnessIndex, test_utils::ClosureFiller};
-use binius_utils::{checked_arithmetics::log2_ceil_usize, rayon::adjust_thread_pool};
+use binius_utils::{checked_arithmetics::log2_ceil_usize, rayon::ThreadPoolHandle};
 use bytesize::ByteSize;
 use clap::{Parser, value_parser};
 use rand::{Rng as _, SeedableRng as _, rngs::StdRng};
@@ -29,9 +29,7 @@ struct Args {
 fn main() -> Result<()> {
 	const SECURITY_BITS: usize = 100;
 
-	adjust_thread_pool()
-		.as_ref()
-		.expect("failed to init thread pool");
+	let thread_pool = ThreadPoolHandle::from_env().expect("failed to init thread pool");
 
 	let args = Args::parse();
 
@@ -62,7 +60,8 @@ fn main() -> Result<()> {
 		1 << (log2_ceil_usize(args.n_ops as _) - PackedType::<OptimalUnderlier, B128>::LOG_WIDTH),
 	);
 	let allocator = allocator.into_bump_allocator();
-	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier, B128>>::new(&cs, &allocator);
+	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier, B128>>::new(&cs, &allocator)
+		.with_thread_pool(thread_pool.clone());
 
 	witness
 		.fill_table_parallel(
@@ -112,7 +111,7 @@ fn main() -> Result<()> {
 		_,
 		_,
 	>(
-		&mut compute_holder.to_data(),
+		&mut compute_holder.to_data().with_thread_pool(thread_pool),
 		&ccs,
 		args.log_inv_rate as usize,
 		SECURITY_BITS,
@@ -14,7 +14,7 @@ use binius_hash::groestl::{Groestl256, Groestl256ByteCompression, Groestl256Para
 use binius_m3::builder::{
 	B1, B128, Col, TableBuilder, TableWitnessSegment, WitnessIndex, test_utils::ClosureFiller,
 };
-use binius_utils::{checked_arithmetics::log2_ceil_usize, rayon::adjust_thread_pool};
+use binius_utils::{checked_arithmetics::log2_ceil_usize, rayon::ThreadPoolHandle};
 use bytesize::ByteSize;
 use clap::{Parser, value_parser};
 use rand::{Rng as _, SeedableRng as _, rngs::StdRng};
@@ -174,9 +174,7 @@ impl BitwiseGadget {
 fn main() -> Result<()> {
 	const SECURITY_BITS: usize = 100;
 
-	adjust_thread_pool()
-		.as_ref()
-		.expect("failed to init thread pool");
+	let thread_pool = ThreadPoolHandle::from_env().expect("failed to init thread pool");
 
 	let args = Args::parse();
 
@@ -213,7 +211,8 @@ fn main() -> Result<()> {
 			- PackedType::<OptimalUnderlier, B128>::LOG_WIDTH),
 	);
 	let allocator = allocator.into_bump_allocator();
-	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier, B128>>::new(&cs, &allocator);
+	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier, B128>>::new(&cs, &allocator)
+		.with_thread_pool(thread_pool.clone());
 
 	witness
 		.fill_table_parallel(
@@ -258,7 +257,7 @@ fn main() -> Result<()> {
 		_,
 		_,
 	>(
-		&mut compute_holder.to_data(),
+		&mut compute_holder.to_data().with_thread_pool(thread_pool),
 		&ccs,
 		args.log_inv_rate as usize,
 		SECURITY_BITS,
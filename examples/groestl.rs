@@ -4,7 +4,13 @@ use std::{array, iter::repeat_with};
 
 use anyhow::Result;
 use binius_compute::{ComputeHolder, cpu::alloc::CpuComputeAllocator};
-use binius_core::{constraint_system, fiat_shamir::HasherChallenger};
+use binius_core::{
+	constraint_system::{
+		self,
+		profile::{Profile, ProfiledProof},
+	},
+	fiat_shamir::HasherChallenger,
+};
 use binius_fast_compute::layer::FastCpuLayerHolder;
 use binius_field::{
 	Field, PackedExtension, PackedFieldIndexable, PackedSubfield, arch::OptimalUnderlier,
@@ -19,7 +25,7 @@ use binius_m3::{
 	},
 	gadgets::hash::groestl,
 };
-use binius_utils::{checked_arithmetics::log2_ceil_usize, rayon::adjust_thread_pool};
+use binius_utils::{checked_arithmetics::log2_ceil_usize, rayon::ThreadPoolHandle};
 use bytesize::ByteSize;
 use clap::{Parser, value_parser};
 use tracing_profile::init_tracing;
@@ -29,9 +35,9 @@ struct Args {
 	/// The number of permutations to verify.
 	#[arg(short, long, default_value_t = 512, value_parser = value_parser!(u32).range(1 << 9..))]
 	n_permutations: u32,
-	/// The negative binary logarithm of the Reed–Solomon code rate.
-	#[arg(long, default_value_t = 1, value_parser = value_parser!(u32).range(1..))]
-	log_inv_rate: u32,
+	/// The named parameter profile to prove and verify with.
+	#[arg(long, default_value_t = Profile::Bits100Fast)]
+	profile: Profile,
 }
 
 #[derive(Debug)]
@@ -73,11 +79,7 @@ where
 }
 
 fn main() -> Result<()> {
-	const SECURITY_BITS: usize = 100;
-
-	adjust_thread_pool()
-		.as_ref()
-		.expect("failed to init thread pool");
+	let thread_pool = ThreadPoolHandle::from_env().expect("failed to init thread pool");
 
 	let args = Args::parse();
 
@@ -103,7 +105,8 @@ fn main() -> Result<()> {
 		.collect::<Vec<_>>();
 
 	let trace_gen_scope = tracing::info_span!("Generating trace", n_permutations).entered();
-	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier, B128>>::new(&cs, &allocator);
+	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier, B128>>::new(&cs, &allocator)
+		.with_thread_pool(thread_pool.clone());
 	witness.fill_table_parallel(&table, &events)?;
 	drop(trace_gen_scope);
 
@@ -131,10 +134,10 @@ fn main() -> Result<()> {
 		_,
 		_,
 	>(
-		&mut compute_holder.to_data(),
+		&mut compute_holder.to_data().with_thread_pool(thread_pool),
 		&ccs,
-		args.log_inv_rate as usize,
-		SECURITY_BITS,
+		args.profile.log_inv_rate(),
+		args.profile.security_bits(),
 		&cs_digest,
 		&boundaries,
 		&table_sizes,
@@ -144,13 +147,12 @@ fn main() -> Result<()> {
 
 	println!("Proof size: {}", ByteSize::b(proof.get_proof_size() as u64));
 
-	binius_core::constraint_system::verify::<
-		OptimalUnderlier,
-		CanonicalTowerFamily,
-		Groestl256,
-		Groestl256ByteCompression,
-		HasherChallenger<Groestl256>,
-	>(&ccs, args.log_inv_rate as usize, SECURITY_BITS, &cs_digest, &boundaries, proof)?;
+	constraint_system::profile::verify_profiled(
+		&ccs,
+		args.profile,
+		&boundaries,
+		ProfiledProof::new(args.profile, proof),
+	)?;
 
 	Ok(())
 }
@@ -20,7 +20,7 @@ use binius_m3::{
 	},
 	gadgets::hash::keccak::{StateMatrix, stacked::Keccakf},
 };
-use binius_utils::{checked_arithmetics::log2_ceil_usize, rayon::adjust_thread_pool};
+use binius_utils::{checked_arithmetics::log2_ceil_usize, rayon::ThreadPoolHandle};
 use bytesize::ByteSize;
 use clap::{Parser, value_parser};
 use rand::RngCore;
@@ -78,9 +78,7 @@ where
 fn main() -> Result<()> {
 	const SECURITY_BITS: usize = 100;
 
-	adjust_thread_pool()
-		.as_ref()
-		.expect("failed to init thread pool");
+	let thread_pool = ThreadPoolHandle::from_env().expect("failed to init thread pool");
 
 	let args = Args::parse();
 
@@ -106,7 +104,8 @@ fn main() -> Result<()> {
 		.collect::<Vec<_>>();
 
 	let trace_gen_scope = tracing::info_span!("Generating trace", n_permutations).entered();
-	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier, B128>>::new(&cs, &allocator);
+	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier, B128>>::new(&cs, &allocator)
+		.with_thread_pool(thread_pool.clone());
 	witness.fill_table_parallel(&table, &events)?;
 	drop(trace_gen_scope);
 
@@ -134,7 +133,7 @@ fn main() -> Result<()> {
 		_,
 		_,
 	>(
-		&mut compute_holder.to_data(),
+		&mut compute_holder.to_data().with_thread_pool(thread_pool),
 		&ccs,
 		args.log_inv_rate as usize,
 		SECURITY_BITS,
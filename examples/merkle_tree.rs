@@ -16,7 +16,7 @@ use binius_m3::{
 		trace::{MerklePath, MerkleTree, MerkleTreeTrace},
 	},
 };
-use binius_utils::rayon::adjust_thread_pool;
+use binius_utils::rayon::ThreadPoolHandle;
 use bytesize::ByteSize;
 use clap::{Parser, value_parser};
 use rand::{Rng, SeedableRng, rngs::StdRng};
@@ -39,9 +39,7 @@ struct Args {
 fn main() -> Result<()> {
 	const SECURITY_BITS: usize = 100;
 
-	adjust_thread_pool()
-		.as_ref()
-		.expect("failed to init thread pool");
+	let thread_pool = ThreadPoolHandle::from_env().expect("failed to init thread pool");
 
 	let args = Args::parse();
 
@@ -83,7 +81,8 @@ fn main() -> Result<()> {
 	let mut allocator =
 		CpuComputeAllocator::new(1 << (22 - PackedType::<OptimalUnderlier, B128>::LOG_WIDTH));
 	let allocator = allocator.into_bump_allocator();
-	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier, B128>>::new(&cs, &allocator);
+	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier, B128>>::new(&cs, &allocator)
+		.with_thread_pool(thread_pool.clone());
 
 	// Fill the tables with the trace
 	merkle_tree_cs.fill_tables(&trace, &cs, &mut witness)?;
@@ -118,7 +117,7 @@ fn main() -> Result<()> {
 		_,
 		_,
 	>(
-		&mut compute_holder.to_data(),
+		&mut compute_holder.to_data().with_thread_pool(thread_pool),
 		&ccs,
 		args.log_inv_rate as usize,
 		SECURITY_BITS,
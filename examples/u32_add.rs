@@ -12,7 +12,7 @@ use binius_m3::{
 	builder::{B1, B128, WitnessIndex, test_utils::ClosureFiller},
 	gadgets::add::{U32Add, U32AddFlags},
 };
-use binius_utils::{checked_arithmetics::log2_ceil_usize, rayon::adjust_thread_pool};
+use binius_utils::{checked_arithmetics::log2_ceil_usize, rayon::ThreadPoolHandle};
 use bytesize::ByteSize;
 use clap::{Parser, value_parser};
 use rand::{Rng as _, SeedableRng as _, rngs::StdRng};
@@ -31,9 +31,7 @@ struct Args {
 fn main() -> Result<()> {
 	const SECURITY_BITS: usize = 100;
 
-	adjust_thread_pool()
-		.as_ref()
-		.expect("failed to init thread pool");
+	let thread_pool = ThreadPoolHandle::from_env().expect("failed to init thread pool");
 
 	let args = Args::parse();
 
@@ -51,6 +49,7 @@ fn main() -> Result<()> {
 
 	let flags = U32AddFlags {
 		carry_in_bit: None,
+		carry_in: None,
 		expose_final_carry: false,
 		commit_zout: true,
 	};
@@ -69,7 +68,8 @@ fn main() -> Result<()> {
 			- PackedType::<OptimalUnderlier, B128>::LOG_WIDTH),
 	);
 	let allocator = allocator.into_bump_allocator();
-	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier, B128>>::new(&cs, &allocator);
+	let mut witness = WitnessIndex::<PackedType<OptimalUnderlier, B128>>::new(&cs, &allocator)
+		.with_thread_pool(thread_pool.clone());
 	witness
 		.fill_table_parallel(
 			&ClosureFiller::new(table_id, |events, index| {
@@ -112,7 +112,7 @@ fn main() -> Result<()> {
 		_,
 		_,
 	>(
-		&mut compute_holder.to_data(),
+		&mut compute_holder.to_data().with_thread_pool(thread_pool),
 		&ccs,
 		args.log_inv_rate as usize,
 		SECURITY_BITS,